@@ -0,0 +1,45 @@
+use std::fmt;
+
+/// An error encountered while reading AMF data.
+#[derive(Debug)]
+pub enum AmfError {
+    /// The data ended in the middle of a value.
+    EndOfData,
+
+    /// A type marker that isn't valid in the encoding.
+    UnknownMarker(u8),
+
+    /// A reference to a string, object or traits that hasn't been read yet.
+    InvalidReference(usize),
+
+    /// An object that serializes itself, which can't be read without knowing
+    /// its class.
+    Externalizable(String),
+
+    /// The data isn't a local shared object.
+    InvalidHeader,
+
+    /// Values are nested more deeply than the reader allows.
+    TooDeep,
+}
+
+impl fmt::Display for AmfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AmfError::EndOfData => write!(f, "Unexpected end of AMF data"),
+            AmfError::UnknownMarker(marker) => write!(f, "Unknown AMF marker {:#x}", marker),
+            AmfError::InvalidReference(index) => write!(f, "Invalid AMF reference {}", index),
+            AmfError::Externalizable(class_name) => {
+                write!(
+                    f,
+                    "Cannot read externalizable object of class {}",
+                    class_name
+                )
+            }
+            AmfError::InvalidHeader => write!(f, "Invalid shared object header"),
+            AmfError::TooDeep => write!(f, "AMF values are nested too deeply"),
+        }
+    }
+}
+
+impl std::error::Error for AmfError {}
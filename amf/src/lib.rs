@@ -0,0 +1,269 @@
+//! # ruffle_amf
+//!
+//! Library for reading and writing the Action Message Format (AMF), the binary
+//! format that Flash Player uses to serialize ActionScript values, such as in
+//! `ByteArray.writeObject`, remoting and local shared objects.
+//!
+//! # Organization
+//!
+//! Both AMF0 and AMF3 are read to and written from `AmfValue`, which is
+//! independent of either ActionScript VM; converting to and from VM values is
+//! up to the caller. The `read` and `write` modules handle single values,
+//! while the `sol` module handles the `.sol` files that local shared objects
+//! are stored in.
+
+mod error;
+pub mod read;
+pub mod sol;
+mod types;
+pub mod write;
+
+pub use error::AmfError;
+pub use read::{read_value, Decoder, ExternalInput, ExternalizableReader};
+pub use sol::{read_lso, write_lso, Lso};
+pub use types::{AmfObject, AmfValue, ObjectEncoding, Traits};
+pub use write::write_value;
+
+/// Type markers of AMF0 values.
+mod amf0 {
+    pub const NUMBER: u8 = 0x00;
+    pub const BOOLEAN: u8 = 0x01;
+    pub const STRING: u8 = 0x02;
+    pub const OBJECT: u8 = 0x03;
+    pub const NULL: u8 = 0x05;
+    pub const UNDEFINED: u8 = 0x06;
+    pub const REFERENCE: u8 = 0x07;
+    pub const ECMA_ARRAY: u8 = 0x08;
+    pub const OBJECT_END: u8 = 0x09;
+    pub const STRICT_ARRAY: u8 = 0x0A;
+    pub const DATE: u8 = 0x0B;
+    pub const LONG_STRING: u8 = 0x0C;
+    pub const XML_DOCUMENT: u8 = 0x0F;
+    pub const TYPED_OBJECT: u8 = 0x10;
+    pub const AVMPLUS: u8 = 0x11;
+}
+
+/// Type markers of AMF3 values, and the range of its integers.
+mod amf3 {
+    pub const UNDEFINED: u8 = 0x00;
+    pub const NULL: u8 = 0x01;
+    pub const FALSE: u8 = 0x02;
+    pub const TRUE: u8 = 0x03;
+    pub const INTEGER: u8 = 0x04;
+    pub const DOUBLE: u8 = 0x05;
+    pub const STRING: u8 = 0x06;
+    pub const XML_DOCUMENT: u8 = 0x07;
+    pub const DATE: u8 = 0x08;
+    pub const ARRAY: u8 = 0x09;
+    pub const OBJECT: u8 = 0x0A;
+    pub const XML: u8 = 0x0B;
+    pub const BYTE_ARRAY: u8 = 0x0C;
+
+    pub const MIN_INTEGER: i32 = -(1 << 28);
+    pub const MAX_INTEGER: i32 = (1 << 28) - 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(value: AmfValue, encoding: ObjectEncoding) -> AmfValue {
+        let data = write_value(&value, encoding);
+        let (read, len) = read_value(&data, encoding).unwrap();
+        assert_eq!(len, data.len());
+        read
+    }
+
+    fn object(class_name: Option<&str>, sealed: Vec<(&str, AmfValue)>) -> AmfValue {
+        AmfValue::Object(AmfObject {
+            traits: Traits {
+                class_name: class_name.map(str::to_string),
+                sealed: sealed.iter().map(|(name, _)| name.to_string()).collect(),
+                dynamic: false,
+            },
+            sealed: sealed.into_iter().map(|(_, value)| value).collect(),
+            dynamic: vec![],
+        })
+    }
+
+    #[test]
+    fn amf3_integers() {
+        for &n in &[
+            0,
+            1,
+            127,
+            128,
+            16383,
+            16384,
+            0x1F_FFFF,
+            0x20_0000,
+            -1,
+            -(1 << 28),
+        ] {
+            let data = write_value(&AmfValue::Integer(n), ObjectEncoding::Amf3);
+            assert_eq!(
+                read_value(&data, ObjectEncoding::Amf3).unwrap().0,
+                AmfValue::Integer(n)
+            );
+        }
+
+        assert_eq!(
+            round_trip(AmfValue::Integer(1 << 28), ObjectEncoding::Amf3),
+            AmfValue::Number(f64::from(1 << 28))
+        );
+    }
+
+    #[test]
+    fn amf3_string_references() {
+        let value = AmfValue::Array {
+            dense: vec![
+                AmfValue::String("hello".to_string()),
+                AmfValue::String("hello".to_string()),
+            ],
+            associative: vec![],
+        };
+        let data = write_value(&value, ObjectEncoding::Amf3);
+        assert_eq!(
+            data,
+            vec![0x09, 0x05, 0x01, 0x06, 0x0B, b'h', b'e', b'l', b'l', b'o', 0x06, 0x00]
+        );
+        assert_eq!(round_trip(value.clone(), ObjectEncoding::Amf3), value);
+    }
+
+    #[test]
+    fn object_references() {
+        for &encoding in &[ObjectEncoding::Amf0, ObjectEncoding::Amf3] {
+            let value = AmfValue::Array {
+                dense: vec![
+                    AmfValue::Object(AmfObject::anonymous(vec![(
+                        "self".to_string(),
+                        AmfValue::Reference(1),
+                    )])),
+                    AmfValue::Reference(1),
+                    AmfValue::Reference(0),
+                ],
+                associative: vec![],
+            };
+            assert_eq!(round_trip(value.clone(), encoding), value);
+        }
+    }
+
+    #[test]
+    fn amf3_traits() {
+        let point = |x: i32, y: i32| {
+            object(
+                Some("flash.geom.Point"),
+                vec![("x", AmfValue::Integer(x)), ("y", AmfValue::Integer(y))],
+            )
+        };
+        let value = AmfValue::Array {
+            dense: vec![point(1, 2), point(3, 4)],
+            associative: vec![],
+        };
+
+        let data = write_value(&value, ObjectEncoding::Amf3);
+        let second = [0x0A, 0x01, 0x04, 0x03, 0x04, 0x04];
+        assert_eq!(&data[data.len() - second.len()..], &second);
+        assert_eq!(round_trip(value.clone(), ObjectEncoding::Amf3), value);
+    }
+
+    #[test]
+    fn amf3_externalizable() {
+        let value = AmfValue::Externalizable {
+            class_name: "flex.messaging.io.ArrayCollection".to_string(),
+            data: vec![AmfValue::Array {
+                dense: vec![AmfValue::Integer(1)],
+                associative: vec![],
+            }],
+        };
+        assert_eq!(round_trip(value.clone(), ObjectEncoding::Amf3), value);
+
+        let unknown = AmfValue::Externalizable {
+            class_name: "com.example.Custom".to_string(),
+            data: vec![AmfValue::Null],
+        };
+        let data = write_value(&unknown, ObjectEncoding::Amf3);
+        assert!(matches!(
+            read_value(&data, ObjectEncoding::Amf3),
+            Err(AmfError::Externalizable(_))
+        ));
+
+        let mut decoder = Decoder::default();
+        decoder
+            .register_externalizable("com.example.Custom", |input| Ok(vec![input.read_value()?]));
+        assert_eq!(
+            decoder.read_value(&data, ObjectEncoding::Amf3).unwrap().0,
+            unknown
+        );
+    }
+
+    #[test]
+    fn dates_and_xml() {
+        for &encoding in &[ObjectEncoding::Amf0, ObjectEncoding::Amf3] {
+            let value = AmfValue::Array {
+                dense: vec![
+                    AmfValue::Date(1_000_000.0),
+                    AmfValue::XmlDocument("<a/>".to_string()),
+                    AmfValue::Xml("<b/>".to_string()),
+                    AmfValue::ByteArray(vec![1, 2, 3]),
+                ],
+                associative: vec![],
+            };
+            assert_eq!(round_trip(value.clone(), encoding), value);
+        }
+    }
+
+    #[test]
+    fn amf0_arrays() {
+        let strict = AmfValue::Array {
+            dense: vec![AmfValue::Bool(true), AmfValue::Null, AmfValue::Undefined],
+            associative: vec![],
+        };
+        assert_eq!(round_trip(strict.clone(), ObjectEncoding::Amf0), strict);
+
+        let ecma = AmfValue::Array {
+            dense: vec![AmfValue::Number(1.0)],
+            associative: vec![("name".to_string(), AmfValue::String("x".to_string()))],
+        };
+        assert_eq!(round_trip(ecma.clone(), ObjectEncoding::Amf0), ecma);
+    }
+
+    #[test]
+    fn truncated_data() {
+        let data = write_value(&AmfValue::Number(1.0), ObjectEncoding::Amf0);
+        assert!(matches!(
+            read_value(&data[..4], ObjectEncoding::Amf0),
+            Err(AmfError::EndOfData)
+        ));
+    }
+
+    #[test]
+    fn nesting_depth() {
+        // Strict arrays that each hold the next one.
+        let nested = |depth: usize| {
+            let mut data = Vec::new();
+            for _ in 0..depth {
+                data.extend_from_slice(&[amf0::STRICT_ARRAY, 0, 0, 0, 1]);
+            }
+            data.push(amf0::NULL);
+            data
+        };
+
+        assert!(read_value(&nested(100), ObjectEncoding::Amf0).is_ok());
+        assert!(matches!(
+            read_value(&nested(100_000), ObjectEncoding::Amf0),
+            Err(AmfError::TooDeep)
+        ));
+
+        // Dense AMF3 arrays with a single element, also nested inside AMF0.
+        let mut data = vec![amf0::AVMPLUS];
+        for _ in 0..100_000 {
+            data.extend_from_slice(&[amf3::ARRAY, 0x03, 0x01]);
+        }
+        data.push(amf3::NULL);
+        assert!(matches!(
+            read_value(&data, ObjectEncoding::Amf0),
+            Err(AmfError::TooDeep)
+        ));
+    }
+}
@@ -0,0 +1,527 @@
+//! Reading AMF values.
+
+use crate::{amf0, amf3, AmfError, AmfObject, AmfValue, ObjectEncoding, Traits};
+use std::collections::HashMap;
+use std::convert::TryInto;
+
+/// How deeply values may be nested inside objects and arrays.
+///
+/// Values are read recursively, so this stops malicious data from overflowing
+/// the stack.
+const MAX_DEPTH: usize = 128;
+
+/// Deserialize a value with the given encoding, using the default `Decoder`.
+///
+/// Returns the value and the number of bytes that were read.
+pub fn read_value(data: &[u8], encoding: ObjectEncoding) -> Result<(AmfValue, usize), AmfError> {
+    Decoder::default().read_value(data, encoding)
+}
+
+/// Reads the data of an externalizable class, as written by its
+/// `writeExternal`.
+pub type ExternalizableReader = fn(&mut ExternalInput<'_, '_>) -> Result<Vec<AmfValue>, AmfError>;
+
+/// Reads AMF data, knowing how to read the externalizable classes that have
+/// been registered with it.
+///
+/// The default decoder knows the Flex collection classes, which each write a
+/// single value.
+#[derive(Clone)]
+pub struct Decoder {
+    externalizable: HashMap<String, ExternalizableReader>,
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        let mut decoder = Self {
+            externalizable: HashMap::new(),
+        };
+
+        for &class_name in &[
+            "flex.messaging.io.ArrayCollection",
+            "flex.messaging.io.ArrayList",
+            "flex.messaging.io.ObjectProxy",
+        ] {
+            decoder.register_externalizable(class_name, |input| Ok(vec![input.read_value()?]));
+        }
+
+        decoder
+    }
+}
+
+impl Decoder {
+    /// Register how to read the data of an externalizable class.
+    pub fn register_externalizable(
+        &mut self,
+        class_name: impl Into<String>,
+        reader: ExternalizableReader,
+    ) {
+        self.externalizable.insert(class_name.into(), reader);
+    }
+
+    /// Deserialize a value with the given encoding.
+    ///
+    /// Returns the value and the number of bytes that were read.
+    pub fn read_value(
+        &self,
+        data: &[u8],
+        encoding: ObjectEncoding,
+    ) -> Result<(AmfValue, usize), AmfError> {
+        let mut reader = Reader::new(data, self);
+        let value = reader.read_value(encoding)?;
+
+        Ok((value, reader.position()))
+    }
+}
+
+/// The input of an externalizable object's `readExternal`.
+pub struct ExternalInput<'a, 'b> {
+    reader: &'b mut Reader<'a>,
+}
+
+impl<'a, 'b> ExternalInput<'a, 'b> {
+    /// Read an AMF3 value, as written by `writeObject`.
+    pub fn read_value(&mut self) -> Result<AmfValue, AmfError> {
+        self.reader.read_amf3()
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, AmfError> {
+        self.reader.read_u8()
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, AmfError> {
+        self.reader.read_u16()
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, AmfError> {
+        self.reader.read_u32()
+    }
+
+    pub fn read_f64(&mut self) -> Result<f64, AmfError> {
+        self.reader.read_f64()
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], AmfError> {
+        self.reader.read_bytes(len)
+    }
+}
+
+/// An entry in the object table of an encoding that was read.
+#[derive(Clone, Debug)]
+enum ReadReference {
+    /// A referenceable value, with its index in the order values were read.
+    Value(usize),
+
+    /// A value that takes up a slot in the AMF3 object table, but isn't a
+    /// referenceable `AmfValue`, such as a date.
+    Inline(AmfValue),
+}
+
+/// Reads a sequence of values that share reference tables.
+pub(crate) struct Reader<'a> {
+    input: &'a [u8],
+    length: usize,
+    decoder: &'a Decoder,
+
+    /// Number of referenceable values read so far.
+    value_count: usize,
+
+    /// Number of values that are currently being read.
+    depth: usize,
+
+    /// The object table of the current encoding.
+    objects: Vec<ReadReference>,
+
+    /// AMF3 strings read so far.
+    strings: Vec<String>,
+
+    /// AMF3 traits read so far, and whether they are externalizable.
+    traits: Vec<(Traits, bool)>,
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(input: &'a [u8], decoder: &'a Decoder) -> Self {
+        Self {
+            input,
+            length: input.len(),
+            decoder,
+            value_count: 0,
+            depth: 0,
+            objects: Vec::new(),
+            strings: Vec::new(),
+            traits: Vec::new(),
+        }
+    }
+
+    pub(crate) fn position(&self) -> usize {
+        self.length - self.input.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.input.is_empty()
+    }
+
+    pub(crate) fn read_value(&mut self, encoding: ObjectEncoding) -> Result<AmfValue, AmfError> {
+        match encoding {
+            ObjectEncoding::Amf0 => self.read_amf0(),
+            ObjectEncoding::Amf3 => self.read_amf3(),
+        }
+    }
+
+    /// Reserve the index of a referenceable value that is about to be read.
+    fn add_value(&mut self) -> usize {
+        let index = self.value_count;
+        self.value_count += 1;
+        self.objects.push(ReadReference::Value(index));
+        index
+    }
+
+    fn reference(&self, index: usize) -> Result<AmfValue, AmfError> {
+        match self.objects.get(index) {
+            Some(ReadReference::Value(index)) => Ok(AmfValue::Reference(*index)),
+            Some(ReadReference::Inline(value)) => Ok(value.clone()),
+            None => Err(AmfError::InvalidReference(index)),
+        }
+    }
+
+    pub(crate) fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], AmfError> {
+        if self.input.len() < len {
+            return Err(AmfError::EndOfData);
+        }
+
+        let (bytes, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(bytes)
+    }
+
+    pub(crate) fn read_u8(&mut self) -> Result<u8, AmfError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    pub(crate) fn read_u16(&mut self) -> Result<u16, AmfError> {
+        Ok(u16::from_be_bytes(self.read_bytes(2)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn read_u32(&mut self) -> Result<u32, AmfError> {
+        Ok(u32::from_be_bytes(self.read_bytes(4)?.try_into().unwrap()))
+    }
+
+    fn read_f64(&mut self) -> Result<f64, AmfError> {
+        Ok(f64::from_be_bytes(self.read_bytes(8)?.try_into().unwrap()))
+    }
+
+    fn read_utf8(&mut self, len: usize) -> Result<String, AmfError> {
+        Ok(String::from_utf8_lossy(self.read_bytes(len)?).into_owned())
+    }
+
+    /// Read a value that may contain other values, failing if it is nested
+    /// too deeply.
+    fn read_nested(
+        &mut self,
+        read: fn(&mut Self) -> Result<AmfValue, AmfError>,
+    ) -> Result<AmfValue, AmfError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(AmfError::TooDeep);
+        }
+
+        self.depth += 1;
+        let value = read(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn read_amf0(&mut self) -> Result<AmfValue, AmfError> {
+        self.read_nested(Self::read_amf0_value)
+    }
+
+    fn read_amf0_value(&mut self) -> Result<AmfValue, AmfError> {
+        let marker = self.read_u8()?;
+        Ok(match marker {
+            amf0::NUMBER => AmfValue::Number(self.read_f64()?),
+            amf0::BOOLEAN => AmfValue::Bool(self.read_u8()? != 0),
+            amf0::STRING => AmfValue::String(self.read_amf0_utf8()?),
+            amf0::LONG_STRING => {
+                let len = self.read_u32()? as usize;
+                AmfValue::String(self.read_utf8(len)?)
+            }
+            amf0::XML_DOCUMENT => {
+                let len = self.read_u32()? as usize;
+                AmfValue::XmlDocument(self.read_utf8(len)?)
+            }
+            amf0::OBJECT => {
+                self.add_value();
+                AmfValue::Object(AmfObject::anonymous(self.read_amf0_properties()?))
+            }
+            amf0::TYPED_OBJECT => {
+                self.add_value();
+                let class_name = self.read_amf0_utf8()?;
+                AmfValue::Object(AmfObject {
+                    traits: Traits {
+                        class_name: Some(class_name),
+                        sealed: Vec::new(),
+                        dynamic: true,
+                    },
+                    sealed: Vec::new(),
+                    dynamic: self.read_amf0_properties()?,
+                })
+            }
+            amf0::NULL => AmfValue::Null,
+            amf0::UNDEFINED => AmfValue::Undefined,
+            amf0::REFERENCE => {
+                let index = self.read_u16()?;
+                self.reference(usize::from(index))?
+            }
+            amf0::ECMA_ARRAY => {
+                self.add_value();
+                let _count = self.read_u32()?;
+                let mut dense = Vec::new();
+                let mut associative = Vec::new();
+                for (name, value) in self.read_amf0_properties()? {
+                    // Indexed properties are in order, but may be mixed in
+                    // with named ones.
+                    if name == dense.len().to_string() {
+                        dense.push(value);
+                    } else {
+                        associative.push((name, value));
+                    }
+                }
+
+                AmfValue::Array { dense, associative }
+            }
+            amf0::STRICT_ARRAY => {
+                self.add_value();
+                let count = self.read_u32()?;
+                let mut dense = Vec::new();
+                for _ in 0..count {
+                    dense.push(self.read_amf0()?);
+                }
+
+                AmfValue::Array {
+                    dense,
+                    associative: Vec::new(),
+                }
+            }
+            amf0::DATE => {
+                let time = self.read_f64()?;
+                let _timezone = self.read_u16()?;
+                AmfValue::Date(time)
+            }
+            amf0::AVMPLUS => {
+                // The AMF3 value has its own reference tables.
+                let mut reader = Reader::new(self.input, self.decoder);
+                reader.value_count = self.value_count;
+                reader.depth = self.depth;
+                let value = reader.read_amf3()?;
+                self.value_count = reader.value_count;
+                self.input = reader.input;
+                value
+            }
+            _ => return Err(AmfError::UnknownMarker(marker)),
+        })
+    }
+
+    pub(crate) fn read_amf0_utf8(&mut self) -> Result<String, AmfError> {
+        let len = self.read_u16()?;
+        self.read_utf8(usize::from(len))
+    }
+
+    fn read_amf0_properties(&mut self) -> Result<Vec<(String, AmfValue)>, AmfError> {
+        let mut properties = Vec::new();
+        loop {
+            let name = self.read_amf0_utf8()?;
+            if name.is_empty() && self.input.first() == Some(&amf0::OBJECT_END) {
+                self.read_u8()?;
+                return Ok(properties);
+            }
+
+            let value = self.read_amf0()?;
+            properties.push((name, value));
+        }
+    }
+
+    fn read_amf3(&mut self) -> Result<AmfValue, AmfError> {
+        self.read_nested(Self::read_amf3_value)
+    }
+
+    fn read_amf3_value(&mut self) -> Result<AmfValue, AmfError> {
+        let marker = self.read_u8()?;
+        Ok(match marker {
+            amf3::UNDEFINED => AmfValue::Undefined,
+            amf3::NULL => AmfValue::Null,
+            amf3::FALSE => AmfValue::Bool(false),
+            amf3::TRUE => AmfValue::Bool(true),
+            amf3::INTEGER => {
+                // Sign-extend the 29-bit integer.
+                let n = self.read_u29()?;
+                AmfValue::Integer(((n << 3) as i32) >> 3)
+            }
+            amf3::DOUBLE => AmfValue::Number(self.read_f64()?),
+            amf3::STRING => AmfValue::String(self.read_amf3_string()?),
+            amf3::DATE => {
+                let header = self.read_u29()?;
+                if header & 1 == 0 {
+                    return self.reference((header >> 1) as usize);
+                }
+
+                let value = AmfValue::Date(self.read_f64()?);
+                self.objects.push(ReadReference::Inline(value.clone()));
+                value
+            }
+            amf3::XML_DOCUMENT | amf3::XML => {
+                let header = self.read_u29()?;
+                if header & 1 == 0 {
+                    return self.reference((header >> 1) as usize);
+                }
+
+                let source = self.read_utf8((header >> 1) as usize)?;
+                let value = if marker == amf3::XML {
+                    AmfValue::Xml(source)
+                } else {
+                    AmfValue::XmlDocument(source)
+                };
+                self.objects.push(ReadReference::Inline(value.clone()));
+                value
+            }
+            amf3::ARRAY => {
+                let header = self.read_u29()?;
+                if header & 1 == 0 {
+                    return self.reference((header >> 1) as usize);
+                }
+
+                self.add_value();
+                let mut associative = Vec::new();
+                loop {
+                    let name = self.read_amf3_string()?;
+                    if name.is_empty() {
+                        break;
+                    }
+
+                    associative.push((name, self.read_amf3()?));
+                }
+
+                let mut dense = Vec::new();
+                for _ in 0..header >> 1 {
+                    dense.push(self.read_amf3()?);
+                }
+
+                AmfValue::Array { dense, associative }
+            }
+            amf3::OBJECT => {
+                let header = self.read_u29()?;
+                if header & 1 == 0 {
+                    return self.reference((header >> 1) as usize);
+                }
+
+                let (traits, externalizable) = self.read_amf3_traits(header)?;
+                self.add_value();
+
+                if externalizable {
+                    let class_name = traits.class_name.unwrap_or_default();
+                    let reader = *self
+                        .decoder
+                        .externalizable
+                        .get(&class_name)
+                        .ok_or_else(|| AmfError::Externalizable(class_name.clone()))?;
+                    let data = reader(&mut ExternalInput { reader: self })?;
+
+                    return Ok(AmfValue::Externalizable { class_name, data });
+                }
+
+                let mut sealed = Vec::new();
+                for _ in &traits.sealed {
+                    sealed.push(self.read_amf3()?);
+                }
+
+                let mut dynamic = Vec::new();
+                if traits.dynamic {
+                    loop {
+                        let name = self.read_amf3_string()?;
+                        if name.is_empty() {
+                            break;
+                        }
+
+                        dynamic.push((name, self.read_amf3()?));
+                    }
+                }
+
+                AmfValue::Object(AmfObject {
+                    traits,
+                    sealed,
+                    dynamic,
+                })
+            }
+            amf3::BYTE_ARRAY => {
+                let header = self.read_u29()?;
+                if header & 1 == 0 {
+                    return self.reference((header >> 1) as usize);
+                }
+
+                self.add_value();
+                AmfValue::ByteArray(self.read_bytes((header >> 1) as usize)?.to_vec())
+            }
+            _ => return Err(AmfError::UnknownMarker(marker)),
+        })
+    }
+
+    pub(crate) fn read_amf3_string(&mut self) -> Result<String, AmfError> {
+        let header = self.read_u29()?;
+        if header & 1 == 0 {
+            let index = (header >> 1) as usize;
+            return self
+                .strings
+                .get(index)
+                .cloned()
+                .ok_or(AmfError::InvalidReference(index));
+        }
+
+        let string = self.read_utf8((header >> 1) as usize)?;
+        if !string.is_empty() {
+            self.strings.push(string.clone());
+        }
+
+        Ok(string)
+    }
+
+    /// Read the traits of an object from the rest of its header, returning
+    /// them along with whether the object is externalizable.
+    fn read_amf3_traits(&mut self, header: u32) -> Result<(Traits, bool), AmfError> {
+        if header & 0b10 == 0 {
+            let index = (header >> 2) as usize;
+            return self
+                .traits
+                .get(index)
+                .cloned()
+                .ok_or(AmfError::InvalidReference(index));
+        }
+
+        let externalizable = header & 0b100 != 0;
+        let class_name = self.read_amf3_string()?;
+        let mut sealed = Vec::new();
+        for _ in 0..header >> 4 {
+            sealed.push(self.read_amf3_string()?);
+        }
+
+        let traits = Traits {
+            class_name: Some(class_name).filter(|name| !name.is_empty()),
+            sealed,
+            dynamic: header & 0b1000 != 0,
+        };
+        self.traits.push((traits.clone(), externalizable));
+
+        Ok((traits, externalizable))
+    }
+
+    /// Read a variable-length 29-bit unsigned integer.
+    fn read_u29(&mut self) -> Result<u32, AmfError> {
+        let mut n = 0;
+        for _ in 0..3 {
+            let byte = self.read_u8()?;
+            n = n << 7 | u32::from(byte & 0x7F);
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+        }
+
+        Ok(n << 8 | u32::from(self.read_u8()?))
+    }
+}
@@ -0,0 +1,158 @@
+//! Local shared objects, as stored in `.sol` files.
+//!
+//! A `.sol` file starts with a header naming the shared object and the AMF
+//! version of its body. The body is a list of the named properties of the
+//! shared object's `data`, which share reference tables.
+
+use crate::read::Reader;
+use crate::write::Writer;
+use crate::{AmfError, AmfValue, Decoder, ObjectEncoding};
+
+/// The signature at the start of every `.sol` file.
+const MAGIC: [u8; 2] = [0x00, 0xBF];
+
+/// The signature after the length of the file, and the padding after it.
+const SIGNATURE: [u8; 10] = *b"TCSO\x00\x04\x00\x00\x00\x00";
+
+/// The contents of a `.sol` file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lso {
+    /// The name the shared object was created with, without its path.
+    pub name: String,
+
+    pub encoding: ObjectEncoding,
+
+    /// The properties of the shared object's `data`.
+    ///
+    /// References in the values are numbered across the whole body, in
+    /// order.
+    pub body: Vec<(String, AmfValue)>,
+}
+
+/// Read a `.sol` file, using the default `Decoder`.
+pub fn read_lso(data: &[u8]) -> Result<Lso, AmfError> {
+    Decoder::default().read_lso(data)
+}
+
+impl Decoder {
+    /// Read a `.sol` file.
+    pub fn read_lso(&self, data: &[u8]) -> Result<Lso, AmfError> {
+        let mut reader = Reader::new(data, self);
+        if reader.read_bytes(MAGIC.len())? != MAGIC {
+            return Err(AmfError::InvalidHeader);
+        }
+
+        // The length of the rest of the file.
+        let length = reader.read_u32()? as usize;
+        if length != data.len() - reader.position() {
+            return Err(AmfError::InvalidHeader);
+        }
+
+        if reader.read_bytes(SIGNATURE.len())? != SIGNATURE {
+            return Err(AmfError::InvalidHeader);
+        }
+
+        let name = reader.read_amf0_utf8()?;
+        let encoding =
+            ObjectEncoding::from_u32(reader.read_u32()?).ok_or(AmfError::InvalidHeader)?;
+
+        let mut body = Vec::new();
+        while !reader.is_empty() {
+            let name = match encoding {
+                ObjectEncoding::Amf0 => reader.read_amf0_utf8()?,
+                ObjectEncoding::Amf3 => reader.read_amf3_string()?,
+            };
+            let value = reader.read_value(encoding)?;
+
+            // Every property is followed by a padding byte.
+            reader.read_u8()?;
+
+            body.push((name, value));
+        }
+
+        Ok(Lso {
+            name,
+            encoding,
+            body,
+        })
+    }
+}
+
+/// Write a `.sol` file.
+pub fn write_lso(lso: &Lso) -> Vec<u8> {
+    let mut writer = Writer::default();
+    writer.output.extend_from_slice(&SIGNATURE);
+    writer.write_amf0_utf8(&lso.name);
+    writer.write_u32(lso.encoding.as_u32());
+
+    for (name, value) in &lso.body {
+        match lso.encoding {
+            ObjectEncoding::Amf0 => writer.write_amf0_utf8(name),
+            ObjectEncoding::Amf3 => writer.write_amf3_string(name),
+        }
+        writer.write_value(value, lso.encoding);
+        writer.write_u8(0);
+    }
+
+    let mut output = MAGIC.to_vec();
+    output.extend_from_slice(&(writer.output.len() as u32).to_be_bytes());
+    output.extend(writer.output);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AmfObject;
+
+    #[test]
+    fn round_trip() {
+        for &encoding in &[ObjectEncoding::Amf0, ObjectEncoding::Amf3] {
+            let lso = Lso {
+                name: "highscores".to_string(),
+                encoding,
+                body: vec![
+                    ("best".to_string(), AmfValue::Number(1500.0)),
+                    (
+                        "player".to_string(),
+                        AmfValue::Object(AmfObject::anonymous(vec![(
+                            "name".to_string(),
+                            AmfValue::String("best".to_string()),
+                        )])),
+                    ),
+                    ("again".to_string(), AmfValue::Reference(0)),
+                ],
+            };
+
+            let data = write_lso(&lso);
+            assert_eq!(&data[..2], &MAGIC);
+            assert_eq!(read_lso(&data).unwrap(), lso);
+        }
+    }
+
+    #[test]
+    fn amf0_layout() {
+        let lso = Lso {
+            name: "a".to_string(),
+            encoding: ObjectEncoding::Amf0,
+            body: vec![("b".to_string(), AmfValue::Bool(true))],
+        };
+
+        assert_eq!(
+            write_lso(&lso),
+            vec![
+                0x00, 0xBF, 0x00, 0x00, 0x00, 0x17, b'T', b'C', b'S', b'O', 0x00, 0x04, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x01, b'a', 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, b'b', 0x01, 0x01,
+                0x00,
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_header() {
+        assert!(matches!(
+            read_lso(b"{\"a\":1}"),
+            Err(AmfError::InvalidHeader)
+        ));
+    }
+}
@@ -0,0 +1,148 @@
+/// The version of AMF used to encode values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObjectEncoding {
+    Amf0,
+    Amf3,
+}
+
+impl ObjectEncoding {
+    /// Convert the numeric value that ActionScript uses for an encoding.
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Amf0),
+            3 => Some(Self::Amf3),
+            _ => None,
+        }
+    }
+
+    /// The numeric value that ActionScript uses for this encoding.
+    pub fn as_u32(self) -> u32 {
+        match self {
+            Self::Amf0 => 0,
+            Self::Amf3 => 3,
+        }
+    }
+}
+
+/// A value that can be serialized with AMF.
+///
+/// Objects, arrays and byte arrays can appear in a value more than once, and
+/// can refer to themselves. The second and later appearances of one of these
+/// are written as a `Reference` to its index in the order that they first
+/// appear in the value, with the outermost value first.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AmfValue {
+    Undefined,
+    Null,
+    Bool(bool),
+    Integer(i32),
+    Number(f64),
+    String(String),
+
+    /// Milliseconds since the Unix epoch, in UTC.
+    Date(f64),
+
+    /// The source of a legacy XML document, as used by the AVM1 `XML` class
+    /// and `flash.xml.XMLDocument`.
+    XmlDocument(String),
+
+    /// The source of an E4X `XML` value.
+    Xml(String),
+
+    /// An array, which has both a dense part and named properties.
+    ///
+    /// The named properties come before the dense part when numbering
+    /// references, as that is the order they are encoded in.
+    Array {
+        dense: Vec<AmfValue>,
+        associative: Vec<(String, AmfValue)>,
+    },
+
+    Object(AmfObject),
+
+    /// An object of a class that serializes itself with `writeExternal`.
+    ///
+    /// Its data is the sequence of AMF3 values that `writeExternal` wrote.
+    /// Classes that write raw bytes instead can't be read or written.
+    Externalizable {
+        class_name: String,
+        data: Vec<AmfValue>,
+    },
+
+    ByteArray(Vec<u8>),
+
+    /// A reference to an object, array or byte array that appeared earlier.
+    Reference(usize),
+}
+
+impl AmfValue {
+    /// Whether this value can be referred to by a `Reference`.
+    pub(crate) fn is_referenceable(&self) -> bool {
+        matches!(
+            self,
+            AmfValue::Array { .. }
+                | AmfValue::Object(_)
+                | AmfValue::Externalizable { .. }
+                | AmfValue::ByteArray(_)
+        )
+    }
+}
+
+/// The class of an object: its name and the properties every instance has.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Traits {
+    /// The alias the class was registered with, or `None` for anonymous
+    /// objects.
+    pub class_name: Option<String>,
+
+    /// The names of the properties declared by the class.
+    pub sealed: Vec<String>,
+
+    /// Whether instances can have properties that the class doesn't declare.
+    pub dynamic: bool,
+}
+
+impl Traits {
+    /// The traits of a plain `Object`.
+    pub fn anonymous() -> Self {
+        Self {
+            class_name: None,
+            sealed: Vec::new(),
+            dynamic: true,
+        }
+    }
+}
+
+/// An object, along with the traits of its class.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AmfObject {
+    pub traits: Traits,
+
+    /// The values of the sealed properties, in the order the traits declare
+    /// them.
+    pub sealed: Vec<AmfValue>,
+
+    /// The dynamic properties, which are only written for dynamic classes.
+    pub dynamic: Vec<(String, AmfValue)>,
+}
+
+impl AmfObject {
+    /// Construct a plain `Object` with the given properties.
+    pub fn anonymous(properties: Vec<(String, AmfValue)>) -> Self {
+        Self {
+            traits: Traits::anonymous(),
+            sealed: Vec::new(),
+            dynamic: properties,
+        }
+    }
+
+    /// All properties of this object, sealed properties first.
+    pub fn into_properties(self) -> Vec<(String, AmfValue)> {
+        self.traits
+            .sealed
+            .into_iter()
+            .zip(self.sealed)
+            .chain(self.dynamic)
+            .collect()
+    }
+}
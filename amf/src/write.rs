@@ -0,0 +1,384 @@
+//! Writing AMF values.
+
+use crate::{amf0, amf3, AmfObject, AmfValue, ObjectEncoding, Traits};
+
+/// Serialize a value with the given encoding.
+pub fn write_value(value: &AmfValue, encoding: ObjectEncoding) -> Vec<u8> {
+    let mut writer = Writer::default();
+    writer.write_value(value, encoding);
+    writer.output
+}
+
+/// The position of a referenceable value in the reference table of an
+/// encoding.
+#[derive(Clone, Debug)]
+enum WrittenReference {
+    /// The value can be referred to by this index, with this type marker.
+    Index(usize, u8),
+
+    /// The value can't be referred to in this encoding, so it has to be
+    /// written out again.
+    Inline(AmfValue),
+}
+
+/// Writes a sequence of values that share reference tables.
+#[derive(Default)]
+pub(crate) struct Writer {
+    pub(crate) output: Vec<u8>,
+
+    /// For each referenceable value written so far, how to write it again.
+    references: Vec<WrittenReference>,
+
+    /// Number of entries in the object table of the current encoding.
+    object_count: usize,
+
+    /// AMF3 strings written so far.
+    strings: Vec<String>,
+
+    /// AMF3 traits written so far, and whether they were externalizable.
+    traits: Vec<(Traits, bool)>,
+}
+
+impl Writer {
+    pub(crate) fn write_value(&mut self, value: &AmfValue, encoding: ObjectEncoding) {
+        match encoding {
+            ObjectEncoding::Amf0 => self.write_amf0(value),
+            ObjectEncoding::Amf3 => self.write_amf3(value),
+        }
+    }
+
+    /// Record a referenceable value before writing it, returning how it can
+    /// be referred to later.
+    fn add_reference(&mut self, value: &AmfValue, reference: WrittenReference) {
+        if value.is_referenceable() {
+            self.references.push(reference);
+        }
+    }
+
+    fn write_amf0(&mut self, value: &AmfValue) {
+        match value {
+            AmfValue::Undefined => self.output.push(amf0::UNDEFINED),
+            AmfValue::Null => self.output.push(amf0::NULL),
+            AmfValue::Bool(b) => {
+                self.output.push(amf0::BOOLEAN);
+                self.output.push(*b as u8);
+            }
+            AmfValue::Integer(i) => self.write_amf0_number(f64::from(*i)),
+            AmfValue::Number(n) => self.write_amf0_number(*n),
+            AmfValue::String(s) => {
+                if s.len() > usize::from(u16::MAX) {
+                    self.output.push(amf0::LONG_STRING);
+                    self.write_u32(s.len() as u32);
+                    self.output.extend_from_slice(s.as_bytes());
+                } else {
+                    self.output.push(amf0::STRING);
+                    self.write_amf0_utf8(s);
+                }
+            }
+            AmfValue::Date(time) => {
+                self.output.push(amf0::DATE);
+                self.write_f64(*time);
+                self.write_u16(0);
+            }
+            AmfValue::XmlDocument(source) => {
+                self.output.push(amf0::XML_DOCUMENT);
+                self.write_u32(source.len() as u32);
+                self.output.extend_from_slice(source.as_bytes());
+            }
+            AmfValue::Array { dense, associative } => {
+                self.add_reference(
+                    value,
+                    WrittenReference::Index(self.object_count, amf0::REFERENCE),
+                );
+                self.object_count += 1;
+
+                if associative.is_empty() {
+                    self.output.push(amf0::STRICT_ARRAY);
+                    self.write_u32(dense.len() as u32);
+                    for item in dense {
+                        self.write_amf0(item);
+                    }
+                } else {
+                    // Named properties are written first, like in AMF3, so
+                    // that references are numbered the same way.
+                    let indices: Vec<String> = (0..dense.len()).map(|i| i.to_string()).collect();
+                    self.output.push(amf0::ECMA_ARRAY);
+                    self.write_u32(dense.len() as u32);
+                    self.write_amf0_properties(
+                        associative
+                            .iter()
+                            .map(|(name, value)| (name, value))
+                            .chain(indices.iter().zip(dense)),
+                    );
+                }
+            }
+            AmfValue::Object(object) => {
+                self.add_reference(
+                    value,
+                    WrittenReference::Index(self.object_count, amf0::REFERENCE),
+                );
+                self.object_count += 1;
+
+                if let Some(class_name) = &object.traits.class_name {
+                    self.output.push(amf0::TYPED_OBJECT);
+                    self.write_amf0_utf8(class_name);
+                } else {
+                    self.output.push(amf0::OBJECT);
+                }
+                self.write_amf0_properties(object_properties(object));
+            }
+            AmfValue::Xml(_) | AmfValue::Externalizable { .. } | AmfValue::ByteArray(_) => {
+                // AMF0 has no such types, so they are written as AMF3 values.
+                // References can't cross into AMF3, so repeated values are
+                // written out again.
+                self.add_reference(value, WrittenReference::Inline(value.clone()));
+                self.write_avmplus(value);
+            }
+            AmfValue::Reference(index) => match self.references.get(*index).cloned() {
+                Some(WrittenReference::Index(index, _)) if index <= usize::from(u16::MAX) => {
+                    self.output.push(amf0::REFERENCE);
+                    self.write_u16(index as u16);
+                }
+                Some(WrittenReference::Inline(value)) => self.write_avmplus(&value),
+                _ => self.output.push(amf0::NULL),
+            },
+        }
+    }
+
+    /// Switch to AMF3 for a single value.
+    fn write_avmplus(&mut self, value: &AmfValue) {
+        let mut writer = Writer::default();
+        writer.write_amf3(value);
+        self.output.push(amf0::AVMPLUS);
+        self.output.extend_from_slice(&writer.output);
+    }
+
+    fn write_amf0_number(&mut self, n: f64) {
+        self.output.push(amf0::NUMBER);
+        self.write_f64(n);
+    }
+
+    pub(crate) fn write_amf0_utf8(&mut self, s: &str) {
+        let len = s.len().min(usize::from(u16::MAX));
+        self.write_u16(len as u16);
+        self.output.extend_from_slice(&s.as_bytes()[..len]);
+    }
+
+    fn write_amf0_properties<'a>(
+        &mut self,
+        properties: impl IntoIterator<Item = (&'a String, &'a AmfValue)>,
+    ) {
+        for (name, value) in properties {
+            self.write_amf0_utf8(name);
+            self.write_amf0(value);
+        }
+
+        self.write_amf0_utf8("");
+        self.output.push(amf0::OBJECT_END);
+    }
+
+    fn write_amf3(&mut self, value: &AmfValue) {
+        match value {
+            AmfValue::Undefined => self.output.push(amf3::UNDEFINED),
+            AmfValue::Null => self.output.push(amf3::NULL),
+            AmfValue::Bool(false) => self.output.push(amf3::FALSE),
+            AmfValue::Bool(true) => self.output.push(amf3::TRUE),
+            AmfValue::Integer(i) if (amf3::MIN_INTEGER..=amf3::MAX_INTEGER).contains(i) => {
+                self.output.push(amf3::INTEGER);
+                self.write_u29(*i as u32 & 0x1FFF_FFFF);
+            }
+            AmfValue::Integer(i) => self.write_amf3_double(f64::from(*i)),
+            AmfValue::Number(n) => self.write_amf3_double(*n),
+            AmfValue::String(s) => {
+                self.output.push(amf3::STRING);
+                self.write_amf3_string(s);
+            }
+            AmfValue::Date(time) => {
+                self.object_count += 1;
+                self.output.push(amf3::DATE);
+                self.write_u29(1);
+                self.write_f64(*time);
+            }
+            AmfValue::XmlDocument(source) => {
+                self.object_count += 1;
+                self.output.push(amf3::XML_DOCUMENT);
+                self.write_u29((source.len() as u32) << 1 | 1);
+                self.output.extend_from_slice(source.as_bytes());
+            }
+            AmfValue::Xml(source) => {
+                self.object_count += 1;
+                self.output.push(amf3::XML);
+                self.write_u29((source.len() as u32) << 1 | 1);
+                self.output.extend_from_slice(source.as_bytes());
+            }
+            AmfValue::Array { dense, associative } => {
+                self.add_reference(
+                    value,
+                    WrittenReference::Index(self.object_count, amf3::ARRAY),
+                );
+                self.object_count += 1;
+
+                self.output.push(amf3::ARRAY);
+                self.write_u29((dense.len() as u32) << 1 | 1);
+                for (name, value) in associative {
+                    self.write_amf3_string(name);
+                    self.write_amf3(value);
+                }
+                self.write_amf3_string("");
+                for item in dense {
+                    self.write_amf3(item);
+                }
+            }
+            AmfValue::Object(object) => {
+                self.add_reference(
+                    value,
+                    WrittenReference::Index(self.object_count, amf3::OBJECT),
+                );
+                self.object_count += 1;
+
+                self.output.push(amf3::OBJECT);
+                self.write_amf3_traits(&object.traits, false);
+                for value in &object.sealed {
+                    self.write_amf3(value);
+                }
+
+                if object.traits.dynamic {
+                    for (name, value) in &object.dynamic {
+                        self.write_amf3_string(name);
+                        self.write_amf3(value);
+                    }
+                    self.write_amf3_string("");
+                }
+            }
+            AmfValue::Externalizable { class_name, data } => {
+                self.add_reference(
+                    value,
+                    WrittenReference::Index(self.object_count, amf3::OBJECT),
+                );
+                self.object_count += 1;
+
+                let traits = Traits {
+                    class_name: Some(class_name.clone()),
+                    sealed: Vec::new(),
+                    dynamic: false,
+                };
+                self.output.push(amf3::OBJECT);
+                self.write_amf3_traits(&traits, true);
+                for value in data {
+                    self.write_amf3(value);
+                }
+            }
+            AmfValue::ByteArray(bytes) => {
+                self.add_reference(
+                    value,
+                    WrittenReference::Index(self.object_count, amf3::BYTE_ARRAY),
+                );
+                self.object_count += 1;
+
+                self.output.push(amf3::BYTE_ARRAY);
+                self.write_u29((bytes.len() as u32) << 1 | 1);
+                self.output.extend_from_slice(bytes);
+            }
+            AmfValue::Reference(index) => match self.references.get(*index).cloned() {
+                Some(WrittenReference::Index(index, marker)) => {
+                    self.output.push(marker);
+                    self.write_u29((index as u32) << 1);
+                }
+                Some(WrittenReference::Inline(value)) => self.write_amf3(&value),
+                None => self.output.push(amf3::NULL),
+            },
+        }
+    }
+
+    fn write_amf3_double(&mut self, n: f64) {
+        self.output.push(amf3::DOUBLE);
+        self.write_f64(n);
+    }
+
+    pub(crate) fn write_amf3_string(&mut self, s: &str) {
+        if s.is_empty() {
+            // The empty string is never added to the reference table.
+            return self.write_u29(1);
+        }
+
+        if let Some(index) = self.strings.iter().position(|other| other == s) {
+            return self.write_u29((index as u32) << 1);
+        }
+
+        self.strings.push(s.to_string());
+        self.write_u29((s.len() as u32) << 1 | 1);
+        self.output.extend_from_slice(s.as_bytes());
+    }
+
+    /// Write the traits of an object, or a reference to them if an earlier
+    /// object had the same traits.
+    fn write_amf3_traits(&mut self, traits: &Traits, externalizable: bool) {
+        let index = self
+            .traits
+            .iter()
+            .position(|(other, other_externalizable)| {
+                other == traits && *other_externalizable == externalizable
+            });
+        if let Some(index) = index {
+            return self.write_u29((index as u32) << 2 | 0b01);
+        }
+
+        self.traits.push((traits.clone(), externalizable));
+        self.write_u29(
+            (traits.sealed.len() as u32) << 4
+                | (traits.dynamic as u32) << 3
+                | (externalizable as u32) << 2
+                | 0b011,
+        );
+        self.write_amf3_string(traits.class_name.as_deref().unwrap_or(""));
+        for name in &traits.sealed {
+            self.write_amf3_string(name);
+        }
+    }
+
+    pub(crate) fn write_u8(&mut self, n: u8) {
+        self.output.push(n);
+    }
+
+    pub(crate) fn write_u16(&mut self, n: u16) {
+        self.output.extend_from_slice(&n.to_be_bytes());
+    }
+
+    pub(crate) fn write_u32(&mut self, n: u32) {
+        self.output.extend_from_slice(&n.to_be_bytes());
+    }
+
+    fn write_f64(&mut self, n: f64) {
+        self.output.extend_from_slice(&n.to_be_bytes());
+    }
+
+    /// Write a variable-length 29-bit unsigned integer.
+    fn write_u29(&mut self, n: u32) {
+        let n = n & 0x1FFF_FFFF;
+        if n < 0x80 {
+            self.output.push(n as u8);
+        } else if n < 0x4000 {
+            self.output.push((n >> 7 | 0x80) as u8);
+            self.output.push((n & 0x7F) as u8);
+        } else if n < 0x20_0000 {
+            self.output.push((n >> 14 | 0x80) as u8);
+            self.output.push((n >> 7 & 0x7F | 0x80) as u8);
+            self.output.push((n & 0x7F) as u8);
+        } else {
+            self.output.push((n >> 22 | 0x80) as u8);
+            self.output.push((n >> 15 & 0x7F | 0x80) as u8);
+            self.output.push((n >> 8 & 0x7F | 0x80) as u8);
+            self.output.push((n & 0xFF) as u8);
+        }
+    }
+}
+
+/// The properties of an object, sealed properties first.
+fn object_properties(object: &AmfObject) -> impl Iterator<Item = (&String, &AmfValue)> {
+    object
+        .traits
+        .sealed
+        .iter()
+        .zip(&object.sealed)
+        .chain(object.dynamic.iter().map(|(name, value)| (name, value)))
+}
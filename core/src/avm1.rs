@@ -25,7 +25,6 @@ pub mod object;
 pub mod property;
 mod scope;
 mod string;
-mod timer;
 mod value;
 
 #[cfg(test)]
@@ -43,7 +42,6 @@ pub use object::{Object, ObjectPtr, TObject};
 use scope::Scope;
 use smallvec::alloc::borrow::Cow;
 pub use string::AvmString;
-pub use timer::Timers;
 pub use value::Value;
 
 macro_rules! avm_debug {
@@ -308,18 +308,17 @@ pub fn create_timer<'gc>(
     is_timeout: bool,
 ) -> Result<Value<'gc>, Error<'gc>> {
     // `setInterval` was added in Flash Player 6 but is not version-gated.
-    use crate::avm1::timer::TimerCallback;
-    let (callback, i) = match args.get(0) {
-        Some(Value::Object(o)) if o.as_executable().is_some() => (TimerCallback::Function(*o), 1),
+    use crate::timer::TimerCallback;
+    let (func, method_name, i) = match args.get(0) {
+        Some(Value::Object(o)) if o.as_executable().is_some() => (*o, None, 1),
         Some(Value::Object(o)) => (
-            TimerCallback::Method {
-                this: *o,
-                method_name: args
-                    .get(1)
+            *o,
+            Some(
+                args.get(1)
                     .unwrap_or(&Value::Undefined)
                     .coerce_to_string(activation)?
                     .to_string(),
-            },
+            ),
             2,
         ),
         _ => return Ok(Value::Undefined),
@@ -335,10 +334,19 @@ pub fn create_timer<'gc>(
         vec![]
     };
 
+    let callback = match method_name {
+        Some(method_name) => TimerCallback::Avm1Method {
+            this: func,
+            method_name,
+            params,
+        },
+        None => TimerCallback::Avm1Function { func, params },
+    };
+
     let id = activation
         .context
         .timers
-        .add_timer(callback, interval, params, is_timeout);
+        .add_timer(callback, interval, is_timeout);
 
     Ok(id.into())
 }
@@ -466,6 +474,7 @@ pub struct SystemPrototypes<'gc> {
     pub array: Object<'gc>,
     pub array_constructor: Object<'gc>,
     pub xml_node: Object<'gc>,
+    pub xml_constructor: Object<'gc>,
     pub string: Object<'gc>,
     pub number: Object<'gc>,
     pub boolean: Object<'gc>,
@@ -1215,6 +1224,7 @@ pub fn create_globals<'gc>(
             array: array_proto,
             array_constructor: array,
             xml_node: xmlnode_proto,
+            xml_constructor: xml,
             string: string_proto,
             number: number_proto,
             boolean: boolean_proto,
@@ -2,9 +2,28 @@
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
+use crate::avm1::object::bevel_filter::{BevelFilterObject, BevelFilterType};
+use crate::avm1::object::bitmap_data::{BitmapDataObject, Color as BitmapColor};
+use crate::avm1::object::blur_filter::BlurFilterObject;
+use crate::avm1::object::color_matrix_filter::ColorMatrixFilterObject;
+use crate::avm1::object::convolution_filter::ConvolutionFilterObject;
+use crate::avm1::object::displacement_map_filter::{
+    DisplacementMapFilterMode, DisplacementMapFilterObject,
+};
+use crate::avm1::object::drop_shadow_filter::DropShadowFilterObject;
+use crate::avm1::object::glow_filter::GlowFilterObject;
+use crate::avm1::object::gradient_bevel_filter::GradientBevelFilterObject;
+use crate::avm1::object::gradient_glow_filter::GradientGlowFilterObject;
 use crate::avm1::property::Attribute;
 use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::bitmap::filters::{
+    BevelFilter, BlurFilter, ColorMatrixFilter, ConvolutionFilter, DisplacementMapFilter,
+    DisplacementMapMode, DropShadowFilter, Filter, FilterBitmap, FilterType, GlowFilter,
+    GradientFilter,
+};
 use gc_arena::MutationContext;
+use std::sync::Arc;
+use swf::{Color, GradientRecord};
 
 pub fn constructor<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
@@ -279,3 +298,371 @@ pub fn create_proto<'gc>(
 
     object.into()
 }
+
+fn quality(quality: i32) -> u8 {
+    quality.max(0).min(15) as u8
+}
+
+fn color(rgb: u32, alpha: f64) -> Color {
+    Color::from_rgb(rgb, (alpha.max(0.0).min(1.0) * 255.0) as u8)
+}
+
+fn alpha(color: &Color) -> f64 {
+    f64::from(color.a) / 255.0
+}
+
+fn filter_type(type_: BevelFilterType) -> FilterType {
+    match type_ {
+        BevelFilterType::Inner => FilterType::Inner,
+        BevelFilterType::Outer => FilterType::Outer,
+        BevelFilterType::Full => FilterType::Full,
+    }
+}
+
+fn bevel_type(filter_type: FilterType) -> BevelFilterType {
+    match filter_type {
+        FilterType::Inner => BevelFilterType::Inner,
+        FilterType::Outer => BevelFilterType::Outer,
+        FilterType::Full => BevelFilterType::Full,
+    }
+}
+
+fn gradient_records(colors: &[u32], alphas: &[f64], ratios: &[u8]) -> Vec<GradientRecord> {
+    colors
+        .iter()
+        .zip(alphas)
+        .zip(ratios)
+        .map(|((rgb, alpha), ratio)| GradientRecord {
+            ratio: *ratio,
+            color: color(*rgb, *alpha),
+        })
+        .collect()
+}
+
+/// Converts an AVM1 filter object into a `Filter` that can be applied when rendering.
+///
+/// Returns `None` if the object isn't a filter.
+pub fn object_to_filter(object: Object<'_>) -> Option<Filter> {
+    if let Some(filter) = object.as_blur_filter_object() {
+        return Some(Filter::Blur(BlurFilter {
+            blur_x: filter.blur_x() as f32,
+            blur_y: filter.blur_y() as f32,
+            quality: quality(filter.quality()),
+        }));
+    }
+
+    if let Some(filter) = object.as_drop_shadow_filter_object() {
+        return Some(Filter::DropShadow(DropShadowFilter {
+            color: color(filter.color(), filter.alpha()),
+            blur_x: filter.blur_x() as f32,
+            blur_y: filter.blur_y() as f32,
+            angle: filter.angle() as f32,
+            distance: filter.distance() as f32,
+            strength: filter.strength() as f32,
+            quality: quality(filter.quality()),
+            inner: filter.inner(),
+            knockout: filter.knockout(),
+            hide_object: filter.hide_object(),
+        }));
+    }
+
+    if let Some(filter) = object.as_glow_filter_object() {
+        return Some(Filter::Glow(GlowFilter {
+            color: color(filter.color() as u32, filter.alpha()),
+            blur_x: filter.blur_x() as f32,
+            blur_y: filter.blur_y() as f32,
+            strength: filter.strength() as f32,
+            quality: quality(filter.quality()),
+            inner: filter.inner(),
+            knockout: filter.knockout(),
+        }));
+    }
+
+    if let Some(filter) = object.as_bevel_filter_object() {
+        return Some(Filter::Bevel(BevelFilter {
+            highlight_color: color(filter.highlight_color(), filter.highlight_alpha()),
+            shadow_color: color(filter.shadow_color(), filter.shadow_alpha()),
+            blur_x: filter.blur_x() as f32,
+            blur_y: filter.blur_y() as f32,
+            angle: filter.angle() as f32,
+            distance: filter.distance() as f32,
+            strength: filter.strength() as f32,
+            quality: quality(filter.quality()),
+            filter_type: filter_type(filter.get_type()),
+            knockout: filter.knockout(),
+        }));
+    }
+
+    if let Some(filter) = object.as_gradient_glow_filter_object() {
+        return Some(Filter::GradientGlow(GradientFilter {
+            colors: gradient_records(&filter.colors(), &filter.alphas(), &filter.ratios()),
+            blur_x: filter.blur_x() as f32,
+            blur_y: filter.blur_y() as f32,
+            angle: filter.angle() as f32,
+            distance: filter.distance() as f32,
+            strength: filter.strength() as f32,
+            quality: quality(filter.quality()),
+            filter_type: filter_type(filter.get_type()),
+            knockout: filter.knockout(),
+        }));
+    }
+
+    if let Some(filter) = object.as_gradient_bevel_filter_object() {
+        return Some(Filter::GradientBevel(GradientFilter {
+            colors: gradient_records(&filter.colors(), &filter.alphas(), &filter.ratios()),
+            blur_x: filter.blur_x() as f32,
+            blur_y: filter.blur_y() as f32,
+            angle: filter.angle() as f32,
+            distance: filter.distance() as f32,
+            strength: filter.strength() as f32,
+            quality: quality(filter.quality()),
+            filter_type: filter_type(filter.get_type()),
+            knockout: filter.knockout(),
+        }));
+    }
+
+    if let Some(filter) = object.as_color_matrix_filter_object() {
+        let mut matrix = [0.0; 20];
+        for (dst, src) in matrix.iter_mut().zip(filter.matrix().iter()) {
+            *dst = *src as f32;
+        }
+        return Some(Filter::ColorMatrix(ColorMatrixFilter { matrix }));
+    }
+
+    if let Some(filter) = object.as_convolution_filter_object() {
+        return Some(Filter::Convolution(ConvolutionFilter {
+            matrix_x: filter.matrix_x(),
+            matrix_y: filter.matrix_y(),
+            matrix: filter.matrix().iter().map(|n| *n as f32).collect(),
+            divisor: filter.divisor() as f32,
+            bias: filter.bias() as f32,
+            preserve_alpha: filter.preserve_alpha(),
+            clamp: filter.clamp(),
+            color: color(filter.color(), filter.alpha()),
+        }));
+    }
+
+    if let Some(filter) = object.as_displacement_map_filter_object() {
+        let map_bitmap = filter
+            .map_bitmap()
+            .and_then(|map| map.as_bitmap_data_object())
+            .map(|map| {
+                let map = map.bitmap_data();
+                let map = map.read();
+                let mut data = Vec::with_capacity(map.pixels().len() * 4);
+                for pixel in map.pixels() {
+                    let pixel = pixel.to_un_multiplied_alpha();
+                    data.extend_from_slice(&[
+                        pixel.red(),
+                        pixel.green(),
+                        pixel.blue(),
+                        pixel.alpha(),
+                    ]);
+                }
+                Arc::new(FilterBitmap {
+                    width: map.width(),
+                    height: map.height(),
+                    data,
+                })
+            });
+        let mode = match filter.mode() {
+            DisplacementMapFilterMode::Wrap => DisplacementMapMode::Wrap,
+            DisplacementMapFilterMode::Clamp => DisplacementMapMode::Clamp,
+            DisplacementMapFilterMode::Ignore => DisplacementMapMode::Ignore,
+            DisplacementMapFilterMode::Color => DisplacementMapMode::Color,
+        };
+        return Some(Filter::DisplacementMap(DisplacementMapFilter {
+            map_bitmap,
+            map_point: filter.map_point(),
+            component_x: filter.component_x() as u8,
+            component_y: filter.component_y() as u8,
+            scale_x: filter.scale_x() as f32,
+            scale_y: filter.scale_y() as f32,
+            mode,
+            color: color(filter.color(), filter.alpha()),
+        }));
+    }
+
+    None
+}
+
+/// Creates a new AVM1 filter object with the parameters of a `Filter`.
+pub fn filter_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    filter: &Filter,
+) -> Object<'gc> {
+    let gc_context = activation.context.gc_context;
+    let prototypes = &activation.context.avm1.prototypes;
+    match filter {
+        Filter::Blur(filter) => {
+            let object = BlurFilterObject::empty_object(gc_context, Some(prototypes.blur_filter));
+            object.set_blur_x(gc_context, filter.blur_x.into());
+            object.set_blur_y(gc_context, filter.blur_y.into());
+            object.set_quality(gc_context, filter.quality.into());
+            object.into()
+        }
+        Filter::DropShadow(filter) => {
+            let object = DropShadowFilterObject::empty_object(
+                gc_context,
+                Some(prototypes.drop_shadow_filter),
+            );
+            object.set_color(gc_context, filter.color.to_rgb());
+            object.set_alpha(gc_context, alpha(&filter.color));
+            object.set_blur_x(gc_context, filter.blur_x.into());
+            object.set_blur_y(gc_context, filter.blur_y.into());
+            object.set_angle(gc_context, filter.angle.into());
+            object.set_distance(gc_context, filter.distance.into());
+            object.set_strength(gc_context, filter.strength.into());
+            object.set_quality(gc_context, filter.quality.into());
+            object.set_inner(gc_context, filter.inner);
+            object.set_knockout(gc_context, filter.knockout);
+            object.set_hide_object(gc_context, filter.hide_object);
+            object.into()
+        }
+        Filter::Glow(filter) => {
+            let object = GlowFilterObject::empty_object(gc_context, Some(prototypes.glow_filter));
+            object.set_color(gc_context, filter.color.to_rgb() as i32);
+            object.set_alpha(gc_context, alpha(&filter.color));
+            object.set_blur_x(gc_context, filter.blur_x.into());
+            object.set_blur_y(gc_context, filter.blur_y.into());
+            object.set_strength(gc_context, filter.strength.into());
+            object.set_quality(gc_context, filter.quality.into());
+            object.set_inner(gc_context, filter.inner);
+            object.set_knockout(gc_context, filter.knockout);
+            object.into()
+        }
+        Filter::Bevel(filter) => {
+            let object = BevelFilterObject::empty_object(gc_context, Some(prototypes.bevel_filter));
+            object.set_highlight_color(gc_context, filter.highlight_color.to_rgb());
+            object.set_highlight_alpha(gc_context, alpha(&filter.highlight_color));
+            object.set_shadow_color(gc_context, filter.shadow_color.to_rgb());
+            object.set_shadow_alpha(gc_context, alpha(&filter.shadow_color));
+            object.set_blur_x(gc_context, filter.blur_x.into());
+            object.set_blur_y(gc_context, filter.blur_y.into());
+            object.set_angle(gc_context, filter.angle.into());
+            object.set_distance(gc_context, filter.distance.into());
+            object.set_strength(gc_context, filter.strength.into());
+            object.set_quality(gc_context, filter.quality.into());
+            object.set_type(gc_context, bevel_type(filter.filter_type));
+            object.set_knockout(gc_context, filter.knockout);
+            object.into()
+        }
+        Filter::GradientGlow(filter) => {
+            let object = GradientGlowFilterObject::empty_object(
+                gc_context,
+                Some(prototypes.gradient_glow_filter),
+            );
+            object.set_colors(
+                gc_context,
+                filter.colors.iter().map(|r| r.color.to_rgb()).collect(),
+            );
+            object.set_alphas(
+                gc_context,
+                filter.colors.iter().map(|r| alpha(&r.color)).collect(),
+            );
+            object.set_ratios(gc_context, filter.colors.iter().map(|r| r.ratio).collect());
+            object.set_blur_x(gc_context, filter.blur_x.into());
+            object.set_blur_y(gc_context, filter.blur_y.into());
+            object.set_angle(gc_context, filter.angle.into());
+            object.set_distance(gc_context, filter.distance.into());
+            object.set_strength(gc_context, filter.strength.into());
+            object.set_quality(gc_context, filter.quality.into());
+            object.set_type(gc_context, bevel_type(filter.filter_type));
+            object.set_knockout(gc_context, filter.knockout);
+            object.into()
+        }
+        Filter::GradientBevel(filter) => {
+            let object = GradientBevelFilterObject::empty_object(
+                gc_context,
+                Some(prototypes.gradient_bevel_filter),
+            );
+            object.set_colors(
+                gc_context,
+                filter.colors.iter().map(|r| r.color.to_rgb()).collect(),
+            );
+            object.set_alphas(
+                gc_context,
+                filter.colors.iter().map(|r| alpha(&r.color)).collect(),
+            );
+            object.set_ratios(gc_context, filter.colors.iter().map(|r| r.ratio).collect());
+            object.set_blur_x(gc_context, filter.blur_x.into());
+            object.set_blur_y(gc_context, filter.blur_y.into());
+            object.set_angle(gc_context, filter.angle.into());
+            object.set_distance(gc_context, filter.distance.into());
+            object.set_strength(gc_context, filter.strength.into());
+            object.set_quality(gc_context, filter.quality.into());
+            object.set_type(gc_context, bevel_type(filter.filter_type));
+            object.set_knockout(gc_context, filter.knockout);
+            object.into()
+        }
+        Filter::ColorMatrix(filter) => {
+            let object = ColorMatrixFilterObject::empty_object(
+                gc_context,
+                Some(prototypes.color_matrix_filter),
+            );
+            let mut matrix = [0.0; 20];
+            for (dst, src) in matrix.iter_mut().zip(filter.matrix.iter()) {
+                *dst = f64::from(*src);
+            }
+            object.set_matrix(gc_context, matrix);
+            object.into()
+        }
+        Filter::Convolution(filter) => {
+            let object = ConvolutionFilterObject::empty_object(
+                gc_context,
+                Some(prototypes.convolution_filter),
+            );
+            object.set_matrix_x(gc_context, filter.matrix_x);
+            object.set_matrix_y(gc_context, filter.matrix_y);
+            object.set_matrix(
+                gc_context,
+                filter.matrix.iter().map(|n| f64::from(*n)).collect(),
+            );
+            object.set_divisor(gc_context, filter.divisor.into());
+            object.set_bias(gc_context, filter.bias.into());
+            object.set_preserve_alpha(gc_context, filter.preserve_alpha);
+            object.set_clamp(gc_context, filter.clamp);
+            object.set_color(gc_context, filter.color.to_rgb());
+            object.set_alpha(gc_context, alpha(&filter.color));
+            object.into()
+        }
+        Filter::DisplacementMap(filter) => {
+            let object = DisplacementMapFilterObject::empty_object(
+                gc_context,
+                Some(prototypes.displacement_map_filter),
+            );
+            let map_bitmap = filter.map_bitmap.as_ref().map(|map| {
+                let bitmap_data =
+                    BitmapDataObject::empty_object(gc_context, Some(prototypes.bitmap_data));
+                let mut data = bitmap_data.bitmap_data().write(gc_context);
+                data.init_pixels(map.width, map.height, 0, true);
+                data.set_pixels(
+                    map.data
+                        .chunks_exact(4)
+                        .map(|p| {
+                            BitmapColor::argb(p[3], p[0], p[1], p[2]).to_premultiplied_alpha(true)
+                        })
+                        .collect(),
+                );
+                drop(data);
+                bitmap_data.into()
+            });
+            let mode = match filter.mode {
+                DisplacementMapMode::Wrap => DisplacementMapFilterMode::Wrap,
+                DisplacementMapMode::Clamp => DisplacementMapFilterMode::Clamp,
+                DisplacementMapMode::Ignore => DisplacementMapFilterMode::Ignore,
+                DisplacementMapMode::Color => DisplacementMapFilterMode::Color,
+            };
+            object.set_map_bitmap(gc_context, map_bitmap);
+            object.set_map_point(gc_context, filter.map_point);
+            object.set_component_x(gc_context, filter.component_x.into());
+            object.set_component_y(gc_context, filter.component_y.into());
+            object.set_scale_x(gc_context, filter.scale_x.into());
+            object.set_scale_y(gc_context, filter.scale_y.into());
+            object.set_mode(gc_context, mode);
+            object.set_color(gc_context, filter.color.to_rgb());
+            object.set_alpha(gc_context, alpha(&filter.color));
+            object.into()
+        }
+    }
+}
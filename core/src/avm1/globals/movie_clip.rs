@@ -3,8 +3,10 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::globals::bitmap_filter;
 use crate::avm1::globals::display_object::{self, AVM_DEPTH_BIAS, AVM_MAX_DEPTH};
 use crate::avm1::globals::matrix::gradient_object_to_matrix;
+use crate::avm1::globals::rectangle;
 use crate::avm1::property::Attribute;
 use crate::avm1::{AvmString, Object, ScriptObject, TObject, Value};
 use crate::avm_error;
@@ -21,8 +23,8 @@ use crate::vminterface::Instantiator;
 use gc_arena::MutationContext;
 use std::borrow::Cow;
 use swf::{
-    FillStyle, Gradient, GradientInterpolation, GradientRecord, GradientSpread, LineCapStyle,
-    LineJoinStyle, LineStyle, Twips,
+    BlendMode, FillStyle, Gradient, GradientInterpolation, GradientRecord, GradientSpread,
+    LineCapStyle, LineJoinStyle, LineStyle, Twips,
 };
 
 /// Implements `MovieClip`
@@ -219,6 +221,12 @@ pub fn create_proto<'gc>(
         "enabled" => [enabled, set_enabled],
         "focusEnabled" => [focus_enabled, set_focus_enabled],
         "_lockroot" => [lock_root, set_lock_root],
+        "filters" => [filters, set_filters],
+        "blendMode" => [blend_mode, set_blend_mode],
+        "cacheAsBitmap" => [cache_as_bitmap, set_cache_as_bitmap],
+        "opaqueBackground" => [opaque_background, set_opaque_background],
+        "scrollRect" => [scroll_rect, set_scroll_rect],
+        "scale9Grid" => [scale9_grid, set_scale9_grid],
     );
 
     object.into()
@@ -1318,3 +1326,148 @@ fn set_lock_root<'gc>(
     this.set_lock_root(activation.context.gc_context, lock_root);
     Ok(())
 }
+
+fn filters<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let array = ScriptObject::array(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes.array),
+    );
+    for (i, filter) in this.filters().iter().enumerate() {
+        let filter = bitmap_filter::filter_to_object(activation, filter);
+        array.set_array_element(i, filter.into(), activation.context.gc_context);
+    }
+    Ok(array.into())
+}
+
+fn set_filters<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let mut filters = vec![];
+    if let Value::Object(value) = value {
+        for filter in value.array() {
+            if let Value::Object(filter) = filter {
+                if let Some(filter) = bitmap_filter::object_to_filter(filter) {
+                    filters.push(filter);
+                }
+            }
+        }
+    }
+    this.set_filters(activation.context.gc_context, filters);
+    Ok(())
+}
+
+fn blend_mode<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(AvmString::new(activation.context.gc_context, this.blend_mode().to_string()).into())
+}
+
+fn set_blend_mode<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    // Blend modes can be set by name or by their numeric value; anything else is ignored.
+    let blend_mode = match value {
+        Value::String(name) => name.parse().ok(),
+        Value::Number(n) if (0.0..=14.0).contains(&n) => BlendMode::from_u8(n as u8),
+        _ => None,
+    };
+    if let Some(blend_mode) = blend_mode {
+        this.set_blend_mode(activation.context.gc_context, blend_mode);
+    }
+    Ok(())
+}
+
+fn cache_as_bitmap<'gc>(
+    this: MovieClip<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.cache_as_bitmap().into())
+}
+
+fn set_cache_as_bitmap<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let value = value.as_bool(activation.current_swf_version());
+    this.set_cache_as_bitmap(activation.context.gc_context, value);
+    Ok(())
+}
+
+fn opaque_background<'gc>(
+    this: MovieClip<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(match this.opaque_background() {
+        Some(color) => color.to_rgb().into(),
+        None => Value::Undefined,
+    })
+}
+
+fn set_opaque_background<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let color = match value {
+        Value::Undefined | Value::Null => None,
+        value => Some(Color::from_rgb(value.coerce_to_u32(activation)?, 255)),
+    };
+    this.set_opaque_background(activation.context.gc_context, color);
+    Ok(())
+}
+
+fn scroll_rect<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    match this.scroll_rect() {
+        Some(rect) => rectangle::bounding_box_to_object(&rect, activation),
+        None => Ok(Value::Undefined),
+    }
+}
+
+fn set_scroll_rect<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    // Any object with rectangle properties is accepted; anything else removes the scroll rect.
+    let rect = match value {
+        Value::Object(object) => Some(rectangle::object_to_bounding_box(object, activation)?),
+        _ => None,
+    };
+    this.set_scroll_rect(activation.context.gc_context, rect);
+    Ok(())
+}
+
+fn scale9_grid<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    match this.scaling_grid() {
+        Some(rect) => rectangle::bounding_box_to_object(&rect, activation),
+        None => Ok(Value::Undefined),
+    }
+}
+
+fn set_scale9_grid<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let rect = match value {
+        Value::Object(object) => Some(rectangle::object_to_bounding_box(object, activation)?),
+        _ => None,
+    };
+    this.set_scaling_grid(activation.context.gc_context, rect);
+    Ok(())
+}
@@ -6,8 +6,44 @@ use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::globals::point::{construct_new_point, point_to_object, value_to_point};
 use crate::avm1::property::Attribute;
 use crate::avm1::{AvmString, Object, ScriptObject, TObject, Value};
+use crate::bounding_box::BoundingBox;
 use gc_arena::MutationContext;
 use std::f64::NAN;
+use swf::Twips;
+
+pub fn bounding_box_to_object<'gc>(
+    rect: &BoundingBox,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let args = [
+        rect.x_min.to_pixels().into(),
+        rect.y_min.to_pixels().into(),
+        rect.width().to_pixels().into(),
+        rect.height().to_pixels().into(),
+    ];
+    let constructor = activation.context.avm1.prototypes.rectangle_constructor;
+    let object = constructor.construct(activation, &args)?;
+    Ok(object)
+}
+
+pub fn object_to_bounding_box<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<BoundingBox, Error<'gc>> {
+    let x = object.get("x", activation)?.coerce_to_f64(activation)?;
+    let y = object.get("y", activation)?.coerce_to_f64(activation)?;
+    let width = object.get("width", activation)?.coerce_to_f64(activation)?;
+    let height = object
+        .get("height", activation)?
+        .coerce_to_f64(activation)?;
+    Ok(BoundingBox {
+        x_min: Twips::from_pixels(x),
+        y_min: Twips::from_pixels(y),
+        x_max: Twips::from_pixels(x + width),
+        y_max: Twips::from_pixels(y + height),
+        valid: true,
+    })
+}
 
 fn constructor<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -7,9 +7,12 @@ use crate::avm_warn;
 use crate::display_object::TDisplayObject;
 use gc_arena::MutationContext;
 
+use crate::avm1::object::date_object::DateObject;
 use crate::avm1::object::shared_object::SharedObject;
 
+use chrono::{LocalResult, TimeZone, Utc};
 use json::JsonValue;
+use ruffle_amf::{AmfObject, AmfValue, Lso, ObjectEncoding};
 
 pub fn delete_all<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -29,126 +32,262 @@ pub fn get_disk_usage<'gc>(
     Ok(Value::Undefined)
 }
 
-/// Serialize an Object and any children to a JSON object
-/// It would be best if this was implemented via serde but due to avm and context it can't
-/// Undefined fields aren't serialized
-fn recursive_serialize<'gc>(
+/// Serialize the enumerable properties of an object to AMF.
+/// Properties that `filter` rejects, functions and undefined values aren't
+/// serialized.
+fn serialize_properties<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     obj: Object<'gc>,
-    json_obj: &mut JsonValue,
-) {
-    for k in &obj.get_keys(activation) {
-        if let Ok(elem) = obj.get(k, activation) {
-            match elem {
-                Value::Undefined => {}
-                Value::Null => json_obj[k] = JsonValue::Null,
-                Value::Bool(b) => json_obj[k] = b.into(),
-                Value::Number(f) => json_obj[k] = f.into(),
-                Value::String(s) => json_obj[k] = s.to_string().into(),
-                Value::Object(o) => {
-                    // Don't attempt to serialize functions
-                    let function = activation.context.avm1.prototypes.function;
-                    let array = activation.context.avm1.prototypes.array;
-                    if !o
-                        .is_instance_of(activation, o, function)
-                        .unwrap_or_default()
-                    {
-                        let mut sub_data_json = JsonValue::new_object();
-                        recursive_serialize(activation, o, &mut sub_data_json);
-                        if o.is_instance_of(activation, o, array).unwrap_or_default() {
-                            sub_data_json["__proto__"] = "Array".into();
-                            sub_data_json["length"] = o.length().into();
-                        }
-                        json_obj[k] = sub_data_json;
-                    }
-                }
-            }
+    objects: &mut Vec<Object<'gc>>,
+    filter: impl Fn(&str) -> bool,
+) -> Result<Vec<(String, AmfValue)>, Error<'gc>> {
+    let mut properties = Vec::new();
+    for k in obj.get_keys(activation) {
+        if !filter(&k) {
+            continue;
+        }
+
+        let elem = obj.get(&k, activation)?;
+        if elem == Value::Undefined {
+            continue;
+        }
+
+        if let Some(value) = serialize_value(activation, elem, objects)? {
+            properties.push((k, value));
         }
     }
+
+    Ok(properties)
 }
 
-fn recursive_deserialize<'gc>(
-    json_value: JsonValue,
+/// Serialize a value and any children to AMF.
+///
+/// `objects` holds every object and array serialized so far, in the order AMF
+/// numbers references in, so that repeated and cyclic objects are written as
+/// references. Functions can't be serialized, and return `None`.
+fn serialize_value<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-) -> Value<'gc> {
-    match json_value {
-        JsonValue::Null => Value::Null,
-        JsonValue::Short(s) => {
-            Value::String(AvmString::new(activation.context.gc_context, s.to_string()))
-        }
-        JsonValue::String(s) => Value::String(AvmString::new(activation.context.gc_context, s)),
-        JsonValue::Number(f) => Value::Number(f.into()),
-        JsonValue::Boolean(b) => Value::Bool(b),
-        JsonValue::Object(o) => {
-            if o.get("__proto__").and_then(JsonValue::as_str) == Some("Array") {
-                deserialize_array(o, activation)
-            } else {
-                deserialize_object(o, activation)
-            }
+    elem: Value<'gc>,
+    objects: &mut Vec<Object<'gc>>,
+) -> Result<Option<AmfValue>, Error<'gc>> {
+    let o = match elem {
+        Value::Undefined => return Ok(Some(AmfValue::Undefined)),
+        Value::Null => return Ok(Some(AmfValue::Null)),
+        Value::Bool(b) => return Ok(Some(AmfValue::Bool(b))),
+        Value::Number(f) => return Ok(Some(AmfValue::Number(f))),
+        Value::String(s) => return Ok(Some(AmfValue::String(s.to_string()))),
+        Value::Object(o) => o,
+    };
+
+    if let Some(index) = objects.iter().position(|obj| Object::ptr_eq(*obj, o)) {
+        return Ok(Some(AmfValue::Reference(index)));
+    }
+
+    // Don't attempt to serialize functions
+    let function = activation.context.avm1.prototypes.function;
+    if o.is_instance_of(activation, o, function)
+        .unwrap_or_default()
+    {
+        return Ok(None);
+    }
+
+    if let Some(date) = o.as_date_object() {
+        let time = date
+            .date_time()
+            .map(|date| date.timestamp_millis() as f64)
+            .unwrap_or(f64::NAN);
+        return Ok(Some(AmfValue::Date(time)));
+    }
+
+    if o.as_xml_node().is_some() {
+        let xml = Value::Object(o).coerce_to_string(activation)?;
+        return Ok(Some(AmfValue::XmlDocument(xml.to_string())));
+    }
+
+    // Only objects and arrays can be referred to, so dates and XML are
+    // written out again every time they appear.
+    objects.push(o);
+
+    let array = activation.context.avm1.prototypes.array;
+    if o.is_instance_of(activation, o, array).unwrap_or_default() {
+        // Named properties come before the elements, as that is the order
+        // references are numbered in.
+        let length = o.length();
+        let associative = serialize_properties(activation, o, objects, |k| {
+            k.parse::<usize>().map(|i| i >= length).unwrap_or(true)
+        })?;
+
+        let mut dense = Vec::with_capacity(length);
+        for i in 0..length {
+            let elem = o.array_element(i);
+            dense.push(serialize_value(activation, elem, objects)?.unwrap_or(AmfValue::Undefined));
         }
-        JsonValue::Array(_) => Value::Undefined,
+
+        return Ok(Some(AmfValue::Array { dense, associative }));
     }
+
+    let properties = serialize_properties(activation, o, objects, |_| true)?;
+    Ok(Some(AmfValue::Object(AmfObject::anonymous(properties))))
 }
 
-/// Deserialize an Object and any children from a JSON object
-/// It would be best if this was implemented via serde but due to avm and context it can't
-/// Undefined fields aren't deserialized
-fn deserialize_object<'gc>(
-    json_obj: json::object::Object,
+/// Deserialize a value and any children from AMF.
+///
+/// `objects` holds every object created so far for a value that can be
+/// referred to, in the order AMF numbers references in. Typed objects are read as plain objects, and values AVM1
+/// has no equivalent for, such as byte arrays, as `undefined`.
+fn deserialize_value<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-) -> Value<'gc> {
-    // Deserialize Object
-    let obj_proto = activation.context.avm1.prototypes.object;
-    if let Ok(obj) = obj_proto.create_bare_object(activation, obj_proto) {
-        for entry in json_obj.iter() {
-            let value = recursive_deserialize(entry.1.clone(), activation);
-            obj.define_value(
-                activation.context.gc_context,
-                entry.0,
-                value,
-                Attribute::empty(),
-            );
+    value: AmfValue,
+    objects: &mut Vec<Object<'gc>>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let gc_context = activation.context.gc_context;
+    Ok(match value {
+        AmfValue::Undefined => Value::Undefined,
+        AmfValue::Null => Value::Null,
+        AmfValue::Bool(b) => Value::Bool(b),
+        AmfValue::Integer(i) => Value::Number(i.into()),
+        AmfValue::Number(f) => Value::Number(f),
+        AmfValue::String(s) => Value::String(AvmString::new(gc_context, s)),
+        AmfValue::Reference(index) => objects
+            .get(index)
+            .copied()
+            .map(Value::Object)
+            .unwrap_or(Value::Undefined),
+        AmfValue::Date(time) => {
+            let date_time = match Utc.timestamp_millis_opt(time as i64) {
+                LocalResult::Single(date_time) if time.is_finite() => Some(date_time),
+                _ => None,
+            };
+            let date_proto = activation.context.avm1.prototypes.date;
+            let date: Object<'gc> =
+                DateObject::with_date_time(gc_context, Some(date_proto), date_time).into();
+
+            date.into()
         }
-        obj.into()
-    } else {
-        Value::Undefined
-    }
+        AmfValue::XmlDocument(xml) | AmfValue::Xml(xml) => {
+            let xml_constructor = activation.context.avm1.prototypes.xml_constructor;
+            let xml = AvmString::new(gc_context, xml);
+            let object = xml_constructor
+                .construct(activation, &[xml.into()])?
+                .coerce_to_object(activation);
+
+            object.into()
+        }
+        AmfValue::Array { dense, associative } => {
+            let array_constructor = activation.context.avm1.prototypes.array_constructor;
+            let object = array_constructor
+                .construct(activation, &[])?
+                .coerce_to_object(activation);
+            objects.push(object);
+
+            deserialize_properties(activation, object, associative, objects)?;
+            for (i, elem) in dense.into_iter().enumerate() {
+                let value = deserialize_value(activation, elem, objects)?;
+                object.set_array_element(i, value, activation.context.gc_context);
+            }
+
+            object.into()
+        }
+        AmfValue::Object(amf_object) => {
+            let obj_proto = activation.context.avm1.prototypes.object;
+            let object = obj_proto.create_bare_object(activation, obj_proto)?;
+            objects.push(object);
+
+            deserialize_properties(activation, object, amf_object.into_properties(), objects)?;
+
+            object.into()
+        }
+        AmfValue::Externalizable { .. } | AmfValue::ByteArray(_) => {
+            // Keep the reference numbering in step.
+            let obj_proto = activation.context.avm1.prototypes.object;
+            objects.push(obj_proto.create_bare_object(activation, obj_proto)?);
+
+            Value::Undefined
+        }
+    })
 }
 
-/// Deserialize an Object and any children from a JSON object
-/// It would be best if this was implemented via serde but due to avm and context it can't
-/// Undefined fields aren't deserialized
-fn deserialize_array<'gc>(
-    mut json_obj: json::object::Object,
+/// Deserialize named properties from AMF onto an object.
+fn deserialize_properties<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-) -> Value<'gc> {
-    let array_constructor = activation.context.avm1.prototypes.array_constructor;
-    let len = json_obj
-        .get("length")
-        .and_then(JsonValue::as_i32)
-        .unwrap_or_default();
-    if let Ok(Value::Object(obj)) = array_constructor.construct(activation, &[len.into()]) {
-        // Remove length and proto meta-properties.
-        json_obj.remove("length");
-        json_obj.remove("__proto__");
-
-        for entry in json_obj.iter() {
-            let value = recursive_deserialize(entry.1.clone(), activation);
-            if let Ok(i) = entry.0.parse::<i32>() {
-                obj.set_array_element(i as usize, value, activation.context.gc_context);
+    object: Object<'gc>,
+    properties: Vec<(String, AmfValue)>,
+    objects: &mut Vec<Object<'gc>>,
+) -> Result<(), Error<'gc>> {
+    for (name, value) in properties {
+        let value = deserialize_value(activation, value, objects)?;
+        object.define_value(
+            activation.context.gc_context,
+            &name,
+            value,
+            Attribute::empty(),
+        );
+    }
+
+    Ok(())
+}
+
+/// The longest array that is read from a shared object saved as JSON.
+const MAX_MIGRATED_ARRAY_LENGTH: usize = 0x10_0000;
+
+/// Convert a shared object saved as JSON, as older versions of Ruffle did, to
+/// AMF.
+///
+/// Arrays were saved as objects with a `__proto__` of `"Array"` and a
+/// `length`, and undefined values weren't saved.
+fn migrate_json(json_value: JsonValue) -> AmfValue {
+    match json_value {
+        JsonValue::Null => AmfValue::Null,
+        JsonValue::Short(s) => AmfValue::String(s.to_string()),
+        JsonValue::String(s) => AmfValue::String(s),
+        JsonValue::Number(f) => AmfValue::Number(f.into()),
+        JsonValue::Boolean(b) => AmfValue::Bool(b),
+        JsonValue::Object(o) => {
+            if o.get("__proto__").and_then(JsonValue::as_str) == Some("Array") {
+                // Undefined elements weren't saved, so the saved length is
+                // kept even if it's past the last saved element. It's capped
+                // so that a corrupt length can't exhaust memory, and any
+                // elements past it are kept as named properties.
+                let length = o
+                    .get("length")
+                    .and_then(JsonValue::as_usize)
+                    .unwrap_or_default()
+                    .min(MAX_MIGRATED_ARRAY_LENGTH);
+                let mut dense = vec![AmfValue::Undefined; length];
+                let mut associative = Vec::new();
+                for (k, v) in o.iter() {
+                    if k == "__proto__" || k == "length" {
+                        continue;
+                    }
+                    match k.parse::<usize>() {
+                        Ok(i) if i < length => dense[i] = migrate_json(v.clone()),
+                        _ => associative.push((k.to_string(), migrate_json(v.clone()))),
+                    }
+                }
+                AmfValue::Array { dense, associative }
             } else {
-                obj.define_value(
-                    activation.context.gc_context,
-                    entry.0,
-                    value,
-                    Attribute::empty(),
-                );
+                let properties = o
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), migrate_json(v.clone())))
+                    .collect();
+                AmfValue::Object(AmfObject::anonymous(properties))
             }
         }
+        JsonValue::Array(_) => AmfValue::Undefined,
+    }
+}
 
-        obj.into()
-    } else {
-        Value::Undefined
+/// Read the properties of a saved shared object's `data`, whether it was saved
+/// as a `.sol` file or as JSON.
+fn read_saved_data(saved: &[u8]) -> Option<Vec<(String, AmfValue)>> {
+    if let Ok(lso) = ruffle_amf::read_lso(saved) {
+        return Some(lso.body);
+    }
+
+    let json_data = json::parse(std::str::from_utf8(saved).ok()?).ok()?;
+    match migrate_json(json_data) {
+        AmfValue::Object(amf_object) => Some(amf_object.into_properties()),
+        _ => None,
     }
 }
 
@@ -263,25 +402,19 @@ pub fn get_local<'gc>(
     let obj_so = this.as_shared_object().unwrap();
     obj_so.set_name(activation.context.gc_context, full_name.clone());
 
+    // Create the data object, and load its properties from storage if it existed prior
     let prototype = activation.context.avm1.prototypes.object;
-    let mut data = Value::Undefined;
-
-    // Load the data object from storage if it existed prior
-    if let Some(saved) = activation.context.storage.get_string(&full_name) {
-        if let Ok(json_data) = json::parse(&saved) {
-            data = recursive_deserialize(json_data, activation);
+    let data = prototype.create_bare_object(activation, prototype)?;
+    if let Some(saved) = activation.context.storage.get(&full_name) {
+        if let Some(properties) = read_saved_data(&saved) {
+            deserialize_properties(activation, data, properties, &mut Vec::new())?;
         }
     }
 
-    if data == Value::Undefined {
-        // No data; create a fresh data object.
-        data = prototype.create_bare_object(activation, prototype)?.into();
-    }
-
     this.define_value(
         activation.context.gc_context,
         "data",
-        data,
+        data.into(),
         Attribute::empty(),
     );
 
@@ -443,16 +576,22 @@ pub fn flush<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let data = this.get("data", activation)?.coerce_to_object(activation);
 
-    let mut data_json = JsonValue::new_object();
-    recursive_serialize(activation, data, &mut data_json);
+    let body = serialize_properties(activation, data, &mut Vec::new(), |_| true)?;
 
     let this_obj = this.as_shared_object().unwrap();
     let name = this_obj.get_name();
 
+    // The `.sol` file only records the last part of the name.
+    let lso = Lso {
+        name: name.rsplit('/').next().unwrap_or_default().to_string(),
+        encoding: ObjectEncoding::Amf0,
+        body,
+    };
+
     Ok(activation
         .context
         .storage
-        .put_string(&name, data_json.dump())
+        .put(&name, &ruffle_amf::write_lso(&lso))
         .into())
 }
 
@@ -585,3 +724,82 @@ pub fn constructor<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     Ok(this.into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::test_utils::with_avm;
+
+    #[test]
+    fn round_trip_references() {
+        with_avm(19, |activation, _this| -> Result<(), Error> {
+            let gc_context = activation.context.gc_context;
+            let object_proto = activation.context.avm1.prototypes.object;
+            let date_proto = activation.context.avm1.prototypes.date;
+
+            let date_time = Utc.timestamp_millis_opt(1_000_000).single();
+            let date: Object<'_> =
+                DateObject::with_date_time(gc_context, Some(date_proto), date_time).into();
+            let obj = object_proto.create_bare_object(activation, object_proto)?;
+            obj.define_value(gc_context, "x", 1.into(), Attribute::empty());
+            let data = object_proto.create_bare_object(activation, object_proto)?;
+            data.define_value(gc_context, "d", date.into(), Attribute::empty());
+            data.define_value(gc_context, "o", obj.into(), Attribute::empty());
+            data.define_value(gc_context, "o2", obj.into(), Attribute::empty());
+
+            let lso = Lso {
+                name: "test".to_string(),
+                encoding: ObjectEncoding::Amf0,
+                body: serialize_properties(activation, data, &mut Vec::new(), |_| true)?,
+            };
+            let saved = ruffle_amf::write_lso(&lso);
+            let properties = read_saved_data(&saved).expect("saved data should be readable");
+
+            let loaded = object_proto.create_bare_object(activation, object_proto)?;
+            deserialize_properties(activation, loaded, properties, &mut Vec::new())?;
+
+            let d = loaded.get("d", activation)?.coerce_to_object(activation);
+            assert_eq!(
+                d.as_date_object().and_then(|date| date.date_time()),
+                date_time
+            );
+            let o = loaded.get("o", activation)?.coerce_to_object(activation);
+            let o2 = loaded.get("o2", activation)?.coerce_to_object(activation);
+            assert!(Object::ptr_eq(o, o2));
+            assert_eq!(o.get("x", activation)?, Value::Number(1.0));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn migrate_json_arrays() {
+        // Undefined elements weren't saved, so the length must be kept.
+        let sparse = json::parse(r#"{"__proto__": "Array", "length": 5, "0": 1, "4": "a"}"#);
+        assert_eq!(
+            migrate_json(sparse.unwrap()),
+            AmfValue::Array {
+                dense: vec![
+                    AmfValue::Number(1.0),
+                    AmfValue::Undefined,
+                    AmfValue::Undefined,
+                    AmfValue::Undefined,
+                    AmfValue::String("a".to_string()),
+                ],
+                associative: vec![],
+            }
+        );
+
+        let huge = json::parse(r#"{"__proto__": "Array", "length": 4294967295, "4294967294": 1}"#);
+        match migrate_json(huge.unwrap()) {
+            AmfValue::Array { dense, associative } => {
+                assert_eq!(dense.len(), MAX_MIGRATED_ARRAY_LENGTH);
+                assert_eq!(
+                    associative,
+                    vec![("4294967294".to_string(), AmfValue::Number(1.0))]
+                );
+            }
+            value => panic!("Expected an array, got {:?}", value),
+        }
+    }
+}
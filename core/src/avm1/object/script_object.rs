@@ -833,7 +833,7 @@ mod tests {
     use crate::avm1::function::Executable;
     use crate::avm1::globals::system::SystemProperties;
     use crate::avm1::property::Attribute;
-    use crate::avm1::{Avm1, Timers};
+    use crate::avm1::Avm1;
     use crate::avm2::Avm2;
     use crate::backend::audio::{AudioManager, NullAudioBackend};
     use crate::backend::locale::NullLocaleBackend;
@@ -849,6 +849,7 @@ mod tests {
     use crate::loader::LoadManager;
     use crate::prelude::*;
     use crate::tag_utils::{SwfMovie, SwfSlice};
+    use crate::timer::Timers;
     use crate::vminterface::Instantiator;
     use gc_arena::rootless_arena;
     use instant::Instant;
@@ -891,6 +892,7 @@ mod tests {
                 locale: &mut NullLocaleBackend::new(),
                 log: &mut NullLogBackend::new(),
                 mouse_hovered_object: None,
+                mouse_pressed_object: None,
                 mouse_position: &(Twips::new(0), Twips::new(0)),
                 drag_object: &mut None,
                 stage_size: (Twips::from_pixels(550.0), Twips::from_pixels(400.0)),
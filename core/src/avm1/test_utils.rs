@@ -1,7 +1,7 @@
 use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::error::Error;
 use crate::avm1::globals::system::SystemProperties;
-use crate::avm1::{Avm1, Object, Timers, UpdateContext};
+use crate::avm1::{Avm1, Object, UpdateContext};
 use crate::avm2::Avm2;
 use crate::backend::audio::{AudioManager, NullAudioBackend};
 use crate::backend::locale::NullLocaleBackend;
@@ -17,6 +17,7 @@ use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::prelude::*;
 use crate::tag_utils::{SwfMovie, SwfSlice};
+use crate::timer::Timers;
 use crate::vminterface::Instantiator;
 use gc_arena::{rootless_arena, MutationContext};
 use instant::Instant;
@@ -60,6 +61,7 @@ where
             locale: &mut NullLocaleBackend::new(),
             log: &mut NullLogBackend::new(),
             mouse_hovered_object: None,
+            mouse_pressed_object: None,
             mouse_position: &(Twips::new(0), Twips::new(0)),
             drag_object: &mut None,
             stage_size: (Twips::from_pixels(550.0), Twips::from_pixels(400.0)),
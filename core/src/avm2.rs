@@ -1,11 +1,16 @@
 //! ActionScript Virtual Machine 2 (AS3) support
 
+use crate::avm2::call_stack::CallStack;
+use crate::avm2::e4x::E4XSettings;
 use crate::avm2::globals::SystemPrototypes;
 use crate::avm2::method::Method;
+use crate::avm2::object::EventObject;
 use crate::avm2::script::{Script, TranslationUnit};
+use crate::avm2::string::AvmString;
 use crate::context::UpdateContext;
 use crate::tag_utils::SwfSlice;
 use gc_arena::{Collect, MutationContext};
+use std::collections::HashMap;
 use std::rc::Rc;
 use swf::avm2::read::Reader;
 
@@ -20,8 +25,12 @@ macro_rules! avm_debug {
 
 mod activation;
 mod array;
+mod bytearray;
+mod call_stack;
 mod class;
 mod domain;
+mod e4x;
+mod error;
 mod events;
 mod function;
 mod globals;
@@ -30,6 +39,7 @@ mod names;
 mod object;
 mod property;
 mod property_map;
+mod regexp;
 mod return_value;
 mod scope;
 mod script;
@@ -37,18 +47,20 @@ mod slot;
 mod string;
 mod traits;
 mod value;
+mod vector;
+mod verify;
 
 pub use crate::avm2::activation::Activation;
 pub use crate::avm2::domain::Domain;
+pub use crate::avm2::error::{Error, ErrorKind};
+pub use crate::avm2::events::{Event, EventData};
 pub use crate::avm2::names::{Namespace, QName};
-pub use crate::avm2::object::{Object, StageObject, TObject};
+pub use crate::avm2::object::{LoaderInfoObject, Object, StageObject, TObject};
 pub use crate::avm2::value::Value;
 
-/// Boxed error alias.
-///
-/// As AVM2 is a far stricter VM than AVM1, this may eventually be replaced
-/// with a proper Avm2Error enum.
-pub type Error = Box<dyn std::error::Error>;
+/// Events that are broadcast to every display object listening for them,
+/// rather than being dispatched to a single target.
+const BROADCAST_WHITELIST: [&str; 3] = ["enterFrame", "exitFrame", "frameConstructed"];
 
 /// The state of an AVM2 interpreter.
 #[derive(Collect)]
@@ -63,6 +75,24 @@ pub struct Avm2<'gc> {
     /// System prototypes.
     system_prototypes: Option<SystemPrototypes<'gc>>,
 
+    /// The specializations of `Vector` created so far, and the value types
+    /// they were created for.
+    vector_classes: Vec<(Option<Object<'gc>>, Object<'gc>)>,
+
+    /// The parsing and serialization settings shared by all XML objects.
+    xml_settings: E4XSettings,
+
+    /// Objects listening for broadcast events, keyed by event type.
+    broadcast_list: HashMap<AvmString<'gc>, Vec<Object<'gc>>>,
+
+    /// The bytecode methods currently running.
+    call_stack: CallStack<'gc>,
+
+    /// The call stack at the point that the error currently unwinding it
+    /// left its first method, for reporting the error if script doesn't
+    /// catch it.
+    error_trace: Option<String>,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
@@ -76,13 +106,18 @@ impl<'gc> Avm2<'gc> {
             stack: Vec::new(),
             globals,
             system_prototypes: None,
+            vector_classes: Vec::new(),
+            xml_settings: Default::default(),
+            broadcast_list: HashMap::new(),
+            call_stack: CallStack::default(),
+            error_trace: None,
 
             #[cfg(feature = "avm_debug")]
             debug_output: false,
         }
     }
 
-    pub fn load_player_globals(context: &mut UpdateContext<'_, 'gc, '_>) -> Result<(), Error> {
+    pub fn load_player_globals(context: &mut UpdateContext<'_, 'gc, '_>) -> Result<(), Error<'gc>> {
         let globals = context.avm2.globals;
         let mut activation = Activation::from_nothing(context.reborrow());
         globals::load_player_globals(&mut activation, globals)
@@ -99,7 +134,7 @@ impl<'gc> Avm2<'gc> {
     pub fn run_script_initializer(
         script: Script<'gc>,
         context: &mut UpdateContext<'_, 'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         let mut init_activation = Activation::from_script(context.reborrow(), script)?;
 
         let (method, scope) = script.init();
@@ -120,7 +155,7 @@ impl<'gc> Avm2<'gc> {
         reciever: Option<Object<'gc>>,
         args: &[Value<'gc>],
         context: &mut UpdateContext<'_, 'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         let mut evt_activation = Activation::from_nothing(context.reborrow());
         callable.call(
             reciever,
@@ -132,6 +167,93 @@ impl<'gc> Avm2<'gc> {
         Ok(())
     }
 
+    /// Dispatch an event on an object.
+    ///
+    /// The event will be dispatched through the target's ancestors as usual.
+    /// Returns `false` if the event was cancelled.
+    pub fn dispatch_event(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event: Event<'gc>,
+        target: Object<'gc>,
+    ) -> Result<bool, Error<'gc>> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+        let prototypes = activation.avm2().prototypes();
+        let event_proto = match event.event_data() {
+            EventData::Empty => prototypes.event,
+            EventData::Mouse { .. } => prototypes.mouseevent,
+            EventData::Keyboard { .. } => prototypes.keyboardevent,
+            EventData::Focus { .. } => prototypes.focusevent,
+            EventData::Progress { .. } => prototypes.progressevent,
+            EventData::IOError { .. } => prototypes.ioerrorevent,
+        };
+        let event_object =
+            EventObject::from_event(activation.context.gc_context, Some(event_proto), event);
+
+        globals::dispatch_event(&mut activation, Some(target), &[event_object.into()])
+            .map(|was_not_cancelled| was_not_cancelled.coerce_to_boolean())
+    }
+
+    /// Add an object to the broadcast list for an event.
+    ///
+    /// Events not on the broadcast whitelist are ignored. An object is only
+    /// added to a given event's list once.
+    pub fn register_broadcast_listener(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: Object<'gc>,
+        event_name: AvmString<'gc>,
+    ) {
+        if !BROADCAST_WHITELIST.contains(&event_name.as_str()) {
+            return;
+        }
+
+        let bucket = context
+            .avm2
+            .broadcast_list
+            .entry(event_name)
+            .or_insert_with(Vec::new);
+
+        if bucket.iter().any(|x| Object::ptr_eq(*x, object)) {
+            return;
+        }
+
+        bucket.push(object);
+    }
+
+    /// Remove an object from the broadcast list for an event.
+    pub fn unregister_broadcast_listener(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: Object<'gc>,
+        event_name: AvmString<'gc>,
+    ) {
+        if let Some(bucket) = context.avm2.broadcast_list.get_mut(&event_name) {
+            bucket.retain(|x| !Object::ptr_eq(*x, object));
+        }
+    }
+
+    /// Dispatch an event on every object registered to receive it.
+    ///
+    /// Errors thrown by one listener are logged and do not prevent the event
+    /// from reaching the rest.
+    pub fn broadcast_event(context: &mut UpdateContext<'_, 'gc, '_>, event: Event<'gc>) {
+        let event_name = event.event_type();
+        if !BROADCAST_WHITELIST.contains(&event_name.as_str()) {
+            return;
+        }
+
+        let targets = context
+            .avm2
+            .broadcast_list
+            .get(&event_name)
+            .cloned()
+            .unwrap_or_default();
+
+        for target in targets {
+            if let Err(e) = Avm2::dispatch_event(context, event.clone(), target) {
+                log::error!("Unhandled AVM2 exception in event handler: {}", e);
+            }
+        }
+    }
+
     /// Load an ABC file embedded in a `SwfSlice`.
     ///
     /// The `SwfSlice` must resolve to the contents of an ABC file.
@@ -141,7 +263,7 @@ impl<'gc> Avm2<'gc> {
         lazy_init: bool,
         context: &mut UpdateContext<'_, 'gc, '_>,
         domain: Domain<'gc>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         let mut read = Reader::new(abc.as_ref());
 
         let abc_file = Rc::new(read.read()?);
@@ -182,6 +304,17 @@ impl<'gc> Avm2<'gc> {
         value
     }
 
+    /// Retrieve a value on the operand stack without removing it, counting
+    /// down from the top-most value at zero.
+    fn peek(&self, index: usize) -> Value<'gc> {
+        self.stack
+            .len()
+            .checked_sub(index + 1)
+            .and_then(|index| self.stack.get(index))
+            .cloned()
+            .unwrap_or(Value::Undefined)
+    }
+
     fn pop_args(&mut self, arg_count: u32) -> Vec<Value<'gc>> {
         let mut args = Vec::with_capacity(arg_count as usize);
         args.resize(arg_count as usize, Value::Undefined);
@@ -2,24 +2,29 @@
 
 use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
+use crate::avm2::e4x::{escape_attribute, escape_text, list_xml_string};
+use crate::avm2::globals::{
+    apply_type, e4x_delete_property, e4x_descendants, e4x_get_property, e4x_set_property,
+    new_xml_list, xml_nodes,
+};
 use crate::avm2::method::BytecodeMethod;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Multiname, Namespace, QName};
-use crate::avm2::object::{ArrayObject, FunctionObject, NamespaceObject, ScriptObject};
+use crate::avm2::object::{
+    ArrayObject, DictionaryObject, FunctionObject, NamespaceObject, ScriptObject,
+};
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::scope::Scope;
 use crate::avm2::script::Script;
 use crate::avm2::string::AvmString;
 use crate::avm2::value::Value;
-use crate::avm2::{value, Avm2, Error};
+use crate::avm2::{value, Avm2, Error, ErrorKind};
 use crate::context::UpdateContext;
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
 use smallvec::SmallVec;
-use std::io::Cursor;
-use swf::avm2::read::Reader;
 use swf::avm2::types::{
-    Class as AbcClass, Index, Method as AbcMethod, Multiname as AbcMultiname,
-    Namespace as AbcNamespace, Op,
+    Class as AbcClass, Exception as AbcException, Index, Method as AbcMethod,
+    Multiname as AbcMultiname, Namespace as AbcNamespace, Op,
 };
 
 /// Represents a particular register set.
@@ -102,6 +107,11 @@ pub struct Activation<'a, 'gc: 'a, 'gc_context: 'a> {
     /// This will not be available if this is not a method call.
     base_proto: Option<Object<'gc>>,
 
+    /// The default XML namespace, as set by `dxns` or `dxnslate`.
+    ///
+    /// A `default_xml_namespace` of `None` indicates the unnamed namespace.
+    default_xml_namespace: Option<AvmString<'gc>>,
+
     pub context: UpdateContext<'a, 'gc, 'gc_context>,
 }
 
@@ -126,6 +136,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope: None,
             base_proto: None,
+            default_xml_namespace: None,
             context,
         }
     }
@@ -135,14 +146,14 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     pub fn from_script(
         context: UpdateContext<'a, 'gc, 'gc_context>,
         script: Script<'gc>,
-    ) -> Result<Self, Error> {
+    ) -> Result<Self, Error<'gc>> {
         let (method, script_scope) = script.init();
         let scope = Some(Scope::push_scope(None, script_scope, context.gc_context));
 
         let num_locals = match method {
             Method::Native(_nm) => 0,
             Method::Entry(bytecode) => {
-                let body: Result<_, Error> = bytecode.body().ok_or_else(|| {
+                let body: Result<_, Error<'gc>> = bytecode.body().ok_or_else(|| {
                     "Cannot execute non-native method (for script) without body".into()
                 });
                 body?.num_locals
@@ -165,6 +176,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope,
             base_proto: None,
+            default_xml_namespace: None,
             context,
         })
     }
@@ -179,8 +191,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         arguments: &[Value<'gc>],
         base_proto: Option<Object<'gc>>,
         callee: Object<'gc>,
-    ) -> Result<Self, Error> {
-        let body: Result<_, Error> = method
+    ) -> Result<Self, Error<'gc>> {
+        let body: Result<_, Error<'gc>> = method
             .body()
             .ok_or_else(|| "Cannot execute non-native method without body".into());
         let num_locals = body?.num_locals;
@@ -195,13 +207,6 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         {
             let mut write = local_registers.write(context.gc_context);
             *write.get_mut(0).unwrap() = this.map(|t| t.into()).unwrap_or(Value::Null);
-
-            for i in 0..num_declared_arguments {
-                *write.get_mut(1 + i).unwrap() = arguments
-                    .get(i as usize)
-                    .cloned()
-                    .unwrap_or(Value::Undefined);
-            }
         }
 
         let mut activation = Self {
@@ -213,9 +218,40 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope,
             base_proto,
+            default_xml_namespace: None,
             context,
         };
 
+        // Declared parameters are coerced to their types, which are resolved
+        // in the scope the method was defined in.
+        for (i, param) in method.method().params.iter().enumerate() {
+            let arg = match (arguments.get(i), &param.default_value) {
+                (Some(arg), _) => arg.clone(),
+                (None, Some(default)) => value::abc_default_value(
+                    method.translation_unit(),
+                    default,
+                    activation.context.avm2,
+                    activation.context.gc_context,
+                )?,
+                (None, None) => Value::Undefined,
+            };
+            let param_type = if param.kind.0 == 0 {
+                Multiname::any()
+            } else {
+                Multiname::from_abc_multiname_static(
+                    method.translation_unit(),
+                    param.kind.clone(),
+                    activation.context.gc_context,
+                )?
+            };
+            let arg = activation.coerce_to_declared_type(arg, &param_type, scope)?;
+
+            *local_registers
+                .write(activation.context.gc_context)
+                .get_mut(1 + i as u32)
+                .unwrap() = arg;
+        }
+
         if has_rest_or_args {
             let args_array = if method.method().needs_arguments_object {
                 ArrayStorage::from_args(arguments)
@@ -274,7 +310,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         scope: Option<GcCell<'gc, Scope<'gc>>>,
         this: Option<Object<'gc>>,
         base_proto: Option<Object<'gc>>,
-    ) -> Result<Self, Error> {
+    ) -> Result<Self, Error<'gc>> {
         let local_registers = GcCell::allocate(context.gc_context, RegisterSet::new(0));
 
         Ok(Self {
@@ -286,12 +322,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope,
             base_proto,
+            default_xml_namespace: None,
             context,
         })
     }
 
     /// Execute a script initializer.
-    pub fn run_stack_frame_for_script(&mut self, script: Script<'gc>) -> Result<(), Error> {
+    pub fn run_stack_frame_for_script(&mut self, script: Script<'gc>) -> Result<(), Error<'gc>> {
         let init = script.init().0.into_bytecode()?;
 
         self.run_actions(init)?;
@@ -321,9 +358,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         receiver: Object<'gc>,
         args: &[Value<'gc>],
-    ) -> Result<Value<'gc>, Error> {
+    ) -> Result<Value<'gc>, Error<'gc>> {
         let name = QName::new(Namespace::public(), "constructor");
-        let base_proto: Result<Object<'gc>, Error> =
+        let base_proto: Result<Object<'gc>, Error<'gc>> =
             self.base_proto().and_then(|p| p.proto()).ok_or_else(|| {
                 "Attempted to call super constructor without a superclass."
                     .to_string()
@@ -341,7 +378,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     /// Attempts to lock the activation frame for execution.
     ///
     /// If this frame is already executing, that is an error condition.
-    pub fn lock(&mut self) -> Result<(), Error> {
+    pub fn lock(&mut self) -> Result<(), Error<'gc>> {
         if self.is_executing {
             return Err("Attempted to execute the same frame twice".into());
         }
@@ -358,7 +395,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     /// Retrieve a local register.
-    pub fn local_register(&self, id: u32) -> Result<Value<'gc>, Error> {
+    pub fn local_register(&self, id: u32) -> Result<Value<'gc>, Error<'gc>> {
         self.local_registers
             .read()
             .get(id)
@@ -376,6 +413,64 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         self.scope = new_scope;
     }
 
+    /// Resolve a type name to the class it refers to, in a given scope.
+    ///
+    /// Returns `None` for the any type. Types that can't be resolved are also
+    /// treated as the any type, as many of the player globals that scripts
+    /// declare types with don't exist yet. Parameterized types, such as
+    /// `Vector.<int>`, resolve to their specialization.
+    pub fn resolve_type(
+        &mut self,
+        type_name: &Multiname<'gc>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Option<Object<'gc>>, Error<'gc>> {
+        if type_name.local_name().is_none() {
+            return Ok(None);
+        }
+
+        let type_object = if let Some(scope) = scope {
+            scope
+                .write(self.context.gc_context)
+                .resolve(type_name, self)?
+        } else {
+            None
+        };
+
+        match type_object {
+            Some(Value::Object(type_object)) if type_name.params().is_empty() => {
+                Ok(Some(type_object))
+            }
+            Some(Value::Object(type_object)) => {
+                let mut params = Vec::with_capacity(type_name.params().len());
+                for param in type_name.params() {
+                    params.push(match self.resolve_type(param, scope)? {
+                        Some(param) => param.into(),
+                        None => Value::Null,
+                    });
+                }
+
+                Ok(Some(apply_type(self, type_object, &params)?))
+            }
+            _ => {
+                avm_debug!(self.avm2(), "Could not resolve type {:?}", type_name);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Coerce a value to the declared type of a parameter or slot.
+    pub fn coerce_to_declared_type(
+        &mut self,
+        value: Value<'gc>,
+        type_name: &Multiname<'gc>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        match self.resolve_type(type_name, scope)? {
+            Some(type_object) => value.coerce_to_type(self, type_object),
+            None => Ok(value),
+        }
+    }
+
     /// Set a local register.
     ///
     /// Returns `true` if the set was successful; `false` otherwise
@@ -384,7 +479,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         id: u32,
         value: impl Into<Value<'gc>>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         if let Some(r) = self.local_registers.write(mc).get_mut(id) {
             *r = value.into();
 
@@ -409,12 +504,17 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         self.base_proto
     }
 
+    /// Get the default XML namespace, if one has been set.
+    pub fn default_xml_namespace(&self) -> Option<AvmString<'gc>> {
+        self.default_xml_namespace
+    }
+
     /// Retrieve a int from the current constant pool.
     fn pool_int(
         &self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<i32>,
-    ) -> Result<i32, Error> {
+    ) -> Result<i32, Error<'gc>> {
         value::abc_int(method.translation_unit(), index)
     }
 
@@ -423,7 +523,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<u32>,
-    ) -> Result<u32, Error> {
+    ) -> Result<u32, Error<'gc>> {
         value::abc_uint(method.translation_unit(), index)
     }
 
@@ -432,7 +532,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<f64>,
-    ) -> Result<f64, Error> {
+    ) -> Result<f64, Error<'gc>> {
         value::abc_double(method.translation_unit(), index)
     }
 
@@ -442,7 +542,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: &'b BytecodeMethod<'gc>,
         index: Index<String>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<AvmString<'gc>, Error> {
+    ) -> Result<AvmString<'gc>, Error<'gc>> {
         method.translation_unit().pool_string(index.0, mc)
     }
 
@@ -452,7 +552,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcNamespace>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Namespace<'gc>, Error> {
+    ) -> Result<Namespace<'gc>, Error<'gc>> {
         Namespace::from_abc_namespace(method.translation_unit(), index, mc)
     }
 
@@ -461,10 +561,48 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<Multiname<'gc>, Error> {
+    ) -> Result<Multiname<'gc>, Error<'gc>> {
         Multiname::from_abc_multiname(method.translation_unit(), index, self)
     }
 
+    /// Pop the object and key of a late-bound property access, if the object
+    /// is a dictionary and the key is an object.
+    ///
+    /// Dictionaries key such properties by identity rather than by name, so
+    /// they can't be accessed through a `Multiname` like other properties.
+    /// Any other access is left on the stack to be resolved as usual.
+    fn pop_dictionary_key(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: &Index<AbcMultiname>,
+    ) -> Option<(DictionaryObject<'gc>, Object<'gc>)> {
+        let abc = method.translation_unit().abc();
+        let abc_multiname = index
+            .0
+            .checked_sub(1)
+            .and_then(|index| abc.constant_pool.multinames.get(index as usize));
+        if !matches!(
+            abc_multiname,
+            Some(AbcMultiname::MultinameL { .. }) | Some(AbcMultiname::MultinameLA { .. })
+        ) {
+            return None;
+        }
+
+        let key = match self.context.avm2.peek(0) {
+            Value::Object(key) => key,
+            _ => return None,
+        };
+        let dictionary = match self.context.avm2.peek(1) {
+            Value::Object(object) => object.as_dictionary_object()?,
+            _ => return None,
+        };
+
+        self.context.avm2.pop();
+        self.context.avm2.pop();
+
+        Some((dictionary, key))
+    }
+
     /// Retrieve a static, or non-runtime, multiname from the current constant
     /// pool.
     fn pool_multiname_static(
@@ -472,7 +610,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Multiname<'gc>, Error> {
+    ) -> Result<Multiname<'gc>, Error<'gc>> {
         Multiname::from_abc_multiname_static(method.translation_unit(), index, mc)
     }
 
@@ -482,7 +620,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMethod>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Gc<'gc, BytecodeMethod<'gc>>, Error> {
+    ) -> Result<Gc<'gc, BytecodeMethod<'gc>>, Error<'gc>> {
         BytecodeMethod::from_method_index(method.translation_unit(), index.clone(), mc)
             .ok_or_else(|| format!("Method index {} does not exist", index.0).into())
     }
@@ -492,7 +630,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcClass>,
-    ) -> Result<GcCell<'gc, Class<'gc>>, Error> {
+    ) -> Result<GcCell<'gc, Class<'gc>>, Error<'gc>> {
         method
             .translation_unit()
             .load_class(index.0, self.context.avm2, self.context.gc_context)
@@ -501,28 +639,143 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     pub fn run_actions(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-    ) -> Result<Value<'gc>, Error> {
-        let body: Result<_, Error> = method
-            .body()
-            .ok_or_else(|| "Cannot execute non-native method without body".into());
-        let mut read = Reader::new(Cursor::new(body?.code.as_ref()));
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let body = method.verified_body(self.context.gc_context)?;
+        let mut ip = 0;
+
+        let name = method.name(self.context.gc_context)?;
+        self.context.avm2.call_stack.push(name);
+
+        // Catching an exception resets the operand and scope stacks to how they were when
+        // this frame started executing.
+        let stack_depth = self.context.avm2.stack.len();
+        let scope = self.scope;
+
+        let result = loop {
+            let instruction_start = ip;
+            let result = self.do_next_opcode(method, &body.code, &mut ip);
+            if result.is_ok() {
+                // Any error that escaped a method this instruction called was handled by
+                // native code.
+                self.context.avm2.error_trace = None;
+            }
 
-        loop {
-            let result = self.do_next_opcode(method, &mut read);
             match result {
                 Ok(FrameControl::Return(value)) => break Ok(value),
                 Ok(FrameControl::Continue) => {}
-                Err(e) => break Err(e),
+                Err(error) => match self.find_exception_handler(
+                    method,
+                    &body.exceptions,
+                    instruction_start,
+                    error,
+                ) {
+                    Ok((thrown, target)) => {
+                        self.context.avm2.error_trace = None;
+                        self.context.avm2.stack.truncate(stack_depth);
+                        self.scope = scope;
+                        self.context.avm2.push(thrown);
+                        ip = target as usize;
+                    }
+                    Err(error) => {
+                        let avm2 = &mut self.context.avm2;
+                        avm2.stack.truncate(stack_depth);
+                        if avm2.error_trace.is_none() {
+                            avm2.error_trace = Some(avm2.call_stack.trace());
+                        }
+
+                        break Err(error);
+                    }
+                },
+            }
+        };
+
+        // Errors are only reported once they leave the outermost method, as any
+        // method on the way there could still catch them.
+        if self.context.avm2.call_stack.depth() == 1 {
+            if let (Err(error), Some(trace)) = (&result, self.context.avm2.error_trace.take()) {
+                log::error!("AVM2 error: {}\n{}", error, trace);
+            }
+        }
+
+        self.context.avm2.call_stack.pop();
+
+        result
+    }
+
+    /// Find the exception handler of a method that catches an error raised by the
+    /// instruction at `pc`.
+    ///
+    /// Handlers are checked in the order of the method's exception table, whose ranges and
+    /// targets are instruction indices. This returns the thrown value and the index of the
+    /// handler to jump to, or hands the error back if no handler in this method catches it,
+    /// so that it can propagate to the caller.
+    fn find_exception_handler(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        exceptions: &[AbcException],
+        pc: usize,
+        error: Error<'gc>,
+    ) -> Result<(Value<'gc>, u32), Error<'gc>> {
+        let covers_pc = |exception: &AbcException| {
+            exception.from_offset as usize <= pc && pc < exception.to_offset as usize
+        };
+
+        // Don't create error objects for errors that this method can't catch anyway.
+        if !exceptions.iter().any(covers_pc) {
+            return Err(error);
+        }
+
+        let thrown = error.into_thrown_value(self)?;
+        for exception in exceptions.iter().filter(|exception| covers_pc(exception)) {
+            // A type name of zero catches everything, and is also used for `finally` blocks.
+            if exception.type_name.0 == 0 {
+                return Ok((thrown, exception.target_offset));
+            }
+
+            // Failing to look up the type must not replace the error that's being handled,
+            // so the handler is skipped instead.
+            let type_name = match self.pool_multiname_static(
+                method,
+                exception.type_name.clone(),
+                self.context.gc_context,
+            ) {
+                Ok(type_name) => type_name,
+                Err(e) => {
+                    log::warn!("Exception handler has an invalid type name: {}", e);
+                    continue;
+                }
+            };
+            let type_object = match self.resolve_type(&type_name, self.scope()) {
+                Ok(Some(type_object)) => type_object,
+                Ok(None) => {
+                    log::warn!("Exception handler catches nonexistent type {:?}", type_name);
+                    continue;
+                }
+                Err(e) => {
+                    log::warn!(
+                        "Couldn't resolve type {:?} of exception handler: {}",
+                        type_name,
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            if thrown.is_of_type(self, type_object)? {
+                return Ok((thrown, exception.target_offset));
             }
         }
+
+        Err(Error::AvmError(thrown))
     }
 
-    /// Run a single action from a given action reader.
+    /// Run the instruction at the instruction pointer of a decoded method body.
     fn do_next_opcode(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+        code: &[Op],
+        ip: &mut usize,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         if self.context.update_start.elapsed() >= self.context.max_execution_duration {
             return Err(
                 "A script in this movie has taken too long to execute and has been terminated."
@@ -530,75 +783,84 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             );
         }
 
-        let op = reader.read_op();
-        if let Ok(Some(op)) = op {
+        let instruction_start = *ip;
+        let op = code.get(instruction_start);
+        *ip += 1;
+
+        if let Some(op) = op {
             avm_debug!(self.avm2(), "Opcode: {:?}", op);
 
-            let result = match op {
-                Op::PushByte { value } => self.op_push_byte(value),
-                Op::PushDouble { value } => self.op_push_double(method, value),
+            match op {
+                Op::PushByte { value } => self.op_push_byte(*value),
+                Op::PushDouble { value } => self.op_push_double(method, value.clone()),
                 Op::PushFalse => self.op_push_false(),
-                Op::PushInt { value } => self.op_push_int(method, value),
-                Op::PushNamespace { value } => self.op_push_namespace(method, value),
+                Op::PushInt { value } => self.op_push_int(method, value.clone()),
+                Op::PushNamespace { value } => self.op_push_namespace(method, value.clone()),
                 Op::PushNaN => self.op_push_nan(),
                 Op::PushNull => self.op_push_null(),
-                Op::PushShort { value } => self.op_push_short(value),
-                Op::PushString { value } => self.op_push_string(method, value),
+                Op::PushShort { value } => self.op_push_short(*value),
+                Op::PushString { value } => self.op_push_string(method, value.clone()),
                 Op::PushTrue => self.op_push_true(),
-                Op::PushUint { value } => self.op_push_uint(method, value),
+                Op::PushUint { value } => self.op_push_uint(method, value.clone()),
                 Op::PushUndefined => self.op_push_undefined(),
                 Op::Pop => self.op_pop(),
                 Op::Dup => self.op_dup(),
-                Op::GetLocal { index } => self.op_get_local(index),
-                Op::SetLocal { index } => self.op_set_local(index),
-                Op::Kill { index } => self.op_kill(index),
-                Op::Call { num_args } => self.op_call(num_args),
-                Op::CallMethod { index, num_args } => self.op_call_method(index, num_args),
+                Op::GetLocal { index } => self.op_get_local(*index),
+                Op::SetLocal { index } => self.op_set_local(*index),
+                Op::Kill { index } => self.op_kill(*index),
+                Op::Call { num_args } => self.op_call(*num_args),
+                Op::CallMethod { index, num_args } => self.op_call_method(index.clone(), *num_args),
                 Op::CallProperty { index, num_args } => {
-                    self.op_call_property(method, index, num_args)
+                    self.op_call_property(method, index.clone(), *num_args)
                 }
                 Op::CallPropLex { index, num_args } => {
-                    self.op_call_prop_lex(method, index, num_args)
+                    self.op_call_prop_lex(method, index.clone(), *num_args)
                 }
                 Op::CallPropVoid { index, num_args } => {
-                    self.op_call_prop_void(method, index, num_args)
+                    self.op_call_prop_void(method, index.clone(), *num_args)
+                }
+                Op::CallStatic { index, num_args } => {
+                    self.op_call_static(method, index.clone(), *num_args)
+                }
+                Op::CallSuper { index, num_args } => {
+                    self.op_call_super(method, index.clone(), *num_args)
                 }
-                Op::CallStatic { index, num_args } => self.op_call_static(method, index, num_args),
-                Op::CallSuper { index, num_args } => self.op_call_super(method, index, num_args),
                 Op::CallSuperVoid { index, num_args } => {
-                    self.op_call_super_void(method, index, num_args)
+                    self.op_call_super_void(method, index.clone(), *num_args)
                 }
                 Op::ReturnValue => self.op_return_value(),
                 Op::ReturnVoid => self.op_return_void(),
-                Op::GetProperty { index } => self.op_get_property(method, index),
-                Op::SetProperty { index } => self.op_set_property(method, index),
-                Op::InitProperty { index } => self.op_init_property(method, index),
-                Op::DeleteProperty { index } => self.op_delete_property(method, index),
-                Op::GetSuper { index } => self.op_get_super(method, index),
-                Op::SetSuper { index } => self.op_set_super(method, index),
+                Op::GetProperty { index } => self.op_get_property(method, index.clone()),
+                Op::SetProperty { index } => self.op_set_property(method, index.clone()),
+                Op::InitProperty { index } => self.op_init_property(method, index.clone()),
+                Op::DeleteProperty { index } => self.op_delete_property(method, index.clone()),
+                Op::GetSuper { index } => self.op_get_super(method, index.clone()),
+                Op::SetSuper { index } => self.op_set_super(method, index.clone()),
                 Op::In => self.op_in(),
                 Op::PushScope => self.op_push_scope(),
                 Op::PushWith => self.op_push_with(),
                 Op::PopScope => self.op_pop_scope(),
-                Op::GetScopeObject { index } => self.op_get_scope_object(index),
+                Op::GetScopeObject { index } => self.op_get_scope_object(*index),
                 Op::GetGlobalScope => self.op_get_global_scope(),
-                Op::FindProperty { index } => self.op_find_property(method, index),
-                Op::FindPropStrict { index } => self.op_find_prop_strict(method, index),
-                Op::GetLex { index } => self.op_get_lex(method, index),
-                Op::GetSlot { index } => self.op_get_slot(index),
-                Op::SetSlot { index } => self.op_set_slot(index),
-                Op::GetGlobalSlot { index } => self.op_get_global_slot(index),
-                Op::SetGlobalSlot { index } => self.op_set_global_slot(index),
-                Op::Construct { num_args } => self.op_construct(num_args),
+                Op::FindProperty { index } => self.op_find_property(method, index.clone()),
+                Op::FindPropStrict { index } => self.op_find_prop_strict(method, index.clone()),
+                Op::GetLex { index } => self.op_get_lex(method, index.clone()),
+                Op::GetSlot { index } => self.op_get_slot(*index),
+                Op::SetSlot { index } => self.op_set_slot(*index),
+                Op::GetGlobalSlot { index } => self.op_get_global_slot(*index),
+                Op::SetGlobalSlot { index } => self.op_set_global_slot(*index),
+                Op::Construct { num_args } => self.op_construct(*num_args),
                 Op::ConstructProp { index, num_args } => {
-                    self.op_construct_prop(method, index, num_args)
+                    self.op_construct_prop(method, index.clone(), *num_args)
                 }
-                Op::ConstructSuper { num_args } => self.op_construct_super(num_args),
+                Op::ConstructSuper { num_args } => self.op_construct_super(*num_args),
                 Op::NewActivation => self.op_new_activation(),
-                Op::NewObject { num_args } => self.op_new_object(num_args),
-                Op::NewFunction { index } => self.op_new_function(method, index),
-                Op::NewClass { index } => self.op_new_class(method, index),
-                Op::NewArray { num_args } => self.op_new_array(num_args),
+                Op::NewObject { num_args } => self.op_new_object(*num_args),
+                Op::NewFunction { index } => self.op_new_function(method, index.clone()),
+                Op::NewClass { index } => self.op_new_class(method, index.clone()),
+                Op::NewArray { num_args } => self.op_new_array(*num_args),
+                Op::ApplyType { num_types } => self.op_apply_type(*num_types),
+                Op::Coerce { index } => self.op_coerce(method, index.clone()),
                 Op::CoerceA => self.op_coerce_a(),
                 Op::CoerceS => self.op_coerce_s(),
                 Op::ConvertB => self.op_convert_b(),
@@ -613,13 +875,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 Op::BitNot => self.op_bitnot(),
                 Op::BitOr => self.op_bitor(),
                 Op::BitXor => self.op_bitxor(),
-                Op::DecLocal { index } => self.op_declocal(index),
-                Op::DecLocalI { index } => self.op_declocal_i(index),
+                Op::DecLocal { index } => self.op_declocal(*index),
+                Op::DecLocalI { index } => self.op_declocal_i(*index),
                 Op::Decrement => self.op_decrement(),
                 Op::DecrementI => self.op_decrement_i(),
                 Op::Divide => self.op_divide(),
-                Op::IncLocal { index } => self.op_inclocal(index),
-                Op::IncLocalI { index } => self.op_inclocal_i(index),
+                Op::IncLocal { index } => self.op_inclocal(*index),
+                Op::IncLocalI { index } => self.op_inclocal_i(*index),
                 Op::Increment => self.op_increment(),
                 Op::IncrementI => self.op_increment_i(),
                 Op::LShift => self.op_lshift(),
@@ -633,21 +895,25 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 Op::SubtractI => self.op_subtract_i(),
                 Op::Swap => self.op_swap(),
                 Op::URShift => self.op_urshift(),
-                Op::Jump { offset } => self.op_jump(offset, reader),
-                Op::IfTrue { offset } => self.op_if_true(offset, reader),
-                Op::IfFalse { offset } => self.op_if_false(offset, reader),
-                Op::IfStrictEq { offset } => self.op_if_strict_eq(offset, reader),
-                Op::IfStrictNe { offset } => self.op_if_strict_ne(offset, reader),
-                Op::IfEq { offset } => self.op_if_eq(offset, reader),
-                Op::IfNe { offset } => self.op_if_ne(offset, reader),
-                Op::IfGe { offset } => self.op_if_ge(offset, reader),
-                Op::IfGt { offset } => self.op_if_gt(offset, reader),
-                Op::IfLe { offset } => self.op_if_le(offset, reader),
-                Op::IfLt { offset } => self.op_if_lt(offset, reader),
-                Op::IfNge { offset } => self.op_if_nge(offset, reader),
-                Op::IfNgt { offset } => self.op_if_ngt(offset, reader),
-                Op::IfNle { offset } => self.op_if_nle(offset, reader),
-                Op::IfNlt { offset } => self.op_if_nlt(offset, reader),
+                Op::Jump { offset } => self.op_jump(*offset, ip),
+                Op::LookupSwitch {
+                    default_offset,
+                    case_offsets,
+                } => self.op_lookup_switch(*default_offset, case_offsets, instruction_start, ip),
+                Op::IfTrue { offset } => self.op_if_true(*offset, ip),
+                Op::IfFalse { offset } => self.op_if_false(*offset, ip),
+                Op::IfStrictEq { offset } => self.op_if_strict_eq(*offset, ip),
+                Op::IfStrictNe { offset } => self.op_if_strict_ne(*offset, ip),
+                Op::IfEq { offset } => self.op_if_eq(*offset, ip),
+                Op::IfNe { offset } => self.op_if_ne(*offset, ip),
+                Op::IfGe { offset } => self.op_if_ge(*offset, ip),
+                Op::IfGt { offset } => self.op_if_gt(*offset, ip),
+                Op::IfLe { offset } => self.op_if_le(*offset, ip),
+                Op::IfLt { offset } => self.op_if_lt(*offset, ip),
+                Op::IfNge { offset } => self.op_if_nge(*offset, ip),
+                Op::IfNgt { offset } => self.op_if_ngt(*offset, ip),
+                Op::IfNle { offset } => self.op_if_nle(*offset, ip),
+                Op::IfNlt { offset } => self.op_if_nlt(*offset, ip),
                 Op::StrictEquals => self.op_strict_equals(),
                 Op::Equals => self.op_equals(),
                 Op::GreaterEquals => self.op_greater_equals(),
@@ -659,45 +925,58 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 Op::HasNext2 {
                     object_register,
                     index_register,
-                } => self.op_has_next_2(object_register, index_register),
+                } => self.op_has_next_2(*object_register, *index_register),
                 Op::NextName => self.op_next_name(),
                 Op::NextValue => self.op_next_value(),
-                Op::IsType { index } => self.op_is_type(method, index),
+                Op::IsType { index } => self.op_is_type(method, index.clone()),
                 Op::IsTypeLate => self.op_is_type_late(),
+                Op::AsType { type_name } => self.op_as_type(method, type_name.clone()),
+                Op::AsTypeLate => self.op_as_type_late(),
+                Op::TypeOf => self.op_type_of(),
                 Op::InstanceOf => self.op_instance_of(),
                 Op::Label => Ok(FrameControl::Continue),
                 Op::Debug {
                     is_local_register,
                     register_name,
                     register,
-                } => self.op_debug(method, is_local_register, register_name, register),
-                Op::DebugFile { file_name } => self.op_debug_file(method, file_name),
-                Op::DebugLine { line_num } => self.op_debug_line(line_num),
+                } => self.op_debug(method, *is_local_register, register_name.clone(), *register),
+                Op::DebugFile { file_name } => self.op_debug_file(method, file_name.clone()),
+                Op::DebugLine { line_num } => self.op_debug_line(*line_num),
+                Op::Throw => self.op_throw(),
+                Op::NewCatch { index } => self.op_new_catch(method, index.clone()),
+                Op::GetDescendants { index } => self.op_get_descendants(method, index.clone()),
+                Op::CheckFilter => self.op_check_filter(),
+                Op::EscXElem => self.op_esc_xelem(),
+                Op::EscXAttr => self.op_esc_xattr(),
+                Op::Dxns { index } => self.op_dxns(method, index.clone()),
+                Op::DxnsLate => self.op_dxns_late(),
+                Op::Li8 => self.op_li8(method),
+                Op::Li16 => self.op_li16(method),
+                Op::Li32 => self.op_li32(method),
+                Op::Lf32 => self.op_lf32(method),
+                Op::Lf64 => self.op_lf64(method),
+                Op::Si8 => self.op_si8(method),
+                Op::Si16 => self.op_si16(method),
+                Op::Si32 => self.op_si32(method),
+                Op::Sf32 => self.op_sf32(method),
+                Op::Sf64 => self.op_sf64(method),
+                Op::Sxi1 => self.op_sxi1(),
+                Op::Sxi8 => self.op_sxi8(),
+                Op::Sxi16 => self.op_sxi16(),
                 _ => self.unknown_op(op),
-            };
-
-            if let Err(e) = result {
-                log::error!("AVM2 error: {}", e);
-                return Err(e);
             }
-            result
-        } else if let Ok(None) = op {
-            log::error!("Unknown opcode!");
-            Err("Unknown opcode!".into())
-        } else if let Err(e) = op {
-            log::error!("Parse error: {:?}", e);
-            Err(e.into())
         } else {
-            unreachable!();
+            log::error!("Instruction pointer ran off the end of the method!");
+            Err("Instruction pointer ran off the end of the method!".into())
         }
     }
 
-    fn unknown_op(&mut self, op: swf::avm2::types::Op) -> Result<FrameControl<'gc>, Error> {
+    fn unknown_op(&mut self, op: &Op) -> Result<FrameControl<'gc>, Error<'gc>> {
         log::error!("Unknown AVM2 opcode: {:?}", op);
         Err("Unknown op".into())
     }
 
-    fn op_push_byte(&mut self, value: u8) -> Result<FrameControl<'gc>, Error> {
+    fn op_push_byte(&mut self, value: u8) -> Result<FrameControl<'gc>, Error<'gc>> {
         //TODO: Adobe Animate CC appears to generate signed byte values, and
         //JPEXS appears to take them.
         self.context.avm2.push(value as i8 as i32);
@@ -708,12 +987,12 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         value: Index<f64>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(self.pool_double(method, value)?);
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_false(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_push_false(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(false);
         Ok(FrameControl::Continue)
     }
@@ -722,7 +1001,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         value: Index<i32>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(self.pool_int(method, value)?);
         Ok(FrameControl::Continue)
     }
@@ -731,7 +1010,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         value: Index<AbcNamespace>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let ns = self.pool_namespace(method, value, self.context.gc_context)?;
 
         self.context.avm2.push(NamespaceObject::from_namespace(
@@ -742,17 +1021,17 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_nan(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_push_nan(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(std::f64::NAN);
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_null(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_push_null(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(Value::Null);
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_short(&mut self, value: i16) -> Result<FrameControl<'gc>, Error> {
+    fn op_push_short(&mut self, value: i16) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(value);
         Ok(FrameControl::Continue)
     }
@@ -761,14 +1040,14 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         value: Index<String>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context
             .avm2
             .push(self.pool_string(&method, value, self.context.gc_context)?);
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_true(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_push_true(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(true);
         Ok(FrameControl::Continue)
     }
@@ -777,23 +1056,23 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         value: Index<u32>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(self.pool_uint(method, value)?);
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_undefined(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_push_undefined(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(Value::Undefined);
         Ok(FrameControl::Continue)
     }
 
-    fn op_pop(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_pop(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.pop();
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_dup(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_dup(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(
             self.context
                 .avm2
@@ -806,12 +1085,12 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_get_local(&mut self, register_index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_get_local(&mut self, register_index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(self.local_register(register_index)?);
         Ok(FrameControl::Continue)
     }
 
-    fn op_set_local(&mut self, register_index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_set_local(&mut self, register_index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop();
 
         self.set_local_register(register_index, value, self.context.gc_context)?;
@@ -819,13 +1098,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_kill(&mut self, register_index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_kill(&mut self, register_index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.set_local_register(register_index, Value::Undefined, self.context.gc_context)?;
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_call(&mut self, arg_count: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_call(&mut self, arg_count: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let receiver = self.context.avm2.pop().coerce_to_object(self).ok();
         let function = self.context.avm2.pop().coerce_to_object(self)?;
@@ -841,10 +1120,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         index: Index<AbcMethod>,
         arg_count: u32,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let receiver = self.context.avm2.pop().coerce_to_object(self)?;
-        let function: Result<Object<'gc>, Error> = receiver
+        let function: Result<Object<'gc>, Error<'gc>> = receiver
             .get_method(index.0)
             .ok_or_else(|| format!("Object method {} does not exist", index.0).into());
         let base_proto = receiver.proto();
@@ -860,13 +1139,17 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
         arg_count: u32,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
         let mut receiver = self.context.avm2.pop().coerce_to_object(self)?;
-        let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
-            .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
+        let name: Result<QName, Error<'gc>> =
+            receiver.resolve_multiname(&multiname)?.ok_or_else(|| {
+                Error::new(
+                    ErrorKind::TypeError,
+                    format!("Could not find method {:?}", multiname.local_name()),
+                )
+            });
         let name = name?;
         let base_proto = receiver.get_base_proto(&name)?;
         let function = receiver
@@ -884,13 +1167,17 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
         arg_count: u32,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
         let mut receiver = self.context.avm2.pop().coerce_to_object(self)?;
-        let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
-            .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
+        let name: Result<QName, Error<'gc>> =
+            receiver.resolve_multiname(&multiname)?.ok_or_else(|| {
+                Error::new(
+                    ErrorKind::TypeError,
+                    format!("Could not find method {:?}", multiname.local_name()),
+                )
+            });
         let function = receiver
             .get_property(receiver, &name?, self)?
             .coerce_to_object(self)?;
@@ -906,13 +1193,17 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
         arg_count: u32,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
         let mut receiver = self.context.avm2.pop().coerce_to_object(self)?;
-        let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
-            .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
+        let name: Result<QName, Error<'gc>> =
+            receiver.resolve_multiname(&multiname)?.ok_or_else(|| {
+                Error::new(
+                    ErrorKind::TypeError,
+                    format!("Could not find method {:?}", multiname.local_name()),
+                )
+            });
         let name = name?;
         let base_proto = receiver.get_base_proto(&name)?;
         let function = receiver
@@ -929,7 +1220,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMethod>,
         arg_count: u32,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let receiver = self.context.avm2.pop().coerce_to_object(self)?;
         let method = self.table_method(method, index, self.context.gc_context)?;
@@ -953,14 +1244,18 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
         arg_count: u32,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
         let receiver = self.context.avm2.pop().coerce_to_object(self)?;
-        let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
-            .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
-        let base_proto: Result<Object<'gc>, Error> =
+        let name: Result<QName, Error<'gc>> =
+            receiver.resolve_multiname(&multiname)?.ok_or_else(|| {
+                Error::new(
+                    ErrorKind::TypeError,
+                    format!("Could not find method {:?}", multiname.local_name()),
+                )
+            });
+        let base_proto: Result<Object<'gc>, Error<'gc>> =
             self.base_proto().and_then(|bp| bp.proto()).ok_or_else(|| {
                 "Attempted to call super method without a superclass."
                     .to_string()
@@ -985,14 +1280,18 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
         arg_count: u32,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
         let receiver = self.context.avm2.pop().coerce_to_object(self)?;
-        let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
-            .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
-        let base_proto: Result<Object<'gc>, Error> =
+        let name: Result<QName, Error<'gc>> =
+            receiver.resolve_multiname(&multiname)?.ok_or_else(|| {
+                Error::new(
+                    ErrorKind::TypeError,
+                    format!("Could not find method {:?}", multiname.local_name()),
+                )
+            });
+        let base_proto: Result<Object<'gc>, Error<'gc>> =
             self.base_proto().and_then(|bp| bp.proto()).ok_or_else(|| {
                 "Attempted to call super method without a superclass."
                     .to_string()
@@ -1010,13 +1309,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_return_value(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_return_value(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let return_value = self.context.avm2.pop();
 
         Ok(FrameControl::Return(return_value))
     }
 
-    fn op_return_void(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_return_void(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         Ok(FrameControl::Return(Value::Undefined))
     }
 
@@ -1024,13 +1323,40 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        if let Some((dictionary, key)) = self.pop_dictionary_key(method, &index) {
+            self.context
+                .avm2
+                .push(dictionary.get_property_by_object(key));
+            return Ok(FrameControl::Continue);
+        }
+
         let multiname = self.pool_multiname(method, index)?;
         let mut object = self.context.avm2.pop().coerce_to_object(self)?;
 
-        let name: Result<QName, Error> = object.resolve_multiname(&multiname)?.ok_or_else(|| {
-            format!("Could not resolve property {:?}", multiname.local_name()).into()
-        });
+        if let Some(value) = e4x_get_property(self, object, &multiname)? {
+            self.context.avm2.push(value);
+            return Ok(FrameControl::Continue);
+        }
+
+        let name: Result<QName, Error<'gc>> =
+            object.resolve_multiname(&multiname)?.ok_or_else(|| {
+                Error::new(
+                    ErrorKind::ReferenceError,
+                    format!("Could not resolve property {:?}", multiname.local_name()),
+                )
+            });
+
+        // Indexes past the end of a vector aren't dynamic properties, and
+        // reading them raises a `RangeError`.
+        if let (Err(_), Some(local_name)) = (&name, multiname.local_name()) {
+            if object.as_vector_storage().is_some() && local_name.parse::<usize>().is_ok() {
+                let value =
+                    object.get_property_local(object, &QName::dynamic_name(local_name), self)?;
+                self.context.avm2.push(value);
+                return Ok(FrameControl::Continue);
+            }
+        }
 
         // Special case for dynamic properties as scripts may attempt to get
         // dynamic properties not yet set
@@ -1054,17 +1380,26 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop();
+        if let Some((dictionary, key)) = self.pop_dictionary_key(method, &index) {
+            dictionary.set_property_by_object(key, value, self.context.gc_context);
+            return Ok(FrameControl::Continue);
+        }
+
         let multiname = self.pool_multiname(method, index)?;
         let mut object = self.context.avm2.pop().coerce_to_object(self)?;
 
+        if e4x_set_property(self, object, &multiname, value.clone())? {
+            return Ok(FrameControl::Continue);
+        }
+
         if let Some(name) = object.resolve_multiname(&multiname)? {
             object.set_property(object, &name, value, self)?;
         } else {
             //TODO: Non-dynamic objects should fail
             //TODO: This should only work if the public namespace is present
-            let local_name: Result<AvmString<'gc>, Error> = multiname
+            let local_name: Result<AvmString<'gc>, Error<'gc>> = multiname
                 .local_name()
                 .ok_or_else(|| "Cannot set property using any name".into());
             let name = QName::dynamic_name(local_name?);
@@ -1078,17 +1413,26 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop();
+        if let Some((dictionary, key)) = self.pop_dictionary_key(method, &index) {
+            dictionary.set_property_by_object(key, value, self.context.gc_context);
+            return Ok(FrameControl::Continue);
+        }
+
         let multiname = self.pool_multiname(method, index)?;
         let mut object = self.context.avm2.pop().coerce_to_object(self)?;
 
+        if e4x_set_property(self, object, &multiname, value.clone())? {
+            return Ok(FrameControl::Continue);
+        }
+
         if let Some(name) = object.resolve_multiname(&multiname)? {
             object.init_property(object, &name, value, self)?;
         } else {
             //TODO: Non-dynamic objects should fail
             //TODO: This should only work if the public namespace is present
-            let local_name: Result<AvmString<'gc>, Error> = multiname
+            let local_name: Result<AvmString<'gc>, Error<'gc>> = multiname
                 .local_name()
                 .ok_or_else(|| "Cannot set property using any name".into());
             let name = QName::dynamic_name(local_name?);
@@ -1102,10 +1446,22 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        if let Some((dictionary, key)) = self.pop_dictionary_key(method, &index) {
+            self.context
+                .avm2
+                .push(dictionary.delete_property_by_object(key, self.context.gc_context));
+            return Ok(FrameControl::Continue);
+        }
+
         let multiname = self.pool_multiname(method, index)?;
         let object = self.context.avm2.pop().coerce_to_object(self)?;
 
+        if let Some(deleted) = e4x_delete_property(self, object, &multiname)? {
+            self.context.avm2.push(deleted);
+            return Ok(FrameControl::Continue);
+        }
+
         if let Some(name) = object.resolve_multiname(&multiname)? {
             self.context
                 .avm2
@@ -1123,27 +1479,339 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    fn op_get_descendants(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<AbcMultiname>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let multiname = self.pool_multiname(method, index)?;
+        let object = self.context.avm2.pop();
+        let descendants = e4x_descendants(self, object, &multiname)?;
+
+        self.context.avm2.push(descendants);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_check_filter(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self.context.avm2.peek(0);
+        let is_xml = match &value {
+            Value::Object(object) => xml_nodes(*object).is_some(),
+            _ => false,
+        };
+
+        if !is_xml {
+            return Err(Error::new(
+                ErrorKind::TypeError,
+                format!(
+                    "Error #1123: Filter operator not supported on type {}.",
+                    value.type_of()
+                ),
+            ));
+        }
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_esc_xelem(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self.context.avm2.pop();
+        let nodes = match &value {
+            Value::Object(object) => xml_nodes(*object),
+            _ => None,
+        };
+
+        let escaped = match nodes {
+            Some(nodes) => list_xml_string(&nodes, &self.context.avm2.xml_settings),
+            None => escape_text(&value.coerce_to_string(self)?),
+        };
+
+        self.context
+            .avm2
+            .push(AvmString::new(self.context.gc_context, escaped));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_esc_xattr(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self.context.avm2.pop().coerce_to_string(self)?;
+        let escaped = escape_attribute(&value);
+
+        self.context
+            .avm2
+            .push(AvmString::new(self.context.gc_context, escaped));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_dxns(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<String>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let uri = self.pool_string(&method, index, self.context.gc_context)?;
+
+        self.default_xml_namespace = Some(uri);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_dxns_late(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self.context.avm2.pop();
+        let namespace = match &value {
+            Value::Object(object) => object.as_namespace().map(|ns| ns.as_uri()),
+            _ => None,
+        };
+
+        let uri = match namespace {
+            Some(uri) => uri,
+            None => value.coerce_to_string(self)?,
+        };
+
+        self.default_xml_namespace = Some(uri);
+
+        Ok(FrameControl::Continue)
+    }
+
+    /// Get the byte range of domain memory accessed by a load or store.
+    ///
+    /// Accesses that aren't entirely within domain memory are range errors.
+    fn domain_memory_range(
+        address: i32,
+        size: usize,
+        length: usize,
+    ) -> Result<std::ops::Range<usize>, Error<'gc>> {
+        let start = address as usize;
+        if address < 0 || start.saturating_add(size) > length {
+            return Err(Error::new(
+                ErrorKind::RangeError,
+                "Error #1506: The specified range is invalid.",
+            ));
+        }
+
+        Ok(start..start + size)
+    }
+
+    /// Get the domain memory of the domain that a method was loaded into.
+    fn domain_memory(method: Gc<'gc, BytecodeMethod<'gc>>) -> Result<Object<'gc>, Error<'gc>> {
+        method
+            .translation_unit()
+            .domain()
+            .domain_memory()
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::RangeError,
+                    "Error #1506: The specified range is invalid.",
+                )
+            })
+    }
+
+    /// Read bytes from domain memory, starting at the given address.
+    fn load_domain_memory(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        address: i32,
+        bytes: &mut [u8],
+    ) -> Result<(), Error<'gc>> {
+        let memory = Self::domain_memory(method)?;
+        let storage = memory
+            .as_bytearray()
+            .ok_or("Domain memory is not a ByteArray")?;
+        let range = Self::domain_memory_range(address, bytes.len(), storage.len())?;
+
+        bytes.copy_from_slice(&storage.bytes()[range]);
+
+        Ok(())
+    }
+
+    /// Write bytes to domain memory, starting at the given address.
+    fn store_domain_memory(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        address: i32,
+        bytes: &[u8],
+    ) -> Result<(), Error<'gc>> {
+        let memory = Self::domain_memory(method)?;
+        let mut storage = memory
+            .as_bytearray_mut(self.context.gc_context)
+            .ok_or("Domain memory is not a ByteArray")?;
+        let range = Self::domain_memory_range(address, bytes.len(), storage.len())?;
+
+        storage.bytes_mut()[range].copy_from_slice(bytes);
+
+        Ok(())
+    }
+
+    fn op_li8(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+        let mut bytes = [0; 1];
+        self.load_domain_memory(method, address, &mut bytes)?;
+
+        self.context.avm2.push(i32::from(bytes[0]));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_li16(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+        let mut bytes = [0; 2];
+        self.load_domain_memory(method, address, &mut bytes)?;
+
+        self.context.avm2.push(i32::from(u16::from_le_bytes(bytes)));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_li32(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+        let mut bytes = [0; 4];
+        self.load_domain_memory(method, address, &mut bytes)?;
+
+        self.context.avm2.push(i32::from_le_bytes(bytes));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_lf32(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+        let mut bytes = [0; 4];
+        self.load_domain_memory(method, address, &mut bytes)?;
+
+        self.context.avm2.push(f64::from(f32::from_le_bytes(bytes)));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_lf64(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+        let mut bytes = [0; 8];
+        self.load_domain_memory(method, address, &mut bytes)?;
+
+        self.context.avm2.push(f64::from_le_bytes(bytes));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_si8(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+        let value = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        self.store_domain_memory(method, address, &[value as u8])?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_si16(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+        let value = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        self.store_domain_memory(method, address, &(value as u16).to_le_bytes())?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_si32(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+        let value = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        self.store_domain_memory(method, address, &value.to_le_bytes())?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_sf32(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+        let value = self.context.avm2.pop().coerce_to_number(self)?;
+
+        self.store_domain_memory(method, address, &(value as f32).to_le_bytes())?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_sf64(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+        let value = self.context.avm2.pop().coerce_to_number(self)?;
+
+        self.store_domain_memory(method, address, &value.to_le_bytes())?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_sxi1(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        self.context.avm2.push(-(value & 1));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_sxi8(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        self.context.avm2.push(i32::from(value as i8));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_sxi16(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        self.context.avm2.push(i32::from(value as i16));
+
+        Ok(FrameControl::Continue)
+    }
+
     fn op_get_super(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let multiname = self.pool_multiname(method, index)?;
         let object = self.context.avm2.pop().coerce_to_object(self)?;
-        let base_proto: Result<Object<'gc>, Error> = self
+        let base_proto: Result<Object<'gc>, Error<'gc>> = self
             .base_proto()
             .and_then(|p| p.proto())
             .ok_or_else(|| "Attempted to get property on non-existent super object".into());
         let base_proto = base_proto?;
         let mut base = base_proto.construct(self, &[])?; //TODO: very hacky workaround
 
-        let name: Result<QName, Error> = base.resolve_multiname(&multiname)?.ok_or_else(|| {
-            format!(
-                "Could not resolve {:?} as super property",
-                multiname.local_name()
-            )
-            .into()
-        });
+        let name: Result<QName, Error<'gc>> =
+            base.resolve_multiname(&multiname)?.ok_or_else(|| {
+                format!(
+                    "Could not resolve {:?} as super property",
+                    multiname.local_name()
+                )
+                .into()
+            });
 
         let value = base.get_property(object, &name?, self)?;
 
@@ -1156,33 +1824,43 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop();
         let multiname = self.pool_multiname(method, index)?;
         let object = self.context.avm2.pop().coerce_to_object(self)?;
-        let base_proto: Result<Object<'gc>, Error> = self
+        let base_proto: Result<Object<'gc>, Error<'gc>> = self
             .base_proto()
             .and_then(|p| p.proto())
             .ok_or_else(|| "Attempted to get property on non-existent super object".into());
         let base_proto = base_proto?;
         let mut base = base_proto.construct(self, &[])?; //TODO: very hacky workaround
 
-        let name: Result<QName, Error> = base.resolve_multiname(&multiname)?.ok_or_else(|| {
-            format!(
-                "Could not resolve {:?} as super property",
-                multiname.local_name()
-            )
-            .into()
-        });
+        let name: Result<QName, Error<'gc>> =
+            base.resolve_multiname(&multiname)?.ok_or_else(|| {
+                format!(
+                    "Could not resolve {:?} as super property",
+                    multiname.local_name()
+                )
+                .into()
+            });
 
         base.set_property(object, &name?, value, self)?;
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_in(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_in(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let obj = self.context.avm2.pop().coerce_to_object(self)?;
-        let name = self.context.avm2.pop().coerce_to_string(self)?;
+        let name = self.context.avm2.pop();
+
+        if let (Some(dictionary), Value::Object(key)) = (obj.as_dictionary_object(), &name) {
+            self.context
+                .avm2
+                .push(dictionary.has_property_by_object(*key));
+            return Ok(FrameControl::Continue);
+        }
+
+        let name = name.coerce_to_string(self)?;
 
         let qname = QName::new(Namespace::public(), name);
         let has_prop = obj.has_property(&qname)?;
@@ -1192,7 +1870,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_scope(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_push_scope(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let object = self.context.avm2.pop().coerce_to_object(self)?;
         let scope_stack = self.scope();
         let new_scope = Scope::push_scope(scope_stack, object, self.context.gc_context);
@@ -1202,7 +1880,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_push_with(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_push_with(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let object = self.context.avm2.pop().coerce_to_object(self)?;
         let scope_stack = self.scope();
         let new_scope = Scope::push_with(scope_stack, object, self.context.gc_context);
@@ -1212,7 +1890,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_pop_scope(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_pop_scope(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let scope_stack = self.scope();
         let new_scope = scope_stack.and_then(|s| s.read().pop_scope());
 
@@ -1221,7 +1899,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_get_scope_object(&mut self, mut index: u8) -> Result<FrameControl<'gc>, Error> {
+    fn op_get_scope_object(&mut self, mut index: u8) -> Result<FrameControl<'gc>, Error<'gc>> {
         let mut scope = self.scope();
 
         while index > 0 {
@@ -1241,7 +1919,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_get_global_scope(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_get_global_scope(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context.avm2.push(self.global_scope());
 
         Ok(FrameControl::Continue)
@@ -1251,7 +1929,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let multiname = self.pool_multiname(method, index)?;
         avm_debug!(self.context.avm2, "Resolving {:?}", multiname);
         let result = if let Some(scope) = self.scope() {
@@ -1271,15 +1949,20 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let multiname = self.pool_multiname(method, index)?;
         avm_debug!(self.context.avm2, "Resolving {:?}", multiname);
-        let found: Result<Object<'gc>, Error> = if let Some(scope) = self.scope() {
+        let found: Result<Object<'gc>, Error<'gc>> = if let Some(scope) = self.scope() {
             scope.read().find(&multiname, self)?
         } else {
             None
         }
-        .ok_or_else(|| format!("Property does not exist: {:?}", multiname).into());
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::ReferenceError,
+                format!("Property does not exist: {:?}", multiname),
+            )
+        });
         let result: Value<'gc> = found?.into();
 
         self.context.avm2.push(result);
@@ -1291,17 +1974,22 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let multiname = self.pool_multiname_static(method, index, self.context.gc_context)?;
         avm_debug!(self.avm2(), "Resolving {:?}", multiname);
-        let found: Result<Value<'gc>, Error> = if let Some(scope) = self.scope() {
+        let found: Result<Value<'gc>, Error<'gc>> = if let Some(scope) = self.scope() {
             scope
                 .write(self.context.gc_context)
                 .resolve(&multiname, self)?
         } else {
             None
         }
-        .ok_or_else(|| format!("Property does not exist: {:?}", multiname).into());
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::ReferenceError,
+                format!("Property does not exist: {:?}", multiname),
+            )
+        });
         let result: Value<'gc> = found?;
 
         self.context.avm2.push(result);
@@ -1309,7 +1997,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_get_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_get_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let object = self.context.avm2.pop().coerce_to_object(self)?;
         let value = object.get_slot(index)?;
 
@@ -1318,16 +2006,17 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_set_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_set_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let object = self.context.avm2.pop().coerce_to_object(self)?;
         let value = self.context.avm2.pop();
+        let value = object.coerce_slot_value(index, value, self)?;
 
         object.set_slot(index, value, self.context.gc_context)?;
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_get_global_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_get_global_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.scope.unwrap().read().globals().get_slot(index)?;
 
         self.context.avm2.push(value);
@@ -1335,19 +2024,17 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_set_global_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_set_global_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop();
+        let globals = self.scope.unwrap().read().globals();
+        let value = globals.coerce_slot_value(index, value, self)?;
 
-        self.scope
-            .unwrap()
-            .read()
-            .globals()
-            .set_slot(index, value, self.context.gc_context)?;
+        globals.set_slot(index, value, self.context.gc_context)?;
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_construct(&mut self, arg_count: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_construct(&mut self, arg_count: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let mut ctor = self.context.avm2.pop().coerce_to_object(self)?;
 
@@ -1368,14 +2055,17 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
         arg_count: u32,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
         let mut source = self.context.avm2.pop().coerce_to_object(self)?;
 
-        let ctor_name: Result<QName, Error> =
+        let ctor_name: Result<QName, Error<'gc>> =
             source.resolve_multiname(&multiname)?.ok_or_else(|| {
-                format!("Could not resolve property {:?}", multiname.local_name()).into()
+                Error::new(
+                    ErrorKind::ReferenceError,
+                    format!("Could not resolve property {:?}", multiname.local_name()),
+                )
             });
         let mut ctor = source
             .get_property(source, &ctor_name?, self)?
@@ -1392,7 +2082,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_construct_super(&mut self, arg_count: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_construct_super(&mut self, arg_count: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(arg_count);
         let receiver = self.context.avm2.pop().coerce_to_object(self)?;
 
@@ -1401,7 +2091,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_new_activation(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_new_activation(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         self.context
             .avm2
             .push(ScriptObject::bare_object(self.context.gc_context));
@@ -1409,7 +2099,44 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_new_object(&mut self, num_args: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_new_catch(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<AbcException>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let body: Result<_, Error<'gc>> = method
+            .body()
+            .ok_or_else(|| "Cannot create catch scope for non-native method without body".into());
+        let exception: Result<_, Error<'gc>> = body?
+            .exceptions
+            .get(index.0 as usize)
+            .ok_or_else(|| format!("Exception index {} does not exist", index.0).into());
+        let variable_name = exception?.variable_name.clone();
+
+        // The catch scope holds the caught value in its first slot. Handlers without a
+        // variable, like `finally` blocks, get an empty scope.
+        let mut catch_scope = ScriptObject::bare_object(self.context.gc_context);
+        if variable_name.0 != 0 {
+            let name = QName::from_abc_multiname(
+                method.translation_unit(),
+                variable_name,
+                self.context.gc_context,
+            )?;
+            catch_scope.install_slot(
+                self.context.gc_context,
+                name,
+                1,
+                Value::Undefined,
+                Multiname::any(),
+            );
+        }
+
+        self.context.avm2.push(catch_scope);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_new_object(&mut self, num_args: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let mut object = ScriptObject::object(
             self.context.gc_context,
             self.context.avm2.prototypes().object,
@@ -1436,7 +2163,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMethod>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let method_entry = self.table_method(method, index, self.context.gc_context)?;
         let scope = self.scope();
 
@@ -1457,6 +2184,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             QName::new(Namespace::public(), "prototype"),
             0,
             es3_proto.into(),
+            Multiname::any(),
         );
 
         self.context.avm2.push(new_fn);
@@ -1468,7 +2196,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcClass>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let base_value = self.context.avm2.pop();
         let base_class = match base_value {
             Value::Object(o) => Some(o),
@@ -1489,7 +2217,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_new_array(&mut self, num_args: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_new_array(&mut self, num_args: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.context.avm2.pop_args(num_args);
         let array = ArrayStorage::from_args(&args[..]);
         let array_obj = ArrayObject::from_array(
@@ -1503,11 +2231,36 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_coerce_a(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_apply_type(&mut self, num_types: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let params = self.context.avm2.pop_args(num_types);
+        let base_type = self.context.avm2.pop().coerce_to_object(self)?;
+
+        let applied = apply_type(self, base_type, &params)?;
+
+        self.context.avm2.push(applied);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_coerce(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<AbcMultiname>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self.context.avm2.pop();
+        let type_name = self.pool_multiname_static(method, index, self.context.gc_context)?;
+        let coerced = self.coerce_to_declared_type(value, &type_name, self.scope())?;
+
+        self.context.avm2.push(coerced);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_coerce_a(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_coerce_s(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_coerce_s(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop();
 
         let coerced = match value {
@@ -1520,7 +2273,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_convert_b(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_convert_b(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_boolean();
 
         self.context.avm2.push(value);
@@ -1528,7 +2281,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_convert_i(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_convert_i(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_i32(self)?;
 
         self.context.avm2.push(Value::Number(value.into()));
@@ -1536,7 +2289,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_convert_d(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_convert_d(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_number(self)?;
 
         self.context.avm2.push(Value::Number(value));
@@ -1544,7 +2297,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_convert_o(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_convert_o(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_object(self)?;
 
         self.context.avm2.push(value);
@@ -1552,7 +2305,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_convert_u(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_convert_u(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_u32(self)?;
 
         self.context.avm2.push(Value::Number(value.into()));
@@ -1560,7 +2313,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_convert_s(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_convert_s(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_string(self)?;
 
         self.context.avm2.push(value);
@@ -1568,12 +2321,19 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_add(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_add(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
-        // TODO: Special handling required for `Date` and ECMA-357/E4X `XML`
         let sum_value = match (value1, value2) {
+            (Value::Object(o1), Value::Object(o2))
+                if xml_nodes(o1).is_some() && xml_nodes(o2).is_some() =>
+            {
+                let mut nodes = xml_nodes(o1).unwrap();
+                nodes.extend(xml_nodes(o2).unwrap());
+
+                new_xml_list(self, nodes).into()
+            }
             (Value::Number(n1), Value::Number(n2)) => Value::Number(n1 + n2),
             (Value::String(s), value2) => {
                 let mut out_s = s.to_string();
@@ -1616,7 +2376,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_add_i(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_add_i(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_i32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
@@ -1625,7 +2385,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_bitand(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_bitand(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_i32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
@@ -1634,7 +2394,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_bitnot(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_bitnot(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
         self.context.avm2.push(!value1);
@@ -1642,7 +2402,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_bitor(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_bitor(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_i32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
@@ -1651,7 +2411,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_bitxor(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_bitxor(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_i32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
@@ -1660,7 +2420,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_declocal(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_declocal(&mut self, index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.local_register(index)?.coerce_to_number(self)?;
 
         self.set_local_register(index, value - 1.0, self.context.gc_context)?;
@@ -1668,7 +2428,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_declocal_i(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_declocal_i(&mut self, index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.local_register(index)?.coerce_to_i32(self)?;
 
         self.set_local_register(index, value - 1, self.context.gc_context)?;
@@ -1676,7 +2436,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_decrement(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_decrement(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_number(self)?;
 
         self.context.avm2.push(value - 1.0);
@@ -1684,7 +2444,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_decrement_i(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_decrement_i(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_i32(self)?;
 
         self.context.avm2.push(value - 1);
@@ -1692,7 +2452,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_divide(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_divide(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_number(self)?;
         let value1 = self.context.avm2.pop().coerce_to_number(self)?;
 
@@ -1701,7 +2461,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_inclocal(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_inclocal(&mut self, index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.local_register(index)?.coerce_to_number(self)?;
 
         self.set_local_register(index, value + 1.0, self.context.gc_context)?;
@@ -1709,7 +2469,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_inclocal_i(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_inclocal_i(&mut self, index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.local_register(index)?.coerce_to_i32(self)?;
 
         self.set_local_register(index, value + 1, self.context.gc_context)?;
@@ -1717,7 +2477,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_increment(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_increment(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_number(self)?;
 
         self.context.avm2.push(value + 1.0);
@@ -1725,7 +2485,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_increment_i(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_increment_i(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_i32(self)?;
 
         self.context.avm2.push(value + 1);
@@ -1733,7 +2493,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_lshift(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_lshift(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_u32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
@@ -1742,7 +2502,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_modulo(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_modulo(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_number(self)?;
         let value1 = self.context.avm2.pop().coerce_to_number(self)?;
 
@@ -1751,7 +2511,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_multiply(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_multiply(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_number(self)?;
         let value1 = self.context.avm2.pop().coerce_to_number(self)?;
 
@@ -1760,7 +2520,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_multiply_i(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_multiply_i(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_i32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
@@ -1769,7 +2529,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_negate(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_negate(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value1 = self.context.avm2.pop().coerce_to_number(self)?;
 
         self.context.avm2.push(-value1);
@@ -1777,7 +2537,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_negate_i(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_negate_i(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
         self.context.avm2.push(-value1);
@@ -1785,7 +2545,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_rshift(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_rshift(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_u32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
@@ -1794,7 +2554,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_subtract(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_subtract(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_number(self)?;
         let value1 = self.context.avm2.pop().coerce_to_number(self)?;
 
@@ -1803,7 +2563,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_subtract_i(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_subtract_i(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_i32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
@@ -1812,7 +2572,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_swap(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_swap(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -1822,7 +2582,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_urshift(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_urshift(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop().coerce_to_u32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_u32(self)?;
 
@@ -1831,25 +2591,40 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_jump(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
-        reader.seek(offset as i64)?;
+    fn op_jump(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
+        *ip = (*ip as i64 + i64::from(offset)) as usize;
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_true(
+    fn op_lookup_switch(
         &mut self,
-        offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+        default_offset: i32,
+        case_offsets: &[i32],
+        instruction_start: usize,
+        ip: &mut usize,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let index = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        // Unlike jumps, the offsets of a `lookupswitch` are relative to its own start.
+        let offset = if index >= 0 {
+            case_offsets
+                .get(index as usize)
+                .copied()
+                .unwrap_or(default_offset)
+        } else {
+            default_offset
+        };
+        *ip = (instruction_start as i64 + i64::from(offset)) as usize;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_if_true(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_boolean();
 
         if value {
-            reader.seek(offset as i64)?;
+            *ip = (*ip as i64 + i64::from(offset)) as usize;
         }
 
         Ok(FrameControl::Continue)
@@ -1858,12 +2633,12 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_if_false(
         &mut self,
         offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+        ip: &mut usize,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_boolean();
 
         if !value {
-            reader.seek(offset as i64)?;
+            *ip = (*ip as i64 + i64::from(offset)) as usize;
         }
 
         Ok(FrameControl::Continue)
@@ -1872,13 +2647,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_if_strict_eq(
         &mut self,
         offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+        ip: &mut usize,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value1 == value2 {
-            reader.seek(offset as i64)?;
+            *ip = (*ip as i64 + i64::from(offset)) as usize;
         }
 
         Ok(FrameControl::Continue)
@@ -1887,169 +2662,129 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_if_strict_ne(
         &mut self,
         offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+        ip: &mut usize,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value1 != value2 {
-            reader.seek(offset as i64)?;
+            *ip = (*ip as i64 + i64::from(offset)) as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_eq(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_eq(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value1.abstract_eq(&value2, self)? {
-            reader.seek(offset as i64)?;
+            *ip = (*ip as i64 + i64::from(offset)) as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_ne(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_ne(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if !value1.abstract_eq(&value2, self)? {
-            reader.seek(offset as i64)?;
+            *ip = (*ip as i64 + i64::from(offset)) as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_ge(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_ge(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value1.abstract_lt(&value2, self)? == Some(false) {
-            reader.seek(offset as i64)?;
+            *ip = (*ip as i64 + i64::from(offset)) as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_gt(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_gt(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value2.abstract_lt(&value1, self)? == Some(true) {
-            reader.seek(offset as i64)?;
+            *ip = (*ip as i64 + i64::from(offset)) as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_le(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_le(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value2.abstract_lt(&value1, self)? == Some(false) {
-            reader.seek(offset as i64)?;
+            *ip = (*ip as i64 + i64::from(offset)) as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_lt(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_lt(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value1.abstract_lt(&value2, self)? == Some(true) {
-            reader.seek(offset as i64)?;
+            *ip = (*ip as i64 + i64::from(offset)) as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_nge(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_nge(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value1.abstract_lt(&value2, self)?.unwrap_or(true) {
-            reader.seek(offset as i64)?;
+            *ip = (*ip as i64 + i64::from(offset)) as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_ngt(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_ngt(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if !value2.abstract_lt(&value1, self)?.unwrap_or(false) {
-            reader.seek(offset as i64)?;
+            *ip = (*ip as i64 + i64::from(offset)) as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_nle(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_nle(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value2.abstract_lt(&value1, self)?.unwrap_or(true) {
-            reader.seek(offset as i64)?;
+            *ip = (*ip as i64 + i64::from(offset)) as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_nlt(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_nlt(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if !value1.abstract_lt(&value2, self)?.unwrap_or(false) {
-            reader.seek(offset as i64)?;
+            *ip = (*ip as i64 + i64::from(offset)) as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_strict_equals(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_strict_equals(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -2058,7 +2793,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_equals(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_equals(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -2069,7 +2804,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_greater_equals(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_greater_equals(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -2080,7 +2815,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_greater_than(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_greater_than(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -2091,7 +2826,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_less_equals(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_less_equals(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -2102,7 +2837,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_less_than(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_less_than(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
@@ -2113,7 +2848,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_not(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_not(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.context.avm2.pop().coerce_to_boolean();
 
         self.context.avm2.push(!value);
@@ -2121,16 +2856,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_has_next(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_has_next(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let cur_index = self.context.avm2.pop().coerce_to_u32(self)?;
         let object = self.context.avm2.pop().coerce_to_object(self)?;
 
-        let next_index = cur_index + 1;
-
-        if object.get_enumerant_name(next_index).is_some() {
-            self.context.avm2.push(next_index);
-        } else {
-            self.context.avm2.push(0.0);
+        match object.get_next_enumerant(cur_index) {
+            Some(next_index) => self.context.avm2.push(next_index),
+            None => self.context.avm2.push(0.0),
         }
 
         Ok(FrameControl::Continue)
@@ -2140,28 +2872,29 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         &mut self,
         object_register: u32,
         index_register: u32,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let cur_index = self.local_register(index_register)?.coerce_to_u32(self)?;
         let mut object = Some(
             self.local_register(object_register)?
                 .coerce_to_object(self)?,
         );
 
-        let mut next_index = cur_index + 1;
+        let mut last_index = cur_index;
+        let mut next_index = 0;
 
         while let Some(cur_object) = object {
-            if cur_object.get_enumerant_name(next_index).is_none() {
-                next_index = 1;
-                object = cur_object.proto();
-            } else {
-                break;
+            match cur_object.get_next_enumerant(last_index) {
+                Some(index) => {
+                    next_index = index;
+                    break;
+                }
+                None => {
+                    last_index = 0;
+                    object = cur_object.proto();
+                }
             }
         }
 
-        if object.is_none() {
-            next_index = 0;
-        }
-
         self.context.avm2.push(next_index != 0);
         self.set_local_register(index_register, next_index, self.context.gc_context)?;
         self.set_local_register(
@@ -2173,53 +2906,48 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_next_name(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_next_name(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let cur_index = self.context.avm2.pop().coerce_to_number(self)?;
         let object = self.context.avm2.pop().coerce_to_object(self)?;
 
-        let name = object
-            .get_enumerant_name(cur_index as u32)
-            .map(|n| n.local_name().into());
+        let name = object.get_enumerant_key(cur_index as u32);
 
         self.context.avm2.push(name.unwrap_or(Value::Undefined));
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_next_value(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_next_value(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let cur_index = self.context.avm2.pop().coerce_to_number(self)?;
-        let mut object = self.context.avm2.pop().coerce_to_object(self)?;
+        let object = self.context.avm2.pop().coerce_to_object(self)?;
 
-        let name = object.get_enumerant_name(cur_index as u32);
-        let value = if let Some(name) = name {
-            object.get_property(object, &name, self)?
-        } else {
-            Value::Undefined
-        };
+        let value = object.get_enumerant_value(cur_index as u32, self)?;
 
         self.context.avm2.push(value);
 
         Ok(FrameControl::Continue)
     }
 
+    fn op_throw(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let thrown = self.context.avm2.pop();
+
+        Err(Error::AvmError(thrown))
+    }
+
     fn op_is_type(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         type_name_index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
-        let value = self.context.avm2.pop().coerce_to_object(self)?;
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self.context.avm2.pop();
 
         let type_name =
             self.pool_multiname_static(method, type_name_index, self.context.gc_context)?;
-        let type_object = if let Some(scope) = self.scope() {
-            scope.read().find(&type_name, self)?
-        } else {
-            None
-        };
+        let type_object = self.resolve_type(&type_name, self.scope())?;
 
         if let Some(type_object) = type_object {
-            let is_instance_of = value.is_instance_of(self, type_object, true)?;
-            self.context.avm2.push(is_instance_of);
+            let is_of_type = value.is_of_type(self, type_object)?;
+            self.context.avm2.push(is_of_type);
         } else {
             return Err(format!(
                 "Attempted to check against nonexistent type {:?}",
@@ -2231,28 +2959,91 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_is_type_late(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_is_type_late(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let type_object = self.context.avm2.pop().coerce_to_object(self)?;
-        let value = self.context.avm2.pop().coerce_to_object(self)?;
+        let value = self.context.avm2.pop();
 
-        let is_instance_of = value.is_instance_of(self, type_object, true)?;
+        let is_of_type = value.is_of_type(self, type_object)?;
 
-        self.context.avm2.push(is_instance_of);
+        self.context.avm2.push(is_of_type);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_as_type(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        type_name_index: Index<AbcMultiname>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self.context.avm2.pop();
+
+        let type_name =
+            self.pool_multiname_static(method, type_name_index, self.context.gc_context)?;
+        let result = match type_name.local_name() {
+            // Every value is of the any type.
+            None => value,
+            Some(local_name) => {
+                let type_object =
+                    self.resolve_type(&type_name, self.scope())?
+                        .ok_or_else(|| {
+                            Error::new(
+                                ErrorKind::ReferenceError,
+                                format!("Error #1065: Variable {} is not defined.", local_name),
+                            )
+                        })?;
+
+                if value.is_of_type(self, type_object)? {
+                    value
+                } else {
+                    Value::Null
+                }
+            }
+        };
+
+        self.context.avm2.push(result);
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_instance_of(&mut self) -> Result<FrameControl<'gc>, Error> {
+    fn op_as_type_late(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let type_object = self.context.avm2.pop().coerce_to_object(self)?;
-        let value = self.context.avm2.pop().coerce_to_object(self)?;
+        let value = self.context.avm2.pop();
 
-        let is_instance_of = value.is_instance_of(self, type_object, false)?;
+        let result = if value.is_of_type(self, type_object)? {
+            value
+        } else {
+            Value::Null
+        };
+
+        self.context.avm2.push(result);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_instance_of(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let type_object = self.context.avm2.pop().coerce_to_object(self)?;
+        let value = self.context.avm2.pop();
+
+        let is_instance_of = match value {
+            Value::Undefined | Value::Null => false,
+            value => value
+                .coerce_to_object(self)?
+                .is_instance_of(self, type_object, false)?,
+        };
 
         self.context.avm2.push(is_instance_of);
 
         Ok(FrameControl::Continue)
     }
 
+    fn op_type_of(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let value = self.context.avm2.pop();
+
+        self.context.avm2.push(value.type_of());
+
+        Ok(FrameControl::Continue)
+    }
+
     #[allow(unused_variables)]
     #[cfg(avm_debug)]
     fn op_debug(
@@ -2261,7 +3052,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         is_local_register: bool,
         register_name: Index<String>,
         register: u8,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         if is_local_register {
             let register_name = self.pool_string(method, register_name)?;
             let value = self.local_register(register as u32)?;
@@ -2282,38 +3073,29 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         is_local_register: bool,
         register_name: Index<String>,
         register: u8,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         Ok(FrameControl::Continue)
     }
 
-    #[allow(unused_variables)]
-    #[cfg(avm_debug)]
     fn op_debug_file(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         file_name: Index<String>,
-    ) -> Result<FrameControl<'gc>, Error> {
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let file_name = self.pool_string(method, file_name)?;
 
         avm_debug!(self.avm2(), "File: {}", file_name);
 
-        Ok(FrameControl::Continue)
-    }
+        self.context.avm2.call_stack.set_file(file_name);
 
-    #[allow(unused_variables)]
-    #[cfg(not(avm_debug))]
-    fn op_debug_file(
-        &mut self,
-        method: Gc<'gc, BytecodeMethod<'gc>>,
-        file_name: Index<String>,
-    ) -> Result<FrameControl<'gc>, Error> {
         Ok(FrameControl::Continue)
     }
 
-    #[allow(unused_variables)]
-    fn op_debug_line(&mut self, line_num: u32) -> Result<FrameControl<'gc>, Error> {
+    fn op_debug_line(&mut self, line_num: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         avm_debug!(self.avm2(), "Line: {}", line_num);
 
+        self.context.avm2.call_stack.set_line(line_num);
+
         Ok(FrameControl::Continue)
     }
 }
@@ -0,0 +1,507 @@
+//! ByteArray support types
+
+use crate::avm2::{Error, ErrorKind};
+use encoding_rs::Encoding;
+use flate2::read::{DeflateDecoder, ZlibDecoder};
+use flate2::write::{DeflateEncoder, ZlibEncoder};
+use flate2::Compression;
+use gc_arena::Collect;
+use ruffle_amf::ObjectEncoding;
+use std::convert::TryInto;
+use std::io::Write;
+
+/// The byte order that multi-byte values are read and written in.
+#[derive(Clone, Collect, Debug, Copy, PartialEq, Eq)]
+#[collect(require_static)]
+pub enum Endian {
+    Big,
+    Little,
+}
+
+impl Endian {
+    /// Parse the name of an endianness, as used by `flash.utils.Endian`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "bigEndian" => Some(Self::Big),
+            "littleEndian" => Some(Self::Little),
+            _ => None,
+        }
+    }
+
+    /// The name of this endianness, as used by `flash.utils.Endian`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Big => "bigEndian",
+            Self::Little => "littleEndian",
+        }
+    }
+}
+
+/// A compression algorithm supported by `ByteArray.compress`.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum CompressionAlgorithm {
+    Zlib,
+    Deflate,
+    Lzma,
+}
+
+impl CompressionAlgorithm {
+    /// Parse the name of an algorithm, as used by
+    /// `flash.utils.CompressionAlgorithm`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "zlib" => Some(Self::Zlib),
+            "deflate" => Some(Self::Deflate),
+            "lzma" => Some(Self::Lzma),
+            _ => None,
+        }
+    }
+}
+
+/// The largest that a byte array is allowed to grow, in bytes.
+///
+/// Flash runs out of memory long before a byte array reaches the 4GiB that
+/// its length could describe, so growing past this raises a `MemoryError`
+/// instead of attempting the allocation.
+pub const MAX_BYTE_ARRAY_LENGTH: usize = 0x1000_0000;
+
+/// The error raised when a byte array would grow past its maximum length.
+fn out_of_memory<'gc>() -> Error<'gc> {
+    Error::new(
+        ErrorKind::MemoryError,
+        "Error #1000: The system is out of memory.",
+    )
+}
+
+/// The storage portion of a `ByteArray` object.
+///
+/// This is a growable buffer with a read/write position. Reads past the end
+/// of the buffer fail, while writes past the end grow it, up to
+/// `MAX_BYTE_ARRAY_LENGTH`.
+#[derive(Clone, Collect, Debug)]
+#[collect(require_static)]
+pub struct ByteArrayStorage {
+    bytes: Vec<u8>,
+    position: usize,
+    endian: Endian,
+    object_encoding: ObjectEncoding,
+}
+
+impl Default for ByteArrayStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Generate a pair of functions to read and write a fixed-size number in the
+/// storage's byte order.
+macro_rules! impl_read_write_number {
+    ($(($type:ident, $read:ident, $write:ident)),* $(,)?) => {
+        $(
+            pub fn $read<'gc>(&mut self) -> Result<$type, Error<'gc>> {
+                let bytes = self
+                    .read_bytes(std::mem::size_of::<$type>())?
+                    .try_into()
+                    .unwrap();
+                Ok(match self.endian {
+                    Endian::Big => $type::from_be_bytes(bytes),
+                    Endian::Little => $type::from_le_bytes(bytes),
+                })
+            }
+
+            pub fn $write<'gc>(&mut self, value: $type) -> Result<(), Error<'gc>> {
+                match self.endian {
+                    Endian::Big => self.write_bytes(&value.to_be_bytes()),
+                    Endian::Little => self.write_bytes(&value.to_le_bytes()),
+                }
+            }
+        )*
+    };
+}
+
+impl ByteArrayStorage {
+    /// Construct an empty byte array.
+    pub fn new() -> Self {
+        Self::from_vec(Vec::new())
+    }
+
+    /// Wrap existing bytes in a byte array, positioned at the start.
+    pub fn from_vec(bytes: Vec<u8>) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            endian: Endian::Big,
+            object_encoding: ObjectEncoding::Amf3,
+        }
+    }
+
+    /// The entire contents of the byte array.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Grow or shrink the byte array. New bytes are zero.
+    ///
+    /// The position is moved to the new end if it was past it.
+    pub fn set_len<'gc>(&mut self, len: usize) -> Result<(), Error<'gc>> {
+        if len > MAX_BYTE_ARRAY_LENGTH {
+            return Err(out_of_memory());
+        }
+
+        self.bytes.resize(len, 0);
+        self.position = self.position.min(len);
+
+        Ok(())
+    }
+
+    /// Empty the byte array and reset its position.
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+        self.position = 0;
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Move the position, which may be past the end of the byte array.
+    pub fn set_position(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    /// The number of bytes that can be read from the current position.
+    pub fn bytes_available(&self) -> usize {
+        self.bytes.len().saturating_sub(self.position)
+    }
+
+    pub fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    pub fn set_endian(&mut self, endian: Endian) {
+        self.endian = endian;
+    }
+
+    pub fn object_encoding(&self) -> ObjectEncoding {
+        self.object_encoding
+    }
+
+    pub fn set_object_encoding(&mut self, object_encoding: ObjectEncoding) {
+        self.object_encoding = object_encoding;
+    }
+
+    /// The bytes from the current position to the end.
+    pub fn remaining_bytes(&self) -> &[u8] {
+        self.bytes.get(self.position..).unwrap_or_default()
+    }
+
+    /// Read a number of bytes, advancing the position past them.
+    pub fn read_bytes<'gc>(&mut self, len: usize) -> Result<&[u8], Error<'gc>> {
+        if self.bytes_available() < len {
+            return Err(Error::new(
+                ErrorKind::EOFError,
+                "Error #2030: End of file was encountered.",
+            ));
+        }
+
+        let start = self.position;
+        self.position += len;
+        Ok(&self.bytes[start..self.position])
+    }
+
+    /// Write bytes at the current position, overwriting existing bytes and
+    /// growing the byte array as needed.
+    pub fn write_bytes<'gc>(&mut self, bytes: &[u8]) -> Result<(), Error<'gc>> {
+        let end = self
+            .position
+            .checked_add(bytes.len())
+            .filter(|end| *end <= MAX_BYTE_ARRAY_LENGTH)
+            .ok_or_else(out_of_memory)?;
+        if self.bytes.len() < end {
+            self.bytes.resize(end, 0);
+        }
+
+        self.bytes[self.position..end].copy_from_slice(bytes);
+        self.position = end;
+
+        Ok(())
+    }
+
+    impl_read_write_number!(
+        (u16, read_u16, write_u16),
+        (i16, read_i16, write_i16),
+        (u32, read_u32, write_u32),
+        (i32, read_i32, write_i32),
+        (f32, read_f32, write_f32),
+        (f64, read_f64, write_f64),
+    );
+
+    pub fn read_u8<'gc>(&mut self) -> Result<u8, Error<'gc>> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    pub fn write_u8<'gc>(&mut self, value: u8) -> Result<(), Error<'gc>> {
+        self.write_bytes(&[value])
+    }
+
+    pub fn read_i8<'gc>(&mut self) -> Result<i8, Error<'gc>> {
+        Ok(self.read_u8()? as i8)
+    }
+
+    pub fn read_bool<'gc>(&mut self) -> Result<bool, Error<'gc>> {
+        Ok(self.read_u8()? != 0)
+    }
+
+    /// Read UTF-8 text of a given length in bytes.
+    ///
+    /// A leading byte order mark is skipped, and invalid sequences are
+    /// replaced.
+    pub fn read_utf_bytes<'gc>(&mut self, len: usize) -> Result<String, Error<'gc>> {
+        let bytes = self.read_bytes(len)?;
+        let bytes = if bytes.starts_with(b"\xEF\xBB\xBF") {
+            &bytes[3..]
+        } else {
+            bytes
+        };
+
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    /// Read UTF-8 text prefixed with its length as an unsigned 16-bit integer.
+    pub fn read_utf<'gc>(&mut self) -> Result<String, Error<'gc>> {
+        let len = self.read_u16()?;
+        self.read_utf_bytes(len.into())
+    }
+
+    /// Write UTF-8 text prefixed with its length as an unsigned 16-bit integer.
+    pub fn write_utf<'gc>(&mut self, text: &str) -> Result<(), Error<'gc>> {
+        if text.len() > usize::from(u16::MAX) {
+            return Err(Error::new(
+                ErrorKind::RangeError,
+                "Error #2006: The supplied index is out of bounds.",
+            ));
+        }
+
+        self.write_u16(text.len() as u16)?;
+        self.write_bytes(text.as_bytes())
+    }
+
+    /// Read text of a given length in bytes in the given character set.
+    ///
+    /// Unknown character sets are read as UTF-8.
+    pub fn read_multi_byte<'gc>(
+        &mut self,
+        len: usize,
+        charset: &str,
+    ) -> Result<String, Error<'gc>> {
+        let encoding = Encoding::for_label(charset.as_bytes()).unwrap_or(encoding_rs::UTF_8);
+        let bytes = self.read_bytes(len)?;
+        let (text, _, _) = encoding.decode(bytes);
+
+        Ok(text.into_owned())
+    }
+
+    /// Write text in the given character set.
+    ///
+    /// Unknown character sets are written as UTF-8.
+    pub fn write_multi_byte<'gc>(&mut self, text: &str, charset: &str) -> Result<(), Error<'gc>> {
+        let encoding = Encoding::for_label(charset.as_bytes()).unwrap_or(encoding_rs::UTF_8);
+        let (bytes, _, _) = encoding.encode(text);
+
+        self.write_bytes(&bytes)
+    }
+
+    /// Compress the entire contents of the byte array.
+    ///
+    /// The position is moved to the end of the compressed data.
+    pub fn compress<'gc>(&mut self, algorithm: CompressionAlgorithm) -> Result<(), Error<'gc>> {
+        let compressed = match algorithm {
+            CompressionAlgorithm::Zlib => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(&self.bytes)?;
+                encoder.finish()?
+            }
+            CompressionAlgorithm::Deflate => {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(&self.bytes)?;
+                encoder.finish()?
+            }
+            CompressionAlgorithm::Lzma => compress_lzma(&self.bytes)?,
+        };
+
+        if compressed.len() > MAX_BYTE_ARRAY_LENGTH {
+            return Err(out_of_memory());
+        }
+
+        self.position = compressed.len();
+        self.bytes = compressed;
+
+        Ok(())
+    }
+
+    /// Decompress the entire contents of the byte array.
+    ///
+    /// The position is moved to the start. If the data can't be decompressed,
+    /// the byte array is left unchanged and an `IOError` is raised, or a
+    /// `MemoryError` if the decompressed data would be too large.
+    pub fn uncompress<'gc>(&mut self, algorithm: CompressionAlgorithm) -> Result<(), Error<'gc>> {
+        let mut decompressed = BoundedBuffer::default();
+        let result = match algorithm {
+            CompressionAlgorithm::Zlib => {
+                std::io::copy(&mut ZlibDecoder::new(&self.bytes[..]), &mut decompressed).map(|_| ())
+            }
+            CompressionAlgorithm::Deflate => {
+                std::io::copy(&mut DeflateDecoder::new(&self.bytes[..]), &mut decompressed)
+                    .map(|_| ())
+            }
+            CompressionAlgorithm::Lzma => decompress_lzma(&self.bytes, &mut decompressed),
+        };
+
+        if decompressed.overflowed {
+            return Err(out_of_memory());
+        }
+
+        if result.is_err() {
+            return Err(Error::new(
+                ErrorKind::IOError,
+                "Error #2058: There was an error decompressing the data.",
+            ));
+        }
+
+        self.bytes = decompressed.bytes;
+        self.position = 0;
+
+        Ok(())
+    }
+}
+
+/// A buffer that refuses to grow past `MAX_BYTE_ARRAY_LENGTH`, so that
+/// decompressing a small byte array can't exhaust memory.
+#[derive(Default)]
+struct BoundedBuffer {
+    bytes: Vec<u8>,
+
+    /// Whether a write was refused because the buffer was full.
+    overflowed: bool,
+}
+
+impl Write for BoundedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.bytes.len() + buf.len() > MAX_BYTE_ARRAY_LENGTH {
+            self.overflowed = true;
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "Decompressed data is too large",
+            ));
+        }
+
+        self.bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "lzma")]
+fn compress_lzma(data: &[u8]) -> Result<Vec<u8>, std::io::Error> {
+    let mut compressed = Vec::new();
+    lzma_rs::lzma_compress(&mut &data[..], &mut compressed)?;
+
+    Ok(compressed)
+}
+
+#[cfg(not(feature = "lzma"))]
+fn compress_lzma(_data: &[u8]) -> Result<Vec<u8>, std::io::Error> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        "Support for LZMA compression was not enabled in this build",
+    ))
+}
+
+#[cfg(feature = "lzma")]
+fn decompress_lzma(data: &[u8], output: &mut BoundedBuffer) -> Result<(), std::io::Error> {
+    lzma_rs::lzma_decompress(&mut &data[..], output)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{:?}", e)))
+}
+
+#[cfg(not(feature = "lzma"))]
+fn decompress_lzma(_data: &[u8], _output: &mut BoundedBuffer) -> Result<(), std::io::Error> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        "Support for LZMA compression was not enabled in this build",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endianness() {
+        let mut storage = ByteArrayStorage::new();
+        storage.write_u32(0x0102_0304).unwrap();
+        storage.set_endian(Endian::Little);
+        storage.write_i16(-2).unwrap();
+        assert_eq!(storage.bytes(), &[1, 2, 3, 4, 0xFE, 0xFF]);
+
+        storage.set_position(0);
+        assert_eq!(storage.read_u32().unwrap(), 0x0403_0201);
+        assert_eq!(storage.read_i16().unwrap(), -2);
+        assert!(storage.read_u8().is_err());
+    }
+
+    #[test]
+    fn overwrite_and_grow() {
+        let mut storage = ByteArrayStorage::from_vec(vec![1, 2, 3]);
+        storage.set_position(2);
+        storage.write_bytes(&[4, 5]).unwrap();
+        assert_eq!(storage.bytes(), &[1, 2, 4, 5]);
+
+        storage.set_position(6);
+        storage.write_u8(6).unwrap();
+        assert_eq!(storage.bytes(), &[1, 2, 4, 5, 0, 0, 6]);
+
+        storage.set_len(2).unwrap();
+        assert_eq!(storage.position(), 2);
+    }
+
+    #[test]
+    fn growth_is_bounded() {
+        let mut storage = ByteArrayStorage::new();
+        assert!(storage.set_len(MAX_BYTE_ARRAY_LENGTH + 1).is_err());
+        assert!(storage.is_empty());
+
+        storage.set_position(MAX_BYTE_ARRAY_LENGTH);
+        assert!(storage.write_u8(1).is_err());
+        storage.set_position(usize::MAX);
+        assert!(storage.write_bytes(&[1, 2]).is_err());
+        assert!(storage.is_empty());
+    }
+
+    #[test]
+    fn compression_round_trip() {
+        for &algorithm in &[CompressionAlgorithm::Zlib, CompressionAlgorithm::Deflate] {
+            let mut storage = ByteArrayStorage::from_vec(b"hello hello hello hello".to_vec());
+            storage.compress(algorithm).unwrap();
+            assert_eq!(storage.position(), storage.len());
+
+            storage.uncompress(algorithm).unwrap();
+            assert_eq!(storage.bytes(), b"hello hello hello hello");
+            assert_eq!(storage.position(), 0);
+        }
+    }
+}
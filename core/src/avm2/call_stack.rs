@@ -0,0 +1,124 @@
+//! AVM2 call stack and stack traces
+
+use crate::avm2::string::AvmString;
+use gc_arena::Collect;
+use std::fmt::Write;
+
+/// A method that is currently running, and the source position that it is
+/// running at.
+///
+/// Source positions are only known for SWFs compiled with debugging
+/// information, which emit `debugfile` and `debugline` instructions.
+#[derive(Clone, Debug, Collect)]
+#[collect(no_drop)]
+pub struct CallFrame<'gc> {
+    /// The name of the running method, such as `flash.display::Sprite/foo`.
+    name: AvmString<'gc>,
+
+    /// The source file of the instruction being executed.
+    file: Option<AvmString<'gc>>,
+
+    /// The source line of the instruction being executed.
+    line: Option<u32>,
+}
+
+/// The stack of bytecode methods currently running in the AVM2 interpreter.
+///
+/// Native methods are not recorded on the call stack.
+#[derive(Clone, Debug, Default, Collect)]
+#[collect(no_drop)]
+pub struct CallStack<'gc>(Vec<CallFrame<'gc>>);
+
+impl<'gc> CallStack<'gc> {
+    /// Record that a method has started running.
+    pub fn push(&mut self, name: AvmString<'gc>) {
+        self.0.push(CallFrame {
+            name,
+            file: None,
+            line: None,
+        });
+    }
+
+    /// Record that the most recently started method has stopped running.
+    pub fn pop(&mut self) {
+        self.0.pop();
+    }
+
+    /// The number of methods currently running.
+    pub fn depth(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Record the source file that the running method is executing.
+    pub fn set_file(&mut self, file: AvmString<'gc>) {
+        if let Some(frame) = self.0.last_mut() {
+            frame.file = Some(file);
+        }
+    }
+
+    /// Record the source line that the running method is executing.
+    pub fn set_line(&mut self, line: u32) {
+        if let Some(frame) = self.0.last_mut() {
+            frame.line = Some(line);
+        }
+    }
+
+    /// Format the call stack in the style of Flash Player's debugger
+    /// stack traces, starting with the innermost frame.
+    ///
+    /// Each frame is written on its own line, such as
+    /// `\tat com.example::Foo/bar()[Foo.as:12]`. An empty call stack yields
+    /// an empty string.
+    pub fn trace(&self) -> String {
+        let mut trace = String::new();
+
+        for frame in self.0.iter().rev() {
+            if !trace.is_empty() {
+                trace.push('\n');
+            }
+
+            let _ = write!(trace, "\tat {}()", frame.name);
+
+            if let Some(file) = frame.file {
+                match frame.line {
+                    Some(line) => {
+                        let _ = write!(trace, "[{}:{}]", file, line);
+                    }
+                    None => {
+                        let _ = write!(trace, "[{}]", file);
+                    }
+                }
+            }
+        }
+
+        trace
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trace_lists_innermost_frame_first() {
+        let mut stack = CallStack::default();
+        assert_eq!(stack.trace(), "");
+
+        stack.push("global$init".into());
+        stack.push("Main".into());
+        stack.set_file("Main.as".into());
+        stack.set_line(12);
+        stack.push("Main/get value".into());
+        stack.set_line(3);
+
+        assert_eq!(
+            stack.trace(),
+            "\tat Main/get value()\n\tat Main()[Main.as:12]\n\tat global$init()"
+        );
+
+        stack.pop();
+        stack.set_line(13);
+
+        assert_eq!(stack.trace(), "\tat Main()[Main.as:13]\n\tat global$init()");
+    }
+}
@@ -82,7 +82,7 @@ fn do_trait_lookup<'gc>(
     name: &QName<'gc>,
     known_traits: &mut Vec<Trait<'gc>>,
     all_traits: &[Trait<'gc>],
-) -> Result<(), Error> {
+) -> Result<(), Error<'gc>> {
     for trait_entry in all_traits {
         if name == trait_entry.name() {
             for known_trait in known_traits.iter() {
@@ -160,15 +160,15 @@ impl<'gc> Class<'gc> {
         unit: TranslationUnit<'gc>,
         class_index: u32,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<GcCell<'gc, Self>, Error> {
+    ) -> Result<GcCell<'gc, Self>, Error<'gc>> {
         let abc = unit.abc();
-        let abc_class: Result<&AbcClass, Error> = abc
+        let abc_class: Result<&AbcClass, Error<'gc>> = abc
             .classes
             .get(class_index as usize)
             .ok_or_else(|| "LoadError: Class index not valid".into());
         let abc_class = abc_class?;
 
-        let abc_instance: Result<&AbcInstance, Error> = abc
+        let abc_instance: Result<&AbcInstance, Error<'gc>> = abc
             .instances
             .get(class_index as usize)
             .ok_or_else(|| "LoadError: Instance index not valid".into());
@@ -203,6 +203,14 @@ impl<'gc> Class<'gc> {
         let instance_init = unit.load_method(abc_instance.init_method.0, mc)?;
         let class_init = unit.load_method(abc_class.init_method.0, mc)?;
 
+        let qualified_name = name.to_qualified_name();
+        unit.set_method_name(
+            abc_class.init_method.0,
+            format!("{}$cinit", qualified_name),
+            mc,
+        );
+        unit.set_method_name(abc_instance.init_method.0, qualified_name, mc);
+
         let mut attributes = ClassAttributes::empty();
         attributes.set(ClassAttributes::SEALED, abc_instance.is_sealed);
         attributes.set(ClassAttributes::FINAL, abc_instance.is_final);
@@ -237,7 +245,7 @@ impl<'gc> Class<'gc> {
         class_index: u32,
         avm2: &mut Avm2<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         if self.traits_loaded {
             return Ok(());
         }
@@ -245,26 +253,32 @@ impl<'gc> Class<'gc> {
         self.traits_loaded = true;
 
         let abc = unit.abc();
-        let abc_class: Result<&AbcClass, Error> = abc
+        let abc_class: Result<&AbcClass, Error<'gc>> = abc
             .classes
             .get(class_index as usize)
             .ok_or_else(|| "LoadError: Class index not valid".into());
         let abc_class = abc_class?;
 
-        let abc_instance: Result<&AbcInstance, Error> = abc
+        let abc_instance: Result<&AbcInstance, Error<'gc>> = abc
             .instances
             .get(class_index as usize)
             .ok_or_else(|| "LoadError: Instance index not valid".into());
         let abc_instance = abc_instance?;
 
+        let qualified_name = self.name.to_qualified_name();
+
         for abc_trait in abc_instance.traits.iter() {
-            self.instance_traits
-                .push(Trait::from_abc_trait(unit, &abc_trait, avm2, mc)?);
+            let instance_trait = Trait::from_abc_trait(unit, &abc_trait, avm2, mc)?;
+            instance_trait.name_method(unit, &qualified_name, mc);
+            self.instance_traits.push(instance_trait);
         }
 
+        let class_name = format!("{}$", qualified_name);
+
         for abc_trait in abc_class.traits.iter() {
-            self.class_traits
-                .push(Trait::from_abc_trait(unit, &abc_trait, avm2, mc)?);
+            let class_trait = Trait::from_abc_trait(unit, &abc_trait, avm2, mc)?;
+            class_trait.name_method(unit, &class_name, mc);
+            self.class_traits.push(class_trait);
         }
 
         Ok(())
@@ -300,7 +314,7 @@ impl<'gc> Class<'gc> {
         &self,
         name: &QName<'gc>,
         known_traits: &mut Vec<Trait<'gc>>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         do_trait_lookup(name, known_traits, &self.class_traits)
     }
 
@@ -353,7 +367,7 @@ impl<'gc> Class<'gc> {
         &self,
         name: &QName<'gc>,
         known_traits: &mut Vec<Trait<'gc>>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         do_trait_lookup(name, known_traits, &self.instance_traits)
     }
 
@@ -405,4 +419,24 @@ impl<'gc> Class<'gc> {
     pub fn is_sealed(&self) -> bool {
         self.attributes.0.contains(ClassAttributes::SEALED)
     }
+
+    /// Determine if this class is final (cannot be subclassed)
+    pub fn is_final(&self) -> bool {
+        self.attributes.0.contains(ClassAttributes::FINAL)
+    }
+
+    /// Determine if this class is an interface
+    pub fn is_interface(&self) -> bool {
+        self.attributes.0.contains(ClassAttributes::INTERFACE)
+    }
+
+    /// Get the traits that this class defines on its instances.
+    pub fn instance_traits(&self) -> &[Trait<'gc>] {
+        &self.instance_traits
+    }
+
+    /// Get the traits that this class defines on its class constructor.
+    pub fn class_traits(&self) -> &[Trait<'gc>] {
+        &self.class_traits
+    }
 }
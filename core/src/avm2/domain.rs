@@ -2,10 +2,11 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::names::{Multiname, QName};
-use crate::avm2::object::TObject;
+use crate::avm2::object::{Object, TObject};
 use crate::avm2::script::Script;
+use crate::avm2::string::AvmString;
 use crate::avm2::value::Value;
-use crate::avm2::Error;
+use crate::avm2::{Error, ErrorKind};
 use gc_arena::{Collect, GcCell, MutationContext};
 use std::collections::HashMap;
 
@@ -23,6 +24,10 @@ struct DomainData<'gc> {
 
     /// The parent domain.
     parent: Option<Domain<'gc>>,
+
+    /// The `ByteArray` that the domain memory opcodes of code loaded into
+    /// this domain read from and write to.
+    domain_memory: Option<Object<'gc>>,
 }
 
 impl<'gc> Domain<'gc> {
@@ -36,6 +41,7 @@ impl<'gc> Domain<'gc> {
             DomainData {
                 defs: HashMap::new(),
                 parent: None,
+                domain_memory: None,
             },
         ))
     }
@@ -47,6 +53,7 @@ impl<'gc> Domain<'gc> {
             DomainData {
                 defs: HashMap::new(),
                 parent: Some(parent),
+                domain_memory: None,
             },
         ))
     }
@@ -56,6 +63,20 @@ impl<'gc> Domain<'gc> {
         self.0.read().parent
     }
 
+    /// Get the `ByteArray` used as this domain's memory, if any.
+    pub fn domain_memory(self) -> Option<Object<'gc>> {
+        self.0.read().domain_memory
+    }
+
+    /// Set the `ByteArray` used as this domain's memory.
+    pub fn set_domain_memory(
+        self,
+        mc: MutationContext<'gc, '_>,
+        domain_memory: Option<Object<'gc>>,
+    ) {
+        self.0.write(mc).domain_memory = domain_memory;
+    }
+
     /// Determine if something has been defined within the current domain.
     pub fn has_definition(self, name: QName<'gc>) -> bool {
         let read = self.0.read();
@@ -78,7 +99,7 @@ impl<'gc> Domain<'gc> {
     pub fn get_defining_script(
         self,
         multiname: &Multiname<'gc>,
-    ) -> Result<Option<(QName<'gc>, Script<'gc>)>, Error> {
+    ) -> Result<Option<(QName<'gc>, Script<'gc>)>, Error<'gc>> {
         let read = self.0.read();
 
         for ns in multiname.namespace_set() {
@@ -115,7 +136,7 @@ impl<'gc> Domain<'gc> {
         self,
         activation: &mut Activation<'_, 'gc, '_>,
         name: QName<'gc>,
-    ) -> Result<Value<'gc>, Error> {
+    ) -> Result<Value<'gc>, Error<'gc>> {
         let (name, mut script) = self
             .get_defining_script(&name.clone().into())?
             .ok_or_else(|| format!("MovieClip Symbol {} does not exist", name.local_name()))?;
@@ -124,6 +145,26 @@ impl<'gc> Domain<'gc> {
         globals.get_property(globals, &name, activation)
     }
 
+    /// Retrieve a value from this domain by its fully qualified name, as
+    /// scripts look them up with `getDefinitionByName`.
+    ///
+    /// This returns a `ReferenceError` if the name is not defined.
+    pub fn get_defined_value_by_name(
+        self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        name: AvmString<'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let qname = QName::from_qualified_name(&name, activation.context.gc_context);
+        if !self.has_definition(qname.clone()) {
+            return Err(Error::new(
+                ErrorKind::ReferenceError,
+                format!("Error #1065: Variable {} is not defined.", name),
+            ));
+        }
+
+        self.get_defined_value(activation, qname)
+    }
+
     /// Export a definition from a script into the current application domain.
     ///
     /// This returns an error if the name is already defined in the current or
@@ -133,7 +174,7 @@ impl<'gc> Domain<'gc> {
         name: QName<'gc>,
         script: Script<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         if self.has_definition(name.clone()) {
             return Err(format!(
                 "VerifyError: Attempted to redefine existing name {}",
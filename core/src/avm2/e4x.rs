@@ -0,0 +1,822 @@
+//! ECMA-357 (E4X) XML trees
+//!
+//! Unlike the AVM1 XML DOM in `crate::xml`, E4X trees have no document node,
+//! hold attributes as nodes of their own, and are shared between every `XML`
+//! and `XMLList` object that refers to them.
+
+use crate::avm2::{Error, ErrorKind};
+use gc_arena::{Collect, GcCell, MutationContext};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::fmt;
+
+/// The settings that control how XML is parsed and printed, as exposed by the
+/// static properties of the `XML` class.
+#[derive(Clone, Collect, Debug)]
+#[collect(require_static)]
+pub struct E4XSettings {
+    pub ignore_comments: bool,
+    pub ignore_processing_instructions: bool,
+    pub ignore_whitespace: bool,
+    pub pretty_printing: bool,
+    pub pretty_indent: i32,
+}
+
+impl Default for E4XSettings {
+    fn default() -> Self {
+        Self {
+            ignore_comments: true,
+            ignore_processing_instructions: true,
+            ignore_whitespace: true,
+            pretty_printing: true,
+            pretty_indent: 2,
+        }
+    }
+}
+
+/// A namespace declared on an element.
+#[derive(Clone, Collect, Debug, PartialEq, Eq)]
+#[collect(require_static)]
+pub struct E4XNamespace {
+    /// The prefix bound to the namespace, which is empty for the default
+    /// namespace.
+    pub prefix: String,
+
+    pub uri: String,
+}
+
+/// The name of an element, attribute or processing instruction.
+#[derive(Clone, Collect, Debug, PartialEq, Eq)]
+#[collect(require_static)]
+pub struct E4XName {
+    pub uri: String,
+
+    /// The prefix the name was written with, which is empty if it had none.
+    pub prefix: String,
+
+    pub local_name: String,
+}
+
+impl E4XName {
+    pub fn new(uri: impl Into<String>, local_name: impl Into<String>) -> Self {
+        Self {
+            uri: uri.into(),
+            prefix: "".to_string(),
+            local_name: local_name.into(),
+        }
+    }
+
+    /// The name as written in markup, including its prefix.
+    fn qualified_name(&self) -> String {
+        if self.prefix.is_empty() {
+            self.local_name.clone()
+        } else {
+            format!("{}:{}", self.prefix, self.local_name)
+        }
+    }
+}
+
+/// A node in an E4X tree.
+#[derive(Copy, Clone, Collect)]
+#[collect(no_drop)]
+pub struct E4XNode<'gc>(GcCell<'gc, E4XNodeData<'gc>>);
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct E4XNodeData<'gc> {
+    /// The element this node is a child or attribute of, if any.
+    parent: Option<E4XNode<'gc>>,
+
+    kind: E4XNodeKind<'gc>,
+}
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub enum E4XNodeKind<'gc> {
+    Text(String),
+    Comment(String),
+    ProcessingInstruction {
+        name: E4XName,
+        contents: String,
+    },
+    Attribute {
+        name: E4XName,
+        value: String,
+    },
+    Element {
+        name: E4XName,
+
+        /// The namespaces declared on this element.
+        namespaces: Vec<E4XNamespace>,
+
+        attributes: Vec<E4XNode<'gc>>,
+        children: Vec<E4XNode<'gc>>,
+    },
+}
+
+/// Convert a markup error into the error `XML` and `XMLList` throw.
+fn parse_error<'gc>(error: impl fmt::Display) -> Error<'gc> {
+    log::warn!("XML parse error: {}", error);
+
+    Error::new(
+        ErrorKind::TypeError,
+        "Error #1090: XML parser failure: element is malformed.",
+    )
+}
+
+/// Escape text for use as element content.
+pub fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Escape text for use as an attribute value.
+pub fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('"', "&quot;")
+        .replace('\n', "&#xA;")
+        .replace('\r', "&#xD;")
+        .replace('\t', "&#x9;")
+}
+
+impl<'gc> E4XNode<'gc> {
+    fn new(mc: MutationContext<'gc, '_>, kind: E4XNodeKind<'gc>) -> Self {
+        E4XNode(GcCell::allocate(mc, E4XNodeData { parent: None, kind }))
+    }
+
+    /// Construct a text node.
+    pub fn new_text(mc: MutationContext<'gc, '_>, text: impl Into<String>) -> Self {
+        Self::new(mc, E4XNodeKind::Text(text.into()))
+    }
+
+    /// Construct an empty element.
+    pub fn new_element(mc: MutationContext<'gc, '_>, name: E4XName) -> Self {
+        Self::new(
+            mc,
+            E4XNodeKind::Element {
+                name,
+                namespaces: Vec::new(),
+                attributes: Vec::new(),
+                children: Vec::new(),
+            },
+        )
+    }
+
+    /// Construct an attribute that does not yet belong to an element.
+    pub fn new_attribute(
+        mc: MutationContext<'gc, '_>,
+        name: E4XName,
+        value: impl Into<String>,
+    ) -> Self {
+        Self::new(
+            mc,
+            E4XNodeKind::Attribute {
+                name,
+                value: value.into(),
+            },
+        )
+    }
+
+    /// Parse markup into a list of parentless nodes.
+    ///
+    /// Markup is parsed as the content of an element, so it may hold any
+    /// number of nodes, including none at all.
+    pub fn parse(
+        mc: MutationContext<'gc, '_>,
+        markup: &str,
+        settings: &E4XSettings,
+    ) -> Result<Vec<Self>, Error<'gc>> {
+        let wrapped = format!("<parent>{}</parent>", markup);
+        let mut parser = Reader::from_str(&wrapped);
+        let mut buf = Vec::new();
+        let mut open_tags: Vec<E4XNode<'gc>> = Vec::new();
+        let mut root = None;
+
+        loop {
+            let event = parser.read_event(&mut buf).map_err(parse_error)?;
+
+            match event {
+                Event::Start(bs) => {
+                    let element = Self::from_start_event(mc, &bs, &open_tags)?;
+                    match open_tags.last() {
+                        Some(parent) => parent.append_child(mc, element),
+                        None => root = Some(element),
+                    }
+
+                    open_tags.push(element);
+                }
+                Event::Empty(bs) => {
+                    let element = Self::from_start_event(mc, &bs, &open_tags)?;
+                    if let Some(parent) = open_tags.last() {
+                        parent.append_child(mc, element);
+                    }
+                }
+                Event::End(_) => {
+                    open_tags.pop();
+                }
+                Event::Text(bt) => {
+                    let text = String::from_utf8(bt.unescaped().map_err(parse_error)?.into_owned())
+                        .map_err(parse_error)?;
+                    let text = if settings.ignore_whitespace {
+                        text.trim().to_string()
+                    } else {
+                        text
+                    };
+
+                    if let (Some(parent), false) = (open_tags.last(), text.is_empty()) {
+                        parent.append_child(mc, Self::new_text(mc, text));
+                    }
+                }
+                Event::CData(bt) => {
+                    let text = String::from_utf8(bt.escaped().to_vec()).map_err(parse_error)?;
+                    if let Some(parent) = open_tags.last() {
+                        parent.append_child(mc, Self::new_text(mc, text));
+                    }
+                }
+                Event::Comment(bt) if !settings.ignore_comments => {
+                    let text = String::from_utf8(bt.escaped().to_vec()).map_err(parse_error)?;
+                    if let Some(parent) = open_tags.last() {
+                        parent.append_child(mc, Self::new(mc, E4XNodeKind::Comment(text)));
+                    }
+                }
+                Event::PI(bt) if !settings.ignore_processing_instructions => {
+                    let text = String::from_utf8(bt.escaped().to_vec()).map_err(parse_error)?;
+                    let mut parts = text.splitn(2, char::is_whitespace);
+                    let name = E4XName::new("", parts.next().unwrap_or(""));
+                    let contents = parts.next().unwrap_or("").trim_start().to_string();
+
+                    if let Some(parent) = open_tags.last() {
+                        parent.append_child(
+                            mc,
+                            Self::new(mc, E4XNodeKind::ProcessingInstruction { name, contents }),
+                        );
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+
+            buf.clear();
+        }
+
+        if !open_tags.is_empty() {
+            return Err(parse_error("unterminated element"));
+        }
+
+        let children = root.map(|root| root.children()).unwrap_or_default();
+        for child in children.iter() {
+            child.0.write(mc).parent = None;
+        }
+
+        Ok(children)
+    }
+
+    /// Construct an element from a start tag, resolving the prefixes of its
+    /// name and attributes against the namespaces declared on it and on the
+    /// elements it is nested in.
+    fn from_start_event(
+        mc: MutationContext<'gc, '_>,
+        bs: &BytesStart<'_>,
+        open_tags: &[E4XNode<'gc>],
+    ) -> Result<Self, Error<'gc>> {
+        let mut namespaces = Vec::new();
+        let mut raw_attributes = Vec::new();
+
+        for attribute in bs.attributes() {
+            let attribute = attribute.map_err(parse_error)?;
+            let key = String::from_utf8(attribute.key.to_vec()).map_err(parse_error)?;
+            let value = String::from_utf8(
+                attribute
+                    .unescaped_value()
+                    .map_err(parse_error)?
+                    .into_owned(),
+            )
+            .map_err(parse_error)?;
+
+            if key == "xmlns" {
+                namespaces.push(E4XNamespace {
+                    prefix: "".to_string(),
+                    uri: value,
+                });
+            } else if let Some(prefix) = key.strip_prefix("xmlns:") {
+                namespaces.push(E4XNamespace {
+                    prefix: prefix.to_string(),
+                    uri: value,
+                });
+            } else {
+                raw_attributes.push((key, value));
+            }
+        }
+
+        let resolve = |qualified_name: &str, is_attribute: bool| -> Result<E4XName, Error<'gc>> {
+            let (prefix, local_name) = match qualified_name.find(':') {
+                Some(colon) => (&qualified_name[..colon], &qualified_name[colon + 1..]),
+                None => ("", qualified_name),
+            };
+
+            // Unprefixed attributes are never in the default namespace.
+            if prefix.is_empty() && is_attribute {
+                return Ok(E4XName::new("", local_name));
+            }
+
+            let declared = namespaces
+                .iter()
+                .rev()
+                .find(|ns| ns.prefix == prefix)
+                .map(|ns| ns.uri.clone());
+            let uri = declared.or_else(|| {
+                open_tags
+                    .iter()
+                    .rev()
+                    .find_map(|element| element.lookup_uri(prefix))
+            });
+            let uri = match uri {
+                Some(uri) => uri,
+                None if prefix.is_empty() => "".to_string(),
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::TypeError,
+                        format!(
+                            "Error #1083: The prefix \"{}\" for element \"{}\" is not bound.",
+                            prefix, local_name
+                        ),
+                    ));
+                }
+            };
+
+            Ok(E4XName {
+                uri,
+                prefix: prefix.to_string(),
+                local_name: local_name.to_string(),
+            })
+        };
+
+        let tag_name = String::from_utf8(bs.name().to_vec()).map_err(parse_error)?;
+        let name = resolve(&tag_name, false)?;
+        let mut attributes = Vec::new();
+        for (key, value) in raw_attributes {
+            attributes.push(Self::new_attribute(mc, resolve(&key, true)?, value));
+        }
+
+        let element = Self::new(
+            mc,
+            E4XNodeKind::Element {
+                name,
+                namespaces,
+                attributes: attributes.clone(),
+                children: Vec::new(),
+            },
+        );
+
+        for attribute in attributes {
+            attribute.0.write(mc).parent = Some(element);
+        }
+
+        Ok(element)
+    }
+
+    /// Find the URI a prefix is bound to on this element.
+    fn lookup_uri(self, prefix: &str) -> Option<String> {
+        match &self.0.read().kind {
+            E4XNodeKind::Element { namespaces, .. } => namespaces
+                .iter()
+                .rev()
+                .find(|ns| ns.prefix == prefix)
+                .map(|ns| ns.uri.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn ptr_eq(a: E4XNode<'gc>, b: E4XNode<'gc>) -> bool {
+        GcCell::ptr_eq(a.0, b.0)
+    }
+
+    pub fn parent(self) -> Option<E4XNode<'gc>> {
+        self.0.read().parent
+    }
+
+    /// The kind of this node, as returned by `XML.nodeKind`.
+    pub fn node_kind(self) -> &'static str {
+        match &self.0.read().kind {
+            E4XNodeKind::Text(_) => "text",
+            E4XNodeKind::Comment(_) => "comment",
+            E4XNodeKind::ProcessingInstruction { .. } => "processing-instruction",
+            E4XNodeKind::Attribute { .. } => "attribute",
+            E4XNodeKind::Element { .. } => "element",
+        }
+    }
+
+    pub fn is_element(self) -> bool {
+        matches!(self.0.read().kind, E4XNodeKind::Element { .. })
+    }
+
+    pub fn is_text(self) -> bool {
+        matches!(self.0.read().kind, E4XNodeKind::Text(_))
+    }
+
+    pub fn is_comment(self) -> bool {
+        matches!(self.0.read().kind, E4XNodeKind::Comment(_))
+    }
+
+    pub fn is_attribute(self) -> bool {
+        matches!(self.0.read().kind, E4XNodeKind::Attribute { .. })
+    }
+
+    /// The name of this node, if it is an element, attribute or processing
+    /// instruction.
+    pub fn name(self) -> Option<E4XName> {
+        match &self.0.read().kind {
+            E4XNodeKind::ProcessingInstruction { name, .. }
+            | E4XNodeKind::Attribute { name, .. }
+            | E4XNodeKind::Element { name, .. } => Some(name.clone()),
+            _ => None,
+        }
+    }
+
+    /// Determine if this node is named with the given local name and URI,
+    /// where `None` matches any local name or URI.
+    pub fn matches_name(self, local_name: Option<&str>, uri: Option<&str>) -> bool {
+        match self.name() {
+            Some(name) => {
+                local_name.map(|l| l == name.local_name).unwrap_or(true)
+                    && uri.map(|u| u == name.uri).unwrap_or(true)
+            }
+            None => false,
+        }
+    }
+
+    /// The namespaces declared on this element.
+    pub fn namespace_declarations(self) -> Vec<E4XNamespace> {
+        match &self.0.read().kind {
+            E4XNodeKind::Element { namespaces, .. } => namespaces.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// All of the namespaces in scope on this node, innermost first, with
+    /// shadowed prefixes omitted.
+    pub fn in_scope_namespaces(self) -> Vec<E4XNamespace> {
+        let mut in_scope: Vec<E4XNamespace> = Vec::new();
+        let mut node = Some(self);
+
+        while let Some(current) = node {
+            for ns in current.namespace_declarations().into_iter().rev() {
+                if !in_scope.iter().any(|other| other.prefix == ns.prefix) {
+                    in_scope.push(ns);
+                }
+            }
+
+            node = current.parent();
+        }
+
+        in_scope
+    }
+
+    /// The child nodes of this element.
+    pub fn children(self) -> Vec<E4XNode<'gc>> {
+        match &self.0.read().kind {
+            E4XNodeKind::Element { children, .. } => children.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The attributes of this element.
+    pub fn attributes(self) -> Vec<E4XNode<'gc>> {
+        match &self.0.read().kind {
+            E4XNodeKind::Element { attributes, .. } => attributes.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Replace the child nodes of this element.
+    ///
+    /// The new children are adopted by this element. Nodes that aren't
+    /// elements can't hold children, and are left unchanged.
+    pub fn set_children(self, mc: MutationContext<'gc, '_>, new_children: Vec<E4XNode<'gc>>) {
+        if let E4XNodeKind::Element { children, .. } = &mut self.0.write(mc).kind {
+            for child in new_children.iter() {
+                child.0.write(mc).parent = Some(self);
+            }
+
+            *children = new_children;
+        }
+    }
+
+    pub fn append_child(self, mc: MutationContext<'gc, '_>, child: E4XNode<'gc>) {
+        let mut children = self.children();
+        children.push(child);
+        self.set_children(mc, children);
+    }
+
+    pub fn prepend_child(self, mc: MutationContext<'gc, '_>, child: E4XNode<'gc>) {
+        let mut children = self.children();
+        children.insert(0, child);
+        self.set_children(mc, children);
+    }
+
+    /// Replace the attributes of this element.
+    pub fn set_attributes(self, mc: MutationContext<'gc, '_>, new_attributes: Vec<E4XNode<'gc>>) {
+        if let E4XNodeKind::Element { attributes, .. } = &mut self.0.write(mc).kind {
+            for attribute in new_attributes.iter() {
+                attribute.0.write(mc).parent = Some(self);
+            }
+
+            *attributes = new_attributes;
+        }
+    }
+
+    /// Set the value of an attribute of this element, adding the attribute if
+    /// it does not exist.
+    pub fn set_attribute(self, mc: MutationContext<'gc, '_>, name: E4XName, value: String) {
+        let mut attributes = self.attributes();
+        let existing = attributes
+            .iter()
+            .find(|attr| attr.matches_name(Some(name.local_name.as_str()), Some(name.uri.as_str())))
+            .cloned();
+
+        match existing {
+            Some(attribute) => {
+                if let E4XNodeKind::Attribute { value: old, .. } = &mut attribute.0.write(mc).kind {
+                    *old = value;
+                }
+            }
+            None => {
+                attributes.push(Self::new_attribute(mc, name, value));
+                self.set_attributes(mc, attributes);
+            }
+        }
+    }
+
+    /// Remove this node from the element it belongs to, if any.
+    pub fn remove(self, mc: MutationContext<'gc, '_>) {
+        let parent = match self.parent() {
+            Some(parent) => parent,
+            None => return,
+        };
+
+        if self.is_attribute() {
+            let mut attributes = parent.attributes();
+            attributes.retain(|other| !Self::ptr_eq(*other, self));
+            parent.set_attributes(mc, attributes);
+        } else {
+            let mut children = parent.children();
+            children.retain(|other| !Self::ptr_eq(*other, self));
+            parent.set_children(mc, children);
+        }
+
+        self.0.write(mc).parent = None;
+    }
+
+    /// The position of this node amongst its parent's children, if it has a
+    /// parent.
+    pub fn child_index(self) -> Option<usize> {
+        let parent = self.parent()?;
+        let children = parent.children();
+
+        children.iter().position(|child| Self::ptr_eq(*child, self))
+    }
+
+    /// Collect the descendants of this node that match a name.
+    ///
+    /// If `attributes` is set, the attributes of this node and its descendant
+    /// elements are collected instead of the elements themselves.
+    pub fn descendants(
+        self,
+        local_name: Option<&str>,
+        uri: Option<&str>,
+        attributes: bool,
+        out: &mut Vec<E4XNode<'gc>>,
+    ) {
+        if attributes {
+            for attribute in self.attributes() {
+                if attribute.matches_name(local_name, uri) {
+                    out.push(attribute);
+                }
+            }
+        }
+
+        for child in self.children() {
+            if !attributes && (local_name.is_none() || child.matches_name(local_name, uri)) {
+                out.push(child);
+            }
+
+            child.descendants(local_name, uri, attributes, out);
+        }
+    }
+
+    /// Determine if this node holds no elements, as `XML.hasSimpleContent`
+    /// does.
+    pub fn has_simple_content(self) -> bool {
+        match &self.0.read().kind {
+            E4XNodeKind::Comment(_) | E4XNodeKind::ProcessingInstruction { .. } => false,
+            E4XNodeKind::Element { children, .. } => {
+                !children.iter().any(|child| child.is_element())
+            }
+            _ => true,
+        }
+    }
+
+    /// The string value of this node.
+    ///
+    /// Text and attributes yield their contents, and elements with simple
+    /// content yield their text. Everything else yields its markup.
+    pub fn string_value(self, settings: &E4XSettings) -> String {
+        match &self.0.read().kind {
+            E4XNodeKind::Text(text) => return text.clone(),
+            E4XNodeKind::Attribute { value, .. } => return value.clone(),
+            _ => {}
+        }
+
+        if self.has_simple_content() {
+            self.children()
+                .into_iter()
+                .filter(|child| child.is_text())
+                .map(|child| child.string_value(settings))
+                .collect()
+        } else {
+            self.to_xml_string(settings)
+        }
+    }
+
+    /// Make a deep copy of this node, without a parent.
+    pub fn deep_copy(self, mc: MutationContext<'gc, '_>) -> E4XNode<'gc> {
+        let kind = self.0.read().kind.clone();
+        let copy = Self::new(mc, kind);
+
+        if copy.is_element() {
+            let attributes = self
+                .attributes()
+                .into_iter()
+                .map(|attribute| attribute.deep_copy(mc))
+                .collect();
+            let children = self
+                .children()
+                .into_iter()
+                .map(|child| child.deep_copy(mc))
+                .collect();
+
+            copy.set_attributes(mc, attributes);
+            copy.set_children(mc, children);
+        }
+
+        copy
+    }
+
+    /// Print this node as markup.
+    ///
+    /// Elements declare any namespaces they inherit from elements they are
+    /// nested in, so that the markup stands alone.
+    pub fn to_xml_string(self, settings: &E4XSettings) -> String {
+        let mut out = String::new();
+        let inherited = self
+            .parent()
+            .map(|parent| parent.in_scope_namespaces())
+            .unwrap_or_default();
+
+        self.write_xml(&mut out, settings, &inherited, 0);
+
+        out
+    }
+
+    fn write_xml(
+        self,
+        out: &mut String,
+        settings: &E4XSettings,
+        inherited: &[E4XNamespace],
+        indent: usize,
+    ) {
+        let read = self.0.read();
+        let (name, namespaces, attributes, children) = match &read.kind {
+            E4XNodeKind::Text(text) => {
+                if settings.pretty_printing {
+                    out.push_str(&escape_text(text.trim()));
+                } else {
+                    out.push_str(&escape_text(text));
+                }
+
+                return;
+            }
+            E4XNodeKind::Attribute { value, .. } => {
+                out.push_str(&escape_attribute(value));
+                return;
+            }
+            E4XNodeKind::Comment(text) => {
+                out.push_str(&format!("<!--{}-->", text));
+                return;
+            }
+            E4XNodeKind::ProcessingInstruction { name, contents } => {
+                out.push_str(&format!("<?{} {}?>", name.local_name, contents));
+                return;
+            }
+            E4XNodeKind::Element {
+                name,
+                namespaces,
+                attributes,
+                children,
+            } => (name, namespaces, attributes, children),
+        };
+
+        out.push('<');
+        out.push_str(&name.qualified_name());
+
+        let mut declarations = namespaces.clone();
+        for ns in inherited {
+            if !declarations.iter().any(|other| other.prefix == ns.prefix) {
+                declarations.push(ns.clone());
+            }
+        }
+
+        for ns in declarations {
+            if ns.prefix.is_empty() {
+                out.push_str(&format!(" xmlns=\"{}\"", escape_attribute(&ns.uri)));
+            } else {
+                out.push_str(&format!(
+                    " xmlns:{}=\"{}\"",
+                    ns.prefix,
+                    escape_attribute(&ns.uri)
+                ));
+            }
+        }
+
+        for attribute in attributes {
+            if let Some(name) = attribute.name() {
+                out.push_str(&format!(" {}=\"", name.qualified_name()));
+                attribute.write_xml(out, settings, &[], indent);
+                out.push('"');
+            }
+        }
+
+        if children.is_empty() {
+            out.push_str("/>");
+            return;
+        }
+
+        out.push('>');
+
+        let indent_children =
+            settings.pretty_printing && (children.len() > 1 || !children[0].is_text());
+        let child_indent = indent + settings.pretty_indent.max(0) as usize;
+
+        for child in children {
+            if indent_children {
+                out.push('\n');
+                out.push_str(&" ".repeat(child_indent));
+            }
+
+            child.write_xml(out, settings, &[], child_indent);
+        }
+
+        if indent_children {
+            out.push('\n');
+            out.push_str(&" ".repeat(indent));
+        }
+
+        out.push_str("</");
+        out.push_str(&name.qualified_name());
+        out.push('>');
+    }
+}
+
+impl<'gc> fmt::Debug for E4XNode<'gc> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("E4XNode")
+            .field("ptr", &self.0.as_ptr())
+            .field("kind", &self.node_kind())
+            .field("name", &self.name())
+            .finish()
+    }
+}
+
+/// Determine if a list of nodes holds no elements, as
+/// `XMLList.hasSimpleContent` does.
+pub fn list_has_simple_content(nodes: &[E4XNode<'_>]) -> bool {
+    match nodes {
+        [node] => node.has_simple_content(),
+        nodes => !nodes.iter().any(|node| node.is_element()),
+    }
+}
+
+/// The string value of a list of nodes.
+pub fn list_string_value(nodes: &[E4XNode<'_>], settings: &E4XSettings) -> String {
+    if list_has_simple_content(nodes) {
+        nodes
+            .iter()
+            .filter(|node| !node.is_comment() && node.node_kind() != "processing-instruction")
+            .map(|node| node.string_value(settings))
+            .collect()
+    } else {
+        list_xml_string(nodes, settings)
+    }
+}
+
+/// Print a list of nodes as markup, one node per line.
+pub fn list_xml_string(nodes: &[E4XNode<'_>], settings: &E4XSettings) -> String {
+    nodes
+        .iter()
+        .map(|node| node.to_xml_string(settings))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,134 @@
+//! AVM2 errors
+
+use crate::avm2::activation::Activation;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::TObject;
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use std::fmt;
+
+/// The built-in error class an internal error is thrown to script as.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    Error,
+    ArgumentError,
+    RangeError,
+    ReferenceError,
+    SyntaxError,
+    TypeError,
+    VerifyError,
+    IOError,
+    EOFError,
+    MemoryError,
+}
+
+impl ErrorKind {
+    /// The package and name of the error class.
+    pub fn class_name(self) -> (&'static str, &'static str) {
+        match self {
+            ErrorKind::Error => ("", "Error"),
+            ErrorKind::ArgumentError => ("", "ArgumentError"),
+            ErrorKind::RangeError => ("", "RangeError"),
+            ErrorKind::ReferenceError => ("", "ReferenceError"),
+            ErrorKind::SyntaxError => ("", "SyntaxError"),
+            ErrorKind::TypeError => ("", "TypeError"),
+            ErrorKind::VerifyError => ("", "VerifyError"),
+            ErrorKind::IOError => ("flash.errors", "IOError"),
+            ErrorKind::EOFError => ("flash.errors", "EOFError"),
+            ErrorKind::MemoryError => ("flash.errors", "MemoryError"),
+        }
+    }
+}
+
+/// An error that occurred while running AVM2 code.
+pub enum Error<'gc> {
+    /// A value thrown by ActionScript, which unwinds the stack until script catches it.
+    AvmError(Value<'gc>),
+
+    /// An error raised by the VM or by a builtin.
+    ///
+    /// Errors with a kind are thrown to script as an instance of that built-in error class.
+    /// Errors without one can't be caught.
+    RustError(Option<ErrorKind>, Box<dyn std::error::Error>),
+}
+
+impl<'gc> Error<'gc> {
+    /// Construct an internal error that script can catch as the given error class.
+    pub fn new(kind: ErrorKind, message: impl Into<Box<dyn std::error::Error>>) -> Self {
+        Error::RustError(Some(kind), message.into())
+    }
+
+    /// Converts this error into the value that is thrown to script.
+    ///
+    /// Internal errors without an error kind can't be caught, and are returned as is.
+    pub fn into_thrown_value(
+        self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let (kind, error) = match self {
+            Error::AvmError(value) => return Ok(value),
+            Error::RustError(Some(kind), error) => (kind, error),
+            error => return Err(error),
+        };
+        let (package, class_name) = kind.class_name();
+
+        let globals = activation.context.avm2.global_domain();
+        let mut constructor = globals
+            .get_defined_value(
+                activation,
+                QName::new(Namespace::package(package), class_name),
+            )?
+            .coerce_to_object(activation)?;
+        let proto = constructor
+            .get_property(
+                constructor,
+                &QName::new(Namespace::public(), "prototype"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+        let message = error.to_string();
+
+        // Flash messages start with their error number, which is also the `errorID`.
+        let error_id = message
+            .strip_prefix("Error #")
+            .and_then(|rest| rest.split(':').next())
+            .and_then(|id| id.parse::<i32>().ok())
+            .unwrap_or(0);
+        let args: [Value<'gc>; 2] = [
+            AvmString::new(activation.context.gc_context, message).into(),
+            error_id.into(),
+        ];
+
+        let object = proto.construct(activation, &args)?;
+        constructor.call(Some(object), &args, activation, Some(proto))?;
+
+        Ok(object.into())
+    }
+}
+
+impl fmt::Debug for Error<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::AvmError(value) => f.debug_tuple("AvmError").field(value).finish(),
+            Error::RustError(kind, error) => {
+                f.debug_tuple("RustError").field(kind).field(error).finish()
+            }
+        }
+    }
+}
+
+impl fmt::Display for Error<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::AvmError(value) => write!(f, "Uncaught exception: {:?}", value),
+            Error::RustError(Some(kind), error) => write!(f, "{}: {}", kind.class_name().1, error),
+            Error::RustError(None, error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl<'gc, E: Into<Box<dyn std::error::Error>>> From<E> for Error<'gc> {
+    fn from(error: E) -> Self {
+        Error::RustError(None, error.into())
+    }
+}
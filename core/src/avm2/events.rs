@@ -46,6 +46,49 @@ pub enum PropagationMode {
     StopImmediatePropagation,
 }
 
+/// Data specific to a particular class of event.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub enum EventData<'gc> {
+    /// A plain `Event` with no further data.
+    Empty,
+
+    /// Data for a `MouseEvent`.
+    Mouse {
+        local_x: f64,
+        local_y: f64,
+        related_object: Option<Object<'gc>>,
+        ctrl_key: bool,
+        alt_key: bool,
+        shift_key: bool,
+        button_down: bool,
+        delta: i32,
+    },
+
+    /// Data for a `KeyboardEvent`.
+    Keyboard {
+        char_code: u32,
+        key_code: u32,
+        key_location: u32,
+        ctrl_key: bool,
+        alt_key: bool,
+        shift_key: bool,
+    },
+
+    /// Data for a `FocusEvent`.
+    Focus {
+        related_object: Option<Object<'gc>>,
+        shift_key: bool,
+        key_code: u32,
+    },
+
+    /// Data for a `ProgressEvent`.
+    Progress { bytes_loaded: f64, bytes_total: f64 },
+
+    /// Data for an `IOErrorEvent`.
+    IOError { text: AvmString<'gc>, error_id: i32 },
+}
+
 /// Represents data fields of an event that can be fired on an object that
 /// implements `IEventDispatcher`.
 #[derive(Clone, Collect, Debug)]
@@ -76,6 +119,9 @@ pub struct Event<'gc> {
 
     /// The name of the event being triggered.
     event_type: AvmString<'gc>,
+
+    /// Data specific to the class of this event.
+    event_data: EventData<'gc>,
 }
 
 impl<'gc> Event<'gc> {
@@ -93,9 +139,20 @@ impl<'gc> Event<'gc> {
             event_phase: EventPhase::Bubbling,
             target: None,
             event_type: event_type.into(),
+            event_data: EventData::Empty,
         }
     }
 
+    /// Construct a new event of a given type, carrying class-specific data.
+    pub fn with_data<S>(event_type: S, event_data: EventData<'gc>) -> Self
+    where
+        S: Into<AvmString<'gc>>,
+    {
+        let mut event = Self::new(event_type);
+        event.event_data = event_data;
+        event
+    }
+
     pub fn event_type(&self) -> AvmString<'gc> {
         self.event_type
     }
@@ -174,6 +231,14 @@ impl<'gc> Event<'gc> {
     pub fn set_current_target(&mut self, current_target: Object<'gc>) {
         self.current_target = Some(current_target)
     }
+
+    pub fn event_data(&self) -> &EventData<'gc> {
+        &self.event_data
+    }
+
+    pub fn event_data_mut(&mut self) -> &mut EventData<'gc> {
+        &mut self.event_data
+    }
 }
 
 /// A set of handlers organized by event type, priority, and order added.
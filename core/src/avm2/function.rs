@@ -84,7 +84,7 @@ impl<'gc> Executable<'gc> {
         activation: &mut Activation<'_, 'gc, '_>,
         base_proto: Option<Object<'gc>>,
         callee: Object<'gc>,
-    ) -> Result<Value<'gc>, Error> {
+    ) -> Result<Value<'gc>, Error<'gc>> {
         match self {
             Executable::Native(nf, receiver) => {
                 let receiver = receiver.or(unbound_reciever);
@@ -20,16 +20,31 @@ use std::f64::NAN;
 mod array;
 mod boolean;
 mod class;
+mod date;
+mod error;
 mod flash;
 mod function;
 mod global_scope;
 mod int;
+mod json;
 mod math;
 mod namespace;
 mod number;
 mod object;
+mod qname;
+mod regexp;
 mod string;
 mod r#uint;
+mod vector;
+mod xml;
+mod xml_list;
+
+pub use crate::avm2::globals::flash::events::eventdispatcher::dispatch_event;
+pub use crate::avm2::globals::vector::apply_type;
+pub use crate::avm2::globals::xml::{
+    e4x_delete_property, e4x_descendants, e4x_get_property, e4x_set_property, new_xml_list,
+    xml_nodes,
+};
 
 const NS_RUFFLE_INTERNAL: &str = "https://ruffle.rs/AS3/impl/";
 
@@ -37,7 +52,7 @@ fn trace<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     let mut message = String::new();
     if !args.is_empty() {
         message.push_str(&args[0].clone().coerce_to_string(activation)?);
@@ -56,7 +71,7 @@ fn is_finite<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(val) = args.get(0) {
         Ok(val.coerce_to_number(activation)?.is_finite().into())
     } else {
@@ -68,7 +83,7 @@ fn is_nan<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(val) = args.get(0) {
         Ok(val.coerce_to_number(activation)?.is_nan().into())
     } else {
@@ -94,8 +109,28 @@ pub struct SystemPrototypes<'gc> {
     pub movieclip: Object<'gc>,
     pub framelabel: Object<'gc>,
     pub scene: Object<'gc>,
+    pub graphics: Object<'gc>,
+    pub point: Object<'gc>,
+    pub rectangle: Object<'gc>,
+    pub matrix: Object<'gc>,
+    pub colortransform: Object<'gc>,
+    pub transform: Object<'gc>,
     pub application_domain: Object<'gc>,
     pub event: Object<'gc>,
+    pub mouseevent: Object<'gc>,
+    pub keyboardevent: Object<'gc>,
+    pub focusevent: Object<'gc>,
+    pub timerevent: Object<'gc>,
+    pub progressevent: Object<'gc>,
+    pub ioerrorevent: Object<'gc>,
+    pub loaderinfo: Object<'gc>,
+    pub bytearray: Object<'gc>,
+    pub regexp: Object<'gc>,
+    pub vector: Object<'gc>,
+    pub xml: Object<'gc>,
+    pub xml_list: Object<'gc>,
+    pub qname: Object<'gc>,
+    pub date: Object<'gc>,
 }
 
 impl<'gc> SystemPrototypes<'gc> {
@@ -127,8 +162,28 @@ impl<'gc> SystemPrototypes<'gc> {
             movieclip: empty,
             framelabel: empty,
             scene: empty,
+            graphics: empty,
+            point: empty,
+            rectangle: empty,
+            matrix: empty,
+            colortransform: empty,
+            transform: empty,
             application_domain: empty,
             event: empty,
+            mouseevent: empty,
+            keyboardevent: empty,
+            focusevent: empty,
+            timerevent: empty,
+            progressevent: empty,
+            ioerrorevent: empty,
+            loaderinfo: empty,
+            bytearray: empty,
+            regexp: empty,
+            vector: empty,
+            xml: empty,
+            xml_list: empty,
+            qname: empty,
+            date: empty,
         }
     }
 }
@@ -142,7 +197,7 @@ fn function<'gc>(
     fn_proto: Object<'gc>,
     mut domain: Domain<'gc>,
     script: Script<'gc>,
-) -> Result<(), Error> {
+) -> Result<(), Error<'gc>> {
     let name = QName::new(Namespace::package(package), name);
     let as3fn = FunctionObject::from_builtin(mc, nf, fn_proto).into();
     domain.export_definition(name.clone(), script, mc)?;
@@ -166,7 +221,7 @@ fn dynamic_class<'gc>(
     class: GcCell<'gc, Class<'gc>>,
     mut domain: Domain<'gc>,
     script: Script<'gc>,
-) -> Result<(), Error> {
+) -> Result<(), Error<'gc>> {
     let name = class.read().name().clone();
 
     script
@@ -188,14 +243,14 @@ fn class<'gc, Deriver>(
     custom_derive: Deriver,
     mut domain: Domain<'gc>,
     script: Script<'gc>,
-) -> Result<Object<'gc>, Error>
+) -> Result<Object<'gc>, Error<'gc>>
 where
     Deriver: FnOnce(
         Object<'gc>,
         &mut Activation<'_, 'gc, '_>,
         GcCell<'gc, Class<'gc>>,
         Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<Object<'gc>, Error>,
+    ) -> Result<Object<'gc>, Error<'gc>>,
 {
     let mut global = script.init().1;
     let global_scope = Scope::push_scope(global.get_scope(), global, activation.context.gc_context);
@@ -206,7 +261,7 @@ where
             .resolve_multiname(sc_name)?
             .unwrap_or_else(|| QName::dynamic_name("Object"));
 
-        let super_class: Result<Object<'gc>, Error> = global
+        let super_class: Result<Object<'gc>, Error<'gc>> = global
             .get_property(global, &super_name, activation)?
             .coerce_to_object(activation)
             .map_err(|_e| {
@@ -251,7 +306,7 @@ fn primitive_deriver<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     class: GcCell<'gc, Class<'gc>>,
     scope: Option<GcCell<'gc, Scope<'gc>>>,
-) -> Result<Object<'gc>, Error> {
+) -> Result<Object<'gc>, Error<'gc>> {
     PrimitiveObject::derive(base_proto, activation.context.gc_context, class, scope)
 }
 
@@ -260,7 +315,7 @@ fn namespace_deriver<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     class: GcCell<'gc, Class<'gc>>,
     scope: Option<GcCell<'gc, Scope<'gc>>>,
-) -> Result<Object<'gc>, Error> {
+) -> Result<Object<'gc>, Error<'gc>> {
     NamespaceObject::derive(base_proto, activation.context.gc_context, class, scope)
 }
 
@@ -269,7 +324,7 @@ fn array_deriver<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     class: GcCell<'gc, Class<'gc>>,
     scope: Option<GcCell<'gc, Scope<'gc>>>,
-) -> Result<Object<'gc>, Error> {
+) -> Result<Object<'gc>, Error<'gc>> {
     ArrayObject::derive(base_proto, activation.context.gc_context, class, scope)
 }
 
@@ -278,7 +333,7 @@ fn stage_deriver<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     class: GcCell<'gc, Class<'gc>>,
     scope: Option<GcCell<'gc, Scope<'gc>>>,
-) -> Result<Object<'gc>, Error> {
+) -> Result<Object<'gc>, Error<'gc>> {
     StageObject::derive(base_proto, activation.context.gc_context, class, scope)
 }
 
@@ -287,7 +342,7 @@ fn appdomain_deriver<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     class: GcCell<'gc, Class<'gc>>,
     scope: Option<GcCell<'gc, Scope<'gc>>>,
-) -> Result<Object<'gc>, Error> {
+) -> Result<Object<'gc>, Error<'gc>> {
     let domain = scope
         .unwrap()
         .read()
@@ -312,7 +367,7 @@ fn constant<'gc>(
     value: Value<'gc>,
     mut domain: Domain<'gc>,
     script: Script<'gc>,
-) -> Result<(), Error> {
+) -> Result<(), Error<'gc>> {
     let name = QName::new(Namespace::package(package), name);
     domain.export_definition(name.clone(), script, mc)?;
     script.init().1.install_const(mc, name, 0, value);
@@ -329,7 +384,7 @@ fn constant<'gc>(
 pub fn load_player_globals<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     domain: Domain<'gc>,
-) -> Result<(), Error> {
+) -> Result<(), Error<'gc>> {
     let mc = activation.context.gc_context;
     let gs = DomainObject::from_domain(mc, None, domain);
     let script = Script::empty_script(mc, gs);
@@ -419,6 +474,48 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    sp.regexp = class(
+        activation,
+        regexp::create_class(mc),
+        regexp::regexp_deriver,
+        domain,
+        script,
+    )?;
+    sp.vector = class(
+        activation,
+        vector::create_class(mc),
+        vector::vector_deriver,
+        domain,
+        script,
+    )?;
+    sp.xml = class(
+        activation,
+        xml::create_class(mc),
+        xml::xml_deriver,
+        domain,
+        script,
+    )?;
+    sp.xml_list = class(
+        activation,
+        xml_list::create_class(mc),
+        xml_list::xml_list_deriver,
+        domain,
+        script,
+    )?;
+    sp.qname = class(
+        activation,
+        qname::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    sp.date = class(
+        activation,
+        date::create_class(mc),
+        date::date_deriver,
+        domain,
+        script,
+    )?;
 
     // At this point we have to hide the fact that we had to create the player
     // globals scope *before* the `Object` class
@@ -441,6 +538,85 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    class(
+        activation,
+        json::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        error::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        error::create_argument_error_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        error::create_range_error_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        error::create_reference_error_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        error::create_syntax_error_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        error::create_type_error_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        error::create_verify_error_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.errors`
+    class(
+        activation,
+        flash::errors::ioerror::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::errors::eoferror::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::errors::memoryerror::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
 
     // package `flash.system`
     activation
@@ -456,6 +632,13 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    class(
+        activation,
+        flash::system::loadercontext::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
 
     // package `flash.events`
     activation
@@ -485,6 +668,84 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .mouseevent = class(
+        activation,
+        flash::events::mouseevent::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .keyboardevent = class(
+        activation,
+        flash::events::keyboardevent::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .focusevent = class(
+        activation,
+        flash::events::focusevent::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .timerevent = class(
+        activation,
+        flash::events::timerevent::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .progressevent = class(
+        activation,
+        flash::events::progressevent::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .ioerrorevent = class(
+        activation,
+        flash::events::ioerrorevent::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
 
     // package `flash.display`
     class(
@@ -494,6 +755,13 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    class(
+        activation,
+        flash::display::shape::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
     class(
         activation,
         flash::display::interactiveobject::create_class(mc),
@@ -508,6 +776,13 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    class(
+        activation,
+        flash::display::loader::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
     class(
         activation,
         flash::display::sprite::create_class(mc),
@@ -554,6 +829,226 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .graphics = class(
+        activation,
+        flash::display::graphics::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .loaderinfo = class(
+        activation,
+        flash::display::loaderinfo::create_class(mc),
+        flash::display::loaderinfo::loaderinfo_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.net`
+    class(
+        activation,
+        flash::net::urlrequest::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.geom`
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .point = class(
+        activation,
+        flash::geom::point::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .rectangle = class(
+        activation,
+        flash::geom::rectangle::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .matrix = class(
+        activation,
+        flash::geom::matrix::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .colortransform = class(
+        activation,
+        flash::geom::colortransform::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .transform = class(
+        activation,
+        flash::geom::transform::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::geom::matrix3d::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.utils`
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .bytearray = class(
+        activation,
+        flash::utils::bytearray::create_class(mc),
+        flash::utils::bytearray::bytearray_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::utils::dictionary::create_class(mc),
+        flash::utils::dictionary::dictionary_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::utils::timer::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    function(
+        mc,
+        "flash.utils",
+        "getTimer",
+        flash::utils::get_timer,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.utils",
+        "setTimeout",
+        flash::utils::set_timeout,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.utils",
+        "clearTimeout",
+        flash::utils::clear_timeout,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.utils",
+        "setInterval",
+        flash::utils::set_interval,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.utils",
+        "clearInterval",
+        flash::utils::clear_interval,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.utils",
+        "getDefinitionByName",
+        flash::utils::get_definition_by_name,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.utils",
+        "getQualifiedClassName",
+        flash::utils::get_qualified_class_name,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.utils",
+        "getQualifiedSuperclassName",
+        flash::utils::get_qualified_superclass_name,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.utils",
+        "describeType",
+        flash::utils::describe_type::describe_type,
+        fn_proto,
+        domain,
+        script,
+    )?;
 
     Ok(())
 }
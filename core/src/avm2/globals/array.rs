@@ -20,7 +20,7 @@ pub fn instance_init<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         activation.super_init(this, &[])?;
 
@@ -54,7 +54,7 @@ pub fn class_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(Value::Undefined)
 }
 
@@ -63,7 +63,7 @@ pub fn length<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         if let Some(array) = this.as_array_storage() {
             return Ok(array.length().into());
@@ -77,7 +77,7 @@ pub fn length<'gc>(
 pub fn build_array<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     array: ArrayStorage<'gc>,
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(ArrayObject::from_array(
         array,
         activation
@@ -98,7 +98,7 @@ pub fn concat<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     let mut base_array = this
         .and_then(|this| this.as_array_storage().map(|a| a.clone()))
         .unwrap_or_else(|| ArrayStorage::new(0));
@@ -120,7 +120,7 @@ pub fn resolve_array_hole<'gc>(
     this: Object<'gc>,
     i: usize,
     item: Option<Value<'gc>>,
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     item.map(Ok).unwrap_or_else(|| {
         this.proto()
             .map(|mut p| {
@@ -142,9 +142,12 @@ pub fn join_inner<'gc, 'a, 'ctxt, C>(
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
     mut conv: C,
-) -> Result<Value<'gc>, Error>
+) -> Result<Value<'gc>, Error<'gc>>
 where
-    C: for<'b> FnMut(Value<'gc>, &'b mut Activation<'a, 'gc, 'ctxt>) -> Result<Value<'gc>, Error>,
+    C: for<'b> FnMut(
+        Value<'gc>,
+        &'b mut Activation<'a, 'gc, 'ctxt>,
+    ) -> Result<Value<'gc>, Error<'gc>>,
 {
     let mut separator = args.get(0).cloned().unwrap_or(Value::Undefined);
     if separator == Value::Undefined {
@@ -186,7 +189,7 @@ pub fn join<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     join_inner(activation, this, args, |v, _act| Ok(v))
 }
 
@@ -195,7 +198,7 @@ pub fn to_string<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     join_inner(activation, this, &[",".into()], |v, _act| Ok(v))
 }
 
@@ -204,7 +207,7 @@ pub fn to_locale_string<'gc>(
     act: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     join_inner(act, this, &[",".into()], |v, activation| {
         let mut o = v.coerce_to_object(activation)?;
 
@@ -224,7 +227,7 @@ pub fn value_of<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     join_inner(activation, this, &[",".into()], |v, _act| Ok(v))
 }
 
@@ -257,7 +260,7 @@ impl<'gc> ArrayIter<'gc> {
     pub fn new(
         activation: &mut Activation<'_, 'gc, '_>,
         mut array_object: Object<'gc>,
-    ) -> Result<Self, Error> {
+    ) -> Result<Self, Error<'gc>> {
         let length = array_object
             .get_property(
                 array_object,
@@ -280,7 +283,7 @@ impl<'gc> ArrayIter<'gc> {
     fn next(
         &mut self,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Option<Result<(u32, Value<'gc>), Error>> {
+    ) -> Option<Result<(u32, Value<'gc>), Error<'gc>>> {
         if self.index < self.length {
             let i = self.index;
 
@@ -309,7 +312,7 @@ pub fn for_each<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         let callback = args
             .get(0)
@@ -344,7 +347,7 @@ pub fn map<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         let callback = args
             .get(0)
@@ -383,7 +386,7 @@ pub fn filter<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         let callback = args
             .get(0)
@@ -426,7 +429,7 @@ pub fn every<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         let callback = args
             .get(0)
@@ -466,7 +469,7 @@ pub fn some<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         let callback = args
             .get(0)
@@ -506,7 +509,7 @@ pub fn index_of<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         if let Some(array) = this.as_array_storage() {
             let search_val = args.get(0).cloned().unwrap_or(Value::Undefined);
@@ -535,7 +538,7 @@ pub fn last_index_of<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         if let Some(array) = this.as_array_storage() {
             let search_val = args.get(0).cloned().unwrap_or(Value::Undefined);
@@ -564,7 +567,7 @@ pub fn pop<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
             return Ok(array.pop());
@@ -579,7 +582,7 @@ pub fn push<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
             for arg in args {
@@ -595,7 +598,7 @@ pub fn reverse<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
             let mut last_non_hole_index = None;
@@ -633,7 +636,7 @@ pub fn shift<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
             return Ok(array.shift());
@@ -648,7 +651,7 @@ pub fn unshift<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
             for arg in args.iter().rev() {
@@ -665,7 +668,7 @@ pub fn resolve_index<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     index: Value<'gc>,
     length: usize,
-) -> Result<usize, Error> {
+) -> Result<usize, Error<'gc>> {
     let index = index.coerce_to_i32(activation)?;
 
     Ok(if index < 0 {
@@ -680,7 +683,7 @@ pub fn slice<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         let array_length = this.as_array_storage().map(|a| a.length());
 
@@ -721,7 +724,7 @@ pub fn splice<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         let array_length = this.as_array_storage().map(|a| a.length());
 
@@ -779,7 +782,7 @@ bitflags! {
     /// The array options that a given sort operation may use.
     ///
     /// These are provided as a number by the VM and converted into bitflags.
-    struct SortOptions: u8 {
+    pub struct SortOptions: u8 {
         /// Request case-insensitive string value sort.
         const CASE_INSENSITIVE     = 1 << 0;
 
@@ -801,7 +804,11 @@ bitflags! {
 /// the HRTB necessary to accept an activation.
 fn constrain<'a, 'gc, 'ctxt, F>(f: F) -> F
 where
-    F: FnMut(&mut Activation<'a, 'gc, 'ctxt>, Value<'gc>, Value<'gc>) -> Result<Ordering, Error>,
+    F: FnMut(
+        &mut Activation<'a, 'gc, 'ctxt>,
+        Value<'gc>,
+        Value<'gc>,
+    ) -> Result<Ordering, Error<'gc>>,
 {
     f
 }
@@ -824,9 +831,13 @@ fn sort_inner<'a, 'gc, 'ctxt, C>(
     values: &mut [(usize, Value<'gc>)],
     options: SortOptions,
     mut sort_func: C,
-) -> Result<bool, Error>
+) -> Result<bool, Error<'gc>>
 where
-    C: FnMut(&mut Activation<'a, 'gc, 'ctxt>, Value<'gc>, Value<'gc>) -> Result<Ordering, Error>,
+    C: FnMut(
+        &mut Activation<'a, 'gc, 'ctxt>,
+        Value<'gc>,
+        Value<'gc>,
+    ) -> Result<Ordering, Error<'gc>>,
 {
     let mut unique_sort_satisfied = true;
     let mut error_signal = Ok(());
@@ -867,7 +878,7 @@ fn compare_string_case_sensitive<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     a: Value<'gc>,
     b: Value<'gc>,
-) -> Result<Ordering, Error> {
+) -> Result<Ordering, Error<'gc>> {
     let string_a = a.coerce_to_string(activation)?;
     let string_b = b.coerce_to_string(activation)?;
 
@@ -878,7 +889,7 @@ fn compare_string_case_insensitive<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     a: Value<'gc>,
     b: Value<'gc>,
-) -> Result<Ordering, Error> {
+) -> Result<Ordering, Error<'gc>> {
     let string_a = a.coerce_to_string(activation)?.to_lowercase();
     let string_b = b.coerce_to_string(activation)?.to_lowercase();
 
@@ -889,7 +900,7 @@ fn compare_numeric<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     a: Value<'gc>,
     b: Value<'gc>,
-) -> Result<Ordering, Error> {
+) -> Result<Ordering, Error<'gc>> {
     let num_a = a.coerce_to_number(activation)?;
     let num_b = b.coerce_to_number(activation)?;
 
@@ -911,7 +922,7 @@ fn sort_postprocess<'gc>(
     options: SortOptions,
     unique_satisfied: bool,
     values: Vec<(usize, Value<'gc>)>,
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if unique_satisfied {
         if options.contains(SortOptions::RETURN_INDEXED_ARRAY) {
             return build_array(
@@ -953,7 +964,7 @@ fn sort_postprocess<'gc>(
 fn extract_array_values<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     value: Value<'gc>,
-) -> Result<Option<Vec<Value<'gc>>>, Error> {
+) -> Result<Option<Vec<Value<'gc>>>, Error<'gc>> {
     let object = value.coerce_to_object(activation).ok();
     let holey_vec = if let Some(object) = object {
         if let Some(field_array) = object.as_array_storage() {
@@ -984,12 +995,51 @@ fn extract_array_values<'gc>(
     Ok(Some(unholey_vec))
 }
 
+/// Sort a set of values with either a comparison function or the default
+/// comparison that a set of sort options selects.
+///
+/// This yields `false` if the `UniqueSort` constraint has been violated, as
+/// `sort_inner` does.
+pub fn sort_values<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    values: &mut [(usize, Value<'gc>)],
+    compare_fnc: Option<Object<'gc>>,
+    options: SortOptions,
+) -> Result<bool, Error<'gc>> {
+    if let Some(v) = compare_fnc {
+        sort_inner(
+            activation,
+            values,
+            options,
+            constrain(|activation, a, b| {
+                let order = v
+                    .call(None, &[a, b], activation, None)?
+                    .coerce_to_number(activation)?;
+
+                if order > 0.0 {
+                    Ok(Ordering::Greater)
+                } else if order < 0.0 {
+                    Ok(Ordering::Less)
+                } else {
+                    Ok(Ordering::Equal)
+                }
+            }),
+        )
+    } else if options.contains(SortOptions::NUMERIC) {
+        sort_inner(activation, values, options, compare_numeric)
+    } else if options.contains(SortOptions::CASE_INSENSITIVE) {
+        sort_inner(activation, values, options, compare_string_case_insensitive)
+    } else {
+        sort_inner(activation, values, options, compare_string_case_sensitive)
+    }
+}
+
 /// Impl `Array.sort`
 pub fn sort<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         let (compare_fnc, options) = if args.len() > 1 {
             (
@@ -1028,42 +1078,7 @@ pub fn sort<'gc>(
             return Ok(0.into());
         };
 
-        let unique_satisfied = if let Some(v) = compare_fnc {
-            sort_inner(
-                activation,
-                &mut values,
-                options,
-                constrain(|activation, a, b| {
-                    let order = v
-                        .call(None, &[a, b], activation, None)?
-                        .coerce_to_number(activation)?;
-
-                    if order > 0.0 {
-                        Ok(Ordering::Greater)
-                    } else if order < 0.0 {
-                        Ok(Ordering::Less)
-                    } else {
-                        Ok(Ordering::Equal)
-                    }
-                }),
-            )?
-        } else if options.contains(SortOptions::NUMERIC) {
-            sort_inner(activation, &mut values, options, compare_numeric)?
-        } else if options.contains(SortOptions::CASE_INSENSITIVE) {
-            sort_inner(
-                activation,
-                &mut values,
-                options,
-                compare_string_case_insensitive,
-            )?
-        } else {
-            sort_inner(
-                activation,
-                &mut values,
-                options,
-                compare_string_case_sensitive,
-            )?
-        };
+        let unique_satisfied = sort_values(activation, &mut values, compare_fnc, options)?;
 
         return sort_postprocess(activation, this, options, unique_satisfied, values);
     }
@@ -1079,7 +1094,7 @@ pub fn sort<'gc>(
 fn extract_maybe_array_values<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     value: Value<'gc>,
-) -> Result<Vec<Value<'gc>>, Error> {
+) -> Result<Vec<Value<'gc>>, Error<'gc>> {
     Ok(extract_array_values(activation, value.clone())?.unwrap_or_else(|| vec![value]))
 }
 
@@ -1092,7 +1107,7 @@ fn extract_maybe_array_values<'gc>(
 fn extract_maybe_array_strings<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     value: Value<'gc>,
-) -> Result<Vec<AvmString<'gc>>, Error> {
+) -> Result<Vec<AvmString<'gc>>, Error<'gc>> {
     let mut out = Vec::new();
 
     for value in extract_maybe_array_values(activation, value)? {
@@ -1111,7 +1126,7 @@ fn extract_maybe_array_strings<'gc>(
 fn extract_maybe_array_sort_options<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     value: Value<'gc>,
-) -> Result<Vec<SortOptions>, Error> {
+) -> Result<Vec<SortOptions>, Error<'gc>> {
     let mut out = Vec::new();
 
     for value in extract_maybe_array_values(activation, value)? {
@@ -1128,7 +1143,7 @@ pub fn sort_on<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         if let Some(field_names_value) = args.get(0).cloned() {
             let field_names = extract_maybe_array_strings(activation, field_names_value)?;
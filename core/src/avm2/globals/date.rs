@@ -0,0 +1,817 @@
+//! `Date` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{DateObject, Object, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, LocalResult, NaiveDate, TimeZone, Timelike, Utc,
+};
+use gc_arena::{GcCell, MutationContext};
+use std::convert::TryFrom;
+use std::f64::NAN;
+
+/// The largest distance from the epoch, in milliseconds, that a date may be.
+const MAX_TIME: f64 = 8.64e15;
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+const WEEKDAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// Define a getter method and its matching property for each date component.
+///
+/// The component is read in the time zone returned by `$timezone`.
+macro_rules! date_getters {
+    ($class:ident, $timezone:ident, $($method:expr, $property:expr => $fn:expr),*) => {{
+        $(
+            let getter: NativeMethod<'gc> = |activation, this, _args| {
+                let timezone = $timezone(activation);
+
+                Ok(date_time(this)
+                    .map(|date| f64::from($fn(&date.with_timezone(&timezone))))
+                    .unwrap_or(NAN)
+                    .into())
+            };
+
+            $class.define_instance_trait(Trait::from_method(
+                QName::new(Namespace::as3_namespace(), $method),
+                Method::from_builtin(getter),
+            ));
+            $class.define_instance_trait(Trait::from_getter(
+                QName::new(Namespace::public(), $property),
+                Method::from_builtin(getter),
+            ));
+        )*
+    }};
+}
+
+/// Define a setter method and its matching property for each date component.
+macro_rules! date_setters {
+    ($class:ident, $($method:expr, $property:expr => $fn:expr),*) => {{
+        $(
+            $class.define_instance_trait(Trait::from_method(
+                QName::new(Namespace::as3_namespace(), $method),
+                Method::from_builtin($fn),
+            ));
+            $class.define_instance_trait(Trait::from_setter(
+                QName::new(Namespace::public(), $property),
+                Method::from_builtin($fn),
+            ));
+        )*
+    }};
+}
+
+/// Define a method in the `AS3` namespace for each date formatter.
+macro_rules! date_methods {
+    ($class:ident, $($method:expr => $fn:expr),*) => {{
+        $(
+            $class.define_instance_trait(Trait::from_method(
+                QName::new(Namespace::as3_namespace(), $method),
+                Method::from_builtin($fn),
+            ));
+        )*
+    }};
+}
+
+/// The time zone local date components are read and written in.
+fn local_timezone(activation: &mut Activation<'_, '_, '_>) -> FixedOffset {
+    activation.context.locale.get_timezone()
+}
+
+/// The time zone UTC date components are read and written in.
+fn utc_timezone(_activation: &mut Activation<'_, '_, '_>) -> Utc {
+    Utc
+}
+
+/// The day of the week, counting from Sunday.
+fn weekday<Tz: TimeZone>(date: &DateTime<Tz>) -> u32 {
+    date.weekday().num_days_from_sunday()
+}
+
+/// The time a `Date` holds, if `this` is a valid one.
+fn date_time(this: Option<Object<'_>>) -> Option<DateTime<Utc>> {
+    this.and_then(|this| this.as_date_object())
+        .and_then(|date| date.date_time())
+}
+
+/// Convert a time in milliseconds since the epoch into a date.
+///
+/// Times that aren't finite or are too far from the epoch yield `None`.
+fn from_time(time: f64) -> Option<DateTime<Utc>> {
+    if !time.is_finite() || time.abs() > MAX_TIME {
+        return None;
+    }
+
+    match Utc.timestamp_millis_opt(time.trunc() as i64) {
+        LocalResult::Single(date) => Some(date),
+        _ => None,
+    }
+}
+
+/// Split a date into its year, month, day, hour, minute, second and
+/// millisecond in a given time zone.
+fn components<Tz: TimeZone>(date: DateTime<Utc>, timezone: &Tz) -> [f64; 7] {
+    let date = date.with_timezone(timezone);
+
+    [
+        f64::from(date.year()),
+        f64::from(date.month0()),
+        f64::from(date.day()),
+        f64::from(date.hour()),
+        f64::from(date.minute()),
+        f64::from(date.second()),
+        f64::from(date.timestamp_subsec_millis()),
+    ]
+}
+
+/// Assemble a date from its year, month, day, hour, minute, second and
+/// millisecond in a given time zone.
+///
+/// Components outside of their usual range carry over into the next larger
+/// one, as they do in Flash. Any non-finite component yields an invalid date.
+fn make_date<Tz: TimeZone>(timezone: &Tz, components: [f64; 7]) -> Option<DateTime<Utc>> {
+    if components.iter().any(|c| !c.is_finite()) {
+        return None;
+    }
+
+    let [year, month, day, hour, minute, second, millisecond] = components;
+    let year = year.trunc() + (month.trunc() / 12.0).floor();
+    let month = month.trunc().rem_euclid(12.0);
+    let offset = (day.trunc() - 1.0) * 86_400_000.0
+        + hour.trunc() * 3_600_000.0
+        + minute.trunc() * 60_000.0
+        + second.trunc() * 1_000.0
+        + millisecond.trunc();
+
+    if year.abs() > f64::from(i32::MAX) || offset.abs() > MAX_TIME * 2.0 {
+        return None;
+    }
+
+    let date = NaiveDate::from_ymd_opt(year as i32, month as u32 + 1, 1)?
+        .and_hms(0, 0, 0)
+        .checked_add_signed(Duration::milliseconds(offset as i64))?;
+    let date = timezone
+        .from_local_datetime(&date)
+        .earliest()?
+        .with_timezone(&Utc);
+
+    from_time(date.timestamp_millis() as f64)
+}
+
+/// Assemble a date from the arguments to the `Date` constructor or
+/// `Date.UTC`.
+///
+/// Two-digit years are taken to be in the twentieth century.
+fn date_from_args<'gc, Tz: TimeZone>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    timezone: &Tz,
+    args: &[Value<'gc>],
+) -> Result<Option<DateTime<Utc>>, Error<'gc>> {
+    let mut components = [NAN, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+
+    for (component, arg) in components.iter_mut().zip(args) {
+        *component = arg.coerce_to_number(activation)?;
+    }
+
+    let year = components[0].trunc();
+    if (0.0..=99.0).contains(&year) {
+        components[0] = year + 1900.0;
+    }
+
+    Ok(make_date(timezone, components))
+}
+
+/// Replace some of the components of a date.
+///
+/// Up to `count` arguments are written to consecutive components, starting
+/// with the component at `first`; components without an argument keep their
+/// current value. The new time is returned.
+fn set_components<'gc, Tz: TimeZone>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    timezone: &Tz,
+    first: usize,
+    count: usize,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let date = match this.and_then(|this| this.as_date_object()) {
+        Some(date) => date,
+        None => return Ok(Value::Undefined),
+    };
+
+    let current = match date.date_time() {
+        Some(current) => current,
+        // Only the year may be set on an invalid date, which then counts
+        // from the epoch.
+        None if first == 0 => Utc.timestamp(0, 0),
+        None => return Ok(NAN.into()),
+    };
+
+    let mut components = components(current, timezone);
+    for (i, component) in components.iter_mut().skip(first).take(count).enumerate() {
+        *component = match args.get(i) {
+            Some(arg) => arg.coerce_to_number(activation)?,
+            None if i == 0 => NAN,
+            None => break,
+        };
+    }
+
+    date.set_date_time(
+        activation.context.gc_context,
+        make_date(timezone, components),
+    );
+
+    Ok(date.time().into())
+}
+
+/// Parse the offset of a time zone, such as `+0545` or `-08:00`, into
+/// seconds east of UTC.
+///
+/// An empty offset is UTC itself.
+fn parse_offset(offset: &str) -> Option<i32> {
+    if offset.is_empty() {
+        return Some(0);
+    }
+
+    let sign = match offset.as_bytes()[0] {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let digits: String = offset[1..].chars().filter(|c| *c != ':').collect();
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let (hours, minutes) = if digits.len() <= 2 {
+        (digits.parse::<i32>().ok()?, 0)
+    } else if digits.len() == 4 {
+        (
+            digits[..2].parse::<i32>().ok()?,
+            digits[2..].parse::<i32>().ok()?,
+        )
+    } else {
+        return None;
+    };
+
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Parse a date in one of the formats `Date.parse` accepts.
+///
+/// This accepts the output of `Date.toString` and `Date.toUTCString`, as well
+/// as dates written as `MM/DD/YYYY` or `YYYY/MM/DD` and months written out by
+/// name, in any order. Dates without an explicit time zone are in the local one.
+fn parse_date(input: &str, local: FixedOffset) -> Option<DateTime<Utc>> {
+    let mut year = None;
+    let mut month = None;
+    let mut day = None;
+    let mut time = (0, 0, 0);
+    let mut is_pm = None;
+    let mut offset = None;
+
+    for token in input
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+    {
+        let lower = token.to_ascii_lowercase();
+
+        if lower.starts_with("gmt") || lower.starts_with("utc") {
+            offset = Some(parse_offset(&token[3..])?);
+        } else if token.starts_with(|c: char| c == '+' || c == '-') {
+            offset = Some(parse_offset(token)?);
+        } else if token.contains(':') {
+            let mut parts = token.split(':').map(|part| part.parse::<u32>().ok());
+            let hour = parts.next()??;
+            let minute = parts.next()??;
+            let second = parts.next().unwrap_or(Some(0))?;
+            if parts.next().is_some() {
+                return None;
+            }
+
+            time = (hour, minute, second);
+        } else if token.contains('/') {
+            let parts = token
+                .split('/')
+                .map(|part| part.parse::<i64>().ok())
+                .collect::<Option<Vec<_>>>()?;
+            match parts[..] {
+                [y, m, d] if y > 31 => {
+                    year = Some(y);
+                    month = Some(m - 1);
+                    day = Some(d);
+                }
+                [m, d, y] => {
+                    month = Some(m - 1);
+                    day = Some(d);
+                    year = Some(y);
+                }
+                _ => return None,
+            }
+        } else if lower == "am" || lower == "pm" {
+            is_pm = Some(lower == "pm");
+        } else if let Ok(number) = token.parse::<i64>() {
+            if day.is_none() && number <= 31 {
+                day = Some(number);
+            } else {
+                year = Some(number);
+            }
+        } else if let Some(index) = MONTHS.iter().position(|m| lower.starts_with(m)) {
+            month = Some(index as i64);
+        } else if !WEEKDAYS.iter().any(|d| lower.starts_with(d)) {
+            return None;
+        }
+    }
+
+    let mut year = i32::try_from(year?).ok()?;
+    if (0..=99).contains(&year) {
+        year += 1900;
+    }
+    let month = u32::try_from(month? + 1).ok()?;
+    let day = u32::try_from(day?).ok()?;
+
+    let (mut hour, minute, second) = time;
+    match is_pm {
+        Some(true) if hour < 12 => hour += 12,
+        Some(false) if hour == 12 => hour = 0,
+        _ => {}
+    }
+
+    let date = NaiveDate::from_ymd_opt(year, month, day)?.and_hms_opt(hour, minute, second)?;
+    let timezone = match offset {
+        Some(offset) => FixedOffset::east_opt(offset)?,
+        None => local,
+    };
+
+    Some(
+        timezone
+            .from_local_datetime(&date)
+            .single()?
+            .with_timezone(&Utc),
+    )
+}
+
+/// Implements `Date`'s instance initializer.
+///
+/// With no arguments, the date is the current time. A single argument is
+/// either a time in milliseconds or a string to parse; otherwise, the
+/// arguments are the local year, month, day, hour, minute, second and
+/// millisecond.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        let date_time = match args {
+            [] => Some(activation.context.locale.get_current_date_time()),
+            [Value::Object(object)] if object.as_date_object().is_some() => {
+                object.as_date_object().unwrap().date_time()
+            }
+            [value] => match value.coerce_to_primitive(None, activation)? {
+                Value::String(string) => parse_date(&string, local_timezone(activation)),
+                value => from_time(value.coerce_to_number(activation)?),
+            },
+            args => {
+                let timezone = local_timezone(activation);
+                date_from_args(activation, &timezone, args)?
+            }
+        };
+
+        if let Some(date) = this.as_date_object() {
+            date.set_date_time(activation.context.gc_context, date_time);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Date`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Date.parse`.
+pub fn parse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let input = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    Ok(parse_date(&input, local_timezone(activation))
+        .map(|date| date.timestamp_millis() as f64)
+        .unwrap_or(NAN)
+        .into())
+}
+
+/// Implements `Date.UTC`.
+pub fn utc<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(date_from_args(activation, &Utc, args)?
+        .map(|date| date.timestamp_millis() as f64)
+        .unwrap_or(NAN)
+        .into())
+}
+
+/// Implements `Date.getTime`, `Date.valueOf`, and the `time` property.
+pub fn time<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(date_time(this)
+        .map(|date| date.timestamp_millis() as f64)
+        .unwrap_or(NAN)
+        .into())
+}
+
+/// Implements `Date.getTimezoneOffset` and the `timezoneOffset` property.
+///
+/// This is the number of minutes the local time zone is behind UTC.
+pub fn timezone_offset<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if date_time(this).is_none() {
+        return Ok(NAN.into());
+    }
+
+    let offset = local_timezone(activation).local_minus_utc();
+
+    Ok((-f64::from(offset) / 60.0).into())
+}
+
+/// Implements `Date.setTime` and the `time` property.
+pub fn set_time<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(date) = this.and_then(|this| this.as_date_object()) {
+        let time = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        date.set_date_time(activation.context.gc_context, from_time(time));
+
+        return Ok(date.time().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Date.setFullYear`.
+pub fn set_full_year<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let timezone = local_timezone(activation);
+    set_components(activation, this, &timezone, 0, 3, args)
+}
+
+/// Implements `Date.setMonth`.
+pub fn set_month<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let timezone = local_timezone(activation);
+    set_components(activation, this, &timezone, 1, 2, args)
+}
+
+/// Implements `Date.setDate`.
+pub fn set_date<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let timezone = local_timezone(activation);
+    set_components(activation, this, &timezone, 2, 1, args)
+}
+
+/// Implements `Date.setHours`.
+pub fn set_hours<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let timezone = local_timezone(activation);
+    set_components(activation, this, &timezone, 3, 4, args)
+}
+
+/// Implements `Date.setMinutes`.
+pub fn set_minutes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let timezone = local_timezone(activation);
+    set_components(activation, this, &timezone, 4, 3, args)
+}
+
+/// Implements `Date.setSeconds`.
+pub fn set_seconds<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let timezone = local_timezone(activation);
+    set_components(activation, this, &timezone, 5, 2, args)
+}
+
+/// Implements `Date.setMilliseconds`.
+pub fn set_milliseconds<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let timezone = local_timezone(activation);
+    set_components(activation, this, &timezone, 6, 1, args)
+}
+
+/// Implements `Date.setUTCFullYear`.
+pub fn set_utc_full_year<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    set_components(activation, this, &Utc, 0, 3, args)
+}
+
+/// Implements `Date.setUTCMonth`.
+pub fn set_utc_month<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    set_components(activation, this, &Utc, 1, 2, args)
+}
+
+/// Implements `Date.setUTCDate`.
+pub fn set_utc_date<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    set_components(activation, this, &Utc, 2, 1, args)
+}
+
+/// Implements `Date.setUTCHours`.
+pub fn set_utc_hours<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    set_components(activation, this, &Utc, 3, 4, args)
+}
+
+/// Implements `Date.setUTCMinutes`.
+pub fn set_utc_minutes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    set_components(activation, this, &Utc, 4, 3, args)
+}
+
+/// Implements `Date.setUTCSeconds`.
+pub fn set_utc_seconds<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    set_components(activation, this, &Utc, 5, 2, args)
+}
+
+/// Implements `Date.setUTCMilliseconds`.
+pub fn set_utc_milliseconds<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    set_components(activation, this, &Utc, 6, 1, args)
+}
+
+/// Format a date in the local time zone.
+///
+/// Invalid dates are formatted as `Invalid Date`.
+fn format_local<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    format: &str,
+) -> Value<'gc> {
+    let timezone = local_timezone(activation);
+
+    match date_time(this) {
+        Some(date) => AvmString::new(
+            activation.context.gc_context,
+            date.with_timezone(&timezone).format(format).to_string(),
+        )
+        .into(),
+        None => "Invalid Date".into(),
+    }
+}
+
+/// Implements `Date.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(format_local(activation, this, "%a %b %-d %T GMT%z %-Y"))
+}
+
+/// Implements `Date.toDateString` and `Date.toLocaleDateString`.
+pub fn to_date_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(format_local(activation, this, "%a %b %-d %-Y"))
+}
+
+/// Implements `Date.toTimeString`.
+pub fn to_time_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(format_local(activation, this, "%T GMT%z"))
+}
+
+/// Implements `Date.toLocaleString`.
+pub fn to_locale_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(format_local(activation, this, "%a %b %-d %-Y %I:%M:%S %p"))
+}
+
+/// Implements `Date.toLocaleTimeString`.
+pub fn to_locale_time_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(format_local(activation, this, "%I:%M:%S %p"))
+}
+
+/// Implements `Date.toUTCString`.
+pub fn to_utc_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match date_time(this) {
+        Some(date) => Ok(AvmString::new(
+            activation.context.gc_context,
+            date.format("%a %b %-d %T %-Y UTC").to_string(),
+        )
+        .into()),
+        None => Ok("Invalid Date".into()),
+    }
+}
+
+/// Construct `Date`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::public(), "Date"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "parse"),
+        Method::from_builtin(parse),
+    ));
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "UTC"),
+        Method::from_builtin(utc),
+    ));
+
+    date_getters! {
+        write,
+        local_timezone,
+        "getFullYear", "fullYear" => Datelike::year,
+        "getMonth", "month" => Datelike::month0,
+        "getDate", "date" => Datelike::day,
+        "getDay", "day" => weekday,
+        "getHours", "hours" => Timelike::hour,
+        "getMinutes", "minutes" => Timelike::minute,
+        "getSeconds", "seconds" => Timelike::second,
+        "getMilliseconds", "milliseconds" => DateTime::timestamp_subsec_millis
+    }
+
+    date_getters! {
+        write,
+        utc_timezone,
+        "getUTCFullYear", "fullYearUTC" => Datelike::year,
+        "getUTCMonth", "monthUTC" => Datelike::month0,
+        "getUTCDate", "dateUTC" => Datelike::day,
+        "getUTCDay", "dayUTC" => weekday,
+        "getUTCHours", "hoursUTC" => Timelike::hour,
+        "getUTCMinutes", "minutesUTC" => Timelike::minute,
+        "getUTCSeconds", "secondsUTC" => Timelike::second,
+        "getUTCMilliseconds", "millisecondsUTC" => DateTime::timestamp_subsec_millis
+    }
+
+    date_setters! {
+        write,
+        "setFullYear", "fullYear" => set_full_year,
+        "setMonth", "month" => set_month,
+        "setDate", "date" => set_date,
+        "setHours", "hours" => set_hours,
+        "setMinutes", "minutes" => set_minutes,
+        "setSeconds", "seconds" => set_seconds,
+        "setMilliseconds", "milliseconds" => set_milliseconds,
+        "setUTCFullYear", "fullYearUTC" => set_utc_full_year,
+        "setUTCMonth", "monthUTC" => set_utc_month,
+        "setUTCDate", "dateUTC" => set_utc_date,
+        "setUTCHours", "hoursUTC" => set_utc_hours,
+        "setUTCMinutes", "minutesUTC" => set_utc_minutes,
+        "setUTCSeconds", "secondsUTC" => set_utc_seconds,
+        "setUTCMilliseconds", "millisecondsUTC" => set_utc_milliseconds,
+        "setTime", "time" => set_time
+    }
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "time"),
+        Method::from_builtin(time),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "timezoneOffset"),
+        Method::from_builtin(timezone_offset),
+    ));
+
+    date_methods! {
+        write,
+        "getTime" => time,
+        "getTimezoneOffset" => timezone_offset,
+        "toDateString" => to_date_string,
+        "toTimeString" => to_time_string,
+        "toLocaleString" => to_locale_string,
+        "toLocaleDateString" => to_date_string,
+        "toLocaleTimeString" => to_locale_time_string,
+        "toUTCString" => to_utc_string
+    }
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "valueOf"),
+        Method::from_builtin(time),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toJSON"),
+        Method::from_builtin(to_string),
+    ));
+
+    class
+}
+
+/// Object deriver for `Date`
+pub fn date_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    Ok(DateObject::derive(
+        base_proto,
+        activation.context.gc_context,
+        class,
+        scope,
+    ))
+}
@@ -0,0 +1,348 @@
+//! `Error` and its builtin subclasses
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `Error`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let message = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined => "".into(),
+            message => message.coerce_to_string(activation)?,
+        };
+        let id = args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "message"),
+            message.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "name"),
+            "Error".into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "errorID"),
+            id.into(),
+            activation,
+        )?;
+
+        let stack_trace = AvmString::new(
+            activation.context.gc_context,
+            activation.context.avm2.call_stack.trace(),
+        );
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "stackTrace"),
+            stack_trace.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements the class constructor of `Error` and its subclasses.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Error.errorID`.
+pub fn error_id<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "errorID"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Error.getStackTrace`.
+///
+/// The call stack is recorded when the error is constructed. Errors constructed
+/// outside of any bytecode method have no stack trace, and return `null`.
+pub fn get_stack_trace<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        let stack_trace = this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "stackTrace"),
+            activation,
+        )?;
+
+        if let Value::String(stack_trace) = stack_trace {
+            if !stack_trace.is_empty() {
+                let description =
+                    to_string(activation, Some(this), &[])?.coerce_to_string(activation)?;
+
+                return Ok(AvmString::new(
+                    activation.context.gc_context,
+                    format!("{}\n{}", description, stack_trace),
+                )
+                .into());
+            }
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `Error.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        let name = this
+            .get_property(this, &QName::new(Namespace::public(), "name"), activation)?
+            .coerce_to_string(activation)?;
+        let message = this
+            .get_property(
+                this,
+                &QName::new(Namespace::public(), "message"),
+                activation,
+            )?
+            .coerce_to_string(activation)?;
+
+        if message.is_empty() {
+            return Ok(name.into());
+        }
+
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            format!("{}: {}", name, message),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Error`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::public(), "Error"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "message"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("".into()),
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "name"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("Error".into()),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "errorID"),
+        Method::from_builtin(error_id),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "getStackTrace"),
+        Method::from_builtin(get_stack_trace),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    class
+}
+
+/// Construct a builtin subclass of `Error`, or of one of its subclasses.
+///
+/// Subclasses only differ from `Error` in their `name`, which their instance constructor sets
+/// with `subclass_init`.
+pub fn create_subclass<'gc>(
+    mc: MutationContext<'gc, '_>,
+    name: QName<'gc>,
+    super_name: QName<'gc>,
+    instance_init: NativeMethod<'gc>,
+) -> GcCell<'gc, Class<'gc>> {
+    Class::new(
+        name,
+        Some(super_name.into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    )
+}
+
+/// Call the superclass's instance constructor, then rename the error.
+pub fn subclass_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+    name: &'static str,
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        activation.super_init(this, args)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "name"),
+            name.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ArgumentError`'s instance constructor.
+pub fn argument_error_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    subclass_init(activation, this, args, "ArgumentError")
+}
+
+/// Construct `ArgumentError`'s class.
+pub fn create_argument_error_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    create_subclass(
+        mc,
+        QName::new(Namespace::public(), "ArgumentError"),
+        QName::new(Namespace::public(), "Error"),
+        argument_error_init,
+    )
+}
+
+/// Implements `RangeError`'s instance constructor.
+pub fn range_error_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    subclass_init(activation, this, args, "RangeError")
+}
+
+/// Construct `RangeError`'s class.
+pub fn create_range_error_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    create_subclass(
+        mc,
+        QName::new(Namespace::public(), "RangeError"),
+        QName::new(Namespace::public(), "Error"),
+        range_error_init,
+    )
+}
+
+/// Implements `ReferenceError`'s instance constructor.
+pub fn reference_error_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    subclass_init(activation, this, args, "ReferenceError")
+}
+
+/// Construct `ReferenceError`'s class.
+pub fn create_reference_error_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    create_subclass(
+        mc,
+        QName::new(Namespace::public(), "ReferenceError"),
+        QName::new(Namespace::public(), "Error"),
+        reference_error_init,
+    )
+}
+
+/// Implements `TypeError`'s instance constructor.
+pub fn type_error_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    subclass_init(activation, this, args, "TypeError")
+}
+
+/// Construct `TypeError`'s class.
+pub fn create_type_error_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    create_subclass(
+        mc,
+        QName::new(Namespace::public(), "TypeError"),
+        QName::new(Namespace::public(), "Error"),
+        type_error_init,
+    )
+}
+
+/// Implements `SyntaxError`'s instance constructor.
+pub fn syntax_error_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    subclass_init(activation, this, args, "SyntaxError")
+}
+
+/// Construct `SyntaxError`'s class.
+pub fn create_syntax_error_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    create_subclass(
+        mc,
+        QName::new(Namespace::public(), "SyntaxError"),
+        QName::new(Namespace::public(), "Error"),
+        syntax_error_init,
+    )
+}
+
+/// Implements `VerifyError`'s instance constructor.
+pub fn verify_error_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    subclass_init(activation, this, args, "VerifyError")
+}
+
+/// Construct `VerifyError`'s class.
+pub fn create_verify_error_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    create_subclass(
+        mc,
+        QName::new(Namespace::public(), "VerifyError"),
+        QName::new(Namespace::public(), "Error"),
+        verify_error_init,
+    )
+}
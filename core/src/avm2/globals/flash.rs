@@ -1,5 +1,9 @@
 //! `flash` namespace
 
 pub mod display;
+pub mod errors;
 pub mod events;
+pub mod geom;
+pub mod net;
 pub mod system;
+pub mod utils;
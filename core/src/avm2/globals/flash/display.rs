@@ -3,7 +3,11 @@
 pub mod displayobject;
 pub mod displayobjectcontainer;
 pub mod framelabel;
+pub mod graphics;
 pub mod interactiveobject;
+pub mod loader;
+pub mod loaderinfo;
 pub mod movieclip;
 pub mod scene;
+pub mod shape;
 pub mod sprite;
@@ -2,24 +2,28 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
+use crate::avm2::globals::flash::display::loaderinfo::loader_info_for;
+use crate::avm2::globals::flash::geom::transform::new_transform;
+use crate::avm2::globals::flash::geom::{colortransform, matrix, point, rectangle};
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::string::AvmString;
 use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
-use crate::avm2::Error;
-use crate::display_object::TDisplayObject;
+use crate::avm2::{Error, ErrorKind};
+use crate::bounding_box::BoundingBox;
+use crate::display_object::{DisplayObject, TDisplayObject};
 use crate::types::{Degrees, Percent};
 use gc_arena::{GcCell, MutationContext};
-use swf::Twips;
+use swf::{Color, Twips};
 
 /// Implements `flash.display.DisplayObject`'s instance constructor.
 pub fn instance_init<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         activation.super_init(this, &[])?;
     }
@@ -32,7 +36,7 @@ pub fn class_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(Value::Undefined)
 }
 
@@ -41,7 +45,7 @@ pub fn alpha<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         return Ok(dobj.alpha().into());
     }
@@ -54,7 +58,7 @@ pub fn set_alpha<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         let new_alpha = args
             .get(0)
@@ -72,7 +76,7 @@ pub fn height<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         return Ok(dobj.height().into());
     }
@@ -85,7 +89,7 @@ pub fn set_height<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         let new_height = args
             .get(0)
@@ -106,7 +110,7 @@ pub fn scale_y<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         return Ok(Value::Number(
             dobj.scale_y(activation.context.gc_context).into_unit(),
@@ -121,7 +125,7 @@ pub fn set_scale_y<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         let new_scale = args
             .get(0)
@@ -139,7 +143,7 @@ pub fn width<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         return Ok(dobj.width().into());
     }
@@ -152,7 +156,7 @@ pub fn set_width<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         let new_width = args
             .get(0)
@@ -173,7 +177,7 @@ pub fn scale_x<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         return Ok(Value::Number(
             dobj.scale_x(activation.context.gc_context).into_unit(),
@@ -188,7 +192,7 @@ pub fn set_scale_x<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         let new_scale = args
             .get(0)
@@ -206,7 +210,7 @@ pub fn x<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         return Ok(dobj.x().into());
     }
@@ -219,7 +223,7 @@ pub fn set_x<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         let new_x = args
             .get(0)
@@ -238,7 +242,7 @@ pub fn y<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         return Ok(dobj.y().into());
     }
@@ -251,7 +255,7 @@ pub fn set_y<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         let new_y = args
             .get(0)
@@ -270,7 +274,7 @@ pub fn rotation<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         let rot: f64 = dobj.rotation(activation.context.gc_context).into();
         let rem = rot % 360.0;
@@ -290,7 +294,7 @@ pub fn set_rotation<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         let new_rotation = args
             .get(0)
@@ -309,7 +313,7 @@ pub fn name<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         return Ok(AvmString::new(activation.context.gc_context, dobj.name().to_string()).into());
     }
@@ -322,7 +326,7 @@ pub fn set_name<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         let new_name = args
             .get(0)
@@ -349,7 +353,7 @@ pub fn parent<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         return Ok(dobj
             .parent()
@@ -365,7 +369,7 @@ pub fn root<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         return Ok(dobj
             .root()
@@ -376,12 +380,27 @@ pub fn root<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `loaderInfo`.
+pub fn loader_info<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(loader_info_for(activation, dobj)?
+            .map(|loader_info| loader_info.into())
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Implements `visible`'s getter.
 pub fn visible<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         return Ok(dobj.visible().into());
     }
@@ -394,7 +413,7 @@ pub fn set_visible<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         let new_visible = args
             .get(0)
@@ -408,12 +427,229 @@ pub fn set_visible<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `blendMode`'s getter.
+pub fn blend_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(
+            AvmString::new(activation.context.gc_context, dobj.blend_mode().to_string()).into(),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `blendMode`'s setter.
+pub fn set_blend_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_blend_mode = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        if let Ok(blend_mode) = new_blend_mode.parse() {
+            dobj.set_blend_mode(activation.context.gc_context, blend_mode);
+        } else {
+            return Err(Error::new(
+                ErrorKind::ArgumentError,
+                format!(
+                    "Error #2008: Parameter blendMode must be one of the accepted values, got {}.",
+                    new_blend_mode
+                ),
+            ));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `cacheAsBitmap`'s getter.
+pub fn cache_as_bitmap<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.cache_as_bitmap().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `cacheAsBitmap`'s setter.
+pub fn set_cache_as_bitmap<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        dobj.set_cache_as_bitmap(activation.context.gc_context, new_value);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `opaqueBackground`'s getter.
+pub fn opaque_background<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(match dobj.opaque_background() {
+            Some(color) => color.to_rgb().into(),
+            None => Value::Null,
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `opaqueBackground`'s setter.
+pub fn set_opaque_background<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let color = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => None,
+            value => Some(Color::from_rgb(value.coerce_to_u32(activation)?, 255)),
+        };
+
+        dobj.set_opaque_background(activation.context.gc_context, color);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Converts a bounding box into a `flash.geom.Rectangle`.
+fn rectangle_to_object<'gc>(
+    rect: &BoundingBox,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(rectangle::new_rectangle(
+        activation,
+        (
+            rect.x_min.to_pixels(),
+            rect.y_min.to_pixels(),
+            rect.width().to_pixels(),
+            rect.height().to_pixels(),
+        ),
+    )?
+    .into())
+}
+
+/// Reads a bounding box from any object with rectangle properties.
+fn object_to_rectangle<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<BoundingBox, Error<'gc>> {
+    let (x, y, width, height) = rectangle::object_to_rectangle(activation, object)?;
+
+    Ok(BoundingBox {
+        x_min: Twips::from_pixels(x),
+        y_min: Twips::from_pixels(y),
+        x_max: Twips::from_pixels(x + width),
+        y_max: Twips::from_pixels(y + height),
+        valid: true,
+    })
+}
+
+/// Implements `scrollRect`'s getter.
+pub fn scroll_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return match dobj.scroll_rect() {
+            Some(rect) => rectangle_to_object(&rect, activation),
+            None => Ok(Value::Null),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `scrollRect`'s setter.
+pub fn set_scroll_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let rect = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => None,
+            value => Some(object_to_rectangle(
+                value.coerce_to_object(activation)?,
+                activation,
+            )?),
+        };
+
+        dobj.set_scroll_rect(activation.context.gc_context, rect);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `scale9Grid`'s getter.
+pub fn scale9_grid<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return match dobj.scaling_grid() {
+            Some(rect) => rectangle_to_object(&rect, activation),
+            None => Ok(Value::Null),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `scale9Grid`'s setter.
+pub fn set_scale9_grid<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let rect = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => None,
+            value => Some(object_to_rectangle(
+                value.coerce_to_object(activation)?,
+                activation,
+            )?),
+        };
+
+        dobj.set_scaling_grid(activation.context.gc_context, rect);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Implements `mouseX`.
 pub fn mouse_x<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         let local_mouse = dobj.global_to_local(*activation.context.mouse_position);
 
@@ -428,7 +664,7 @@ pub fn mouse_y<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         let local_mouse = dobj.global_to_local(*activation.context.mouse_position);
 
@@ -443,7 +679,7 @@ pub fn hit_test_point<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         let x = Twips::from_pixels(
             args.get(0)
@@ -478,7 +714,7 @@ pub fn hit_test_object<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         if let Some(rhs_dobj) = args
             .get(0)
@@ -494,6 +730,126 @@ pub fn hit_test_object<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `transform`'s getter.
+pub fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        return Ok(new_transform(activation, this)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `transform`'s setter.
+///
+/// The matrix and color transform of the given `Transform` are copied onto
+/// this display object.
+pub fn set_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let mut transform = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let matrix = transform
+            .get_property(
+                transform,
+                &QName::new(Namespace::public(), "matrix"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+        let matrix = matrix::object_to_matrix(activation, matrix)?;
+        let color_transform = transform
+            .get_property(
+                transform,
+                &QName::new(Namespace::public(), "colorTransform"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+        let color_transform =
+            colortransform::object_to_color_transform(activation, color_transform)?;
+
+        dobj.set_matrix(activation.context.gc_context, &matrix);
+        dobj.set_color_transform(activation.context.gc_context, &color_transform);
+        dobj.set_transformed_by_script(activation.context.gc_context, true);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `localToGlobal`.
+pub fn local_to_global<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let (x, y) = point::point_arg(activation, args, 0)?;
+        let (x, y) = dobj.local_to_global((Twips::from_pixels(x), Twips::from_pixels(y)));
+
+        return Ok(point::new_point(activation, x.to_pixels(), y.to_pixels())?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `globalToLocal`.
+pub fn global_to_local<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let (x, y) = point::point_arg(activation, args, 0)?;
+        let (x, y) = dobj.global_to_local((Twips::from_pixels(x), Twips::from_pixels(y)));
+
+        return Ok(point::new_point(activation, x.to_pixels(), y.to_pixels())?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `getBounds`.
+///
+/// Both `getBounds` and `getRect` use this, as we do not yet account for
+/// strokes separately from fills.
+pub fn get_bounds<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let target = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => dobj,
+            target => target
+                .coerce_to_object(activation)?
+                .as_display_object()
+                .unwrap_or(dobj),
+        };
+
+        let bounds = dobj.bounds();
+        let out_bounds = if DisplayObject::ptr_eq(dobj, target) {
+            bounds
+        } else {
+            // Like AVM1, this transforms the local bounding box rather than
+            // recomputing a tighter one in the target space.
+            let bounds_transform = target.global_to_local_matrix() * dobj.local_to_global_matrix();
+            bounds.transform(&bounds_transform)
+        };
+
+        return rectangle_to_object(&out_bounds, activation);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `DisplayObject`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -586,6 +942,10 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         QName::new(Namespace::public(), "root"),
         Method::from_builtin(root),
     ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "loaderInfo"),
+        Method::from_builtin(loader_info),
+    ));
     write.define_instance_trait(Trait::from_getter(
         QName::new(Namespace::public(), "visible"),
         Method::from_builtin(visible),
@@ -594,6 +954,46 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         QName::new(Namespace::public(), "visible"),
         Method::from_builtin(set_visible),
     ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "blendMode"),
+        Method::from_builtin(blend_mode),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "blendMode"),
+        Method::from_builtin(set_blend_mode),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "cacheAsBitmap"),
+        Method::from_builtin(cache_as_bitmap),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "cacheAsBitmap"),
+        Method::from_builtin(set_cache_as_bitmap),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "opaqueBackground"),
+        Method::from_builtin(opaque_background),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "opaqueBackground"),
+        Method::from_builtin(set_opaque_background),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "scrollRect"),
+        Method::from_builtin(scroll_rect),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "scrollRect"),
+        Method::from_builtin(set_scroll_rect),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "scale9Grid"),
+        Method::from_builtin(scale9_grid),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "scale9Grid"),
+        Method::from_builtin(set_scale9_grid),
+    ));
     write.define_instance_trait(Trait::from_getter(
         QName::new(Namespace::public(), "mouseX"),
         Method::from_builtin(mouse_x),
@@ -610,6 +1010,30 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         QName::new(Namespace::public(), "hitTestObject"),
         Method::from_builtin(hit_test_object),
     ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "transform"),
+        Method::from_builtin(transform),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "transform"),
+        Method::from_builtin(set_transform),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "localToGlobal"),
+        Method::from_builtin(local_to_global),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "globalToLocal"),
+        Method::from_builtin(global_to_local),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "getBounds"),
+        Method::from_builtin(get_bounds),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "getRect"),
+        Method::from_builtin(get_bounds),
+    ));
 
     class
 }
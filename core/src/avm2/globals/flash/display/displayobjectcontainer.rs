@@ -7,7 +7,7 @@ use crate::avm2::names::{Namespace, QName};
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
-use crate::avm2::Error;
+use crate::avm2::{Error, ErrorKind};
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, Lists, TDisplayObject, TDisplayObjectContainer};
 use gc_arena::{GcCell, MutationContext};
@@ -18,7 +18,7 @@ pub fn instance_init<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         activation.super_init(this, &[])?;
     }
@@ -31,7 +31,7 @@ pub fn class_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(Value::Undefined)
 }
 
@@ -45,18 +45,20 @@ fn validate_add_operation<'gc>(
     new_parent: DisplayObject<'gc>,
     proposed_child: DisplayObject<'gc>,
     proposed_index: usize,
-) -> Result<(), Error> {
-    let ctr = new_parent
-        .as_container()
-        .ok_or("ArgumentError: Parent is not a DisplayObjectContainer")?;
+) -> Result<(), Error<'gc>> {
+    let ctr = new_parent.as_container().ok_or_else(|| {
+        Error::new(
+            ErrorKind::ArgumentError,
+            "Parent is not a DisplayObjectContainer",
+        )
+    })?;
 
     let mut checking_parent = Some(new_parent);
 
     while let Some(tp) = checking_parent {
         if DisplayObject::ptr_eq(tp, proposed_child) {
             return Err(
-                "ArgumentError: Proposed child is an ancestor of the proposed parent, you cannot add the child to the parent"
-                    .into(),
+                Error::new(ErrorKind::ArgumentError, "Proposed child is an ancestor of the proposed parent, you cannot add the child to the parent"),
             );
         }
 
@@ -64,7 +66,10 @@ fn validate_add_operation<'gc>(
     }
 
     if proposed_index > ctr.num_children() {
-        return Err("RangeError: Index position does not exist in the child list".into());
+        return Err(Error::new(
+            ErrorKind::RangeError,
+            "Index position does not exist in the child list",
+        ));
     }
 
     Ok(())
@@ -78,10 +83,13 @@ fn validate_add_operation<'gc>(
 fn validate_remove_operation<'gc>(
     old_parent: DisplayObject<'gc>,
     proposed_child: DisplayObject<'gc>,
-) -> Result<(), Error> {
-    let old_ctr = old_parent
-        .as_container()
-        .ok_or("ArgumentError: Parent is not a DisplayObjectContainer")?;
+) -> Result<(), Error<'gc>> {
+    let old_ctr = old_parent.as_container().ok_or_else(|| {
+        Error::new(
+            ErrorKind::ArgumentError,
+            "Parent is not a DisplayObjectContainer",
+        )
+    })?;
 
     for child in old_ctr.iter_execution_list() {
         if DisplayObject::ptr_eq(child, proposed_child) {
@@ -89,7 +97,10 @@ fn validate_remove_operation<'gc>(
         }
     }
 
-    Err("ArgumentError: Cannot remove object from display list it is not a child of.".into())
+    Err(Error::new(
+        ErrorKind::ArgumentError,
+        "Cannot remove object from display list it is not a child of.",
+    ))
 }
 
 /// Remove an element from it's parent display list.
@@ -117,12 +128,44 @@ fn add_child_to_displaylist<'gc>(
     }
 }
 
+/// Implements `mouseChildren`'s getter.
+pub fn mouse_children<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.mouse_children().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `mouseChildren`'s setter.
+pub fn set_mouse_children<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        dobj.set_mouse_children(activation.context.gc_context, value);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Implements `DisplayObjectContainer.getChildAt`
 pub fn get_child_at<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this
         .and_then(|this| this.as_display_object())
         .and_then(|this| this.as_container())
@@ -133,9 +176,9 @@ pub fn get_child_at<'gc>(
             .unwrap_or(Value::Undefined)
             .coerce_to_i32(activation)?;
         let child = dobj.child_by_index(index as usize).ok_or_else(|| {
-            format!(
-                "RangeError: Display object container has no child with id {}",
-                index
+            Error::new(
+                ErrorKind::RangeError,
+                format!("Display object container has no child with id {}", index),
             )
         })?;
 
@@ -150,7 +193,7 @@ pub fn get_child_by_name<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this
         .and_then(|this| this.as_display_object())
         .and_then(|this| this.as_container())
@@ -161,9 +204,9 @@ pub fn get_child_by_name<'gc>(
             .unwrap_or(Value::Undefined)
             .coerce_to_string(activation)?;
         let child = dobj.child_by_name(&name, false).ok_or_else(|| {
-            format!(
-                "RangeError: Display object container has no child with name {}",
-                name
+            Error::new(
+                ErrorKind::RangeError,
+                format!("Display object container has no child with name {}", name),
             )
         })?;
 
@@ -178,7 +221,7 @@ pub fn add_child<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(parent) = this.and_then(|this| this.as_display_object()) {
         if let Some(ctr) = parent.as_container() {
             let child = args
@@ -187,7 +230,9 @@ pub fn add_child<'gc>(
                 .unwrap_or(Value::Undefined)
                 .coerce_to_object(activation)?
                 .as_display_object()
-                .ok_or("ArgumentError: Child not a valid display object")?;
+                .ok_or_else(|| {
+                    Error::new(ErrorKind::ArgumentError, "Child not a valid display object")
+                })?;
             let target_index = ctr.num_children();
 
             validate_add_operation(parent, child, target_index)?;
@@ -205,7 +250,7 @@ pub fn add_child_at<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(parent) = this.and_then(|this| this.as_display_object()) {
         let child = args
             .get(0)
@@ -213,11 +258,18 @@ pub fn add_child_at<'gc>(
             .unwrap_or(Value::Undefined)
             .coerce_to_object(activation)?
             .as_display_object()
-            .ok_or("ArgumentError: Child not a valid display object")?;
+            .ok_or_else(|| {
+                Error::new(ErrorKind::ArgumentError, "Child not a valid display object")
+            })?;
         let target_index = args
             .get(1)
             .cloned()
-            .ok_or("ArgumentError: Index to add child at not specified")?
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::ArgumentError,
+                    "Index to add child at not specified",
+                )
+            })?
             .coerce_to_i32(activation)? as usize;
 
         validate_add_operation(parent, child, target_index)?;
@@ -234,7 +286,7 @@ pub fn remove_child<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(parent) = this.and_then(|this| this.as_display_object()) {
         let child = args
             .get(0)
@@ -242,7 +294,9 @@ pub fn remove_child<'gc>(
             .unwrap_or(Value::Undefined)
             .coerce_to_object(activation)?
             .as_display_object()
-            .ok_or("ArgumentError: Child not a valid display object")?;
+            .ok_or_else(|| {
+                Error::new(ErrorKind::ArgumentError, "Child not a valid display object")
+            })?;
 
         validate_remove_operation(parent, child)?;
         remove_child_from_displaylist(&mut activation.context, child);
@@ -258,7 +312,7 @@ pub fn num_children<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(parent) = this
         .and_then(|this| this.as_display_object())
         .and_then(|this| this.as_container())
@@ -274,7 +328,7 @@ pub fn contains<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(parent) = this.and_then(|this| this.as_display_object()) {
         if parent.as_container().is_some() {
             if let Some(child) = args
@@ -304,7 +358,7 @@ pub fn get_child_index<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(parent) = this.and_then(|this| this.as_display_object()) {
         if let Some(ctr) = parent.as_container() {
             let target_child = args
@@ -324,7 +378,10 @@ pub fn get_child_index<'gc>(
         }
     }
 
-    Err("ArgumentError: Child is not a child of this object".into())
+    Err(Error::new(
+        ErrorKind::ArgumentError,
+        "Child is not a child of this object",
+    ))
 }
 
 /// Implements `DisplayObjectContainer.removeChildAt`
@@ -332,7 +389,7 @@ pub fn remove_child_at<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(parent) = this.and_then(|this| this.as_display_object()) {
         if let Some(mut ctr) = parent.as_container() {
             let target_child = args
@@ -342,12 +399,14 @@ pub fn remove_child_at<'gc>(
                 .coerce_to_i32(activation)?;
 
             if target_child >= ctr.num_children() as i32 || target_child < 0 {
-                return Err(format!(
-                    "RangeError: {} does not exist in the child list (valid range is 0 to {})",
-                    target_child,
-                    ctr.num_children()
-                )
-                .into());
+                return Err(Error::new(
+                    ErrorKind::RangeError,
+                    format!(
+                        "{} does not exist in the child list (valid range is 0 to {})",
+                        target_child,
+                        ctr.num_children()
+                    ),
+                ));
             }
 
             let child = ctr.child_by_index(target_child as usize).unwrap();
@@ -366,7 +425,7 @@ pub fn remove_children<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(parent) = this.and_then(|this| this.as_display_object()) {
         if let Some(mut ctr) = parent.as_container() {
             let from = args
@@ -381,25 +440,29 @@ pub fn remove_children<'gc>(
                 .coerce_to_i32(activation)?;
 
             if from >= ctr.num_children() as i32 || from < 0 {
-                return Err(format!(
-                    "RangeError: Starting position {} does not exist in the child list (valid range is 0 to {})",
+                return Err(Error::new(ErrorKind::RangeError, format!(
+                    "Starting position {} does not exist in the child list (valid range is 0 to {})",
                     from,
                     ctr.num_children()
-                )
-                .into());
+                )));
             }
 
             if (to >= ctr.num_children() as i32 || to < 0) && to != i32::MAX {
-                return Err(format!(
-                    "RangeError: Ending position {} does not exist in the child list (valid range is 0 to {})",
+                return Err(Error::new(
+                    ErrorKind::RangeError,
+                    format!(
+                    "Ending position {} does not exist in the child list (valid range is 0 to {})",
                     to,
                     ctr.num_children()
-                )
-                .into());
+                ),
+                ));
             }
 
             if from > to {
-                return Err(format!("RangeError: Range {} to {} is invalid", from, to).into());
+                return Err(Error::new(
+                    ErrorKind::RangeError,
+                    format!("Range {} to {} is invalid", from, to),
+                ));
             }
 
             ctr.remove_range(
@@ -417,7 +480,7 @@ pub fn set_child_index<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(parent) = this.and_then(|this| this.as_display_object()) {
         let child = args
             .get(0)
@@ -425,16 +488,26 @@ pub fn set_child_index<'gc>(
             .unwrap_or(Value::Undefined)
             .coerce_to_object(activation)?
             .as_display_object()
-            .ok_or("ArgumentError: Child not a valid display object")?;
+            .ok_or_else(|| {
+                Error::new(ErrorKind::ArgumentError, "Child not a valid display object")
+            })?;
         let target_index = args
             .get(1)
             .cloned()
-            .ok_or("ArgumentError: Index to add child at not specified")?
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::ArgumentError,
+                    "Index to add child at not specified",
+                )
+            })?
             .coerce_to_i32(activation)? as usize;
 
         let child_parent = child.parent();
         if child_parent.is_none() || !DisplayObject::ptr_eq(child_parent.unwrap(), parent) {
-            return Err("ArgumentError: Given child is not a child of this display object".into());
+            return Err(Error::new(
+                ErrorKind::ArgumentError,
+                "Given child is not a child of this display object",
+            ));
         }
 
         validate_add_operation(parent, child, target_index)?;
@@ -451,7 +524,7 @@ pub fn swap_children_at<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(parent) = this.and_then(|this| this.as_display_object()) {
         if let Some(mut ctr) = parent.as_container() {
             let index0 = args
@@ -467,11 +540,17 @@ pub fn swap_children_at<'gc>(
             let bounds = ctr.num_children();
 
             if index0 < 0 || index0 as usize >= bounds {
-                return Err(format!("RangeError: Index {} is out of bounds", index0).into());
+                return Err(Error::new(
+                    ErrorKind::RangeError,
+                    format!("Index {} is out of bounds", index0),
+                ));
             }
 
             if index1 < 0 || index1 as usize >= bounds {
-                return Err(format!("RangeError: Index {} is out of bounds", index1).into());
+                return Err(Error::new(
+                    ErrorKind::RangeError,
+                    format!("Index {} is out of bounds", index1),
+                ));
             }
 
             let child0 = ctr.child_by_index(index0 as usize).unwrap();
@@ -492,7 +571,7 @@ pub fn swap_children<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(parent) = this.and_then(|this| this.as_display_object()) {
         if let Some(mut ctr) = parent.as_container() {
             let child0 = args
@@ -501,23 +580,37 @@ pub fn swap_children<'gc>(
                 .unwrap_or(Value::Undefined)
                 .coerce_to_object(activation)?
                 .as_display_object()
-                .ok_or("ArgumentError: Child is not a display object")?;
+                .ok_or_else(|| {
+                    Error::new(ErrorKind::ArgumentError, "Child is not a display object")
+                })?;
             let child1 = args
                 .get(1)
                 .cloned()
                 .unwrap_or(Value::Undefined)
                 .coerce_to_object(activation)?
                 .as_display_object()
-                .ok_or("ArgumentError: Child is not a display object")?;
+                .ok_or_else(|| {
+                    Error::new(ErrorKind::ArgumentError, "Child is not a display object")
+                })?;
 
             let index0 = ctr
                 .iter_render_list()
                 .position(|a| DisplayObject::ptr_eq(a, child0))
-                .ok_or("ArgumentError: Child is not a child of this display object")?;
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::ArgumentError,
+                        "Child is not a child of this display object",
+                    )
+                })?;
             let index1 = ctr
                 .iter_render_list()
                 .position(|a| DisplayObject::ptr_eq(a, child1))
-                .ok_or("ArgumentError: Child is not a child of this display object")?;
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::ArgumentError,
+                        "Child is not a child of this display object",
+                    )
+                })?;
 
             child0.set_placed_by_script(activation.context.gc_context, true);
             child1.set_placed_by_script(activation.context.gc_context, true);
@@ -534,7 +627,7 @@ pub fn stop_all_movie_clips<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(parent) = this.and_then(|this| this.as_display_object()) {
         if let Some(mc) = parent.as_movie_clip() {
             mc.stop(&mut activation.context);
@@ -558,7 +651,7 @@ pub fn get_objects_under_point<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Err("DisplayObjectContainer.getObjectsUnderPoint not yet implemented".into())
 }
 
@@ -567,7 +660,7 @@ pub fn are_inaccessible_objects_under_point<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Err("DisplayObjectContainer.areInaccessibleObjectsUnderPoint not yet implemented".into())
 }
 
@@ -598,6 +691,14 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         QName::new(Namespace::public(), "numChildren"),
         Method::from_builtin(num_children),
     ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "mouseChildren"),
+        Method::from_builtin(mouse_children),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "mouseChildren"),
+        Method::from_builtin(set_mouse_children),
+    ));
     write.define_instance_trait(Trait::from_method(
         QName::new(Namespace::public(), "addChild"),
         Method::from_builtin(add_child),
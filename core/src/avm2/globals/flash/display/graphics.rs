@@ -0,0 +1,857 @@
+//! `flash.display.Graphics` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::geom::matrix::object_to_matrix;
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, StageObject, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorKind};
+use crate::display_object::TDisplayObject;
+use crate::drawing::Drawing;
+use crate::shape_utils::DrawCommand;
+use gc_arena::{GcCell, MutationContext};
+use std::f64::consts::PI;
+use swf::{
+    Color, FillStyle, Gradient, GradientInterpolation, GradientRecord, GradientSpread,
+    LineCapStyle, LineJoinStyle, LineStyle, Matrix, Twips,
+};
+
+/// Implements `flash.display.Graphics`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Err(Error::new(
+        ErrorKind::ArgumentError,
+        "Error #2012: Graphics$ class cannot be instantiated.",
+    ))
+}
+
+/// Implements `flash.display.Graphics`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// The hidden slot a `Sprite` or `Shape` keeps its `Graphics` object in.
+pub fn graphics_slot_name<'gc>() -> QName<'gc> {
+    QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "graphics")
+}
+
+/// Implements the `graphics` getter of `Sprite` and `Shape`.
+///
+/// The `Graphics` object is created on first access and shares the display
+/// object of its owner.
+pub fn graphics<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        if let Some(dobj) = this.as_display_object() {
+            let slot_name = graphics_slot_name();
+
+            return match this.get_property(this, &slot_name, activation)? {
+                Value::Undefined | Value::Null => {
+                    let graphics_proto = activation.avm2().prototypes().graphics;
+                    let graphics: Value<'gc> = StageObject::for_display_object(
+                        activation.context.gc_context,
+                        dobj,
+                        graphics_proto,
+                    )
+                    .into();
+                    this.set_property(this, &slot_name, graphics.clone(), activation)?;
+
+                    Ok(graphics)
+                }
+                graphics => Ok(graphics),
+            };
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Apply a change to the drawing of the display object that owns a
+/// `Graphics` object.
+fn modify_drawing<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    f: impl FnOnce(&mut Drawing),
+) {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        if let Some(mut drawing) = dobj.as_drawing(activation.context.gc_context) {
+            f(&mut drawing);
+        }
+
+        dobj.invalidate_cached_bitmap(activation.context.gc_context);
+    }
+}
+
+/// Convert an AS3 color and alpha pair into a `Color`.
+fn color_from_args(rgb: u32, alpha: f64) -> Color {
+    Color::from_rgb(rgb, (alpha.min(1.0).max(0.0) * 255.0) as u8)
+}
+
+/// Read the elements of an `Array` or `Vector`.
+fn collection_values<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<Vec<Value<'gc>>, Error<'gc>> {
+    if let Value::Undefined | Value::Null = value {
+        return Ok(Vec::new());
+    }
+
+    let object = value.coerce_to_object(activation)?;
+    if let Some(array) = object.as_array_storage() {
+        return Ok(array
+            .iter()
+            .map(|v| v.unwrap_or(Value::Undefined))
+            .collect());
+    }
+    if let Some(vector) = object.as_vector_storage() {
+        return Ok(vector.iter().collect());
+    }
+
+    Ok(Vec::new())
+}
+
+/// Read the elements of an `Array` or `Vector` as numbers.
+fn collection_numbers<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<Vec<f64>, Error<'gc>> {
+    let mut numbers = Vec::new();
+    for value in collection_values(activation, value)? {
+        numbers.push(value.coerce_to_number(activation)?);
+    }
+
+    Ok(numbers)
+}
+
+/// Parse the arguments shared by `beginGradientFill` and
+/// `lineGradientStyle` into a fill style.
+fn gradient_style_from_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Option<FillStyle>, Error<'gc>> {
+    let method = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let colors = collection_numbers(activation, args.get(1).cloned().unwrap_or(Value::Null))?;
+    let alphas = collection_numbers(activation, args.get(2).cloned().unwrap_or(Value::Null))?;
+    let ratios = collection_numbers(activation, args.get(3).cloned().unwrap_or(Value::Null))?;
+    if colors.len() != alphas.len() || colors.len() != ratios.len() {
+        log::warn!("Gradient fill received different sized arrays for colors, alphas and ratios");
+        return Ok(None);
+    }
+
+    let records = colors
+        .iter()
+        .zip(alphas.iter())
+        .zip(ratios.iter())
+        .map(|((rgb, alpha), ratio)| GradientRecord {
+            ratio: ratio.min(255.0).max(0.0) as u8,
+            color: color_from_args(*rgb as u32, *alpha),
+        })
+        .collect();
+
+    let matrix = match args.get(4).cloned().unwrap_or(Value::Null) {
+        Value::Undefined | Value::Null => Matrix::identity(),
+        matrix => {
+            let matrix = matrix.coerce_to_object(activation)?;
+            object_to_matrix(activation, matrix)?
+        }
+    };
+    let spread = match args.get(5).cloned().unwrap_or(Value::Null) {
+        Value::Undefined | Value::Null => GradientSpread::Pad,
+        spread => match spread.coerce_to_string(activation)?.as_str() {
+            "reflect" => GradientSpread::Reflect,
+            "repeat" => GradientSpread::Repeat,
+            _ => GradientSpread::Pad,
+        },
+    };
+    let interpolation = match args.get(6).cloned().unwrap_or(Value::Null) {
+        Value::Undefined | Value::Null => GradientInterpolation::RGB,
+        interpolation => match interpolation.coerce_to_string(activation)?.as_str() {
+            "linearRGB" => GradientInterpolation::LinearRGB,
+            _ => GradientInterpolation::RGB,
+        },
+    };
+    let focal_point = args
+        .get(7)
+        .cloned()
+        .unwrap_or_else(|| 0.0.into())
+        .coerce_to_number(activation)?;
+
+    let gradient = Gradient {
+        matrix,
+        spread,
+        interpolation,
+        records,
+    };
+
+    Ok(match method.as_str() {
+        "linear" => Some(FillStyle::LinearGradient(gradient)),
+        "radial" if focal_point != 0.0 => Some(FillStyle::FocalGradient {
+            gradient,
+            focal_point: focal_point.min(1.0).max(-1.0) as f32,
+        }),
+        "radial" => Some(FillStyle::RadialGradient(gradient)),
+        other => {
+            log::warn!("Gradient fill received invalid fill type {:?}", other);
+            None
+        }
+    })
+}
+
+/// Implements `Graphics.beginFill`.
+pub fn begin_fill<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let rgb = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+    let alpha = args
+        .get(1)
+        .cloned()
+        .unwrap_or_else(|| 1.0.into())
+        .coerce_to_number(activation)?;
+
+    let style = FillStyle::Color(color_from_args(rgb, alpha));
+    modify_drawing(activation, this, |drawing| {
+        drawing.set_fill_style(Some(style))
+    });
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.beginGradientFill`.
+pub fn begin_gradient_fill<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let style = gradient_style_from_args(activation, args)?;
+    modify_drawing(activation, this, |drawing| drawing.set_fill_style(style));
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.beginBitmapFill`.
+///
+/// AVM2 has no `BitmapData` yet, so the fill is dropped and the shape is
+/// drawn unfilled.
+pub fn begin_bitmap_fill<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    log::warn!("Graphics.beginBitmapFill: bitmap fills are not yet supported");
+    modify_drawing(activation, this, |drawing| drawing.set_fill_style(None));
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.endFill`.
+pub fn end_fill<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    modify_drawing(activation, this, |drawing| drawing.set_fill_style(None));
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.lineStyle`.
+pub fn line_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let thickness = args
+        .get(0)
+        .cloned()
+        .unwrap_or_else(|| f64::NAN.into())
+        .coerce_to_number(activation)?;
+
+    if thickness.is_nan() {
+        modify_drawing(activation, this, |drawing| drawing.set_line_style(None));
+
+        return Ok(Value::Undefined);
+    }
+
+    let width = pixels_to_twips(thickness.min(255.0).max(0.0));
+    let rgb = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Unsigned(0))
+        .coerce_to_u32(activation)?;
+    let alpha = args
+        .get(2)
+        .cloned()
+        .unwrap_or_else(|| 1.0.into())
+        .coerce_to_number(activation)?;
+    let is_pixel_hinted = args
+        .get(3)
+        .cloned()
+        .unwrap_or(Value::Bool(false))
+        .coerce_to_boolean();
+    let (allow_scale_x, allow_scale_y) = match args.get(4).cloned().unwrap_or(Value::Null) {
+        Value::Undefined | Value::Null => (true, true),
+        scale_mode => match scale_mode.coerce_to_string(activation)?.as_str() {
+            "none" => (false, false),
+            "vertical" => (true, false),
+            "horizontal" => (false, true),
+            _ => (true, true),
+        },
+    };
+    let cap_style = match args.get(5).cloned().unwrap_or(Value::Null) {
+        Value::Undefined | Value::Null => LineCapStyle::Round,
+        caps => match caps.coerce_to_string(activation)?.as_str() {
+            "none" => LineCapStyle::None,
+            "square" => LineCapStyle::Square,
+            _ => LineCapStyle::Round,
+        },
+    };
+    let join_style = match args.get(6).cloned().unwrap_or(Value::Null) {
+        Value::Undefined | Value::Null => LineJoinStyle::Round,
+        joints => match joints.coerce_to_string(activation)?.as_str() {
+            "miter" => {
+                let miter_limit = args
+                    .get(7)
+                    .cloned()
+                    .unwrap_or_else(|| 3.0.into())
+                    .coerce_to_number(activation)?;
+                LineJoinStyle::Miter(miter_limit.min(255.0).max(1.0) as f32)
+            }
+            "bevel" => LineJoinStyle::Bevel,
+            _ => LineJoinStyle::Round,
+        },
+    };
+
+    let style = LineStyle {
+        width,
+        color: color_from_args(rgb, alpha),
+        start_cap: cap_style,
+        end_cap: cap_style,
+        join_style,
+        fill_style: None,
+        allow_scale_x,
+        allow_scale_y,
+        is_pixel_hinted,
+        allow_close: true,
+    };
+    modify_drawing(activation, this, |drawing| {
+        drawing.set_line_style(Some(style))
+    });
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.lineGradientStyle`.
+pub fn line_gradient_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(style) = gradient_style_from_args(activation, args)? {
+        modify_drawing(activation, this, |drawing| {
+            drawing.set_line_fill_style(style)
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Read a list of numeric arguments, converting `undefined` to `NaN`.
+fn number_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    count: usize,
+) -> Result<Vec<f64>, Error<'gc>> {
+    let mut numbers = Vec::with_capacity(count);
+    for i in 0..count {
+        numbers.push(
+            args.get(i)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_number(activation)?,
+        );
+    }
+
+    Ok(numbers)
+}
+
+/// Convert a coordinate in pixels to the nearest twip.
+///
+/// The points generated for circles and rounded rectangles land a hair to
+/// either side of whole twips, so truncating them would skew their bounds.
+fn pixels_to_twips(pixels: f64) -> Twips {
+    Twips::new((pixels * Twips::TWIPS_PER_PIXEL).round() as i32)
+}
+
+fn move_to_command(x: f64, y: f64) -> DrawCommand {
+    DrawCommand::MoveTo {
+        x: pixels_to_twips(x),
+        y: pixels_to_twips(y),
+    }
+}
+
+fn line_to_command(x: f64, y: f64) -> DrawCommand {
+    DrawCommand::LineTo {
+        x: pixels_to_twips(x),
+        y: pixels_to_twips(y),
+    }
+}
+
+fn curve_to_command(control_x: f64, control_y: f64, anchor_x: f64, anchor_y: f64) -> DrawCommand {
+    DrawCommand::CurveTo {
+        x1: pixels_to_twips(control_x),
+        y1: pixels_to_twips(control_y),
+        x2: pixels_to_twips(anchor_x),
+        y2: pixels_to_twips(anchor_y),
+    }
+}
+
+/// Approximate a cubic Bezier curve with quadratic ones, which are all that
+/// `Drawing` and the renderers support.
+///
+/// The curve is split into four pieces, each of which is replaced by the
+/// quadratic curve sharing its end points and tangents.
+fn cubic_curve_commands(
+    start: (f64, f64),
+    control_1: (f64, f64),
+    control_2: (f64, f64),
+    anchor: (f64, f64),
+) -> Vec<DrawCommand> {
+    const SEGMENTS: usize = 4;
+
+    let point_at = |t: f64| {
+        let mt = 1.0 - t;
+        let a = mt * mt * mt;
+        let b = 3.0 * mt * mt * t;
+        let c = 3.0 * mt * t * t;
+        let d = t * t * t;
+        (
+            a * start.0 + b * control_1.0 + c * control_2.0 + d * anchor.0,
+            a * start.1 + b * control_1.1 + c * control_2.1 + d * anchor.1,
+        )
+    };
+    let derivative_at = |t: f64| {
+        let mt = 1.0 - t;
+        let a = 3.0 * mt * mt;
+        let b = 6.0 * mt * t;
+        let c = 3.0 * t * t;
+        (
+            a * (control_1.0 - start.0)
+                + b * (control_2.0 - control_1.0)
+                + c * (anchor.0 - control_2.0),
+            a * (control_1.1 - start.1)
+                + b * (control_2.1 - control_1.1)
+                + c * (anchor.1 - control_2.1),
+        )
+    };
+
+    let mut commands = Vec::with_capacity(SEGMENTS);
+    for i in 0..SEGMENTS {
+        let t0 = i as f64 / SEGMENTS as f64;
+        let t1 = (i + 1) as f64 / SEGMENTS as f64;
+        let dt = t1 - t0;
+        let p0 = point_at(t0);
+        let p3 = point_at(t1);
+        let d0 = derivative_at(t0);
+        let d1 = derivative_at(t1);
+
+        // The cubic control points of this piece are `p0 + d0 * dt / 3` and
+        // `p3 - d1 * dt / 3`; the best quadratic control point lies halfway
+        // between their extrapolations.
+        let control = (
+            (p0.0 + d0.0 * dt / 2.0 + p3.0 - d1.0 * dt / 2.0) / 2.0,
+            (p0.1 + d0.1 * dt / 2.0 + p3.1 - d1.1 * dt / 2.0) / 2.0,
+        );
+        commands.push(curve_to_command(control.0, control.1, p3.0, p3.1));
+    }
+
+    commands
+}
+
+/// Produce quadratic curves approximating an elliptical arc, starting at
+/// `start_angle` and sweeping by `sweep` radians.
+fn arc_commands(
+    center: (f64, f64),
+    radius: (f64, f64),
+    start_angle: f64,
+    sweep: f64,
+) -> Vec<DrawCommand> {
+    let segments = (sweep.abs() / (PI / 4.0)).ceil().max(1.0) as usize;
+    let step = sweep / segments as f64;
+    let control_scale = 1.0 / (step / 2.0).cos();
+
+    (0..segments)
+        .map(|i| {
+            let angle = start_angle + step * (i + 1) as f64;
+            let mid_angle = angle - step / 2.0;
+            curve_to_command(
+                center.0 + radius.0 * mid_angle.cos() * control_scale,
+                center.1 + radius.1 * mid_angle.sin() * control_scale,
+                center.0 + radius.0 * angle.cos(),
+                center.1 + radius.1 * angle.sin(),
+            )
+        })
+        .collect()
+}
+
+/// Produce the commands for a full ellipse.
+fn ellipse_commands(center: (f64, f64), radius: (f64, f64)) -> Vec<DrawCommand> {
+    let mut commands = vec![move_to_command(center.0 + radius.0, center.1)];
+    commands.extend(arc_commands(center, radius, 0.0, 2.0 * PI));
+
+    commands
+}
+
+/// Implements `Graphics.moveTo`.
+pub fn move_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let n = number_args(activation, args, 2)?;
+    modify_drawing(activation, this, |drawing| {
+        drawing.draw_command(move_to_command(n[0], n[1]))
+    });
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.lineTo`.
+pub fn line_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let n = number_args(activation, args, 2)?;
+    modify_drawing(activation, this, |drawing| {
+        drawing.draw_command(line_to_command(n[0], n[1]))
+    });
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.curveTo`.
+pub fn curve_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let n = number_args(activation, args, 4)?;
+    modify_drawing(activation, this, |drawing| {
+        drawing.draw_command(curve_to_command(n[0], n[1], n[2], n[3]))
+    });
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.cubicCurveTo`.
+pub fn cubic_curve_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let n = number_args(activation, args, 6)?;
+    modify_drawing(activation, this, |drawing| {
+        let (x, y) = drawing.cursor();
+        let commands = cubic_curve_commands(
+            (x.to_pixels(), y.to_pixels()),
+            (n[0], n[1]),
+            (n[2], n[3]),
+            (n[4], n[5]),
+        );
+        for command in commands {
+            drawing.draw_command(command);
+        }
+    });
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.drawRect`.
+pub fn draw_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let n = number_args(activation, args, 4)?;
+    let (x, y, width, height) = (n[0], n[1], n[2], n[3]);
+    modify_drawing(activation, this, |drawing| {
+        drawing.draw_command(move_to_command(x, y));
+        drawing.draw_command(line_to_command(x + width, y));
+        drawing.draw_command(line_to_command(x + width, y + height));
+        drawing.draw_command(line_to_command(x, y + height));
+        drawing.draw_command(line_to_command(x, y));
+    });
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.drawRoundRect`.
+pub fn draw_round_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let n = number_args(activation, args, 6)?;
+    let (x, y, width, height) = (n[0], n[1], n[2], n[3]);
+    let ellipse_width = n[4];
+    let ellipse_height = if n[5].is_nan() { ellipse_width } else { n[5] };
+    let rx = (ellipse_width / 2.0).min(width / 2.0).max(0.0);
+    let ry = (ellipse_height / 2.0).min(height / 2.0).max(0.0);
+
+    let mut commands = vec![move_to_command(x + width, y + height - ry)];
+    commands.extend(arc_commands(
+        (x + width - rx, y + height - ry),
+        (rx, ry),
+        0.0,
+        PI / 2.0,
+    ));
+    commands.push(line_to_command(x + rx, y + height));
+    commands.extend(arc_commands(
+        (x + rx, y + height - ry),
+        (rx, ry),
+        PI / 2.0,
+        PI / 2.0,
+    ));
+    commands.push(line_to_command(x, y + ry));
+    commands.extend(arc_commands((x + rx, y + ry), (rx, ry), PI, PI / 2.0));
+    commands.push(line_to_command(x + width - rx, y));
+    commands.extend(arc_commands(
+        (x + width - rx, y + ry),
+        (rx, ry),
+        PI * 1.5,
+        PI / 2.0,
+    ));
+    commands.push(line_to_command(x + width, y + height - ry));
+
+    modify_drawing(activation, this, |drawing| {
+        for command in commands {
+            drawing.draw_command(command);
+        }
+    });
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.drawCircle`.
+pub fn draw_circle<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let n = number_args(activation, args, 3)?;
+    let commands = ellipse_commands((n[0], n[1]), (n[2], n[2]));
+    modify_drawing(activation, this, |drawing| {
+        for command in commands {
+            drawing.draw_command(command);
+        }
+    });
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.drawEllipse`.
+pub fn draw_ellipse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let n = number_args(activation, args, 4)?;
+    let (x, y, width, height) = (n[0], n[1], n[2], n[3]);
+    let commands = ellipse_commands(
+        (x + width / 2.0, y + height / 2.0),
+        (width / 2.0, height / 2.0),
+    );
+    modify_drawing(activation, this, |drawing| {
+        for command in commands {
+            drawing.draw_command(command);
+        }
+    });
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.drawPath`.
+///
+/// Commands are the values of `flash.display.GraphicsPathCommand`. The
+/// winding rule is not supported by `Drawing` and is ignored.
+pub fn draw_path<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path_commands =
+        collection_numbers(activation, args.get(0).cloned().unwrap_or(Value::Null))?;
+    let data = collection_numbers(activation, args.get(1).cloned().unwrap_or(Value::Null))?;
+
+    modify_drawing(activation, this, |drawing| {
+        let mut data = data.iter().copied();
+        let mut take = |count: usize| -> Option<Vec<f64>> {
+            let values: Vec<f64> = data.by_ref().take(count).collect();
+            if values.len() == count {
+                Some(values)
+            } else {
+                None
+            }
+        };
+
+        for command in path_commands {
+            let commands = match command as i32 {
+                1 => take(2).map(|d| vec![move_to_command(d[0], d[1])]),
+                2 => take(2).map(|d| vec![line_to_command(d[0], d[1])]),
+                3 => take(4).map(|d| vec![curve_to_command(d[0], d[1], d[2], d[3])]),
+                4 => take(4).map(|d| vec![move_to_command(d[2], d[3])]),
+                5 => take(4).map(|d| vec![line_to_command(d[2], d[3])]),
+                6 => take(6).map(|d| {
+                    let (x, y) = drawing.cursor();
+                    cubic_curve_commands(
+                        (x.to_pixels(), y.to_pixels()),
+                        (d[0], d[1]),
+                        (d[2], d[3]),
+                        (d[4], d[5]),
+                    )
+                }),
+                _ => Some(vec![]),
+            };
+
+            match commands {
+                Some(commands) => {
+                    for command in commands {
+                        drawing.draw_command(command);
+                    }
+                }
+                None => break,
+            }
+        }
+    });
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.drawTriangles`.
+///
+/// Each triangle is drawn as a closed path using the current fill and line
+/// styles. UV mapping requires bitmap fills and is ignored.
+pub fn draw_triangles<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let vertices = collection_numbers(activation, args.get(0).cloned().unwrap_or(Value::Null))?;
+    let vertices: Vec<(f64, f64)> = vertices.chunks_exact(2).map(|v| (v[0], v[1])).collect();
+    let indices: Vec<usize> =
+        match collection_numbers(activation, args.get(1).cloned().unwrap_or(Value::Null))? {
+            indices if indices.is_empty() => (0..vertices.len()).collect(),
+            indices => indices.into_iter().map(|i| i as usize).collect(),
+        };
+    let culling = match args.get(3).cloned().unwrap_or(Value::Null) {
+        Value::Undefined | Value::Null => 0.0,
+        culling => match culling.coerce_to_string(activation)?.as_str() {
+            "positive" => 1.0,
+            "negative" => -1.0,
+            _ => 0.0,
+        },
+    };
+
+    modify_drawing(activation, this, |drawing| {
+        for triangle in indices.chunks_exact(3) {
+            let (a, b, c) = match (
+                vertices.get(triangle[0]),
+                vertices.get(triangle[1]),
+                vertices.get(triangle[2]),
+            ) {
+                (Some(a), Some(b), Some(c)) => (*a, *b, *c),
+                _ => continue,
+            };
+
+            let winding = (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0);
+            if winding * culling > 0.0 {
+                continue;
+            }
+
+            drawing.draw_command(move_to_command(a.0, a.1));
+            drawing.draw_command(line_to_command(b.0, b.1));
+            drawing.draw_command(line_to_command(c.0, c.1));
+            drawing.draw_command(line_to_command(a.0, a.1));
+        }
+    });
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.clear`.
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    modify_drawing(activation, this, |drawing| drawing.clear());
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Graphics`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Graphics"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    let methods: [(&str, NativeMethod<'gc>); 17] = [
+        ("beginFill", begin_fill),
+        ("beginGradientFill", begin_gradient_fill),
+        ("beginBitmapFill", begin_bitmap_fill),
+        ("endFill", end_fill),
+        ("lineStyle", line_style),
+        ("lineGradientStyle", line_gradient_style),
+        ("moveTo", move_to),
+        ("lineTo", line_to),
+        ("curveTo", curve_to),
+        ("cubicCurveTo", cubic_curve_to),
+        ("drawRect", draw_rect),
+        ("drawRoundRect", draw_round_rect),
+        ("drawCircle", draw_circle),
+        ("drawEllipse", draw_ellipse),
+        ("drawPath", draw_path),
+        ("drawTriangles", draw_triangles),
+        ("clear", clear),
+    ];
+    for (name, method) in methods.iter() {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public(), *name),
+            Method::from_builtin(*method),
+        ));
+    }
+
+    class
+}
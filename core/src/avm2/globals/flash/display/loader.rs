@@ -0,0 +1,327 @@
+//! `flash.display.Loader` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::domain::Domain;
+use crate::avm2::globals::flash::display::loaderinfo::init_loader_info;
+use crate::avm2::globals::flash::net::urlrequest::request_url;
+use crate::avm2::globals::flash::system::loadercontext::{context_domain, context_parameters};
+use crate::avm2::globals::flash::utils::caller_domain;
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{LoaderInfoObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Avm2;
+use crate::avm2::Event;
+use crate::avm2::{Error, ErrorKind};
+use crate::backend::navigator::{OwnedFuture, RequestOptions};
+use crate::display_object::{MovieClip, TDisplayObject, TDisplayObjectContainer};
+use crate::loader::Error as LoaderError;
+use crate::tag_utils::{SwfMovie, SwfSlice};
+use gc_arena::{GcCell, MutationContext};
+use std::sync::Arc;
+
+/// The hidden slot a `Loader` keeps its `LoaderInfo` in.
+fn content_loader_info_slot_name<'gc>() -> QName<'gc> {
+    QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "contentLoaderInfo")
+}
+
+/// Implements `flash.display.Loader`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        if this.as_display_object().is_none() {
+            let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
+            let new_do = MovieClip::new(SwfSlice::empty(movie), activation.context.gc_context);
+
+            this.init_display_object(activation.context.gc_context, new_do.into());
+            new_do.set_object2(activation.context.gc_context, this);
+        }
+
+        let loader_info = LoaderInfoObject::from_loader(
+            activation.context.gc_context,
+            Some(activation.context.avm2.prototypes().loaderinfo),
+            Some(this),
+        );
+        init_loader_info(activation, loader_info)?;
+
+        this.set_property(
+            this,
+            &content_loader_info_slot_name(),
+            loader_info.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.Loader`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Get the `LoaderInfo` of a `Loader`.
+fn content_loader_info<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<Option<LoaderInfoObject<'gc>>, Error<'gc>> {
+    match this.get_property(this, &content_loader_info_slot_name(), activation)? {
+        Value::Object(loader_info) => Ok(loader_info.as_loader_info_object()),
+        _ => Ok(None),
+    }
+}
+
+/// Implements `contentLoaderInfo`'s getter.
+pub fn content_loader_info_getter<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        return Ok(content_loader_info(activation, this)?
+            .map(|loader_info| loader_info.into())
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `content`'s getter.
+pub fn content<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let content = content_loader_info(activation, this)?
+            .and_then(|loader_info| loader_info.content())
+            .map(|content| content.object2());
+
+        return Ok(match content {
+            Some(Value::Object(content)) => content.into(),
+            _ => Value::Null,
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Start loading content into a `Loader`.
+///
+/// Any content that was loaded before is removed first. Loaded movies run
+/// their code in the application domain named by the `LoaderContext`, or in
+/// a new child of the calling script's domain.
+fn start_load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    fetch: OwnedFuture<Vec<u8>, LoaderError>,
+    url: Option<String>,
+    context: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let loader_info = match content_loader_info(activation, this)? {
+        Some(loader_info) => loader_info,
+        None => return Ok(()),
+    };
+
+    let (domain, parameters) = match context {
+        Value::Undefined | Value::Null => (None, Vec::new()),
+        context => {
+            let context = context.coerce_to_object(activation)?;
+
+            (
+                context_domain(activation, context)?,
+                context_parameters(activation, context)?,
+            )
+        }
+    };
+    let domain = match domain {
+        Some(domain) => domain,
+        None => {
+            let parent_domain = caller_domain(activation);
+
+            Domain::movie_domain(activation.context.gc_context, parent_domain)
+        }
+    };
+
+    unload_content(activation, this, loader_info);
+    loader_info.begin_load(activation.context.gc_context, url.clone());
+
+    let process = activation.context.load_manager.load_into_avm2_loader(
+        activation.context.player.clone().unwrap(),
+        loader_info,
+        domain,
+        parameters,
+        fetch,
+        url,
+    );
+    activation.context.navigator.spawn_future(process);
+
+    Ok(())
+}
+
+/// Remove the content of a `Loader`, dispatching `unload` if there was any.
+fn unload_content<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    loader_info: LoaderInfoObject<'gc>,
+) {
+    let content = match loader_info.content() {
+        Some(content) => content,
+        None => return,
+    };
+
+    if let Some(mut container) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_container())
+    {
+        container.clear(activation.context.gc_context);
+    }
+    content.unload(&mut activation.context);
+
+    loader_info.begin_load(activation.context.gc_context, None);
+
+    if let Err(e) = Avm2::dispatch_event(
+        &mut activation.context,
+        Event::new("unload"),
+        loader_info.into(),
+    ) {
+        log::error!("Encountered AVM2 error when dispatching event: {}", e);
+    }
+}
+
+/// Implements `Loader.load`.
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let request = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let url = request_url(activation, request)?.ok_or_else(|| {
+            Error::new(
+                ErrorKind::TypeError,
+                "Error #2007: Parameter url must be non-null.",
+            )
+        })?;
+        let context = args.get(1).cloned().unwrap_or(Value::Null);
+
+        let fetch = activation
+            .context
+            .navigator
+            .fetch(&url, RequestOptions::get());
+
+        start_load(activation, this, fetch, Some(url.to_string()), context)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Loader.loadBytes`.
+pub fn load_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let bytes = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let data = bytes
+            .as_bytearray()
+            .map(|bytearray| bytearray.bytes().to_vec())
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::TypeError,
+                    "Error #2007: Parameter bytes must be non-null.",
+                )
+            })?;
+        let context = args.get(1).cloned().unwrap_or(Value::Null);
+
+        let fetch: OwnedFuture<Vec<u8>, LoaderError> = Box::pin(async move { Ok(data) });
+
+        start_load(activation, this, fetch, None, context)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Loader.unload`.
+pub fn unload<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(loader_info) = content_loader_info(activation, this)? {
+            unload_content(activation, this, loader_info);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Loader`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Loader"),
+        Some(
+            QName::new(
+                Namespace::package("flash.display"),
+                "DisplayObjectContainer",
+            )
+            .into(),
+        ),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "contentLoaderInfo"),
+        Method::from_builtin(content_loader_info_getter),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "content"),
+        Method::from_builtin(content),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "load"),
+        Method::from_builtin(load),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "loadBytes"),
+        Method::from_builtin(load_bytes),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "unload"),
+        Method::from_builtin(unload),
+    ));
+
+    write.define_instance_trait(Trait::from_slot(
+        content_loader_info_slot_name(),
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "BareObject").into(),
+        None,
+    ));
+
+    class
+}
@@ -0,0 +1,478 @@
+//! `flash.display.LoaderInfo` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{DomainObject, LoaderInfoObject, Object, ScriptObject, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorKind};
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::vminterface::AvmType;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.display.LoaderInfo`'s instance constructor.
+///
+/// Script cannot construct a `LoaderInfo`; the player allocates them with
+/// `LoaderInfoObject` and then runs this constructor on them.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.LoaderInfo`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Run `LoaderInfo`'s constructor on a `LoaderInfoObject` allocated by the
+/// player.
+pub fn init_loader_info<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    loader_info: Object<'gc>,
+) -> Result<(), Error<'gc>> {
+    let mut proto = activation.context.avm2.prototypes().loaderinfo;
+    let constructor = proto
+        .get_property(
+            proto,
+            &QName::new(Namespace::public(), "constructor"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+
+    constructor.call(Some(loader_info), &[], activation, Some(proto))?;
+
+    Ok(())
+}
+
+/// Get the `LoaderInfo` of the movie that a display object belongs to.
+///
+/// The root movie's `LoaderInfo` is created the first time it is asked for.
+/// Display objects that do not belong to a loaded movie have none.
+pub fn loader_info_for<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    dobj: DisplayObject<'gc>,
+) -> Result<Option<Object<'gc>>, Error<'gc>> {
+    let root = match dobj.root().and_then(|root| root.as_movie_clip()) {
+        Some(root) => root,
+        None => return Ok(None),
+    };
+
+    if let Some(loader_info) = root.loader_info() {
+        return Ok(Some(loader_info));
+    }
+
+    // Only the levels of the player load their movies without a `Loader`.
+    let is_level = activation
+        .context
+        .levels
+        .values()
+        .any(|level| DisplayObject::ptr_eq(*level, root.into()));
+    if !is_level {
+        return Ok(None);
+    }
+
+    let movie = match root.movie() {
+        Some(movie) => movie,
+        None => return Ok(None),
+    };
+    let domain = activation
+        .context
+        .library
+        .library_for_movie(movie.clone())
+        .filter(|library| library.avm_type() == AvmType::Avm2)
+        .map(|library| library.avm2_domain());
+    let loader_info = LoaderInfoObject::from_movie(
+        activation.context.gc_context,
+        Some(activation.context.avm2.prototypes().loaderinfo),
+        movie,
+        root.into(),
+        domain,
+    );
+
+    init_loader_info(activation, loader_info)?;
+    root.set_loader_info(activation.context.gc_context, Some(loader_info));
+
+    Ok(Some(loader_info))
+}
+
+/// Unwrap `this` as a `LoaderInfoObject`.
+fn loader_info_of<'gc>(this: Option<Object<'gc>>) -> Option<LoaderInfoObject<'gc>> {
+    this.and_then(|this| this.as_loader_info_object())
+}
+
+/// The error thrown when reading information about content that has not
+/// loaded yet.
+fn not_loaded_error<'gc>() -> Error<'gc> {
+    Error::new(
+        ErrorKind::Error,
+        "Error #2099: The loading object is not sufficiently loaded to provide this information.",
+    )
+}
+
+/// Implements `loader`'s getter.
+pub fn loader<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(loader_info) = loader_info_of(this) {
+        return Ok(loader_info
+            .loader()
+            .map(|o| o.into())
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `content`'s getter.
+pub fn content<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(loader_info) = loader_info_of(this) {
+        return Ok(
+            match loader_info.content().map(|content| content.object2()) {
+                Some(Value::Object(content)) => content.into(),
+                _ => Value::Null,
+            },
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `url`'s getter.
+pub fn url<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(loader_info) = loader_info_of(this) {
+        return Ok(loader_info
+            .url()
+            .map(|url| AvmString::new(activation.context.gc_context, url).into())
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `loaderURL`'s getter.
+///
+/// Content loaded by a `Loader` reports the URL of the root movie, as movies
+/// loaded into other movies are not tracked. The root movie reports its own
+/// URL.
+pub fn loader_url<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(loader_info) = loader_info_of(this) {
+        if loader_info.loader().is_none() {
+            return url(activation, this, args);
+        }
+
+        return Ok(activation
+            .context
+            .swf
+            .url()
+            .map(|url| AvmString::new(activation.context.gc_context, url).into())
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `contentType`'s getter.
+pub fn content_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(loader_info) = loader_info_of(this) {
+        return Ok(loader_info
+            .content_type()
+            .map(|content_type| AvmString::new(activation.context.gc_context, content_type).into())
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `bytesLoaded`'s getter.
+pub fn bytes_loaded<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(loader_info) = loader_info_of(this) {
+        return Ok((loader_info.bytes_loaded() as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `bytesTotal`'s getter.
+pub fn bytes_total<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(loader_info) = loader_info_of(this) {
+        return Ok((loader_info.bytes_total() as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `applicationDomain`'s getter.
+pub fn application_domain<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(loader_info) = loader_info_of(this) {
+        if let Some(domain) = loader_info.domain() {
+            return Ok(DomainObject::from_domain(
+                activation.context.gc_context,
+                Some(activation.context.avm2.prototypes().application_domain),
+                domain,
+            )
+            .into());
+        }
+
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `parameters`'s getter.
+pub fn parameters<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(loader_info) = loader_info_of(this) {
+        let mut params = ScriptObject::object(
+            activation.context.gc_context,
+            activation.context.avm2.prototypes().object,
+        );
+
+        if let Some(movie) = loader_info.movie() {
+            for (key, value) in movie.parameters().iter() {
+                let key = AvmString::new(activation.context.gc_context, key);
+                let value = AvmString::new(activation.context.gc_context, value);
+
+                params.set_property(params, &QName::dynamic_name(key), value.into(), activation)?;
+            }
+        }
+
+        return Ok(params.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `swfVersion`'s getter.
+pub fn swf_version<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(loader_info) = loader_info_of(this) {
+        let movie = loader_info.movie().ok_or_else(not_loaded_error)?;
+
+        return Ok(movie.version().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `actionScriptVersion`'s getter.
+pub fn action_script_version<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(loader_info) = loader_info_of(this) {
+        let movie = loader_info.movie().ok_or_else(not_loaded_error)?;
+        let avm_type = activation
+            .context
+            .library
+            .library_for_movie(movie)
+            .map(|library| library.avm_type())
+            .unwrap_or(AvmType::Avm1);
+
+        return Ok(match avm_type {
+            AvmType::Avm1 => 2.into(),
+            AvmType::Avm2 => 3.into(),
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `frameRate`'s getter.
+pub fn frame_rate<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(loader_info) = loader_info_of(this) {
+        let movie = loader_info.movie().ok_or_else(not_loaded_error)?;
+
+        return Ok(movie.header().frame_rate.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `width`'s getter.
+///
+/// This is the stage width of a loaded movie, or the width of a loaded image.
+pub fn width<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(loader_info) = loader_info_of(this) {
+        if let Some(movie) = loader_info.movie() {
+            return Ok(movie.width().into());
+        }
+
+        let content = loader_info.content().ok_or_else(not_loaded_error)?;
+
+        return Ok((content.width() as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `height`'s getter.
+///
+/// This is the stage height of a loaded movie, or the height of a loaded
+/// image.
+pub fn height<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(loader_info) = loader_info_of(this) {
+        if let Some(movie) = loader_info.movie() {
+            return Ok(movie.height().into());
+        }
+
+        let content = loader_info.content().ok_or_else(not_loaded_error)?;
+
+        return Ok((content.height() as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `LoaderInfo`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "LoaderInfo"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "loader"),
+        Method::from_builtin(loader),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "content"),
+        Method::from_builtin(content),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "url"),
+        Method::from_builtin(url),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "loaderURL"),
+        Method::from_builtin(loader_url),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "contentType"),
+        Method::from_builtin(content_type),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bytesLoaded"),
+        Method::from_builtin(bytes_loaded),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bytesTotal"),
+        Method::from_builtin(bytes_total),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "applicationDomain"),
+        Method::from_builtin(application_domain),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "parameters"),
+        Method::from_builtin(parameters),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "swfVersion"),
+        Method::from_builtin(swf_version),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "actionScriptVersion"),
+        Method::from_builtin(action_script_version),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "frameRate"),
+        Method::from_builtin(frame_rate),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "width"),
+        Method::from_builtin(width),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "height"),
+        Method::from_builtin(height),
+    ));
+
+    class
+}
+
+/// Object deriver for `LoaderInfo`
+pub fn loaderinfo_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    Ok(LoaderInfoObject::derive(
+        base_proto,
+        activation.context.gc_context,
+        class,
+        scope,
+    ))
+}
@@ -10,27 +10,18 @@ use crate::avm2::object::{ArrayObject, Object, TObject};
 use crate::avm2::string::AvmString;
 use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
-use crate::avm2::Error;
+use crate::avm2::{Error, ErrorKind};
 use crate::display_object::{MovieClip, Scene, TDisplayObject};
-use crate::tag_utils::{SwfMovie, SwfSlice};
 use gc_arena::{GcCell, MutationContext};
-use std::sync::Arc;
 
 /// Implements `flash.display.MovieClip`'s instance constructor.
 pub fn instance_init<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         activation.super_init(this, &[])?;
-
-        if this.as_display_object().is_none() {
-            let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
-            let new_do = MovieClip::new(SwfSlice::empty(movie), activation.context.gc_context);
-
-            this.init_display_object(activation.context.gc_context, new_do.into());
-        }
     }
     Ok(Value::Undefined)
 }
@@ -40,7 +31,7 @@ pub fn class_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(Value::Undefined)
 }
 
@@ -50,7 +41,7 @@ pub fn add_frame_script<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mc) = this
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
@@ -73,7 +64,7 @@ pub fn current_frame<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mc) = this
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
@@ -98,7 +89,7 @@ pub fn current_frame_label<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mc) = this
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
@@ -123,7 +114,7 @@ pub fn current_label<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mc) = this
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
@@ -147,7 +138,7 @@ fn labels_for_scene<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     mc: MovieClip<'gc>,
     scene: &Scene,
-) -> Result<(String, u16, Object<'gc>), Error> {
+) -> Result<(String, u16, Object<'gc>), Error<'gc>> {
     let Scene {
         name: scene_name,
         start: scene_start,
@@ -183,7 +174,7 @@ pub fn current_labels<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mc) = this
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
@@ -204,7 +195,7 @@ pub fn current_scene<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mc) = this
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
@@ -237,7 +228,7 @@ pub fn scenes<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mc) = this
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
@@ -286,7 +277,7 @@ pub fn frames_loaded<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mc) = this
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
@@ -302,7 +293,7 @@ pub fn is_playing<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mc) = this
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
@@ -318,7 +309,7 @@ pub fn total_frames<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mc) = this
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
@@ -334,7 +325,7 @@ pub fn goto_and_play<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mc) = this
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
@@ -351,7 +342,7 @@ pub fn goto_and_stop<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mc) = this
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
@@ -367,7 +358,7 @@ pub fn goto_frame<'gc>(
     mc: MovieClip<'gc>,
     args: &[Value<'gc>],
     stop: bool,
-) -> Result<(), Error> {
+) -> Result<(), Error<'gc>> {
     let frame_or_label = args.get(0).cloned().unwrap_or(Value::Null);
 
     let scene = match args.get(1).cloned().unwrap_or(Value::Null) {
@@ -390,18 +381,20 @@ pub fn goto_frame<'gc>(
                     //the requested frame exists within that scene.
                     let scene = scene.coerce_to_string(activation)?;
                     if !mc.frame_exists_within_scene(&frame_or_label, &scene) {
-                        return Err(format!(
-                            "ArgumentError: Frame label {} not found in scene {}",
-                            frame_or_label, scene
-                        )
-                        .into());
+                        return Err(Error::new(
+                            ErrorKind::ArgumentError,
+                            format!(
+                                "Frame label {} not found in scene {}",
+                                frame_or_label, scene
+                            ),
+                        ));
                     }
                 }
 
                 mc.frame_label_to_number(&frame_or_label).ok_or_else(|| {
-                    format!(
-                        "ArgumentError: {} is not a valid frame label.",
-                        frame_or_label
+                    Error::new(
+                        ErrorKind::ArgumentError,
+                        format!("{} is not a valid frame label.", frame_or_label),
                     )
                 })? as u32
             }
@@ -418,7 +411,7 @@ pub fn stop<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mc) = this
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
@@ -434,7 +427,7 @@ pub fn play<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mc) = this
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
@@ -451,7 +444,7 @@ pub fn prev_frame<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mc) = this
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
@@ -467,7 +460,7 @@ pub fn next_frame<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mc) = this
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
@@ -483,7 +476,7 @@ pub fn prev_scene<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mc) = this
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
@@ -506,7 +499,7 @@ pub fn next_scene<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mc) = this
         .and_then(|o| o.as_display_object())
         .and_then(|dobj| dobj.as_movie_clip())
@@ -0,0 +1,68 @@
+//! `flash.display.Shape` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::display::graphics;
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::Graphic;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.display.Shape`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        if this.as_display_object().is_none() {
+            let new_do = Graphic::new_with_avm2(&mut activation.context, this);
+
+            this.init_display_object(activation.context.gc_context, new_do.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.Shape`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `Shape`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Shape"),
+        Some(QName::new(Namespace::package("flash.display"), "DisplayObject").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "graphics"),
+        Method::from_builtin(graphics::graphics),
+    ));
+
+    write.define_instance_trait(Trait::from_slot(
+        graphics::graphics_slot_name(),
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "BareObject").into(),
+        None,
+    ));
+
+    class
+}
@@ -0,0 +1,5 @@
+//! `flash.errors` namespace
+
+pub mod eoferror;
+pub mod ioerror;
+pub mod memoryerror;
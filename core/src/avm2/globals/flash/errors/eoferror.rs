@@ -0,0 +1,29 @@
+//! `flash.errors.EOFError` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::error::{create_subclass, subclass_init};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.errors.EOFError`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    subclass_init(activation, this, args, "EOFError")
+}
+
+/// Construct `EOFError`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    create_subclass(
+        mc,
+        QName::new(Namespace::package("flash.errors"), "EOFError"),
+        QName::new(Namespace::package("flash.errors"), "IOError"),
+        instance_init,
+    )
+}
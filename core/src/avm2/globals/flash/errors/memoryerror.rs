@@ -0,0 +1,29 @@
+//! `flash.errors.MemoryError` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::error::{create_subclass, subclass_init};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.errors.MemoryError`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    subclass_init(activation, this, args, "MemoryError")
+}
+
+/// Construct `MemoryError`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    create_subclass(
+        mc,
+        QName::new(Namespace::package("flash.errors"), "MemoryError"),
+        QName::new(Namespace::public(), "Error"),
+        instance_init,
+    )
+}
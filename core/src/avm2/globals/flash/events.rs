@@ -1,5 +1,74 @@
 //! `flash.events` namespace
 
+/// Define native getters and setters for fields of an event's data.
+///
+/// Each field is given as `getter, setter, field: kind`, where `kind` is one
+/// of `number`, `boolean`, `int`, `uint` or `string` and determines how
+/// assigned values are coerced.
+macro_rules! event_data_accessors {
+    (@coerce number, $value:expr, $activation:expr) => {
+        $value.coerce_to_number($activation)?
+    };
+    (@coerce boolean, $value:expr, $activation:expr) => {
+        $value.coerce_to_boolean()
+    };
+    (@coerce int, $value:expr, $activation:expr) => {
+        $value.coerce_to_i32($activation)?
+    };
+    (@coerce uint, $value:expr, $activation:expr) => {
+        $value.coerce_to_u32($activation)?
+    };
+    (@coerce string, $value:expr, $activation:expr) => {
+        $value.coerce_to_string($activation)?
+    };
+    ($variant:ident, $($getter:ident, $setter:ident, $field:ident: $kind:ident;)*) => {
+        $(
+            /// Implements the getter for a field of this event's data.
+            pub fn $getter<'gc>(
+                _activation: &mut Activation<'_, 'gc, '_>,
+                this: Option<Object<'gc>>,
+                _args: &[Value<'gc>],
+            ) -> Result<Value<'gc>, Error<'gc>> {
+                if let Some(this) = this {
+                    if let Some(evt) = this.as_event() {
+                        if let EventData::$variant { $field, .. } = evt.event_data() {
+                            return Ok((*$field).into());
+                        }
+                    }
+                }
+
+                Ok(Value::Undefined)
+            }
+
+            /// Implements the setter for a field of this event's data.
+            pub fn $setter<'gc>(
+                activation: &mut Activation<'_, 'gc, '_>,
+                this: Option<Object<'gc>>,
+                args: &[Value<'gc>],
+            ) -> Result<Value<'gc>, Error<'gc>> {
+                let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+                let value = event_data_accessors!(@coerce $kind, value, activation);
+
+                if let Some(this) = this {
+                    if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+                        if let EventData::$variant { $field, .. } = evt.event_data_mut() {
+                            *$field = value;
+                        }
+                    }
+                }
+
+                Ok(Value::Undefined)
+            }
+        )*
+    };
+}
+
 pub mod event;
 pub mod eventdispatcher;
+pub mod focusevent;
 pub mod ieventdispatcher;
+pub mod ioerrorevent;
+pub mod keyboardevent;
+pub mod mouseevent;
+pub mod progressevent;
+pub mod timerevent;
@@ -17,7 +17,7 @@ pub fn instance_init<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         activation.super_init(this, &[])?;
 
@@ -51,7 +51,7 @@ pub fn class_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(Value::Undefined)
 }
 
@@ -60,7 +60,7 @@ pub fn bubbles<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(evt) = this.unwrap().as_event() {
         return Ok(evt.is_bubbling().into());
     }
@@ -73,7 +73,7 @@ pub fn cancelable<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(evt) = this.unwrap().as_event() {
         return Ok(evt.is_cancelable().into());
     }
@@ -86,7 +86,7 @@ pub fn get_type<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(evt) = this.unwrap().as_event() {
         return Ok(evt.event_type().into());
     }
@@ -99,7 +99,7 @@ pub fn target<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(evt) = this.unwrap().as_event() {
         return Ok(evt.target().map(|o| o.into()).unwrap_or(Value::Null));
     }
@@ -112,7 +112,7 @@ pub fn current_target<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(evt) = this.unwrap().as_event() {
         return Ok(evt
             .current_target()
@@ -128,7 +128,7 @@ pub fn event_phase<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(evt) = this.unwrap().as_event() {
         let event_phase: u32 = evt.phase().into();
         return Ok(event_phase.into());
@@ -142,7 +142,7 @@ pub fn clone<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(evt) = this.unwrap().as_event() {
         let evt_proto = activation.avm2().system_prototypes.as_ref().unwrap().event;
 
@@ -162,7 +162,7 @@ pub fn format_to_string<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mut this) = this {
         let class_name = args
             .get(0)
@@ -200,7 +200,7 @@ pub fn is_default_prevented<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(evt) = this.unwrap().as_event() {
         return Ok(evt.is_cancelled().into());
     }
@@ -213,7 +213,7 @@ pub fn prevent_default<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mut evt) = this.unwrap().as_event_mut(activation.context.gc_context) {
         evt.cancel();
     }
@@ -226,7 +226,7 @@ pub fn stop_propagation<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mut evt) = this.unwrap().as_event_mut(activation.context.gc_context) {
         evt.stop_propagation();
     }
@@ -239,7 +239,7 @@ pub fn stop_immediate_propagation<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mut evt) = this.unwrap().as_event_mut(activation.context.gc_context) {
         evt.stop_immediate_propagation();
     }
@@ -252,7 +252,7 @@ pub fn to_string<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         return this.value_of(activation.context.gc_context);
     }
@@ -622,7 +622,7 @@ pub fn event_deriver<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     class: GcCell<'gc, Class<'gc>>,
     scope: Option<GcCell<'gc, Scope<'gc>>>,
-) -> Result<Object<'gc>, Error> {
+) -> Result<Object<'gc>, Error<'gc>> {
     Ok(EventObject::derive(
         base_proto,
         activation.context.gc_context,
@@ -9,7 +9,7 @@ use crate::avm2::names::{Namespace, QName};
 use crate::avm2::object::{DispatchObject, Object, TObject};
 use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
-use crate::avm2::Error;
+use crate::avm2::{Avm2, Error};
 use crate::display_object::TDisplayObject;
 use gc_arena::{GcCell, MutationContext};
 
@@ -20,7 +20,7 @@ pub fn instance_init<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mut this) = this {
         activation.super_init(this, &[])?;
 
@@ -49,7 +49,7 @@ pub fn add_event_listener<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mut this) = this {
         let dispatch_list = this
             .get_property(
@@ -84,6 +84,10 @@ pub fn add_event_listener<'gc>(
             .as_dispatch_mut(activation.context.gc_context)
             .ok_or_else(|| Error::from("Internal properties should have what I put in them"))?
             .add_event_listener(event_type, priority, listener, use_capture);
+
+        if this.as_display_object().is_some() {
+            Avm2::register_broadcast_listener(&mut activation.context, this, event_type);
+        }
     }
 
     Ok(Value::Undefined)
@@ -94,7 +98,7 @@ pub fn remove_event_listener<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mut this) = this {
         let dispatch_list = this
             .get_property(
@@ -123,6 +127,15 @@ pub fn remove_event_listener<'gc>(
             .as_dispatch_mut(activation.context.gc_context)
             .ok_or_else(|| Error::from("Internal properties should have what I put in them"))?
             .remove_event_listener(event_type, listener, use_capture);
+
+        let has_listeners = dispatch_list
+            .as_dispatch_mut(activation.context.gc_context)
+            .ok_or_else(|| Error::from("Internal properties should have what I put in them"))?
+            .has_event_listener(event_type);
+
+        if !has_listeners {
+            Avm2::unregister_broadcast_listener(&mut activation.context, this, event_type);
+        }
     }
 
     Ok(Value::Undefined)
@@ -133,7 +146,7 @@ pub fn has_event_listener<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mut this) = this {
         let dispatch_list = this
             .get_property(
@@ -181,7 +194,7 @@ pub fn will_trigger<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mut this) = this {
         let dispatch_list = this
             .get_property(
@@ -232,7 +245,7 @@ pub fn dispatch_event_to_target<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     mut target: Object<'gc>,
     event: Object<'gc>,
-) -> Result<(), Error> {
+) -> Result<(), Error<'gc>> {
     let dispatch_list = target
         .get_property(
             target,
@@ -280,7 +293,7 @@ pub fn dispatch_event<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     let event = args
         .get(0)
         .cloned()
@@ -359,7 +372,7 @@ pub fn class_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(Value::Undefined)
 }
 
@@ -0,0 +1,239 @@
+//! `flash.events.FocusEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::events::EventData;
+use crate::avm2::globals::flash::events::event::format_to_string;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{EventObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.FocusEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let event_args = [
+            args.get(0).cloned().unwrap_or(Value::Undefined),
+            args.get(1).cloned().unwrap_or(Value::Bool(true)),
+            args.get(2).cloned().unwrap_or(Value::Bool(false)),
+        ];
+        activation.super_init(this, &event_args)?;
+
+        let related_object = match args.get(3).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            related_object => Some(related_object.coerce_to_object(activation)?),
+        };
+        let shift_key = args
+            .get(4)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+        let key_code = args
+            .get(5)
+            .cloned()
+            .unwrap_or(Value::Unsigned(0))
+            .coerce_to_u32(activation)?;
+
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            *evt.event_data_mut() = EventData::Focus {
+                related_object,
+                shift_key,
+                key_code,
+            };
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.FocusEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+event_data_accessors!(
+    Focus,
+    shift_key, set_shift_key, shift_key: boolean;
+    key_code, set_key_code, key_code: uint;
+);
+
+/// Implements `relatedObject`'s getter.
+pub fn related_object<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(evt) = this.as_event() {
+            if let EventData::Focus { related_object, .. } = evt.event_data() {
+                return Ok(related_object.map(|o| o.into()).unwrap_or(Value::Null));
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `relatedObject`'s setter.
+pub fn set_related_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = match args.get(0).cloned().unwrap_or(Value::Null) {
+        Value::Undefined | Value::Null => None,
+        value => Some(value.coerce_to_object(activation)?),
+    };
+
+    if let Some(this) = this {
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            if let EventData::Focus { related_object, .. } = evt.event_data_mut() {
+                *related_object = value;
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `isRelatedObjectInaccessible`.
+///
+/// We do not implement security sandboxes, so related objects are always
+/// accessible.
+pub fn is_related_object_inaccessible<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(false.into())
+}
+
+/// Implements `clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(evt) = this.as_event() {
+            let evt_proto = activation.avm2().prototypes().focusevent;
+
+            return Ok(EventObject::from_event(
+                activation.context.gc_context,
+                Some(evt_proto),
+                evt.clone(),
+            )
+            .into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    format_to_string(
+        activation,
+        this,
+        &[
+            "FocusEvent".into(),
+            "type".into(),
+            "bubbles".into(),
+            "cancelable".into(),
+            "eventPhase".into(),
+            "relatedObject".into(),
+            "shiftKey".into(),
+            "keyCode".into(),
+        ],
+    )
+}
+
+/// Construct `FocusEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "FocusEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "relatedObject"),
+        Method::from_builtin(related_object),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "relatedObject"),
+        Method::from_builtin(set_related_object),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "shiftKey"),
+        Method::from_builtin(shift_key),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "shiftKey"),
+        Method::from_builtin(set_shift_key),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "keyCode"),
+        Method::from_builtin(key_code),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "keyCode"),
+        Method::from_builtin(set_key_code),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "isRelatedObjectInaccessible"),
+        Method::from_builtin(is_related_object_inaccessible),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clone"),
+        Method::from_builtin(clone),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "FOCUS_IN"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("focusIn".into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "FOCUS_OUT"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("focusOut".into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "KEY_FOCUS_CHANGE"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("keyFocusChange".into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "MOUSE_FOCUS_CHANGE"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("mouseFocusChange".into()),
+    ));
+
+    class
+}
@@ -0,0 +1,167 @@
+//! `flash.events.IOErrorEvent` builtin/prototype
+//!
+//! `ErrorEvent` and `TextEvent` are not implemented yet, so this class
+//! extends `Event` directly and provides their `text` and `errorID`
+//! properties itself.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::events::EventData;
+use crate::avm2::globals::flash::events::event::format_to_string;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{EventObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.IOErrorEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let event_args = [
+            args.get(0).cloned().unwrap_or(Value::Undefined),
+            args.get(1).cloned().unwrap_or(Value::Bool(false)),
+            args.get(2).cloned().unwrap_or(Value::Bool(false)),
+        ];
+        activation.super_init(this, &event_args)?;
+
+        let text = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| "".into())
+            .coerce_to_string(activation)?;
+        let error_id = args
+            .get(4)
+            .cloned()
+            .unwrap_or(Value::Integer(0))
+            .coerce_to_i32(activation)?;
+
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            *evt.event_data_mut() = EventData::IOError { text, error_id };
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.IOErrorEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+event_data_accessors!(
+    IOError,
+    text, set_text, text: string;
+);
+
+/// Implements `errorID`'s getter.
+pub fn error_id<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(evt) = this.as_event() {
+            if let EventData::IOError { error_id, .. } = evt.event_data() {
+                return Ok((*error_id).into());
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(evt) = this.as_event() {
+            let evt_proto = activation.avm2().prototypes().ioerrorevent;
+
+            return Ok(EventObject::from_event(
+                activation.context.gc_context,
+                Some(evt_proto),
+                evt.clone(),
+            )
+            .into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    format_to_string(
+        activation,
+        this,
+        &[
+            "IOErrorEvent".into(),
+            "type".into(),
+            "bubbles".into(),
+            "cancelable".into(),
+            "eventPhase".into(),
+            "text".into(),
+        ],
+    )
+}
+
+/// Construct `IOErrorEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "IOErrorEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "text"),
+        Method::from_builtin(text),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "text"),
+        Method::from_builtin(set_text),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "errorID"),
+        Method::from_builtin(error_id),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clone"),
+        Method::from_builtin(clone),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "IO_ERROR"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("ioError".into()),
+    ));
+
+    class
+}
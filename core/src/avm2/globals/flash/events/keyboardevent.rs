@@ -0,0 +1,239 @@
+//! `flash.events.KeyboardEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::events::EventData;
+use crate::avm2::globals::flash::events::event::format_to_string;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{EventObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.KeyboardEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let event_args = [
+            args.get(0).cloned().unwrap_or(Value::Undefined),
+            args.get(1).cloned().unwrap_or(Value::Bool(true)),
+            args.get(2).cloned().unwrap_or(Value::Bool(false)),
+        ];
+        activation.super_init(this, &event_args)?;
+
+        let char_code = args
+            .get(3)
+            .cloned()
+            .unwrap_or(Value::Unsigned(0))
+            .coerce_to_u32(activation)?;
+        let key_code = args
+            .get(4)
+            .cloned()
+            .unwrap_or(Value::Unsigned(0))
+            .coerce_to_u32(activation)?;
+        let key_location = args
+            .get(5)
+            .cloned()
+            .unwrap_or(Value::Unsigned(0))
+            .coerce_to_u32(activation)?;
+        let ctrl_key = args
+            .get(6)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+        let alt_key = args
+            .get(7)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+        let shift_key = args
+            .get(8)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            *evt.event_data_mut() = EventData::Keyboard {
+                char_code,
+                key_code,
+                key_location,
+                ctrl_key,
+                alt_key,
+                shift_key,
+            };
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.KeyboardEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+event_data_accessors!(
+    Keyboard,
+    char_code, set_char_code, char_code: uint;
+    key_code, set_key_code, key_code: uint;
+    key_location, set_key_location, key_location: uint;
+    ctrl_key, set_ctrl_key, ctrl_key: boolean;
+    alt_key, set_alt_key, alt_key: boolean;
+    shift_key, set_shift_key, shift_key: boolean;
+);
+
+/// Implements `updateAfterEvent`.
+pub fn update_after_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    *activation.context.needs_render = true;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(evt) = this.as_event() {
+            let evt_proto = activation.avm2().prototypes().keyboardevent;
+
+            return Ok(EventObject::from_event(
+                activation.context.gc_context,
+                Some(evt_proto),
+                evt.clone(),
+            )
+            .into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    format_to_string(
+        activation,
+        this,
+        &[
+            "KeyboardEvent".into(),
+            "type".into(),
+            "bubbles".into(),
+            "cancelable".into(),
+            "eventPhase".into(),
+            "charCode".into(),
+            "keyCode".into(),
+            "keyLocation".into(),
+            "ctrlKey".into(),
+            "altKey".into(),
+            "shiftKey".into(),
+        ],
+    )
+}
+
+/// Construct `KeyboardEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "KeyboardEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "charCode"),
+        Method::from_builtin(char_code),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "charCode"),
+        Method::from_builtin(set_char_code),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "keyCode"),
+        Method::from_builtin(key_code),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "keyCode"),
+        Method::from_builtin(set_key_code),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "keyLocation"),
+        Method::from_builtin(key_location),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "keyLocation"),
+        Method::from_builtin(set_key_location),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "ctrlKey"),
+        Method::from_builtin(ctrl_key),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "ctrlKey"),
+        Method::from_builtin(set_ctrl_key),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "altKey"),
+        Method::from_builtin(alt_key),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "altKey"),
+        Method::from_builtin(set_alt_key),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "shiftKey"),
+        Method::from_builtin(shift_key),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "shiftKey"),
+        Method::from_builtin(set_shift_key),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "updateAfterEvent"),
+        Method::from_builtin(update_after_event),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clone"),
+        Method::from_builtin(clone),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "KEY_DOWN"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("keyDown".into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "KEY_UP"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("keyUp".into()),
+    ));
+
+    class
+}
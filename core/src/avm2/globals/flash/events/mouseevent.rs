@@ -0,0 +1,403 @@
+//! `flash.events.MouseEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::events::EventData;
+use crate::avm2::globals::flash::events::event::format_to_string;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{EventObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::TDisplayObject;
+use crate::prelude::Twips;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.MouseEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let event_args = [
+            args.get(0).cloned().unwrap_or(Value::Undefined),
+            args.get(1).cloned().unwrap_or(Value::Bool(true)),
+            args.get(2).cloned().unwrap_or(Value::Bool(false)),
+        ];
+        activation.super_init(this, &event_args)?;
+
+        let local_x = args
+            .get(3)
+            .cloned()
+            .unwrap_or(Value::Number(f64::NAN))
+            .coerce_to_number(activation)?;
+        let local_y = args
+            .get(4)
+            .cloned()
+            .unwrap_or(Value::Number(f64::NAN))
+            .coerce_to_number(activation)?;
+        let related_object = match args.get(5).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            related_object => Some(related_object.coerce_to_object(activation)?),
+        };
+        let ctrl_key = args
+            .get(6)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+        let alt_key = args
+            .get(7)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+        let shift_key = args
+            .get(8)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+        let button_down = args
+            .get(9)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+        let delta = args
+            .get(10)
+            .cloned()
+            .unwrap_or(Value::Integer(0))
+            .coerce_to_i32(activation)?;
+
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            *evt.event_data_mut() = EventData::Mouse {
+                local_x,
+                local_y,
+                related_object,
+                ctrl_key,
+                alt_key,
+                shift_key,
+                button_down,
+                delta,
+            };
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.MouseEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+event_data_accessors!(
+    Mouse,
+    local_x, set_local_x, local_x: number;
+    local_y, set_local_y, local_y: number;
+    ctrl_key, set_ctrl_key, ctrl_key: boolean;
+    alt_key, set_alt_key, alt_key: boolean;
+    shift_key, set_shift_key, shift_key: boolean;
+    button_down, set_button_down, button_down: boolean;
+    delta, set_delta, delta: int;
+);
+
+/// Implements `relatedObject`'s getter.
+pub fn related_object<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(evt) = this.as_event() {
+            if let EventData::Mouse { related_object, .. } = evt.event_data() {
+                return Ok(related_object.map(|o| o.into()).unwrap_or(Value::Null));
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `relatedObject`'s setter.
+pub fn set_related_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = match args.get(0).cloned().unwrap_or(Value::Null) {
+        Value::Undefined | Value::Null => None,
+        value => Some(value.coerce_to_object(activation)?),
+    };
+
+    if let Some(this) = this {
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            if let EventData::Mouse { related_object, .. } = evt.event_data_mut() {
+                *related_object = value;
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Convert the local coordinates of a mouse event to stage coordinates.
+///
+/// The coordinates are relative to the event's target, or the stage if the
+/// target is not a display object.
+fn stage_position(this: Object<'_>) -> Option<(f64, f64)> {
+    let evt = this.as_event()?;
+
+    if let EventData::Mouse {
+        local_x, local_y, ..
+    } = evt.event_data()
+    {
+        let local = (Twips::from_pixels(*local_x), Twips::from_pixels(*local_y));
+        let global = match evt.target().and_then(|t| t.as_display_object()) {
+            Some(target) => target.local_to_global(local),
+            None => local,
+        };
+
+        return Some((global.0.to_pixels(), global.1.to_pixels()));
+    }
+
+    None
+}
+
+/// Implements `stageX`'s getter.
+pub fn stage_x<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some((x, _)) = this.and_then(stage_position) {
+        return Ok(x.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `stageY`'s getter.
+pub fn stage_y<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some((_, y)) = this.and_then(stage_position) {
+        return Ok(y.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `isRelatedObjectInaccessible`.
+///
+/// We do not implement security sandboxes, so related objects are always
+/// accessible.
+pub fn is_related_object_inaccessible<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(false.into())
+}
+
+/// Implements `updateAfterEvent`.
+pub fn update_after_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    *activation.context.needs_render = true;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(evt) = this.as_event() {
+            let evt_proto = activation.avm2().prototypes().mouseevent;
+
+            return Ok(EventObject::from_event(
+                activation.context.gc_context,
+                Some(evt_proto),
+                evt.clone(),
+            )
+            .into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    format_to_string(
+        activation,
+        this,
+        &[
+            "MouseEvent".into(),
+            "type".into(),
+            "bubbles".into(),
+            "cancelable".into(),
+            "eventPhase".into(),
+            "localX".into(),
+            "localY".into(),
+            "stageX".into(),
+            "stageY".into(),
+            "relatedObject".into(),
+            "ctrlKey".into(),
+            "altKey".into(),
+            "shiftKey".into(),
+            "buttonDown".into(),
+            "delta".into(),
+        ],
+    )
+}
+
+/// Construct `MouseEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "MouseEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "localX"),
+        Method::from_builtin(local_x),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "localX"),
+        Method::from_builtin(set_local_x),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "localY"),
+        Method::from_builtin(local_y),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "localY"),
+        Method::from_builtin(set_local_y),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "stageX"),
+        Method::from_builtin(stage_x),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "stageY"),
+        Method::from_builtin(stage_y),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "relatedObject"),
+        Method::from_builtin(related_object),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "relatedObject"),
+        Method::from_builtin(set_related_object),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "ctrlKey"),
+        Method::from_builtin(ctrl_key),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "ctrlKey"),
+        Method::from_builtin(set_ctrl_key),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "altKey"),
+        Method::from_builtin(alt_key),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "altKey"),
+        Method::from_builtin(set_alt_key),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "shiftKey"),
+        Method::from_builtin(shift_key),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "shiftKey"),
+        Method::from_builtin(set_shift_key),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "buttonDown"),
+        Method::from_builtin(button_down),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "buttonDown"),
+        Method::from_builtin(set_button_down),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "delta"),
+        Method::from_builtin(delta),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "delta"),
+        Method::from_builtin(set_delta),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "isRelatedObjectInaccessible"),
+        Method::from_builtin(is_related_object_inaccessible),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "updateAfterEvent"),
+        Method::from_builtin(update_after_event),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clone"),
+        Method::from_builtin(clone),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    const CONSTANTS: &[(&str, &str)] = &[
+        ("CLICK", "click"),
+        ("CONTEXT_MENU", "contextMenu"),
+        ("DOUBLE_CLICK", "doubleClick"),
+        ("MIDDLE_CLICK", "middleClick"),
+        ("MIDDLE_MOUSE_DOWN", "middleMouseDown"),
+        ("MIDDLE_MOUSE_UP", "middleMouseUp"),
+        ("MOUSE_DOWN", "mouseDown"),
+        ("MOUSE_MOVE", "mouseMove"),
+        ("MOUSE_OUT", "mouseOut"),
+        ("MOUSE_OVER", "mouseOver"),
+        ("MOUSE_UP", "mouseUp"),
+        ("MOUSE_WHEEL", "mouseWheel"),
+        ("RELEASE_OUTSIDE", "releaseOutside"),
+        ("RIGHT_CLICK", "rightClick"),
+        ("RIGHT_MOUSE_DOWN", "rightMouseDown"),
+        ("RIGHT_MOUSE_UP", "rightMouseUp"),
+        ("ROLL_OUT", "rollOut"),
+        ("ROLL_OVER", "rollOver"),
+    ];
+
+    for (name, value) in CONSTANTS {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public(), *name),
+            QName::new(Namespace::public(), "String").into(),
+            Some((*value).into()),
+        ));
+    }
+
+    class
+}
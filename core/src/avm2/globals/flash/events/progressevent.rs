@@ -0,0 +1,160 @@
+//! `flash.events.ProgressEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::events::EventData;
+use crate::avm2::globals::flash::events::event::format_to_string;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{EventObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.ProgressEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let event_args = [
+            args.get(0).cloned().unwrap_or(Value::Undefined),
+            args.get(1).cloned().unwrap_or(Value::Bool(false)),
+            args.get(2).cloned().unwrap_or(Value::Bool(false)),
+        ];
+        activation.super_init(this, &event_args)?;
+
+        let bytes_loaded = args
+            .get(3)
+            .cloned()
+            .unwrap_or(Value::Number(0.0))
+            .coerce_to_number(activation)?;
+        let bytes_total = args
+            .get(4)
+            .cloned()
+            .unwrap_or(Value::Number(0.0))
+            .coerce_to_number(activation)?;
+
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            *evt.event_data_mut() = EventData::Progress {
+                bytes_loaded,
+                bytes_total,
+            };
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.ProgressEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+event_data_accessors!(
+    Progress,
+    bytes_loaded, set_bytes_loaded, bytes_loaded: number;
+    bytes_total, set_bytes_total, bytes_total: number;
+);
+
+/// Implements `clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(evt) = this.as_event() {
+            let evt_proto = activation.avm2().prototypes().progressevent;
+
+            return Ok(EventObject::from_event(
+                activation.context.gc_context,
+                Some(evt_proto),
+                evt.clone(),
+            )
+            .into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    format_to_string(
+        activation,
+        this,
+        &[
+            "ProgressEvent".into(),
+            "type".into(),
+            "bubbles".into(),
+            "cancelable".into(),
+            "eventPhase".into(),
+            "bytesLoaded".into(),
+            "bytesTotal".into(),
+        ],
+    )
+}
+
+/// Construct `ProgressEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "ProgressEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bytesLoaded"),
+        Method::from_builtin(bytes_loaded),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "bytesLoaded"),
+        Method::from_builtin(set_bytes_loaded),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bytesTotal"),
+        Method::from_builtin(bytes_total),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "bytesTotal"),
+        Method::from_builtin(set_bytes_total),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clone"),
+        Method::from_builtin(clone),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "PROGRESS"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("progress".into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "SOCKET_DATA"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("socketData".into()),
+    ));
+
+    class
+}
@@ -0,0 +1,24 @@
+//! `flash.geom` namespace
+
+use crate::avm2::activation::Activation;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+
+pub mod colortransform;
+pub mod matrix;
+pub mod matrix3d;
+pub mod point;
+pub mod rectangle;
+pub mod transform;
+
+/// Coerce the given argument to a number, defaulting to zero.
+fn number_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<f64, Error<'gc>> {
+    args.get(index)
+        .cloned()
+        .unwrap_or_else(|| 0.into())
+        .coerce_to_number(activation)
+}
@@ -0,0 +1,292 @@
+//! `flash.geom.ColorTransform` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::color_transform::ColorTransform;
+use gc_arena::{GcCell, MutationContext};
+
+/// The names of each of a `ColorTransform`'s components, in constructor order.
+const COMPONENTS: [&str; 8] = [
+    "redMultiplier",
+    "greenMultiplier",
+    "blueMultiplier",
+    "alphaMultiplier",
+    "redOffset",
+    "greenOffset",
+    "blueOffset",
+    "alphaOffset",
+];
+
+/// Construct a new `ColorTransform` with the values of the given transform.
+pub fn new_color_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    color_transform: &ColorTransform,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let color_transform_proto = activation.context.avm2.prototypes().colortransform;
+    let args = [
+        color_transform.r_mult.into(),
+        color_transform.g_mult.into(),
+        color_transform.b_mult.into(),
+        color_transform.a_mult.into(),
+        (color_transform.r_add * 255.0).into(),
+        (color_transform.g_add * 255.0).into(),
+        (color_transform.b_add * 255.0).into(),
+        (color_transform.a_add * 255.0).into(),
+    ];
+    let object = color_transform_proto.construct(activation, &args)?;
+
+    instance_init(activation, Some(object), &args)?;
+
+    Ok(object)
+}
+
+/// Read the values of a `ColorTransform`, or of any object with color
+/// transform properties.
+pub fn object_to_color_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+) -> Result<ColorTransform, Error<'gc>> {
+    let mut get = |name: &'static str| -> Result<f32, Error<'gc>> {
+        Ok(object
+            .get_property(object, &QName::new(Namespace::public(), name), activation)?
+            .coerce_to_number(activation)? as f32)
+    };
+
+    Ok(ColorTransform {
+        r_mult: get("redMultiplier")?,
+        g_mult: get("greenMultiplier")?,
+        b_mult: get("blueMultiplier")?,
+        a_mult: get("alphaMultiplier")?,
+        r_add: get("redOffset")? / 255.0,
+        g_add: get("greenOffset")? / 255.0,
+        b_add: get("blueOffset")? / 255.0,
+        a_add: get("alphaOffset")? / 255.0,
+    })
+}
+
+/// Overwrite the values of a `ColorTransform` with those of the given
+/// transform.
+fn apply_color_transform_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+    color_transform: &ColorTransform,
+) -> Result<(), Error<'gc>> {
+    let values = [
+        color_transform.r_mult,
+        color_transform.g_mult,
+        color_transform.b_mult,
+        color_transform.a_mult,
+        color_transform.r_add * 255.0,
+        color_transform.g_add * 255.0,
+        color_transform.b_add * 255.0,
+        color_transform.a_add * 255.0,
+    ];
+
+    for (name, value) in COMPONENTS.iter().zip(values.iter()) {
+        object.set_property(
+            object,
+            &QName::new(Namespace::public(), *name),
+            (*value).into(),
+            activation,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Implements `flash.geom.ColorTransform`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        for (index, name) in COMPONENTS.iter().enumerate() {
+            let default = if index < 4 { 1.0 } else { 0.0 };
+            let value = args
+                .get(index)
+                .cloned()
+                .unwrap_or_else(|| default.into())
+                .coerce_to_number(activation)?;
+
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), *name),
+                value.into(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.ColorTransform`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `color`'s getter.
+pub fn color<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let ct = object_to_color_transform(activation, this)?;
+        let red = (ct.r_add * 255.0) as u32 & 0xFF;
+        let green = (ct.g_add * 255.0) as u32 & 0xFF;
+        let blue = (ct.b_add * 255.0) as u32 & 0xFF;
+
+        return Ok(((red << 16) | (green << 8) | blue).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `color`'s setter.
+///
+/// Setting a color replaces the red, green and blue channels outright, so
+/// their multipliers are cleared.
+pub fn set_color<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let color = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+        let mut ct = object_to_color_transform(activation, this)?;
+
+        ct.r_mult = 0.0;
+        ct.g_mult = 0.0;
+        ct.b_mult = 0.0;
+        ct.r_add = ((color >> 16) & 0xFF) as f32 / 255.0;
+        ct.g_add = ((color >> 8) & 0xFF) as f32 / 255.0;
+        ct.b_add = (color & 0xFF) as f32 / 255.0;
+
+        apply_color_transform_to_object(activation, this, &ct)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `concat`.
+///
+/// The second transform is applied first, and this transform is applied to
+/// its result.
+pub fn concat<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let other = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let other = object_to_color_transform(activation, other)?;
+        let ct = object_to_color_transform(activation, this)?;
+
+        let result = ColorTransform {
+            r_mult: other.r_mult * ct.r_mult,
+            g_mult: other.g_mult * ct.g_mult,
+            b_mult: other.b_mult * ct.b_mult,
+            a_mult: other.a_mult * ct.a_mult,
+            r_add: other.r_add * ct.r_mult + ct.r_add,
+            g_add: other.g_add * ct.g_mult + ct.g_add,
+            b_add: other.b_add * ct.b_mult + ct.b_add,
+            a_add: other.a_add * ct.a_mult + ct.a_add,
+        };
+
+        apply_color_transform_to_object(activation, this, &result)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        let mut parts = Vec::with_capacity(COMPONENTS.len());
+
+        for name in COMPONENTS.iter() {
+            let value = this
+                .get_property(this, &QName::new(Namespace::public(), *name), activation)?
+                .coerce_to_string(activation)?;
+
+            parts.push(format!("{}={}", name, value));
+        }
+
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            format!("({})", parts.join(", ")),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `ColorTransform`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "ColorTransform"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    for (index, name) in COMPONENTS.iter().enumerate() {
+        let default = if index < 4 { 1.0 } else { 0.0 };
+
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            QName::new(Namespace::public(), "Number").into(),
+            Some(default.into()),
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "color"),
+        Method::from_builtin(color),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "color"),
+        Method::from_builtin(set_color),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "concat"),
+        Method::from_builtin(concat),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    class
+}
@@ -0,0 +1,486 @@
+//! `flash.geom.Matrix` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::geom::number_arg;
+use crate::avm2::globals::flash::geom::point::{new_point, point_arg};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+use swf::{Matrix, Twips};
+
+/// Construct a new `Matrix` with the values of the given matrix.
+pub fn new_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    matrix: &Matrix,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let matrix_proto = activation.context.avm2.prototypes().matrix;
+    let args = [
+        matrix.a.into(),
+        matrix.b.into(),
+        matrix.c.into(),
+        matrix.d.into(),
+        matrix.tx.to_pixels().into(),
+        matrix.ty.to_pixels().into(),
+    ];
+    let object = matrix_proto.construct(activation, &args)?;
+
+    instance_init(activation, Some(object), &args)?;
+
+    Ok(object)
+}
+
+/// Read the values of a `Matrix`, or of any object with matrix properties.
+pub fn object_to_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+) -> Result<Matrix, Error<'gc>> {
+    let mut get = |name: &'static str| -> Result<f64, Error<'gc>> {
+        object
+            .get_property(object, &QName::new(Namespace::public(), name), activation)?
+            .coerce_to_number(activation)
+    };
+
+    Ok(Matrix {
+        a: get("a")? as f32,
+        b: get("b")? as f32,
+        c: get("c")? as f32,
+        d: get("d")? as f32,
+        tx: Twips::from_pixels(get("tx")?),
+        ty: Twips::from_pixels(get("ty")?),
+    })
+}
+
+/// Overwrite the values of a `Matrix` with those of the given matrix.
+pub fn apply_matrix_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+    matrix: &Matrix,
+) -> Result<(), Error<'gc>> {
+    for (name, value) in &[
+        ("a", f64::from(matrix.a)),
+        ("b", f64::from(matrix.b)),
+        ("c", f64::from(matrix.c)),
+        ("d", f64::from(matrix.d)),
+        ("tx", matrix.tx.to_pixels()),
+        ("ty", matrix.ty.to_pixels()),
+    ] {
+        object.set_property(
+            object,
+            &QName::new(Namespace::public(), *name),
+            (*value).into(),
+            activation,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Implements `flash.geom.Matrix`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        let a = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| 1.into())
+            .coerce_to_number(activation)?;
+        let b = number_arg(activation, args, 1)?;
+        let c = number_arg(activation, args, 2)?;
+        let d = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| 1.into())
+            .coerce_to_number(activation)?;
+        let tx = number_arg(activation, args, 4)?;
+        let ty = number_arg(activation, args, 5)?;
+
+        set_to(
+            activation,
+            Some(this),
+            &[a.into(), b.into(), c.into(), d.into(), tx.into(), ty.into()],
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Matrix`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let matrix = object_to_matrix(activation, this)?;
+
+        return Ok(new_matrix(activation, &matrix)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `concat`.
+pub fn concat<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let other = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let other = object_to_matrix(activation, other)?;
+        let matrix = other * object_to_matrix(activation, this)?;
+        apply_matrix_to_object(activation, this, &matrix)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `copyFrom`.
+pub fn copy_from<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let other = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let matrix = object_to_matrix(activation, other)?;
+        apply_matrix_to_object(activation, this, &matrix)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `createBox`.
+pub fn create_box<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let scale_x = number_arg(activation, args, 0)?;
+        let scale_y = number_arg(activation, args, 1)?;
+        let rotation = number_arg(activation, args, 2)?;
+        let translate_x = number_arg(activation, args, 3)?;
+        let translate_y = number_arg(activation, args, 4)?;
+
+        let matrix = Matrix::create_box(
+            scale_x as f32,
+            scale_y as f32,
+            rotation as f32,
+            Twips::from_pixels(translate_x),
+            Twips::from_pixels(translate_y),
+        );
+        apply_matrix_to_object(activation, this, &matrix)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `createGradientBox`.
+pub fn create_gradient_box<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let width = number_arg(activation, args, 0)?;
+        let height = number_arg(activation, args, 1)?;
+        let rotation = number_arg(activation, args, 2)?;
+        let translate_x = number_arg(activation, args, 3)?;
+        let translate_y = number_arg(activation, args, 4)?;
+
+        let matrix = Matrix::create_gradient_box(
+            width as f32,
+            height as f32,
+            rotation as f32,
+            Twips::from_pixels(translate_x),
+            Twips::from_pixels(translate_y),
+        );
+        apply_matrix_to_object(activation, this, &matrix)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `deltaTransformPoint`.
+///
+/// This ignores the translation of the matrix.
+pub fn delta_transform_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let matrix = object_to_matrix(activation, this)?;
+        let (x, y) = point_arg(activation, args, 0)?;
+
+        let new_x = x * f64::from(matrix.a) + y * f64::from(matrix.c);
+        let new_y = x * f64::from(matrix.b) + y * f64::from(matrix.d);
+
+        return Ok(new_point(activation, new_x, new_y)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `identity`.
+pub fn identity<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        apply_matrix_to_object(activation, this, &Matrix::identity())?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `invert`.
+pub fn invert<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let mut matrix = object_to_matrix(activation, this)?;
+        matrix.invert();
+        apply_matrix_to_object(activation, this, &matrix)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `rotate`.
+pub fn rotate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let angle = number_arg(activation, args, 0)?;
+        let matrix = Matrix::rotate(angle as f32) * object_to_matrix(activation, this)?;
+        apply_matrix_to_object(activation, this, &matrix)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `scale`.
+pub fn scale<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let scale_x = number_arg(activation, args, 0)?;
+        let scale_y = number_arg(activation, args, 1)?;
+        let matrix =
+            Matrix::scale(scale_x as f32, scale_y as f32) * object_to_matrix(activation, this)?;
+        apply_matrix_to_object(activation, this, &matrix)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `setTo`.
+pub fn set_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        for (index, name) in ["a", "b", "c", "d", "tx", "ty"].iter().enumerate() {
+            let value = number_arg(activation, args, index)?;
+
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), *name),
+                value.into(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `transformPoint`.
+pub fn transform_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let matrix = object_to_matrix(activation, this)?;
+        let (x, y) = point_arg(activation, args, 0)?;
+
+        let new_x = x * f64::from(matrix.a) + y * f64::from(matrix.c) + matrix.tx.to_pixels();
+        let new_y = x * f64::from(matrix.b) + y * f64::from(matrix.d) + matrix.ty.to_pixels();
+
+        return Ok(new_point(activation, new_x, new_y)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `translate`.
+pub fn translate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let translate_x = number_arg(activation, args, 0)?;
+        let translate_y = number_arg(activation, args, 1)?;
+        let matrix = Matrix::translate(
+            Twips::from_pixels(translate_x),
+            Twips::from_pixels(translate_y),
+        ) * object_to_matrix(activation, this)?;
+        apply_matrix_to_object(activation, this, &matrix)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        let mut parts = Vec::with_capacity(6);
+
+        for name in &["a", "b", "c", "d", "tx", "ty"] {
+            let value = this
+                .get_property(this, &QName::new(Namespace::public(), *name), activation)?
+                .coerce_to_string(activation)?;
+
+            parts.push(format!("{}={}", name, value));
+        }
+
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            format!("({})", parts.join(", ")),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Matrix`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Matrix"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    for (name, default) in &[
+        ("a", 1.0),
+        ("b", 0.0),
+        ("c", 0.0),
+        ("d", 1.0),
+        ("tx", 0.0),
+        ("ty", 0.0),
+    ] {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            QName::new(Namespace::public(), "Number").into(),
+            Some((*default).into()),
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clone"),
+        Method::from_builtin(clone),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "concat"),
+        Method::from_builtin(concat),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "copyFrom"),
+        Method::from_builtin(copy_from),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "createBox"),
+        Method::from_builtin(create_box),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "createGradientBox"),
+        Method::from_builtin(create_gradient_box),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "deltaTransformPoint"),
+        Method::from_builtin(delta_transform_point),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "identity"),
+        Method::from_builtin(identity),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "invert"),
+        Method::from_builtin(invert),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "rotate"),
+        Method::from_builtin(rotate),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "scale"),
+        Method::from_builtin(scale),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "setTo"),
+        Method::from_builtin(set_to),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "transformPoint"),
+        Method::from_builtin(transform_point),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "translate"),
+        Method::from_builtin(translate),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    class
+}
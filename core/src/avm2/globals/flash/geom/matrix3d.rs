@@ -0,0 +1,119 @@
+//! `flash.geom.Matrix3D` builtin/prototype
+//!
+//! We do not support 3D transforms, so this is only a stub that holds on to
+//! its raw data without interpreting it.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The name of the hidden slot holding the raw data of a `Matrix3D`.
+fn raw_data_slot_name<'gc>() -> QName<'gc> {
+    QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "rawData")
+}
+
+/// Implements `flash.geom.Matrix3D`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        log::warn!("Matrix3D: 3D transforms are not yet supported");
+
+        set_raw_data(activation, Some(this), args)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Matrix3D`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `rawData`'s getter.
+pub fn raw_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        return this.get_property(this, &raw_data_slot_name(), activation);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `rawData`'s setter.
+pub fn set_raw_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        let raw_data = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => Value::Null,
+            raw_data => raw_data.coerce_to_object(activation)?.into(),
+        };
+
+        this.set_property(this, &raw_data_slot_name(), raw_data, activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `identity`.
+pub fn identity<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    set_raw_data(activation, this, &[])
+}
+
+/// Construct `Matrix3D`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Matrix3D"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_slot(
+        raw_data_slot_name(),
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "BareObject").into(),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "rawData"),
+        Method::from_builtin(raw_data),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "rawData"),
+        Method::from_builtin(set_raw_data),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "identity"),
+        Method::from_builtin(identity),
+    ));
+
+    class
+}
@@ -0,0 +1,390 @@
+//! `flash.geom.Point` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::geom::number_arg;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Construct a new `Point` at the given coordinates.
+pub fn new_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    x: f64,
+    y: f64,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let point_proto = activation.context.avm2.prototypes().point;
+    let args = [x.into(), y.into()];
+    let point = point_proto.construct(activation, &args)?;
+
+    instance_init(activation, Some(point), &args)?;
+
+    Ok(point)
+}
+
+/// Read the coordinates of a `Point`, or of any object with `x` and `y`
+/// properties.
+pub fn object_to_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+) -> Result<(f64, f64), Error<'gc>> {
+    let x = object
+        .get_property(object, &QName::new(Namespace::public(), "x"), activation)?
+        .coerce_to_number(activation)?;
+    let y = object
+        .get_property(object, &QName::new(Namespace::public(), "y"), activation)?
+        .coerce_to_number(activation)?;
+
+    Ok((x, y))
+}
+
+/// Read the coordinates of the `Point` passed as the given argument.
+pub fn point_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<(f64, f64), Error<'gc>> {
+    let object = args
+        .get(index)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+
+    object_to_point(activation, object)
+}
+
+/// Overwrite the coordinates of a point.
+fn set_coords<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    x: f64,
+    y: f64,
+) -> Result<(), Error<'gc>> {
+    this.set_property(
+        this,
+        &QName::new(Namespace::public(), "x"),
+        x.into(),
+        activation,
+    )?;
+    this.set_property(
+        this,
+        &QName::new(Namespace::public(), "y"),
+        y.into(),
+        activation,
+    )?;
+
+    Ok(())
+}
+
+/// Implements `flash.geom.Point`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        let x = number_arg(activation, args, 0)?;
+        let y = number_arg(activation, args, 1)?;
+        set_coords(activation, this, x, y)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Point`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `length`'s getter.
+pub fn length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (x, y) = object_to_point(activation, this)?;
+
+        return Ok(x.hypot(y).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `add`.
+pub fn add<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (x, y) = object_to_point(activation, this)?;
+        let (other_x, other_y) = point_arg(activation, args, 0)?;
+
+        return Ok(new_point(activation, x + other_x, y + other_y)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `subtract`.
+pub fn subtract<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (x, y) = object_to_point(activation, this)?;
+        let (other_x, other_y) = point_arg(activation, args, 0)?;
+
+        return Ok(new_point(activation, x - other_x, y - other_y)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (x, y) = object_to_point(activation, this)?;
+
+        return Ok(new_point(activation, x, y)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `copyFrom`.
+pub fn copy_from<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (x, y) = point_arg(activation, args, 0)?;
+        set_coords(activation, this, x, y)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `equals`.
+#[allow(clippy::float_cmp)]
+pub fn equals<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (x, y) = object_to_point(activation, this)?;
+        let (other_x, other_y) = point_arg(activation, args, 0)?;
+
+        return Ok((x == other_x && y == other_y).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `normalize`.
+pub fn normalize<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let thickness = number_arg(activation, args, 0)?;
+        let (x, y) = object_to_point(activation, this)?;
+        let length = x.hypot(y);
+
+        if length > 0.0 {
+            let factor = thickness / length;
+            set_coords(activation, this, x * factor, y * factor)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `offset`.
+pub fn offset<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let dx = number_arg(activation, args, 0)?;
+        let dy = number_arg(activation, args, 1)?;
+        let (x, y) = object_to_point(activation, this)?;
+        set_coords(activation, this, x + dx, y + dy)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `setTo`.
+pub fn set_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let x = number_arg(activation, args, 0)?;
+        let y = number_arg(activation, args, 1)?;
+        set_coords(activation, this, x, y)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (x, y) = object_to_point(activation, this)?;
+        let x = Value::from(x).coerce_to_string(activation)?;
+        let y = Value::from(y).coerce_to_string(activation)?;
+
+        return Ok(
+            AvmString::new(activation.context.gc_context, format!("(x={}, y={})", x, y)).into(),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Point.distance`.
+pub fn distance<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (x1, y1) = point_arg(activation, args, 0)?;
+    let (x2, y2) = point_arg(activation, args, 1)?;
+
+    Ok((x2 - x1).hypot(y2 - y1).into())
+}
+
+/// Implements `Point.interpolate`.
+///
+/// The interpolation level `f` runs from the second point at `0` to the
+/// first point at `1`.
+pub fn interpolate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (x1, y1) = point_arg(activation, args, 0)?;
+    let (x2, y2) = point_arg(activation, args, 1)?;
+    let f = number_arg(activation, args, 2)?;
+
+    Ok(new_point(activation, x2 + (x1 - x2) * f, y2 + (y1 - y2) * f)?.into())
+}
+
+/// Implements `Point.polar`.
+pub fn polar<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let length = number_arg(activation, args, 0)?;
+    let angle = number_arg(activation, args, 1)?;
+
+    Ok(new_point(activation, length * angle.cos(), length * angle.sin())?.into())
+}
+
+/// Construct `Point`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Point"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "x"),
+        QName::new(Namespace::public(), "Number").into(),
+        Some(0.0.into()),
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "y"),
+        QName::new(Namespace::public(), "Number").into(),
+        Some(0.0.into()),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "length"),
+        Method::from_builtin(length),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "add"),
+        Method::from_builtin(add),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "subtract"),
+        Method::from_builtin(subtract),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clone"),
+        Method::from_builtin(clone),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "copyFrom"),
+        Method::from_builtin(copy_from),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "equals"),
+        Method::from_builtin(equals),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "normalize"),
+        Method::from_builtin(normalize),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "offset"),
+        Method::from_builtin(offset),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "setTo"),
+        Method::from_builtin(set_to),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "distance"),
+        Method::from_builtin(distance),
+    ));
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "interpolate"),
+        Method::from_builtin(interpolate),
+    ));
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "polar"),
+        Method::from_builtin(polar),
+    ));
+
+    class
+}
@@ -0,0 +1,796 @@
+//! `flash.geom.Rectangle` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::geom::number_arg;
+use crate::avm2::globals::flash::geom::point::{new_point, point_arg};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The position and size of a rectangle, as `(x, y, width, height)`.
+pub type Rect = (f64, f64, f64, f64);
+
+/// Construct a new `Rectangle` with the given position and size.
+pub fn new_rectangle<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    (x, y, width, height): Rect,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let rectangle_proto = activation.context.avm2.prototypes().rectangle;
+    let args = [x.into(), y.into(), width.into(), height.into()];
+    let rectangle = rectangle_proto.construct(activation, &args)?;
+
+    instance_init(activation, Some(rectangle), &args)?;
+
+    Ok(rectangle)
+}
+
+/// Read the position and size of a `Rectangle`, or of any object with `x`,
+/// `y`, `width` and `height` properties.
+pub fn object_to_rectangle<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+) -> Result<Rect, Error<'gc>> {
+    let mut get = |name: &'static str| -> Result<f64, Error<'gc>> {
+        object
+            .get_property(object, &QName::new(Namespace::public(), name), activation)?
+            .coerce_to_number(activation)
+    };
+
+    Ok((get("x")?, get("y")?, get("width")?, get("height")?))
+}
+
+/// Read the `Rectangle` passed as the given argument.
+fn rectangle_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<Rect, Error<'gc>> {
+    let object = args
+        .get(index)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+
+    object_to_rectangle(activation, object)
+}
+
+/// Overwrite the position and size of a rectangle.
+fn set_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    (x, y, width, height): Rect,
+) -> Result<(), Error<'gc>> {
+    for (name, value) in &[("x", x), ("y", y), ("width", width), ("height", height)] {
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), *name),
+            (*value).into(),
+            activation,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Determine if a rectangle has no area.
+fn rect_is_empty((_, _, width, height): Rect) -> bool {
+    !(width > 0.0 && height > 0.0)
+}
+
+/// Implements `flash.geom.Rectangle`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        let x = number_arg(activation, args, 0)?;
+        let y = number_arg(activation, args, 1)?;
+        let width = number_arg(activation, args, 2)?;
+        let height = number_arg(activation, args, 3)?;
+        set_rect(activation, this, (x, y, width, height))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Rectangle`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `left`'s getter.
+pub fn left<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (x, _, _, _) = object_to_rectangle(activation, this)?;
+
+        return Ok(x.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `left`'s setter.
+///
+/// Moving the left edge keeps the right edge in place.
+pub fn set_left<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let left = number_arg(activation, args, 0)?;
+        let (x, y, width, height) = object_to_rectangle(activation, this)?;
+        set_rect(activation, this, (left, y, width + x - left, height))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `top`'s getter.
+pub fn top<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (_, y, _, _) = object_to_rectangle(activation, this)?;
+
+        return Ok(y.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `top`'s setter.
+///
+/// Moving the top edge keeps the bottom edge in place.
+pub fn set_top<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let top = number_arg(activation, args, 0)?;
+        let (x, y, width, height) = object_to_rectangle(activation, this)?;
+        set_rect(activation, this, (x, top, width, height + y - top))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `right`'s getter.
+pub fn right<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (x, _, width, _) = object_to_rectangle(activation, this)?;
+
+        return Ok((x + width).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `right`'s setter.
+pub fn set_right<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let right = number_arg(activation, args, 0)?;
+        let (x, y, _, height) = object_to_rectangle(activation, this)?;
+        set_rect(activation, this, (x, y, right - x, height))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `bottom`'s getter.
+pub fn bottom<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (_, y, _, height) = object_to_rectangle(activation, this)?;
+
+        return Ok((y + height).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `bottom`'s setter.
+pub fn set_bottom<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let bottom = number_arg(activation, args, 0)?;
+        let (x, y, width, _) = object_to_rectangle(activation, this)?;
+        set_rect(activation, this, (x, y, width, bottom - y))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `topLeft`'s getter.
+pub fn top_left<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (x, y, _, _) = object_to_rectangle(activation, this)?;
+
+        return Ok(new_point(activation, x, y)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `topLeft`'s setter.
+///
+/// Moving the top-left corner keeps the bottom-right corner in place.
+pub fn set_top_left<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (left, top) = point_arg(activation, args, 0)?;
+        let (x, y, width, height) = object_to_rectangle(activation, this)?;
+        set_rect(
+            activation,
+            this,
+            (left, top, width + x - left, height + y - top),
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `bottomRight`'s getter.
+pub fn bottom_right<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (x, y, width, height) = object_to_rectangle(activation, this)?;
+
+        return Ok(new_point(activation, x + width, y + height)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `bottomRight`'s setter.
+pub fn set_bottom_right<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (right, bottom) = point_arg(activation, args, 0)?;
+        let (x, y, _, _) = object_to_rectangle(activation, this)?;
+        set_rect(activation, this, (x, y, right - x, bottom - y))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `size`'s getter.
+pub fn size<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (_, _, width, height) = object_to_rectangle(activation, this)?;
+
+        return Ok(new_point(activation, width, height)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `size`'s setter.
+pub fn set_size<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (width, height) = point_arg(activation, args, 0)?;
+        let (x, y, _, _) = object_to_rectangle(activation, this)?;
+        set_rect(activation, this, (x, y, width, height))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let rect = object_to_rectangle(activation, this)?;
+
+        return Ok(new_rectangle(activation, rect)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `contains`.
+pub fn contains<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let point_x = number_arg(activation, args, 0)?;
+        let point_y = number_arg(activation, args, 1)?;
+        let (x, y, width, height) = object_to_rectangle(activation, this)?;
+
+        return Ok(
+            (point_x >= x && point_x < x + width && point_y >= y && point_y < y + height).into(),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `containsPoint`.
+pub fn contains_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (point_x, point_y) = point_arg(activation, args, 0)?;
+
+        return contains(activation, Some(this), &[point_x.into(), point_y.into()]);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `containsRect`.
+pub fn contains_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (x, y, width, height) = object_to_rectangle(activation, this)?;
+        let (other_x, other_y, other_width, other_height) = rectangle_arg(activation, args, 0)?;
+
+        return Ok((other_x >= x
+            && other_y >= y
+            && other_x + other_width <= x + width
+            && other_y + other_height <= y + height)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `copyFrom`.
+pub fn copy_from<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let rect = rectangle_arg(activation, args, 0)?;
+        set_rect(activation, this, rect)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `equals`.
+#[allow(clippy::float_cmp)]
+pub fn equals<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let rect = object_to_rectangle(activation, this)?;
+        let other = rectangle_arg(activation, args, 0)?;
+
+        return Ok((rect == other).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `inflate`.
+pub fn inflate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let dx = number_arg(activation, args, 0)?;
+        let dy = number_arg(activation, args, 1)?;
+        let (x, y, width, height) = object_to_rectangle(activation, this)?;
+        set_rect(
+            activation,
+            this,
+            (x - dx, y - dy, width + 2.0 * dx, height + 2.0 * dy),
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `inflatePoint`.
+pub fn inflate_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (dx, dy) = point_arg(activation, args, 0)?;
+
+        return inflate(activation, Some(this), &[dx.into(), dy.into()]);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `intersection`.
+///
+/// Rectangles that do not overlap have an empty intersection at the origin.
+pub fn intersection<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (x, y, width, height) = object_to_rectangle(activation, this)?;
+        let (other_x, other_y, other_width, other_height) = rectangle_arg(activation, args, 0)?;
+
+        let left = x.max(other_x);
+        let top = y.max(other_y);
+        let right = (x + width).min(other_x + other_width);
+        let bottom = (y + height).min(other_y + other_height);
+
+        let result = if right > left && bottom > top {
+            (left, top, right - left, bottom - top)
+        } else {
+            (0.0, 0.0, 0.0, 0.0)
+        };
+
+        return Ok(new_rectangle(activation, result)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `intersects`.
+pub fn intersects<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (x, y, width, height) = object_to_rectangle(activation, this)?;
+        let (other_x, other_y, other_width, other_height) = rectangle_arg(activation, args, 0)?;
+
+        return Ok((x < other_x + other_width
+            && x + width > other_x
+            && y < other_y + other_height
+            && y + height > other_y)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `isEmpty`.
+pub fn is_empty<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let rect = object_to_rectangle(activation, this)?;
+
+        return Ok(rect_is_empty(rect).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `offset`.
+pub fn offset<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let dx = number_arg(activation, args, 0)?;
+        let dy = number_arg(activation, args, 1)?;
+        let (x, y, width, height) = object_to_rectangle(activation, this)?;
+        set_rect(activation, this, (x + dx, y + dy, width, height))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `offsetPoint`.
+pub fn offset_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (dx, dy) = point_arg(activation, args, 0)?;
+
+        return offset(activation, Some(this), &[dx.into(), dy.into()]);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `setEmpty`.
+pub fn set_empty<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        set_rect(activation, this, (0.0, 0.0, 0.0, 0.0))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `setTo`.
+pub fn set_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let x = number_arg(activation, args, 0)?;
+        let y = number_arg(activation, args, 1)?;
+        let width = number_arg(activation, args, 2)?;
+        let height = number_arg(activation, args, 3)?;
+        set_rect(activation, this, (x, y, width, height))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `union`.
+///
+/// Empty rectangles do not contribute to the union.
+pub fn union<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let rect = object_to_rectangle(activation, this)?;
+        let other = rectangle_arg(activation, args, 0)?;
+
+        let result = if rect_is_empty(rect) {
+            other
+        } else if rect_is_empty(other) {
+            rect
+        } else {
+            let (x, y, width, height) = rect;
+            let (other_x, other_y, other_width, other_height) = other;
+            let left = x.min(other_x);
+            let top = y.min(other_y);
+            let right = (x + width).max(other_x + other_width);
+            let bottom = (y + height).max(other_y + other_height);
+
+            (left, top, right - left, bottom - top)
+        };
+
+        return Ok(new_rectangle(activation, result)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (x, y, width, height) = object_to_rectangle(activation, this)?;
+        let x = Value::from(x).coerce_to_string(activation)?;
+        let y = Value::from(y).coerce_to_string(activation)?;
+        let width = Value::from(width).coerce_to_string(activation)?;
+        let height = Value::from(height).coerce_to_string(activation)?;
+
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            format!("(x={}, y={}, w={}, h={})", x, y, width, height),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Rectangle`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Rectangle"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    for name in &["x", "y", "width", "height"] {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            QName::new(Namespace::public(), "Number").into(),
+            Some(0.0.into()),
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "left"),
+        Method::from_builtin(left),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "left"),
+        Method::from_builtin(set_left),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "top"),
+        Method::from_builtin(top),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "top"),
+        Method::from_builtin(set_top),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "right"),
+        Method::from_builtin(right),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "right"),
+        Method::from_builtin(set_right),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bottom"),
+        Method::from_builtin(bottom),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "bottom"),
+        Method::from_builtin(set_bottom),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "topLeft"),
+        Method::from_builtin(top_left),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "topLeft"),
+        Method::from_builtin(set_top_left),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bottomRight"),
+        Method::from_builtin(bottom_right),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "bottomRight"),
+        Method::from_builtin(set_bottom_right),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "size"),
+        Method::from_builtin(size),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "size"),
+        Method::from_builtin(set_size),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clone"),
+        Method::from_builtin(clone),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "contains"),
+        Method::from_builtin(contains),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "containsPoint"),
+        Method::from_builtin(contains_point),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "containsRect"),
+        Method::from_builtin(contains_rect),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "copyFrom"),
+        Method::from_builtin(copy_from),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "equals"),
+        Method::from_builtin(equals),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "inflate"),
+        Method::from_builtin(inflate),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "inflatePoint"),
+        Method::from_builtin(inflate_point),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "intersection"),
+        Method::from_builtin(intersection),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "intersects"),
+        Method::from_builtin(intersects),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "isEmpty"),
+        Method::from_builtin(is_empty),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "offset"),
+        Method::from_builtin(offset),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "offsetPoint"),
+        Method::from_builtin(offset_point),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "setEmpty"),
+        Method::from_builtin(set_empty),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "setTo"),
+        Method::from_builtin(set_to),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "union"),
+        Method::from_builtin(union),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    class
+}
@@ -0,0 +1,309 @@
+//! `flash.geom.Transform` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::geom::colortransform::{
+    new_color_transform, object_to_color_transform,
+};
+use crate::avm2::globals::flash::geom::matrix::{new_matrix, object_to_matrix};
+use crate::avm2::globals::flash::geom::rectangle::new_rectangle;
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorKind};
+use crate::display_object::{DisplayObject, TDisplayObject};
+use gc_arena::{GcCell, MutationContext};
+
+/// Construct a new `Transform` for the given display object.
+pub fn new_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    display_object: Object<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let transform_proto = activation.context.avm2.prototypes().transform;
+    let args = [display_object.into()];
+    let transform = transform_proto.construct(activation, &args)?;
+
+    instance_init(activation, Some(transform), &args)?;
+
+    Ok(transform)
+}
+
+/// The name of the hidden slot holding the display object of a `Transform`.
+fn display_object_slot_name<'gc>() -> QName<'gc> {
+    QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "displayObject")
+}
+
+/// Get the display object that a `Transform` reads from and writes to.
+fn target<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<Option<DisplayObject<'gc>>, Error<'gc>> {
+    let target = this.get_property(this, &display_object_slot_name(), activation)?;
+
+    Ok(match target {
+        Value::Object(target) => target.as_display_object(),
+        _ => None,
+    })
+}
+
+/// Implements `flash.geom.Transform`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let target = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        if target.as_display_object().is_none() {
+            return Err(Error::new(
+                ErrorKind::ArgumentError,
+                "Error #2007: Parameter displayObject must be non-null.",
+            ));
+        }
+
+        this.set_property(this, &display_object_slot_name(), target.into(), activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Transform`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `matrix`'s getter.
+pub fn matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(dobj) = target(activation, this)? {
+            let matrix = *dobj.matrix();
+
+            return Ok(new_matrix(activation, &matrix)?.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `matrix`'s setter.
+pub fn set_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(dobj) = target(activation, this)? {
+            let matrix = args
+                .get(0)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_object(activation)?;
+            let matrix = object_to_matrix(activation, matrix)?;
+
+            dobj.set_matrix(activation.context.gc_context, &matrix);
+            dobj.set_transformed_by_script(activation.context.gc_context, true);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `colorTransform`'s getter.
+pub fn color_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(dobj) = target(activation, this)? {
+            let color_transform = *dobj.color_transform();
+
+            return Ok(new_color_transform(activation, &color_transform)?.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `colorTransform`'s setter.
+pub fn set_color_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(dobj) = target(activation, this)? {
+            let color_transform = args
+                .get(0)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_object(activation)?;
+            let color_transform = object_to_color_transform(activation, color_transform)?;
+
+            dobj.set_color_transform(activation.context.gc_context, &color_transform);
+            dobj.set_transformed_by_script(activation.context.gc_context, true);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `concatenatedMatrix`'s getter.
+pub fn concatenated_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(dobj) = target(activation, this)? {
+            let matrix = dobj.local_to_global_matrix();
+
+            return Ok(new_matrix(activation, &matrix)?.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `concatenatedColorTransform`'s getter.
+pub fn concatenated_color_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(dobj) = target(activation, this)? {
+            let mut color_transform = *dobj.color_transform();
+            let mut node = dobj.parent();
+            while let Some(parent) = node {
+                color_transform = *parent.color_transform() * color_transform;
+                node = parent.parent();
+            }
+
+            return Ok(new_color_transform(activation, &color_transform)?.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `pixelBounds`'s getter.
+pub fn pixel_bounds<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(dobj) = target(activation, this)? {
+            let bounds = dobj.world_bounds();
+            let rect = (
+                bounds.x_min.to_pixels(),
+                bounds.y_min.to_pixels(),
+                bounds.width().to_pixels(),
+                bounds.height().to_pixels(),
+            );
+
+            return Ok(new_rectangle(activation, rect)?.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `matrix3D`'s getter.
+///
+/// We do not support 3D transforms, so display objects never have one.
+pub fn matrix_3d<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Null)
+}
+
+/// Implements `matrix3D`'s setter.
+pub fn set_matrix_3d<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    log::warn!("Transform.matrix3D: 3D transforms are not yet supported");
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Transform`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Transform"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_slot(
+        display_object_slot_name(),
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "BareObject").into(),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "matrix"),
+        Method::from_builtin(matrix),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "matrix"),
+        Method::from_builtin(set_matrix),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "colorTransform"),
+        Method::from_builtin(color_transform),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "colorTransform"),
+        Method::from_builtin(set_color_transform),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "concatenatedMatrix"),
+        Method::from_builtin(concatenated_matrix),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "concatenatedColorTransform"),
+        Method::from_builtin(concatenated_color_transform),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "pixelBounds"),
+        Method::from_builtin(pixel_bounds),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "matrix3D"),
+        Method::from_builtin(matrix_3d),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "matrix3D"),
+        Method::from_builtin(set_matrix_3d),
+    ));
+
+    class
+}
@@ -0,0 +1,94 @@
+//! `flash.net.URLRequest` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.net.URLRequest`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        if let Some(url) = args.get(0) {
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), "url"),
+                url.clone(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.URLRequest`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Read the URL of a `URLRequest`.
+///
+/// Requests without a URL yield `None`.
+pub fn request_url<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut request: Object<'gc>,
+) -> Result<Option<AvmString<'gc>>, Error<'gc>> {
+    match request.get_property(request, &QName::new(Namespace::public(), "url"), activation)? {
+        Value::Undefined | Value::Null => Ok(None),
+        url => Ok(Some(url.coerce_to_string(activation)?)),
+    }
+}
+
+/// Construct `URLRequest`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "URLRequest"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "url"),
+        QName::new(Namespace::public(), "String").into(),
+        Some(Value::Null),
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "method"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("GET".into()),
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "data"),
+        QName::new(Namespace::public(), "Object").into(),
+        Some(Value::Null),
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "contentType"),
+        QName::new(Namespace::public(), "String").into(),
+        Some(Value::Null),
+    ));
+
+    class
+}
@@ -1,3 +1,4 @@
 //! `flash.system` namespace
 
 pub mod application_domain;
+pub mod loadercontext;
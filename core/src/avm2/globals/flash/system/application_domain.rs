@@ -7,7 +7,7 @@ use crate::avm2::names::{Namespace, QName};
 use crate::avm2::object::{DomainObject, Object, TObject};
 use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
-use crate::avm2::Error;
+use crate::avm2::{Error, ErrorKind};
 use gc_arena::{GcCell, MutationContext};
 
 /// Implements `flash.system.ApplicationDomain`'s instance constructor.
@@ -15,7 +15,7 @@ pub fn instance_init<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         activation.super_init(this, &[])?;
     }
@@ -28,7 +28,7 @@ pub fn class_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(Value::Undefined)
 }
 
@@ -37,7 +37,7 @@ pub fn current_domain<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     let globals = activation.scope().map(|s| s.read().globals());
     let appdomain = globals.and_then(|g| g.as_application_domain());
     if let Some(appdomain) = appdomain {
@@ -57,7 +57,7 @@ pub fn parent_domain<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(appdomain) = this.and_then(|this| this.as_application_domain()) {
         if let Some(parent_domain) = appdomain.parent_domain() {
             return Ok(DomainObject::from_domain(
@@ -77,22 +77,15 @@ pub fn get_definition<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(appdomain) = this.and_then(|this| this.as_application_domain()) {
-        let local_name = args
+        let name = args
             .get(0)
             .cloned()
             .unwrap_or_else(|| "".into())
             .coerce_to_string(activation)?;
-        let qname = QName::new(Namespace::public(), local_name);
 
-        let (qname, mut defined_script) = appdomain
-            .get_defining_script(&qname.into())?
-            .ok_or_else(|| format!("No definition called {} exists", local_name))?;
-        let mut globals = defined_script.globals(&mut activation.context)?;
-        let definition = globals.get_property(globals, &qname, activation)?;
-
-        return Ok(definition);
+        return appdomain.get_defined_value_by_name(activation, name);
     }
 
     Ok(Value::Undefined)
@@ -103,14 +96,14 @@ pub fn has_definition<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(appdomain) = this.and_then(|this| this.as_application_domain()) {
-        let local_name = args
+        let name = args
             .get(0)
             .cloned()
             .unwrap_or_else(|| "".into())
             .coerce_to_string(activation)?;
-        let qname = QName::new(Namespace::public(), local_name);
+        let qname = QName::from_qualified_name(&name, activation.context.gc_context);
 
         return Ok(appdomain.has_definition(qname).into());
     }
@@ -118,6 +111,50 @@ pub fn has_definition<'gc>(
     Ok(Value::Undefined)
 }
 
+/// `domainMemory` property's getter
+pub fn domain_memory<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(appdomain) = this.and_then(|this| this.as_application_domain()) {
+        return Ok(appdomain
+            .domain_memory()
+            .map(|memory| memory.into())
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// `domainMemory` property's setter
+pub fn set_domain_memory<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(appdomain) = this.and_then(|this| this.as_application_domain()) {
+        let memory = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Null | Value::Undefined => None,
+            value => {
+                let memory = value.coerce_to_object(activation)?;
+                if memory.as_bytearray().is_none() {
+                    return Err(Error::new(
+                        ErrorKind::TypeError,
+                        "Error #1034: Type Coercion failed: domainMemory must be a ByteArray.",
+                    ));
+                }
+
+                Some(memory)
+            }
+        };
+
+        appdomain.set_domain_memory(activation.context.gc_context, memory);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `ApplicationDomain`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -134,18 +171,26 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         QName::new(Namespace::public(), "currentDomain"),
         Method::from_builtin(current_domain),
     ));
-    write.define_class_trait(Trait::from_getter(
+    write.define_instance_trait(Trait::from_getter(
         QName::new(Namespace::public(), "parentDomain"),
         Method::from_builtin(parent_domain),
     ));
-    write.define_class_trait(Trait::from_method(
+    write.define_instance_trait(Trait::from_method(
         QName::new(Namespace::public(), "getDefinition"),
         Method::from_builtin(get_definition),
     ));
-    write.define_class_trait(Trait::from_method(
+    write.define_instance_trait(Trait::from_method(
         QName::new(Namespace::public(), "hasDefinition"),
         Method::from_builtin(has_definition),
     ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "domainMemory"),
+        Method::from_builtin(domain_memory),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "domainMemory"),
+        Method::from_builtin(set_domain_memory),
+    ));
 
     class
 }
@@ -0,0 +1,151 @@
+//! `flash.system.LoaderContext` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::domain::Domain;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.system.LoaderContext`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let check_policy_file = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+        let application_domain = args.get(1).cloned().unwrap_or(Value::Null);
+        let security_domain = args.get(2).cloned().unwrap_or(Value::Null);
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "checkPolicyFile"),
+            check_policy_file.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "applicationDomain"),
+            application_domain,
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "securityDomain"),
+            security_domain,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.system.LoaderContext`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Read the application domain that a `LoaderContext` asks for code to be
+/// loaded into.
+///
+/// Contexts that do not name a domain yield `None`.
+pub fn context_domain<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut context: Object<'gc>,
+) -> Result<Option<Domain<'gc>>, Error<'gc>> {
+    match context.get_property(
+        context,
+        &QName::new(Namespace::public(), "applicationDomain"),
+        activation,
+    )? {
+        Value::Undefined | Value::Null => Ok(None),
+        domain => Ok(domain.coerce_to_object(activation)?.as_application_domain()),
+    }
+}
+
+/// Read the parameters that a `LoaderContext` passes to a loaded movie.
+///
+/// Contexts that do not provide parameters yield an empty list.
+pub fn context_parameters<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut context: Object<'gc>,
+) -> Result<Vec<(String, String)>, Error<'gc>> {
+    let mut parameters = match context.get_property(
+        context,
+        &QName::new(Namespace::public(), "parameters"),
+        activation,
+    )? {
+        Value::Undefined | Value::Null => return Ok(Vec::new()),
+        parameters => parameters.coerce_to_object(activation)?,
+    };
+
+    let mut result = Vec::new();
+    let mut index = 1;
+
+    while let Some(name) = parameters.get_enumerant_name(index) {
+        let value = parameters
+            .get_property(parameters, &name, activation)?
+            .coerce_to_string(activation)?;
+
+        result.push((name.local_name().to_string(), value.to_string()));
+        index += 1;
+    }
+
+    Ok(result)
+}
+
+/// Construct `LoaderContext`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.system"), "LoaderContext"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "checkPolicyFile"),
+        QName::new(Namespace::public(), "Boolean").into(),
+        Some(false.into()),
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "applicationDomain"),
+        QName::new(Namespace::package("flash.system"), "ApplicationDomain").into(),
+        Some(Value::Null),
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "securityDomain"),
+        QName::new(Namespace::public(), "Object").into(),
+        Some(Value::Null),
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "allowCodeImport"),
+        QName::new(Namespace::public(), "Boolean").into(),
+        Some(true.into()),
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "parameters"),
+        QName::new(Namespace::public(), "Object").into(),
+        Some(Value::Null),
+    ));
+
+    class
+}
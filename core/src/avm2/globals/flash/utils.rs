@@ -0,0 +1,207 @@
+//! `flash.utils` namespace
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::domain::Domain;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorKind};
+use crate::timer::TimerCallback;
+use gc_arena::GcCell;
+
+pub mod bytearray;
+pub mod describe_type;
+pub mod dictionary;
+pub mod timer;
+
+/// Implements `flash.utils.getTimer`
+pub fn get_timer<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.times_get_time_called += 1;
+    // heuristic to detect busy loops used for delays and slowly progress fake time
+    if activation.context.times_get_time_called >= 20
+        && activation.context.times_get_time_called % 5 == 0
+    {
+        *activation.context.time_offset += 1;
+    }
+
+    let time = activation.context.navigator.time_since_launch().as_millis() as u32;
+
+    Ok(time.wrapping_add(*activation.context.time_offset).into())
+}
+
+/// Shared implementation of `setTimeout` and `setInterval`.
+fn create_timer<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    is_timeout: bool,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let callable = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+    if callable.as_executable().is_none() {
+        return Err(Error::new(
+            ErrorKind::TypeError,
+            "Error #1034: Type Coercion failed: closure is not a Function.",
+        ));
+    }
+
+    let interval = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+    let params = args.get(2..).unwrap_or_default().to_vec();
+    let callback = TimerCallback::Avm2Callable { callable, params };
+
+    let id = activation
+        .context
+        .timers
+        .add_timer(callback, interval as i32, is_timeout);
+
+    Ok((id as u32).into())
+}
+
+/// Shared implementation of `clearTimeout` and `clearInterval`.
+fn remove_timer<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+    activation.context.timers.remove(id as i32);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.utils.setTimeout`
+pub fn set_timeout<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    create_timer(activation, args, true)
+}
+
+/// Implements `flash.utils.clearTimeout`
+pub fn clear_timeout<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    remove_timer(activation, args)
+}
+
+/// Implements `flash.utils.setInterval`
+pub fn set_interval<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    create_timer(activation, args, false)
+}
+
+/// Implements `flash.utils.clearInterval`
+pub fn clear_interval<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    remove_timer(activation, args)
+}
+
+/// Get the domain of the script that called a builtin.
+///
+/// Builtins called from outside of any script use the global domain.
+pub fn caller_domain<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Domain<'gc> {
+    activation
+        .scope()
+        .map(|s| s.read().globals())
+        .and_then(|g| g.as_application_domain())
+        .unwrap_or_else(|| activation.context.avm2.global_domain())
+}
+
+/// Get the class of a value, as the reflection functions report it.
+///
+/// Class objects report their own class, and primitives report the class of
+/// their boxed form. `null` and `undefined` have no class.
+pub fn class_of_value<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<Option<GcCell<'gc, Class<'gc>>>, Error<'gc>> {
+    match value {
+        Value::Undefined | Value::Null => Ok(None),
+        value => Ok(value.coerce_to_object(activation)?.as_proto_class()),
+    }
+}
+
+/// Implements `flash.utils.getDefinitionByName`
+pub fn get_definition_by_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let domain = caller_domain(activation);
+
+    domain.get_defined_value_by_name(activation, name)
+}
+
+/// Implements `flash.utils.getQualifiedClassName`
+pub fn get_qualified_class_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let name = match value {
+        Value::Undefined => "void".to_string(),
+        Value::Null => "null".to_string(),
+        Value::Integer(_) => "int".to_string(),
+        Value::Unsigned(u) if u <= i32::MAX as u32 => "int".to_string(),
+        // Numbers that an `int` can hold are reported as `int`, like in Flash.
+        Value::Number(n) if n as i32 as f64 == n && !(n == 0.0 && n.is_sign_negative()) => {
+            "int".to_string()
+        }
+        value => match class_of_value(activation, value)? {
+            Some(class) => class.read().name().to_qualified_name(),
+            None => "Object".to_string(),
+        },
+    };
+
+    Ok(AvmString::new(activation.context.gc_context, name).into())
+}
+
+/// Implements `flash.utils.getQualifiedSuperclassName`
+pub fn get_qualified_superclass_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+
+    if let Some(class) = class_of_value(activation, value)? {
+        if let Some(super_name) = class.read().super_class_name() {
+            return Ok(AvmString::new(
+                activation.context.gc_context,
+                super_name.to_qualified_name(),
+            )
+            .into());
+        }
+    }
+
+    Ok(Value::Null)
+}
@@ -0,0 +1,1294 @@
+//! `flash.utils.ByteArray` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::bytearray::{ByteArrayStorage, CompressionAlgorithm, Endian};
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{
+    ArrayObject, ByteArrayObject, DateObject, Object, ScriptObject, TObject,
+};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorKind};
+use chrono::{LocalResult, TimeZone, Utc};
+use gc_arena::{GcCell, MutationContext};
+use ruffle_amf::{self as amf, AmfError, AmfObject, AmfValue, ObjectEncoding};
+
+/// Implements `flash.utils.ByteArray`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.utils.ByteArray`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.length`'s getter.
+pub fn length<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(bytearray) = this.as_bytearray() {
+            return Ok((bytearray.len() as u32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.length`'s setter.
+pub fn set_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let length = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.set_len(length as usize)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.position`'s getter.
+pub fn position<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(bytearray) = this.as_bytearray() {
+            return Ok((bytearray.position() as u32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.position`'s setter.
+pub fn set_position<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let position = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.set_position(position as usize);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.bytesAvailable`.
+pub fn bytes_available<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(bytearray) = this.as_bytearray() {
+            return Ok((bytearray.bytes_available() as u32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.endian`'s getter.
+pub fn endian<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(bytearray) = this.as_bytearray() {
+            return Ok(bytearray.endian().name().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.endian`'s setter.
+pub fn set_endian<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let endian = Endian::from_name(&name).ok_or_else(|| {
+        Error::new(
+            ErrorKind::ArgumentError,
+            "Error #2008: Parameter endian must be one of the accepted values.",
+        )
+    })?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.set_endian(endian);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.objectEncoding`'s getter.
+pub fn object_encoding<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(bytearray) = this.as_bytearray() {
+            return Ok(bytearray.object_encoding().as_u32().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.objectEncoding`'s setter.
+pub fn set_object_encoding<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let encoding = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+    let encoding = ObjectEncoding::from_u32(encoding).ok_or_else(|| {
+        Error::new(
+            ErrorKind::ArgumentError,
+            "Error #2008: Parameter objectEncoding must be one of the accepted values.",
+        )
+    })?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.set_object_encoding(encoding);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.clear`.
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.clear();
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.toString`.
+///
+/// The entire contents are decoded as UTF-8, regardless of the position.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(bytearray) = this.as_bytearray() {
+            let bytes = bytearray.bytes();
+            let bytes = if bytes.starts_with(b"\xEF\xBB\xBF") {
+                &bytes[3..]
+            } else {
+                bytes
+            };
+
+            return Ok(AvmString::new(
+                activation.context.gc_context,
+                String::from_utf8_lossy(bytes).into_owned(),
+            )
+            .into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readByte`.
+pub fn read_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            return Ok(i32::from(bytearray.read_i8()?).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeByte`.
+pub fn write_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_u8(value as u8)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readUnsignedByte`.
+pub fn read_unsigned_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            return Ok(u32::from(bytearray.read_u8()?).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readShort`.
+pub fn read_short<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            return Ok(i32::from(bytearray.read_i16()?).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeShort`.
+pub fn write_short<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_i16(value as i16)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readUnsignedShort`.
+pub fn read_unsigned_short<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            return Ok(u32::from(bytearray.read_u16()?).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readInt`.
+pub fn read_int<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            return Ok(bytearray.read_i32()?.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeInt`.
+pub fn write_int<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_i32(value)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readUnsignedInt`.
+pub fn read_unsigned_int<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            return Ok(bytearray.read_u32()?.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeUnsignedInt`.
+pub fn write_unsigned_int<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_u32(value)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readFloat`.
+pub fn read_float<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            return Ok(f64::from(bytearray.read_f32()?).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeFloat`.
+pub fn write_float<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_f32(value as f32)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readDouble`.
+pub fn read_double<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            return Ok(bytearray.read_f64()?.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeDouble`.
+pub fn write_double<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_f64(value)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readBoolean`.
+pub fn read_boolean<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            return Ok(bytearray.read_bool()?.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeBoolean`.
+pub fn write_boolean<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_boolean();
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_u8(value as u8)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readUTF`.
+pub fn read_utf<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            let text = bytearray.read_utf()?;
+            drop(bytearray);
+
+            return Ok(AvmString::new(activation.context.gc_context, text).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeUTF`.
+pub fn write_utf<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let text = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_utf(&text)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readUTFBytes`.
+pub fn read_utf_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let length = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            let text = bytearray.read_utf_bytes(length as usize)?;
+            drop(bytearray);
+
+            return Ok(AvmString::new(activation.context.gc_context, text).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeUTFBytes`.
+pub fn write_utf_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let text = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_bytes(text.as_bytes())?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readMultiByte`.
+pub fn read_multi_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let length = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+    let charset = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            let text = bytearray.read_multi_byte(length as usize, &charset)?;
+            drop(bytearray);
+
+            return Ok(AvmString::new(activation.context.gc_context, text).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeMultiByte`.
+pub fn write_multi_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let text = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let charset = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_multi_byte(&text, &charset)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Resolve the `offset` and `length` arguments of `readBytes` and
+/// `writeBytes` against the number of bytes that are available.
+///
+/// A length of zero means everything after the offset.
+fn byte_range<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<(usize, usize), Error<'gc>> {
+    let offset = args
+        .get(1)
+        .cloned()
+        .unwrap_or_else(|| 0.into())
+        .coerce_to_u32(activation)?;
+    let length = args
+        .get(2)
+        .cloned()
+        .unwrap_or_else(|| 0.into())
+        .coerce_to_u32(activation)?;
+
+    Ok((offset as usize, length as usize))
+}
+
+/// Implements `ByteArray.readBytes`.
+///
+/// The bytes are written into the target byte array at the given offset,
+/// without moving its position.
+pub fn read_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let target = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+    let (offset, length) = byte_range(activation, args)?;
+    let this = match this {
+        Some(this) => this,
+        None => return Ok(Value::Undefined),
+    };
+
+    let bytes = match this.as_bytearray_mut(activation.context.gc_context) {
+        Some(mut bytearray) => {
+            let length = if length == 0 {
+                bytearray.bytes_available()
+            } else {
+                length
+            };
+
+            bytearray.read_bytes(length)?.to_vec()
+        }
+        None => return Ok(Value::Undefined),
+    };
+
+    if let Some(mut target) = target.as_bytearray_mut(activation.context.gc_context) {
+        let position = target.position();
+        target.set_position(offset);
+        target.write_bytes(&bytes)?;
+        target.set_position(position);
+    } else {
+        return Err(Error::new(
+            ErrorKind::TypeError,
+            "Error #1034: Type Coercion failed: cannot convert target to flash.utils.ByteArray.",
+        ));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeBytes`.
+pub fn write_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let source = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+    let (offset, length) = byte_range(activation, args)?;
+
+    // The source is copied out first, as it may be this same byte array.
+    let bytes = match source.as_bytearray() {
+        Some(source) => {
+            let available = source.len().saturating_sub(offset);
+            let length = if length == 0 { available } else { length };
+            if length > available {
+                return Err(Error::new(
+                    ErrorKind::RangeError,
+                    "Error #2006: The supplied index is out of bounds.",
+                ));
+            }
+
+            source.bytes()[offset..offset + length].to_vec()
+        }
+        None => return Err(Error::new(
+            ErrorKind::TypeError,
+            "Error #1034: Type Coercion failed: cannot convert source to flash.utils.ByteArray.",
+        )),
+    };
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_bytes(&bytes)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Parse the algorithm argument of `compress` and `uncompress`, which
+/// defaults to zlib.
+fn compression_algorithm<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<CompressionAlgorithm, Error<'gc>> {
+    let name = match args.get(0) {
+        None | Some(Value::Undefined) | Some(Value::Null) => return Ok(CompressionAlgorithm::Zlib),
+        Some(name) => name.clone().coerce_to_string(activation)?,
+    };
+
+    CompressionAlgorithm::from_name(&name).ok_or_else(|| {
+        Error::new(
+            ErrorKind::ArgumentError,
+            "Error #2008: Parameter algorithm must be one of the accepted values.",
+        )
+    })
+}
+
+/// Implements `ByteArray.compress`.
+pub fn compress<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let algorithm = compression_algorithm(activation, args)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.compress(algorithm)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.uncompress`.
+pub fn uncompress<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let algorithm = compression_algorithm(activation, args)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.uncompress(algorithm)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.deflate`.
+pub fn deflate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    compress(activation, this, &["deflate".into()])
+}
+
+/// Implements `ByteArray.inflate`.
+pub fn inflate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    uncompress(activation, this, &["deflate".into()])
+}
+
+/// Convert a value into its AMF representation.
+///
+/// `objects` holds every object converted so far that AMF can refer to, in
+/// the order AMF numbers references in, so that repeated and cyclic objects are written as
+/// references. Functions can't be serialized, and are written as `undefined`.
+fn value_to_amf<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+    objects: &mut Vec<Object<'gc>>,
+) -> Result<AmfValue, Error<'gc>> {
+    let mut object = match value {
+        Value::Undefined => return Ok(AmfValue::Undefined),
+        Value::Null => return Ok(AmfValue::Null),
+        Value::Bool(b) => return Ok(AmfValue::Bool(b)),
+        Value::Number(n) => return Ok(AmfValue::Number(n)),
+        Value::Integer(i) => return Ok(AmfValue::Integer(i)),
+        Value::Unsigned(u) if u <= i32::MAX as u32 => return Ok(AmfValue::Integer(u as i32)),
+        Value::Unsigned(u) => return Ok(AmfValue::Number(f64::from(u))),
+        Value::String(s) => return Ok(AmfValue::String(s.to_string())),
+        Value::Object(object) => object,
+    };
+
+    if let Some(index) = objects.iter().position(|o| Object::ptr_eq(*o, object)) {
+        return Ok(AmfValue::Reference(index));
+    }
+
+    if object.as_executable().is_some() {
+        return Ok(AmfValue::Undefined);
+    }
+
+    // Dates can't be referred to, so they are written out again every time
+    // they appear.
+    if let Some(date) = object.as_date_object() {
+        return Ok(AmfValue::Date(date.time()));
+    }
+
+    objects.push(object);
+
+    if let Some(bytearray) = object.as_bytearray() {
+        return Ok(AmfValue::ByteArray(bytearray.bytes().to_vec()));
+    }
+
+    let dense: Option<Vec<Value<'gc>>> = object.as_array_storage().map(|array| {
+        array
+            .iter()
+            .map(|v| v.unwrap_or(Value::Undefined))
+            .collect()
+    });
+
+    // Named properties are converted before the dense part of an array, as
+    // that is the order references are numbered in.
+    let mut properties = Vec::new();
+    let mut index = 1;
+    while let Some(name) = object.get_enumerant_name(index) {
+        let value = object.get_property(object, &name, activation)?;
+        properties.push((
+            name.local_name().to_string(),
+            value_to_amf(activation, value, objects)?,
+        ));
+        index += 1;
+    }
+
+    Ok(match dense {
+        Some(dense) => AmfValue::Array {
+            dense: dense
+                .into_iter()
+                .map(|v| value_to_amf(activation, v, objects))
+                .collect::<Result<_, _>>()?,
+            associative: properties,
+        },
+        None => AmfValue::Object(AmfObject::anonymous(properties)),
+    })
+}
+
+/// Convert an AMF value into a script value.
+///
+/// `objects` holds every object created so far for a value that AMF can
+/// refer to, in the order AMF numbers references in. Typed objects are read as anonymous objects, XML as its
+/// source, and externalizable objects as the first value they serialize.
+fn amf_to_value<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: AmfValue,
+    objects: &mut Vec<Object<'gc>>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mc = activation.context.gc_context;
+
+    Ok(match value {
+        AmfValue::Undefined => Value::Undefined,
+        AmfValue::Null => Value::Null,
+        AmfValue::Bool(b) => b.into(),
+        AmfValue::Integer(i) => i.into(),
+        AmfValue::Number(n) => n.into(),
+        AmfValue::String(s) | AmfValue::XmlDocument(s) | AmfValue::Xml(s) => {
+            AvmString::new(mc, s).into()
+        }
+        AmfValue::Reference(index) => objects
+            .get(index)
+            .copied()
+            .map(Value::Object)
+            .ok_or(AmfError::InvalidReference(index))?,
+        AmfValue::Date(time) => {
+            let proto = activation.avm2().prototypes().date;
+            let date_time = match Utc.timestamp_millis_opt(time as i64) {
+                LocalResult::Single(date_time) if time.is_finite() => Some(date_time),
+                _ => None,
+            };
+            DateObject::from_date_time(mc, Some(proto), date_time).into()
+        }
+        AmfValue::ByteArray(bytes) => {
+            let proto = activation.avm2().prototypes().bytearray;
+            let object =
+                ByteArrayObject::from_storage(mc, Some(proto), ByteArrayStorage::from_vec(bytes));
+            objects.push(object);
+
+            object.into()
+        }
+        AmfValue::Array { dense, associative } => {
+            let proto = activation.avm2().prototypes().array;
+            let object = ArrayObject::from_array(ArrayStorage::new(0), proto, mc);
+            objects.push(object);
+
+            set_amf_properties(activation, object, associative, objects)?;
+            for item in dense {
+                let item = amf_to_value(activation, item, objects)?;
+                if let Some(mut array) = object.as_array_storage_mut(activation.context.gc_context)
+                {
+                    array.push(item);
+                }
+            }
+
+            object.into()
+        }
+        AmfValue::Object(amf_object) => {
+            let proto = activation.avm2().prototypes().object;
+            let object = ScriptObject::object(mc, proto);
+            objects.push(object);
+
+            set_amf_properties(activation, object, amf_object.into_properties(), objects)?;
+
+            object.into()
+        }
+        AmfValue::Externalizable { data, .. } => {
+            // The externalizable object takes a reference before its data,
+            // so reserve it and fill it in once the data is converted.
+            let proto = activation.avm2().prototypes().object;
+            let index = objects.len();
+            objects.push(ScriptObject::object(mc, proto));
+
+            let value = match data.into_iter().next() {
+                Some(value) => amf_to_value(activation, value, objects)?,
+                None => Value::Undefined,
+            };
+            if let Value::Object(object) = value {
+                objects[index] = object;
+            }
+
+            value
+        }
+    })
+}
+
+/// Set the named properties read from AMF on an object.
+fn set_amf_properties<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+    properties: Vec<(String, AmfValue)>,
+    objects: &mut Vec<Object<'gc>>,
+) -> Result<(), Error<'gc>> {
+    for (name, value) in properties {
+        let value = amf_to_value(activation, value, objects)?;
+        let name = QName::new(
+            Namespace::public(),
+            AvmString::new(activation.context.gc_context, name),
+        );
+        object.set_property(object, &name, value, activation)?;
+    }
+
+    Ok(())
+}
+
+/// Implements `ByteArray.writeObject`.
+pub fn write_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = match this {
+        Some(this) => this,
+        None => return Ok(Value::Undefined),
+    };
+    let encoding = match this.as_bytearray() {
+        Some(bytearray) => bytearray.object_encoding(),
+        None => return Ok(Value::Undefined),
+    };
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let value = value_to_amf(activation, value, &mut Vec::new())?;
+    let data = amf::write_value(&value, encoding);
+
+    if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+        bytearray.write_bytes(&data)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readObject`.
+pub fn read_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = match this {
+        Some(this) => this,
+        None => return Ok(Value::Undefined),
+    };
+    let value = match this.as_bytearray_mut(activation.context.gc_context) {
+        Some(mut bytearray) => {
+            let (value, length) =
+                amf::read_value(bytearray.remaining_bytes(), bytearray.object_encoding()).map_err(
+                    |e| match e {
+                        AmfError::EndOfData => Error::new(
+                            ErrorKind::EOFError,
+                            "Error #2030: End of file was encountered.",
+                        ),
+                        e => Error::new(ErrorKind::Error, format!("{}", e)),
+                    },
+                )?;
+            let position = bytearray.position();
+            bytearray.set_position(position + length);
+
+            value
+        }
+        None => return Ok(Value::Undefined),
+    };
+
+    amf_to_value(activation, value, &mut Vec::new())
+}
+
+/// Construct `ByteArray`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.utils"), "ByteArray"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "length"),
+        Method::from_builtin(length),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "length"),
+        Method::from_builtin(set_length),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "position"),
+        Method::from_builtin(position),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "position"),
+        Method::from_builtin(set_position),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bytesAvailable"),
+        Method::from_builtin(bytes_available),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "endian"),
+        Method::from_builtin(endian),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "endian"),
+        Method::from_builtin(set_endian),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "objectEncoding"),
+        Method::from_builtin(object_encoding),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "objectEncoding"),
+        Method::from_builtin(set_object_encoding),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clear"),
+        Method::from_builtin(clear),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readByte"),
+        Method::from_builtin(read_byte),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeByte"),
+        Method::from_builtin(write_byte),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readUnsignedByte"),
+        Method::from_builtin(read_unsigned_byte),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readShort"),
+        Method::from_builtin(read_short),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeShort"),
+        Method::from_builtin(write_short),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readUnsignedShort"),
+        Method::from_builtin(read_unsigned_short),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readInt"),
+        Method::from_builtin(read_int),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeInt"),
+        Method::from_builtin(write_int),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readUnsignedInt"),
+        Method::from_builtin(read_unsigned_int),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeUnsignedInt"),
+        Method::from_builtin(write_unsigned_int),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readFloat"),
+        Method::from_builtin(read_float),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeFloat"),
+        Method::from_builtin(write_float),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readDouble"),
+        Method::from_builtin(read_double),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeDouble"),
+        Method::from_builtin(write_double),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readBoolean"),
+        Method::from_builtin(read_boolean),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeBoolean"),
+        Method::from_builtin(write_boolean),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readUTF"),
+        Method::from_builtin(read_utf),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeUTF"),
+        Method::from_builtin(write_utf),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readUTFBytes"),
+        Method::from_builtin(read_utf_bytes),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeUTFBytes"),
+        Method::from_builtin(write_utf_bytes),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readMultiByte"),
+        Method::from_builtin(read_multi_byte),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeMultiByte"),
+        Method::from_builtin(write_multi_byte),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readBytes"),
+        Method::from_builtin(read_bytes),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeBytes"),
+        Method::from_builtin(write_bytes),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readObject"),
+        Method::from_builtin(read_object),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "writeObject"),
+        Method::from_builtin(write_object),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "compress"),
+        Method::from_builtin(compress),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "uncompress"),
+        Method::from_builtin(uncompress),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "deflate"),
+        Method::from_builtin(deflate),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "inflate"),
+        Method::from_builtin(inflate),
+    ));
+
+    class
+}
+
+/// Object deriver for `ByteArray`
+pub fn bytearray_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    Ok(ByteArrayObject::derive(
+        base_proto,
+        activation.context.gc_context,
+        class,
+        scope,
+    ))
+}
@@ -0,0 +1,461 @@
+//! `flash.utils.describeType`
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::e4x::{E4XName, E4XNode};
+use crate::avm2::globals::xml::new_xml;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::script::TranslationUnit;
+use crate::avm2::traits::{Trait, TraitKind};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+use std::collections::{HashMap, HashSet};
+use swf::avm2::types::{Index, Multiname as AbcMultiname};
+
+/// Construct an element with the given attributes.
+fn element<'gc>(
+    mc: MutationContext<'gc, '_>,
+    name: &str,
+    attributes: &[(&str, &str)],
+) -> E4XNode<'gc> {
+    let node = E4XNode::new_element(mc, E4XName::new("", name));
+    let attributes = attributes
+        .iter()
+        .map(|(name, value)| E4XNode::new_attribute(mc, E4XName::new("", *name), *value))
+        .collect();
+
+    node.set_attributes(mc, attributes);
+
+    node
+}
+
+/// Get the name of a type in a method signature.
+///
+/// Types that are not declared are printed as `*`.
+fn type_name<'gc>(
+    unit: TranslationUnit<'gc>,
+    index: Index<AbcMultiname>,
+    mc: MutationContext<'gc, '_>,
+) -> Result<String, Error<'gc>> {
+    if index.0 == 0 {
+        return Ok("*".to_string());
+    }
+
+    Ok(Multiname::from_abc_multiname_static(unit, index, mc)?.to_qualified_name())
+}
+
+/// Get the return type of a method, and the type and optionality of each of
+/// its parameters.
+///
+/// Native methods don't declare a signature, so they are described as taking
+/// no parameters and returning any type.
+fn method_signature<'gc>(
+    method: &Method<'gc>,
+    mc: MutationContext<'gc, '_>,
+) -> Result<(String, Vec<(String, bool)>), Error<'gc>> {
+    let bm = match method {
+        Method::Entry(bm) => *bm,
+        Method::Native(_) => return Ok(("*".to_string(), Vec::new())),
+    };
+    let unit = bm.translation_unit();
+    let abc_method = bm.method();
+
+    let return_type = type_name(unit, abc_method.return_type.clone(), mc)?;
+    let mut params = Vec::new();
+    for param in abc_method.params.iter() {
+        params.push((
+            type_name(unit, param.kind.clone(), mc)?,
+            param.default_value.is_some(),
+        ));
+    }
+
+    Ok((return_type, params))
+}
+
+/// Append a `parameter` element for each parameter of a method.
+fn describe_params<'gc>(
+    mc: MutationContext<'gc, '_>,
+    node: E4XNode<'gc>,
+    params: &[(String, bool)],
+) {
+    for (index, (param_type, optional)) in params.iter().enumerate() {
+        node.append_child(
+            mc,
+            element(
+                mc,
+                "parameter",
+                &[
+                    ("index", (index + 1).to_string().as_str()),
+                    ("type", param_type.as_str()),
+                    ("optional", if *optional { "true" } else { "false" }),
+                ],
+            ),
+        );
+    }
+}
+
+/// Append a `metadata` element for each metadata tag of a trait.
+fn describe_metadata<'gc>(
+    mc: MutationContext<'gc, '_>,
+    node: E4XNode<'gc>,
+    class_trait: &Trait<'gc>,
+) {
+    for metadata in class_trait.metadata() {
+        let metadata_node = element(mc, "metadata", &[("name", metadata.name.as_str())]);
+        for item in metadata.items.iter() {
+            metadata_node.append_child(
+                mc,
+                element(
+                    mc,
+                    "arg",
+                    &[("key", item.key.as_str()), ("value", item.value.as_str())],
+                ),
+            );
+        }
+
+        node.append_child(mc, metadata_node);
+    }
+}
+
+/// A getter and setter pair, which are described together as one accessor.
+///
+/// `traits` holds the getters and setters, whose metadata is merged.
+struct Accessor<'gc> {
+    uri: Option<String>,
+    declared_by: String,
+    accessor_type: Option<String>,
+    readable: bool,
+    writable: bool,
+    traits: Vec<Trait<'gc>>,
+}
+
+/// Append descriptions of the traits of a list of classes, from the most
+/// derived class to its base class.
+///
+/// Only public traits and traits in user-defined namespaces are described.
+/// Overridden traits are described once, as declared by the most derived
+/// class.
+fn describe_traits<'gc>(
+    mc: MutationContext<'gc, '_>,
+    node: E4XNode<'gc>,
+    classes: &[GcCell<'gc, Class<'gc>>],
+    is_static: bool,
+) -> Result<(), Error<'gc>> {
+    let mut described = HashSet::new();
+    let mut accessor_names = Vec::new();
+    let mut accessors: HashMap<QName<'gc>, Accessor<'gc>> = HashMap::new();
+
+    for class in classes {
+        let read = class.read();
+        let declared_by = read.name().to_qualified_name();
+        let traits = if is_static {
+            read.class_traits()
+        } else {
+            read.instance_traits()
+        };
+
+        for class_trait in traits {
+            let name = class_trait.name();
+            let uri = match name.namespace() {
+                ns if ns.is_public() => None,
+                Namespace::Namespace(uri) if *name.namespace() != Namespace::as3_namespace() => {
+                    Some(uri.to_string())
+                }
+                _ => continue,
+            };
+
+            let (accessor_type, readable, writable) = match class_trait.kind() {
+                TraitKind::Getter { method, .. } => (method_signature(method, mc)?.0, true, false),
+                TraitKind::Setter { method, .. } => {
+                    let (_, params) = method_signature(method, mc)?;
+                    let setter_type = params
+                        .into_iter()
+                        .next()
+                        .map(|(param_type, _)| param_type)
+                        .unwrap_or_else(|| "*".to_string());
+
+                    (setter_type, false, true)
+                }
+                _ => {
+                    if !described.insert(name.clone()) {
+                        continue;
+                    }
+
+                    let mut attributes = vec![("name", name.local_name().to_string())];
+                    if let Some(uri) = uri {
+                        attributes.push(("uri", uri));
+                    }
+
+                    let trait_node = match class_trait.kind() {
+                        TraitKind::Slot { type_name, .. } => {
+                            attributes.push(("type", type_name.to_qualified_name()));
+
+                            element(mc, "variable", &as_attributes(&attributes))
+                        }
+                        TraitKind::Const { type_name, .. } => {
+                            attributes.push(("type", type_name.to_qualified_name()));
+
+                            element(mc, "constant", &as_attributes(&attributes))
+                        }
+                        TraitKind::Method { method, .. } => {
+                            let (return_type, params) = method_signature(method, mc)?;
+                            attributes.push(("declaredBy", declared_by.clone()));
+                            attributes.push(("returnType", return_type));
+
+                            let method_node = element(mc, "method", &as_attributes(&attributes));
+                            describe_params(mc, method_node, &params);
+
+                            method_node
+                        }
+                        _ => continue,
+                    };
+
+                    describe_metadata(mc, trait_node, class_trait);
+                    node.append_child(mc, trait_node);
+                    continue;
+                }
+            };
+
+            if described.contains(name) {
+                continue;
+            }
+
+            let accessor = accessors.entry(name.clone()).or_insert_with(|| {
+                accessor_names.push(name.clone());
+
+                Accessor {
+                    uri,
+                    declared_by: declared_by.clone(),
+                    accessor_type: None,
+                    readable: false,
+                    writable: false,
+                    traits: Vec::new(),
+                }
+            });
+            accessor.accessor_type.get_or_insert(accessor_type);
+            accessor.readable |= readable;
+            accessor.writable |= writable;
+            accessor.traits.push(class_trait.clone());
+        }
+    }
+
+    for name in accessor_names {
+        let accessor = accessors.remove(&name).unwrap();
+        let access = match (accessor.readable, accessor.writable) {
+            (true, true) => "readwrite",
+            (true, false) => "readonly",
+            _ => "writeonly",
+        };
+
+        let mut attributes = vec![("name", name.local_name().to_string())];
+        if let Some(uri) = accessor.uri {
+            attributes.push(("uri", uri));
+        }
+        attributes.push(("access", access.to_string()));
+        attributes.push((
+            "type",
+            accessor.accessor_type.unwrap_or_else(|| "*".to_string()),
+        ));
+        attributes.push(("declaredBy", accessor.declared_by));
+
+        let accessor_node = element(mc, "accessor", &as_attributes(&attributes));
+        for accessor_trait in accessor.traits.iter() {
+            describe_metadata(mc, accessor_node, accessor_trait);
+        }
+
+        node.append_child(mc, accessor_node);
+    }
+
+    Ok(())
+}
+
+/// Borrow a list of owned attributes.
+fn as_attributes<'a>(attributes: &'a [(&'a str, String)]) -> Vec<(&'a str, &'a str)> {
+    attributes
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .collect()
+}
+
+/// Append the description of instances of a class: its superclasses,
+/// interfaces, constructor and instance traits.
+///
+/// `classes` lists the class followed by each of its superclasses.
+fn describe_instance<'gc>(
+    mc: MutationContext<'gc, '_>,
+    node: E4XNode<'gc>,
+    classes: &[GcCell<'gc, Class<'gc>>],
+) -> Result<(), Error<'gc>> {
+    for superclass in classes.iter().skip(1) {
+        let superclass_name = superclass.read().name().to_qualified_name();
+        node.append_child(
+            mc,
+            element(mc, "extendsClass", &[("type", superclass_name.as_str())]),
+        );
+    }
+
+    let mut interfaces = Vec::new();
+    for class in classes {
+        for interface in class.read().interfaces() {
+            let interface_name = interface.to_qualified_name();
+            if !interfaces.contains(&interface_name) {
+                interfaces.push(interface_name);
+            }
+        }
+    }
+
+    for interface_name in interfaces {
+        node.append_child(
+            mc,
+            element(
+                mc,
+                "implementsInterface",
+                &[("type", interface_name.as_str())],
+            ),
+        );
+    }
+
+    if let Some(class) = classes.first() {
+        let (_, params) = method_signature(&class.read().instance_init(), mc)?;
+        if !params.is_empty() {
+            let constructor_node = element(mc, "constructor", &[]);
+            describe_params(mc, constructor_node, &params);
+            node.append_child(mc, constructor_node);
+        }
+    }
+
+    describe_traits(mc, node, classes, false)
+}
+
+/// Get a class and all of its superclasses from its instance prototype.
+fn class_chain<'gc>(proto: Option<Object<'gc>>) -> Vec<GcCell<'gc, Class<'gc>>> {
+    let mut classes: Vec<GcCell<'gc, Class<'gc>>> = Vec::new();
+    let mut next = proto;
+
+    while let Some(proto) = next {
+        if let Some(class) = proto.as_class() {
+            if !classes.iter().any(|c| GcCell::ptr_eq(*c, class)) {
+                classes.push(class);
+            }
+        }
+
+        next = proto.proto();
+    }
+
+    classes
+}
+
+/// Implements `flash.utils.describeType`
+pub fn describe_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mc = activation.context.gc_context;
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+
+    let type_node = match value {
+        Value::Undefined | Value::Null => {
+            let name = if matches!(value, Value::Null) {
+                "null"
+            } else {
+                "void"
+            };
+
+            element(
+                mc,
+                "type",
+                &[
+                    ("name", name),
+                    ("isDynamic", "false"),
+                    ("isFinal", "true"),
+                    ("isStatic", "false"),
+                ],
+            )
+        }
+        value => {
+            let mut object = value.coerce_to_object(activation)?;
+            let is_class = object.as_class().is_some() && object.as_executable().is_some();
+            let proto = if is_class {
+                object
+                    .get_property(
+                        object,
+                        &QName::new(Namespace::public(), "prototype"),
+                        activation,
+                    )?
+                    .coerce_to_object(activation)
+                    .ok()
+            } else {
+                Some(object)
+            };
+
+            let classes = class_chain(proto);
+            let class_name = classes
+                .first()
+                .map(|class| class.read().name().to_qualified_name())
+                .unwrap_or_else(|| "Object".to_string());
+
+            if is_class {
+                let type_node = element(
+                    mc,
+                    "type",
+                    &[
+                        ("name", class_name.as_str()),
+                        ("base", "Class"),
+                        ("isDynamic", "true"),
+                        ("isFinal", "true"),
+                        ("isStatic", "true"),
+                    ],
+                );
+                type_node.append_child(mc, element(mc, "extendsClass", &[("type", "Class")]));
+                type_node.append_child(mc, element(mc, "extendsClass", &[("type", "Object")]));
+                type_node.append_child(
+                    mc,
+                    element(
+                        mc,
+                        "accessor",
+                        &[
+                            ("name", "prototype"),
+                            ("access", "readonly"),
+                            ("type", "*"),
+                            ("declaredBy", "Class"),
+                        ],
+                    ),
+                );
+                describe_traits(mc, type_node, &classes[..classes.len().min(1)], true)?;
+
+                let factory_node = element(mc, "factory", &[("type", class_name.as_str())]);
+                describe_instance(mc, factory_node, &classes)?;
+                type_node.append_child(mc, factory_node);
+
+                type_node
+            } else {
+                let (is_dynamic, is_final) = classes
+                    .first()
+                    .map(|class| {
+                        let read = class.read();
+                        (!read.is_sealed(), read.is_final())
+                    })
+                    .unwrap_or((true, false));
+
+                let mut attributes = vec![("name", class_name)];
+                if let Some(superclass) = classes.get(1) {
+                    attributes.push(("base", superclass.read().name().to_qualified_name()));
+                }
+                attributes.push(("isDynamic", is_dynamic.to_string()));
+                attributes.push(("isFinal", is_final.to_string()));
+                attributes.push(("isStatic", "false".to_string()));
+
+                let type_node = element(mc, "type", &as_attributes(&attributes));
+                describe_instance(mc, type_node, &classes)?;
+
+                type_node
+            }
+        }
+    };
+
+    Ok(new_xml(activation, type_node).into())
+}
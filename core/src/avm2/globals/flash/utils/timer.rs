@@ -0,0 +1,415 @@
+//! `flash.utils.Timer` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::Event;
+use crate::avm2::globals::{dispatch_event, NS_RUFFLE_INTERNAL};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{EventObject, FunctionObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorKind};
+use crate::timer::TimerCallback;
+use gc_arena::{GcCell, MutationContext};
+
+/// The name of one of the hidden slots holding a `Timer`'s state.
+fn slot_name<'gc>(name: &'static str) -> QName<'gc> {
+    QName::new(Namespace::private(NS_RUFFLE_INTERNAL), name)
+}
+
+/// Validate a timer delay, which must be a finite, non-negative number.
+fn check_delay<'gc>(delay: f64) -> Result<f64, Error<'gc>> {
+    if !delay.is_finite() || delay < 0.0 {
+        return Err(Error::new(
+            ErrorKind::RangeError,
+            "Error #2066: The Timer delay specified is out of range.",
+        ));
+    }
+
+    Ok(delay)
+}
+
+/// Get the ID of the queued timer driving this `Timer`, if it is running.
+fn timer_id<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<Option<i32>, Error<'gc>> {
+    match this.get_property(this, &slot_name("timerId"), activation)? {
+        Value::Null | Value::Undefined => Ok(None),
+        id => Ok(Some(id.coerce_to_i32(activation)?)),
+    }
+}
+
+/// Queue a timer that ticks this `Timer` once every `delay` milliseconds.
+fn start_timer<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<(), Error<'gc>> {
+    let delay = this
+        .get_property(this, &slot_name("delay"), activation)?
+        .coerce_to_number(activation)?;
+    let fn_proto = activation.avm2().prototypes().function;
+    let callable = FunctionObject::from_method(
+        activation.context.gc_context,
+        Method::from_builtin(tick),
+        None,
+        fn_proto,
+        Some(this),
+    );
+    let callback = TimerCallback::Avm2Callable {
+        callable,
+        params: vec![],
+    };
+
+    let id = activation
+        .context
+        .timers
+        .add_timer(callback, delay as i32, false);
+    this.set_property(this, &slot_name("timerId"), id.into(), activation)?;
+
+    Ok(())
+}
+
+/// Remove the queued timer driving this `Timer`, if any.
+fn stop_timer<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<(), Error<'gc>> {
+    if let Some(id) = timer_id(activation, this)? {
+        activation.context.timers.remove(id);
+        this.set_property(this, &slot_name("timerId"), Value::Null, activation)?;
+    }
+
+    Ok(())
+}
+
+/// Dispatch a `TimerEvent` of the given type on this `Timer`.
+fn dispatch_timer_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    event_type: &'static str,
+) -> Result<(), Error<'gc>> {
+    let timerevent_proto = activation.avm2().prototypes().timerevent;
+    let event = EventObject::from_event(
+        activation.context.gc_context,
+        Some(timerevent_proto),
+        Event::new(event_type),
+    );
+
+    dispatch_event(activation, Some(this), &[event.into()])?;
+
+    Ok(())
+}
+
+/// Called by the timer queue each time this `Timer`'s delay elapses.
+fn tick<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        let current_count = this
+            .get_property(this, &slot_name("currentCount"), activation)?
+            .coerce_to_i32(activation)?
+            .wrapping_add(1);
+        this.set_property(
+            this,
+            &slot_name("currentCount"),
+            current_count.into(),
+            activation,
+        )?;
+
+        dispatch_timer_event(activation, this, "timer")?;
+
+        // The listener may have stopped, reset or reconfigured the timer.
+        if timer_id(activation, this)?.is_none() {
+            return Ok(Value::Undefined);
+        }
+
+        let repeat_count = this
+            .get_property(this, &slot_name("repeatCount"), activation)?
+            .coerce_to_i32(activation)?;
+        let current_count = this
+            .get_property(this, &slot_name("currentCount"), activation)?
+            .coerce_to_i32(activation)?;
+        if repeat_count > 0 && current_count >= repeat_count {
+            stop_timer(activation, this)?;
+            dispatch_timer_event(activation, this, "timerComplete")?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.utils.Timer`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let delay = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        let delay = check_delay(delay)?;
+        let repeat_count = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Integer(0))
+            .coerce_to_i32(activation)?;
+
+        this.set_property(this, &slot_name("delay"), delay.into(), activation)?;
+        this.set_property(
+            this,
+            &slot_name("repeatCount"),
+            repeat_count.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &slot_name("currentCount"),
+            Value::Integer(0),
+            activation,
+        )?;
+        this.set_property(this, &slot_name("timerId"), Value::Null, activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.utils.Timer`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `delay`'s getter.
+pub fn delay<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        return this.get_property(this, &slot_name("delay"), activation);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `delay`'s setter.
+///
+/// Changing the delay of a running timer restarts it with the new delay.
+pub fn set_delay<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        let delay = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        let delay = check_delay(delay)?;
+
+        this.set_property(this, &slot_name("delay"), delay.into(), activation)?;
+
+        if timer_id(activation, this)?.is_some() {
+            stop_timer(activation, this)?;
+            start_timer(activation, this)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `repeatCount`'s getter.
+pub fn repeat_count<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        return this.get_property(this, &slot_name("repeatCount"), activation);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `repeatCount`'s setter.
+pub fn set_repeat_count<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        let repeat_count = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        this.set_property(
+            this,
+            &slot_name("repeatCount"),
+            repeat_count.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `currentCount`'s getter.
+pub fn current_count<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        return this.get_property(this, &slot_name("currentCount"), activation);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `running`'s getter.
+pub fn running<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        return Ok(timer_id(activation, this)?.is_some().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `start`.
+pub fn start<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if timer_id(activation, this)?.is_none() {
+            start_timer(activation, this)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `stop`.
+pub fn stop<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        stop_timer(activation, this)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `reset`.
+pub fn reset<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        stop_timer(activation, this)?;
+        this.set_property(
+            this,
+            &slot_name("currentCount"),
+            Value::Integer(0),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Timer`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.utils"), "Timer"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_slot(
+        slot_name("delay"),
+        QName::new(Namespace::public(), "Number").into(),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        slot_name("repeatCount"),
+        QName::new(Namespace::public(), "int").into(),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        slot_name("currentCount"),
+        QName::new(Namespace::public(), "int").into(),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        slot_name("timerId"),
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "BareObject").into(),
+        None,
+    ));
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "delay"),
+        Method::from_builtin(delay),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "delay"),
+        Method::from_builtin(set_delay),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "repeatCount"),
+        Method::from_builtin(repeat_count),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "repeatCount"),
+        Method::from_builtin(set_repeat_count),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "currentCount"),
+        Method::from_builtin(current_count),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "running"),
+        Method::from_builtin(running),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "start"),
+        Method::from_builtin(start),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "stop"),
+        Method::from_builtin(stop),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "reset"),
+        Method::from_builtin(reset),
+    ));
+
+    class
+}
@@ -0,0 +1,458 @@
+//! `JSON` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, ScriptObject, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorKind};
+use gc_arena::{GcCell, MutationContext};
+use json::JsonValue;
+use std::fmt::Write;
+
+/// Implements `JSON`'s instance initializer.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Err(Error::new(
+        ErrorKind::TypeError,
+        "Error #1076: JSON is not a constructor.",
+    ))
+}
+
+/// Implements `JSON`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Convert a parsed JSON value into a script value.
+fn json_to_value<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    json: JsonValue,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mc = activation.context.gc_context;
+
+    Ok(match json {
+        JsonValue::Null => Value::Null,
+        JsonValue::Short(s) => AvmString::new(mc, s.to_string()).into(),
+        JsonValue::String(s) => AvmString::new(mc, s).into(),
+        JsonValue::Number(n) => f64::from(n).into(),
+        JsonValue::Boolean(b) => b.into(),
+        JsonValue::Object(properties) => {
+            let proto = activation.avm2().prototypes().object;
+            let mut object = ScriptObject::object(mc, proto);
+
+            for (key, value) in properties.iter() {
+                let value = json_to_value(activation, value.clone())?;
+                let name = QName::dynamic_name(AvmString::new(mc, key));
+                object.set_property(object, &name, value, activation)?;
+            }
+
+            object.into()
+        }
+        JsonValue::Array(items) => {
+            let proto = activation.avm2().prototypes().array;
+            let values = items
+                .into_iter()
+                .map(|item| json_to_value(activation, item))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            ArrayObject::from_array(ArrayStorage::from_args(&values), proto, mc).into()
+        }
+    })
+}
+
+/// List the names of the properties `JSON` reads from an object.
+///
+/// This is every index of an array, or every enumerable property of any other
+/// object.
+fn property_names<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+) -> Vec<AvmString<'gc>> {
+    if let Some(length) = object.as_array_storage().map(|array| array.length()) {
+        return (0..length)
+            .map(|index| AvmString::new(activation.context.gc_context, index.to_string()))
+            .collect();
+    }
+
+    let mut names = Vec::new();
+    let mut index = 1;
+    while let Some(name) = object.get_enumerant_name(index) {
+        names.push(name.local_name());
+        index += 1;
+    }
+
+    names
+}
+
+/// Walk a parsed value depth-first, handing each property to a reviver and
+/// replacing it with the result.
+///
+/// Properties the reviver returns `undefined` for are deleted.
+fn revive<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    reviver: Object<'gc>,
+    mut holder: Object<'gc>,
+    key: AvmString<'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = holder.get_property(holder, &QName::dynamic_name(key), activation)?;
+
+    if let Value::Object(mut object) = value {
+        for name in property_names(activation, object) {
+            let revived = revive(activation, reviver, object, name)?;
+            let name = QName::dynamic_name(name);
+
+            if let Value::Undefined = revived {
+                object.delete_property(activation.context.gc_context, &name);
+            } else {
+                object.set_property(object, &name, revived, activation)?;
+            }
+        }
+    }
+
+    reviver.call(Some(holder), &[key.into(), value], activation, None)
+}
+
+/// Implements `JSON.parse`.
+pub fn parse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let input = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let json = json::parse(&input).map_err(|_| {
+        Error::new(
+            ErrorKind::SyntaxError,
+            "Error #1132: Invalid JSON parse input.",
+        )
+    })?;
+    let value = json_to_value(activation, json)?;
+
+    match args.get(1) {
+        Some(Value::Object(reviver)) => {
+            let proto = activation.avm2().prototypes().object;
+            let mut holder = ScriptObject::object(activation.context.gc_context, proto);
+            holder.set_property(holder, &QName::dynamic_name(""), value, activation)?;
+
+            revive(activation, *reviver, holder, "".into())
+        }
+        _ => Ok(value),
+    }
+}
+
+/// Write a string as a quoted JSON string.
+fn quote(string: &str, output: &mut String) {
+    output.push('"');
+
+    for c in string.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\u{8}' => output.push_str("\\b"),
+            '\u{c}' => output.push_str("\\f"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(output, "\\u{:04x}", c as u32);
+            }
+            c => output.push(c),
+        }
+    }
+
+    output.push('"');
+}
+
+/// How `JSON.stringify` picks and transforms the values it writes.
+enum Replacer<'gc> {
+    /// Write every value as-is.
+    None,
+
+    /// Call a function with each key and value, and write what it returns.
+    Function(Object<'gc>),
+
+    /// Only write object properties with these names.
+    Properties(Vec<AvmString<'gc>>),
+}
+
+/// State of a single call to `JSON.stringify`.
+struct Stringifier<'gc> {
+    replacer: Replacer<'gc>,
+
+    /// The whitespace each level of nesting is indented by.
+    gap: String,
+
+    /// Every object currently being written, used to detect cycles.
+    stack: Vec<Object<'gc>>,
+}
+
+impl<'gc> Stringifier<'gc> {
+    /// Write the value of a property of `holder`.
+    ///
+    /// Values that JSON can't represent, such as functions and `undefined`,
+    /// are not written, and yield `false`.
+    fn serialize(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        holder: Object<'gc>,
+        key: AvmString<'gc>,
+        mut value: Value<'gc>,
+        indent: &str,
+        output: &mut String,
+    ) -> Result<bool, Error<'gc>> {
+        if let Value::Object(mut object) = value {
+            let to_json =
+                object.get_property(object, &QName::dynamic_name("toJSON"), activation)?;
+            if let Value::Object(to_json) = to_json {
+                if to_json.as_executable().is_some() {
+                    value = to_json.call(Some(object), &[key.into()], activation, None)?;
+                }
+            }
+        }
+
+        if let Replacer::Function(replacer) = self.replacer {
+            value = replacer.call(Some(holder), &[key.into(), value], activation, None)?;
+        }
+
+        // Boxed primitives are written as the primitive they hold.
+        if let Value::Object(object) = value {
+            let primitive = object.value_of(activation.context.gc_context)?;
+            if !matches!(primitive, Value::Object(_)) {
+                value = primitive;
+            }
+        }
+
+        match value {
+            Value::Undefined => return Ok(false),
+            Value::Null => output.push_str("null"),
+            Value::Bool(true) => output.push_str("true"),
+            Value::Bool(false) => output.push_str("false"),
+            Value::String(s) => quote(&s, output),
+            Value::Number(n) if !n.is_finite() => output.push_str("null"),
+            Value::Number(_) | Value::Integer(_) | Value::Unsigned(_) => {
+                output.push_str(&value.coerce_to_string(activation)?)
+            }
+            Value::Object(object) => {
+                if object.as_executable().is_some() {
+                    return Ok(false);
+                }
+
+                if self.stack.iter().any(|o| Object::ptr_eq(*o, object)) {
+                    return Err(Error::new(
+                        ErrorKind::TypeError,
+                        "Error #1129: Cyclic structure cannot be converted to JSON string.",
+                    ));
+                }
+
+                self.stack.push(object);
+                if object.as_array_storage().is_some() {
+                    self.serialize_array(activation, object, indent, output)?;
+                } else {
+                    self.serialize_object(activation, object, indent, output)?;
+                }
+                self.stack.pop();
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Write the members of an object or array, one per line if indenting.
+    fn write_members(
+        &self,
+        members: Vec<String>,
+        open: char,
+        close: char,
+        indent: &str,
+        output: &mut String,
+    ) {
+        output.push(open);
+
+        if !members.is_empty() {
+            if self.gap.is_empty() {
+                output.push_str(&members.join(","));
+            } else {
+                let inner = format!("{}{}", indent, self.gap);
+                output.push('\n');
+                output.push_str(&inner);
+                output.push_str(&members.join(&format!(",\n{}", inner)));
+                output.push('\n');
+                output.push_str(indent);
+            }
+        }
+
+        output.push(close);
+    }
+
+    /// Write an array, writing `null` for elements JSON can't represent.
+    fn serialize_array(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        array: Object<'gc>,
+        indent: &str,
+        output: &mut String,
+    ) -> Result<(), Error<'gc>> {
+        let inner = format!("{}{}", indent, self.gap);
+        let length = array
+            .as_array_storage()
+            .map(|storage| storage.length())
+            .unwrap_or(0);
+        let mut members = Vec::with_capacity(length);
+
+        for index in 0..length {
+            let item = array
+                .as_array_storage()
+                .and_then(|storage| storage.get(index))
+                .unwrap_or(Value::Undefined);
+            let key = AvmString::new(activation.context.gc_context, index.to_string());
+
+            let mut member = String::new();
+            if !self.serialize(activation, array, key, item, &inner, &mut member)? {
+                member.push_str("null");
+            }
+            members.push(member);
+        }
+
+        self.write_members(members, '[', ']', indent, output);
+
+        Ok(())
+    }
+
+    /// Write an object, leaving out properties JSON can't represent.
+    fn serialize_object(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut object: Object<'gc>,
+        indent: &str,
+        output: &mut String,
+    ) -> Result<(), Error<'gc>> {
+        let inner = format!("{}{}", indent, self.gap);
+        let names = match &self.replacer {
+            Replacer::Properties(names) => names.clone(),
+            _ => property_names(activation, object),
+        };
+        let mut members = Vec::with_capacity(names.len());
+
+        for name in names {
+            let value = object.get_property(object, &QName::dynamic_name(name), activation)?;
+
+            let mut member = String::new();
+            quote(&name, &mut member);
+            member.push(':');
+            if !self.gap.is_empty() {
+                member.push(' ');
+            }
+
+            if self.serialize(activation, object, name, value, &inner, &mut member)? {
+                members.push(member);
+            }
+        }
+
+        self.write_members(members, '{', '}', indent, output);
+
+        Ok(())
+    }
+}
+
+/// Implements `JSON.stringify`.
+pub fn stringify<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+
+    let replacer = match args.get(1).cloned().unwrap_or(Value::Null) {
+        Value::Undefined | Value::Null => Replacer::None,
+        Value::Object(replacer) if replacer.as_executable().is_some() => {
+            Replacer::Function(replacer)
+        }
+        Value::Object(replacer) if replacer.as_array_storage().is_some() => {
+            let items: Vec<Value<'gc>> = replacer
+                .as_array_storage()
+                .unwrap()
+                .iter()
+                .map(|item| item.unwrap_or(Value::Undefined))
+                .collect();
+
+            let mut names = Vec::with_capacity(items.len());
+            for item in items {
+                let name = item.coerce_to_string(activation)?;
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+
+            Replacer::Properties(names)
+        }
+        _ => {
+            return Err(Error::new(ErrorKind::TypeError, "Error #1131: Replacer argument to JSON stringifier must be an array or a two parameter function."));
+        }
+    };
+
+    let gap = match args.get(2).cloned().unwrap_or(Value::Null) {
+        Value::String(space) => space.chars().take(10).collect(),
+        space @ Value::Number(_) | space @ Value::Integer(_) | space @ Value::Unsigned(_) => {
+            let count = space.coerce_to_number(activation)?.max(0.0).min(10.0);
+            " ".repeat(count as usize)
+        }
+        _ => String::new(),
+    };
+
+    let proto = activation.avm2().prototypes().object;
+    let mut holder = ScriptObject::object(activation.context.gc_context, proto);
+    holder.set_property(holder, &QName::dynamic_name(""), value.clone(), activation)?;
+
+    let mut stringifier = Stringifier {
+        replacer,
+        gap,
+        stack: Vec::new(),
+    };
+    let mut output = String::new();
+
+    if stringifier.serialize(activation, holder, "".into(), value, "", &mut output)? {
+        Ok(AvmString::new(activation.context.gc_context, output).into())
+    } else {
+        Ok(Value::Undefined)
+    }
+}
+
+/// Construct `JSON`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::public(), "JSON"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "parse"),
+        Method::from_builtin(parse),
+    ));
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "stringify"),
+        Method::from_builtin(stringify),
+    ));
+
+    class
+}
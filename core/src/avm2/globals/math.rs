@@ -7,7 +7,7 @@ use crate::avm2::names::{Multiname, Namespace, QName};
 use crate::avm2::object::Object;
 use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
-use crate::avm2::Error;
+use crate::avm2::{Error, ErrorKind};
 use gc_arena::{GcCell, MutationContext};
 use rand::Rng;
 use std::f64::{INFINITY, NAN, NEG_INFINITY};
@@ -41,7 +41,7 @@ macro_rules! math_wrap_std {
             $class.define_class_trait(Trait::from_method(
                 QName::new(Namespace::public(), $name),
                 Method::from_builtin(
-                    |activation, _this, args| -> Result<Value<'gc>, Error> {
+                    |activation, _this, args| -> Result<Value<'gc>, Error<'gc>> {
                         if let Some(input) = args.get(0) {
                             Ok($std(input.coerce_to_number(activation)?).into())
                         } else {
@@ -59,9 +59,12 @@ pub fn instance_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     // TODO: Replace with actual error type.
-    Err("TypeError: Error #1076: Math is not a constructor.".into())
+    Err(Error::new(
+        ErrorKind::TypeError,
+        "Error #1076: Math is not a constructor.",
+    ))
 }
 
 /// Implements `Math`'s class initializer.
@@ -69,7 +72,7 @@ pub fn class_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(Value::Undefined)
 }
 
@@ -132,7 +135,7 @@ fn round<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(x) = args.get(0) {
         let x = x.coerce_to_number(activation)?;
         // Note that Flash Math.round always rounds toward infinity,
@@ -147,7 +150,7 @@ fn atan2<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     let y = args
         .get(0)
         .unwrap_or(&Value::Undefined)
@@ -163,7 +166,7 @@ fn max<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     let mut cur_max = NEG_INFINITY;
     for arg in args {
         let val = arg.coerce_to_number(activation)?;
@@ -180,7 +183,7 @@ fn min<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     let mut cur_min = INFINITY;
     for arg in args {
         let val = arg.coerce_to_number(activation)?;
@@ -197,7 +200,7 @@ fn pow<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     let n = args
         .get(0)
         .unwrap_or(&Value::Undefined)
@@ -213,6 +216,6 @@ pub fn random<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(activation.context.rng.gen_range(0.0f64..1.0f64).into())
 }
@@ -2,20 +2,87 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
+use crate::avm2::globals::qname::as_qname;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
-use crate::avm2::Error;
+use crate::avm2::{Error, ErrorKind};
 use gc_arena::{GcCell, MutationContext};
 
 /// Implements `Namespace`'s instance initializer.
+///
+/// A `Namespace` may be constructed from a URI alone, or from a prefix and a
+/// URI. A URI given alone may also be another `Namespace` or a `QName`.
 pub fn instance_init<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
-    Err("Namespace constructor is a stub.".into())
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        let (prefix, uri): (Option<AvmString<'gc>>, AvmString<'gc>) = match args {
+            [] => (Some("".into()), "".into()),
+            [uri] => {
+                let copied = match uri {
+                    Value::Object(uri) => uri.as_namespace_object().map(|namespace| {
+                        let uri = namespace.as_namespace().unwrap().as_uri();
+
+                        (namespace.prefix(), uri)
+                    }),
+                    _ => None,
+                };
+
+                match copied {
+                    Some(copied) => copied,
+                    None => {
+                        let uri = match as_qname(activation, uri)? {
+                            Some((_local_name, uri)) => uri,
+                            None => uri.clone().coerce_to_string(activation)?,
+                        };
+
+                        if uri.is_empty() {
+                            (Some("".into()), uri)
+                        } else {
+                            (None, uri)
+                        }
+                    }
+                }
+            }
+            [prefix, uri, ..] => {
+                let uri = match as_qname(activation, uri)? {
+                    Some((_local_name, uri)) => uri,
+                    None => uri.clone().coerce_to_string(activation)?,
+                };
+                let prefix = match prefix {
+                    Value::Undefined => None,
+                    prefix => Some(prefix.clone().coerce_to_string(activation)?),
+                };
+
+                if uri.is_empty() && prefix.map(|p| !p.is_empty()).unwrap_or(false) {
+                    return Err(Error::new(
+                        ErrorKind::TypeError,
+                        "Error #1098: Illegal prefix for namespace with no URI.",
+                    ));
+                }
+
+                (prefix, uri)
+            }
+        };
+
+        if let Some(namespace) = this.as_namespace_object() {
+            namespace.init_namespace(
+                activation.context.gc_context,
+                Namespace::Namespace(uri),
+                prefix,
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
 }
 
 /// Implements `Namespace`'s class initializer.
@@ -23,17 +90,74 @@ pub fn class_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Namespace.prefix`.
+pub fn prefix<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(prefix) = this
+        .and_then(|this| this.as_namespace_object())
+        .and_then(|namespace| namespace.prefix())
+    {
+        return Ok(prefix.into());
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `Namespace.uri`.
+pub fn uri<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(namespace) = this.as_namespace() {
+            return Ok(namespace.as_uri().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Namespace.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    uri(activation, this, args)
+}
+
 /// Construct `Namespace`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
-    Class::new(
+    let class = Class::new(
         QName::new(Namespace::public(), "Namespace"),
         Some(QName::new(Namespace::public(), "Object").into()),
         Method::from_builtin(instance_init),
         Method::from_builtin(class_init),
         mc,
-    )
+    );
+
+    class.write(mc).define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "prefix"),
+        Method::from_builtin(prefix),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "uri"),
+        Method::from_builtin(uri),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    class
 }
@@ -0,0 +1,218 @@
+//! `QName` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `QName`'s instance initializer.
+///
+/// A `QName` may be constructed from a local name alone, in which case it is
+/// in the unnamed namespace, or from a namespace and a local name. Either
+/// argument may itself be a `QName` or `Namespace`.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let (namespace, name) = match args {
+            [] => (Value::Undefined, Value::Undefined),
+            [name] => (Value::Undefined, name.clone()),
+            [namespace, name, ..] => (namespace.clone(), name.clone()),
+        };
+
+        let (local_name, name_uri) = match name {
+            Value::Undefined => ("".into(), None),
+            Value::Object(name) if is_qname(name) => {
+                (local_name(activation, name)?, Some(uri(activation, name)?))
+            }
+            name => (name.coerce_to_string(activation)?, None),
+        };
+
+        let uri = match &namespace {
+            Value::Undefined if args.len() < 2 => name_uri.unwrap_or_else(|| "".into()),
+            Value::Undefined | Value::Null => "".into(),
+            Value::Object(namespace) if namespace.as_namespace().is_some() => {
+                namespace.as_namespace().unwrap().as_uri()
+            }
+            Value::Object(namespace) if is_qname(*namespace) => uri(activation, *namespace)?,
+            namespace => namespace.clone().coerce_to_string(activation)?,
+        };
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "localName"),
+            local_name.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), "uri"),
+            uri.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `QName`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Determine if an object is a `QName`.
+fn is_qname(object: Object<'_>) -> bool {
+    object
+        .has_own_property(&QName::new(
+            Namespace::Private("ruffle".into()),
+            "localName",
+        ))
+        .unwrap_or(false)
+}
+
+/// Read the local name of a `QName`.
+fn local_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<AvmString<'gc>, Error<'gc>> {
+    this.get_property(
+        this,
+        &QName::new(Namespace::Private("ruffle".into()), "localName"),
+        activation,
+    )?
+    .coerce_to_string(activation)
+}
+
+/// Read the namespace URI of a `QName`.
+fn uri<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<AvmString<'gc>, Error<'gc>> {
+    this.get_property(
+        this,
+        &QName::new(Namespace::Private("ruffle".into()), "uri"),
+        activation,
+    )?
+    .coerce_to_string(activation)
+}
+
+/// Read the local name and URI of a value, if it is a `QName`.
+pub fn as_qname<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: &Value<'gc>,
+) -> Result<Option<(AvmString<'gc>, AvmString<'gc>)>, Error<'gc>> {
+    match value {
+        Value::Object(object) if is_qname(*object) => Ok(Some((
+            local_name(activation, *object)?,
+            uri(activation, *object)?,
+        ))),
+        _ => Ok(None),
+    }
+}
+
+/// Construct a new `QName`.
+pub fn new_qname<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    uri: AvmString<'gc>,
+    local_name: AvmString<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let qname_proto = activation.context.avm2.prototypes().qname;
+    let args = [uri.into(), local_name.into()];
+    let qname = qname_proto.construct(activation, &args)?;
+
+    instance_init(activation, Some(qname), &args)?;
+
+    Ok(qname)
+}
+
+/// Implements `QName.localName`.
+pub fn local_name_getter<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        return Ok(local_name(activation, this)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `QName.uri`.
+pub fn uri_getter<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        return Ok(uri(activation, this)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `QName.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let local_name = local_name(activation, this)?;
+        let uri = uri(activation, this)?;
+
+        if uri.is_empty() {
+            return Ok(local_name.into());
+        }
+
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            format!("{}::{}", uri, local_name),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `QName`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::public(), "QName"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    class.write(mc).define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "localName"),
+        Method::from_builtin(local_name_getter),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "uri"),
+        Method::from_builtin(uri_getter),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    class
+}
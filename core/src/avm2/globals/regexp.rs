@@ -0,0 +1,401 @@
+//! `RegExp` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::Class;
+use crate::avm2::globals::array::build_array;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, RegExpObject, TObject};
+use crate::avm2::regexp::{RegExp, RegExpFlags, RegExpMatch};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorKind};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `RegExp`'s instance initializer.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        let pattern = args.get(0).cloned().unwrap_or(Value::Undefined);
+        let flags = args.get(1).cloned().unwrap_or(Value::Undefined);
+
+        let copied = match &pattern {
+            Value::Object(pattern) => pattern
+                .as_regexp()
+                .map(|regexp| (regexp.source().to_string(), regexp.flags())),
+            _ => None,
+        };
+
+        let (source, flags) = match copied {
+            Some(_) if flags != Value::Undefined => {
+                return Err(Error::new(
+                    ErrorKind::TypeError,
+                    "Error #1100: Cannot supply flags when constructing one RegExp from another.",
+                ));
+            }
+            Some(copied) => copied,
+            None => {
+                let source = match pattern {
+                    Value::Undefined => "".to_string(),
+                    pattern => pattern.coerce_to_string(activation)?.to_string(),
+                };
+                let flags = match flags {
+                    Value::Undefined => RegExpFlags::empty(),
+                    flags => RegExpFlags::from_flags(&flags.coerce_to_string(activation)?),
+                };
+                (source, flags)
+            }
+        };
+
+        if let Some(mut regexp) = this.as_regexp_mut(activation.context.gc_context) {
+            *regexp = RegExp::new(&source, flags);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp.source`.
+pub fn source<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(regexp) = this.as_regexp() {
+            return Ok(
+                AvmString::new(activation.context.gc_context, regexp.source().to_string()).into(),
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Read one of the flags of a `RegExp`.
+fn flag<'gc>(this: Option<Object<'gc>>, flag: RegExpFlags) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(regexp) = this.as_regexp() {
+            return Ok(regexp.flags().contains(flag).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp.global`.
+pub fn global<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    flag(this, RegExpFlags::GLOBAL)
+}
+
+/// Implements `RegExp.ignoreCase`.
+pub fn ignore_case<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    flag(this, RegExpFlags::IGNORE_CASE)
+}
+
+/// Implements `RegExp.multiline`.
+pub fn multiline<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    flag(this, RegExpFlags::MULTILINE)
+}
+
+/// Implements `RegExp.dotall`.
+pub fn dotall<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    flag(this, RegExpFlags::DOTALL)
+}
+
+/// Implements `RegExp.extended`.
+pub fn extended<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    flag(this, RegExpFlags::EXTENDED)
+}
+
+/// Implements `RegExp.lastIndex`'s getter.
+pub fn last_index<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(regexp) = this.as_regexp() {
+            return Ok((regexp.last_index() as u32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `RegExp.lastIndex`'s setter.
+pub fn set_last_index<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let last_index = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+        if let Some(mut regexp) = this.as_regexp_mut(activation.context.gc_context) {
+            regexp.set_last_index(last_index as usize);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Build the result of `RegExp.exec`: an array of the match and each group,
+/// with the `index` and `input` of the match and the value of each named
+/// group as properties.
+pub fn build_exec_result<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    text: &[u16],
+    input: AvmString<'gc>,
+    regexp_match: &RegExpMatch,
+    group_names: &[(String, usize)],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mc = activation.context.gc_context;
+    let captures: Vec<Value<'gc>> = regexp_match
+        .captures
+        .iter()
+        .map(|capture| match capture {
+            Some(range) => {
+                AvmString::new(mc, String::from_utf16_lossy(&text[range.clone()])).into()
+            }
+            None => Value::Undefined,
+        })
+        .collect();
+
+    let mut object = build_array(activation, ArrayStorage::from_args(&captures))?
+        .coerce_to_object(activation)?;
+
+    object.set_property(
+        object,
+        &QName::new(Namespace::public(), "index"),
+        (regexp_match.range().start as u32).into(),
+        activation,
+    )?;
+    object.set_property(
+        object,
+        &QName::new(Namespace::public(), "input"),
+        input.into(),
+        activation,
+    )?;
+    for (name, index) in group_names {
+        let value = captures.get(*index).cloned().unwrap_or(Value::Undefined);
+        object.set_property(
+            object,
+            &QName::new(
+                Namespace::public(),
+                AvmString::new(activation.context.gc_context, name.clone()),
+            ),
+            value,
+            activation,
+        )?;
+    }
+
+    Ok(object.into())
+}
+
+/// Implements `RegExp.exec`.
+pub fn exec<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = match this {
+        Some(this) => this,
+        None => return Ok(Value::Null),
+    };
+    let input = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let text: Vec<u16> = input.encode_utf16().collect();
+
+    let (regexp_match, group_names) = match this.as_regexp_mut(activation.context.gc_context) {
+        Some(mut regexp) => (regexp.exec(&text), regexp.group_names().to_vec()),
+        None => return Ok(Value::Null),
+    };
+
+    match regexp_match {
+        Some(regexp_match) => {
+            build_exec_result(activation, &text, input, &regexp_match, &group_names)
+        }
+        None => Ok(Value::Null),
+    }
+}
+
+/// Implements `RegExp.test`.
+pub fn test<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = match this {
+        Some(this) => this,
+        None => return Ok(false.into()),
+    };
+    let input = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let text: Vec<u16> = input.encode_utf16().collect();
+
+    let matched = match this.as_regexp_mut(activation.context.gc_context) {
+        Some(mut regexp) => regexp.exec(&text).is_some(),
+        None => false,
+    };
+
+    Ok(matched.into())
+}
+
+/// Implements `RegExp.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(regexp) = this.as_regexp() {
+            let mut string = format!("/{}/", regexp.source());
+            let flags = regexp.flags();
+            for &(flag, c) in &[
+                (RegExpFlags::GLOBAL, 'g'),
+                (RegExpFlags::IGNORE_CASE, 'i'),
+                (RegExpFlags::MULTILINE, 'm'),
+                (RegExpFlags::DOTALL, 's'),
+                (RegExpFlags::EXTENDED, 'x'),
+            ] {
+                if flags.contains(flag) {
+                    string.push(c);
+                }
+            }
+
+            return Ok(AvmString::new(activation.context.gc_context, string).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `RegExp`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::public(), "RegExp"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    class.write(mc).define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "source"),
+        Method::from_builtin(source),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "global"),
+        Method::from_builtin(global),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "ignoreCase"),
+        Method::from_builtin(ignore_case),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "multiline"),
+        Method::from_builtin(multiline),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "dotall"),
+        Method::from_builtin(dotall),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "extended"),
+        Method::from_builtin(extended),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "lastIndex"),
+        Method::from_builtin(last_index),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "lastIndex"),
+        Method::from_builtin(set_last_index),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "exec"),
+        Method::from_builtin(exec),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "test"),
+        Method::from_builtin(test),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    class
+}
+
+/// Object deriver for `RegExp`
+pub fn regexp_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    Ok(RegExpObject::derive(
+        base_proto,
+        activation.context.gc_context,
+        class,
+        scope,
+    ))
+}
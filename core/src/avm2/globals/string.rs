@@ -1,20 +1,27 @@
 //! `String` impl
 
 use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
+use crate::avm2::globals::array::build_array;
+use crate::avm2::globals::regexp::build_exec_result;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, RegExpObject, TObject};
+use crate::avm2::regexp::{RegExp, RegExpFlags, RegExpMatch};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use gc_arena::{GcCell, MutationContext};
+use std::cmp::{max, min, Ordering};
 
 /// Implements `String`'s instance initializer.
 pub fn instance_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Err("String constructor is a stub.".into())
 }
 
@@ -23,17 +30,804 @@ pub fn class_init<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     Ok(Value::Undefined)
 }
 
+/// Resolve the string a method was called on.
+///
+/// String methods are called on boxed strings, so this unboxes them. The
+/// `String` prototype itself holds no string, and acts as the empty string.
+fn this_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+) -> Result<AvmString<'gc>, Error<'gc>> {
+    match this {
+        Some(this) => match this.value_of(activation.context.gc_context)? {
+            Value::Undefined => Ok("".into()),
+            value => value.coerce_to_string(activation),
+        },
+        None => Ok("".into()),
+    }
+}
+
+/// Split a string into UTF-16 code units, which string indices count.
+fn utf16(string: &str) -> Vec<u16> {
+    string.encode_utf16().collect()
+}
+
+/// Build a string out of UTF-16 code units.
+fn from_utf16<'gc>(mc: MutationContext<'gc, '_>, units: &[u16]) -> Value<'gc> {
+    AvmString::new(mc, String::from_utf16_lossy(units)).into()
+}
+
+/// Coerce an optional argument to a string, defaulting to `"undefined"`.
+fn string_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<AvmString<'gc>, Error<'gc>> {
+    args.get(index)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)
+}
+
+/// Coerce an optional argument to an integer, as ECMAScript's `ToInteger`
+/// does, using a default if it's missing or undefined.
+fn integer_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+    default: f64,
+) -> Result<f64, Error<'gc>> {
+    let n = match args.get(index) {
+        None | Some(Value::Undefined) => return Ok(default),
+        Some(value) => value.coerce_to_number(activation)?,
+    };
+
+    Ok(if n.is_nan() { 0.0 } else { n.trunc() })
+}
+
+/// Clamp an index into a string of the given length, counting negative
+/// indices from the end if `from_end` is set.
+fn clamp_index(index: f64, len: usize, from_end: bool) -> usize {
+    let len_f = len as f64;
+    let index = if from_end && index < 0.0 {
+        index + len_f
+    } else {
+        index
+    };
+
+    if index <= 0.0 {
+        0
+    } else if index >= len_f {
+        len
+    } else {
+        index as usize
+    }
+}
+
+/// Find the first occurrence of `needle` at or after `start`.
+fn find(haystack: &[u16], needle: &[u16], start: usize) -> Option<usize> {
+    if needle.len() > haystack.len() {
+        return None;
+    }
+
+    (start..=haystack.len() - needle.len()).find(|&i| haystack[i..].starts_with(needle))
+}
+
+/// Implements `String.length`.
+pub fn length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this_string(activation, this)?;
+
+    Ok(this.encode_utf16().count().into())
+}
+
+/// Implements `String.charAt`.
+pub fn char_at<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = utf16(&this_string(activation, this)?);
+    let index = integer_arg(activation, args, 0, 0.0)?;
+
+    if index >= 0.0 && index < this.len() as f64 {
+        let index = index as usize;
+        return Ok(from_utf16(
+            activation.context.gc_context,
+            &this[index..index + 1],
+        ));
+    }
+
+    Ok("".into())
+}
+
+/// Implements `String.charCodeAt`.
+pub fn char_code_at<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = utf16(&this_string(activation, this)?);
+    let index = integer_arg(activation, args, 0, 0.0)?;
+
+    if index >= 0.0 && index < this.len() as f64 {
+        return Ok(this[index as usize].into());
+    }
+
+    Ok(f64::NAN.into())
+}
+
+/// Implements `String.concat`.
+pub fn concat<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut result = this_string(activation, this)?.to_string();
+    for arg in args {
+        result.push_str(&arg.coerce_to_string(activation)?);
+    }
+
+    Ok(AvmString::new(activation.context.gc_context, result).into())
+}
+
+/// Implements `String.fromCharCode`.
+pub fn from_char_code<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut units = Vec::with_capacity(args.len());
+    for arg in args {
+        units.push(arg.coerce_to_u32(activation)? as u16);
+    }
+
+    Ok(from_utf16(activation.context.gc_context, &units))
+}
+
+/// Implements `String.indexOf`.
+pub fn index_of<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = utf16(&this_string(activation, this)?);
+    let needle = utf16(&string_arg(activation, args, 0)?);
+    let start = clamp_index(integer_arg(activation, args, 1, 0.0)?, this.len(), false);
+
+    Ok(find(&this, &needle, start)
+        .map(|i| i as i32)
+        .unwrap_or(-1)
+        .into())
+}
+
+/// Implements `String.lastIndexOf`.
+pub fn last_index_of<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = utf16(&this_string(activation, this)?);
+    let needle = utf16(&string_arg(activation, args, 0)?);
+    let start = clamp_index(
+        integer_arg(activation, args, 1, f64::from(i32::MAX))?,
+        this.len(),
+        false,
+    );
+
+    if needle.len() > this.len() {
+        return Ok((-1).into());
+    }
+
+    let start = min(start, this.len() - needle.len());
+    Ok((0..=start)
+        .rev()
+        .find(|&i| this[i..].starts_with(&needle))
+        .map(|i| i as i32)
+        .unwrap_or(-1)
+        .into())
+}
+
+/// Implements `String.localeCompare`.
+pub fn locale_compare<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = utf16(&this_string(activation, this)?);
+    let other = utf16(&string_arg(activation, args, 0)?);
+
+    Ok(match this.cmp(&other) {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+    .into())
+}
+
+/// Coerce a pattern to a `RegExp` object, as `String.match` and
+/// `String.search` do with patterns that aren't one already.
+fn coerce_to_regexp<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    pattern: Value<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    if let Value::Object(object) = pattern {
+        if object.as_regexp().is_some() {
+            return Ok(object);
+        }
+    }
+
+    let source = match pattern {
+        Value::Undefined => "".into(),
+        pattern => pattern.coerce_to_string(activation)?,
+    };
+    let proto = activation.avm2().prototypes().regexp;
+
+    Ok(RegExpObject::from_regexp(
+        activation.context.gc_context,
+        Some(proto),
+        RegExp::new(&source, RegExpFlags::empty()),
+    ))
+}
+
+/// Find every match of a regular expression, from the start of the text.
+///
+/// A match of the empty string moves the search one code unit further, so
+/// that it isn't found again.
+fn find_all(regexp: &RegExp, text: &[u16]) -> Vec<RegExpMatch> {
+    let mut matches = Vec::new();
+    let mut start = 0;
+    while let Some(regexp_match) = regexp.find_at(text, start) {
+        let range = regexp_match.range();
+        start = if range.is_empty() {
+            range.end + 1
+        } else {
+            range.end
+        };
+        matches.push(regexp_match);
+    }
+
+    matches
+}
+
+/// Implements `String.match`.
+pub fn match_<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let input = this_string(activation, this)?;
+    let text = utf16(&input);
+    let pattern = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let regexp_object = coerce_to_regexp(activation, pattern)?;
+
+    let (global, matches, group_names) = match regexp_object.as_regexp() {
+        Some(regexp) if regexp.flags().contains(RegExpFlags::GLOBAL) => {
+            (true, find_all(&regexp, &text), Vec::new())
+        }
+        Some(regexp) => (
+            false,
+            regexp.find_at(&text, 0).into_iter().collect(),
+            regexp.group_names().to_vec(),
+        ),
+        None => return Ok(Value::Null),
+    };
+
+    if !global {
+        return match matches.first() {
+            Some(regexp_match) => {
+                build_exec_result(activation, &text, input, regexp_match, &group_names)
+            }
+            None => Ok(Value::Null),
+        };
+    }
+
+    if let Some(mut regexp) = regexp_object.as_regexp_mut(activation.context.gc_context) {
+        regexp.set_last_index(0);
+    }
+
+    let mc = activation.context.gc_context;
+    let matched: Vec<Value<'gc>> = matches
+        .iter()
+        .map(|regexp_match| from_utf16(mc, &text[regexp_match.range()]))
+        .collect();
+
+    build_array(activation, ArrayStorage::from_args(&matched))
+}
+
+/// Append the replacement for a match to `output`, expanding the `$`
+/// patterns in it.
+fn expand_replacement(
+    output: &mut Vec<u16>,
+    replacement: &[u16],
+    text: &[u16],
+    regexp_match: &RegExpMatch,
+) {
+    let range = regexp_match.range();
+    let group_count = regexp_match.captures.len() - 1;
+    let digit = |c: Option<&u16>| match c {
+        Some(&c) if (u16::from(b'0')..=u16::from(b'9')).contains(&c) => {
+            Some(usize::from(c - u16::from(b'0')))
+        }
+        _ => None,
+    };
+
+    let mut i = 0;
+    while i < replacement.len() {
+        let c = replacement[i];
+        if c != u16::from(b'$') || i + 1 == replacement.len() {
+            output.push(c);
+            i += 1;
+            continue;
+        }
+
+        let next = replacement[i + 1];
+        let first_digit = digit(replacement.get(i + 1));
+        let second_digit = digit(replacement.get(i + 2));
+        if next == u16::from(b'$') {
+            output.push(next);
+            i += 2;
+        } else if next == u16::from(b'&') {
+            output.extend_from_slice(&text[range.clone()]);
+            i += 2;
+        } else if next == u16::from(b'`') {
+            output.extend_from_slice(&text[..range.start]);
+            i += 2;
+        } else if next == u16::from(b'\'') {
+            output.extend_from_slice(&text[range.end..]);
+            i += 2;
+        } else if let Some(first) = first_digit {
+            // Two digit group numbers take precedence, if there are enough
+            // groups for them to refer to.
+            let (group, length) = match second_digit {
+                Some(second) if (1..=group_count).contains(&(first * 10 + second)) => {
+                    (first * 10 + second, 3)
+                }
+                _ => (first, 2),
+            };
+
+            if (1..=group_count).contains(&group) {
+                if let Some(capture) = &regexp_match.captures[group] {
+                    output.extend_from_slice(&text[capture.clone()]);
+                }
+                i += length;
+            } else {
+                output.push(c);
+                i += 1;
+            }
+        } else {
+            output.push(c);
+            i += 1;
+        }
+    }
+}
+
+/// Implements `String.replace`.
+pub fn replace<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let input = this_string(activation, this)?;
+    let text = utf16(&input);
+    let pattern = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let replacement = args.get(1).cloned().unwrap_or(Value::Undefined);
+
+    let regexp_object = match pattern {
+        Value::Object(object) if object.as_regexp().is_some() => Some(object),
+        _ => None,
+    };
+
+    let matches = match regexp_object {
+        Some(regexp_object) => {
+            let matches = match regexp_object.as_regexp() {
+                Some(regexp) if regexp.flags().contains(RegExpFlags::GLOBAL) => {
+                    find_all(&regexp, &text)
+                }
+                Some(regexp) => regexp.find_at(&text, 0).into_iter().collect(),
+                None => Vec::new(),
+            };
+
+            if let Some(mut regexp) = regexp_object.as_regexp_mut(activation.context.gc_context) {
+                if regexp.flags().contains(RegExpFlags::GLOBAL) {
+                    regexp.set_last_index(0);
+                }
+            }
+
+            matches
+        }
+        None => {
+            // Strings are searched for literally, and only replaced once.
+            let needle = utf16(&pattern.coerce_to_string(activation)?);
+            find(&text, &needle, 0)
+                .map(|start| RegExpMatch {
+                    captures: vec![Some(start..start + needle.len())],
+                })
+                .into_iter()
+                .collect()
+        }
+    };
+
+    let function = match replacement {
+        Value::Object(object) if object.as_executable().is_some() => Some(object),
+        _ => None,
+    };
+    let replacement = match function {
+        Some(_) => Vec::new(),
+        None => utf16(&replacement.coerce_to_string(activation)?),
+    };
+
+    let mut output = Vec::with_capacity(text.len());
+    let mut last_end = 0;
+    for regexp_match in &matches {
+        let range = regexp_match.range();
+        output.extend_from_slice(&text[last_end..range.start]);
+        last_end = range.end;
+
+        match function {
+            Some(function) => {
+                // The function is called with the match, each group, the
+                // index of the match and the whole string.
+                let mc = activation.context.gc_context;
+                let mut args: Vec<Value<'gc>> = regexp_match
+                    .captures
+                    .iter()
+                    .map(|capture| match capture {
+                        Some(capture) => from_utf16(mc, &text[capture.clone()]),
+                        None => Value::Undefined,
+                    })
+                    .collect();
+                args.push(range.start.into());
+                args.push(input.into());
+
+                let result = function
+                    .call(None, &args, activation, None)?
+                    .coerce_to_string(activation)?;
+                output.extend(result.encode_utf16());
+            }
+            None => expand_replacement(&mut output, &replacement, &text, regexp_match),
+        }
+    }
+    output.extend_from_slice(&text[last_end..]);
+
+    Ok(from_utf16(activation.context.gc_context, &output))
+}
+
+/// Implements `String.search`.
+pub fn search<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let text = utf16(&this_string(activation, this)?);
+    let pattern = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let regexp_object = coerce_to_regexp(activation, pattern)?;
+
+    let index = regexp_object
+        .as_regexp()
+        .and_then(|regexp| regexp.find_at(&text, 0))
+        .map(|regexp_match| regexp_match.range().start as i32)
+        .unwrap_or(-1);
+
+    Ok(index.into())
+}
+
+/// Implements `String.slice`.
+pub fn slice<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = utf16(&this_string(activation, this)?);
+    let start = clamp_index(integer_arg(activation, args, 0, 0.0)?, this.len(), true);
+    let end = clamp_index(
+        integer_arg(activation, args, 1, f64::from(i32::MAX))?,
+        this.len(),
+        true,
+    );
+
+    if start >= end {
+        return Ok("".into());
+    }
+
+    Ok(from_utf16(activation.context.gc_context, &this[start..end]))
+}
+
+/// Split text by a regular expression, including the groups of each match
+/// in the result, as ECMAScript's `String.prototype.split` does.
+fn split_regexp(regexp: &RegExp, text: &[u16], limit: usize) -> Vec<Vec<u16>> {
+    let mut pieces = Vec::new();
+    if text.is_empty() {
+        if regexp.find_at(text, 0).is_none() {
+            pieces.push(Vec::new());
+        }
+        return pieces;
+    }
+
+    let mut start = 0;
+    let mut search = 0;
+    while search < text.len() {
+        let regexp_match = match regexp.find_at(text, search) {
+            Some(regexp_match) => regexp_match,
+            None => break,
+        };
+        let range = regexp_match.range();
+        if range.start >= text.len() {
+            break;
+        }
+        if range.end == start {
+            // An empty match where the last piece ended doesn't split.
+            search = range.start + 1;
+            continue;
+        }
+
+        pieces.push(text[start..range.start].to_vec());
+        if pieces.len() == limit {
+            return pieces;
+        }
+
+        for capture in &regexp_match.captures[1..] {
+            pieces.push(
+                capture
+                    .clone()
+                    .map(|capture| text[capture].to_vec())
+                    .unwrap_or_default(),
+            );
+            if pieces.len() == limit {
+                return pieces;
+            }
+        }
+
+        start = range.end;
+        search = start;
+    }
+
+    pieces.push(text[start..].to_vec());
+    pieces
+}
+
+/// Implements `String.split`.
+pub fn split<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let input = this_string(activation, this)?;
+    let text = utf16(&input);
+    let delimiter = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let limit = match args.get(1) {
+        None | Some(Value::Undefined) => usize::MAX,
+        Some(limit) => limit.coerce_to_u32(activation)? as usize,
+    };
+
+    let regexp = match &delimiter {
+        Value::Object(object) => object.as_regexp().map(|regexp| RegExp::clone(&regexp)),
+        _ => None,
+    };
+
+    let pieces = if limit == 0 {
+        Vec::new()
+    } else if delimiter == Value::Undefined {
+        vec![text]
+    } else if let Some(regexp) = regexp {
+        split_regexp(&regexp, &text, limit)
+    } else {
+        let delimiter = utf16(&delimiter.coerce_to_string(activation)?);
+        if delimiter.is_empty() {
+            text.iter().take(limit).map(|&c| vec![c]).collect()
+        } else {
+            let mut pieces = Vec::new();
+            let mut start = 0;
+            while let Some(end) = find(&text, &delimiter, start) {
+                if pieces.len() == limit {
+                    break;
+                }
+                pieces.push(text[start..end].to_vec());
+                start = end + delimiter.len();
+            }
+            if pieces.len() < limit {
+                pieces.push(text[start..].to_vec());
+            }
+            pieces
+        }
+    };
+
+    let mc = activation.context.gc_context;
+    let pieces: Vec<Value<'gc>> = pieces.iter().map(|piece| from_utf16(mc, piece)).collect();
+
+    build_array(activation, ArrayStorage::from_args(&pieces))
+}
+
+/// Implements `String.substr`.
+pub fn substr<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = utf16(&this_string(activation, this)?);
+    let start = clamp_index(integer_arg(activation, args, 0, 0.0)?, this.len(), true);
+    let length = integer_arg(activation, args, 1, f64::from(i32::MAX))?;
+    let end = clamp_index(start as f64 + length, this.len(), false);
+
+    if start >= end {
+        return Ok("".into());
+    }
+
+    Ok(from_utf16(activation.context.gc_context, &this[start..end]))
+}
+
+/// Implements `String.substring`.
+pub fn substring<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = utf16(&this_string(activation, this)?);
+    let start = clamp_index(integer_arg(activation, args, 0, 0.0)?, this.len(), false);
+    let end = clamp_index(
+        integer_arg(activation, args, 1, f64::from(i32::MAX))?,
+        this.len(),
+        false,
+    );
+
+    Ok(from_utf16(
+        activation.context.gc_context,
+        &this[min(start, end)..max(start, end)],
+    ))
+}
+
+/// Implements `String.toLowerCase`.
+pub fn to_lower_case<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this_string(activation, this)?;
+
+    Ok(AvmString::new(activation.context.gc_context, this.to_lowercase()).into())
+}
+
+/// Implements `String.toUpperCase`.
+pub fn to_upper_case<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this_string(activation, this)?;
+
+    Ok(AvmString::new(activation.context.gc_context, this.to_uppercase()).into())
+}
+
+/// Implements `String.toString` and `String.valueOf`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this_string(activation, this)?.into())
+}
+
 /// Construct `String`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
-    Class::new(
+    let class = Class::new(
         QName::new(Namespace::public(), "String"),
         Some(QName::new(Namespace::public(), "Object").into()),
         Method::from_builtin(instance_init),
         Method::from_builtin(class_init),
         mc,
-    )
+    );
+
+    class.write(mc).define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "length"),
+        Method::from_builtin(length),
+    ));
+
+    class.write(mc).define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "fromCharCode"),
+        Method::from_builtin(from_char_code),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "charAt"),
+        Method::from_builtin(char_at),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "charCodeAt"),
+        Method::from_builtin(char_code_at),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "concat"),
+        Method::from_builtin(concat),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "indexOf"),
+        Method::from_builtin(index_of),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "lastIndexOf"),
+        Method::from_builtin(last_index_of),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "localeCompare"),
+        Method::from_builtin(locale_compare),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "match"),
+        Method::from_builtin(match_),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "replace"),
+        Method::from_builtin(replace),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "search"),
+        Method::from_builtin(search),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "slice"),
+        Method::from_builtin(slice),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "split"),
+        Method::from_builtin(split),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "substr"),
+        Method::from_builtin(substr),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "substring"),
+        Method::from_builtin(substring),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "toLowerCase"),
+        Method::from_builtin(to_lower_case),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "toLocaleLowerCase"),
+        Method::from_builtin(to_lower_case),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "toUpperCase"),
+        Method::from_builtin(to_upper_case),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "toLocaleUpperCase"),
+        Method::from_builtin(to_upper_case),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "valueOf"),
+        Method::from_builtin(to_string),
+    ));
+
+    class
 }
@@ -0,0 +1,899 @@
+//! `Vector` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::array::{build_array, resolve_index, sort_values, SortOptions};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{FunctionObject, Object, TObject, VectorObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::vector::VectorStorage;
+use crate::avm2::{Error, ErrorKind};
+use gc_arena::{GcCell, MutationContext};
+use std::cmp::min;
+
+/// The package that `Vector` and its specializations live in.
+const VECTOR_PACKAGE: &str = "__AS3__.vec";
+
+/// Implements `Vector`'s instance initializer.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        let length = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_number(activation)?;
+        if length < 0.0 {
+            return Err(Error::new(
+                ErrorKind::RangeError,
+                format!(
+                    "Error #1005: Array index is not a positive integer ({}).",
+                    Value::from(length).coerce_to_string(activation)?
+                ),
+            ));
+        }
+        let is_fixed = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+
+        if let Some(mut vector) = this.as_vector_storage_mut(activation.context.gc_context) {
+            vector.set_length(length as usize)?;
+            vector.set_is_fixed(is_fixed);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements the instance initializer of `Vector`'s specializations.
+///
+/// The vector itself is initialized by `Vector`'s initializer.
+pub fn specialized_instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        activation.super_init(this, args)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Apply type parameters to a parameterized type, as `Vector.<T>` does.
+///
+/// `Vector` is the only parameterized type, and takes one parameter: the
+/// class of its values, or `null` for `Vector.<*>`. Each specialization is
+/// only created once, so that every `Vector.<T>` is the same class.
+pub fn apply_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut base_type: Object<'gc>,
+    params: &[Value<'gc>],
+) -> Result<Object<'gc>, Error<'gc>> {
+    let base_proto = base_type
+        .get_property(
+            base_type,
+            &QName::new(Namespace::public(), "prototype"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    if !Object::ptr_eq(base_proto, activation.avm2().prototypes().vector) {
+        return Err(Error::new(
+            ErrorKind::TypeError,
+            "Error #1127: Type application attempted on a non-parameterized type.",
+        ));
+    }
+
+    if params.len() != 1 {
+        return Err(Error::new(
+            ErrorKind::TypeError,
+            format!(
+                "Error #1128: Incorrect number of type parameters for Vector. Expected 1, got {}.",
+                params.len()
+            ),
+        ));
+    }
+
+    let value_type = match &params[0] {
+        Value::Undefined | Value::Null => None,
+        param => Some(param.coerce_to_object(activation)?),
+    };
+
+    let existing = activation
+        .context
+        .avm2
+        .vector_classes
+        .iter()
+        .find(|(class_type, _)| match (class_type, value_type) {
+            (Some(a), Some(b)) => Object::ptr_eq(*a, b),
+            (None, None) => true,
+            _ => false,
+        });
+    if let Some((_, class)) = existing {
+        return Ok(*class);
+    }
+
+    let mc = activation.context.gc_context;
+    let type_name = match value_type.and_then(|t| t.as_class()) {
+        Some(class) => class.read().name().local_name().to_string(),
+        None => "*".to_string(),
+    };
+    let class = Class::new(
+        QName::new(
+            Namespace::package(VECTOR_PACKAGE),
+            AvmString::new(mc, format!("Vector.<{}>", type_name)),
+        ),
+        Some(QName::new(Namespace::package(VECTOR_PACKAGE), "Vector").into()),
+        Method::from_builtin(specialized_instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+    class
+        .write(mc)
+        .set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    let default_value = VectorStorage::default_value(activation, value_type)?;
+    let scope = base_type.get_scope();
+    let (specialized, _cinit) = FunctionObject::from_class_with_deriver(
+        activation,
+        class,
+        Some(base_type),
+        scope,
+        |base_proto, activation, class, scope| {
+            VectorObject::derive(
+                base_proto,
+                activation.context.gc_context,
+                class,
+                scope,
+                VectorStorage::new(0, false, value_type, default_value),
+            )
+        },
+    )?;
+
+    activation
+        .context
+        .avm2
+        .vector_classes
+        .push((value_type, specialized));
+
+    Ok(specialized)
+}
+
+/// Bundle vector storage in an object of the same class as another vector.
+fn build_vector<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    vector: VectorStorage<'gc>,
+) -> Value<'gc> {
+    let proto = this
+        .proto()
+        .unwrap_or_else(|| activation.avm2().prototypes().vector);
+
+    VectorObject::from_vector(vector, proto, activation.context.gc_context).into()
+}
+
+/// Coerce a list of values to the value type of a vector.
+fn coerce_values<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    values: &[Value<'gc>],
+) -> Result<Vec<Value<'gc>>, Error<'gc>> {
+    let value_type = this.as_vector_storage().and_then(|v| v.value_type());
+
+    values
+        .iter()
+        .map(|value| VectorStorage::coerce(activation, value_type, value.clone()))
+        .collect()
+}
+
+/// Get a value in a vector that may have been changed by user code, yielding
+/// `None` once the index is past the end of it.
+fn get_live<'gc>(this: Object<'gc>, index: usize) -> Option<Value<'gc>> {
+    this.as_vector_storage()
+        .and_then(|vector| vector.get(index).ok())
+}
+
+/// Implements `Vector.length`'s getter.
+pub fn length<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(vector) = this.as_vector_storage() {
+            return Ok(vector.length().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.length`'s setter.
+pub fn set_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let length = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+
+        if let Some(mut vector) = this.as_vector_storage_mut(activation.context.gc_context) {
+            vector.set_length(length as usize)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.fixed`'s getter.
+pub fn fixed<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(vector) = this.as_vector_storage() {
+            return Ok(vector.is_fixed().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.fixed`'s setter.
+pub fn set_fixed<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let is_fixed = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        if let Some(mut vector) = this.as_vector_storage_mut(activation.context.gc_context) {
+            vector.set_is_fixed(is_fixed);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.concat`
+pub fn concat<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (mut values, value_type) = match this.as_vector_storage() {
+            Some(vector) => (vector.iter().collect::<Vec<_>>(), vector.value_type()),
+            None => return Ok(Value::Undefined),
+        };
+
+        for arg in args {
+            let other = arg.coerce_to_object(activation)?;
+            let other_values = match other.as_vector_storage() {
+                Some(other) => other.iter().collect::<Vec<_>>(),
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::TypeError,
+                        format!(
+                            "Error #1034: Type Coercion failed: cannot convert {} to Vector.",
+                            arg.type_of()
+                        ),
+                    ))
+                }
+            };
+
+            for value in other_values {
+                values.push(VectorStorage::coerce(activation, value_type, value)?);
+            }
+        }
+
+        let vector = this.as_vector_storage().unwrap().with_values(values);
+
+        return Ok(build_vector(activation, this, vector));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.join`
+pub fn join<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let values = match this.as_vector_storage() {
+            Some(vector) => vector.iter().collect::<Vec<_>>(),
+            None => return Ok(Value::Undefined),
+        };
+
+        let separator = match args.get(0) {
+            None | Some(Value::Undefined) => ",".into(),
+            Some(separator) => separator.coerce_to_string(activation)?,
+        };
+
+        let mut accum = Vec::with_capacity(values.len());
+        for value in values {
+            accum.push(value.coerce_to_string(activation)?.to_string());
+        }
+
+        return Ok(AvmString::new(activation.context.gc_context, accum.join(&separator)).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.toString`
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    join(activation, this, &[",".into()])
+}
+
+/// Call a callback on every value in a vector, as the iteration methods do.
+///
+/// `each` is handed each value and the callback's result for it, and stops
+/// the iteration by returning `false`. Callbacks are allowed to change the
+/// vector as it is iterated.
+fn iterate<'a, 'gc, 'ctxt, F>(
+    activation: &mut Activation<'a, 'gc, 'ctxt>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+    mut each: F,
+) -> Result<(), Error<'gc>>
+where
+    F: FnMut(&mut Activation<'a, 'gc, 'ctxt>, Value<'gc>, Value<'gc>) -> Result<bool, Error<'gc>>,
+{
+    let callback = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+    let receiver = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Null)
+        .coerce_to_object(activation)
+        .ok();
+
+    let mut i = 0;
+    while let Some(item) = get_live(this, i) {
+        let result = callback.call(
+            receiver,
+            &[item.clone(), i.into(), this.into()],
+            activation,
+            receiver.and_then(|r| r.proto()),
+        )?;
+
+        if !each(activation, item, result)? {
+            break;
+        }
+
+        i += 1;
+    }
+
+    Ok(())
+}
+
+/// Implements `Vector.every`
+pub fn every<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let mut is_every = true;
+        iterate(activation, this, args, |_activation, _item, result| {
+            is_every = result.coerce_to_boolean();
+            Ok(is_every)
+        })?;
+
+        return Ok(is_every.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.some`
+pub fn some<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let mut is_some = false;
+        iterate(activation, this, args, |_activation, _item, result| {
+            is_some = result.coerce_to_boolean();
+            Ok(!is_some)
+        })?;
+
+        return Ok(is_some.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.forEach`
+pub fn for_each<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        iterate(activation, this, args, |_activation, _item, _result| {
+            Ok(true)
+        })?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.filter`
+pub fn filter<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let mut values = Vec::new();
+        iterate(activation, this, args, |_activation, item, result| {
+            if result.coerce_to_boolean() {
+                values.push(item);
+            }
+
+            Ok(true)
+        })?;
+
+        if let Some(vector) = this.as_vector_storage().map(|v| v.with_values(values)) {
+            return Ok(build_vector(activation, this, vector));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.map`
+pub fn map<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let value_type = this.as_vector_storage().and_then(|v| v.value_type());
+        let mut values = Vec::new();
+        iterate(activation, this, args, |activation, _item, result| {
+            values.push(VectorStorage::coerce(activation, value_type, result)?);
+
+            Ok(true)
+        })?;
+
+        if let Some(vector) = this.as_vector_storage().map(|v| v.with_values(values)) {
+            return Ok(build_vector(activation, this, vector));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.indexOf`
+pub fn index_of<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let length = match this.as_vector_storage() {
+            Some(vector) => vector.length(),
+            None => return Ok(Value::Undefined),
+        };
+        let search_val = args.get(0).cloned().unwrap_or(Value::Undefined);
+        let from = resolve_index(
+            activation,
+            args.get(1).cloned().unwrap_or_else(|| 0.into()),
+            length,
+        )?;
+
+        let vector = this.as_vector_storage().unwrap();
+        for (i, val) in vector.iter().enumerate().skip(from) {
+            if val == search_val {
+                return Ok(i.into());
+            }
+        }
+
+        return Ok((-1).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.lastIndexOf`
+pub fn last_index_of<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let length = match this.as_vector_storage() {
+            Some(vector) => vector.length(),
+            None => return Ok(Value::Undefined),
+        };
+        let search_val = args.get(0).cloned().unwrap_or(Value::Undefined);
+        let from = resolve_index(
+            activation,
+            args.get(1).cloned().unwrap_or_else(|| i32::MAX.into()),
+            length,
+        )?;
+
+        let vector = this.as_vector_storage().unwrap();
+        for (i, val) in vector.iter().enumerate().rev() {
+            if i <= from && val == search_val {
+                return Ok(i.into());
+            }
+        }
+
+        return Ok((-1).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.pop`
+pub fn pop<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut vector) = this.as_vector_storage_mut(activation.context.gc_context) {
+            return vector.pop();
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.push`
+pub fn push<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let values = coerce_values(activation, this, args)?;
+
+        if let Some(mut vector) = this.as_vector_storage_mut(activation.context.gc_context) {
+            for value in values {
+                vector.push(value)?;
+            }
+
+            return Ok(vector.length().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.reverse`
+pub fn reverse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut vector) = this.as_vector_storage_mut(activation.context.gc_context) {
+            vector.reverse();
+
+            return Ok(this.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.shift`
+pub fn shift<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        if let Some(mut vector) = this.as_vector_storage_mut(activation.context.gc_context) {
+            return vector.shift();
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.unshift`
+pub fn unshift<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let values = coerce_values(activation, this, args)?;
+
+        if let Some(mut vector) = this.as_vector_storage_mut(activation.context.gc_context) {
+            for value in values.into_iter().rev() {
+                vector.unshift(value)?;
+            }
+
+            return Ok(vector.length().into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.slice`
+pub fn slice<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let length = match this.as_vector_storage() {
+            Some(vector) => vector.length(),
+            None => return Ok(Value::Undefined),
+        };
+        let start = resolve_index(
+            activation,
+            args.get(0).cloned().unwrap_or_else(|| 0.into()),
+            length,
+        )?;
+        let end = resolve_index(
+            activation,
+            args.get(1).cloned().unwrap_or_else(|| i32::MAX.into()),
+            length,
+        )?;
+
+        let vector = this.as_vector_storage().unwrap();
+        let values = vector.iter().take(min(end, length)).skip(start).collect();
+        let vector = vector.with_values(values);
+
+        return Ok(build_vector(activation, this, vector));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.splice`
+pub fn splice<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let length = match this.as_vector_storage() {
+            Some(vector) => vector.length(),
+            None => return Ok(Value::Undefined),
+        };
+        let start = min(
+            resolve_index(
+                activation,
+                args.get(0).cloned().unwrap_or_else(|| 0.into()),
+                length,
+            )?,
+            length,
+        );
+        let delete_count = args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| length.into())
+            .coerce_to_u32(activation)? as usize;
+        let end = min(length, start.saturating_add(delete_count));
+        let items = coerce_values(activation, this, args.get(2..).unwrap_or(&[]))?;
+
+        let mut vector = this
+            .as_vector_storage_mut(activation.context.gc_context)
+            .unwrap();
+        let removed = vector.splice(start..end, items)?;
+        let removed = vector.with_values(removed);
+        drop(vector);
+
+        return Ok(build_vector(activation, this, removed));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector.sort`
+pub fn sort<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        let (compare_fnc, options) = match args.get(0) {
+            Some(Value::Object(f)) if f.as_executable().is_some() => {
+                (Some(*f), SortOptions::empty())
+            }
+            Some(options) => (
+                None,
+                SortOptions::from_bits_truncate(options.coerce_to_u32(activation)? as u8),
+            ),
+            None => (None, SortOptions::empty()),
+        };
+
+        let mut values = match this.as_vector_storage() {
+            Some(vector) => vector.iter().enumerate().collect::<Vec<_>>(),
+            None => return Ok(Value::Undefined),
+        };
+
+        if !sort_values(activation, &mut values, compare_fnc, options)? {
+            return Ok(0.into());
+        }
+
+        if options.contains(SortOptions::RETURN_INDEXED_ARRAY) {
+            let indices: Vec<Value<'gc>> = values.iter().map(|(i, _v)| (*i).into()).collect();
+
+            return build_array(activation, ArrayStorage::from_args(&indices));
+        }
+
+        if let Some(mut vector) = this.as_vector_storage_mut(activation.context.gc_context) {
+            vector.replace(values.into_iter().map(|(_i, v)| v).collect());
+        }
+
+        return Ok(this.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Vector`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package(VECTOR_PACKAGE), "Vector"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "length"),
+        Method::from_builtin(length),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "length"),
+        Method::from_builtin(set_length),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "fixed"),
+        Method::from_builtin(fixed),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "fixed"),
+        Method::from_builtin(set_fixed),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "concat"),
+        Method::from_builtin(concat),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "join"),
+        Method::from_builtin(join),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "every"),
+        Method::from_builtin(every),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "some"),
+        Method::from_builtin(some),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "forEach"),
+        Method::from_builtin(for_each),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "filter"),
+        Method::from_builtin(filter),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "map"),
+        Method::from_builtin(map),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "indexOf"),
+        Method::from_builtin(index_of),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "lastIndexOf"),
+        Method::from_builtin(last_index_of),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "pop"),
+        Method::from_builtin(pop),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "push"),
+        Method::from_builtin(push),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "reverse"),
+        Method::from_builtin(reverse),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "shift"),
+        Method::from_builtin(shift),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "unshift"),
+        Method::from_builtin(unshift),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "slice"),
+        Method::from_builtin(slice),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "splice"),
+        Method::from_builtin(splice),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "sort"),
+        Method::from_builtin(sort),
+    ));
+
+    drop(write);
+
+    class
+}
+
+/// Object deriver for `Vector`, whose instances hold values of any type.
+pub fn vector_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    VectorObject::derive(
+        base_proto,
+        activation.context.gc_context,
+        class,
+        scope,
+        VectorStorage::new(0, false, None, Value::Undefined),
+    )
+}
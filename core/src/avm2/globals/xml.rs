@@ -0,0 +1,1267 @@
+//! `XML` impl, and the E4X operators shared with `XMLList`
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::e4x::{
+    list_has_simple_content, list_string_value, list_xml_string, E4XName, E4XNode,
+};
+use crate::avm2::globals::qname::{as_qname, new_qname};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{NamespaceObject, Object, TObject, XmlListObject, XmlObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorKind};
+use gc_arena::{GcCell, MutationContext};
+
+/// The nodes held by an XML or XML list object.
+///
+/// `XML` objects are treated as a list of their one node. Any other object
+/// yields `None`.
+pub fn xml_nodes<'gc>(object: Object<'gc>) -> Option<Vec<E4XNode<'gc>>> {
+    if let Some(xml) = object.as_xml_object() {
+        Some(vec![xml.node()])
+    } else if let Some(list) = object.as_xml_list_object() {
+        Some(list.nodes())
+    } else {
+        None
+    }
+}
+
+/// Wrap a node in a new `XML` object.
+pub fn new_xml<'gc>(activation: &mut Activation<'_, 'gc, '_>, node: E4XNode<'gc>) -> Object<'gc> {
+    let proto = activation.context.avm2.prototypes().xml;
+
+    XmlObject::from_node(activation.context.gc_context, Some(proto), node)
+}
+
+/// Wrap a list of nodes in a new `XMLList` object.
+pub fn new_xml_list<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    nodes: Vec<E4XNode<'gc>>,
+) -> Object<'gc> {
+    let proto = activation.context.avm2.prototypes().xml_list;
+
+    XmlListObject::from_nodes(activation.context.gc_context, Some(proto), nodes)
+}
+
+/// Convert a value into a list of parentless nodes, as the `XML` and
+/// `XMLList` constructors do.
+///
+/// XML values are copied, and anything else is parsed as markup.
+pub fn parse_value<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<Vec<E4XNode<'gc>>, Error<'gc>> {
+    match value {
+        Value::Undefined | Value::Null => Ok(Vec::new()),
+        Value::Object(object) if xml_nodes(object).is_some() => Ok(xml_nodes(object)
+            .unwrap()
+            .into_iter()
+            .map(|node| node.deep_copy(activation.context.gc_context))
+            .collect()),
+        value => {
+            let markup = value.coerce_to_string(activation)?;
+            let settings = activation.context.avm2.xml_settings.clone();
+
+            E4XNode::parse(activation.context.gc_context, &markup, &settings)
+        }
+    }
+}
+
+/// Convert a value into nodes to be inserted into an XML tree.
+///
+/// XML values are used as-is, unless they already belong to a tree, in which
+/// case they are copied. Anything else becomes a text node.
+fn value_to_nodes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<Vec<E4XNode<'gc>>, Error<'gc>> {
+    let mc = activation.context.gc_context;
+
+    if let Value::Object(object) = &value {
+        if let Some(nodes) = xml_nodes(*object) {
+            return Ok(nodes
+                .into_iter()
+                .map(|node| match node.parent() {
+                    Some(_) => node.deep_copy(mc),
+                    None => node,
+                })
+                .collect());
+        }
+    }
+
+    let text = value.coerce_to_string(activation)?;
+
+    Ok(vec![E4XNode::new_text(mc, text.to_string())])
+}
+
+/// A name to look up XML children or attributes by.
+///
+/// A `local_name` or `uri` of `None` matches any name or namespace.
+struct E4XQuery {
+    local_name: Option<String>,
+    uri: Option<String>,
+    is_attribute: bool,
+}
+
+impl E4XQuery {
+    /// A query that matches every child.
+    fn any() -> Self {
+        Self {
+            local_name: None,
+            uri: None,
+            is_attribute: false,
+        }
+    }
+
+    /// Build a query from the name of a property access, such as `xml.item`
+    /// or `xml.@id`.
+    fn from_multiname<'gc>(
+        activation: &Activation<'_, 'gc, '_>,
+        multiname: &Multiname<'gc>,
+    ) -> Self {
+        let local_name = multiname
+            .local_name()
+            .filter(|name| &**name != "*")
+            .map(|name| name.to_string());
+        let explicit_uri = explicit_uri(multiname);
+        let uri = if multiname.namespace_set().any(|ns| ns.is_any()) {
+            None
+        } else if let Some(uri) = explicit_uri {
+            Some(uri.to_string())
+        } else if local_name.is_none() {
+            None
+        } else if multiname.is_attribute() {
+            Some("".to_string())
+        } else {
+            Some(default_namespace(activation))
+        };
+
+        Self {
+            local_name,
+            uri,
+            is_attribute: multiname.is_attribute(),
+        }
+    }
+
+    /// Build a query from a name passed to a method, such as
+    /// `xml.child("item")`, which may be a string or a `QName`.
+    fn from_value<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        value: &Value<'gc>,
+        is_attribute: bool,
+    ) -> Result<Self, Error<'gc>> {
+        let (local_name, uri) = match as_qname(activation, value)? {
+            Some((local_name, uri)) => (local_name, Some(uri.to_string())),
+            None => match value {
+                Value::Undefined => ("*".into(), None),
+                value => (value.clone().coerce_to_string(activation)?, None),
+            },
+        };
+
+        if &*local_name == "*" {
+            return Ok(Self {
+                local_name: None,
+                uri,
+                is_attribute,
+            });
+        }
+
+        let uri = match uri {
+            Some(uri) => uri,
+            None if is_attribute => "".to_string(),
+            None => default_namespace(activation),
+        };
+
+        Ok(Self {
+            local_name: Some(local_name.to_string()),
+            uri: Some(uri),
+            is_attribute,
+        })
+    }
+
+    fn matches(&self, node: E4XNode<'_>) -> bool {
+        if self.local_name.is_none() && self.uri.is_none() {
+            return true;
+        }
+
+        node.matches_name(self.local_name.as_deref(), self.uri.as_deref())
+    }
+
+    /// Collect the children, or attributes, of a list of nodes that match
+    /// this query.
+    fn select<'gc>(&self, nodes: &[E4XNode<'gc>]) -> Vec<E4XNode<'gc>> {
+        let mut selected = Vec::new();
+
+        for node in nodes {
+            let candidates = if self.is_attribute {
+                node.attributes()
+            } else {
+                node.children()
+            };
+
+            selected.extend(candidates.into_iter().filter(|child| self.matches(*child)));
+        }
+
+        selected
+    }
+
+    /// The name a new element or attribute matching this query would have.
+    fn to_name<'gc>(&self) -> Result<E4XName, Error<'gc>> {
+        match &self.local_name {
+            Some(local_name) => Ok(E4XName::new(
+                self.uri.clone().unwrap_or_default(),
+                local_name.clone(),
+            )),
+            None => Err("Cannot create an XML node with any name".into()),
+        }
+    }
+}
+
+/// The default XML namespace of the running code.
+fn default_namespace(activation: &Activation<'_, '_, '_>) -> String {
+    activation
+        .default_xml_namespace()
+        .map(|uri| uri.to_string())
+        .unwrap_or_default()
+}
+
+/// The namespace of a name qualified with a user namespace, as in
+/// `xml.ns::item`.
+fn explicit_uri<'gc>(multiname: &Multiname<'gc>) -> Option<AvmString<'gc>> {
+    let mut namespaces = multiname.namespace_set();
+
+    match (namespaces.next(), namespaces.next()) {
+        (Some(Namespace::Namespace(uri)), None) => Some(*uri),
+        _ => None,
+    }
+}
+
+/// Determine if a name can name the children or attributes of an XML
+/// object.
+fn is_e4x_name(multiname: &Multiname<'_>) -> bool {
+    multiname.is_attribute()
+        || multiname.includes_dynamic_namespace()
+        || explicit_uri(multiname).is_some()
+}
+
+/// Parse the name of a property access as a list index, as in `list[0]`.
+fn e4x_index(multiname: &Multiname<'_>) -> Option<usize> {
+    if multiname.is_attribute() {
+        return None;
+    }
+
+    multiname.local_name()?.parse().ok()
+}
+
+/// Replace one node in a list with any number of nodes.
+fn replace_at<'gc>(nodes: &mut Vec<E4XNode<'gc>>, index: usize, new_nodes: Vec<E4XNode<'gc>>) {
+    let tail = nodes.split_off(index + 1);
+
+    nodes.pop();
+    nodes.extend(new_nodes);
+    nodes.extend(tail);
+}
+
+/// Look up a property of an XML or XML list object, as E4X does.
+///
+/// This yields `None` if the object isn't XML, or the name can't name XML
+/// children or attributes, in which case the property should be looked up
+/// as usual.
+pub fn e4x_get_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    multiname: &Multiname<'gc>,
+) -> Result<Option<Value<'gc>>, Error<'gc>> {
+    let nodes = match xml_nodes(object) {
+        Some(nodes) if is_e4x_name(multiname) => nodes,
+        _ => return Ok(None),
+    };
+
+    if let Some(index) = e4x_index(multiname) {
+        if object.as_xml_object().is_some() {
+            return Ok(Some(if index == 0 {
+                object.into()
+            } else {
+                Value::Undefined
+            }));
+        }
+
+        return Ok(Some(match nodes.get(index) {
+            Some(node) => new_xml(activation, *node).into(),
+            None => Value::Undefined,
+        }));
+    }
+
+    let query = E4XQuery::from_multiname(activation, multiname);
+    let selected = query.select(&nodes);
+
+    Ok(Some(new_xml_list(activation, selected).into()))
+}
+
+/// Set a property of an XML or XML list object, as E4X does.
+///
+/// This yields `false` if the object isn't XML, or the name can't name XML
+/// children or attributes, in which case the property should be set as
+/// usual.
+pub fn e4x_set_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    multiname: &Multiname<'gc>,
+    value: Value<'gc>,
+) -> Result<bool, Error<'gc>> {
+    let nodes = match xml_nodes(object) {
+        Some(nodes) if is_e4x_name(multiname) => nodes,
+        _ => return Ok(false),
+    };
+    let mc = activation.context.gc_context;
+
+    if let Some(index) = e4x_index(multiname) {
+        let list = match object.as_xml_list_object() {
+            Some(list) => list,
+            None => {
+                return Err(Error::new(
+                    ErrorKind::TypeError,
+                    "Error #1087: Assignment to indexed XML is not allowed.",
+                ))
+            }
+        };
+
+        let mut new_nodes = match &value {
+            Value::Object(value) if xml_nodes(*value).is_some() => xml_nodes(*value).unwrap(),
+            value => value_to_nodes(activation, value.clone())?,
+        };
+
+        let mut list_nodes = list.nodes();
+        match list_nodes.get(index).cloned() {
+            Some(old) if old.is_element() && new_nodes.iter().all(|n| n.is_text()) => {
+                old.set_children(mc, new_nodes);
+            }
+            Some(old) => {
+                if let (Some(parent), Some(child_index)) = (old.parent(), old.child_index()) {
+                    let mut children = parent.children();
+                    replace_at(&mut children, child_index, new_nodes.clone());
+                    parent.set_children(mc, children);
+                }
+
+                replace_at(&mut list_nodes, index, new_nodes);
+                list.set_nodes(mc, list_nodes);
+            }
+            None => {
+                list_nodes.append(&mut new_nodes);
+                list.set_nodes(mc, list_nodes);
+            }
+        }
+
+        return Ok(true);
+    }
+
+    let node = match nodes.as_slice() {
+        [node] => *node,
+        [] => return Ok(true),
+        _ => {
+            return Err(Error::new(
+                ErrorKind::TypeError,
+                "Error #1089: Assignment to lists with more than one item is not supported.",
+            ))
+        }
+    };
+
+    if !node.is_element() {
+        return Ok(true);
+    }
+
+    let query = E4XQuery::from_multiname(activation, multiname);
+
+    if query.is_attribute {
+        let value = match &value {
+            Value::Object(value) if xml_nodes(*value).is_some() => {
+                let settings = activation.context.avm2.xml_settings.clone();
+                xml_nodes(*value)
+                    .unwrap()
+                    .iter()
+                    .map(|node| node.string_value(&settings))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            }
+            value => value.clone().coerce_to_string(activation)?.to_string(),
+        };
+
+        node.set_attribute(mc, query.to_name()?, value);
+
+        return Ok(true);
+    }
+
+    let is_xml_value = matches!(&value, Value::Object(value) if xml_nodes(*value).is_some());
+    let new_nodes = value_to_nodes(activation, value)?;
+    let matching = query.select(&[node]);
+
+    match matching.split_first() {
+        Some((first, rest)) => {
+            for other in rest {
+                other.remove(mc);
+            }
+
+            if is_xml_value {
+                if let Some(child_index) = first.child_index() {
+                    let mut children = node.children();
+                    replace_at(&mut children, child_index, new_nodes);
+                    node.set_children(mc, children);
+                }
+            } else {
+                first.set_children(mc, new_nodes);
+            }
+        }
+        None if is_xml_value => {
+            for new_node in new_nodes {
+                node.append_child(mc, new_node);
+            }
+        }
+        None => {
+            let element = E4XNode::new_element(mc, query.to_name()?);
+
+            element.set_children(mc, new_nodes);
+            node.append_child(mc, element);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Delete a property of an XML or XML list object, as E4X does.
+///
+/// This yields `None` if the object isn't XML, or the name can't name XML
+/// children or attributes, in which case the property should be deleted as
+/// usual.
+pub fn e4x_delete_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    multiname: &Multiname<'gc>,
+) -> Result<Option<bool>, Error<'gc>> {
+    let nodes = match xml_nodes(object) {
+        Some(nodes) if is_e4x_name(multiname) => nodes,
+        _ => return Ok(None),
+    };
+    let mc = activation.context.gc_context;
+
+    if let Some(index) = e4x_index(multiname) {
+        if let Some(list) = object.as_xml_list_object() {
+            let mut list_nodes = list.nodes();
+
+            if index < list_nodes.len() {
+                list_nodes.remove(index).remove(mc);
+                list.set_nodes(mc, list_nodes);
+            }
+        }
+
+        return Ok(Some(true));
+    }
+
+    let query = E4XQuery::from_multiname(activation, multiname);
+
+    for node in query.select(&nodes) {
+        node.remove(mc);
+    }
+
+    Ok(Some(true))
+}
+
+/// Collect the descendants of an XML or XML list object, as the `..`
+/// operator does.
+pub fn e4x_descendants<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Value<'gc>,
+    multiname: &Multiname<'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let nodes = match &object {
+        Value::Object(object) => xml_nodes(*object),
+        _ => None,
+    };
+    let nodes = match nodes {
+        Some(nodes) => nodes,
+        None => {
+            return Err(Error::new(
+                ErrorKind::TypeError,
+                format!(
+                    "Error #1016: Descendants operator (..) not supported on type {}.",
+                    object.type_of()
+                ),
+            ))
+        }
+    };
+
+    let query = E4XQuery::from_multiname(activation, multiname);
+
+    Ok(descendants(activation, &nodes, &query))
+}
+
+fn descendants<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    nodes: &[E4XNode<'gc>],
+    query: &E4XQuery,
+) -> Value<'gc> {
+    let mut found = Vec::new();
+    let local_name = query.local_name.as_deref();
+    let uri = query.uri.as_deref();
+
+    for node in nodes {
+        node.descendants(local_name, uri, query.is_attribute, &mut found);
+    }
+
+    new_xml_list(activation, found).into()
+}
+
+/// The nodes of the object a method was called on.
+fn this_nodes<'gc>(this: Option<Object<'gc>>) -> Vec<E4XNode<'gc>> {
+    this.and_then(xml_nodes).unwrap_or_default()
+}
+
+/// The one node of the object a method was called on.
+///
+/// Most methods of `XMLList` only work on lists of one item, which they
+/// treat as that item.
+fn this_node<'gc>(this: Option<Object<'gc>>, method: &str) -> Result<E4XNode<'gc>, Error<'gc>> {
+    match this_nodes(this).as_slice() {
+        [node] => Ok(*node),
+        _ => Err(Error::new(
+            ErrorKind::TypeError,
+            format!(
+                "Error #1086: The {} method only works on lists containing one item.",
+                method
+            ),
+        )),
+    }
+}
+
+/// Implements `XML`'s instance initializer.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+        let mut nodes = parse_value(activation, value)?;
+        let node = match nodes.len() {
+            0 => E4XNode::new_text(activation.context.gc_context, ""),
+            1 => nodes.remove(0),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::TypeError,
+                    "Error #1088: The markup in the document following the root element must be well-formed.",
+                ))
+            }
+        };
+
+        if let Some(xml) = this.as_xml_object() {
+            xml.set_node(activation.context.gc_context, node);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML.appendChild`.
+pub fn append_child<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let node = this_node(this, "appendChild")?;
+    let child = args.get(0).cloned().unwrap_or(Value::Undefined);
+
+    for child in value_to_nodes(activation, child)? {
+        node.append_child(activation.context.gc_context, child);
+    }
+
+    Ok(this.map(Value::Object).unwrap_or(Value::Undefined))
+}
+
+/// Implements `XML.prependChild`.
+pub fn prepend_child<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let node = this_node(this, "prependChild")?;
+    let child = args.get(0).cloned().unwrap_or(Value::Undefined);
+
+    for child in value_to_nodes(activation, child)?.into_iter().rev() {
+        node.prepend_child(activation.context.gc_context, child);
+    }
+
+    Ok(this.map(Value::Object).unwrap_or(Value::Undefined))
+}
+
+/// Implements `XML.attribute`.
+pub fn attribute<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let query = E4XQuery::from_value(activation, &name, true)?;
+    let selected = query.select(&this_nodes(this));
+
+    Ok(new_xml_list(activation, selected).into())
+}
+
+/// Implements `XML.attributes`.
+pub fn attributes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let query = E4XQuery {
+        is_attribute: true,
+        ..E4XQuery::any()
+    };
+    let selected = query.select(&this_nodes(this));
+
+    Ok(new_xml_list(activation, selected).into())
+}
+
+/// Implements `XML.child`.
+pub fn child<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let nodes = this_nodes(this);
+
+    let selected = match name {
+        Value::Number(_) | Value::Unsigned(_) | Value::Integer(_) => {
+            let index = name.coerce_to_number(activation)?;
+
+            nodes
+                .iter()
+                .filter_map(|node| {
+                    if index < 0.0 {
+                        return None;
+                    }
+
+                    node.children().get(index as usize).cloned()
+                })
+                .collect()
+        }
+        name => E4XQuery::from_value(activation, &name, false)?.select(&nodes),
+    };
+
+    Ok(new_xml_list(activation, selected).into())
+}
+
+/// Implements `XML.childIndex`.
+pub fn child_index<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let node = this_node(this, "childIndex")?;
+
+    Ok(node
+        .child_index()
+        .map(|index| index as i32)
+        .unwrap_or(-1)
+        .into())
+}
+
+/// Implements `XML.children`.
+pub fn children<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let selected = E4XQuery::any().select(&this_nodes(this));
+
+    Ok(new_xml_list(activation, selected).into())
+}
+
+/// Implements `XML.comments`.
+pub fn comments<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let selected = E4XQuery::any()
+        .select(&this_nodes(this))
+        .into_iter()
+        .filter(|node| node.is_comment())
+        .collect();
+
+    Ok(new_xml_list(activation, selected).into())
+}
+
+/// Implements `XML.copy`.
+pub fn copy<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mc = activation.context.gc_context;
+    let copies: Vec<_> = this_nodes(this)
+        .into_iter()
+        .map(|node| node.deep_copy(mc))
+        .collect();
+
+    if this.and_then(|this| this.as_xml_object()).is_some() {
+        return Ok(new_xml(activation, copies[0]).into());
+    }
+
+    Ok(new_xml_list(activation, copies).into())
+}
+
+/// Implements `XML.descendants`.
+pub fn descendants_method<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let query = E4XQuery::from_value(activation, &name, false)?;
+
+    Ok(descendants(activation, &this_nodes(this), &query))
+}
+
+/// Implements `XML.elements`.
+pub fn elements<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let selected = E4XQuery::from_value(activation, &name, false)?
+        .select(&this_nodes(this))
+        .into_iter()
+        .filter(|node| node.is_element())
+        .collect();
+
+    Ok(new_xml_list(activation, selected).into())
+}
+
+/// Implements `XML.hasComplexContent`.
+pub fn has_complex_content<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let nodes = this_nodes(this);
+
+    Ok((!list_has_simple_content(&nodes)).into())
+}
+
+/// Implements `XML.hasSimpleContent`.
+pub fn has_simple_content<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let nodes = this_nodes(this);
+
+    Ok(list_has_simple_content(&nodes).into())
+}
+
+/// Implements `XML.length`.
+pub fn length<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this_nodes(this).len().into())
+}
+
+/// Implements `XML.localName`.
+pub fn local_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let node = this_node(this, "localName")?;
+
+    Ok(match node.name() {
+        Some(name) => AvmString::new(activation.context.gc_context, name.local_name).into(),
+        None => Value::Null,
+    })
+}
+
+/// Implements `XML.name`.
+pub fn name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let node = this_node(this, "name")?;
+
+    Ok(match node.name() {
+        Some(name) => {
+            let uri = AvmString::new(activation.context.gc_context, name.uri);
+            let local_name = AvmString::new(activation.context.gc_context, name.local_name);
+
+            new_qname(activation, uri, local_name)?.into()
+        }
+        None => Value::Null,
+    })
+}
+
+/// Box a namespace URI and prefix into a `Namespace` object.
+fn new_namespace<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    prefix: String,
+    uri: String,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mc = activation.context.gc_context;
+    let namespace_proto = activation.context.avm2.prototypes().namespace;
+    let namespace = Namespace::Namespace(AvmString::new(mc, uri));
+    let object = NamespaceObject::from_namespace(namespace.clone(), namespace_proto, mc)?;
+
+    if let Some(object) = object.as_namespace_object() {
+        object.init_namespace(mc, namespace, Some(AvmString::new(mc, prefix)));
+    }
+
+    Ok(object.into())
+}
+
+/// Implements `XML.namespace`.
+pub fn namespace<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let node = this_node(this, "namespace")?;
+
+    if let Some(prefix) = args.get(0) {
+        let prefix = prefix.clone().coerce_to_string(activation)?;
+        let found = node
+            .in_scope_namespaces()
+            .into_iter()
+            .find(|ns| ns.prefix == &*prefix);
+
+        return match found {
+            Some(ns) => new_namespace(activation, ns.prefix, ns.uri),
+            None => Ok(Value::Undefined),
+        };
+    }
+
+    match node.name() {
+        Some(name) => new_namespace(activation, name.prefix, name.uri),
+        None => Ok(Value::Null),
+    }
+}
+
+/// Implements `XML.nodeKind`.
+pub fn node_kind<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let node = this_node(this, "nodeKind")?;
+
+    Ok(node.node_kind().into())
+}
+
+/// Implements `XML.parent`.
+///
+/// Lists yield the parent shared by all of their nodes, if there is one.
+pub fn parent<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let nodes = this_nodes(this);
+    let parent = nodes.first().and_then(|node| node.parent());
+    let shared = parent.filter(|parent| {
+        nodes.iter().all(|node| {
+            node.parent()
+                .map(|other| E4XNode::ptr_eq(other, *parent))
+                .unwrap_or(false)
+        })
+    });
+
+    Ok(match shared {
+        Some(parent) => new_xml(activation, parent).into(),
+        None => Value::Undefined,
+    })
+}
+
+/// Implements `XML.text`.
+pub fn text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let selected = E4XQuery::any()
+        .select(&this_nodes(this))
+        .into_iter()
+        .filter(|node| node.is_text())
+        .collect();
+
+    Ok(new_xml_list(activation, selected).into())
+}
+
+/// Implements `XML.toXMLString`.
+pub fn to_xml_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let settings = activation.context.avm2.xml_settings.clone();
+    let markup = list_xml_string(&this_nodes(this), &settings);
+
+    Ok(AvmString::new(activation.context.gc_context, markup).into())
+}
+
+/// Implements `XML.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let settings = activation.context.avm2.xml_settings.clone();
+    let string = list_string_value(&this_nodes(this), &settings);
+
+    Ok(AvmString::new(activation.context.gc_context, string).into())
+}
+
+/// Implements `XML.valueOf`.
+pub fn value_of<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.map(Value::Object).unwrap_or(Value::Undefined))
+}
+
+/// Implements `XML.ignoreComments`.
+pub fn ignore_comments<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.avm2.xml_settings.ignore_comments.into())
+}
+
+/// Implements `XML.ignoreComments`'s setter.
+pub fn set_ignore_comments<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+    activation.context.avm2.xml_settings.ignore_comments = value.coerce_to_boolean();
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML.ignoreProcessingInstructions`.
+pub fn ignore_processing_instructions<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation
+        .context
+        .avm2
+        .xml_settings
+        .ignore_processing_instructions
+        .into())
+}
+
+/// Implements `XML.ignoreProcessingInstructions`'s setter.
+pub fn set_ignore_processing_instructions<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+    activation
+        .context
+        .avm2
+        .xml_settings
+        .ignore_processing_instructions = value.coerce_to_boolean();
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML.ignoreWhitespace`.
+pub fn ignore_whitespace<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation
+        .context
+        .avm2
+        .xml_settings
+        .ignore_whitespace
+        .into())
+}
+
+/// Implements `XML.ignoreWhitespace`'s setter.
+pub fn set_ignore_whitespace<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+    activation.context.avm2.xml_settings.ignore_whitespace = value.coerce_to_boolean();
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML.prettyPrinting`.
+pub fn pretty_printing<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.avm2.xml_settings.pretty_printing.into())
+}
+
+/// Implements `XML.prettyPrinting`'s setter.
+pub fn set_pretty_printing<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+    activation.context.avm2.xml_settings.pretty_printing = value.coerce_to_boolean();
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML.prettyIndent`.
+pub fn pretty_indent<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.avm2.xml_settings.pretty_indent.into())
+}
+
+/// Implements `XML.prettyIndent`'s setter.
+pub fn set_pretty_indent<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+    activation.context.avm2.xml_settings.pretty_indent = value;
+
+    Ok(Value::Undefined)
+}
+
+/// Define the methods shared by `XML` and `XMLList`.
+pub fn define_e4x_methods<'gc>(mc: MutationContext<'gc, '_>, class: GcCell<'gc, Class<'gc>>) {
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "appendChild"),
+        Method::from_builtin(append_child),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "attribute"),
+        Method::from_builtin(attribute),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "attributes"),
+        Method::from_builtin(attributes),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "child"),
+        Method::from_builtin(child),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "childIndex"),
+        Method::from_builtin(child_index),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "children"),
+        Method::from_builtin(children),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "comments"),
+        Method::from_builtin(comments),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "copy"),
+        Method::from_builtin(copy),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "descendants"),
+        Method::from_builtin(descendants_method),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "elements"),
+        Method::from_builtin(elements),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "hasComplexContent"),
+        Method::from_builtin(has_complex_content),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "hasSimpleContent"),
+        Method::from_builtin(has_simple_content),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "length"),
+        Method::from_builtin(length),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "localName"),
+        Method::from_builtin(local_name),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "name"),
+        Method::from_builtin(name),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "namespace"),
+        Method::from_builtin(namespace),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "nodeKind"),
+        Method::from_builtin(node_kind),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "parent"),
+        Method::from_builtin(parent),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "prependChild"),
+        Method::from_builtin(prepend_child),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "text"),
+        Method::from_builtin(text),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "toXMLString"),
+        Method::from_builtin(to_xml_string),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::as3_namespace(), "valueOf"),
+        Method::from_builtin(value_of),
+    ));
+
+    class.write(mc).define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+}
+
+/// Construct `XML`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::public(), "XML"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    define_e4x_methods(mc, class);
+
+    class.write(mc).define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "ignoreComments"),
+        Method::from_builtin(ignore_comments),
+    ));
+
+    class.write(mc).define_class_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "ignoreComments"),
+        Method::from_builtin(set_ignore_comments),
+    ));
+
+    class.write(mc).define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "ignoreProcessingInstructions"),
+        Method::from_builtin(ignore_processing_instructions),
+    ));
+
+    class.write(mc).define_class_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "ignoreProcessingInstructions"),
+        Method::from_builtin(set_ignore_processing_instructions),
+    ));
+
+    class.write(mc).define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "ignoreWhitespace"),
+        Method::from_builtin(ignore_whitespace),
+    ));
+
+    class.write(mc).define_class_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "ignoreWhitespace"),
+        Method::from_builtin(set_ignore_whitespace),
+    ));
+
+    class.write(mc).define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "prettyPrinting"),
+        Method::from_builtin(pretty_printing),
+    ));
+
+    class.write(mc).define_class_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "prettyPrinting"),
+        Method::from_builtin(set_pretty_printing),
+    ));
+
+    class.write(mc).define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "prettyIndent"),
+        Method::from_builtin(pretty_indent),
+    ));
+
+    class.write(mc).define_class_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "prettyIndent"),
+        Method::from_builtin(set_pretty_indent),
+    ));
+
+    class
+}
+
+/// Object deriver for `XML`
+pub fn xml_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    Ok(XmlObject::derive(
+        base_proto,
+        activation.context.gc_context,
+        class,
+        scope,
+    ))
+}
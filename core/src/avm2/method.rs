@@ -3,7 +3,9 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::object::Object;
 use crate::avm2::script::TranslationUnit;
+use crate::avm2::string::AvmString;
 use crate::avm2::value::Value;
+use crate::avm2::verify::VerifiedMethodBody;
 use crate::avm2::Error;
 use crate::collect::CollectWrapper;
 use gc_arena::{Collect, CollectionContext, Gc, MutationContext};
@@ -29,7 +31,7 @@ pub type NativeMethod<'gc> = fn(
     &mut Activation<'_, 'gc, '_>,
     Option<Object<'gc>>,
     &[Value<'gc>],
-) -> Result<Value<'gc>, Error>;
+) -> Result<Value<'gc>, Error<'gc>>;
 
 /// Represents a reference to an AVM2 method and body.
 #[derive(Collect, Clone, Debug)]
@@ -112,6 +114,26 @@ impl<'gc> BytecodeMethod<'gc> {
             None
         }
     }
+
+    /// Get the name of this method, as it appears in stack traces.
+    pub fn name(&self, mc: MutationContext<'gc, '_>) -> Result<AvmString<'gc>, Error<'gc>> {
+        self.txunit.method_name(self.abc_method, mc)
+    }
+
+    /// Get the decoded and verified instructions of the ABC method body entry
+    /// this refers to.
+    ///
+    /// This function returns `Err` if there is no body for this method, or if
+    /// the body failed verification.
+    pub fn verified_body(
+        &self,
+        mc: MutationContext<'gc, '_>,
+    ) -> Result<Rc<VerifiedMethodBody>, Error<'gc>> {
+        match self.abc_method_body {
+            Some(abc_method_body) => self.txunit.load_method_body(abc_method_body, mc),
+            None => Err("Cannot execute non-native method without body".into()),
+        }
+    }
 }
 
 /// An uninstantiated method that can either be natively implemented or sourced
@@ -168,7 +190,7 @@ impl<'gc> Method<'gc> {
     /// Access the bytecode of this method.
     ///
     /// This function returns `Err` if there is no bytecode for this method.
-    pub fn into_bytecode(self) -> Result<Gc<'gc, BytecodeMethod<'gc>>, Error> {
+    pub fn into_bytecode(self) -> Result<Gc<'gc, BytecodeMethod<'gc>>, Error<'gc>> {
         match self {
             Method::Native(_) => {
                 Err("Attempted to unwrap a native method as a user-defined one".into())
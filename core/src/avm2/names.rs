@@ -30,14 +30,14 @@ impl<'gc> Namespace<'gc> {
         translation_unit: TranslationUnit<'gc>,
         namespace_index: Index<AbcNamespace>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Self, Error> {
+    ) -> Result<Self, Error<'gc>> {
         if namespace_index.0 == 0 {
             return Ok(Self::Any);
         }
 
         let actual_index = namespace_index.0 as usize - 1;
         let abc = translation_unit.abc();
-        let abc_namespace: Result<_, Error> = abc
+        let abc_namespace: Result<_, Error<'gc>> = abc
             .constant_pool
             .namespaces
             .get(actual_index)
@@ -150,14 +150,14 @@ impl<'gc> QName<'gc> {
         translation_unit: TranslationUnit<'gc>,
         multiname_index: Index<AbcMultiname>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Self, Error> {
+    ) -> Result<Self, Error<'gc>> {
         if multiname_index.0 == 0 {
             return Err("Attempted to load a trait name of index zero".into());
         }
 
         let actual_index = multiname_index.0 as usize - 1;
         let abc = translation_unit.abc();
-        let abc_multiname: Result<_, Error> = abc
+        let abc_multiname: Result<_, Error<'gc>> = abc
             .constant_pool
             .multinames
             .get(actual_index)
@@ -190,6 +190,23 @@ impl<'gc> QName<'gc> {
         }
     }
 
+    /// Parse a fully qualified name, as given to `getDefinitionByName`.
+    ///
+    /// Both `flash.display::Sprite` and `flash.display.Sprite` name the
+    /// `Sprite` class in the `flash.display` package. Names without a package
+    /// are public.
+    pub fn from_qualified_name(name: &str, mc: MutationContext<'gc, '_>) -> Self {
+        if let Some(separator) = name.rfind("::") {
+            return Self {
+                ns: Namespace::Package(AvmString::new(mc, &name[..separator])),
+                name: AvmString::new(mc, &name[separator + 2..]),
+            };
+        }
+
+        Self::from_symbol_class(name, mc)
+            .unwrap_or_else(|| Self::new(Namespace::public(), AvmString::new(mc, name)))
+    }
+
     pub fn local_name(&self) -> AvmString<'gc> {
         self.name
     }
@@ -197,6 +214,23 @@ impl<'gc> QName<'gc> {
     pub fn namespace(&self) -> &Namespace<'gc> {
         &self.ns
     }
+
+    /// Format this name the way Flash Player prints it in stack traces and
+    /// error messages.
+    ///
+    /// Names in a package are prefixed with the package name, such as
+    /// `flash.display::Sprite`. All other names are printed as their local
+    /// name alone.
+    pub fn to_qualified_name(&self) -> String {
+        match &self.ns {
+            Namespace::Package(package) | Namespace::PackageInternal(package)
+                if !package.is_empty() =>
+            {
+                format!("{}::{}", package, self.name)
+            }
+            _ => self.name.to_string(),
+        }
+    }
 }
 
 /// A `Multiname` consists of a name which could be resolved in one or more
@@ -211,6 +245,13 @@ impl<'gc> QName<'gc> {
 pub struct Multiname<'gc> {
     ns: Vec<Namespace<'gc>>,
     name: Option<AvmString<'gc>>,
+
+    /// The type parameters of a parameterized type name, such as the `int`
+    /// in `Vector.<int>`.
+    params: Vec<Multiname<'gc>>,
+
+    /// Whether or not this names an XML attribute, as in `xml.@name`.
+    is_attribute: bool,
 }
 
 impl<'gc> Multiname<'gc> {
@@ -220,7 +261,7 @@ impl<'gc> Multiname<'gc> {
         translation_unit: TranslationUnit<'gc>,
         namespace_set_index: Index<AbcNamespaceSet>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Vec<Namespace<'gc>>, Error> {
+    ) -> Result<Vec<Namespace<'gc>>, Error<'gc>> {
         if namespace_set_index.0 == 0 {
             //TODO: What is namespace set zero?
             return Ok(vec![]);
@@ -228,7 +269,7 @@ impl<'gc> Multiname<'gc> {
 
         let actual_index = namespace_set_index.0 as usize - 1;
         let abc = translation_unit.abc();
-        let ns_set: Result<_, Error> = abc
+        let ns_set: Result<_, Error<'gc>> = abc
             .constant_pool
             .namespace_sets
             .get(actual_index)
@@ -254,19 +295,20 @@ impl<'gc> Multiname<'gc> {
         translation_unit: TranslationUnit<'gc>,
         multiname_index: Index<AbcMultiname>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Self, Error> {
-        let actual_index: Result<usize, Error> = (multiname_index.0 as usize)
+    ) -> Result<Self, Error<'gc>> {
+        let actual_index: Result<usize, Error<'gc>> = (multiname_index.0 as usize)
             .checked_sub(1)
             .ok_or_else(|| "Attempted to resolve a multiname at index zero. This is a bug.".into());
         let actual_index = actual_index?;
         let abc = translation_unit.abc();
-        let abc_multiname: Result<_, Error> = abc
+        let abc_multiname: Result<_, Error<'gc>> = abc
             .constant_pool
             .multinames
             .get(actual_index)
             .ok_or_else(|| format!("Unknown multiname constant {}", multiname_index.0).into());
 
-        Ok(match abc_multiname? {
+        let abc_multiname = abc_multiname?;
+        let mut multiname = match abc_multiname {
             AbcMultiname::QName { namespace, name } | AbcMultiname::QNameA { namespace, name } => {
                 Self {
                     ns: vec![Namespace::from_abc_namespace(
@@ -276,6 +318,8 @@ impl<'gc> Multiname<'gc> {
                     )?],
                     name: translation_unit
                         .pool_string_option(name.0, activation.context.gc_context)?,
+                    params: Vec::new(),
+                    is_attribute: false,
                 }
             }
             AbcMultiname::RTQName { name } | AbcMultiname::RTQNameA { name } => {
@@ -284,6 +328,8 @@ impl<'gc> Multiname<'gc> {
                     ns: vec![ns],
                     name: translation_unit
                         .pool_string_option(name.0, activation.context.gc_context)?,
+                    params: Vec::new(),
+                    is_attribute: false,
                 }
             }
             AbcMultiname::RTQNameL | AbcMultiname::RTQNameLA => {
@@ -292,6 +338,8 @@ impl<'gc> Multiname<'gc> {
                 Self {
                     ns: vec![ns],
                     name: Some(name),
+                    params: Vec::new(),
+                    is_attribute: false,
                 }
             }
             AbcMultiname::Multiname {
@@ -308,6 +356,8 @@ impl<'gc> Multiname<'gc> {
                     activation.context.gc_context,
                 )?,
                 name: translation_unit.pool_string_option(name.0, activation.context.gc_context)?,
+                params: Vec::new(),
+                is_attribute: false,
             },
             AbcMultiname::MultinameL { namespace_set }
             | AbcMultiname::MultinameLA { namespace_set } => {
@@ -319,9 +369,33 @@ impl<'gc> Multiname<'gc> {
                         activation.context.gc_context,
                     )?,
                     name: Some(name),
+                    params: Vec::new(),
+                    is_attribute: false,
                 }
             }
-        })
+            AbcMultiname::TypeName { .. } => Self::from_abc_multiname_static(
+                translation_unit,
+                multiname_index.clone(),
+                activation.context.gc_context,
+            )?,
+        };
+
+        multiname.is_attribute = Self::is_abc_attribute(abc_multiname);
+
+        Ok(multiname)
+    }
+
+    /// Determine if a multiname from the ABC constant pool names an XML
+    /// attribute.
+    fn is_abc_attribute(abc_multiname: &AbcMultiname) -> bool {
+        matches!(
+            abc_multiname,
+            AbcMultiname::QNameA { .. }
+                | AbcMultiname::RTQNameA { .. }
+                | AbcMultiname::RTQNameLA
+                | AbcMultiname::MultinameA { .. }
+                | AbcMultiname::MultinameLA { .. }
+        )
     }
 
     /// Read a static multiname from the ABC constant pool
@@ -332,20 +406,21 @@ impl<'gc> Multiname<'gc> {
         translation_unit: TranslationUnit<'gc>,
         multiname_index: Index<AbcMultiname>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Self, Error> {
-        let actual_index: Result<usize, Error> =
+    ) -> Result<Self, Error<'gc>> {
+        let actual_index: Result<usize, Error<'gc>> =
             (multiname_index.0 as usize).checked_sub(1).ok_or_else(|| {
                 "Attempted to resolve a (static) multiname at index zero. This is a bug.".into()
             });
         let actual_index = actual_index?;
         let abc = translation_unit.abc();
-        let abc_multiname: Result<_, Error> = abc
+        let abc_multiname: Result<_, Error<'gc>> = abc
             .constant_pool
             .multinames
             .get(actual_index)
             .ok_or_else(|| format!("Unknown multiname constant {}", multiname_index.0).into());
 
-        Ok(match abc_multiname? {
+        let abc_multiname = abc_multiname?;
+        let mut multiname = match abc_multiname {
             AbcMultiname::QName { namespace, name } | AbcMultiname::QNameA { namespace, name } => {
                 Self {
                     ns: vec![Namespace::from_abc_namespace(
@@ -354,6 +429,8 @@ impl<'gc> Multiname<'gc> {
                         mc,
                     )?],
                     name: translation_unit.pool_string_option(name.0, mc)?,
+                    params: Vec::new(),
+                    is_attribute: false,
                 }
             }
             AbcMultiname::Multiname {
@@ -366,9 +443,33 @@ impl<'gc> Multiname<'gc> {
             } => Self {
                 ns: Self::abc_namespace_set(translation_unit, namespace_set.clone(), mc)?,
                 name: translation_unit.pool_string_option(name.0, mc)?,
+                params: Vec::new(),
+                is_attribute: false,
             },
+            AbcMultiname::TypeName {
+                base_type,
+                parameters,
+            } => {
+                let mut base =
+                    Self::from_abc_multiname_static(translation_unit, base_type.clone(), mc)?;
+
+                // Index zero stands for the any type, as in `Vector.<*>`.
+                for parameter in parameters {
+                    base.params.push(if parameter.0 == 0 {
+                        Self::any()
+                    } else {
+                        Self::from_abc_multiname_static(translation_unit, parameter.clone(), mc)?
+                    });
+                }
+
+                base
+            }
             _ => return Err(format!("Multiname {} is not static", multiname_index.0).into()),
-        })
+        };
+
+        multiname.is_attribute = Self::is_abc_attribute(abc_multiname);
+
+        Ok(multiname)
     }
 
     /// Indicates the any type (any name in any namespace).
@@ -376,6 +477,8 @@ impl<'gc> Multiname<'gc> {
         Self {
             ns: vec![Namespace::Any],
             name: None,
+            params: Vec::new(),
+            is_attribute: false,
         }
     }
 
@@ -387,6 +490,40 @@ impl<'gc> Multiname<'gc> {
         self.name
     }
 
+    /// The type parameters of this name, which are empty for names that
+    /// aren't parameterized types.
+    pub fn params(&self) -> &[Multiname<'gc>] {
+        &self.params
+    }
+
+    /// Whether or not this names an XML attribute, as in `xml.@name`.
+    pub fn is_attribute(&self) -> bool {
+        self.is_attribute
+    }
+
+    /// Format this name the way Flash Player prints type names, such as
+    /// `flash.display::Sprite` or `__AS3__.vec::Vector.<int>`.
+    ///
+    /// The any name is printed as `*`. Names with several namespaces are
+    /// qualified with the first one.
+    pub fn to_qualified_name(&self) -> String {
+        let local_name = match self.name {
+            Some(name) => name,
+            None => return "*".to_string(),
+        };
+        let mut qualified_name = match self.ns.first() {
+            Some(ns) => QName::new(ns.clone(), local_name).to_qualified_name(),
+            None => local_name.to_string(),
+        };
+
+        if !self.params.is_empty() {
+            let params: Vec<String> = self.params.iter().map(|p| p.to_qualified_name()).collect();
+            qualified_name.push_str(&format!(".<{}>", params.join(",")));
+        }
+
+        qualified_name
+    }
+
     pub fn includes_dynamic_namespace(&self) -> bool {
         for ns in self.ns.iter() {
             if ns.is_dynamic() {
@@ -403,6 +540,8 @@ impl<'gc> From<QName<'gc>> for Multiname<'gc> {
         Self {
             ns: vec![q.ns],
             name: Some(q.name),
+            params: Vec::new(),
+            is_attribute: false,
         }
     }
 }
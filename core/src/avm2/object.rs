@@ -2,42 +2,62 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::array::ArrayStorage;
+use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::class::Class;
 use crate::avm2::domain::Domain;
 use crate::avm2::events::{DispatchList, Event};
 use crate::avm2::function::Executable;
 use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::regexp::RegExp;
 use crate::avm2::scope::Scope;
 use crate::avm2::string::AvmString;
 use crate::avm2::traits::{Trait, TraitKind};
-use crate::avm2::value::{Hint, Value};
-use crate::avm2::Error;
+use crate::avm2::value::{default_value_for_type, Hint, Value};
+use crate::avm2::vector::VectorStorage;
+use crate::avm2::{Error, ErrorKind};
 use crate::display_object::DisplayObject;
 use gc_arena::{Collect, GcCell, MutationContext};
 use ruffle_macros::enum_trait_object;
 use std::cell::{Ref, RefMut};
 use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
 
 mod array_object;
+mod bytearray_object;
 mod custom_object;
+mod date_object;
+mod dictionary_object;
 mod dispatch_object;
 mod domain_object;
 mod event_object;
 mod function_object;
+mod loaderinfo_object;
 mod namespace_object;
 mod primitive_object;
+mod regexp_object;
 mod script_object;
 mod stage_object;
+mod vector_object;
+mod xml_list_object;
+mod xml_object;
 
 pub use crate::avm2::object::array_object::ArrayObject;
+pub use crate::avm2::object::bytearray_object::ByteArrayObject;
+pub use crate::avm2::object::date_object::DateObject;
+pub use crate::avm2::object::dictionary_object::DictionaryObject;
 pub use crate::avm2::object::dispatch_object::DispatchObject;
 pub use crate::avm2::object::domain_object::DomainObject;
 pub use crate::avm2::object::event_object::EventObject;
 pub use crate::avm2::object::function_object::{implicit_deriver, FunctionObject};
+pub use crate::avm2::object::loaderinfo_object::LoaderInfoObject;
 pub use crate::avm2::object::namespace_object::NamespaceObject;
 pub use crate::avm2::object::primitive_object::PrimitiveObject;
+pub use crate::avm2::object::regexp_object::RegExpObject;
 pub use crate::avm2::object::script_object::ScriptObject;
 pub use crate::avm2::object::stage_object::StageObject;
+pub use crate::avm2::object::vector_object::VectorObject;
+pub use crate::avm2::object::xml_list_object::XmlListObject;
+pub use crate::avm2::object::xml_object::XmlObject;
 
 /// Represents an object that can be directly interacted with by the AVM2
 /// runtime.
@@ -53,7 +73,15 @@ pub use crate::avm2::object::stage_object::StageObject;
         StageObject(StageObject<'gc>),
         DomainObject(DomainObject<'gc>),
         EventObject(EventObject<'gc>),
-        DispatchObject(DispatchObject<'gc>)
+        DispatchObject(DispatchObject<'gc>),
+        ByteArrayObject(ByteArrayObject<'gc>),
+        RegExpObject(RegExpObject<'gc>),
+        VectorObject(VectorObject<'gc>),
+        DictionaryObject(DictionaryObject<'gc>),
+        DateObject(DateObject<'gc>),
+        XmlObject(XmlObject<'gc>),
+        XmlListObject(XmlListObject<'gc>),
+        LoaderInfoObject(LoaderInfoObject<'gc>)
     }
 )]
 pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy {
@@ -64,7 +92,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         receiver: Object<'gc>,
         name: &QName<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Value<'gc>, Error>;
+    ) -> Result<Value<'gc>, Error<'gc>>;
 
     /// Retrieve a property by its QName.
     fn get_property(
@@ -72,7 +100,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         receiver: Object<'gc>,
         name: &QName<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Value<'gc>, Error> {
+    ) -> Result<Value<'gc>, Error<'gc>> {
         if !self.has_instantiated_property(name) {
             for abc_trait in self.get_trait(name)? {
                 self.install_trait(activation, abc_trait, receiver)?;
@@ -96,7 +124,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     ///
     /// This function returns `None` for non-trait properties, such as actually
     /// defined prototype methods for ES3-style classes.
-    fn get_base_proto(self, name: &QName<'gc>) -> Result<Option<Object<'gc>>, Error> {
+    fn get_base_proto(self, name: &QName<'gc>) -> Result<Option<Object<'gc>>, Error<'gc>> {
         if self.provides_trait(name)? {
             return Ok(Some(self.into()));
         }
@@ -115,7 +143,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<(), Error>;
+    ) -> Result<(), Error<'gc>>;
 
     /// Set a property by its QName.
     fn set_property(
@@ -124,7 +152,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         if !self.has_instantiated_property(name) {
             for abc_trait in self.get_trait(name)? {
                 self.install_trait(activation, abc_trait, receiver)?;
@@ -147,6 +175,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
             proto = my_proto.proto();
         }
 
+        let value = receiver.coerce_property_value(name, value, activation)?;
         receiver.set_property_local(receiver, name, value, activation)
     }
 
@@ -157,7 +186,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<(), Error>;
+    ) -> Result<(), Error<'gc>>;
 
     /// Init a property by its QName.
     fn init_property(
@@ -166,7 +195,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         if !self.has_instantiated_property(name) {
             for abc_trait in self.get_trait(name)? {
                 self.install_trait(activation, abc_trait, receiver)?;
@@ -189,11 +218,12 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
             proto = my_proto.proto();
         }
 
+        let value = receiver.coerce_property_value(name, value, activation)?;
         receiver.init_property_local(receiver, name, value, activation)
     }
 
     /// Retrieve a slot by its index.
-    fn get_slot(self, id: u32) -> Result<Value<'gc>, Error>;
+    fn get_slot(self, id: u32) -> Result<Value<'gc>, Error<'gc>>;
 
     /// Set a slot by its index.
     fn set_slot(
@@ -201,7 +231,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         id: u32,
         value: Value<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error>;
+    ) -> Result<(), Error<'gc>>;
 
     /// Initialize a slot by its index.
     fn init_slot(
@@ -209,7 +239,51 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         id: u32,
         value: Value<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error>;
+    ) -> Result<(), Error<'gc>>;
+
+    /// Retrieve the declared type of a slot by its index.
+    ///
+    /// Returns `None` if the slot doesn't exist or accepts any type.
+    fn get_slot_type(self, id: u32) -> Option<Multiname<'gc>>;
+
+    /// Retrieve the index of the slot that backs a property, if it has one.
+    fn get_property_slot_id(self, name: &QName<'gc>) -> Option<u32>;
+
+    /// Coerce a value to the declared type of a slot, so that it can be
+    /// written to that slot.
+    ///
+    /// The type is resolved in the scope the object was created in, which is
+    /// where its traits were declared. This must be called before writing the
+    /// slot, and not while the object is borrowed, as coercion may run user
+    /// code.
+    fn coerce_slot_value(
+        self,
+        id: u32,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        if let Some(type_name) = self.get_slot_type(id) {
+            let scope = self.get_scope().or_else(|| activation.scope());
+            activation.coerce_to_declared_type(value, &type_name, scope)
+        } else {
+            Ok(value)
+        }
+    }
+
+    /// Coerce a value to the declared type of a property, if it is backed by
+    /// a slot.
+    fn coerce_property_value(
+        self,
+        name: &QName<'gc>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        if let Some(id) = self.get_property_slot_id(name) {
+            self.coerce_slot_value(id, value, activation)
+        } else {
+            Ok(value)
+        }
+    }
 
     /// Retrieve a method by its index.
     fn get_method(self, id: u32) -> Option<Object<'gc>>;
@@ -219,7 +293,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// This function returns `None` if no such trait exists, or the object
     /// does not have traits. It returns `Err` if *any* trait in the object is
     /// malformed in some way.
-    fn get_trait(self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error>;
+    fn get_trait(self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error<'gc>>;
 
     /// Populate a list of traits that this object provides.
     ///
@@ -231,7 +305,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         &self,
         name: &QName<'gc>,
         known_traits: &mut Vec<Trait<'gc>>,
-    ) -> Result<(), Error>;
+    ) -> Result<(), Error<'gc>>;
 
     /// Retrieves the scope chain of the object at time of its creation.
     ///
@@ -243,7 +317,10 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
 
     /// Resolve a multiname into a single QName, if any of the namespaces
     /// match.
-    fn resolve_multiname(self, multiname: &Multiname<'gc>) -> Result<Option<QName<'gc>>, Error> {
+    fn resolve_multiname(
+        self,
+        multiname: &Multiname<'gc>,
+    ) -> Result<Option<QName<'gc>>, Error<'gc>> {
         for ns in multiname.namespace_set() {
             if ns.is_any() {
                 if let Some(name) = multiname.local_name() {
@@ -277,18 +354,20 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// Trait names will be resolve on class constructors and object instances,
     /// but not prototypes. If you want to search a prototype's provided traits
     /// you must walk the prototype chain using `resolve_any_trait`.
-    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error>;
+    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error<'gc>>;
 
     /// Given a local name of a trait, find the namespace it resides in, if any.
     ///
     /// This function only works for names which are trait properties, not
     /// dynamic or prototype properties. Furthermore, instance prototypes *will*
     /// resolve trait names here, contrary to their behavior in `resolve_any.`
-    fn resolve_any_trait(self, local_name: AvmString<'gc>)
-        -> Result<Option<Namespace<'gc>>, Error>;
+    fn resolve_any_trait(
+        self,
+        local_name: AvmString<'gc>,
+    ) -> Result<Option<Namespace<'gc>>, Error<'gc>>;
 
     /// Indicates whether or not a property exists on an object.
-    fn has_property(self, name: &QName<'gc>) -> Result<bool, Error> {
+    fn has_property(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
         if self.has_own_property(name)? {
             Ok(true)
         } else if let Some(proto) = self.proto() {
@@ -300,15 +379,15 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
 
     /// Indicates whether or not a property or trait exists on an object and is
     /// not part of the prototype chain.
-    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error>;
+    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error<'gc>>;
 
     /// Returns true if an object has one or more traits of a given name.
-    fn has_trait(self, name: &QName<'gc>) -> Result<bool, Error>;
+    fn has_trait(self, name: &QName<'gc>) -> Result<bool, Error<'gc>>;
 
     /// Returns true if an object is part of a class that defines a trait of a
     /// given name on itself (as opposed to merely inheriting a superclass
     /// trait.)
-    fn provides_trait(self, name: &QName<'gc>) -> Result<bool, Error>;
+    fn provides_trait(self, name: &QName<'gc>) -> Result<bool, Error<'gc>>;
 
     /// Indicates whether or not a property or *instantiated* trait exists on
     /// an object and is not part of the prototype chain.
@@ -362,6 +441,44 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// mechanism.
     fn get_enumerant_name(&self, index: u32) -> Option<QName<'gc>>;
 
+    /// Retrieve the index of the enumerant after a given one, or `None` if
+    /// there are no more enumerants.
+    ///
+    /// Objects whose enumerants can have gaps in their indices, such as
+    /// dictionaries that had keys deleted, must override this to skip them.
+    fn get_next_enumerant(self, last_index: u32) -> Option<u32> {
+        let next_index = last_index + 1;
+
+        self.get_enumerant_key(next_index).map(|_| next_index)
+    }
+
+    /// Retrieve the key of a given enumerant by index, as yielded by
+    /// `for..in`.
+    ///
+    /// Most objects key their enumerants by name. Objects that can also be
+    /// keyed by other values, such as dictionaries, must override this and
+    /// `get_enumerant_value` together.
+    fn get_enumerant_key(&self, index: u32) -> Option<Value<'gc>> {
+        self.get_enumerant_name(index)
+            .map(|name| name.local_name().into())
+    }
+
+    /// Retrieve the value of a given enumerant by index, as yielded by
+    /// `for each..in`.
+    fn get_enumerant_value(
+        self,
+        index: u32,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        match self.get_enumerant_name(index) {
+            Some(name) => {
+                let mut this: Object<'gc> = self.into();
+                this.get_property(this, &name, activation)
+            }
+            None => Ok(Value::Undefined),
+        }
+    }
+
     /// Determine if a property is currently enumerable.
     ///
     /// Properties that do not exist are also not enumerable.
@@ -373,7 +490,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         mc: MutationContext<'gc, '_>,
         name: &QName<'gc>,
         is_enumerable: bool,
-    ) -> Result<(), Error>;
+    ) -> Result<(), Error<'gc>>;
 
     /// Install a method (or any other non-slot value) on an object.
     fn install_method(
@@ -391,7 +508,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         name: QName<'gc>,
         disp_id: u32,
         function: Object<'gc>,
-    ) -> Result<(), Error>;
+    ) -> Result<(), Error<'gc>>;
 
     /// Install a setter method on an object property.
     fn install_setter(
@@ -400,7 +517,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         name: QName<'gc>,
         disp_id: u32,
         function: Object<'gc>,
-    ) -> Result<(), Error>;
+    ) -> Result<(), Error<'gc>>;
 
     /// Install a dynamic or built-in value property on an object.
     fn install_dynamic_property(
@@ -408,15 +525,19 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         mc: MutationContext<'gc, '_>,
         name: QName<'gc>,
         value: Value<'gc>,
-    ) -> Result<(), Error>;
+    ) -> Result<(), Error<'gc>>;
 
     /// Install a slot on an object property.
+    ///
+    /// Values written to the slot are coerced to `type_name`, which may be
+    /// `Multiname::any()` for untyped slots.
     fn install_slot(
         &mut self,
         mc: MutationContext<'gc, '_>,
         name: QName<'gc>,
         id: u32,
         value: Value<'gc>,
+        type_name: Multiname<'gc>,
     );
 
     /// Install a const on an object property.
@@ -449,7 +570,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         activation: &mut Activation<'_, 'gc, '_>,
         trait_entry: Trait<'gc>,
         receiver: Object<'gc>,
-    ) -> Result<Value<'gc>, Error> {
+    ) -> Result<Value<'gc>, Error<'gc>> {
         self.install_foreign_trait(activation, trait_entry, self.get_scope(), receiver)
     }
 
@@ -463,7 +584,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         trait_entry: Trait<'gc>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
         receiver: Object<'gc>,
-    ) -> Result<Value<'gc>, Error> {
+    ) -> Result<Value<'gc>, Error<'gc>> {
         let fn_proto = activation.avm2().prototypes().function;
         let trait_name = trait_entry.name().clone();
         avm_debug!(
@@ -476,15 +597,18 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         match trait_entry.kind() {
             TraitKind::Slot {
                 slot_id,
+                type_name,
                 default_value,
-                ..
             } => {
-                let value = default_value.clone().unwrap_or(Value::Undefined);
+                let value = default_value
+                    .clone()
+                    .unwrap_or_else(|| default_value_for_type(type_name));
                 self.install_slot(
                     activation.context.gc_context,
                     trait_name,
                     *slot_id,
                     value.clone(),
+                    type_name.clone(),
                 );
 
                 Ok(value)
@@ -606,6 +730,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
                     QName::new(Namespace::public(), "prototype"),
                     0,
                     es3_proto.into(),
+                    Multiname::any(),
                 );
                 self.install_const(
                     activation.context.gc_context,
@@ -618,10 +743,12 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
             }
             TraitKind::Const {
                 slot_id,
+                type_name,
                 default_value,
-                ..
             } => {
-                let value = default_value.clone().unwrap_or(Value::Undefined);
+                let value = default_value
+                    .clone()
+                    .unwrap_or_else(|| default_value_for_type(type_name));
                 self.install_const(
                     activation.context.gc_context,
                     trait_name,
@@ -641,8 +768,11 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         _arguments: &[Value<'gc>],
         _activation: &mut Activation<'_, 'gc, '_>,
         _base_proto: Option<Object<'gc>>,
-    ) -> Result<Value<'gc>, Error> {
-        Err("Object is not callable".into())
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        Err(Error::new(
+            ErrorKind::TypeError,
+            "Error #1006: value is not a function.",
+        ))
     }
 
     /// Construct a host object of some kind and return its cell.
@@ -666,7 +796,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         &self,
         activation: &mut Activation<'_, 'gc, '_>,
         args: &[Value<'gc>],
-    ) -> Result<Object<'gc>, Error>;
+    ) -> Result<Object<'gc>, Error<'gc>>;
 
     /// Construct a host object prototype of some kind and return it.
     ///
@@ -682,7 +812,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         activation: &mut Activation<'_, 'gc, '_>,
         class: GcCell<'gc, Class<'gc>>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<Object<'gc>, Error>;
+    ) -> Result<Object<'gc>, Error<'gc>>;
 
     /// Determine the type of primitive coercion this object would prefer, in
     /// the case that there is no obvious reason to prefer one type over the
@@ -702,7 +832,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// coercions happen by defining `toString` in a downstream class or
     /// prototype; this is then picked up by the VM runtime when doing
     /// coercions.
-    fn to_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn to_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         let class_name = self
             .as_proto_class()
             .map(|c| c.read().name().local_name())
@@ -719,7 +849,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// generate a debug-style string based on the name of the class this
     /// object is, in the format of `[object Class]` (where `Class` is the name
     /// of the class that created this object).
-    fn to_locale_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn to_locale_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         let class_name = self
             .as_proto_class()
             .map(|c| c.read().name().local_name())
@@ -733,7 +863,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     ///
     /// `valueOf` is a method used to request an object be coerced to a
     /// primitive value. Typically, this would be a number of some kind.
-    fn value_of(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error>;
+    fn value_of(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>>;
 
     /// Enumerate all interfaces implemented by this object.
     fn interfaces(&self) -> Vec<Object<'gc>>;
@@ -753,7 +883,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         activation: &mut Activation<'_, 'gc, '_>,
         mut constructor: Object<'gc>,
         check_interfaces: bool,
-    ) -> Result<bool, Error> {
+    ) -> Result<bool, Error<'gc>> {
         let type_proto = constructor
             .get_property(constructor, &QName::dynamic_name("prototype"), activation)?
             .coerce_to_object(activation)?;
@@ -771,7 +901,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         &self,
         type_proto: Object<'gc>,
         check_interfaces: bool,
-    ) -> Result<bool, Error> {
+    ) -> Result<bool, Error<'gc>> {
         let mut my_proto = self.proto();
 
         //TODO: Is it a verification error to do `obj instanceof bare_object`?
@@ -805,10 +935,12 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// This only yields `None` for bare objects.
     fn as_proto_class(&self) -> Option<GcCell<'gc, Class<'gc>>> {
         let mut class = self.as_class();
+        let mut next = self.proto();
 
         while class.is_none() {
-            if let Some(proto) = self.proto() {
+            if let Some(proto) = next {
                 class = proto.as_class();
+                next = proto.proto();
             } else {
                 return None;
             }
@@ -827,6 +959,11 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         None
     }
 
+    /// Unwrap this object as a namespace object.
+    fn as_namespace_object(self) -> Option<NamespaceObject<'gc>> {
+        None
+    }
+
     /// Unwrap this object as array storage.
     fn as_array_storage(&self) -> Option<Ref<ArrayStorage<'gc>>> {
         None
@@ -875,6 +1012,64 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     fn as_dispatch_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<DispatchList<'gc>>> {
         None
     }
+
+    /// Unwrap this object as byte array storage.
+    fn as_bytearray(&self) -> Option<Ref<ByteArrayStorage>> {
+        None
+    }
+
+    /// Unwrap this object as mutable byte array storage.
+    fn as_bytearray_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<ByteArrayStorage>> {
+        None
+    }
+
+    /// Unwrap this object as a regular expression.
+    fn as_regexp(&self) -> Option<Ref<RegExp>> {
+        None
+    }
+
+    /// Unwrap this object as a mutable regular expression.
+    fn as_regexp_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<RegExp>> {
+        None
+    }
+
+    /// Unwrap this object as a dictionary.
+    fn as_dictionary_object(self) -> Option<DictionaryObject<'gc>> {
+        None
+    }
+
+    /// Unwrap this object as a date.
+    fn as_date_object(self) -> Option<DateObject<'gc>> {
+        None
+    }
+
+    /// Unwrap this object as loader info.
+    fn as_loader_info_object(self) -> Option<LoaderInfoObject<'gc>> {
+        None
+    }
+
+    /// Unwrap this object as an XML node.
+    fn as_xml_object(self) -> Option<XmlObject<'gc>> {
+        None
+    }
+
+    /// Unwrap this object as an XML list.
+    fn as_xml_list_object(self) -> Option<XmlListObject<'gc>> {
+        None
+    }
+
+    /// Unwrap this object as vector storage.
+    fn as_vector_storage(&self) -> Option<Ref<VectorStorage<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object as mutable vector storage.
+    fn as_vector_storage_mut(
+        &self,
+        _mc: MutationContext<'gc, '_>,
+    ) -> Option<RefMut<VectorStorage<'gc>>> {
+        None
+    }
 }
 
 pub enum ObjectPtr {}
@@ -884,3 +1079,17 @@ impl<'gc> Object<'gc> {
         a.as_ptr() == b.as_ptr()
     }
 }
+
+impl<'gc> PartialEq for Object<'gc> {
+    fn eq(&self, other: &Self) -> bool {
+        Object::ptr_eq(*self, *other)
+    }
+}
+
+impl<'gc> Eq for Object<'gc> {}
+
+impl<'gc> Hash for Object<'gc> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_ptr().hash(state);
+    }
+}
@@ -4,7 +4,7 @@ use crate::avm1::AvmString;
 use crate::avm2::activation::Activation;
 use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
-use crate::avm2::names::{Namespace, QName};
+use crate::avm2::names::{Multiname, Namespace, QName};
 use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
 use crate::avm2::object::{Object, ObjectPtr, TObject};
 use crate::avm2::scope::Scope;
@@ -51,7 +51,7 @@ impl<'gc> ArrayObject<'gc> {
         mc: MutationContext<'gc, '_>,
         class: GcCell<'gc, Class<'gc>>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let base = ScriptObjectData::base_new(
             Some(base_proto),
             ScriptObjectClass::InstancePrototype(class, scope),
@@ -87,7 +87,7 @@ impl<'gc> TObject<'gc> for ArrayObject<'gc> {
         receiver: Object<'gc>,
         name: &QName<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Value<'gc>, Error> {
+    ) -> Result<Value<'gc>, Error<'gc>> {
         let read = self.0.read();
 
         if name.namespace().is_public() {
@@ -109,7 +109,7 @@ impl<'gc> TObject<'gc> for ArrayObject<'gc> {
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         let mut write = self.0.write(activation.context.gc_context);
 
         if name.namespace().is_public() {
@@ -137,7 +137,7 @@ impl<'gc> TObject<'gc> for ArrayObject<'gc> {
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         let mut write = self.0.write(activation.context.gc_context);
 
         if name.namespace().is_public() {
@@ -178,7 +178,7 @@ impl<'gc> TObject<'gc> for ArrayObject<'gc> {
         self.0.write(gc_context).base.delete_property(name)
     }
 
-    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error> {
+    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
         if name.namespace().is_public() {
             if let Ok(index) = name.local_name().parse::<usize>() {
                 return Ok(self.0.read().array.get(index).is_some());
@@ -188,7 +188,7 @@ impl<'gc> TObject<'gc> for ArrayObject<'gc> {
         self.0.read().base.has_own_property(name)
     }
 
-    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error> {
+    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
         if let Ok(index) = local_name.parse::<usize>() {
             if self.0.read().array.get(index).is_some() {
                 return Ok(Some(Namespace::public()));
@@ -201,15 +201,15 @@ impl<'gc> TObject<'gc> for ArrayObject<'gc> {
     fn resolve_any_trait(
         self,
         local_name: AvmString<'gc>,
-    ) -> Result<Option<Namespace<'gc>>, Error> {
+    ) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
         self.0.read().base.resolve_any_trait(local_name)
     }
 
-    fn to_string(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn to_string(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         Ok(Value::Object(Object::from(*self)))
     }
 
-    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         Ok(Value::Object(Object::from(*self)))
     }
 
@@ -228,7 +228,7 @@ impl<'gc> TObject<'gc> for ArrayObject<'gc> {
         &self,
         activation: &mut Activation<'_, 'gc, '_>,
         _args: &[Value<'gc>],
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let this: Object<'gc> = Object::ArrayObject(*self);
         let base = ScriptObjectData::base_new(Some(this), ScriptObjectClass::NoClass);
 
@@ -247,7 +247,7 @@ impl<'gc> TObject<'gc> for ArrayObject<'gc> {
         activation: &mut Activation<'_, 'gc, '_>,
         class: GcCell<'gc, Class<'gc>>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let this: Object<'gc> = Object::ArrayObject(*self);
         let base = ScriptObjectData::base_new(
             Some(this),
@@ -0,0 +1,261 @@
+//! Object representation for byte arrays
+
+use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::class::Class;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::impl_avm2_custom_object;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// An Object which stores a buffer of bytes, indexable by number.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct ByteArrayObject<'gc>(GcCell<'gc, ByteArrayObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct ByteArrayObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The bytes this object holds.
+    storage: ByteArrayStorage,
+}
+
+impl<'gc> ByteArrayObject<'gc> {
+    /// Wrap existing byte array storage in an object.
+    pub fn from_storage(
+        mc: MutationContext<'gc, '_>,
+        base_proto: Option<Object<'gc>>,
+        storage: ByteArrayStorage,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(base_proto, ScriptObjectClass::NoClass);
+
+        ByteArrayObject(GcCell::allocate(mc, ByteArrayObjectData { base, storage })).into()
+    }
+
+    /// Instantiate a byte array subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        ByteArrayObject(GcCell::allocate(
+            mc,
+            ByteArrayObjectData {
+                base,
+                storage: ByteArrayStorage::new(),
+            },
+        ))
+        .into()
+    }
+
+    /// Overwrite a single byte, growing the array if the index is past its
+    /// end. The position is left untouched.
+    fn set_byte(
+        self,
+        mc: MutationContext<'gc, '_>,
+        index: usize,
+        byte: u8,
+    ) -> Result<(), Error<'gc>> {
+        let mut write = self.0.write(mc);
+
+        if write.storage.len() <= index {
+            write.storage.set_len(index + 1)?;
+        }
+
+        write.storage.bytes_mut()[index] = byte;
+
+        Ok(())
+    }
+}
+
+impl<'gc> TObject<'gc> for ByteArrayObject<'gc> {
+    impl_avm2_custom_object!(base);
+
+    fn get_property_local(
+        self,
+        receiver: Object<'gc>,
+        name: &QName<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let read = self.0.read();
+
+        if name.namespace().is_public() {
+            if let Ok(index) = name.local_name().parse::<usize>() {
+                return Ok(read
+                    .storage
+                    .bytes()
+                    .get(index)
+                    .map(|b| Value::Unsigned((*b).into()))
+                    .unwrap_or(Value::Undefined));
+            }
+        }
+
+        let rv = read.base.get_property_local(receiver, name, activation)?;
+
+        drop(read);
+
+        rv.resolve(activation)
+    }
+
+    fn set_property_local(
+        self,
+        receiver: Object<'gc>,
+        name: &QName<'gc>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error<'gc>> {
+        if name.namespace().is_public() {
+            if let Ok(index) = name.local_name().parse::<usize>() {
+                let byte = value.coerce_to_i32(activation)? as u8;
+                self.set_byte(activation.context.gc_context, index, byte)?;
+
+                return Ok(());
+            }
+        }
+
+        let mut write = self.0.write(activation.context.gc_context);
+        let rv = write
+            .base
+            .set_property_local(receiver, name, value, activation)?;
+
+        drop(write);
+
+        rv.resolve(activation)?;
+
+        Ok(())
+    }
+
+    fn init_property_local(
+        self,
+        receiver: Object<'gc>,
+        name: &QName<'gc>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error<'gc>> {
+        if name.namespace().is_public() {
+            if let Ok(index) = name.local_name().parse::<usize>() {
+                let byte = value.coerce_to_i32(activation)? as u8;
+                self.set_byte(activation.context.gc_context, index, byte)?;
+
+                return Ok(());
+            }
+        }
+
+        let mut write = self.0.write(activation.context.gc_context);
+        let rv = write
+            .base
+            .init_property_local(receiver, name, value, activation)?;
+
+        drop(write);
+
+        rv.resolve(activation)?;
+
+        Ok(())
+    }
+
+    fn is_property_overwritable(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        name: &QName<'gc>,
+    ) -> bool {
+        self.0.write(gc_context).base.is_property_overwritable(name)
+    }
+
+    fn delete_property(&self, gc_context: MutationContext<'gc, '_>, name: &QName<'gc>) -> bool {
+        if name.namespace().is_public() && name.local_name().parse::<usize>().is_ok() {
+            // Bytes can't be deleted, only overwritten.
+            return false;
+        }
+
+        self.0.write(gc_context).base.delete_property(name)
+    }
+
+    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
+        if name.namespace().is_public() {
+            if let Ok(index) = name.local_name().parse::<usize>() {
+                return Ok(index < self.0.read().storage.len());
+            }
+        }
+
+        self.0.read().base.has_own_property(name)
+    }
+
+    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
+        if let Ok(index) = local_name.parse::<usize>() {
+            if index < self.0.read().storage.len() {
+                return Ok(Some(Namespace::public()));
+            }
+        }
+
+        self.0.read().base.resolve_any(local_name)
+    }
+
+    fn resolve_any_trait(
+        self,
+        local_name: AvmString<'gc>,
+    ) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
+        self.0.read().base.resolve_any_trait(local_name)
+    }
+
+    fn to_string(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_bytearray(&self) -> Option<Ref<ByteArrayStorage>> {
+        Some(Ref::map(self.0.read(), |bod| &bod.storage))
+    }
+
+    fn as_bytearray_mut(&self, mc: MutationContext<'gc, '_>) -> Option<RefMut<ByteArrayStorage>> {
+        Some(RefMut::map(self.0.write(mc), |bod| &mut bod.storage))
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::ByteArrayObject(*self);
+
+        Ok(ByteArrayObject::from_storage(
+            activation.context.gc_context,
+            Some(this),
+            ByteArrayStorage::new(),
+        ))
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::ByteArrayObject(*self);
+
+        Ok(Self::derive(
+            this,
+            activation.context.gc_context,
+            class,
+            scope,
+        ))
+    }
+}
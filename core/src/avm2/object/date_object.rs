@@ -0,0 +1,141 @@
+//! Object representation for dates
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::{Hint, Value};
+use crate::avm2::Error;
+use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
+use chrono::{DateTime, Utc};
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::f64::NAN;
+
+/// An Object which holds a point in time.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct DateObject<'gc>(GcCell<'gc, DateObjectData<'gc>>);
+
+#[derive(Debug, Clone)]
+pub struct DateObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The time this object holds, or `None` for an invalid date.
+    date_time: Option<DateTime<Utc>>,
+}
+
+unsafe impl<'gc> Collect for DateObjectData<'gc> {
+    fn trace(&self, cc: gc_arena::CollectionContext) {
+        self.base.trace(cc);
+    }
+}
+
+impl<'gc> DateObject<'gc> {
+    /// Wrap a point in time in an object.
+    pub fn from_date_time(
+        mc: MutationContext<'gc, '_>,
+        base_proto: Option<Object<'gc>>,
+        date_time: Option<DateTime<Utc>>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(base_proto, ScriptObjectClass::NoClass);
+
+        DateObject(GcCell::allocate(mc, DateObjectData { base, date_time })).into()
+    }
+
+    /// Instantiate a date subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        DateObject(GcCell::allocate(
+            mc,
+            DateObjectData {
+                base,
+                date_time: None,
+            },
+        ))
+        .into()
+    }
+
+    /// The time this object holds, or `None` for an invalid date.
+    pub fn date_time(self) -> Option<DateTime<Utc>> {
+        self.0.read().date_time
+    }
+
+    /// Replace the time this object holds.
+    pub fn set_date_time(self, mc: MutationContext<'gc, '_>, date_time: Option<DateTime<Utc>>) {
+        self.0.write(mc).date_time = date_time;
+    }
+
+    /// The time this object holds, in milliseconds since the epoch.
+    ///
+    /// Invalid dates yield `NaN`.
+    pub fn time(self) -> f64 {
+        self.date_time()
+            .map(|date_time| date_time.timestamp_millis() as f64)
+            .unwrap_or(NAN)
+    }
+}
+
+impl<'gc> TObject<'gc> for DateObject<'gc> {
+    impl_avm2_custom_object!(base);
+    impl_avm2_custom_object_properties!(base);
+
+    fn default_hint(&self) -> Hint {
+        Hint::String
+    }
+
+    fn to_string(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(self.time().into())
+    }
+
+    fn as_date_object(self) -> Option<DateObject<'gc>> {
+        Some(self)
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::DateObject(*self);
+
+        Ok(DateObject::from_date_time(
+            activation.context.gc_context,
+            Some(this),
+            None,
+        ))
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::DateObject(*self);
+
+        Ok(Self::derive(
+            this,
+            activation.context.gc_context,
+            class,
+            scope,
+        ))
+    }
+}
@@ -0,0 +1,251 @@
+//! Object representation for dictionaries
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
+use fnv::FnvHashMap;
+use gc_arena::{Collect, GcCell, MutationContext};
+
+/// An Object which can key properties by other objects as well as by name.
+///
+/// Primitive keys are stored as ordinary dynamic properties, as they are
+/// converted to names anyway. Object keys, including functions, are compared
+/// by identity and kept separately.
+///
+/// TODO: Object keys are always held strongly, even in dictionaries created
+/// with `weakKeys`, as our garbage collector has no weak references yet.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct DictionaryObject<'gc>(GcCell<'gc, DictionaryObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct DictionaryObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// Values keyed by object identity, and the position of each key in
+    /// `object_keys`.
+    object_space: FnvHashMap<Object<'gc>, (usize, Value<'gc>)>,
+
+    /// Object keys in the order they were added, which is the order they are
+    /// enumerated in.
+    ///
+    /// Deleted keys leave a hole, so that deleting keys while enumerating
+    /// the dictionary doesn't move the keys after them. Holes are only
+    /// removed when a new key is added.
+    object_keys: Vec<Option<Object<'gc>>>,
+}
+
+impl<'gc> DictionaryObject<'gc> {
+    /// Construct an empty dictionary.
+    pub fn new(mc: MutationContext<'gc, '_>, base_proto: Option<Object<'gc>>) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(base_proto, ScriptObjectClass::NoClass);
+
+        DictionaryObject(GcCell::allocate(
+            mc,
+            DictionaryObjectData {
+                base,
+                object_space: FnvHashMap::default(),
+                object_keys: Vec::new(),
+            },
+        ))
+        .into()
+    }
+
+    /// Instantiate a dictionary subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        DictionaryObject(GcCell::allocate(
+            mc,
+            DictionaryObjectData {
+                base,
+                object_space: FnvHashMap::default(),
+                object_keys: Vec::new(),
+            },
+        ))
+        .into()
+    }
+
+    /// Retrieve a value keyed by an object.
+    pub fn get_property_by_object(self, key: Object<'gc>) -> Value<'gc> {
+        self.0
+            .read()
+            .object_space
+            .get(&key)
+            .map(|(_, value)| value.clone())
+            .unwrap_or(Value::Undefined)
+    }
+
+    /// Set a value keyed by an object.
+    pub fn set_property_by_object(
+        self,
+        key: Object<'gc>,
+        value: Value<'gc>,
+        mc: MutationContext<'gc, '_>,
+    ) {
+        let mut write = self.0.write(mc);
+        if let Some((_, old_value)) = write.object_space.get_mut(&key) {
+            *old_value = value;
+            return;
+        }
+
+        if write.object_keys.len() >= 2 * write.object_space.len() {
+            write.remove_holes();
+        }
+
+        let position = write.object_keys.len();
+        write.object_keys.push(Some(key));
+        write.object_space.insert(key, (position, value));
+    }
+
+    /// Delete a value keyed by an object.
+    ///
+    /// As with other dynamic properties, this succeeds whether or not the key
+    /// was present.
+    pub fn delete_property_by_object(self, key: Object<'gc>, mc: MutationContext<'gc, '_>) -> bool {
+        let mut write = self.0.write(mc);
+        if let Some((position, _)) = write.object_space.remove(&key) {
+            write.object_keys[position] = None;
+        }
+
+        true
+    }
+
+    /// Determine if a value is keyed by an object.
+    pub fn has_property_by_object(self, key: Object<'gc>) -> bool {
+        self.0.read().object_space.contains_key(&key)
+    }
+
+    /// Retrieve the object key of a given enumerant.
+    ///
+    /// Object keys are enumerated after all of the named properties. This
+    /// yields `None` for the holes left by deleted keys.
+    fn get_enumerant_object(self, index: u32) -> Option<Object<'gc>> {
+        let read = self.0.read();
+        let object_index = index.checked_sub(read.base.enumerant_count() + 1)?;
+
+        read.object_keys
+            .get(object_index as usize)
+            .copied()
+            .flatten()
+    }
+}
+
+impl<'gc> DictionaryObjectData<'gc> {
+    /// Remove the holes left in the key order by deleted keys.
+    fn remove_holes(&mut self) {
+        self.object_keys.retain(Option::is_some);
+
+        let object_space = &mut self.object_space;
+        for (position, key) in self.object_keys.iter().flatten().enumerate() {
+            if let Some((key_position, _)) = object_space.get_mut(key) {
+                *key_position = position;
+            }
+        }
+    }
+}
+
+impl<'gc> TObject<'gc> for DictionaryObject<'gc> {
+    impl_avm2_custom_object!(base);
+    impl_avm2_custom_object_properties!(base);
+
+    fn get_next_enumerant(self, last_index: u32) -> Option<u32> {
+        let read = self.0.read();
+        let named_count = read.base.enumerant_count();
+        if last_index < named_count {
+            return Some(last_index + 1);
+        }
+
+        // Skip over the holes left by deleted keys.
+        let object_index = (last_index - named_count) as usize;
+        read.object_keys
+            .get(object_index..)?
+            .iter()
+            .position(Option::is_some)
+            .map(|offset| last_index + offset as u32 + 1)
+    }
+
+    fn get_enumerant_key(&self, index: u32) -> Option<Value<'gc>> {
+        if let Some(name) = self.0.read().base.get_enumerant_name(index) {
+            return Some(name.local_name().into());
+        }
+
+        self.get_enumerant_object(index).map(Value::Object)
+    }
+
+    fn get_enumerant_value(
+        self,
+        index: u32,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let name = self.0.read().base.get_enumerant_name(index);
+        if let Some(name) = name {
+            let mut this: Object<'gc> = self.into();
+            return this.get_property(this, &name, activation);
+        }
+
+        Ok(self
+            .get_enumerant_object(index)
+            .map(|key| self.get_property_by_object(key))
+            .unwrap_or(Value::Undefined))
+    }
+
+    fn to_string(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_dictionary_object(self) -> Option<DictionaryObject<'gc>> {
+        Some(self)
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::DictionaryObject(*self);
+
+        Ok(DictionaryObject::new(
+            activation.context.gc_context,
+            Some(this),
+        ))
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::DictionaryObject(*self);
+
+        Ok(Self::derive(
+            this,
+            activation.context.gc_context,
+            class,
+            scope,
+        ))
+    }
+}
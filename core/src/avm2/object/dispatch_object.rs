@@ -3,7 +3,7 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
 use crate::avm2::events::DispatchList;
-use crate::avm2::names::{Namespace, QName};
+use crate::avm2::names::{Multiname, Namespace, QName};
 use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
 use crate::avm2::object::{Object, ObjectPtr, TObject};
 use crate::avm2::scope::Scope;
@@ -78,7 +78,7 @@ impl<'gc> TObject<'gc> for DispatchObject<'gc> {
         &self,
         _activation: &mut Activation<'_, 'gc, '_>,
         _args: &[Value<'gc>],
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         Err("Cannot construct internal event dispatcher structures.".into())
     }
 
@@ -87,11 +87,11 @@ impl<'gc> TObject<'gc> for DispatchObject<'gc> {
         _activation: &mut Activation<'_, 'gc, '_>,
         _class: GcCell<'gc, Class<'gc>>,
         _scope: Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         Err("Cannot subclass internal event dispatcher structures.".into())
     }
 
-    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         Err("Cannot subclass internal event dispatcher structures.".into())
     }
 
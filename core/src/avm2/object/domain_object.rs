@@ -3,7 +3,7 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
 use crate::avm2::domain::Domain;
-use crate::avm2::names::{Namespace, QName};
+use crate::avm2::names::{Multiname, Namespace, QName};
 use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
 use crate::avm2::object::{Object, ObjectPtr, TObject};
 use crate::avm2::scope::Scope;
@@ -46,7 +46,7 @@ impl<'gc> DomainObject<'gc> {
         domain: Domain<'gc>,
         class: GcCell<'gc, Class<'gc>>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let base = ScriptObjectData::base_new(
             Some(base_proto),
             ScriptObjectClass::InstancePrototype(class, scope),
@@ -64,7 +64,7 @@ impl<'gc> TObject<'gc> for DomainObject<'gc> {
         Some(self.0.read().domain)
     }
 
-    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         let this: Object<'gc> = Object::DomainObject(*self);
 
         Ok(this.into())
@@ -74,7 +74,7 @@ impl<'gc> TObject<'gc> for DomainObject<'gc> {
         &self,
         activation: &mut Activation<'_, 'gc, '_>,
         args: &[Value<'gc>],
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let this: Object<'gc> = Object::DomainObject(*self);
         let parent_domain = if let Some(parent_domain) = args
             .get(0)
@@ -100,7 +100,7 @@ impl<'gc> TObject<'gc> for DomainObject<'gc> {
         activation: &mut Activation<'_, 'gc, '_>,
         _class: GcCell<'gc, Class<'gc>>,
         _scope: Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let this: Object<'gc> = Object::DomainObject(*self);
         Ok(DomainObject::from_domain(
             activation.context.gc_context,
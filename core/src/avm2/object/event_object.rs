@@ -3,7 +3,7 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
 use crate::avm2::events::Event;
-use crate::avm2::names::{Namespace, QName};
+use crate::avm2::names::{Multiname, Namespace, QName};
 use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
 use crate::avm2::object::{Object, ObjectPtr, TObject};
 use crate::avm2::scope::Scope;
@@ -72,7 +72,7 @@ impl<'gc> TObject<'gc> for EventObject<'gc> {
         &self,
         activation: &mut Activation<'_, 'gc, '_>,
         _args: &[Value<'gc>],
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let this: Object<'gc> = Object::EventObject(*self);
         Ok(EventObject::from_event(
             activation.context.gc_context,
@@ -86,7 +86,7 @@ impl<'gc> TObject<'gc> for EventObject<'gc> {
         activation: &mut Activation<'_, 'gc, '_>,
         class: GcCell<'gc, Class<'gc>>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let this: Object<'gc> = Object::EventObject(*self);
 
         Ok(Self::derive(
@@ -97,7 +97,7 @@ impl<'gc> TObject<'gc> for EventObject<'gc> {
         ))
     }
 
-    fn value_of(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn value_of(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         let read = self.0.read();
         let event_type = read.event.event_type();
         let bubbles = read.event.is_bubbling();
@@ -4,7 +4,7 @@ use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
 use crate::avm2::function::Executable;
 use crate::avm2::method::{Method, NativeMethod};
-use crate::avm2::names::{Namespace, QName};
+use crate::avm2::names::{Multiname, Namespace, QName};
 use crate::avm2::object::script_object::{ScriptObject, ScriptObjectClass, ScriptObjectData};
 use crate::avm2::object::{Object, ObjectPtr, TObject};
 use crate::avm2::scope::Scope;
@@ -35,7 +35,7 @@ pub fn implicit_deriver<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     class: GcCell<'gc, Class<'gc>>,
     scope: Option<GcCell<'gc, Scope<'gc>>>,
-) -> Result<Object<'gc>, Error> {
+) -> Result<Object<'gc>, Error<'gc>> {
     base_proto.derive(activation, class, scope)
 }
 
@@ -55,7 +55,7 @@ impl<'gc> FunctionObject<'gc> {
         class: GcCell<'gc, Class<'gc>>,
         base_class: Option<Object<'gc>>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<(Object<'gc>, Object<'gc>), Error> {
+    ) -> Result<(Object<'gc>, Object<'gc>), Error<'gc>> {
         FunctionObject::from_class_with_deriver(
             activation,
             class,
@@ -79,18 +79,18 @@ impl<'gc> FunctionObject<'gc> {
         base_class: Option<Object<'gc>>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
         derive: DERIVE,
-    ) -> Result<(Object<'gc>, Object<'gc>), Error>
+    ) -> Result<(Object<'gc>, Object<'gc>), Error<'gc>>
     where
         DERIVE: FnOnce(
             Object<'gc>,
             &mut Activation<'_, 'gc, '_>,
             GcCell<'gc, Class<'gc>>,
             Option<GcCell<'gc, Scope<'gc>>>,
-        ) -> Result<Object<'gc>, Error>,
+        ) -> Result<Object<'gc>, Error<'gc>>,
     {
         let class_read = class.read();
         let class_proto = if let Some(mut base_class) = base_class {
-            let super_proto: Result<_, Error> = base_class
+            let super_proto: Result<_, Error<'gc>> = base_class
                 .get_property(
                     base_class,
                     &QName::new(Namespace::public(), "prototype"),
@@ -123,7 +123,7 @@ impl<'gc> FunctionObject<'gc> {
         class: GcCell<'gc, Class<'gc>>,
         mut class_proto: Object<'gc>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<(Object<'gc>, Object<'gc>), Error> {
+    ) -> Result<(Object<'gc>, Object<'gc>), Error<'gc>> {
         let mut interfaces = Vec::new();
         let interface_names = class.read().interfaces().to_vec();
         for interface_name in interface_names {
@@ -246,7 +246,7 @@ impl<'gc> FunctionObject<'gc> {
         constr: NativeMethod<'gc>,
         mut prototype: Object<'gc>,
         fn_proto: Object<'gc>,
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let scope = prototype.get_scope();
         let class = prototype
             .as_class()
@@ -280,7 +280,7 @@ impl<'gc> TObject<'gc> for FunctionObject<'gc> {
     impl_avm2_custom_object!(base);
     impl_avm2_custom_object_properties!(base);
 
-    fn to_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn to_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         if let ScriptObjectClass::ClassConstructor(class, ..) = self.0.read().base.class() {
             Ok(AvmString::new(mc, format!("[class {}]", class.read().name().local_name())).into())
         } else {
@@ -288,11 +288,11 @@ impl<'gc> TObject<'gc> for FunctionObject<'gc> {
         }
     }
 
-    fn to_locale_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn to_locale_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         self.to_string(mc)
     }
 
-    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         Ok(Value::Object(Object::from(*self)))
     }
 
@@ -306,7 +306,7 @@ impl<'gc> TObject<'gc> for FunctionObject<'gc> {
         arguments: &[Value<'gc>],
         activation: &mut Activation<'_, 'gc, '_>,
         base_proto: Option<Object<'gc>>,
-    ) -> Result<Value<'gc>, Error> {
+    ) -> Result<Value<'gc>, Error<'gc>> {
         if let Some(exec) = &self.0.read().exec {
             exec.exec(receiver, arguments, activation, base_proto, self.into())
         } else {
@@ -318,7 +318,7 @@ impl<'gc> TObject<'gc> for FunctionObject<'gc> {
         &self,
         activation: &mut Activation<'_, 'gc, '_>,
         _args: &[Value<'gc>],
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let this: Object<'gc> = Object::FunctionObject(*self);
         let base = ScriptObjectData::base_new(Some(this), ScriptObjectClass::NoClass);
 
@@ -334,7 +334,7 @@ impl<'gc> TObject<'gc> for FunctionObject<'gc> {
         activation: &mut Activation<'_, 'gc, '_>,
         class: GcCell<'gc, Class<'gc>>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let this: Object<'gc> = Object::FunctionObject(*self);
         let base = ScriptObjectData::base_new(
             Some(this),
@@ -0,0 +1,281 @@
+//! Object representation for loader info
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::domain::Domain;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorKind};
+use crate::display_object::DisplayObject;
+use crate::tag_utils::SwfMovie;
+use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::sync::Arc;
+
+/// An Object which describes content loaded into a `Loader`, or the root
+/// movie.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct LoaderInfoObject<'gc>(GcCell<'gc, LoaderInfoObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct LoaderInfoObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The `Loader` that loads the content, or `None` for the root movie.
+    loader: Option<Object<'gc>>,
+
+    /// The URL that the content is loaded from.
+    url: Option<String>,
+
+    /// The MIME type of the loaded content.
+    content_type: Option<String>,
+
+    /// The loaded movie, if the content is a SWF.
+    movie: Option<Arc<SwfMovie>>,
+
+    /// The display object that holds the loaded content.
+    content: Option<DisplayObject<'gc>>,
+
+    /// The application domain that the loaded movie's code runs in.
+    domain: Option<Domain<'gc>>,
+
+    /// The number of bytes of the content that have been loaded.
+    bytes_loaded: usize,
+
+    /// The size of the content in bytes.
+    bytes_total: usize,
+
+    /// Whether the `complete` event has been dispatched for the content.
+    complete: bool,
+}
+
+impl<'gc> LoaderInfoObject<'gc> {
+    /// Describe a `Loader` that has not loaded anything yet.
+    pub fn from_loader(
+        mc: MutationContext<'gc, '_>,
+        base_proto: Option<Object<'gc>>,
+        loader: Option<Object<'gc>>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(base_proto, ScriptObjectClass::NoClass);
+
+        LoaderInfoObject(GcCell::allocate(
+            mc,
+            LoaderInfoObjectData {
+                base,
+                loader,
+                url: None,
+                content_type: None,
+                movie: None,
+                content: None,
+                domain: None,
+                bytes_loaded: 0,
+                bytes_total: 0,
+                complete: false,
+            },
+        ))
+        .into()
+    }
+
+    /// Describe a movie that has been loaded in full into a display object.
+    pub fn from_movie(
+        mc: MutationContext<'gc, '_>,
+        base_proto: Option<Object<'gc>>,
+        movie: Arc<SwfMovie>,
+        content: DisplayObject<'gc>,
+        domain: Option<Domain<'gc>>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(base_proto, ScriptObjectClass::NoClass);
+        let length = movie.header().uncompressed_length as usize;
+
+        LoaderInfoObject(GcCell::allocate(
+            mc,
+            LoaderInfoObjectData {
+                base,
+                loader: None,
+                url: movie.url().map(|url| url.to_string()),
+                content_type: Some("application/x-shockwave-flash".to_string()),
+                movie: Some(movie),
+                content: Some(content),
+                domain,
+                bytes_loaded: length,
+                bytes_total: length,
+                complete: false,
+            },
+        ))
+        .into()
+    }
+
+    /// Instantiate a loader info subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        LoaderInfoObject(GcCell::allocate(
+            mc,
+            LoaderInfoObjectData {
+                base,
+                loader: None,
+                url: None,
+                content_type: None,
+                movie: None,
+                content: None,
+                domain: None,
+                bytes_loaded: 0,
+                bytes_total: 0,
+                complete: false,
+            },
+        ))
+        .into()
+    }
+
+    /// The `Loader` that loads the content, or `None` for the root movie.
+    pub fn loader(self) -> Option<Object<'gc>> {
+        self.0.read().loader
+    }
+
+    /// The URL that the content is loaded from.
+    pub fn url(self) -> Option<String> {
+        self.0.read().url.clone()
+    }
+
+    /// Start describing a new load from the given URL.
+    ///
+    /// Anything that was loaded before is forgotten.
+    pub fn begin_load(self, mc: MutationContext<'gc, '_>, url: Option<String>) {
+        let mut write = self.0.write(mc);
+
+        write.url = url;
+        write.content_type = None;
+        write.movie = None;
+        write.content = None;
+        write.domain = None;
+        write.bytes_loaded = 0;
+        write.bytes_total = 0;
+        write.complete = false;
+    }
+
+    /// The MIME type of the loaded content.
+    pub fn content_type(self) -> Option<String> {
+        self.0.read().content_type.clone()
+    }
+
+    /// The loaded movie, if the content is a SWF.
+    pub fn movie(self) -> Option<Arc<SwfMovie>> {
+        self.0.read().movie.clone()
+    }
+
+    /// The display object that holds the loaded content.
+    pub fn content(self) -> Option<DisplayObject<'gc>> {
+        self.0.read().content
+    }
+
+    /// Record the content that has been loaded.
+    pub fn set_content(
+        self,
+        mc: MutationContext<'gc, '_>,
+        content: DisplayObject<'gc>,
+        content_type: &str,
+        movie: Option<Arc<SwfMovie>>,
+        domain: Option<Domain<'gc>>,
+    ) {
+        let mut write = self.0.write(mc);
+
+        write.content = Some(content);
+        write.content_type = Some(content_type.to_string());
+        write.movie = movie;
+        write.domain = domain;
+    }
+
+    /// The application domain that the loaded movie's code runs in.
+    pub fn domain(self) -> Option<Domain<'gc>> {
+        self.0.read().domain
+    }
+
+    /// The number of bytes of the content that have been loaded.
+    pub fn bytes_loaded(self) -> usize {
+        self.0.read().bytes_loaded
+    }
+
+    /// The size of the content in bytes.
+    pub fn bytes_total(self) -> usize {
+        self.0.read().bytes_total
+    }
+
+    /// Record how much of the content has been loaded.
+    pub fn set_progress(
+        self,
+        mc: MutationContext<'gc, '_>,
+        bytes_loaded: usize,
+        bytes_total: usize,
+    ) {
+        let mut write = self.0.write(mc);
+
+        write.bytes_loaded = bytes_loaded;
+        write.bytes_total = bytes_total;
+    }
+
+    /// Whether the `complete` event has been dispatched for the content.
+    pub fn is_complete(self) -> bool {
+        self.0.read().complete
+    }
+
+    /// Record that the `complete` event has been dispatched for the content.
+    pub fn set_complete(self, mc: MutationContext<'gc, '_>) {
+        self.0.write(mc).complete = true;
+    }
+}
+
+impl<'gc> TObject<'gc> for LoaderInfoObject<'gc> {
+    impl_avm2_custom_object!(base);
+    impl_avm2_custom_object_properties!(base);
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_loader_info_object(self) -> Option<LoaderInfoObject<'gc>> {
+        Some(self)
+    }
+
+    fn construct(
+        &self,
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        Err(Error::new(
+            ErrorKind::ArgumentError,
+            "Error #2012: LoaderInfo class cannot be instantiated.",
+        ))
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::LoaderInfoObject(*self);
+
+        Ok(Self::derive(
+            this,
+            activation.context.gc_context,
+            class,
+            scope,
+        ))
+    }
+}
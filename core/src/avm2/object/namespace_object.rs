@@ -3,7 +3,7 @@
 use crate::avm1::AvmString;
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
-use crate::avm2::names::{Namespace, QName};
+use crate::avm2::names::{Multiname, Namespace, QName};
 use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
 use crate::avm2::object::{Object, ObjectPtr, TObject};
 use crate::avm2::scope::Scope;
@@ -27,6 +27,12 @@ pub struct NamespaceObjectData<'gc> {
 
     /// The namespace name this object is associated with.
     namespace: Namespace<'gc>,
+
+    /// The prefix of this namespace, if it has one.
+    ///
+    /// A prefix of `None` indicates that the prefix is unknown, which is not
+    /// the same as the empty prefix.
+    prefix: Option<AvmString<'gc>>,
 }
 
 impl<'gc> NamespaceObject<'gc> {
@@ -35,12 +41,16 @@ impl<'gc> NamespaceObject<'gc> {
         namespace: Namespace<'gc>,
         base_proto: Object<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let base = ScriptObjectData::base_new(Some(base_proto), ScriptObjectClass::NoClass);
 
         Ok(NamespaceObject(GcCell::allocate(
             mc,
-            NamespaceObjectData { base, namespace },
+            NamespaceObjectData {
+                base,
+                namespace,
+                prefix: None,
+            },
         ))
         .into())
     }
@@ -51,7 +61,7 @@ impl<'gc> NamespaceObject<'gc> {
         mc: MutationContext<'gc, '_>,
         class: GcCell<'gc, Class<'gc>>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let base = ScriptObjectData::base_new(
             Some(base_proto),
             ScriptObjectClass::InstancePrototype(class, scope),
@@ -62,21 +72,40 @@ impl<'gc> NamespaceObject<'gc> {
             NamespaceObjectData {
                 base,
                 namespace: Namespace::public(),
+                prefix: None,
             },
         ))
         .into())
     }
+
+    /// The prefix of this namespace, if it has one.
+    pub fn prefix(self) -> Option<AvmString<'gc>> {
+        self.0.read().prefix
+    }
+
+    /// Change the namespace and prefix this object holds.
+    pub fn init_namespace(
+        self,
+        mc: MutationContext<'gc, '_>,
+        namespace: Namespace<'gc>,
+        prefix: Option<AvmString<'gc>>,
+    ) {
+        let mut write = self.0.write(mc);
+
+        write.namespace = namespace;
+        write.prefix = prefix;
+    }
 }
 
 impl<'gc> TObject<'gc> for NamespaceObject<'gc> {
     impl_avm2_custom_object!(base);
     impl_avm2_custom_object_properties!(base);
 
-    fn to_string(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn to_string(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         Ok(self.0.read().namespace.as_uri().into())
     }
 
-    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         Ok(self.0.read().namespace.as_uri().into())
     }
 
@@ -84,11 +113,15 @@ impl<'gc> TObject<'gc> for NamespaceObject<'gc> {
         Some(Ref::map(self.0.read(), |s| &s.namespace))
     }
 
+    fn as_namespace_object(self) -> Option<NamespaceObject<'gc>> {
+        Some(self)
+    }
+
     fn construct(
         &self,
         activation: &mut Activation<'_, 'gc, '_>,
         _args: &[Value<'gc>],
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let this: Object<'gc> = Object::NamespaceObject(*self);
         let base = ScriptObjectData::base_new(Some(this), ScriptObjectClass::NoClass);
 
@@ -97,6 +130,7 @@ impl<'gc> TObject<'gc> for NamespaceObject<'gc> {
             NamespaceObjectData {
                 base,
                 namespace: Namespace::public(),
+                prefix: None,
             },
         ))
         .into())
@@ -107,7 +141,7 @@ impl<'gc> TObject<'gc> for NamespaceObject<'gc> {
         activation: &mut Activation<'_, 'gc, '_>,
         class: GcCell<'gc, Class<'gc>>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let this: Object<'gc> = Object::NamespaceObject(*self);
         let base = ScriptObjectData::base_new(
             Some(this),
@@ -119,6 +153,7 @@ impl<'gc> TObject<'gc> for NamespaceObject<'gc> {
             NamespaceObjectData {
                 base,
                 namespace: Namespace::public(),
+                prefix: None,
             },
         ))
         .into())
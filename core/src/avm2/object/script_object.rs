@@ -2,7 +2,7 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
-use crate::avm2::names::{Namespace, QName};
+use crate::avm2::names::{Multiname, Namespace, QName};
 use crate::avm2::object::{Object, ObjectPtr, TObject};
 use crate::avm2::property::Property;
 use crate::avm2::property_map::PropertyMap;
@@ -79,7 +79,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         receiver: Object<'gc>,
         name: &QName<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Value<'gc>, Error> {
+    ) -> Result<Value<'gc>, Error<'gc>> {
         let rv = self
             .0
             .read()
@@ -94,7 +94,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         let rv = self
             .0
             .write(activation.context.gc_context)
@@ -111,7 +111,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         let rv = self
             .0
             .write(activation.context.gc_context)
@@ -134,7 +134,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         self.0.write(gc_context).delete_property(name)
     }
 
-    fn get_slot(self, id: u32) -> Result<Value<'gc>, Error> {
+    fn get_slot(self, id: u32) -> Result<Value<'gc>, Error<'gc>> {
         self.0.read().get_slot(id)
     }
 
@@ -143,7 +143,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         id: u32,
         value: Value<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0.write(mc).set_slot(id, value, mc)
     }
 
@@ -152,15 +152,23 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         id: u32,
         value: Value<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0.write(mc).init_slot(id, value, mc)
     }
 
+    fn get_slot_type(self, id: u32) -> Option<Multiname<'gc>> {
+        self.0.read().get_slot_type(id)
+    }
+
+    fn get_property_slot_id(self, name: &QName<'gc>) -> Option<u32> {
+        self.0.read().get_property_slot_id(name)
+    }
+
     fn get_method(self, id: u32) -> Option<Object<'gc>> {
         self.0.read().get_method(id)
     }
 
-    fn get_trait(self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error> {
+    fn get_trait(self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error<'gc>> {
         self.0.read().get_trait(name)
     }
 
@@ -168,7 +176,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         &self,
         name: &QName<'gc>,
         known_traits: &mut Vec<Trait<'gc>>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0.read().get_provided_trait(name, known_traits)
     }
 
@@ -176,26 +184,26 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         self.0.read().get_scope()
     }
 
-    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error> {
+    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
         self.0.read().resolve_any(local_name)
     }
 
     fn resolve_any_trait(
         self,
         local_name: AvmString<'gc>,
-    ) -> Result<Option<Namespace<'gc>>, Error> {
+    ) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
         self.0.read().resolve_any_trait(local_name)
     }
 
-    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error> {
+    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
         self.0.read().has_own_property(name)
     }
 
-    fn has_trait(self, name: &QName<'gc>) -> Result<bool, Error> {
+    fn has_trait(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
         self.0.read().has_trait(name)
     }
 
-    fn provides_trait(self, name: &QName<'gc>) -> Result<bool, Error> {
+    fn provides_trait(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
         self.0.read().provides_trait(name)
     }
 
@@ -232,7 +240,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         mc: MutationContext<'gc, '_>,
         name: &QName<'gc>,
         is_enumerable: bool,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0
             .write(mc)
             .set_local_property_is_enumerable(name, is_enumerable)
@@ -246,7 +254,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         &self,
         activation: &mut Activation<'_, 'gc, '_>,
         _args: &[Value<'gc>],
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let this: Object<'gc> = Object::ScriptObject(*self);
         Ok(ScriptObject::object(activation.context.gc_context, this))
     }
@@ -256,7 +264,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         activation: &mut Activation<'_, 'gc, '_>,
         class: GcCell<'gc, Class<'gc>>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let this: Object<'gc> = Object::ScriptObject(*self);
         Ok(ScriptObject::prototype(
             activation.context.gc_context,
@@ -266,7 +274,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         ))
     }
 
-    fn to_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn to_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         if let Some(class) = self.as_proto_class() {
             Ok(AvmString::new(mc, format!("[object {}]", class.read().name().local_name())).into())
         } else {
@@ -274,7 +282,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         }
     }
 
-    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
         Ok(Value::Object(Object::from(*self)))
     }
 
@@ -294,7 +302,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         name: QName<'gc>,
         disp_id: u32,
         function: Object<'gc>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0.write(mc).install_getter(name, disp_id, function)
     }
 
@@ -304,7 +312,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         name: QName<'gc>,
         disp_id: u32,
         function: Object<'gc>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0.write(mc).install_setter(name, disp_id, function)
     }
 
@@ -313,7 +321,7 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         mc: MutationContext<'gc, '_>,
         name: QName<'gc>,
         value: Value<'gc>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.0.write(mc).install_dynamic_property(name, value)
     }
 
@@ -323,8 +331,9 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         name: QName<'gc>,
         id: u32,
         value: Value<'gc>,
+        type_name: Multiname<'gc>,
     ) {
-        self.0.write(mc).install_slot(name, id, value)
+        self.0.write(mc).install_slot(name, id, value, type_name)
     }
 
     fn install_const(
@@ -425,7 +434,7 @@ impl<'gc> ScriptObjectData<'gc> {
         receiver: Object<'gc>,
         name: &QName<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<ReturnValue<'gc>, Error> {
+    ) -> Result<ReturnValue<'gc>, Error<'gc>> {
         let prop = self.values.get(name);
 
         if let Some(prop) = prop {
@@ -441,7 +450,7 @@ impl<'gc> ScriptObjectData<'gc> {
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<ReturnValue<'gc>, Error> {
+    ) -> Result<ReturnValue<'gc>, Error<'gc>> {
         let slot_id = if let Some(prop) = self.values.get(name) {
             if let Some(slot_id) = prop.slot_id() {
                 Some(slot_id)
@@ -475,7 +484,7 @@ impl<'gc> ScriptObjectData<'gc> {
         name: &QName<'gc>,
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<ReturnValue<'gc>, Error> {
+    ) -> Result<ReturnValue<'gc>, Error<'gc>> {
         if let Some(prop) = self.values.get_mut(name) {
             if let Some(slot_id) = prop.slot_id() {
                 self.init_slot(slot_id, value, activation.context.gc_context)?;
@@ -509,12 +518,13 @@ impl<'gc> ScriptObjectData<'gc> {
 
         if can_delete {
             self.values.remove(name);
+            self.enumerants.retain(|enumerant| enumerant != name);
         }
 
         can_delete
     }
 
-    pub fn get_slot(&self, id: u32) -> Result<Value<'gc>, Error> {
+    pub fn get_slot(&self, id: u32) -> Result<Value<'gc>, Error<'gc>> {
         //TODO: slot inheritance, I think?
         self.slots
             .get(id as usize)
@@ -529,7 +539,7 @@ impl<'gc> ScriptObjectData<'gc> {
         id: u32,
         value: Value<'gc>,
         _mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         if let Some(slot) = self.slots.get_mut(id as usize) {
             slot.set(value)
         } else {
@@ -543,7 +553,7 @@ impl<'gc> ScriptObjectData<'gc> {
         id: u32,
         value: Value<'gc>,
         _mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         if let Some(slot) = self.slots.get_mut(id as usize) {
             slot.init(value)
         } else {
@@ -551,12 +561,25 @@ impl<'gc> ScriptObjectData<'gc> {
         }
     }
 
+    /// Retrieve the declared type of a slot by its index.
+    pub fn get_slot_type(&self, id: u32) -> Option<Multiname<'gc>> {
+        self.slots
+            .get(id as usize)
+            .and_then(|slot| slot.type_name())
+            .cloned()
+    }
+
+    /// Retrieve the index of the slot that backs a property, if it has one.
+    pub fn get_property_slot_id(&self, name: &QName<'gc>) -> Option<u32> {
+        self.values.get(name).and_then(|prop| prop.slot_id())
+    }
+
     /// Retrieve a method from the method table.
     pub fn get_method(&self, id: u32) -> Option<Object<'gc>> {
         self.methods.get(id as usize).and_then(|v| *v)
     }
 
-    pub fn get_trait(&self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error> {
+    pub fn get_trait(&self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error<'gc>> {
         match &self.class {
             //Class constructors have local traits only.
             ScriptObjectClass::ClassConstructor(..) => {
@@ -595,7 +618,7 @@ impl<'gc> ScriptObjectData<'gc> {
         &self,
         name: &QName<'gc>,
         known_traits: &mut Vec<Trait<'gc>>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         match &self.class {
             ScriptObjectClass::ClassConstructor(class, ..) => {
                 class.read().lookup_class_traits(name, known_traits)
@@ -607,7 +630,7 @@ impl<'gc> ScriptObjectData<'gc> {
         }
     }
 
-    pub fn has_trait(&self, name: &QName<'gc>) -> Result<bool, Error> {
+    pub fn has_trait(&self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
         match &self.class {
             //Class constructors have local traits only.
             ScriptObjectClass::ClassConstructor(..) => self.provides_trait(name),
@@ -634,7 +657,7 @@ impl<'gc> ScriptObjectData<'gc> {
         }
     }
 
-    pub fn provides_trait(&self, name: &QName<'gc>) -> Result<bool, Error> {
+    pub fn provides_trait(&self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
         match &self.class {
             ScriptObjectClass::ClassConstructor(class, ..) => {
                 Ok(class.read().has_class_trait(name))
@@ -654,7 +677,10 @@ impl<'gc> ScriptObjectData<'gc> {
         }
     }
 
-    pub fn resolve_any(&self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error> {
+    pub fn resolve_any(
+        &self,
+        local_name: AvmString<'gc>,
+    ) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
         for (key, _value) in self.values.iter() {
             if key.local_name() == local_name {
                 return Ok(Some(key.namespace().clone()));
@@ -681,7 +707,7 @@ impl<'gc> ScriptObjectData<'gc> {
     pub fn resolve_any_trait(
         &self,
         local_name: AvmString<'gc>,
-    ) -> Result<Option<Namespace<'gc>>, Error> {
+    ) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
         if let Some(proto) = self.proto {
             let proto_trait_name = proto.resolve_any_trait(local_name)?;
             if let Some(ns) = proto_trait_name {
@@ -700,7 +726,7 @@ impl<'gc> ScriptObjectData<'gc> {
         }
     }
 
-    pub fn has_own_property(&self, name: &QName<'gc>) -> Result<bool, Error> {
+    pub fn has_own_property(&self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
         Ok(self.values.get(name).is_some() || self.has_trait(name)?)
     }
 
@@ -742,6 +768,11 @@ impl<'gc> ScriptObjectData<'gc> {
         self.enumerants.get(true_index).cloned()
     }
 
+    /// Count the enumerable names on this object.
+    pub fn enumerant_count(&self) -> u32 {
+        self.enumerants.len() as u32
+    }
+
     pub fn property_is_enumerable(&self, name: &QName<'gc>) -> bool {
         self.enumerants.contains(name)
     }
@@ -750,7 +781,7 @@ impl<'gc> ScriptObjectData<'gc> {
         &mut self,
         name: &QName<'gc>,
         is_enumerable: bool,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         if is_enumerable && self.values.contains_key(name) && !self.enumerants.contains(name) {
             // Traits are never enumerable
             if self.has_trait(name)? {
@@ -802,7 +833,7 @@ impl<'gc> ScriptObjectData<'gc> {
         name: QName<'gc>,
         disp_id: u32,
         function: Object<'gc>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         function
             .as_executable()
             .ok_or_else(|| Error::from("Attempted to install getter without a valid method"))?;
@@ -836,7 +867,7 @@ impl<'gc> ScriptObjectData<'gc> {
         name: QName<'gc>,
         disp_id: u32,
         function: Object<'gc>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         function
             .as_executable()
             .ok_or_else(|| Error::from("Attempted to install setter without a valid method"))?;
@@ -864,7 +895,7 @@ impl<'gc> ScriptObjectData<'gc> {
         &mut self,
         name: QName<'gc>,
         value: Value<'gc>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         self.values
             .insert(name, Property::new_dynamic_property(value));
 
@@ -875,8 +906,15 @@ impl<'gc> ScriptObjectData<'gc> {
     ///
     /// Slot number zero indicates a slot ID that is unknown and should be
     /// allocated by the VM - as far as I know, there is no way to discover
-    /// slot IDs, so we don't allocate a slot for them at all.
-    pub fn install_slot(&mut self, name: QName<'gc>, id: u32, value: Value<'gc>) {
+    /// slot IDs, so we don't allocate a slot for them at all. This also means
+    /// that their declared type isn't checked.
+    pub fn install_slot(
+        &mut self,
+        name: QName<'gc>,
+        id: u32,
+        value: Value<'gc>,
+        type_name: Multiname<'gc>,
+    ) {
         if id == 0 {
             self.values.insert(name, Property::new_stored(value));
         } else {
@@ -886,7 +924,7 @@ impl<'gc> ScriptObjectData<'gc> {
             }
 
             if let Some(slot) = self.slots.get_mut(id as usize) {
-                *slot = Slot::new(value);
+                *slot = Slot::new_typed(value, type_name);
             }
         }
     }
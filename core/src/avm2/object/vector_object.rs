@@ -0,0 +1,247 @@
+//! Vector-structured objects
+
+use crate::avm1::AvmString;
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::vector::VectorStorage;
+use crate::avm2::Error;
+use crate::impl_avm2_custom_object;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// An Object which stores typed properties in a vector.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct VectorObject<'gc>(GcCell<'gc, VectorObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct VectorObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// Vector-structured properties
+    vector: VectorStorage<'gc>,
+}
+
+impl<'gc> VectorObject<'gc> {
+    /// Wrap an existing vector in an object.
+    ///
+    /// The prototype should be that of the `Vector` specialization whose
+    /// value type the vector has.
+    pub fn from_vector(
+        vector: VectorStorage<'gc>,
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(Some(base_proto), ScriptObjectClass::NoClass);
+
+        VectorObject(GcCell::allocate(mc, VectorObjectData { base, vector })).into()
+    }
+
+    /// Construct a vector subclass.
+    ///
+    /// Instances of the subclass hold values of the type of the given vector
+    /// storage.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+        vector: VectorStorage<'gc>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(VectorObject(GcCell::allocate(mc, VectorObjectData { base, vector })).into())
+    }
+}
+
+impl<'gc> TObject<'gc> for VectorObject<'gc> {
+    impl_avm2_custom_object!(base);
+
+    fn get_property_local(
+        self,
+        receiver: Object<'gc>,
+        name: &QName<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let read = self.0.read();
+
+        if name.namespace().is_public() {
+            if let Ok(index) = name.local_name().parse::<usize>() {
+                return read.vector.get(index);
+            }
+        }
+
+        let rv = read.base.get_property_local(receiver, name, activation)?;
+
+        drop(read);
+
+        rv.resolve(activation)
+    }
+
+    fn set_property_local(
+        self,
+        receiver: Object<'gc>,
+        name: &QName<'gc>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error<'gc>> {
+        if name.namespace().is_public() {
+            if let Ok(index) = name.local_name().parse::<usize>() {
+                let value_type = self.0.read().vector.value_type();
+                let value = VectorStorage::coerce(activation, value_type, value)?;
+
+                return self
+                    .0
+                    .write(activation.context.gc_context)
+                    .vector
+                    .set(index, value);
+            }
+        }
+
+        let mut write = self.0.write(activation.context.gc_context);
+
+        let rv = write
+            .base
+            .set_property_local(receiver, name, value, activation)?;
+
+        drop(write);
+
+        rv.resolve(activation)?;
+
+        Ok(())
+    }
+
+    fn init_property_local(
+        self,
+        receiver: Object<'gc>,
+        name: &QName<'gc>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error<'gc>> {
+        if name.namespace().is_public() {
+            if let Ok(index) = name.local_name().parse::<usize>() {
+                let value_type = self.0.read().vector.value_type();
+                let value = VectorStorage::coerce(activation, value_type, value)?;
+
+                return self
+                    .0
+                    .write(activation.context.gc_context)
+                    .vector
+                    .set(index, value);
+            }
+        }
+
+        let mut write = self.0.write(activation.context.gc_context);
+
+        let rv = write
+            .base
+            .init_property_local(receiver, name, value, activation)?;
+
+        drop(write);
+
+        rv.resolve(activation)?;
+
+        Ok(())
+    }
+
+    fn is_property_overwritable(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        name: &QName<'gc>,
+    ) -> bool {
+        self.0.write(gc_context).base.is_property_overwritable(name)
+    }
+
+    fn delete_property(&self, gc_context: MutationContext<'gc, '_>, name: &QName<'gc>) -> bool {
+        if name.namespace().is_public() && name.local_name().parse::<usize>().is_ok() {
+            return false;
+        }
+
+        self.0.write(gc_context).base.delete_property(name)
+    }
+
+    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
+        if name.namespace().is_public() {
+            if let Ok(index) = name.local_name().parse::<usize>() {
+                return Ok(index < self.0.read().vector.length());
+            }
+        }
+
+        self.0.read().base.has_own_property(name)
+    }
+
+    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
+        if let Ok(index) = local_name.parse::<usize>() {
+            if index < self.0.read().vector.length() {
+                return Ok(Some(Namespace::public()));
+            }
+        }
+
+        self.0.read().base.resolve_any(local_name)
+    }
+
+    fn resolve_any_trait(
+        self,
+        local_name: AvmString<'gc>,
+    ) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
+        self.0.read().base.resolve_any_trait(local_name)
+    }
+
+    fn to_string(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_vector_storage(&self) -> Option<Ref<VectorStorage<'gc>>> {
+        Some(Ref::map(self.0.read(), |vod| &vod.vector))
+    }
+
+    fn as_vector_storage_mut(
+        &self,
+        mc: MutationContext<'gc, '_>,
+    ) -> Option<RefMut<VectorStorage<'gc>>> {
+        Some(RefMut::map(self.0.write(mc), |vod| &mut vod.vector))
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::VectorObject(*self);
+        let base = ScriptObjectData::base_new(Some(this), ScriptObjectClass::NoClass);
+        let vector = self.0.read().vector.with_values(Vec::new());
+
+        Ok(VectorObject(GcCell::allocate(
+            activation.context.gc_context,
+            VectorObjectData { base, vector },
+        ))
+        .into())
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::VectorObject(*self);
+        let vector = self.0.read().vector.with_values(Vec::new());
+
+        VectorObject::derive(this, activation.context.gc_context, class, scope, vector)
+    }
+}
@@ -0,0 +1,268 @@
+//! Object representation for E4X XML lists
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::e4x::E4XNode;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::xml_object::resolve_xml_multiname;
+use crate::avm2::object::{Object, ObjectPtr, TObject, XmlObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::impl_avm2_custom_object;
+use gc_arena::{Collect, GcCell, MutationContext};
+
+/// An Object which holds an ordered list of XML nodes.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct XmlListObject<'gc>(GcCell<'gc, XmlListObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct XmlListObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The nodes this list holds.
+    nodes: Vec<E4XNode<'gc>>,
+}
+
+impl<'gc> XmlListObject<'gc> {
+    /// Wrap a list of XML nodes in an object.
+    pub fn from_nodes(
+        mc: MutationContext<'gc, '_>,
+        base_proto: Option<Object<'gc>>,
+        nodes: Vec<E4XNode<'gc>>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(base_proto, ScriptObjectClass::NoClass);
+
+        XmlListObject(GcCell::allocate(mc, XmlListObjectData { base, nodes })).into()
+    }
+
+    /// Instantiate an XML list subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        XmlListObject(GcCell::allocate(
+            mc,
+            XmlListObjectData {
+                base,
+                nodes: Vec::new(),
+            },
+        ))
+        .into()
+    }
+
+    /// The nodes this list holds.
+    pub fn nodes(self) -> Vec<E4XNode<'gc>> {
+        self.0.read().nodes.clone()
+    }
+
+    /// Replace the nodes this list holds.
+    pub fn set_nodes(self, mc: MutationContext<'gc, '_>, nodes: Vec<E4XNode<'gc>>) {
+        self.0.write(mc).nodes = nodes;
+    }
+
+    /// The number of nodes this list holds.
+    pub fn length(self) -> usize {
+        self.0.read().nodes.len()
+    }
+}
+
+impl<'gc> TObject<'gc> for XmlListObject<'gc> {
+    impl_avm2_custom_object!(base);
+
+    fn get_property_local(
+        self,
+        receiver: Object<'gc>,
+        name: &QName<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let read = self.0.read();
+        let rv = read.base.get_property_local(receiver, name, activation)?;
+
+        drop(read);
+
+        rv.resolve(activation)
+    }
+
+    fn set_property_local(
+        self,
+        receiver: Object<'gc>,
+        name: &QName<'gc>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error<'gc>> {
+        let mut write = self.0.write(activation.context.gc_context);
+        let rv = write
+            .base
+            .set_property_local(receiver, name, value, activation)?;
+
+        drop(write);
+
+        rv.resolve(activation)?;
+
+        Ok(())
+    }
+
+    fn init_property_local(
+        self,
+        receiver: Object<'gc>,
+        name: &QName<'gc>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error<'gc>> {
+        let mut write = self.0.write(activation.context.gc_context);
+        let rv = write
+            .base
+            .init_property_local(receiver, name, value, activation)?;
+
+        drop(write);
+
+        rv.resolve(activation)?;
+
+        Ok(())
+    }
+
+    fn is_property_overwritable(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        name: &QName<'gc>,
+    ) -> bool {
+        self.0.write(gc_context).base.is_property_overwritable(name)
+    }
+
+    fn delete_property(&self, gc_context: MutationContext<'gc, '_>, name: &QName<'gc>) -> bool {
+        self.0.write(gc_context).base.delete_property(name)
+    }
+
+    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
+        if name.namespace().is_public() {
+            if let Ok(index) = name.local_name().parse::<usize>() {
+                return Ok(index < self.length());
+            }
+        }
+
+        self.0.read().base.has_own_property(name)
+    }
+
+    fn resolve_multiname(
+        self,
+        multiname: &Multiname<'gc>,
+    ) -> Result<Option<QName<'gc>>, Error<'gc>> {
+        resolve_xml_multiname(self.into(), multiname)
+    }
+
+    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
+        if let Ok(index) = local_name.parse::<usize>() {
+            if index < self.length() {
+                return Ok(Some(Namespace::public()));
+            }
+        }
+
+        self.0.read().base.resolve_any(local_name)
+    }
+
+    fn resolve_any_trait(
+        self,
+        local_name: AvmString<'gc>,
+    ) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
+        self.0.read().base.resolve_any_trait(local_name)
+    }
+
+    fn get_enumerant_key(&self, index: u32) -> Option<Value<'gc>> {
+        let length = self.length() as u32;
+
+        // Enumerants start from one, as they do for every other object.
+        if index >= 1 && index <= length {
+            return Some((index - 1).into());
+        }
+
+        self.0
+            .read()
+            .base
+            .get_enumerant_name(index.checked_sub(length)?)
+            .map(|name| name.local_name().into())
+    }
+
+    fn get_enumerant_value(
+        self,
+        index: u32,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let length = self.length() as u32;
+
+        if index >= 1 && index <= length {
+            let node = self.0.read().nodes[index as usize - 1];
+            let proto = activation.avm2().prototypes().xml;
+
+            return Ok(
+                XmlObject::from_node(activation.context.gc_context, Some(proto), node).into(),
+            );
+        }
+
+        let name = index
+            .checked_sub(length)
+            .and_then(|index| self.0.read().base.get_enumerant_name(index));
+        match name {
+            Some(name) => {
+                let mut this: Object<'gc> = self.into();
+                this.get_property(this, &name, activation)
+            }
+            None => Ok(Value::Undefined),
+        }
+    }
+
+    fn to_string(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_xml_list_object(self) -> Option<XmlListObject<'gc>> {
+        Some(self)
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::XmlListObject(*self);
+
+        Ok(XmlListObject::from_nodes(
+            activation.context.gc_context,
+            Some(this),
+            Vec::new(),
+        ))
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::XmlListObject(*self);
+
+        Ok(Self::derive(
+            this,
+            activation.context.gc_context,
+            class,
+            scope,
+        ))
+    }
+}
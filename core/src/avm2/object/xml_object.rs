@@ -0,0 +1,262 @@
+//! Object representation for E4X XML
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::e4x::E4XNode;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::impl_avm2_custom_object;
+use gc_arena::{Collect, GcCell, MutationContext};
+
+/// An Object which holds a single node of an XML tree.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct XmlObject<'gc>(GcCell<'gc, XmlObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct XmlObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The node this object holds.
+    node: E4XNode<'gc>,
+}
+
+impl<'gc> XmlObject<'gc> {
+    /// Wrap an existing XML node in an object.
+    pub fn from_node(
+        mc: MutationContext<'gc, '_>,
+        base_proto: Option<Object<'gc>>,
+        node: E4XNode<'gc>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(base_proto, ScriptObjectClass::NoClass);
+
+        XmlObject(GcCell::allocate(mc, XmlObjectData { base, node })).into()
+    }
+
+    /// Instantiate an XML subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        XmlObject(GcCell::allocate(
+            mc,
+            XmlObjectData {
+                base,
+                node: E4XNode::new_text(mc, ""),
+            },
+        ))
+        .into()
+    }
+
+    /// The node this object holds.
+    pub fn node(self) -> E4XNode<'gc> {
+        self.0.read().node
+    }
+
+    /// Replace the node this object holds.
+    pub fn set_node(self, mc: MutationContext<'gc, '_>, node: E4XNode<'gc>) {
+        self.0.write(mc).node = node;
+    }
+}
+
+/// Resolve a multiname against an XML or XML list object.
+///
+/// The methods of `XML` and `XMLList` live in the `AS3` namespace, and have to
+/// be found before any child or attribute of the same name, which is
+/// otherwise visible in the public namespace.
+pub fn resolve_xml_multiname<'gc>(
+    object: Object<'gc>,
+    multiname: &Multiname<'gc>,
+) -> Result<Option<QName<'gc>>, Error<'gc>> {
+    let local_name = match multiname.local_name() {
+        Some(local_name) => local_name,
+        None => return Ok(None),
+    };
+
+    for ns in multiname.namespace_set() {
+        let qname = QName::new(ns.clone(), local_name);
+        if !ns.is_dynamic() && object.has_trait(&qname)? {
+            return Ok(Some(qname));
+        }
+    }
+
+    for ns in multiname.namespace_set() {
+        if ns.is_any() {
+            let ns = object.resolve_any(local_name)?;
+            return Ok(ns.map(|ns| QName::new(ns, local_name)));
+        }
+
+        let qname = QName::new(ns.clone(), local_name);
+        if object.has_property(&qname)? {
+            return Ok(Some(qname));
+        }
+    }
+
+    if let Some(proto) = object.proto() {
+        return proto.resolve_multiname(multiname);
+    }
+
+    Ok(None)
+}
+
+impl<'gc> TObject<'gc> for XmlObject<'gc> {
+    impl_avm2_custom_object!(base);
+
+    fn get_property_local(
+        self,
+        receiver: Object<'gc>,
+        name: &QName<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let read = self.0.read();
+        let rv = read.base.get_property_local(receiver, name, activation)?;
+
+        drop(read);
+
+        rv.resolve(activation)
+    }
+
+    fn set_property_local(
+        self,
+        receiver: Object<'gc>,
+        name: &QName<'gc>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error<'gc>> {
+        let mut write = self.0.write(activation.context.gc_context);
+        let rv = write
+            .base
+            .set_property_local(receiver, name, value, activation)?;
+
+        drop(write);
+
+        rv.resolve(activation)?;
+
+        Ok(())
+    }
+
+    fn init_property_local(
+        self,
+        receiver: Object<'gc>,
+        name: &QName<'gc>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error<'gc>> {
+        let mut write = self.0.write(activation.context.gc_context);
+        let rv = write
+            .base
+            .init_property_local(receiver, name, value, activation)?;
+
+        drop(write);
+
+        rv.resolve(activation)?;
+
+        Ok(())
+    }
+
+    fn is_property_overwritable(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        name: &QName<'gc>,
+    ) -> bool {
+        self.0.write(gc_context).base.is_property_overwritable(name)
+    }
+
+    fn delete_property(&self, gc_context: MutationContext<'gc, '_>, name: &QName<'gc>) -> bool {
+        self.0.write(gc_context).base.delete_property(name)
+    }
+
+    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error<'gc>> {
+        // XML objects also have the names of their child elements and
+        // attributes, so that filter predicates such as `xml.item.(@id == 3)`
+        // can find them on the scope chain.
+        if name.namespace().is_public() {
+            let node = self.0.read().node;
+            let local_name = name.local_name();
+            let has_name =
+                |child: &E4XNode<'gc>| child.matches_name(Some(local_name.as_str()), None);
+
+            if node.children().iter().any(has_name) || node.attributes().iter().any(has_name) {
+                return Ok(true);
+            }
+        }
+
+        self.0.read().base.has_own_property(name)
+    }
+
+    fn resolve_multiname(
+        self,
+        multiname: &Multiname<'gc>,
+    ) -> Result<Option<QName<'gc>>, Error<'gc>> {
+        resolve_xml_multiname(self.into(), multiname)
+    }
+
+    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
+        self.0.read().base.resolve_any(local_name)
+    }
+
+    fn resolve_any_trait(
+        self,
+        local_name: AvmString<'gc>,
+    ) -> Result<Option<Namespace<'gc>>, Error<'gc>> {
+        self.0.read().base.resolve_any_trait(local_name)
+    }
+
+    fn to_string(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_xml_object(self) -> Option<XmlObject<'gc>> {
+        Some(self)
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::XmlObject(*self);
+        let node = E4XNode::new_text(activation.context.gc_context, "");
+
+        Ok(XmlObject::from_node(
+            activation.context.gc_context,
+            Some(this),
+            node,
+        ))
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let this: Object<'gc> = Object::XmlObject(*self);
+
+        Ok(Self::derive(
+            this,
+            activation.context.gc_context,
+            class,
+            scope,
+        ))
+    }
+}
@@ -107,7 +107,7 @@ impl<'gc> Property<'gc> {
     ///
     /// The implementation must be a valid function, otherwise the VM will
     /// panic when the property is accessed.
-    pub fn install_virtual_getter(&mut self, getter_impl: Object<'gc>) -> Result<(), Error> {
+    pub fn install_virtual_getter(&mut self, getter_impl: Object<'gc>) -> Result<(), Error<'gc>> {
         match self {
             Property::Virtual { get, .. } => *get = Some(getter_impl),
             Property::Stored { .. } => return Err("Not a virtual property".into()),
@@ -124,7 +124,7 @@ impl<'gc> Property<'gc> {
     ///
     /// The implementation must be a valid function, otherwise the VM will
     /// panic when the property is accessed.
-    pub fn install_virtual_setter(&mut self, setter_impl: Object<'gc>) -> Result<(), Error> {
+    pub fn install_virtual_setter(&mut self, setter_impl: Object<'gc>) -> Result<(), Error<'gc>> {
         match self {
             Property::Virtual { set, .. } => *set = Some(setter_impl),
             Property::Stored { .. } => return Err("Not a virtual property".into()),
@@ -142,7 +142,7 @@ impl<'gc> Property<'gc> {
         &self,
         this: Object<'gc>,
         base_proto: Option<Object<'gc>>,
-    ) -> Result<ReturnValue<'gc>, Error> {
+    ) -> Result<ReturnValue<'gc>, Error<'gc>> {
         match self {
             Property::Virtual { get: Some(get), .. } => Ok(ReturnValue::defer_execution(
                 *get,
@@ -168,7 +168,7 @@ impl<'gc> Property<'gc> {
         this: Object<'gc>,
         base_proto: Option<Object<'gc>>,
         new_value: impl Into<Value<'gc>>,
-    ) -> Result<ReturnValue<'gc>, Error> {
+    ) -> Result<ReturnValue<'gc>, Error<'gc>> {
         match self {
             Property::Virtual { set, .. } => {
                 if let Some(function) = set {
@@ -212,7 +212,7 @@ impl<'gc> Property<'gc> {
         this: Object<'gc>,
         base_proto: Option<Object<'gc>>,
         new_value: impl Into<Value<'gc>>,
-    ) -> Result<ReturnValue<'gc>, Error> {
+    ) -> Result<ReturnValue<'gc>, Error<'gc>> {
         match self {
             Property::Virtual { set, .. } => {
                 if let Some(function) = set {
@@ -0,0 +1,299 @@
+//! RegExp support types
+
+mod matcher;
+mod parser;
+
+use self::matcher::{MatchOptions, Program, StepLimitExceeded};
+use bitflags::bitflags;
+use gc_arena::Collect;
+use std::ops::Range;
+
+bitflags! {
+    /// The flags a regular expression is created with.
+    pub struct RegExpFlags: u8 {
+        /// `g`: match repeatedly, starting from `lastIndex`.
+        const GLOBAL = 1 << 0;
+
+        /// `i`: match regardless of case.
+        const IGNORE_CASE = 1 << 1;
+
+        /// `m`: `^` and `$` match at the start and end of lines.
+        const MULTILINE = 1 << 2;
+
+        /// `s`: `.` matches line terminators.
+        const DOTALL = 1 << 3;
+
+        /// `x`: whitespace in the pattern is ignored.
+        const EXTENDED = 1 << 4;
+    }
+}
+
+impl RegExpFlags {
+    /// Parse a flags string like `"gi"`, ignoring unknown flags.
+    pub fn from_flags(flags: &str) -> Self {
+        let mut result = Self::empty();
+        for c in flags.chars() {
+            result |= match c {
+                'g' => Self::GLOBAL,
+                'i' => Self::IGNORE_CASE,
+                'm' => Self::MULTILINE,
+                's' => Self::DOTALL,
+                'x' => Self::EXTENDED,
+                _ => Self::empty(),
+            };
+        }
+        result
+    }
+}
+
+/// A successful match of a regular expression.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegExpMatch {
+    /// The range of the whole match, followed by the range of each group,
+    /// which is `None` if it didn't participate in the match.
+    ///
+    /// Ranges are in UTF-16 code units.
+    pub captures: Vec<Option<Range<usize>>>,
+}
+
+impl RegExpMatch {
+    /// The range of the whole match.
+    pub fn range(&self) -> Range<usize> {
+        self.captures[0].clone().unwrap()
+    }
+}
+
+/// The storage portion of a `RegExp` object.
+///
+/// Patterns that fail to parse are kept, so that their `source` can still be
+/// read, but never match.
+#[derive(Clone, Collect, Debug)]
+#[collect(require_static)]
+pub struct RegExp {
+    source: String,
+    flags: RegExpFlags,
+    last_index: usize,
+    program: Option<Program>,
+    group_names: Vec<(String, usize)>,
+}
+
+impl Default for RegExp {
+    fn default() -> Self {
+        Self::new("", RegExpFlags::empty())
+    }
+}
+
+impl RegExp {
+    pub fn new(source: &str, flags: RegExpFlags) -> Self {
+        let pattern = parser::parse(source, flags.contains(RegExpFlags::EXTENDED));
+        if let Err(e) = &pattern {
+            log::warn!("RegExp /{}/: {}", source, e);
+        }
+
+        let (program, group_names) = match pattern {
+            Ok(pattern) => (Program::compile(&pattern).ok(), pattern.group_names.clone()),
+            Err(_) => (None, Vec::new()),
+        };
+
+        Self {
+            source: source.to_string(),
+            flags,
+            last_index: 0,
+            program,
+            group_names,
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn flags(&self) -> RegExpFlags {
+        self.flags
+    }
+
+    pub fn last_index(&self) -> usize {
+        self.last_index
+    }
+
+    pub fn set_last_index(&mut self, last_index: usize) {
+        self.last_index = last_index;
+    }
+
+    /// The names of named groups, and their indices.
+    pub fn group_names(&self) -> &[(String, usize)] {
+        &self.group_names
+    }
+
+    /// Find the first match in UTF-16 text starting at or after `start`.
+    ///
+    /// This ignores `lastIndex` and the `g` flag.
+    pub fn find_at(&self, text: &[u16], start: usize) -> Option<RegExpMatch> {
+        if start > text.len() {
+            return None;
+        }
+
+        let options = MatchOptions {
+            ignore_case: self.flags.contains(RegExpFlags::IGNORE_CASE),
+            multiline: self.flags.contains(RegExpFlags::MULTILINE),
+            dotall: self.flags.contains(RegExpFlags::DOTALL),
+        };
+        let captures = match self.program.as_ref()?.find_at(text, start, options) {
+            Ok(captures) => captures?,
+            Err(StepLimitExceeded) => {
+                // Flash Player also treats running into the match limit as no match.
+                log::warn!(
+                    "RegExp /{}/: Gave up matching after too many steps",
+                    self.source
+                );
+                return None;
+            }
+        };
+
+        Some(RegExpMatch { captures })
+    }
+
+    /// Find the next match, as `RegExp.exec` does.
+    ///
+    /// Global expressions search from and update `lastIndex`, which is reset
+    /// when there are no more matches.
+    pub fn exec(&mut self, text: &[u16]) -> Option<RegExpMatch> {
+        if !self.flags.contains(RegExpFlags::GLOBAL) {
+            return self.find_at(text, 0);
+        }
+
+        let result = self.find_at(text, self.last_index);
+        self.last_index = match &result {
+            Some(m) => m.range().end,
+            None => 0,
+        };
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16(s: &str) -> Vec<u16> {
+        s.encode_utf16().collect()
+    }
+
+    /// The text of each capture of the first match.
+    fn captures(source: &str, flags: &str, text: &str) -> Option<Vec<Option<String>>> {
+        let text = utf16(text);
+        let regexp = RegExp::new(source, RegExpFlags::from_flags(flags));
+        regexp.find_at(&text, 0).map(|m| {
+            m.captures
+                .iter()
+                .map(|c| c.clone().map(|r| String::from_utf16_lossy(&text[r])))
+                .collect()
+        })
+    }
+
+    fn matched(source: &str, flags: &str, text: &str) -> Option<String> {
+        captures(source, flags, text).map(|c| c[0].clone().unwrap())
+    }
+
+    #[test]
+    fn literals_and_classes() {
+        assert_eq!(matched("b+", "", "abbbc"), Some("bbb".to_string()));
+        assert_eq!(matched("[a-c]{2,}", "", "xxcabx"), Some("cab".to_string()));
+        assert_eq!(matched("[^a-c]+", "", "abcdef"), Some("def".to_string()));
+        assert_eq!(
+            matched("\\d+\\.\\d*", "", "pi=3.14"),
+            Some("3.14".to_string())
+        );
+        assert_eq!(
+            matched("\\w+\\s\\w", "", "hi there!"),
+            Some("hi t".to_string())
+        );
+        assert_eq!(matched("\\x41\\u0042", "", "zAB"), Some("AB".to_string()));
+        assert_eq!(matched("a.c", "", "a\nc"), None);
+        assert_eq!(matched("a.c", "s", "a\nc"), Some("a\nc".to_string()));
+        assert_eq!(matched("a{,2}", "", "a{,2}"), Some("a{,2}".to_string()));
+    }
+
+    #[test]
+    fn anchors_and_boundaries() {
+        assert_eq!(matched("^b", "", "a\nb"), None);
+        assert_eq!(matched("^b$", "m", "a\nb\nc"), Some("b".to_string()));
+        assert_eq!(
+            matched("\\bcat\\b", "", "concat cat"),
+            Some("cat".to_string())
+        );
+        assert_eq!(
+            captures("(\\w+)\\b", "", "one two").unwrap()[1],
+            Some("one".to_string())
+        );
+    }
+
+    #[test]
+    fn groups_and_backreferences() {
+        assert_eq!(
+            captures("(a)|(b)", "", "b"),
+            Some(vec![Some("b".to_string()), None, Some("b".to_string())])
+        );
+        assert_eq!(matched("(\\w)\\1", "", "abccd"), Some("cc".to_string()));
+        assert_eq!(matched("(?:ab)+", "", "ababa"), Some("abab".to_string()));
+        assert_eq!(matched("a+?", "", "aaa"), Some("a".to_string()));
+        assert_eq!(matched("(a*)*b", "", "aab"), Some("aab".to_string()));
+
+        let regexp = RegExp::new("(?P<year>\\d{4})-(?P<month>\\d\\d)", RegExpFlags::empty());
+        assert_eq!(
+            regexp.group_names(),
+            &[("year".to_string(), 1), ("month".to_string(), 2)]
+        );
+        assert_eq!(matched("(?P<x>a)\\k<x>", "", "aa"), Some("aa".to_string()));
+    }
+
+    #[test]
+    fn look_ahead() {
+        assert_eq!(
+            matched("\\w+(?=!)", "", "hey you!"),
+            Some("you".to_string())
+        );
+        assert_eq!(matched("a(?!b)", "", "abac"), Some("a".to_string()));
+        assert_eq!(
+            captures("(?=(a+))a", "", "aaa").unwrap()[1],
+            Some("aaa".to_string())
+        );
+    }
+
+    #[test]
+    fn flags() {
+        assert_eq!(
+            matched("HELLO", "i", "say hello"),
+            Some("hello".to_string())
+        );
+        assert_eq!(matched("[A-Z]+", "i", "abc"), Some("abc".to_string()));
+        assert_eq!(matched("a b  c", "x", "abc"), Some("abc".to_string()));
+        assert_eq!(matched("a\\ b", "x", "a b"), Some("a b".to_string()));
+    }
+
+    #[test]
+    fn global_exec() {
+        let text = utf16("a1b2");
+        let mut regexp = RegExp::new("\\d", RegExpFlags::GLOBAL);
+        assert_eq!(regexp.exec(&text).map(|m| m.range()), Some(1..2));
+        assert_eq!(regexp.last_index(), 2);
+        assert_eq!(regexp.exec(&text).map(|m| m.range()), Some(3..4));
+        assert_eq!(regexp.exec(&text), None);
+        assert_eq!(regexp.last_index(), 0);
+    }
+
+    #[test]
+    fn invalid_patterns() {
+        assert_eq!(matched("(a", "", "a"), None);
+        assert_eq!(matched("a)", "", "a"), None);
+        assert_eq!(matched("[b-a]", "", "a"), None);
+        assert_eq!(matched("*", "", "*"), None);
+        assert_eq!(RegExp::new("(a", RegExpFlags::empty()).source(), "(a");
+    }
+
+    #[test]
+    fn catastrophic_backtracking() {
+        assert_eq!(matched("(a+)+b", "", "aaab"), Some("aaab".to_string()));
+        assert_eq!(matched("(a+)+b", "", &"a".repeat(40)), None);
+    }
+}
@@ -0,0 +1,503 @@
+//! Backtracking matcher for parsed patterns.
+//!
+//! Patterns are compiled into a small program, which is run against UTF-16
+//! input by a backtracking VM. Changes to captures are recorded on the same
+//! stack as branches, so that backtracking past a branch undoes them.
+
+use super::parser::{is_line_terminator, is_word, CharClass, Node, Pattern};
+use std::ops::Range;
+
+/// The most instructions a program may have, to keep large counted
+/// repetitions from using unbounded memory.
+const MAX_PROGRAM_LENGTH: usize = 1 << 20;
+
+/// The most steps a search may take, including backtracking, before it is
+/// given up on. Patterns such as `(a+)+b` otherwise take exponential time to
+/// fail. This is the default match limit of PCRE, which Flash Player uses.
+const MAX_STEPS: usize = 10_000_000;
+
+/// The options a program is matched with.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MatchOptions {
+    pub ignore_case: bool,
+    pub multiline: bool,
+    pub dotall: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Inst {
+    Char(u16),
+    Any,
+    Class(usize),
+    Start,
+    End,
+    WordBoundary {
+        negated: bool,
+    },
+
+    /// Continue at the first target, and at the second when backtracking.
+    Split(usize, usize),
+    Jump(usize),
+
+    /// Record the current position in a capture slot.
+    Save(usize),
+    BackReference(usize),
+
+    /// Run the look-ahead body that follows, then continue at the target.
+    LookAhead {
+        negated: bool,
+        end: usize,
+    },
+    LookAheadEnd,
+
+    /// Record the position an iteration of a loop started at.
+    RepeatStart(usize),
+
+    /// Fail if an iteration of a loop matched the empty string, which would
+    /// otherwise loop forever.
+    RepeatCheck(usize),
+
+    Match,
+}
+
+/// A compiled pattern.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Program {
+    insts: Vec<Inst>,
+    classes: Vec<CharClass>,
+    group_count: usize,
+    register_count: usize,
+}
+
+/// An error compiling a pattern.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TooLarge;
+
+/// An error running a program: the search took more than `MAX_STEPS` steps.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StepLimitExceeded;
+
+impl Program {
+    pub fn compile(pattern: &Pattern) -> Result<Self, TooLarge> {
+        let mut program = Program {
+            insts: Vec::new(),
+            classes: Vec::new(),
+            group_count: pattern.group_count,
+            register_count: 0,
+        };
+
+        program.push(Inst::Save(0))?;
+        program.compile_node(&pattern.node)?;
+        program.push(Inst::Save(1))?;
+        program.push(Inst::Match)?;
+
+        Ok(program)
+    }
+
+    fn push(&mut self, inst: Inst) -> Result<usize, TooLarge> {
+        if self.insts.len() >= MAX_PROGRAM_LENGTH {
+            return Err(TooLarge);
+        }
+        self.insts.push(inst);
+        Ok(self.insts.len() - 1)
+    }
+
+    fn compile_node(&mut self, node: &Node) -> Result<(), TooLarge> {
+        match node {
+            Node::Empty => {}
+            Node::Char(c) => {
+                self.push(Inst::Char(*c))?;
+            }
+            Node::Any => {
+                self.push(Inst::Any)?;
+            }
+            Node::Class(class) => {
+                self.classes.push(class.clone());
+                self.push(Inst::Class(self.classes.len() - 1))?;
+            }
+            Node::Start => {
+                self.push(Inst::Start)?;
+            }
+            Node::End => {
+                self.push(Inst::End)?;
+            }
+            Node::WordBoundary { negated } => {
+                self.push(Inst::WordBoundary { negated: *negated })?;
+            }
+            Node::Group(node, None) => self.compile_node(node)?,
+            Node::Group(node, Some(index)) => {
+                self.push(Inst::Save(index * 2))?;
+                self.compile_node(node)?;
+                self.push(Inst::Save(index * 2 + 1))?;
+            }
+            Node::LookAhead { node, negated } => {
+                let start = self.push(Inst::LookAhead {
+                    negated: *negated,
+                    end: 0,
+                })?;
+                self.compile_node(node)?;
+                self.push(Inst::LookAheadEnd)?;
+                let end = self.insts.len();
+                self.insts[start] = Inst::LookAhead {
+                    negated: *negated,
+                    end,
+                };
+            }
+            Node::BackReference(index) => {
+                self.push(Inst::BackReference(*index))?;
+            }
+            Node::Concat(nodes) => {
+                for node in nodes {
+                    self.compile_node(node)?;
+                }
+            }
+            Node::Alternation(nodes) => {
+                // Each alternative but the last is preceded by a split to the
+                // next one, and followed by a jump to the end.
+                let mut jumps = Vec::new();
+                for (i, node) in nodes.iter().enumerate() {
+                    if i + 1 < nodes.len() {
+                        let split = self.push(Inst::Split(0, 0))?;
+                        self.compile_node(node)?;
+                        jumps.push(self.push(Inst::Jump(0))?);
+                        let next = self.insts.len();
+                        self.insts[split] = Inst::Split(split + 1, next);
+                    } else {
+                        self.compile_node(node)?;
+                    }
+                }
+
+                let end = self.insts.len();
+                for jump in jumps {
+                    self.insts[jump] = Inst::Jump(end);
+                }
+            }
+            Node::Repeat {
+                node,
+                min,
+                max,
+                greedy,
+            } => self.compile_repeat(node, *min, *max, *greedy)?,
+        }
+
+        Ok(())
+    }
+
+    fn compile_repeat(
+        &mut self,
+        node: &Node,
+        min: u32,
+        max: Option<u32>,
+        greedy: bool,
+    ) -> Result<(), TooLarge> {
+        for _ in 0..min {
+            self.compile_node(node)?;
+        }
+
+        let register = self.register_count;
+        self.register_count += 1;
+
+        let split = |body: usize, skip: usize| {
+            if greedy {
+                Inst::Split(body, skip)
+            } else {
+                Inst::Split(skip, body)
+            }
+        };
+
+        match max {
+            None => {
+                let start = self.push(Inst::Split(0, 0))?;
+                self.push(Inst::RepeatStart(register))?;
+                self.compile_node(node)?;
+                self.push(Inst::RepeatCheck(register))?;
+                self.push(Inst::Jump(start))?;
+                let end = self.insts.len();
+                self.insts[start] = split(start + 1, end);
+            }
+            Some(max) => {
+                // Every optional iteration can skip to the end, so that they
+                // nest like `(x(x)?)?`.
+                let mut splits = Vec::new();
+                for _ in min..max {
+                    splits.push(self.push(Inst::Split(0, 0))?);
+                    self.push(Inst::RepeatStart(register))?;
+                    self.compile_node(node)?;
+                    self.push(Inst::RepeatCheck(register))?;
+                }
+
+                let end = self.insts.len();
+                for start in splits {
+                    self.insts[start] = split(start + 1, end);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Find the first match starting at or after `start`.
+    ///
+    /// The returned captures start with the whole match, followed by each
+    /// group, which is `None` if it didn't participate in the match.
+    pub fn find_at(
+        &self,
+        text: &[u16],
+        start: usize,
+        options: MatchOptions,
+    ) -> Result<Option<Vec<Option<Range<usize>>>>, StepLimitExceeded> {
+        let mut vm = Vm {
+            program: self,
+            text,
+            options,
+            slots: vec![None; (self.group_count + 1) * 2],
+            registers: vec![0; self.register_count],
+            stack: Vec::new(),
+            steps: 0,
+        };
+
+        for pos in start..=text.len() {
+            if vm.run(0, pos)?.is_some() {
+                return Ok(Some(
+                    vm.slots
+                        .chunks(2)
+                        .map(|slots| match (slots[0], slots[1]) {
+                            (Some(start), Some(end)) => Some(start..end),
+                            _ => None,
+                        })
+                        .collect(),
+                ));
+            }
+
+            for slot in &mut vm.slots {
+                *slot = None;
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+enum Frame {
+    Branch { pc: usize, pos: usize },
+    Slot { index: usize, old: Option<usize> },
+    Register { index: usize, old: usize },
+}
+
+struct Vm<'a> {
+    program: &'a Program,
+    text: &'a [u16],
+    options: MatchOptions,
+    slots: Vec<Option<usize>>,
+    registers: Vec<usize>,
+    stack: Vec<Frame>,
+
+    /// The number of instructions run so far.
+    steps: usize,
+}
+
+impl<'a> Vm<'a> {
+    /// Run the program from an instruction until it matches, returning the
+    /// position it matched up to.
+    ///
+    /// On success, the frames pushed during the run are left on the stack,
+    /// so that the caller can either backtrack into them or discard them.
+    /// On failure, every change made during the run has been undone. Running
+    /// out of steps is an error, after which the state of the VM is invalid.
+    fn run(&mut self, mut pc: usize, mut pos: usize) -> Result<Option<usize>, StepLimitExceeded> {
+        let base = self.stack.len();
+        loop {
+            self.steps += 1;
+            if self.steps > MAX_STEPS {
+                return Err(StepLimitExceeded);
+            }
+
+            let matched = match self.program.insts[pc] {
+                Inst::Char(c) => match self.text.get(pos) {
+                    Some(&t) if self.chars_eq(c, t) => {
+                        pos += 1;
+                        true
+                    }
+                    _ => false,
+                },
+                Inst::Any => match self.text.get(pos) {
+                    Some(&t) if self.options.dotall || !is_line_terminator(t) => {
+                        pos += 1;
+                        true
+                    }
+                    _ => false,
+                },
+                Inst::Class(index) => match self.text.get(pos) {
+                    Some(&t) if self.class_matches(&self.program.classes[index], t) => {
+                        pos += 1;
+                        true
+                    }
+                    _ => false,
+                },
+                Inst::Start => {
+                    pos == 0 || (self.options.multiline && is_line_terminator(self.text[pos - 1]))
+                }
+                Inst::End => {
+                    pos == self.text.len()
+                        || (self.options.multiline && is_line_terminator(self.text[pos]))
+                }
+                Inst::WordBoundary { negated } => {
+                    let before = pos > 0 && is_word(self.text[pos - 1]);
+                    let after = pos < self.text.len() && is_word(self.text[pos]);
+                    (before != after) != negated
+                }
+                Inst::Split(first, second) => {
+                    self.stack.push(Frame::Branch { pc: second, pos });
+                    pc = first;
+                    continue;
+                }
+                Inst::Jump(target) => {
+                    pc = target;
+                    continue;
+                }
+                Inst::Save(index) => {
+                    self.stack.push(Frame::Slot {
+                        index,
+                        old: self.slots[index],
+                    });
+                    self.slots[index] = Some(pos);
+                    true
+                }
+                Inst::BackReference(index) => match self.back_reference(index, pos) {
+                    Some(end) => {
+                        pos = end;
+                        true
+                    }
+                    None => false,
+                },
+                Inst::LookAhead { negated, end } => {
+                    let look_base = self.stack.len();
+                    let matched = self.run(pc + 1, pos)?.is_some();
+                    if matched && !negated {
+                        // Captures made by a positive look-ahead are kept,
+                        // but it is never backtracked into.
+                        let frames = self.stack.split_off(look_base);
+                        self.stack.extend(
+                            frames
+                                .into_iter()
+                                .filter(|frame| !matches!(frame, Frame::Branch { .. })),
+                        );
+                    } else if matched {
+                        self.undo_to(look_base);
+                    }
+
+                    if matched != negated {
+                        pc = end;
+                        continue;
+                    }
+                    false
+                }
+                Inst::LookAheadEnd | Inst::Match => return Ok(Some(pos)),
+                Inst::RepeatStart(index) => {
+                    self.stack.push(Frame::Register {
+                        index,
+                        old: self.registers[index],
+                    });
+                    self.registers[index] = pos;
+                    true
+                }
+                Inst::RepeatCheck(index) => self.registers[index] != pos,
+            };
+
+            if matched {
+                pc += 1;
+                continue;
+            }
+
+            match self.backtrack(base) {
+                Some((next_pc, next_pos)) => {
+                    pc = next_pc;
+                    pos = next_pos;
+                }
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Undo changes until the most recent branch above `base`, and return
+    /// where it continues.
+    fn backtrack(&mut self, base: usize) -> Option<(usize, usize)> {
+        while self.stack.len() > base {
+            match self.stack.pop().unwrap() {
+                Frame::Branch { pc, pos } => return Some((pc, pos)),
+                Frame::Slot { index, old } => self.slots[index] = old,
+                Frame::Register { index, old } => self.registers[index] = old,
+            }
+        }
+        None
+    }
+
+    /// Undo every change above `base`.
+    fn undo_to(&mut self, base: usize) {
+        while self.backtrack(base).is_some() {}
+    }
+
+    /// Match the text captured by a group, returning where the match ends.
+    fn back_reference(&self, index: usize, pos: usize) -> Option<usize> {
+        let (start, end) = match (self.slots.get(index * 2), self.slots.get(index * 2 + 1)) {
+            (Some(Some(start)), Some(Some(end))) if start <= end => (*start, *end),
+            // A group that hasn't captured anything matches the empty string.
+            _ => return Some(pos),
+        };
+
+        let captured = &self.text[start..end];
+        let rest = self.text.get(pos..pos + captured.len())?;
+        if captured
+            .iter()
+            .zip(rest)
+            .all(|(&a, &b)| self.chars_eq(a, b))
+        {
+            Some(pos + captured.len())
+        } else {
+            None
+        }
+    }
+
+    fn chars_eq(&self, a: u16, b: u16) -> bool {
+        a == b || (self.options.ignore_case && canonicalize(a) == canonicalize(b))
+    }
+
+    fn class_matches(&self, class: &CharClass, c: u16) -> bool {
+        if class.matches(c) {
+            return true;
+        }
+        if !self.options.ignore_case {
+            return false;
+        }
+
+        // The class matches if it contains any case variant of the character.
+        let upper = canonicalize(c);
+        let lower = lowercase(c);
+        (upper != c && class.matches(upper)) || (lower != c && class.matches(lower))
+    }
+}
+
+/// Map a code unit to its uppercase form, as the ECMAScript `Canonicalize`
+/// operation does.
+fn canonicalize(c: u16) -> u16 {
+    map_case(c, char::to_uppercase)
+}
+
+fn lowercase(c: u16) -> u16 {
+    map_case(c, char::to_lowercase)
+}
+
+/// Map the case of a code unit, leaving it untouched if the mapping isn't a
+/// single code unit, or maps a non-ASCII character into ASCII.
+fn map_case<I: Iterator<Item = char>>(c: u16, map: impl Fn(char) -> I) -> u16 {
+    let ch = match std::char::from_u32(c.into()) {
+        Some(ch) => ch,
+        None => return c,
+    };
+
+    let mut mapped = map(ch);
+    match (mapped.next(), mapped.next()) {
+        (Some(m), None) if (m as u32) <= 0xFFFF && !(c >= 0x80 && (m as u32) < 0x80) => m as u16,
+        _ => c,
+    }
+}
@@ -0,0 +1,683 @@
+//! Parser for regular expression patterns.
+//!
+//! Patterns follow ECMA-262 3rd Edition, with the extensions ActionScript 3
+//! adds to it: named groups written `(?P<name>...)`, and the `x` flag, which
+//! ignores whitespace in the pattern. Patterns are parsed as UTF-16 code
+//! units, as that is what they are matched against.
+
+use std::fmt;
+
+/// A node of a parsed pattern.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Node {
+    /// Matches the empty string.
+    Empty,
+
+    /// Matches a single code unit.
+    Char(u16),
+
+    /// Matches any code unit, except for line terminators unless the `s` flag
+    /// is set.
+    Any,
+
+    /// Matches any code unit in (or not in) a class.
+    Class(CharClass),
+
+    /// Matches the start of the input, or of a line with the `m` flag set.
+    Start,
+
+    /// Matches the end of the input, or of a line with the `m` flag set.
+    End,
+
+    /// Matches between a word and a non-word character, or the opposite if
+    /// negated.
+    WordBoundary { negated: bool },
+
+    /// A group, which is captured with the given index if it has one.
+    Group(Box<Node>, Option<usize>),
+
+    /// Matches if the node matches (or doesn't match) at this position,
+    /// without consuming input.
+    LookAhead { node: Box<Node>, negated: bool },
+
+    /// Matches the text captured by a group.
+    BackReference(usize),
+
+    /// Matches each node in order.
+    Concat(Vec<Node>),
+
+    /// Matches the first node that matches.
+    Alternation(Vec<Node>),
+
+    /// Matches a node repeatedly.
+    Repeat {
+        node: Box<Node>,
+        min: u32,
+        max: Option<u32>,
+        greedy: bool,
+    },
+}
+
+/// A set of code units, written as `[...]` or as an escape like `\d`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CharClass {
+    pub negated: bool,
+    pub items: Vec<ClassItem>,
+}
+
+/// A part of a `CharClass`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClassItem {
+    /// An inclusive range of code units.
+    Range(u16, u16),
+
+    /// `\d`, or `\D` if negated.
+    Digit { negated: bool },
+
+    /// `\w`, or `\W` if negated.
+    Word { negated: bool },
+
+    /// `\s`, or `\S` if negated.
+    Space { negated: bool },
+}
+
+impl CharClass {
+    fn single(item: ClassItem) -> Self {
+        Self {
+            negated: false,
+            items: vec![item],
+        }
+    }
+
+    /// Whether this class matches a code unit, before accounting for case.
+    pub fn matches(&self, c: u16) -> bool {
+        self.items.iter().any(|item| item.matches(c)) != self.negated
+    }
+}
+
+impl ClassItem {
+    fn matches(self, c: u16) -> bool {
+        match self {
+            ClassItem::Range(start, end) => start <= c && c <= end,
+            ClassItem::Digit { negated } => is_digit(c) != negated,
+            ClassItem::Word { negated } => is_word(c) != negated,
+            ClassItem::Space { negated } => is_space(c) != negated,
+        }
+    }
+}
+
+/// Whether a code unit is a decimal digit.
+pub fn is_digit(c: u16) -> bool {
+    (u16::from(b'0')..=u16::from(b'9')).contains(&c)
+}
+
+/// Whether a code unit is a word character, as matched by `\w`.
+pub fn is_word(c: u16) -> bool {
+    c < 0x80 && {
+        let c = c as u8;
+        c.is_ascii_alphanumeric() || c == b'_'
+    }
+}
+
+/// Whether a code unit is whitespace, as matched by `\s`.
+pub fn is_space(c: u16) -> bool {
+    matches!(
+        c,
+        0x09..=0x0D
+            | 0x20
+            | 0xA0
+            | 0x1680
+            | 0x2000..=0x200A
+            | 0x2028
+            | 0x2029
+            | 0x202F
+            | 0x205F
+            | 0x3000
+            | 0xFEFF
+    )
+}
+
+/// Whether a code unit ends a line.
+pub fn is_line_terminator(c: u16) -> bool {
+    c == 0x0A || c == 0x0D || c == 0x2028 || c == 0x2029
+}
+
+/// An error in a pattern.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseError {
+    /// A `(` without a matching `)`.
+    UnclosedGroup,
+
+    /// A `)` without a matching `(`.
+    UnopenedGroup,
+
+    /// A `[` without a matching `]`.
+    UnclosedClass,
+
+    /// A range in a class whose end comes before its start.
+    InvalidRange,
+
+    /// A quantifier with nothing to repeat, or a `{n,m}` with `n` above `m`.
+    InvalidQuantifier,
+
+    /// A group name that is malformed or isn't defined.
+    InvalidGroupName,
+
+    /// A `(?` followed by something other than a known group type.
+    InvalidGroup,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ParseError::UnclosedGroup => "unclosed group",
+            ParseError::UnopenedGroup => "unmatched closing parenthesis",
+            ParseError::UnclosedClass => "unclosed character class",
+            ParseError::InvalidRange => "character class range out of order",
+            ParseError::InvalidQuantifier => "nothing to repeat",
+            ParseError::InvalidGroupName => "invalid group name",
+            ParseError::InvalidGroup => "invalid group",
+        };
+        write!(f, "Invalid regular expression: {}", message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A parsed pattern.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pattern {
+    pub node: Node,
+
+    /// The number of capturing groups, not counting the whole match.
+    pub group_count: usize,
+
+    /// The names of named groups, and their indices.
+    pub group_names: Vec<(String, usize)>,
+}
+
+/// Parse a pattern.
+///
+/// `extended` is the `x` flag, which ignores whitespace outside of classes.
+pub fn parse(source: &str, extended: bool) -> Result<Pattern, ParseError> {
+    let mut parser = Parser {
+        pattern: source.encode_utf16().collect(),
+        pos: 0,
+        extended,
+        group_count: 0,
+        group_names: Vec::new(),
+        named_references: Vec::new(),
+    };
+
+    let mut node = parser.parse_disjunction()?;
+    if parser.pos < parser.pattern.len() {
+        return Err(ParseError::UnopenedGroup);
+    }
+
+    if !parser.named_references.is_empty() {
+        resolve_named_references(&mut node, &parser.named_references, &parser.group_names)?;
+    }
+
+    Ok(Pattern {
+        node,
+        group_count: parser.group_count,
+        group_names: parser.group_names,
+    })
+}
+
+/// Replace the placeholders left by named back references with the index of
+/// the group they name, which may be defined after the reference.
+fn resolve_named_references(
+    node: &mut Node,
+    references: &[String],
+    group_names: &[(String, usize)],
+) -> Result<(), ParseError> {
+    match node {
+        Node::BackReference(index) if *index >= NAMED_REFERENCE => {
+            let name = &references[*index - NAMED_REFERENCE];
+            *index = group_names
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, i)| *i)
+                .ok_or(ParseError::InvalidGroupName)?;
+        }
+        Node::Group(node, _) | Node::LookAhead { node, .. } | Node::Repeat { node, .. } => {
+            resolve_named_references(node, references, group_names)?
+        }
+        Node::Concat(nodes) | Node::Alternation(nodes) => {
+            for node in nodes {
+                resolve_named_references(node, references, group_names)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Named back references are parsed as references to this index plus the
+/// index of their name, until every group has been seen.
+const NAMED_REFERENCE: usize = usize::MAX / 2;
+
+struct Parser {
+    pattern: Vec<u16>,
+    pos: usize,
+    extended: bool,
+    group_count: usize,
+    group_names: Vec<(String, usize)>,
+    named_references: Vec<String>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<u16> {
+        self.pattern.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<u16> {
+        self.pattern.get(self.pos + offset).copied()
+    }
+
+    fn next(&mut self) -> Option<u16> {
+        let c = self.peek();
+        if c.is_some() {
+            self.pos += 1;
+        }
+        c
+    }
+
+    /// Consume the given ASCII text if the pattern continues with it.
+    fn eat(&mut self, text: &str) -> bool {
+        let matches = text
+            .bytes()
+            .enumerate()
+            .all(|(i, b)| self.peek_at(i) == Some(u16::from(b)));
+        if matches {
+            self.pos += text.len();
+        }
+        matches
+    }
+
+    fn peek_is(&self, c: u8) -> bool {
+        self.peek() == Some(u16::from(c))
+    }
+
+    fn skip_whitespace(&mut self) {
+        if self.extended {
+            while let Some(c) = self.peek() {
+                if !is_space(c) {
+                    break;
+                }
+                self.pos += 1;
+            }
+        }
+    }
+
+    fn parse_disjunction(&mut self) -> Result<Node, ParseError> {
+        let mut alternatives = vec![self.parse_alternative()?];
+        while self.peek_is(b'|') {
+            self.pos += 1;
+            alternatives.push(self.parse_alternative()?);
+        }
+
+        Ok(if alternatives.len() == 1 {
+            alternatives.pop().unwrap()
+        } else {
+            Node::Alternation(alternatives)
+        })
+    }
+
+    fn parse_alternative(&mut self) -> Result<Node, ParseError> {
+        let mut terms = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None => break,
+                Some(c) if c == u16::from(b'|') || c == u16::from(b')') => break,
+                Some(_) => {
+                    let atom = self.parse_atom()?;
+                    let term = self.parse_quantifier(atom)?;
+                    terms.push(term);
+                }
+            }
+        }
+
+        Ok(match terms.len() {
+            0 => Node::Empty,
+            1 => terms.pop().unwrap(),
+            _ => Node::Concat(terms),
+        })
+    }
+
+    fn parse_atom(&mut self) -> Result<Node, ParseError> {
+        let c = self.next().unwrap();
+        if c >= 0x80 {
+            return Ok(Node::Char(c));
+        }
+
+        Ok(match c as u8 {
+            b'^' => Node::Start,
+            b'$' => Node::End,
+            b'.' => Node::Any,
+            b'(' => self.parse_group()?,
+            b'[' => Node::Class(self.parse_class()?),
+            b'\\' => self.parse_atom_escape()?,
+            b'*' | b'+' | b'?' => return Err(ParseError::InvalidQuantifier),
+            b'{' => {
+                // A brace only starts a quantifier if it is well formed.
+                self.pos -= 1;
+                if self.parse_braces().is_some() {
+                    return Err(ParseError::InvalidQuantifier);
+                }
+                self.pos += 1;
+                Node::Char(c)
+            }
+            _ => Node::Char(c),
+        })
+    }
+
+    fn parse_group(&mut self) -> Result<Node, ParseError> {
+        let node = if self.eat("?:") {
+            Node::Group(Box::new(self.parse_disjunction()?), None)
+        } else if self.eat("?=") {
+            Node::LookAhead {
+                node: Box::new(self.parse_disjunction()?),
+                negated: false,
+            }
+        } else if self.eat("?!") {
+            Node::LookAhead {
+                node: Box::new(self.parse_disjunction()?),
+                negated: true,
+            }
+        } else if self.eat("?P<") || self.eat("?<") {
+            let name = self.parse_group_name()?;
+            if self.group_names.iter().any(|(n, _)| *n == name) {
+                return Err(ParseError::InvalidGroupName);
+            }
+            self.group_count += 1;
+            let index = self.group_count;
+            self.group_names.push((name, index));
+            Node::Group(Box::new(self.parse_disjunction()?), Some(index))
+        } else if self.peek_is(b'?') {
+            return Err(ParseError::InvalidGroup);
+        } else {
+            self.group_count += 1;
+            let index = self.group_count;
+            Node::Group(Box::new(self.parse_disjunction()?), Some(index))
+        };
+
+        if self.next() != Some(u16::from(b')')) {
+            return Err(ParseError::UnclosedGroup);
+        }
+
+        Ok(node)
+    }
+
+    /// Parse the name of a group up to and including its closing `>`.
+    fn parse_group_name(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+        while let Some(c) = self.next() {
+            if c == u16::from(b'>') {
+                let name = &self.pattern[start..self.pos - 1];
+                let valid = !name.is_empty()
+                    && !is_digit(name[0])
+                    && name.iter().all(|&c| is_word(c) || c == u16::from(b'$'));
+                return if valid {
+                    Ok(String::from_utf16_lossy(name))
+                } else {
+                    Err(ParseError::InvalidGroupName)
+                };
+            }
+        }
+
+        Err(ParseError::InvalidGroupName)
+    }
+
+    fn parse_atom_escape(&mut self) -> Result<Node, ParseError> {
+        let c = match self.peek() {
+            Some(c) => c,
+            // A trailing backslash matches itself.
+            None => return Ok(Node::Char(u16::from(b'\\'))),
+        };
+
+        if c == u16::from(b'b') {
+            self.pos += 1;
+            return Ok(Node::WordBoundary { negated: false });
+        }
+        if c == u16::from(b'B') {
+            self.pos += 1;
+            return Ok(Node::WordBoundary { negated: true });
+        }
+        if (u16::from(b'1')..=u16::from(b'9')).contains(&c) {
+            return Ok(Node::BackReference(self.parse_decimal().unwrap() as usize));
+        }
+        if c == u16::from(b'k') && self.peek_at(1) == Some(u16::from(b'<')) {
+            self.pos += 2;
+            let name = self.parse_group_name()?;
+            self.named_references.push(name);
+            return Ok(Node::BackReference(
+                NAMED_REFERENCE + self.named_references.len() - 1,
+            ));
+        }
+
+        Ok(match self.parse_char_escape() {
+            Ok(c) => Node::Char(c),
+            Err(item) => Node::Class(CharClass::single(item)),
+        })
+    }
+
+    /// Parse an escape after its backslash, which is either a single code unit
+    /// or a class like `\d`.
+    fn parse_char_escape(&mut self) -> Result<u16, ClassItem> {
+        let c = self.next().unwrap();
+        if c >= 0x80 {
+            return Ok(c);
+        }
+
+        Ok(match c as u8 {
+            b'd' => return Err(ClassItem::Digit { negated: false }),
+            b'D' => return Err(ClassItem::Digit { negated: true }),
+            b'w' => return Err(ClassItem::Word { negated: false }),
+            b'W' => return Err(ClassItem::Word { negated: true }),
+            b's' => return Err(ClassItem::Space { negated: false }),
+            b'S' => return Err(ClassItem::Space { negated: true }),
+            b'n' => 0x0A,
+            b'r' => 0x0D,
+            b't' => 0x09,
+            b'v' => 0x0B,
+            b'f' => 0x0C,
+            b'0' if !self.peek().map(is_digit).unwrap_or(false) => 0,
+            b'x' => self.parse_hex(2).unwrap_or(c),
+            b'u' => self.parse_hex(4).unwrap_or(c),
+            b'c' => match self.peek() {
+                Some(letter) if letter < 0x80 && (letter as u8).is_ascii_alphabetic() => {
+                    self.pos += 1;
+                    letter % 32
+                }
+                // An invalid control escape matches its backslash literally.
+                _ => {
+                    self.pos -= 1;
+                    u16::from(b'\\')
+                }
+            },
+            _ => c,
+        })
+    }
+
+    /// Parse exactly `digits` hexadecimal digits.
+    fn parse_hex(&mut self, digits: usize) -> Option<u16> {
+        let mut value = 0;
+        for i in 0..digits {
+            let digit = self.peek_at(i).and_then(|c| char::from_u32(c.into()))?;
+            value = value * 16 + digit.to_digit(16)? as u16;
+        }
+        self.pos += digits;
+        Some(value)
+    }
+
+    /// Parse a decimal number, saturating if it is too large.
+    fn parse_decimal(&mut self) -> Option<u32> {
+        let mut value: Option<u32> = None;
+        while let Some(c) = self.peek() {
+            if !is_digit(c) {
+                break;
+            }
+            self.pos += 1;
+            let digit = u32::from(c - u16::from(b'0'));
+            value = Some(value.unwrap_or(0).saturating_mul(10).saturating_add(digit));
+        }
+        value
+    }
+
+    /// Parse the contents of a class after its `[`.
+    fn parse_class(&mut self) -> Result<CharClass, ParseError> {
+        let mut class = CharClass::default();
+        if self.peek_is(b'^') {
+            self.pos += 1;
+            class.negated = true;
+        }
+
+        loop {
+            let start = match self.peek() {
+                None => return Err(ParseError::UnclosedClass),
+                Some(c) if c == u16::from(b']') => {
+                    self.pos += 1;
+                    return Ok(class);
+                }
+                Some(_) => self.parse_class_atom(),
+            };
+
+            let is_range = self.peek_is(b'-')
+                && self.peek_at(1).is_some()
+                && self.peek_at(1) != Some(u16::from(b']'));
+            match start {
+                Ok(start) if is_range => {
+                    self.pos += 1;
+                    match self.parse_class_atom() {
+                        Ok(end) if end < start => return Err(ParseError::InvalidRange),
+                        Ok(end) => class.items.push(ClassItem::Range(start, end)),
+                        Err(item) => {
+                            // A range with a class at either end is read as
+                            // its parts and a literal hyphen.
+                            class.items.push(ClassItem::Range(start, start));
+                            class
+                                .items
+                                .push(ClassItem::Range(u16::from(b'-'), u16::from(b'-')));
+                            class.items.push(item);
+                        }
+                    }
+                }
+                Ok(c) => class.items.push(ClassItem::Range(c, c)),
+                Err(item) => class.items.push(item),
+            }
+        }
+    }
+
+    fn parse_class_atom(&mut self) -> Result<u16, ClassItem> {
+        let c = self.next().unwrap();
+        if c != u16::from(b'\\') {
+            return Ok(c);
+        }
+
+        match self.peek() {
+            // A trailing backslash is caught as an unclosed class.
+            None => Ok(c),
+            Some(b) if b == u16::from(b'b') => {
+                self.pos += 1;
+                Ok(0x08)
+            }
+            Some(d) if is_digit(d) && d != u16::from(b'0') => {
+                // Back references aren't meaningful in a class, so digits are
+                // read as an octal escape instead.
+                let mut value = 0;
+                while let Some(d) = self.peek() {
+                    if !(u16::from(b'0')..=u16::from(b'7')).contains(&d) || value * 8 > 0xFF {
+                        break;
+                    }
+                    self.pos += 1;
+                    value = value * 8 + (d - u16::from(b'0'));
+                }
+                if value == 0 && self.peek().map(is_digit).unwrap_or(false) {
+                    // `\8` and `\9` match themselves.
+                    return Ok(self.next().unwrap());
+                }
+                Ok(value)
+            }
+            Some(_) => self.parse_char_escape(),
+        }
+    }
+
+    /// Parse a quantifier after an atom, if there is one.
+    fn parse_quantifier(&mut self, atom: Node) -> Result<Node, ParseError> {
+        self.skip_whitespace();
+        let (min, max) = match self.peek() {
+            Some(c) if c == u16::from(b'*') => {
+                self.pos += 1;
+                (0, None)
+            }
+            Some(c) if c == u16::from(b'+') => {
+                self.pos += 1;
+                (1, None)
+            }
+            Some(c) if c == u16::from(b'?') => {
+                self.pos += 1;
+                (0, Some(1))
+            }
+            Some(c) if c == u16::from(b'{') => match self.parse_braces() {
+                Some(bounds) => bounds,
+                None => return Ok(atom),
+            },
+            _ => return Ok(atom),
+        };
+
+        if let Some(max) = max {
+            if max < min {
+                return Err(ParseError::InvalidQuantifier);
+            }
+        }
+
+        let greedy = if self.peek_is(b'?') {
+            self.pos += 1;
+            false
+        } else {
+            true
+        };
+
+        match atom {
+            Node::Start | Node::End | Node::WordBoundary { .. } => {
+                Err(ParseError::InvalidQuantifier)
+            }
+            atom => Ok(Node::Repeat {
+                node: Box::new(atom),
+                min,
+                max,
+                greedy,
+            }),
+        }
+    }
+
+    /// Parse a `{n}`, `{n,}` or `{n,m}` quantifier, leaving the position
+    /// untouched if there isn't a well formed one.
+    fn parse_braces(&mut self) -> Option<(u32, Option<u32>)> {
+        let start = self.pos;
+        let result = (|| {
+            self.pos += 1;
+            let min = self.parse_decimal()?;
+            let max = if self.peek_is(b',') {
+                self.pos += 1;
+                self.parse_decimal()
+            } else {
+                Some(min)
+            };
+            if !self.peek_is(b'}') {
+                return None;
+            }
+            self.pos += 1;
+            Some((min, max))
+        })();
+
+        if result.is_none() {
+            self.pos = start;
+        }
+        result
+    }
+}
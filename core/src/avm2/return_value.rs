@@ -81,7 +81,10 @@ impl<'gc> ReturnValue<'gc> {
     ///
     /// All return values must eventually resolved - it is a compile error to
     /// fail to do so.
-    pub fn resolve(self, activation: &mut Activation<'_, 'gc, '_>) -> Result<Value<'gc>, Error> {
+    pub fn resolve(
+        self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
         match self {
             Self::Immediate(v) => Ok(v),
             Self::ResultOf {
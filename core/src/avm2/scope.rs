@@ -111,7 +111,7 @@ impl<'gc> Scope<'gc> {
         &self,
         name: &Multiname<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Option<Object<'gc>>, Error> {
+    ) -> Result<Option<Object<'gc>>, Error<'gc>> {
         if let Some(qname) = self.locals().resolve_multiname(name)? {
             if self.locals().has_property(&qname)? {
                 return Ok(Some(*self.locals()));
@@ -141,7 +141,7 @@ impl<'gc> Scope<'gc> {
         &mut self,
         name: &Multiname<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Option<Value<'gc>>, Error> {
+    ) -> Result<Option<Value<'gc>>, Error<'gc>> {
         if let Some(qname) = self.locals().resolve_multiname(name)? {
             if self.locals().has_property(&qname)? {
                 return Ok(Some(self.values.get_property(
@@ -9,6 +9,7 @@ use crate::avm2::scope::Scope;
 use crate::avm2::string::AvmString;
 use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
+use crate::avm2::verify::{verify_method_body, VerifiedMethodBody};
 use crate::avm2::{Avm2, Error};
 use crate::collect::CollectWrapper;
 use crate::context::UpdateContext;
@@ -55,6 +56,14 @@ pub struct TranslationUnitData<'gc> {
 
     /// All strings loaded from the ABC's strings list.
     strings: FnvHashMap<u32, AvmString<'gc>>,
+
+    /// All method bodies decoded and verified from the ABC's method body list,
+    /// or the error that verifying them failed with.
+    method_bodies: FnvHashMap<u32, CollectWrapper<Result<Rc<VerifiedMethodBody>, String>>>,
+
+    /// The names of methods that are bound to a class or script, as they
+    /// appear in stack traces.
+    method_names: FnvHashMap<u32, AvmString<'gc>>,
 }
 
 impl<'gc> TranslationUnit<'gc> {
@@ -70,10 +79,18 @@ impl<'gc> TranslationUnit<'gc> {
                 methods: FnvHashMap::default(),
                 scripts: FnvHashMap::default(),
                 strings: FnvHashMap::default(),
+                method_bodies: FnvHashMap::default(),
+                method_names: FnvHashMap::default(),
             },
         ))
     }
 
+    /// Retrieve the domain that this translation unit's scripts were loaded
+    /// into.
+    pub fn domain(self) -> Domain<'gc> {
+        self.0.read().domain
+    }
+
     /// Retrieve the underlying `AbcFile` for this translation unit.
     pub fn abc(self) -> Rc<AbcFile> {
         self.0.read().abc.0.clone()
@@ -84,7 +101,7 @@ impl<'gc> TranslationUnit<'gc> {
         self,
         method_index: u32,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Method<'gc>, Error> {
+    ) -> Result<Method<'gc>, Error<'gc>> {
         let read = self.0.read();
         if let Some(method) = read.methods.get(&method_index) {
             return Ok(method.clone());
@@ -92,7 +109,7 @@ impl<'gc> TranslationUnit<'gc> {
 
         drop(read);
 
-        let method: Result<Gc<'gc, BytecodeMethod<'gc>>, Error> =
+        let method: Result<Gc<'gc, BytecodeMethod<'gc>>, Error<'gc>> =
             BytecodeMethod::from_method_index(self, Index::new(method_index), mc)
                 .ok_or_else(|| "Method index does not exist".into());
         let method: Method<'gc> = method?.into();
@@ -105,13 +122,91 @@ impl<'gc> TranslationUnit<'gc> {
         Ok(method)
     }
 
+    /// Load a method body from the ABC file and return its decoded
+    /// instructions.
+    ///
+    /// Method bodies are verified when they are first loaded, and this
+    /// function yields an error if the method body is malformed. Failures are
+    /// remembered too, so that a malformed body is only verified once.
+    pub fn load_method_body(
+        self,
+        body_index: u32,
+        mc: MutationContext<'gc, '_>,
+    ) -> Result<Rc<VerifiedMethodBody>, Error<'gc>> {
+        let read = self.0.read();
+        if let Some(body) = read.method_bodies.get(&body_index) {
+            return body.0.clone().map_err(|e| e.into());
+        }
+
+        let abc = read.abc.0.clone();
+
+        drop(read);
+
+        let body = match abc.method_bodies.get(body_index as usize) {
+            Some(body) => verify_method_body(&abc, body)
+                .map(Rc::new)
+                .map_err(|e| e.to_string()),
+            None => Err(format!("Method body index {} does not exist", body_index)),
+        };
+
+        self.0
+            .write(mc)
+            .method_bodies
+            .insert(body_index, CollectWrapper(body.clone()));
+
+        body.map_err(|e| e.into())
+    }
+
+    /// Record the name of a method, as it appears in stack traces.
+    ///
+    /// Methods are named after the first class or script trait that they are
+    /// loaded from; later names for the same method are ignored.
+    pub fn set_method_name(
+        self,
+        method_index: u32,
+        name: impl Into<String>,
+        mc: MutationContext<'gc, '_>,
+    ) {
+        self.0
+            .write(mc)
+            .method_names
+            .entry(method_index)
+            .or_insert_with(|| AvmString::new(mc, name));
+    }
+
+    /// Retrieve the name of a method, as it appears in stack traces.
+    ///
+    /// Methods that were not named after a trait use the name given to them in
+    /// the ABC file, and anonymous functions are named `Function/<anonymous>`.
+    pub fn method_name(
+        self,
+        method_index: u32,
+        mc: MutationContext<'gc, '_>,
+    ) -> Result<AvmString<'gc>, Error<'gc>> {
+        if let Some(name) = self.0.read().method_names.get(&method_index) {
+            return Ok(*name);
+        }
+
+        let abc = self.abc();
+        let name_index = abc
+            .methods
+            .get(method_index as usize)
+            .map(|method| method.name.0)
+            .unwrap_or(0);
+
+        match self.pool_string_option(name_index, mc)? {
+            Some(name) if !name.is_empty() => Ok(name),
+            _ => Ok("Function/<anonymous>".into()),
+        }
+    }
+
     /// Load a class from the ABC file and return its class definition.
     pub fn load_class(
         self,
         class_index: u32,
         avm2: &mut Avm2<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<GcCell<'gc, Class<'gc>>, Error> {
+    ) -> Result<GcCell<'gc, Class<'gc>>, Error<'gc>> {
         let read = self.0.read();
         if let Some(class) = read.classes.get(&class_index) {
             return Ok(*class);
@@ -133,7 +228,7 @@ impl<'gc> TranslationUnit<'gc> {
         script_index: u32,
         avm2: &mut Avm2<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Script<'gc>, Error> {
+    ) -> Result<Script<'gc>, Error<'gc>> {
         let read = self.0.read();
         if let Some(scripts) = read.scripts.get(&script_index) {
             return Ok(*scripts);
@@ -167,7 +262,7 @@ impl<'gc> TranslationUnit<'gc> {
         self,
         string_index: u32,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Option<AvmString<'gc>>, Error> {
+    ) -> Result<Option<AvmString<'gc>>, Error<'gc>> {
         let mut write = self.0.write(mc);
         if let Some(string) = write.strings.get(&string_index) {
             return Ok(Some(*string));
@@ -202,7 +297,7 @@ impl<'gc> TranslationUnit<'gc> {
         self,
         string_index: u32,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<AvmString<'gc>, Error> {
+    ) -> Result<AvmString<'gc>, Error<'gc>> {
         Ok(self
             .pool_string_option(string_index, mc)?
             .unwrap_or_default())
@@ -271,15 +366,16 @@ impl<'gc> Script<'gc> {
         script_index: u32,
         globals: Object<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Self, Error> {
+    ) -> Result<Self, Error<'gc>> {
         let abc = unit.abc();
-        let script: Result<&AbcScript, Error> = abc
+        let script: Result<&AbcScript, Error<'gc>> = abc
             .scripts
             .get(script_index as usize)
             .ok_or_else(|| "LoadError: Script index not valid".into());
         let script = script?;
 
         let init = unit.load_method(script.init_method.0, mc)?;
+        unit.set_method_name(script.init_method.0, "global$init", mc);
 
         Ok(Self(GcCell::allocate(
             mc,
@@ -305,7 +401,7 @@ impl<'gc> Script<'gc> {
         script_index: u32,
         avm2: &mut Avm2<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<(), Error> {
+    ) -> Result<(), Error<'gc>> {
         let mut write = self.0.write(mc);
 
         if write.traits_loaded {
@@ -315,7 +411,7 @@ impl<'gc> Script<'gc> {
         write.traits_loaded = true;
 
         let abc = unit.abc();
-        let script: Result<_, Error> = abc
+        let script: Result<_, Error<'gc>> = abc
             .scripts
             .get(script_index as usize)
             .ok_or_else(|| "LoadError: Script index not valid".into());
@@ -325,6 +421,7 @@ impl<'gc> Script<'gc> {
             drop(write);
 
             let newtrait = Trait::from_abc_trait(unit, &abc_trait, avm2, mc)?;
+            newtrait.name_method(unit, "global", mc);
 
             write = self.0.write(mc);
             write.traits.push(newtrait);
@@ -346,7 +443,7 @@ impl<'gc> Script<'gc> {
     pub fn globals(
         &mut self,
         context: &mut UpdateContext<'_, 'gc, '_>,
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         let mut write = self.0.write(context.gc_context);
         if !write.initialized {
             write.initialized = true;
@@ -378,7 +475,7 @@ impl<'gc> Script<'gc> {
     ///
     /// This function will return an error if it is incorrectly called before
     /// traits are loaded.
-    pub fn traits<'a>(&'a self) -> Result<Ref<'a, [Trait<'gc>]>, Error> {
+    pub fn traits<'a>(&'a self) -> Result<Ref<'a, [Trait<'gc>]>, Error<'gc>> {
         let read = self.0.read();
 
         if !read.traits_loaded {
@@ -1,8 +1,9 @@
 //! Slot contents type
 
+use crate::avm2::names::Multiname;
 use crate::avm2::property::Attribute;
 use crate::avm2::value::Value;
-use crate::avm2::Error;
+use crate::avm2::{Error, ErrorKind};
 use gc_arena::{Collect, CollectionContext};
 
 /// Represents a single slot on an object.
@@ -20,6 +21,12 @@ pub enum Slot<'gc> {
     Occupied {
         value: Value<'gc>,
         attributes: Attribute,
+
+        /// The declared type of the slot.
+        ///
+        /// Values are coerced to this type by the object that owns the slot
+        /// before they are written, as that may need to run user code.
+        type_name: Multiname<'gc>,
     },
 }
 
@@ -27,7 +34,12 @@ unsafe impl<'gc> Collect for Slot<'gc> {
     fn trace(&self, cc: CollectionContext) {
         match self {
             Self::Unoccupied => {}
-            Self::Occupied { value, .. } => value.trace(cc),
+            Self::Occupied {
+                value, type_name, ..
+            } => {
+                value.trace(cc);
+                type_name.trace(cc);
+            }
         }
     }
 }
@@ -41,9 +53,15 @@ impl<'gc> Default for Slot<'gc> {
 impl<'gc> Slot<'gc> {
     /// Create a normal slot with a given value.
     pub fn new(value: impl Into<Value<'gc>>) -> Self {
+        Self::new_typed(value, Multiname::any())
+    }
+
+    /// Create a normal slot with a given value and declared type.
+    pub fn new_typed(value: impl Into<Value<'gc>>, type_name: Multiname<'gc>) -> Self {
         Self::Occupied {
             value: value.into(),
             attributes: Attribute::empty(),
+            type_name,
         }
     }
 
@@ -52,6 +70,7 @@ impl<'gc> Slot<'gc> {
         Self::Occupied {
             value: value.into(),
             attributes: Attribute::READ_ONLY,
+            type_name: Multiname::any(),
         }
     }
 
@@ -63,17 +82,30 @@ impl<'gc> Slot<'gc> {
         }
     }
 
+    /// Retrieve the declared type of this slot.
+    ///
+    /// Returns `None` for unoccupied slots and for slots of the any type.
+    pub fn type_name(&self) -> Option<&Multiname<'gc>> {
+        match self {
+            Self::Occupied { type_name, .. } if type_name.local_name().is_some() => Some(type_name),
+            _ => None,
+        }
+    }
+
     /// Write the value of this slot.
-    pub fn set(&mut self, new_value: impl Into<Value<'gc>>) -> Result<(), Error> {
+    pub fn set(&mut self, new_value: impl Into<Value<'gc>>) -> Result<(), Error<'gc>> {
         match self {
             Self::Unoccupied => Err("Cannot overwrite unoccupied slot".into()),
-            Self::Occupied { value, attributes } => {
+            Self::Occupied {
+                value, attributes, ..
+            } => {
                 if attributes.contains(Attribute::READ_ONLY) {
-                    return Err("Cannot overwrite const slot".into());
+                    return Err(Error::new(
+                        ErrorKind::ReferenceError,
+                        "Error #1074: Illegal write to read-only property.",
+                    ));
                 }
 
-                //TODO: Type assert
-
                 *value = new_value.into();
 
                 Ok(())
@@ -82,12 +114,10 @@ impl<'gc> Slot<'gc> {
     }
 
     /// Initialize a slot to a particular value.
-    pub fn init(&mut self, new_value: impl Into<Value<'gc>>) -> Result<(), Error> {
+    pub fn init(&mut self, new_value: impl Into<Value<'gc>>) -> Result<(), Error<'gc>> {
         match self {
             Self::Unoccupied => Err("Cannot initialize unoccupied slot".into()),
             Self::Occupied { value, .. } => {
-                //TODO: Type assert
-
                 *value = new_value.into();
 
                 Ok(())
@@ -4,6 +4,7 @@ use crate::avm2::class::Class;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Multiname, QName};
 use crate::avm2::script::TranslationUnit;
+use crate::avm2::string::AvmString;
 use crate::avm2::value::{abc_default_value, Value};
 use crate::avm2::{Avm2, Error};
 use crate::collect::CollectWrapper;
@@ -45,6 +46,31 @@ pub struct Trait<'gc> {
 
     /// The kind of trait in use.
     kind: TraitKind<'gc>,
+
+    /// The metadata tags attached to this trait in ABC.
+    metadata: Vec<Metadata<'gc>>,
+}
+
+/// A metadata tag attached to a trait, such as `[Inject(name="foo")]`.
+#[derive(Clone, Debug, Collect)]
+#[collect(no_drop)]
+pub struct Metadata<'gc> {
+    /// The name of the tag.
+    pub name: AvmString<'gc>,
+
+    /// The arguments of the tag, in order.
+    pub items: Vec<MetadataItem<'gc>>,
+}
+
+/// An argument of a metadata tag.
+///
+/// Arguments without a key, such as the `foo` in `[Event("foo")]`, have an
+/// empty key.
+#[derive(Clone, Debug, Collect)]
+#[collect(no_drop)]
+pub struct MetadataItem<'gc> {
+    pub key: AvmString<'gc>,
+    pub value: AvmString<'gc>,
 }
 
 fn trait_attribs_from_abc_traits(abc_trait: &AbcTrait) -> CollectWrapper<TraitAttributes> {
@@ -104,6 +130,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: CollectWrapper(TraitAttributes::empty()),
+            metadata: Vec::new(),
             kind: TraitKind::Class { slot_id: 0, class },
         }
     }
@@ -112,6 +139,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: CollectWrapper(TraitAttributes::empty()),
+            metadata: Vec::new(),
             kind: TraitKind::Method { disp_id: 0, method },
         }
     }
@@ -120,6 +148,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: CollectWrapper(TraitAttributes::empty()),
+            metadata: Vec::new(),
             kind: TraitKind::Getter { disp_id: 0, method },
         }
     }
@@ -128,6 +157,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: CollectWrapper(TraitAttributes::empty()),
+            metadata: Vec::new(),
             kind: TraitKind::Setter { disp_id: 0, method },
         }
     }
@@ -136,6 +166,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: CollectWrapper(TraitAttributes::empty()),
+            metadata: Vec::new(),
             kind: TraitKind::Function {
                 slot_id: 0,
                 function,
@@ -151,6 +182,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: CollectWrapper(TraitAttributes::empty()),
+            metadata: Vec::new(),
             kind: TraitKind::Slot {
                 slot_id: 0,
                 type_name,
@@ -167,6 +199,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: CollectWrapper(TraitAttributes::empty()),
+            metadata: Vec::new(),
             kind: TraitKind::Slot {
                 slot_id: 0,
                 type_name,
@@ -181,92 +214,92 @@ impl<'gc> Trait<'gc> {
         abc_trait: &AbcTrait,
         avm2: &mut Avm2<'gc>,
         mc: MutationContext<'gc, '_>,
-    ) -> Result<Self, Error> {
+    ) -> Result<Self, Error<'gc>> {
         let name = QName::from_abc_multiname(unit, abc_trait.name.clone(), mc)?;
 
-        Ok(match &abc_trait.kind {
+        let kind = match &abc_trait.kind {
             AbcTraitKind::Slot {
                 slot_id,
                 type_name,
                 value,
-            } => Trait {
-                name,
-                attributes: trait_attribs_from_abc_traits(abc_trait),
-                kind: TraitKind::Slot {
-                    slot_id: *slot_id,
-                    type_name: if type_name.0 == 0 {
-                        Multiname::any()
-                    } else {
-                        Multiname::from_abc_multiname_static(unit, type_name.clone(), mc)?
-                    },
-                    default_value: if let Some(dv) = value {
-                        Some(abc_default_value(unit, &dv, avm2, mc)?)
-                    } else {
-                        None
-                    },
+            } => TraitKind::Slot {
+                slot_id: *slot_id,
+                type_name: if type_name.0 == 0 {
+                    Multiname::any()
+                } else {
+                    Multiname::from_abc_multiname_static(unit, type_name.clone(), mc)?
                 },
-            },
-            AbcTraitKind::Method { disp_id, method } => Trait {
-                name,
-                attributes: trait_attribs_from_abc_traits(abc_trait),
-                kind: TraitKind::Method {
-                    disp_id: *disp_id,
-                    method: unit.load_method(method.0, mc)?,
+                default_value: if let Some(dv) = value {
+                    Some(abc_default_value(unit, &dv, avm2, mc)?)
+                } else {
+                    None
                 },
             },
-            AbcTraitKind::Getter { disp_id, method } => Trait {
-                name,
-                attributes: trait_attribs_from_abc_traits(abc_trait),
-                kind: TraitKind::Getter {
-                    disp_id: *disp_id,
-                    method: unit.load_method(method.0, mc)?,
-                },
+            AbcTraitKind::Method { disp_id, method } => TraitKind::Method {
+                disp_id: *disp_id,
+                method: unit.load_method(method.0, mc)?,
             },
-            AbcTraitKind::Setter { disp_id, method } => Trait {
-                name,
-                attributes: trait_attribs_from_abc_traits(abc_trait),
-                kind: TraitKind::Setter {
-                    disp_id: *disp_id,
-                    method: unit.load_method(method.0, mc)?,
-                },
+            AbcTraitKind::Getter { disp_id, method } => TraitKind::Getter {
+                disp_id: *disp_id,
+                method: unit.load_method(method.0, mc)?,
             },
-            AbcTraitKind::Class { slot_id, class } => Trait {
-                name,
-                attributes: trait_attribs_from_abc_traits(abc_trait),
-                kind: TraitKind::Class {
-                    slot_id: *slot_id,
-                    class: unit.load_class(class.0, avm2, mc)?,
-                },
+            AbcTraitKind::Setter { disp_id, method } => TraitKind::Setter {
+                disp_id: *disp_id,
+                method: unit.load_method(method.0, mc)?,
             },
-            AbcTraitKind::Function { slot_id, function } => Trait {
-                name,
-                attributes: trait_attribs_from_abc_traits(abc_trait),
-                kind: TraitKind::Function {
-                    slot_id: *slot_id,
-                    function: unit.load_method(function.0, mc)?,
-                },
+            AbcTraitKind::Class { slot_id, class } => TraitKind::Class {
+                slot_id: *slot_id,
+                class: unit.load_class(class.0, avm2, mc)?,
+            },
+            AbcTraitKind::Function { slot_id, function } => TraitKind::Function {
+                slot_id: *slot_id,
+                function: unit.load_method(function.0, mc)?,
             },
             AbcTraitKind::Const {
                 slot_id,
                 type_name,
                 value,
-            } => Trait {
-                name,
-                attributes: trait_attribs_from_abc_traits(abc_trait),
-                kind: TraitKind::Const {
-                    slot_id: *slot_id,
-                    type_name: if type_name.0 == 0 {
-                        Multiname::any()
-                    } else {
-                        Multiname::from_abc_multiname_static(unit, type_name.clone(), mc)?
-                    },
-                    default_value: if let Some(dv) = value {
-                        Some(abc_default_value(unit, &dv, avm2, mc)?)
-                    } else {
-                        None
-                    },
+            } => TraitKind::Const {
+                slot_id: *slot_id,
+                type_name: if type_name.0 == 0 {
+                    Multiname::any()
+                } else {
+                    Multiname::from_abc_multiname_static(unit, type_name.clone(), mc)?
+                },
+                default_value: if let Some(dv) = value {
+                    Some(abc_default_value(unit, &dv, avm2, mc)?)
+                } else {
+                    None
                 },
             },
+        };
+
+        let abc = unit.abc();
+        let mut metadata = Vec::new();
+        for metadata_index in abc_trait.metadata.iter() {
+            let abc_metadata = abc
+                .metadata
+                .get(metadata_index.0 as usize)
+                .ok_or_else(|| format!("Unknown metadata {}", metadata_index.0))?;
+            let mut items = Vec::new();
+            for item in abc_metadata.items.iter() {
+                items.push(MetadataItem {
+                    key: unit.pool_string(item.key.0, mc)?,
+                    value: unit.pool_string(item.value.0, mc)?,
+                });
+            }
+
+            metadata.push(Metadata {
+                name: unit.pool_string(abc_metadata.name.0, mc)?,
+                items,
+            });
+        }
+
+        Ok(Trait {
+            name,
+            attributes: trait_attribs_from_abc_traits(abc_trait),
+            kind,
+            metadata,
         })
     }
 
@@ -274,10 +307,42 @@ impl<'gc> Trait<'gc> {
         &self.name
     }
 
+    /// Name the bytecode method bound by this trait after the trait, as it
+    /// appears in stack traces.
+    ///
+    /// `owner` is the name of the class (or `global`, for scripts) that the
+    /// trait was loaded into. Accessors are prefixed with `get` or `set`.
+    pub fn name_method(
+        &self,
+        unit: TranslationUnit<'gc>,
+        owner: &str,
+        mc: MutationContext<'gc, '_>,
+    ) {
+        let (prefix, method) = match &self.kind {
+            TraitKind::Method { method, .. } => ("", method),
+            TraitKind::Getter { method, .. } => ("get ", method),
+            TraitKind::Setter { method, .. } => ("set ", method),
+            TraitKind::Function { function, .. } => ("", function),
+            _ => return,
+        };
+
+        if let Method::Entry(bm) = method {
+            unit.set_method_name(
+                bm.abc_method,
+                format!("{}/{}{}", owner, prefix, self.name.to_qualified_name()),
+                mc,
+            );
+        }
+    }
+
     pub fn kind(&self) -> &TraitKind<'gc> {
         &self.kind
     }
 
+    pub fn metadata(&self) -> &[Metadata<'gc>] {
+        &self.metadata
+    }
+
     pub fn is_final(&self) -> bool {
         self.attributes.0.contains(TraitAttributes::FINAL)
     }
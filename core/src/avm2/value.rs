@@ -1,12 +1,13 @@
 //! AVM2 values
 
 use crate::avm2::activation::Activation;
+use crate::avm2::names::Multiname;
 use crate::avm2::names::Namespace;
 use crate::avm2::names::QName;
 use crate::avm2::object::{NamespaceObject, Object, PrimitiveObject, TObject};
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::string::AvmString;
-use crate::avm2::{Avm2, Error};
+use crate::avm2::{Avm2, Error, ErrorKind};
 use crate::ecma_conversions::{f64_to_wrapping_i32, f64_to_wrapping_u32};
 use gc_arena::{Collect, MutationContext};
 use std::cell::Ref;
@@ -28,7 +29,7 @@ pub enum Hint {
 
 /// An AVM2 value.
 ///
-/// TODO: AVM2 also needs Scope, Namespace, and XML values.
+/// TODO: AVM2 also needs Scope and Namespace values.
 #[derive(Clone, Collect, Debug)]
 #[collect(no_drop)]
 pub enum Value<'gc> {
@@ -139,7 +140,10 @@ impl PartialEq for Value<'_> {
     }
 }
 
-pub fn abc_int(translation_unit: TranslationUnit<'_>, index: Index<i32>) -> Result<i32, Error> {
+pub fn abc_int<'gc>(
+    translation_unit: TranslationUnit<'gc>,
+    index: Index<i32>,
+) -> Result<i32, Error<'gc>> {
     if index.0 == 0 {
         return Ok(0);
     }
@@ -153,7 +157,10 @@ pub fn abc_int(translation_unit: TranslationUnit<'_>, index: Index<i32>) -> Resu
         .ok_or_else(|| format!("Unknown int constant {}", index.0).into())
 }
 
-pub fn abc_uint(translation_unit: TranslationUnit<'_>, index: Index<u32>) -> Result<u32, Error> {
+pub fn abc_uint<'gc>(
+    translation_unit: TranslationUnit<'gc>,
+    index: Index<u32>,
+) -> Result<u32, Error<'gc>> {
     if index.0 == 0 {
         return Ok(0);
     }
@@ -167,7 +174,10 @@ pub fn abc_uint(translation_unit: TranslationUnit<'_>, index: Index<u32>) -> Res
         .ok_or_else(|| format!("Unknown uint constant {}", index.0).into())
 }
 
-pub fn abc_double(translation_unit: TranslationUnit<'_>, index: Index<f64>) -> Result<f64, Error> {
+pub fn abc_double<'gc>(
+    translation_unit: TranslationUnit<'gc>,
+    index: Index<f64>,
+) -> Result<f64, Error<'gc>> {
     if index.0 == 0 {
         return Ok(NAN);
     }
@@ -187,7 +197,7 @@ pub fn abc_default_value<'gc>(
     default: &AbcDefaultValue,
     avm2: &mut Avm2<'gc>,
     mc: MutationContext<'gc, '_>,
-) -> Result<Value<'gc>, Error> {
+) -> Result<Value<'gc>, Error<'gc>> {
     match default {
         AbcDefaultValue::Int(i) => abc_int(translation_unit, *i).map(|v| v.into()),
         AbcDefaultValue::Uint(u) => abc_uint(translation_unit, *u).map(|v| v.into()),
@@ -212,8 +222,31 @@ pub fn abc_default_value<'gc>(
     }
 }
 
+/// The value that a slot of a given type holds before it is first written.
+///
+/// Slots of the any type start out `undefined`, numeric and `Boolean` slots
+/// start out as their zero value, and all other slots start out `null`.
+pub fn default_value_for_type<'gc>(type_name: &Multiname<'gc>) -> Value<'gc> {
+    let local_name = match type_name.local_name() {
+        Some(local_name) => local_name,
+        None => return Value::Undefined,
+    };
+
+    if !type_name.namespace_set().any(|ns| ns.is_public()) {
+        return Value::Null;
+    }
+
+    match local_name.as_str() {
+        "int" => Value::Integer(0),
+        "uint" => Value::Unsigned(0),
+        "Number" => Value::Number(NAN),
+        "Boolean" => Value::Bool(false),
+        _ => Value::Null,
+    }
+}
+
 impl<'gc> Value<'gc> {
-    pub fn as_namespace(&self) -> Result<Ref<Namespace<'gc>>, Error> {
+    pub fn as_namespace(&self) -> Result<Ref<Namespace<'gc>>, Error<'gc>> {
         match self {
             Value::Object(ns) => ns
                 .as_namespace()
@@ -228,7 +261,7 @@ impl<'gc> Value<'gc> {
     /// object methods called. This should only be used if you specifically
     /// need the behavior of only handling actual numbers; otherwise you should
     /// use the appropriate `coerce_to_` method.
-    pub fn as_number(&self, mc: MutationContext<'gc, '_>) -> Result<f64, Error> {
+    pub fn as_number(&self, mc: MutationContext<'gc, '_>) -> Result<f64, Error<'gc>> {
         match self {
             // Methods that look for numbers in Flash Player don't seem to care
             // about user-defined `valueOf` implementations. This code upholds
@@ -288,7 +321,7 @@ impl<'gc> Value<'gc> {
         &self,
         hint: Option<Hint>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Value<'gc>, Error> {
+    ) -> Result<Value<'gc>, Error<'gc>> {
         let hint = hint.unwrap_or_else(|| match self {
             Value::Object(o) => o.default_hint(),
             _ => Hint::Number,
@@ -319,7 +352,10 @@ impl<'gc> Value<'gc> {
                     return Ok(prim);
                 }
 
-                Err("TypeError: cannot convert object to string".into())
+                Err(Error::new(
+                    ErrorKind::TypeError,
+                    "cannot convert object to string",
+                ))
             }
             Value::Object(o) if hint == Hint::Number => {
                 let mut prim = self.clone();
@@ -345,7 +381,10 @@ impl<'gc> Value<'gc> {
                     return Ok(prim);
                 }
 
-                Err("TypeError: cannot convert object to number".into())
+                Err(Error::new(
+                    ErrorKind::TypeError,
+                    "cannot convert object to number",
+                ))
             }
             _ => Ok(self.clone()),
         }
@@ -359,7 +398,10 @@ impl<'gc> Value<'gc> {
     ///
     /// Numerical conversions occur according to ECMA-262 3rd Edition's
     /// ToNumber algorithm which appears to match AVM2.
-    pub fn coerce_to_number(&self, activation: &mut Activation<'_, 'gc, '_>) -> Result<f64, Error> {
+    pub fn coerce_to_number(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<f64, Error<'gc>> {
         Ok(match self {
             Value::Undefined => f64::NAN,
             Value::Null => 0.0,
@@ -430,7 +472,10 @@ impl<'gc> Value<'gc> {
     ///
     /// Numerical conversions occur according to ECMA-262 3rd Edition's
     /// ToUint32 algorithm which appears to match AVM2.
-    pub fn coerce_to_u32(&self, activation: &mut Activation<'_, 'gc, '_>) -> Result<u32, Error> {
+    pub fn coerce_to_u32(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<u32, Error<'gc>> {
         Ok(f64_to_wrapping_u32(self.coerce_to_number(activation)?))
     }
 
@@ -441,7 +486,10 @@ impl<'gc> Value<'gc> {
     ///
     /// Numerical conversions occur according to ECMA-262 3rd Edition's
     /// ToInt32 algorithm which appears to match AVM2.
-    pub fn coerce_to_i32(&self, activation: &mut Activation<'_, 'gc, '_>) -> Result<i32, Error> {
+    pub fn coerce_to_i32(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<i32, Error<'gc>> {
         Ok(f64_to_wrapping_i32(self.coerce_to_number(activation)?))
     }
 
@@ -477,7 +525,7 @@ impl<'gc> Value<'gc> {
     pub fn coerce_to_string<'a>(
         &'a self,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<AvmString<'gc>, Error> {
+    ) -> Result<AvmString<'gc>, Error<'gc>> {
         Ok(match self {
             Value::Undefined => "undefined".into(),
             Value::Null => "null".into(),
@@ -530,7 +578,7 @@ impl<'gc> Value<'gc> {
     pub fn coerce_to_debug_string<'a>(
         &'a self,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<AvmString<'gc>, Error> {
+    ) -> Result<AvmString<'gc>, Error<'gc>> {
         Ok(match self {
             Value::String(s) => AvmString::new(activation.context.gc_context, format!("\"{}\"", s)),
             Value::Object(_) => self
@@ -551,10 +599,20 @@ impl<'gc> Value<'gc> {
     pub fn coerce_to_object(
         &self,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Object<'gc>, Error> {
+    ) -> Result<Object<'gc>, Error<'gc>> {
         match self {
-            Value::Undefined => return Err("TypeError: undefined is not an Object".into()),
-            Value::Null => return Err("TypeError: null is not an Object".into()),
+            Value::Undefined => {
+                return Err(Error::new(
+                    ErrorKind::TypeError,
+                    "Error #1010: A term is undefined and has no properties.",
+                ))
+            }
+            Value::Null => {
+                return Err(Error::new(
+                    ErrorKind::TypeError,
+                    "Error #1009: Cannot access a property or method of a null object reference.",
+                ))
+            }
             Value::Object(o) => return Ok(*o),
             _ => {}
         };
@@ -571,6 +629,142 @@ impl<'gc> Value<'gc> {
         PrimitiveObject::from_primitive(self.clone(), proto, activation.context.gc_context)
     }
 
+    /// Determine the name of the type of this value, as returned by the
+    /// `typeof` operator.
+    pub fn type_of(&self) -> &'static str {
+        match self {
+            Value::Undefined => "undefined",
+            Value::Null => "object",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) | Value::Unsigned(_) | Value::Integer(_) => "number",
+            Value::String(_) => "string",
+            Value::Object(o) if o.as_executable().is_some() && o.as_class().is_none() => "function",
+            Value::Object(o) if o.as_xml_object().is_some() || o.as_xml_list_object().is_some() => {
+                "xml"
+            }
+            Value::Object(_) => "object",
+        }
+    }
+
+    /// Determine if this value is of a given type, as the `is` operator does.
+    ///
+    /// The numeric types are checked by value rather than by representation,
+    /// so `1.0 is int` is `true` and `-1 is uint` is `false`. `null` and
+    /// `undefined` are not of any class type.
+    pub fn is_of_type(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut type_object: Object<'gc>,
+    ) -> Result<bool, Error<'gc>> {
+        let type_proto = type_object
+            .get_property(
+                type_object,
+                &QName::new(Namespace::public(), "prototype"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+        let number = match self {
+            Value::Number(n) => Some(*n),
+            Value::Unsigned(u) => Some(f64::from(*u)),
+            Value::Integer(i) => Some(f64::from(*i)),
+            _ => None,
+        };
+
+        if let Some(n) = number {
+            let prototypes = activation.avm2().prototypes();
+            if Object::ptr_eq(type_proto, prototypes.int) {
+                return Ok(f64::from(n as i32).to_bits() == n.to_bits());
+            }
+            if Object::ptr_eq(type_proto, prototypes.uint) {
+                return Ok(f64::from(n as u32).to_bits() == n.to_bits());
+            }
+            if Object::ptr_eq(type_proto, prototypes.number) {
+                return Ok(true);
+            }
+        }
+
+        match self {
+            Value::Undefined | Value::Null => Ok(false),
+            _ => self
+                .coerce_to_object(activation)?
+                .is_instance_of(activation, type_object, true),
+        }
+    }
+
+    /// Coerce this value to a given type, as is done when a value is stored
+    /// in a typed slot or passed as a typed parameter, or by the `coerce`
+    /// opcode.
+    ///
+    /// Values are converted to the primitive types. Values of any other type
+    /// are passed through unchanged if they are instances of that type, and
+    /// otherwise a `TypeError` is raised. `null` and `undefined` coerce to
+    /// `null` for every class type.
+    pub fn coerce_to_type(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut type_object: Object<'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let type_proto = type_object
+            .get_property(
+                type_object,
+                &QName::new(Namespace::public(), "prototype"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+
+        if Object::ptr_eq(type_proto, activation.avm2().prototypes().int) {
+            return Ok(Value::Integer(self.coerce_to_i32(activation)?));
+        }
+
+        if Object::ptr_eq(type_proto, activation.avm2().prototypes().uint) {
+            return Ok(Value::Unsigned(self.coerce_to_u32(activation)?));
+        }
+
+        if Object::ptr_eq(type_proto, activation.avm2().prototypes().number) {
+            return Ok(Value::Number(self.coerce_to_number(activation)?));
+        }
+
+        if Object::ptr_eq(type_proto, activation.avm2().prototypes().boolean) {
+            return Ok(Value::Bool(self.coerce_to_boolean()));
+        }
+
+        if Object::ptr_eq(type_proto, activation.avm2().prototypes().string) {
+            return match self {
+                Value::Undefined | Value::Null => Ok(Value::Null),
+                _ => Ok(self.coerce_to_string(activation)?.into()),
+            };
+        }
+
+        if Object::ptr_eq(type_proto, activation.avm2().prototypes().object) {
+            return match self {
+                Value::Undefined => Ok(Value::Null),
+                _ => Ok(self.clone()),
+            };
+        }
+
+        if let Value::Undefined | Value::Null = self {
+            return Ok(Value::Null);
+        }
+
+        if self.is_of_type(activation, type_object)? {
+            return Ok(self.clone());
+        }
+
+        let type_name = type_object
+            .as_class()
+            .map(|class| class.read().name().local_name().to_string())
+            .unwrap_or_else(|| "Object".to_string());
+
+        Err(Error::new(
+            ErrorKind::TypeError,
+            format!(
+                "Error #1034: Type Coercion failed: cannot convert {} to {}.",
+                self.type_of(),
+                type_name
+            ),
+        ))
+    }
+
     /// Determine if two values are abstractly equal to each other.
     ///
     /// This abstract equality algorithm is intended to match ECMA-262 3rd
@@ -580,7 +774,7 @@ impl<'gc> Value<'gc> {
         &self,
         other: &Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<bool, Error> {
+    ) -> Result<bool, Error<'gc>> {
         match (self, other) {
             (Value::Undefined, Value::Undefined) => Ok(true),
             (Value::Null, Value::Null) => Ok(true),
@@ -671,7 +865,7 @@ impl<'gc> Value<'gc> {
         &self,
         other: &Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<Option<bool>, Error> {
+    ) -> Result<Option<bool>, Error<'gc>> {
         let prim_self = self.coerce_to_primitive(Some(Hint::Number), activation)?;
         let prim_other = other.coerce_to_primitive(Some(Hint::Number), activation)?;
 
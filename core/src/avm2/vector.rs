@@ -0,0 +1,277 @@
+//! Vector support types
+
+use crate::avm2::activation::Activation;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::{Error, ErrorKind};
+use gc_arena::Collect;
+use std::ops::Range;
+
+/// The longest a vector may be.
+///
+/// Flash Player runs out of memory well before a vector reaches the largest
+/// length it could have, so vectors are kept to a length that can actually
+/// be allocated.
+pub const MAX_VECTOR_LENGTH: usize = 1 << 26;
+
+/// The vector storage portion of a vector object.
+///
+/// Unlike arrays, vectors have no holes, and every value in a vector has been
+/// coerced to the vector's value type. Reading or writing past the end of a
+/// vector is an error, as is changing the length of a fixed vector.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct VectorStorage<'gc> {
+    /// The values in the vector.
+    storage: Vec<Value<'gc>>,
+
+    /// Whether or not the length of the vector is fixed.
+    is_fixed: bool,
+
+    /// The class of the values in the vector, or `None` for `Vector.<*>`.
+    value_type: Option<Object<'gc>>,
+
+    /// The value that new slots in the vector are filled with.
+    default_value: Value<'gc>,
+}
+
+impl<'gc> VectorStorage<'gc> {
+    /// Construct new vector storage of a given type.
+    ///
+    /// The default value should be the one `default_value` returns for the
+    /// value type.
+    pub fn new(
+        length: usize,
+        is_fixed: bool,
+        value_type: Option<Object<'gc>>,
+        default_value: Value<'gc>,
+    ) -> Self {
+        Self {
+            storage: vec![default_value.clone(); length],
+            is_fixed,
+            value_type,
+            default_value,
+        }
+    }
+
+    /// Construct new, non-fixed vector storage of the same type as this one,
+    /// holding a set of values that have already been coerced to that type.
+    pub fn with_values(&self, values: Vec<Value<'gc>>) -> Self {
+        Self {
+            storage: values,
+            is_fixed: false,
+            value_type: self.value_type,
+            default_value: self.default_value.clone(),
+        }
+    }
+
+    /// Determine the value that new slots in a vector of the given type are
+    /// filled with.
+    pub fn default_value(
+        activation: &mut Activation<'_, 'gc, '_>,
+        value_type: Option<Object<'gc>>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let mut value_type = match value_type {
+            Some(value_type) => value_type,
+            None => return Ok(Value::Undefined),
+        };
+
+        let type_proto = value_type
+            .get_property(
+                value_type,
+                &QName::new(Namespace::public(), "prototype"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+        let prototypes = activation.avm2().prototypes();
+
+        if Object::ptr_eq(type_proto, prototypes.int) || Object::ptr_eq(type_proto, prototypes.uint)
+        {
+            Ok(Value::Number(0.0))
+        } else if Object::ptr_eq(type_proto, prototypes.number) {
+            Ok(Value::Number(f64::NAN))
+        } else if Object::ptr_eq(type_proto, prototypes.boolean) {
+            Ok(Value::Bool(false))
+        } else {
+            Ok(Value::Null)
+        }
+    }
+
+    /// Coerce a value to the value type of a vector.
+    pub fn coerce(
+        activation: &mut Activation<'_, 'gc, '_>,
+        value_type: Option<Object<'gc>>,
+        value: Value<'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        match value_type {
+            Some(value_type) => value.coerce_to_type(activation, value_type),
+            None => Ok(value),
+        }
+    }
+
+    /// The class of the values in the vector, or `None` for `Vector.<*>`.
+    pub fn value_type(&self) -> Option<Object<'gc>> {
+        self.value_type
+    }
+
+    pub fn is_fixed(&self) -> bool {
+        self.is_fixed
+    }
+
+    pub fn set_is_fixed(&mut self, is_fixed: bool) {
+        self.is_fixed = is_fixed;
+    }
+
+    /// Raise an error if the length of the vector is fixed.
+    pub fn check_fixed(&self) -> Result<(), Error<'gc>> {
+        if self.is_fixed {
+            return Err(Error::new(
+                ErrorKind::RangeError,
+                "Error #1126: Cannot change the length of a fixed Vector.",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Get the length of the vector.
+    pub fn length(&self) -> usize {
+        self.storage.len()
+    }
+
+    /// Set the length of the vector, filling new slots with the default
+    /// value.
+    pub fn set_length(&mut self, length: usize) -> Result<(), Error<'gc>> {
+        self.check_fixed()?;
+        if length > MAX_VECTOR_LENGTH {
+            return Err(Error::new(
+                ErrorKind::RangeError,
+                format!(
+                    "Error #1125: The index {} is out of range {}.",
+                    length, MAX_VECTOR_LENGTH
+                ),
+            ));
+        }
+
+        self.storage.resize(length, self.default_value.clone());
+
+        Ok(())
+    }
+
+    /// Retrieve a value from the vector by index.
+    pub fn get(&self, index: usize) -> Result<Value<'gc>, Error<'gc>> {
+        self.storage.get(index).cloned().ok_or_else(|| {
+            Error::new(
+                ErrorKind::RangeError,
+                format!(
+                    "Error #1125: The index {} is out of range {}.",
+                    index,
+                    self.length()
+                ),
+            )
+        })
+    }
+
+    /// Set a value in the vector by index.
+    ///
+    /// The value must already have been coerced to the value type. Setting
+    /// the slot just past the end of a vector that isn't fixed appends to it.
+    pub fn set(&mut self, index: usize, value: Value<'gc>) -> Result<(), Error<'gc>> {
+        if index == self.length() && !self.is_fixed {
+            self.storage.push(value);
+            return Ok(());
+        }
+
+        match self.storage.get_mut(index) {
+            Some(slot) => {
+                *slot = value;
+                Ok(())
+            }
+            None => Err(Error::new(
+                ErrorKind::RangeError,
+                format!(
+                    "Error #1125: The index {} is out of range {}.",
+                    index,
+                    self.length()
+                ),
+            )),
+        }
+    }
+
+    /// Append a value, which must already have been coerced to the value
+    /// type, to the end of the vector.
+    pub fn push(&mut self, value: Value<'gc>) -> Result<(), Error<'gc>> {
+        self.check_fixed()?;
+        self.storage.push(value);
+
+        Ok(())
+    }
+
+    /// Remove the last value in the vector.
+    ///
+    /// Empty vectors yield the default value.
+    pub fn pop(&mut self) -> Result<Value<'gc>, Error<'gc>> {
+        self.check_fixed()?;
+
+        Ok(self
+            .storage
+            .pop()
+            .unwrap_or_else(|| self.default_value.clone()))
+    }
+
+    /// Insert a value, which must already have been coerced to the value
+    /// type, at the start of the vector.
+    pub fn unshift(&mut self, value: Value<'gc>) -> Result<(), Error<'gc>> {
+        self.check_fixed()?;
+        self.storage.insert(0, value);
+
+        Ok(())
+    }
+
+    /// Remove the first value in the vector.
+    ///
+    /// Empty vectors yield the default value.
+    pub fn shift(&mut self) -> Result<Value<'gc>, Error<'gc>> {
+        self.check_fixed()?;
+
+        if self.storage.is_empty() {
+            return Ok(self.default_value.clone());
+        }
+
+        Ok(self.storage.remove(0))
+    }
+
+    /// Replace a range of the vector with a set of values that have already
+    /// been coerced to the value type, returning the values that were
+    /// removed.
+    ///
+    /// Fixed vectors can only have ranges replaced with as many values as
+    /// they hold.
+    pub fn splice(
+        &mut self,
+        range: Range<usize>,
+        values: Vec<Value<'gc>>,
+    ) -> Result<Vec<Value<'gc>>, Error<'gc>> {
+        if range.len() != values.len() {
+            self.check_fixed()?;
+        }
+
+        Ok(self.storage.splice(range, values).collect())
+    }
+
+    /// Reverse the order of the values in the vector.
+    pub fn reverse(&mut self) {
+        self.storage.reverse();
+    }
+
+    /// Replace every value in the vector with a reordering of them.
+    pub fn replace(&mut self, values: Vec<Value<'gc>>) {
+        self.storage = values;
+    }
+
+    /// Iterate over the values in the vector.
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = Value<'gc>> + 'a {
+        self.storage.iter().cloned()
+    }
+}
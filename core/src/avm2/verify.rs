@@ -0,0 +1,635 @@
+//! Method body decoding and verification
+//!
+//! Method bodies are decoded into a list of instructions once, before they are
+//! first run. Branch targets and exception handler ranges are rewritten from
+//! byte offsets into instruction indices, and the whole body is checked
+//! against the limits it declares, so that the interpreter never has to deal
+//! with malformed bytecode halfway through a method.
+
+use crate::avm2::{Error, ErrorKind};
+use std::io::Cursor;
+use swf::avm2::read::Reader;
+use swf::avm2::types::{
+    AbcFile, Exception as AbcException, Index, MethodBody as AbcMethodBody,
+    Multiname as AbcMultiname, Op,
+};
+
+/// A method body that has been decoded and verified.
+#[derive(Clone, Debug)]
+pub struct VerifiedMethodBody {
+    /// The decoded instructions of the method body.
+    ///
+    /// The offsets of all branches are counted in instructions rather than
+    /// bytes. As with the bytecode, jumps and conditional branches are
+    /// relative to the instruction following them, and `lookupswitch` is
+    /// relative to itself.
+    pub code: Vec<Op>,
+
+    /// The exception handlers of the method body.
+    ///
+    /// The `from_offset`, `to_offset` and `target_offset` of each handler are
+    /// instruction indices rather than byte offsets.
+    pub exceptions: Vec<AbcException>,
+}
+
+/// The operand and scope stack depths at the start of an instruction.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct FrameState {
+    stack_depth: u32,
+    scope_depth: u32,
+}
+
+/// Decode and verify a method body from the given ABC file.
+pub fn verify_method_body<'gc>(
+    abc: &AbcFile,
+    body: &AbcMethodBody,
+) -> Result<VerifiedMethodBody, Error<'gc>> {
+    let (mut code, offsets) = decode(body)?;
+    let exceptions = resolve_exceptions(body, &offsets, code.len())?;
+
+    for (index, op) in code.iter_mut().enumerate() {
+        resolve_branches(op, index, &offsets, body.code.len())?;
+    }
+
+    verify_stack(abc, body, &code, &exceptions)?;
+
+    Ok(VerifiedMethodBody { code, exceptions })
+}
+
+/// Decode every instruction in a method body.
+///
+/// Also returns the byte offset that each instruction starts at.
+fn decode<'gc>(body: &AbcMethodBody) -> Result<(Vec<Op>, Vec<u32>), Error<'gc>> {
+    let mut reader = Reader::new(Cursor::new(body.code.as_slice()));
+    let mut code = Vec::new();
+    let mut offsets = Vec::new();
+
+    loop {
+        let offset = reader.pos()?;
+        if offset >= body.code.len() as u64 {
+            break;
+        }
+
+        match reader.read_op() {
+            Ok(Some(op)) => {
+                code.push(op);
+                offsets.push(offset as u32);
+            }
+            Ok(None) | Err(_) => {
+                return Err(Error::new(
+                    ErrorKind::VerifyError,
+                    format!(
+                        "Error #1011: Method contained illegal opcode {:#x} at offset {}.",
+                        body.code[offset as usize], offset
+                    ),
+                ))
+            }
+        }
+    }
+
+    Ok((code, offsets))
+}
+
+/// Find the index of the instruction starting at a given byte offset.
+fn instruction_at<'gc>(offsets: &[u32], offset: i64) -> Result<usize, Error<'gc>> {
+    if offset >= 0 && offset <= i64::from(u32::MAX) {
+        if let Ok(index) = offsets.binary_search(&(offset as u32)) {
+            return Ok(index);
+        }
+    }
+
+    Err(Error::new(
+        ErrorKind::VerifyError,
+        "Error #1021: At least one branch target was not on a valid instruction in the method.",
+    ))
+}
+
+/// Rewrite the byte offsets of a branching instruction into instruction
+/// offsets.
+fn resolve_branches<'gc>(
+    op: &mut Op,
+    index: usize,
+    offsets: &[u32],
+    code_length: usize,
+) -> Result<(), Error<'gc>> {
+    let start = i64::from(offsets[index]);
+    let next = offsets
+        .get(index + 1)
+        .map(|offset| i64::from(*offset))
+        .unwrap_or(code_length as i64);
+
+    match op {
+        Op::Jump { offset }
+        | Op::IfTrue { offset }
+        | Op::IfFalse { offset }
+        | Op::IfStrictEq { offset }
+        | Op::IfStrictNe { offset }
+        | Op::IfEq { offset }
+        | Op::IfNe { offset }
+        | Op::IfGe { offset }
+        | Op::IfGt { offset }
+        | Op::IfLe { offset }
+        | Op::IfLt { offset }
+        | Op::IfNge { offset }
+        | Op::IfNgt { offset }
+        | Op::IfNle { offset }
+        | Op::IfNlt { offset } => {
+            let target = instruction_at(offsets, next + i64::from(*offset))?;
+            *offset = target as i32 - (index as i32 + 1);
+        }
+        Op::LookupSwitch {
+            default_offset,
+            case_offsets,
+        } => {
+            for offset in std::iter::once(default_offset).chain(case_offsets.iter_mut()) {
+                let target = instruction_at(offsets, start + i64::from(*offset))?;
+                *offset = target as i32 - index as i32;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Rewrite the byte offsets of a method's exception handlers into instruction
+/// indices.
+///
+/// A handler covers every instruction that starts within its range.
+fn resolve_exceptions<'gc>(
+    body: &AbcMethodBody,
+    offsets: &[u32],
+    code_length: usize,
+) -> Result<Vec<AbcException>, Error<'gc>> {
+    let mut exceptions = Vec::with_capacity(body.exceptions.len());
+
+    for exception in body.exceptions.iter() {
+        let target = offsets.binary_search(&exception.target_offset);
+        if exception.from_offset > exception.to_offset
+            || exception.to_offset as usize > body.code.len()
+            || target.is_err()
+        {
+            return Err(Error::new(
+                ErrorKind::VerifyError,
+                "Error #1054: Illegal range or target offsets in exception handler.",
+            ));
+        }
+
+        let mut resolved = exception.clone();
+        resolved.from_offset = offsets
+            .binary_search(&exception.from_offset)
+            .unwrap_or_else(|index| index) as u32;
+        resolved.to_offset = offsets
+            .binary_search(&exception.to_offset)
+            .unwrap_or_else(|index| index)
+            .min(code_length) as u32;
+        resolved.target_offset = target.unwrap_or_default() as u32;
+        exceptions.push(resolved);
+    }
+
+    Ok(exceptions)
+}
+
+/// The number of runtime name parts that are popped off the stack to resolve
+/// a multiname.
+fn runtime_name_parts<'gc>(abc: &AbcFile, index: &Index<AbcMultiname>) -> Result<u32, Error<'gc>> {
+    if index.0 == 0 {
+        return Ok(0);
+    }
+
+    let multiname = abc
+        .constant_pool
+        .multinames
+        .get(index.0 as usize - 1)
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::VerifyError,
+                format!(
+                    "Error #1032: Cpool index {} is out of range {}.",
+                    index.0,
+                    abc.constant_pool.multinames.len() + 1
+                ),
+            )
+        })?;
+
+    Ok(match multiname {
+        AbcMultiname::RTQName { .. } | AbcMultiname::RTQNameA { .. } => 1,
+        AbcMultiname::MultinameL { .. } | AbcMultiname::MultinameLA { .. } => 1,
+        AbcMultiname::RTQNameL | AbcMultiname::RTQNameLA => 2,
+        _ => 0,
+    })
+}
+
+/// Check that a local register exists in a method body.
+fn check_register<'gc>(body: &AbcMethodBody, register: u32) -> Result<(), Error<'gc>> {
+    if register >= body.num_locals {
+        return Err(Error::new(
+            ErrorKind::VerifyError,
+            format!(
+                "Error #1025: An invalid register {} was accessed.",
+                register
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Compute the state of the stacks after running an instruction.
+///
+/// This also checks that the instruction only accesses registers and scopes
+/// that exist.
+fn step<'gc>(
+    abc: &AbcFile,
+    body: &AbcMethodBody,
+    op: &Op,
+    state: FrameState,
+) -> Result<FrameState, Error<'gc>> {
+    // The number of values popped from and pushed onto each stack.
+    let (pops, pushes, scope_pops, scope_pushes) = match op {
+        Op::PushByte { .. }
+        | Op::PushDouble { .. }
+        | Op::PushFalse
+        | Op::PushInt { .. }
+        | Op::PushNamespace { .. }
+        | Op::PushNaN
+        | Op::PushNull
+        | Op::PushShort { .. }
+        | Op::PushString { .. }
+        | Op::PushTrue
+        | Op::PushUint { .. }
+        | Op::PushUndefined
+        | Op::GetGlobalScope
+        | Op::GetGlobalSlot { .. }
+        | Op::NewActivation
+        | Op::NewCatch { .. }
+        | Op::NewFunction { .. } => (0, 1, 0, 0),
+        Op::Pop
+        | Op::SetGlobalSlot { .. }
+        | Op::IfTrue { .. }
+        | Op::IfFalse { .. }
+        | Op::LookupSwitch { .. }
+        | Op::DxnsLate
+        | Op::ReturnValue
+        | Op::Throw => (1, 0, 0, 0),
+        Op::Dup => (1, 2, 0, 0),
+        Op::Swap => (2, 2, 0, 0),
+        Op::GetLocal { index } => {
+            check_register(body, *index)?;
+            (0, 1, 0, 0)
+        }
+        Op::SetLocal { index } => {
+            check_register(body, *index)?;
+            (1, 0, 0, 0)
+        }
+        Op::Kill { index }
+        | Op::IncLocal { index }
+        | Op::IncLocalI { index }
+        | Op::DecLocal { index }
+        | Op::DecLocalI { index } => {
+            check_register(body, *index)?;
+            (0, 0, 0, 0)
+        }
+        Op::HasNext2 {
+            object_register,
+            index_register,
+        } => {
+            check_register(body, *object_register)?;
+            check_register(body, *index_register)?;
+            (0, 1, 0, 0)
+        }
+        Op::Call { num_args } => (num_args + 2, 1, 0, 0),
+        Op::CallMethod { num_args, .. }
+        | Op::CallStatic { num_args, .. }
+        | Op::Construct { num_args } => (num_args + 1, 1, 0, 0),
+        Op::ConstructSuper { num_args } => (num_args + 1, 0, 0, 0),
+        Op::CallProperty { index, num_args }
+        | Op::CallPropLex { index, num_args }
+        | Op::CallSuper { index, num_args }
+        | Op::ConstructProp { index, num_args } => {
+            (num_args + 1 + runtime_name_parts(abc, index)?, 1, 0, 0)
+        }
+        Op::CallPropVoid { index, num_args } | Op::CallSuperVoid { index, num_args } => {
+            (num_args + 1 + runtime_name_parts(abc, index)?, 0, 0, 0)
+        }
+        Op::GetProperty { index }
+        | Op::GetSuper { index }
+        | Op::DeleteProperty { index }
+        | Op::GetDescendants { index } => (1 + runtime_name_parts(abc, index)?, 1, 0, 0),
+        Op::SetProperty { index } | Op::InitProperty { index } | Op::SetSuper { index } => {
+            (2 + runtime_name_parts(abc, index)?, 0, 0, 0)
+        }
+        Op::FindProperty { index } | Op::FindPropStrict { index } => {
+            (runtime_name_parts(abc, index)?, 1, 0, 0)
+        }
+        Op::GetLex { .. } => (0, 1, 0, 0),
+        Op::GetSlot { .. } => (1, 1, 0, 0),
+        Op::SetSlot { .. } | Op::Si8 | Op::Si16 | Op::Si32 | Op::Sf32 | Op::Sf64 => (2, 0, 0, 0),
+        Op::PushScope | Op::PushWith => (1, 0, 0, 1),
+        Op::PopScope => (0, 0, 1, 0),
+        Op::GetScopeObject { index } => {
+            if u32::from(*index) >= state.scope_depth {
+                return Err(Error::new(
+                    ErrorKind::VerifyError,
+                    format!("Error #1019: Getscopeobject {} is out of bounds.", index),
+                ));
+            }
+
+            (0, 1, 0, 0)
+        }
+        Op::NewObject { num_args } => (num_args * 2, 1, 0, 0),
+        Op::NewArray { num_args } => (*num_args, 1, 0, 0),
+        Op::NewClass { .. } => (1, 1, 0, 0),
+        Op::ApplyType { num_types } => (num_types + 1, 1, 0, 0),
+        Op::Coerce { .. }
+        | Op::CoerceA
+        | Op::CoerceS
+        | Op::ConvertB
+        | Op::ConvertI
+        | Op::ConvertD
+        | Op::ConvertO
+        | Op::ConvertU
+        | Op::ConvertS
+        | Op::AsType { .. }
+        | Op::IsType { .. }
+        | Op::BitNot
+        | Op::Decrement
+        | Op::DecrementI
+        | Op::Increment
+        | Op::IncrementI
+        | Op::Negate
+        | Op::NegateI
+        | Op::Not
+        | Op::TypeOf
+        | Op::CheckFilter
+        | Op::EscXElem
+        | Op::EscXAttr
+        | Op::Li8
+        | Op::Li16
+        | Op::Li32
+        | Op::Lf32
+        | Op::Lf64
+        | Op::Sxi1
+        | Op::Sxi8
+        | Op::Sxi16 => (1, 1, 0, 0),
+        Op::Add
+        | Op::AddI
+        | Op::BitAnd
+        | Op::BitOr
+        | Op::BitXor
+        | Op::Divide
+        | Op::LShift
+        | Op::Modulo
+        | Op::Multiply
+        | Op::MultiplyI
+        | Op::RShift
+        | Op::Subtract
+        | Op::SubtractI
+        | Op::URShift
+        | Op::StrictEquals
+        | Op::Equals
+        | Op::GreaterEquals
+        | Op::GreaterThan
+        | Op::LessEquals
+        | Op::LessThan
+        | Op::In
+        | Op::InstanceOf
+        | Op::IsTypeLate
+        | Op::AsTypeLate
+        | Op::HasNext
+        | Op::NextName
+        | Op::NextValue => (2, 1, 0, 0),
+        Op::IfStrictEq { .. }
+        | Op::IfStrictNe { .. }
+        | Op::IfEq { .. }
+        | Op::IfNe { .. }
+        | Op::IfGe { .. }
+        | Op::IfGt { .. }
+        | Op::IfLe { .. }
+        | Op::IfLt { .. }
+        | Op::IfNge { .. }
+        | Op::IfNgt { .. }
+        | Op::IfNle { .. }
+        | Op::IfNlt { .. } => (2, 0, 0, 0),
+        Op::Jump { .. }
+        | Op::ReturnVoid
+        | Op::Label
+        | Op::Nop
+        | Op::Debug { .. }
+        | Op::DebugFile { .. }
+        | Op::DebugLine { .. }
+        | Op::Dxns { .. } => (0, 0, 0, 0),
+    };
+
+    if pops > state.stack_depth {
+        return Err(Error::new(
+            ErrorKind::VerifyError,
+            "Error #1024: Stack underflow occurred.",
+        ));
+    }
+
+    if scope_pops > state.scope_depth {
+        return Err(Error::new(
+            ErrorKind::VerifyError,
+            "Error #1018: Scope stack underflow occurred.",
+        ));
+    }
+
+    let next = FrameState {
+        stack_depth: state.stack_depth - pops + pushes,
+        scope_depth: state.scope_depth - scope_pops + scope_pushes,
+    };
+
+    if next.stack_depth > body.max_stack {
+        return Err(Error::new(
+            ErrorKind::VerifyError,
+            "Error #1023: Stack overflow occurred.",
+        ));
+    }
+
+    if next.scope_depth > body.max_scope_depth.saturating_sub(body.init_scope_depth) {
+        return Err(Error::new(
+            ErrorKind::VerifyError,
+            "Error #1017: Scope stack overflow occurred.",
+        ));
+    }
+
+    Ok(next)
+}
+
+/// List the instructions that may run after a given instruction.
+fn successors(op: &Op, index: usize) -> Vec<usize> {
+    let relative = |base: usize, offset: &i32| (base as i64 + i64::from(*offset)) as usize;
+
+    match op {
+        Op::ReturnValue | Op::ReturnVoid | Op::Throw => vec![],
+        Op::Jump { offset } => vec![relative(index + 1, offset)],
+        Op::IfTrue { offset }
+        | Op::IfFalse { offset }
+        | Op::IfStrictEq { offset }
+        | Op::IfStrictNe { offset }
+        | Op::IfEq { offset }
+        | Op::IfNe { offset }
+        | Op::IfGe { offset }
+        | Op::IfGt { offset }
+        | Op::IfLe { offset }
+        | Op::IfLt { offset }
+        | Op::IfNge { offset }
+        | Op::IfNgt { offset }
+        | Op::IfNle { offset }
+        | Op::IfNlt { offset } => vec![index + 1, relative(index + 1, offset)],
+        Op::LookupSwitch {
+            default_offset,
+            case_offsets,
+        } => std::iter::once(default_offset)
+            .chain(case_offsets.iter())
+            .map(|offset| relative(index, offset))
+            .collect(),
+        _ => vec![index + 1],
+    }
+}
+
+/// Check that every reachable instruction sees the same stack depths no
+/// matter how it is reached, and that those depths stay within the limits
+/// declared by the method body.
+///
+/// Exception handlers start with only the thrown value on the stack and with
+/// the scope stack of the method's entry.
+fn verify_stack<'gc>(
+    abc: &AbcFile,
+    body: &AbcMethodBody,
+    code: &[Op],
+    exceptions: &[AbcException],
+) -> Result<(), Error<'gc>> {
+    let mut states: Vec<Option<FrameState>> = vec![None; code.len()];
+    let mut worklist = vec![(
+        0,
+        FrameState {
+            stack_depth: 0,
+            scope_depth: 0,
+        },
+    )];
+
+    for exception in exceptions.iter() {
+        worklist.push((
+            exception.target_offset as usize,
+            FrameState {
+                stack_depth: 1,
+                scope_depth: 0,
+            },
+        ));
+    }
+
+    while let Some((index, state)) = worklist.pop() {
+        let op = code.get(index).ok_or_else(|| {
+            Error::new(
+                ErrorKind::VerifyError,
+                "Error #1020: Code cannot fall off the end of a method.",
+            )
+        })?;
+
+        match states[index] {
+            Some(existing) if existing == state => continue,
+            Some(existing) if existing.stack_depth != state.stack_depth => {
+                return Err(Error::new(
+                    ErrorKind::VerifyError,
+                    format!(
+                        "Error #1030: Stack depth is unbalanced. {} != {}.",
+                        existing.stack_depth, state.stack_depth
+                    ),
+                ));
+            }
+            Some(existing) => {
+                return Err(Error::new(
+                    ErrorKind::VerifyError,
+                    format!(
+                        "Error #1031: Scope depth is unbalanced. {} != {}.",
+                        existing.scope_depth, state.scope_depth
+                    ),
+                ));
+            }
+            None => states[index] = Some(state),
+        }
+
+        let next = step(abc, body, op, state)?;
+        for successor in successors(op, index) {
+            worklist.push((successor, next));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::avm2::types::ConstantPool;
+
+    fn empty_abc() -> AbcFile {
+        AbcFile {
+            major_version: 46,
+            minor_version: 16,
+            constant_pool: ConstantPool {
+                ints: vec![],
+                uints: vec![],
+                doubles: vec![],
+                strings: vec![],
+                namespaces: vec![],
+                namespace_sets: vec![],
+                multinames: vec![],
+            },
+            methods: vec![],
+            metadata: vec![],
+            instances: vec![],
+            classes: vec![],
+            scripts: vec![],
+            method_bodies: vec![],
+        }
+    }
+
+    fn method_body(code: &[u8]) -> AbcMethodBody {
+        AbcMethodBody {
+            method: Index::new(0),
+            max_stack: 1,
+            num_locals: 1,
+            init_scope_depth: 0,
+            max_scope_depth: 0,
+            code: code.to_vec(),
+            exceptions: vec![],
+            traits: vec![],
+        }
+    }
+
+    fn verify_error(code: &[u8]) -> String {
+        verify_method_body(&empty_abc(), &method_body(code))
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn branches_resolve_to_instructions() {
+        // pushtrue; iftrue +1; nop; returnvoid
+        let body = verify_method_body(
+            &empty_abc(),
+            &method_body(&[0x26, 0x11, 0x01, 0x00, 0x00, 0x02, 0x47]),
+        )
+        .unwrap();
+
+        assert_eq!(body.code.len(), 4);
+        assert_eq!(body.code[1], Op::IfTrue { offset: 1 });
+    }
+
+    #[test]
+    fn malformed_bodies_are_rejected() {
+        // pop; returnvoid
+        assert!(verify_error(&[0x29, 0x47]).contains("#1024"));
+        // jump -3, into the middle of itself
+        assert!(verify_error(&[0x10, 0xFD, 0xFF, 0xFF]).contains("#1021"));
+        // nop
+        assert!(verify_error(&[0x02]).contains("#1020"));
+        // pushtrue; iftrue +1; pushtrue; returnvoid
+        assert!(verify_error(&[0x26, 0x11, 0x01, 0x00, 0x00, 0x26, 0x47]).contains("#1030"));
+        // getlocal 1; returnvalue
+        assert!(verify_error(&[0x62, 0x01, 0x48]).contains("#1025"));
+    }
+}
@@ -10,6 +10,8 @@ use gc_arena::{Collect, CollectionContext};
 use generational_arena::{Arena, Index};
 
 pub mod decoders;
+#[cfg(feature = "audio")]
+pub mod mixer;
 pub mod swf {
     pub use swf::{
         read, AudioCompression, CharacterId, Sound, SoundEnvelope, SoundEnvelopePoint, SoundEvent,
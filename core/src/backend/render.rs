@@ -1,9 +1,11 @@
+pub use crate::bitmap::filters::Filter;
 use crate::shape_utils::DistilledShape;
 pub use crate::{library::MovieLibrary, transform::Transform, Color};
 use downcast_rs::Downcast;
 use gc_arena::Collect;
 use std::io::Read;
 pub use swf;
+pub use swf::BlendMode;
 use swf::Matrix;
 
 pub trait RenderBackend: Downcast {
@@ -46,6 +48,21 @@ pub trait RenderBackend: Downcast {
     fn deactivate_mask(&mut self);
     fn pop_mask(&mut self);
 
+    /// Starts drawing into a new, transparent layer.
+    /// All drawing goes to this layer until the matching call to `pop_layer`.
+    fn push_layer(&mut self);
+
+    /// Applies `filters` to the contents of the current layer, and composites the result
+    /// onto the layer below it using `blend_mode`.
+    fn pop_layer(&mut self, filters: &[Filter], blend_mode: BlendMode);
+
+    /// Ends the current layer like `pop_layer`, but returns its premultiplied RGBA contents
+    /// instead of compositing them onto the layer below it.
+    /// The layer is the size of the viewport.
+    ///
+    /// Backends without layers draw straight into the layer below and return `None`.
+    fn pop_layer_to_bitmap(&mut self) -> Option<Bitmap>;
+
     fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap>;
     fn register_bitmap_raw(
         &mut self,
@@ -161,6 +178,11 @@ impl RenderBackend for NullRenderer {
     fn activate_mask(&mut self) {}
     fn deactivate_mask(&mut self) {}
     fn pop_mask(&mut self) {}
+    fn push_layer(&mut self) {}
+    fn pop_layer(&mut self, _filters: &[Filter], _blend_mode: BlendMode) {}
+    fn pop_layer_to_bitmap(&mut self) -> Option<Bitmap> {
+        None
+    }
 
     fn get_bitmap_pixels(&mut self, _bitmap: BitmapHandle) -> Option<Bitmap> {
         None
@@ -578,6 +600,16 @@ pub fn unmultiply_alpha_rgba(rgba: &mut [u8]) {
     })
 }
 
+/// Converts standard RGBA to RGBA premultiplied alpha.
+pub fn premultiply_alpha_rgba(rgba: &mut [u8]) {
+    rgba.chunks_exact_mut(4).for_each(|rgba| {
+        let a = f32::from(rgba[3]) / 255.0;
+        rgba[0] = (f32::from(rgba[0]) * a) as u8;
+        rgba[1] = (f32::from(rgba[1]) * a) as u8;
+        rgba[2] = (f32::from(rgba[2]) * a) as u8;
+    })
+}
+
 /// Converts an RGBA color from sRGB space to linear color space.
 pub fn srgb_to_linear(color: [f32; 4]) -> [f32; 4] {
     fn to_linear_channel(n: f32) -> f32 {
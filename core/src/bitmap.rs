@@ -1 +1,3 @@
+pub mod blend;
+pub mod filters;
 pub mod turbulence;
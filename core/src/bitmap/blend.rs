@@ -0,0 +1,122 @@
+//! Compositing of premultiplied colors using the blend modes of display objects.
+//!
+//! Backends that can't express a blend mode with their fixed-function blending
+//! composite the layer of the display object on the CPU with these functions instead.
+
+use swf::BlendMode;
+
+/// Blends a premultiplied `src` color onto a premultiplied `dst` color.
+/// All components are in the range 0.0-1.0.
+pub fn blend(mode: BlendMode, src: [f32; 4], dst: [f32; 4]) -> [f32; 4] {
+    let (sa, da) = (src[3], dst[3]);
+    let over_alpha = sa + da - sa * da;
+    match mode {
+        BlendMode::Normal | BlendMode::Layer => [
+            src[0] + dst[0] * (1.0 - sa),
+            src[1] + dst[1] * (1.0 - sa),
+            src[2] + dst[2] * (1.0 - sa),
+            over_alpha,
+        ],
+        BlendMode::Multiply => separable(src, dst, |s, d| s * d),
+        BlendMode::Screen => separable(src, dst, |s, d| s + d - s * d),
+        BlendMode::Lighten => separable(src, dst, f32::max),
+        BlendMode::Darken => separable(src, dst, f32::min),
+        BlendMode::Difference => separable(src, dst, |s, d| (s - d).abs()),
+        BlendMode::Overlay => separable(src, dst, |s, d| hard_light(d, s)),
+        BlendMode::HardLight => separable(src, dst, hard_light),
+        BlendMode::Add => [
+            (src[0] + dst[0]).min(1.0),
+            (src[1] + dst[1]).min(1.0),
+            (src[2] + dst[2]).min(1.0),
+            over_alpha,
+        ],
+        BlendMode::Subtract => [
+            (dst[0] - src[0]).max(0.0),
+            (dst[1] - src[1]).max(0.0),
+            (dst[2] - src[2]).max(0.0),
+            over_alpha,
+        ],
+        BlendMode::Invert => [
+            (da - dst[0]) * sa + dst[0] * (1.0 - sa),
+            (da - dst[1]) * sa + dst[1] * (1.0 - sa),
+            (da - dst[2]) * sa + dst[2] * (1.0 - sa),
+            da,
+        ],
+        BlendMode::Alpha => [dst[0] * sa, dst[1] * sa, dst[2] * sa, da * sa],
+        BlendMode::Erase => [
+            dst[0] * (1.0 - sa),
+            dst[1] * (1.0 - sa),
+            dst[2] * (1.0 - sa),
+            da * (1.0 - sa),
+        ],
+    }
+}
+
+/// Blends premultiplied RGBA `src` pixels onto premultiplied RGBA `dst` pixels, in place.
+pub fn blend_rgba(mode: BlendMode, src: &[u8], dst: &mut [u8]) {
+    for (src, dst) in src.chunks_exact(4).zip(dst.chunks_exact_mut(4)) {
+        if src[3] == 0 && mode != BlendMode::Alpha {
+            continue;
+        }
+        let out = blend(mode, to_f32(src), to_f32(dst));
+        for (dst, out) in dst.iter_mut().zip(out.iter()) {
+            *dst = (out * 255.0).round().max(0.0).min(255.0) as u8;
+        }
+    }
+}
+
+/// Composites using a separable blend function `f(source, backdrop)` of unmultiplied colors,
+/// as described by the W3C compositing spec.
+fn separable(src: [f32; 4], dst: [f32; 4], f: impl Fn(f32, f32) -> f32) -> [f32; 4] {
+    let (sa, da) = (src[3], dst[3]);
+    let channel = |s: f32, d: f32| {
+        let b = f(
+            if sa > 0.0 { s / sa } else { 0.0 },
+            if da > 0.0 { d / da } else { 0.0 },
+        );
+        (1.0 - da) * s + (1.0 - sa) * d + sa * da * b.max(0.0).min(1.0)
+    };
+    [
+        channel(src[0], dst[0]),
+        channel(src[1], dst[1]),
+        channel(src[2], dst[2]),
+        sa + da - sa * da,
+    ]
+}
+
+fn hard_light(s: f32, d: f32) -> f32 {
+    if s <= 0.5 {
+        d * 2.0 * s
+    } else {
+        let s = 2.0 * s - 1.0;
+        d + s - d * s
+    }
+}
+
+fn to_f32(pixel: &[u8]) -> [f32; 4] {
+    [
+        f32::from(pixel[0]) / 255.0,
+        f32::from(pixel[1]) / 255.0,
+        f32::from(pixel[2]) / 255.0,
+        f32::from(pixel[3]) / 255.0,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiply_darkens_backdrop() {
+        let mut dst = [255, 128, 0, 255];
+        blend_rgba(BlendMode::Multiply, &[128, 128, 128, 255], &mut dst);
+        assert_eq!(dst, [128, 64, 0, 255]);
+    }
+
+    #[test]
+    fn erase_clears_covered_pixels() {
+        let mut dst = [255, 255, 255, 255, 255, 255, 255, 255];
+        blend_rgba(BlendMode::Erase, &[0, 0, 0, 255, 0, 0, 0, 0], &mut dst);
+        assert_eq!(dst, [0, 0, 0, 0, 255, 255, 255, 255]);
+    }
+}
@@ -0,0 +1,966 @@
+//! Bitmap filters, as applied to display objects through `PlaceObject3` or the `filters`
+//! property.
+//!
+//! Filters are applied to the premultiplied RGBA pixels of an object that has been rendered
+//! into its own layer. The kernels here run on the CPU, for render backends without shaders.
+//! Backends that apply filters on the GPU should match their results.
+
+use std::sync::Arc;
+use swf::{Color, GradientRecord};
+
+/// A single bitmap filter.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Filter {
+    Blur(BlurFilter),
+    DropShadow(DropShadowFilter),
+    Glow(GlowFilter),
+    Bevel(BevelFilter),
+    GradientGlow(GradientFilter),
+    GradientBevel(GradientFilter),
+    ColorMatrix(ColorMatrixFilter),
+    Convolution(ConvolutionFilter),
+    DisplacementMap(DisplacementMapFilter),
+}
+
+/// Where the effect of a glow or bevel filter is visible, relative to the object.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FilterType {
+    Inner,
+    Outer,
+    Full,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlurFilter {
+    pub blur_x: f32,
+    pub blur_y: f32,
+    pub quality: u8,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DropShadowFilter {
+    pub color: Color,
+    pub blur_x: f32,
+    pub blur_y: f32,
+    /// The direction of the shadow, in degrees.
+    pub angle: f32,
+    pub distance: f32,
+    pub strength: f32,
+    pub quality: u8,
+    pub inner: bool,
+    pub knockout: bool,
+    pub hide_object: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GlowFilter {
+    pub color: Color,
+    pub blur_x: f32,
+    pub blur_y: f32,
+    pub strength: f32,
+    pub quality: u8,
+    pub inner: bool,
+    pub knockout: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BevelFilter {
+    pub highlight_color: Color,
+    pub shadow_color: Color,
+    pub blur_x: f32,
+    pub blur_y: f32,
+    /// The direction of the light source, in degrees.
+    pub angle: f32,
+    pub distance: f32,
+    pub strength: f32,
+    pub quality: u8,
+    pub filter_type: FilterType,
+    pub knockout: bool,
+}
+
+/// The parameters shared by `GradientGlowFilter` and `GradientBevelFilter`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GradientFilter {
+    pub colors: Vec<GradientRecord>,
+    pub blur_x: f32,
+    pub blur_y: f32,
+    /// In degrees.
+    pub angle: f32,
+    pub distance: f32,
+    pub strength: f32,
+    pub quality: u8,
+    pub filter_type: FilterType,
+    pub knockout: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorMatrixFilter {
+    /// A 4x5 matrix applied to unmultiplied RGBA values in the range 0-255.
+    pub matrix: [f32; 20],
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConvolutionFilter {
+    pub matrix_x: u8,
+    pub matrix_y: u8,
+    pub matrix: Vec<f32>,
+    pub divisor: f32,
+    pub bias: f32,
+    pub preserve_alpha: bool,
+    pub clamp: bool,
+    /// The color used for pixels outside of the image when `clamp` is false.
+    pub color: Color,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisplacementMapMode {
+    Wrap,
+    Clamp,
+    Ignore,
+    Color,
+}
+
+/// The pixels of the map used by a `DisplacementMapFilter`, as unmultiplied RGBA.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FilterBitmap {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DisplacementMapFilter {
+    pub map_bitmap: Option<Arc<FilterBitmap>>,
+    pub map_point: (i32, i32),
+    /// The `BitmapDataChannel` used for horizontal displacement.
+    pub component_x: u8,
+    /// The `BitmapDataChannel` used for vertical displacement.
+    pub component_y: u8,
+    pub scale_x: f32,
+    pub scale_y: f32,
+    pub mode: DisplacementMapMode,
+    pub color: Color,
+}
+
+impl From<&swf::Filter> for Filter {
+    fn from(filter: &swf::Filter) -> Self {
+        fn filter_type(is_inner: bool, is_on_top: bool) -> FilterType {
+            if is_on_top {
+                FilterType::Full
+            } else if is_inner {
+                FilterType::Inner
+            } else {
+                FilterType::Outer
+            }
+        }
+
+        match filter {
+            swf::Filter::BlurFilter(filter) => Filter::Blur(BlurFilter {
+                blur_x: filter.blur_x as f32,
+                blur_y: filter.blur_y as f32,
+                quality: filter.num_passes,
+            }),
+            swf::Filter::DropShadowFilter(filter) => Filter::DropShadow(DropShadowFilter {
+                color: filter.color.clone(),
+                blur_x: filter.blur_x as f32,
+                blur_y: filter.blur_y as f32,
+                angle: filter.angle.to_degrees() as f32,
+                distance: filter.distance as f32,
+                strength: filter.strength,
+                quality: filter.num_passes,
+                inner: filter.is_inner,
+                knockout: filter.is_knockout,
+                hide_object: false,
+            }),
+            swf::Filter::GlowFilter(filter) => Filter::Glow(GlowFilter {
+                color: filter.color.clone(),
+                blur_x: filter.blur_x as f32,
+                blur_y: filter.blur_y as f32,
+                strength: filter.strength,
+                quality: filter.num_passes,
+                inner: filter.is_inner,
+                knockout: filter.is_knockout,
+            }),
+            swf::Filter::BevelFilter(filter) => Filter::Bevel(BevelFilter {
+                highlight_color: filter.highlight_color.clone(),
+                shadow_color: filter.shadow_color.clone(),
+                blur_x: filter.blur_x as f32,
+                blur_y: filter.blur_y as f32,
+                angle: filter.angle.to_degrees() as f32,
+                distance: filter.distance as f32,
+                strength: filter.strength,
+                quality: filter.num_passes,
+                filter_type: filter_type(filter.is_inner, filter.is_on_top),
+                knockout: filter.is_knockout,
+            }),
+            swf::Filter::GradientGlowFilter(filter) => Filter::GradientGlow(GradientFilter {
+                colors: filter.colors.clone(),
+                blur_x: filter.blur_x as f32,
+                blur_y: filter.blur_y as f32,
+                angle: filter.angle.to_degrees() as f32,
+                distance: filter.distance as f32,
+                strength: filter.strength,
+                quality: filter.num_passes,
+                filter_type: filter_type(filter.is_inner, filter.is_on_top),
+                knockout: filter.is_knockout,
+            }),
+            swf::Filter::GradientBevelFilter(filter) => Filter::GradientBevel(GradientFilter {
+                colors: filter.colors.clone(),
+                blur_x: filter.blur_x as f32,
+                blur_y: filter.blur_y as f32,
+                angle: filter.angle.to_degrees() as f32,
+                distance: filter.distance as f32,
+                strength: filter.strength,
+                quality: filter.num_passes,
+                filter_type: filter_type(filter.is_inner, filter.is_on_top),
+                knockout: filter.is_knockout,
+            }),
+            swf::Filter::ColorMatrixFilter(filter) => {
+                let mut matrix = [0.0; 20];
+                for (dst, src) in matrix.iter_mut().zip(filter.matrix.iter()) {
+                    *dst = *src as f32;
+                }
+                Filter::ColorMatrix(ColorMatrixFilter { matrix })
+            }
+            swf::Filter::ConvolutionFilter(filter) => Filter::Convolution(ConvolutionFilter {
+                matrix_x: filter.num_matrix_cols,
+                matrix_y: filter.num_matrix_rows,
+                matrix: filter.matrix.iter().map(|n| *n as f32).collect(),
+                divisor: filter.divisor as f32,
+                bias: filter.bias as f32,
+                preserve_alpha: filter.is_preserve_alpha,
+                clamp: filter.is_clamped,
+                color: filter.default_color.clone(),
+            }),
+        }
+    }
+}
+
+impl Filter {
+    /// The amount of pixels that this filter may extend the image by, on each side.
+    pub fn padding(&self) -> (i32, i32) {
+        match self {
+            Filter::Blur(filter) => blur_padding(filter.blur_x, filter.blur_y, filter.quality),
+            Filter::DropShadow(filter) => {
+                let (dx, dy) = offset(filter.angle, filter.distance);
+                let (x, y) = blur_padding(filter.blur_x, filter.blur_y, filter.quality);
+                (x + dx.abs(), y + dy.abs())
+            }
+            Filter::Glow(filter) => blur_padding(filter.blur_x, filter.blur_y, filter.quality),
+            Filter::Bevel(filter) => {
+                let (dx, dy) = offset(filter.angle, filter.distance);
+                let (x, y) = blur_padding(filter.blur_x, filter.blur_y, filter.quality);
+                (x + dx.abs(), y + dy.abs())
+            }
+            Filter::GradientGlow(filter) | Filter::GradientBevel(filter) => {
+                let (dx, dy) = offset(filter.angle, filter.distance);
+                let (x, y) = blur_padding(filter.blur_x, filter.blur_y, filter.quality);
+                (x + dx.abs(), y + dy.abs())
+            }
+            Filter::ColorMatrix(_) | Filter::Convolution(_) => (0, 0),
+            Filter::DisplacementMap(filter) => (
+                (filter.scale_x.abs() / 2.0).ceil() as i32,
+                (filter.scale_y.abs() / 2.0).ceil() as i32,
+            ),
+        }
+    }
+
+    fn apply(&self, image: &Image, origin: (i32, i32)) -> Image {
+        match self {
+            Filter::Blur(filter) => {
+                let mut channels = image.channels();
+                for channel in &mut channels {
+                    blur(
+                        channel,
+                        image.width,
+                        image.height,
+                        filter.blur_x,
+                        filter.blur_y,
+                        filter.quality,
+                    );
+                }
+                Image::from_channels(image.width, image.height, &channels)
+            }
+            Filter::DropShadow(filter) => {
+                let (dx, dy) = offset(filter.angle, filter.distance);
+                let shadow = shadow_plane(
+                    image,
+                    filter.blur_x,
+                    filter.blur_y,
+                    filter.quality,
+                    (dx, dy),
+                    filter.strength,
+                    filter.inner,
+                );
+                let color = premultiplied(&filter.color);
+                let layer = image.map(|i, _| scale(color, shadow[i]));
+                if filter.hide_object && !filter.inner {
+                    return layer;
+                }
+                let filter_type = if filter.inner {
+                    FilterType::Inner
+                } else {
+                    FilterType::Outer
+                };
+                composite(
+                    image,
+                    &layer,
+                    filter_type,
+                    filter.knockout || filter.hide_object,
+                )
+            }
+            Filter::Glow(filter) => {
+                let shadow = shadow_plane(
+                    image,
+                    filter.blur_x,
+                    filter.blur_y,
+                    filter.quality,
+                    (0, 0),
+                    filter.strength,
+                    filter.inner,
+                );
+                let color = premultiplied(&filter.color);
+                let layer = image.map(|i, _| scale(color, shadow[i]));
+                let filter_type = if filter.inner {
+                    FilterType::Inner
+                } else {
+                    FilterType::Outer
+                };
+                composite(image, &layer, filter_type, filter.knockout)
+            }
+            Filter::Bevel(filter) => {
+                let bevel = bevel_plane(
+                    image,
+                    filter.blur_x,
+                    filter.blur_y,
+                    filter.quality,
+                    offset(filter.angle, filter.distance),
+                    filter.strength,
+                );
+                let highlight = premultiplied(&filter.highlight_color);
+                let shadow = premultiplied(&filter.shadow_color);
+                let layer = image.map(|i, _| {
+                    let amount = bevel[i];
+                    if amount >= 0.0 {
+                        scale(shadow, amount)
+                    } else {
+                        scale(highlight, -amount)
+                    }
+                });
+                composite(image, &layer, filter.filter_type, filter.knockout)
+            }
+            Filter::GradientGlow(filter) => {
+                let inner = filter.filter_type == FilterType::Inner;
+                let glow = shadow_plane(
+                    image,
+                    filter.blur_x,
+                    filter.blur_y,
+                    filter.quality,
+                    offset(filter.angle, filter.distance),
+                    filter.strength,
+                    inner,
+                );
+                let lut = gradient_lut(&filter.colors);
+                let layer = image.map(|i, _| lut[(glow[i] * 255.0).round() as usize]);
+                composite(image, &layer, filter.filter_type, filter.knockout)
+            }
+            Filter::GradientBevel(filter) => {
+                let bevel = bevel_plane(
+                    image,
+                    filter.blur_x,
+                    filter.blur_y,
+                    filter.quality,
+                    offset(filter.angle, filter.distance),
+                    filter.strength,
+                );
+                let lut = gradient_lut(&filter.colors);
+                let layer = image.map(|i, _| {
+                    let ratio = 0.5 + 0.5 * bevel[i];
+                    lut[(ratio * 255.0).round() as usize]
+                });
+                composite(image, &layer, filter.filter_type, filter.knockout)
+            }
+            Filter::ColorMatrix(filter) => image.map(|_, pixel| {
+                let [r, g, b, a] = unmultiplied(pixel);
+                let m = &filter.matrix;
+                let channel = |row: usize| {
+                    let row = &m[row * 5..row * 5 + 5];
+                    (row[0] * r + row[1] * g + row[2] * b + row[3] * a + row[4])
+                        .max(0.0)
+                        .min(255.0)
+                };
+                premultiply([channel(0), channel(1), channel(2), channel(3)])
+            }),
+            Filter::Convolution(filter) => convolve(image, filter),
+            Filter::DisplacementMap(filter) => displace(image, filter, origin),
+        }
+    }
+}
+
+/// Applies a chain of filters to a premultiplied RGBA image, in place.
+///
+/// Only the area around the visible content of the image is processed, so that
+/// layers much larger than the filtered object stay cheap.
+pub fn apply_filters(width: u32, height: u32, data: &mut [u8], filters: &[Filter]) {
+    if filters.is_empty() {
+        return;
+    }
+
+    let (width, height) = (width as i32, height as i32);
+    let (left, top, right, bottom) = match content_bounds(width, height, data) {
+        Some(bounds) => bounds,
+        None => return,
+    };
+
+    let (pad_x, pad_y) = filters.iter().fold((0, 0), |(x, y), filter| {
+        let (pad_x, pad_y) = filter.padding();
+        (x + pad_x, y + pad_y)
+    });
+    let x0 = (left - pad_x).max(0);
+    let y0 = (top - pad_y).max(0);
+    let x1 = (right + pad_x).min(width);
+    let y1 = (bottom + pad_y).min(height);
+
+    let mut image = Image::read(data, width as usize, x0, y0, x1, y1);
+    let origin = (left - x0, top - y0);
+    for filter in filters {
+        image = filter.apply(&image, origin);
+    }
+    image.write(data, width as usize, x0, y0);
+}
+
+/// Returns the bounds `(left, top, right, bottom)` of all non-transparent pixels.
+fn content_bounds(width: i32, height: i32, data: &[u8]) -> Option<(i32, i32, i32, i32)> {
+    let mut bounds: Option<(i32, i32, i32, i32)> = None;
+    for y in 0..height {
+        let row = &data[(y * width * 4) as usize..((y + 1) * width * 4) as usize];
+        let mut pixels = row.chunks_exact(4).map(|pixel| pixel[3] != 0);
+        if let Some(first) = pixels.position(|opaque| opaque) {
+            let last = row
+                .chunks_exact(4)
+                .rposition(|pixel| pixel[3] != 0)
+                .unwrap_or(first);
+            let (first, last) = (first as i32, last as i32 + 1);
+            bounds = Some(match bounds {
+                Some((left, top, right, _)) => (left.min(first), top, right.max(last), y + 1),
+                None => (first, y, last, y + 1),
+            });
+        }
+    }
+    bounds
+}
+
+/// A premultiplied RGBA image with components in the range 0.0-1.0.
+struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<[f32; 4]>,
+}
+
+impl Image {
+    fn read(data: &[u8], stride: usize, x0: i32, y0: i32, x1: i32, y1: i32) -> Self {
+        let width = (x1 - x0) as usize;
+        let height = (y1 - y0) as usize;
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in y0 as usize..y1 as usize {
+            let start = (y * stride + x0 as usize) * 4;
+            for pixel in data[start..start + width * 4].chunks_exact(4) {
+                pixels.push([
+                    f32::from(pixel[0]) / 255.0,
+                    f32::from(pixel[1]) / 255.0,
+                    f32::from(pixel[2]) / 255.0,
+                    f32::from(pixel[3]) / 255.0,
+                ]);
+            }
+        }
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn write(&self, data: &mut [u8], stride: usize, x0: i32, y0: i32) {
+        for (y, row) in self.pixels.chunks_exact(self.width.max(1)).enumerate() {
+            let start = ((y + y0 as usize) * stride + x0 as usize) * 4;
+            for (dst, src) in data[start..start + self.width * 4]
+                .chunks_exact_mut(4)
+                .zip(row)
+            {
+                let a = src[3].max(0.0).min(1.0);
+                for c in 0..3 {
+                    dst[c] = (src[c].max(0.0).min(a) * 255.0).round() as u8;
+                }
+                dst[3] = (a * 255.0).round() as u8;
+            }
+        }
+    }
+
+    fn get(&self, x: i32, y: i32) -> Option<[f32; 4]> {
+        if x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height {
+            Some(self.pixels[y as usize * self.width + x as usize])
+        } else {
+            None
+        }
+    }
+
+    fn map(&self, mut f: impl FnMut(usize, [f32; 4]) -> [f32; 4]) -> Self {
+        Self {
+            width: self.width,
+            height: self.height,
+            pixels: self
+                .pixels
+                .iter()
+                .enumerate()
+                .map(|(i, pixel)| f(i, *pixel))
+                .collect(),
+        }
+    }
+
+    fn alpha(&self) -> Vec<f32> {
+        self.pixels.iter().map(|pixel| pixel[3]).collect()
+    }
+
+    fn channels(&self) -> [Vec<f32>; 4] {
+        let channel = |c: usize| self.pixels.iter().map(|pixel| pixel[c]).collect();
+        [channel(0), channel(1), channel(2), channel(3)]
+    }
+
+    fn from_channels(width: usize, height: usize, channels: &[Vec<f32>; 4]) -> Self {
+        Self {
+            width,
+            height,
+            pixels: (0..width * height)
+                .map(|i| {
+                    [
+                        channels[0][i],
+                        channels[1][i],
+                        channels[2][i],
+                        channels[3][i],
+                    ]
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Converts an angle and distance into a whole pixel offset.
+pub fn offset(angle: f32, distance: f32) -> (i32, i32) {
+    let angle = angle.to_radians();
+    (
+        (angle.cos() * distance).round() as i32,
+        (angle.sin() * distance).round() as i32,
+    )
+}
+
+/// The radius of each box blur pass for a `blur_x` or `blur_y` amount.
+pub fn blur_radius(blur: f32) -> usize {
+    (blur.max(0.0) / 2.0).floor() as usize
+}
+
+fn blur_padding(blur_x: f32, blur_y: f32, quality: u8) -> (i32, i32) {
+    (
+        (blur_radius(blur_x) * usize::from(quality)) as i32,
+        (blur_radius(blur_y) * usize::from(quality)) as i32,
+    )
+}
+
+/// Runs `quality` passes of a box blur over a single channel.
+/// Pixels outside of the image are treated as zero.
+fn blur(plane: &mut [f32], width: usize, height: usize, blur_x: f32, blur_y: f32, quality: u8) {
+    let radius_x = blur_radius(blur_x);
+    let radius_y = blur_radius(blur_y);
+    let mut line = Vec::new();
+    let mut prefix = Vec::new();
+    for _ in 0..quality {
+        if radius_x > 0 {
+            for row in plane.chunks_exact_mut(width) {
+                line.clear();
+                line.extend_from_slice(row);
+                box_blur_line(&line, radius_x, &mut prefix, |i, value| row[i] = value);
+            }
+        }
+        if radius_y > 0 {
+            for x in 0..width {
+                line.clear();
+                line.extend((0..height).map(|y| plane[y * width + x]));
+                box_blur_line(&line, radius_y, &mut prefix, |y, value| {
+                    plane[y * width + x] = value
+                });
+            }
+        }
+    }
+}
+
+fn box_blur_line(
+    line: &[f32],
+    radius: usize,
+    prefix: &mut Vec<f32>,
+    mut output: impl FnMut(usize, f32),
+) {
+    prefix.clear();
+    prefix.push(0.0);
+    let mut sum = 0.0;
+    for value in line {
+        sum += value;
+        prefix.push(sum);
+    }
+
+    let window = (radius * 2 + 1) as f32;
+    for i in 0..line.len() {
+        let start = i.saturating_sub(radius);
+        let end = (i + radius + 1).min(line.len());
+        output(i, (prefix[end] - prefix[start]) / window);
+    }
+}
+
+/// Computes the coverage of a shadow or glow: the blurred alpha of the image, moved by
+/// `offset` and scaled by `strength`.
+///
+/// Inner shadows are cast by the inverted alpha of the image.
+fn shadow_plane(
+    image: &Image,
+    blur_x: f32,
+    blur_y: f32,
+    quality: u8,
+    (dx, dy): (i32, i32),
+    strength: f32,
+    inner: bool,
+) -> Vec<f32> {
+    let mut alpha = image.alpha();
+    blur(
+        &mut alpha,
+        image.width,
+        image.height,
+        blur_x,
+        blur_y,
+        quality,
+    );
+
+    let mut plane = Vec::with_capacity(alpha.len());
+    for y in 0..image.height as i32 {
+        for x in 0..image.width as i32 {
+            let (sx, sy) = (x - dx, y - dy);
+            let shifted = if sx >= 0
+                && sy >= 0
+                && (sx as usize) < image.width
+                && (sy as usize) < image.height
+            {
+                alpha[sy as usize * image.width + sx as usize]
+            } else {
+                0.0
+            };
+            let value = if inner { 1.0 - shifted } else { shifted };
+            plane.push((value * strength).max(0.0).min(1.0));
+        }
+    }
+    plane
+}
+
+/// Computes the amount of bevel at each pixel, from -1.0 (highlight) to 1.0 (shadow).
+fn bevel_plane(
+    image: &Image,
+    blur_x: f32,
+    blur_y: f32,
+    quality: u8,
+    (dx, dy): (i32, i32),
+    strength: f32,
+) -> Vec<f32> {
+    let mut alpha = image.alpha();
+    blur(
+        &mut alpha,
+        image.width,
+        image.height,
+        blur_x,
+        blur_y,
+        quality,
+    );
+
+    let sample = |x: i32, y: i32| {
+        if x >= 0 && y >= 0 && (x as usize) < image.width && (y as usize) < image.height {
+            alpha[y as usize * image.width + x as usize]
+        } else {
+            0.0
+        }
+    };
+
+    let mut plane = Vec::with_capacity(alpha.len());
+    for y in 0..image.height as i32 {
+        for x in 0..image.width as i32 {
+            let value = sample(x - dx, y - dy) - sample(x + dx, y + dy);
+            plane.push((value * strength).max(-1.0).min(1.0));
+        }
+    }
+    plane
+}
+
+/// Combines the output of a shadow, glow or bevel with the source image.
+fn composite(source: &Image, layer: &Image, filter_type: FilterType, knockout: bool) -> Image {
+    source.map(|i, src| {
+        let layer = layer.pixels[i];
+        let layer = match filter_type {
+            FilterType::Inner => scale(layer, src[3]),
+            FilterType::Outer => scale(layer, 1.0 - src[3]),
+            FilterType::Full => layer,
+        };
+        if knockout {
+            layer
+        } else if filter_type == FilterType::Outer {
+            over(src, layer)
+        } else {
+            over(layer, src)
+        }
+    })
+}
+
+/// Builds a lookup table of premultiplied colors for the ratios 0-255 of a gradient.
+fn gradient_lut(records: &[GradientRecord]) -> Vec<[f32; 4]> {
+    (0..=255u8)
+        .map(|ratio| {
+            let next = records.iter().position(|record| record.ratio >= ratio);
+            let color = match next {
+                None => records.last().map(|record| premultiplied(&record.color)),
+                Some(0) => records.first().map(|record| premultiplied(&record.color)),
+                Some(i) => {
+                    let (a, b) = (&records[i - 1], &records[i]);
+                    let t = f32::from(ratio.saturating_sub(a.ratio))
+                        / f32::from(b.ratio.saturating_sub(a.ratio).max(1));
+                    let (a, b) = (premultiplied(&a.color), premultiplied(&b.color));
+                    Some([
+                        a[0] + (b[0] - a[0]) * t,
+                        a[1] + (b[1] - a[1]) * t,
+                        a[2] + (b[2] - a[2]) * t,
+                        a[3] + (b[3] - a[3]) * t,
+                    ])
+                }
+            };
+            color.unwrap_or([0.0; 4])
+        })
+        .collect()
+}
+
+fn convolve(image: &Image, filter: &ConvolutionFilter) -> Image {
+    let cols = i32::from(filter.matrix_x);
+    let rows = i32::from(filter.matrix_y);
+    if cols == 0 || rows == 0 || filter.matrix.len() < (cols * rows) as usize {
+        return image.map(|_, pixel| pixel);
+    }
+    let divisor = if filter.divisor == 0.0 {
+        1.0
+    } else {
+        filter.divisor
+    };
+    let default_color = unmultiplied(premultiplied(&filter.color));
+
+    image.map(|i, pixel| {
+        let x = (i % image.width) as i32;
+        let y = (i / image.width) as i32;
+        let mut sum = [0.0; 4];
+        for row in 0..rows {
+            for col in 0..cols {
+                let (sx, sy) = (x + col - cols / 2, y + row - rows / 2);
+                let sample = if filter.clamp {
+                    image
+                        .get(
+                            sx.max(0).min(image.width as i32 - 1),
+                            sy.max(0).min(image.height as i32 - 1),
+                        )
+                        .map(unmultiplied)
+                } else {
+                    image.get(sx, sy).map(unmultiplied)
+                }
+                .unwrap_or(default_color);
+                let weight = filter.matrix[(row * cols + col) as usize];
+                for c in 0..4 {
+                    sum[c] += sample[c] * weight;
+                }
+            }
+        }
+
+        let mut result = [0.0; 4];
+        for c in 0..4 {
+            result[c] = (sum[c] / divisor + filter.bias).max(0.0).min(255.0);
+        }
+        if filter.preserve_alpha {
+            result[3] = pixel[3] * 255.0;
+        }
+        premultiply(result)
+    })
+}
+
+fn displace(image: &Image, filter: &DisplacementMapFilter, origin: (i32, i32)) -> Image {
+    let map = match &filter.map_bitmap {
+        Some(map) => map,
+        None => return image.map(|_, pixel| pixel),
+    };
+
+    let channel = |component: u8, pixel: &[u8]| match component {
+        1 => f32::from(pixel[0]),
+        2 => f32::from(pixel[1]),
+        4 => f32::from(pixel[2]),
+        8 => f32::from(pixel[3]),
+        _ => 128.0,
+    };
+    let color = premultiplied(&filter.color);
+    let (width, height) = (image.width as i32, image.height as i32);
+
+    image.map(|i, pixel| {
+        let x = (i % image.width) as i32;
+        let y = (i / image.width) as i32;
+        let mx = x - origin.0 - filter.map_point.0;
+        let my = y - origin.1 - filter.map_point.1;
+        if mx < 0 || my < 0 || mx >= map.width as i32 || my >= map.height as i32 {
+            return pixel;
+        }
+
+        let start = ((my as u32 * map.width + mx as u32) * 4) as usize;
+        let map_pixel = &map.data[start..start + 4];
+        let dx = (channel(filter.component_x, map_pixel) - 128.0) * filter.scale_x / 256.0;
+        let dy = (channel(filter.component_y, map_pixel) - 128.0) * filter.scale_y / 256.0;
+        let (sx, sy) = (x + dx.round() as i32, y + dy.round() as i32);
+
+        if let Some(sample) = image.get(sx, sy) {
+            return sample;
+        }
+        match filter.mode {
+            DisplacementMapMode::Wrap => image
+                .get(sx.rem_euclid(width), sy.rem_euclid(height))
+                .unwrap_or(pixel),
+            DisplacementMapMode::Clamp => image
+                .get(sx.max(0).min(width - 1), sy.max(0).min(height - 1))
+                .unwrap_or(pixel),
+            DisplacementMapMode::Ignore => pixel,
+            DisplacementMapMode::Color => color,
+        }
+    })
+}
+
+fn premultiplied(color: &Color) -> [f32; 4] {
+    let a = f32::from(color.a) / 255.0;
+    [
+        f32::from(color.r) / 255.0 * a,
+        f32::from(color.g) / 255.0 * a,
+        f32::from(color.b) / 255.0 * a,
+        a,
+    ]
+}
+
+/// Converts a premultiplied pixel to unmultiplied components in the range 0-255.
+fn unmultiplied(pixel: [f32; 4]) -> [f32; 4] {
+    let a = pixel[3];
+    if a <= 0.0 {
+        return [0.0; 4];
+    }
+    [
+        pixel[0] / a * 255.0,
+        pixel[1] / a * 255.0,
+        pixel[2] / a * 255.0,
+        a * 255.0,
+    ]
+}
+
+/// Converts unmultiplied components in the range 0-255 to a premultiplied pixel.
+fn premultiply(color: [f32; 4]) -> [f32; 4] {
+    let a = color[3] / 255.0;
+    [
+        color[0] / 255.0 * a,
+        color[1] / 255.0 * a,
+        color[2] / 255.0 * a,
+        a,
+    ]
+}
+
+fn scale(pixel: [f32; 4], amount: f32) -> [f32; 4] {
+    [
+        pixel[0] * amount,
+        pixel[1] * amount,
+        pixel[2] * amount,
+        pixel[3] * amount,
+    ]
+}
+
+/// Premultiplied source-over compositing.
+fn over(top: [f32; 4], bottom: [f32; 4]) -> [f32; 4] {
+    let inverse = 1.0 - top[3];
+    [
+        top[0] + bottom[0] * inverse,
+        top[1] + bottom[1] * inverse,
+        top[2] + bottom[2] * inverse,
+        top[3] + bottom[3] * inverse,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(size: u32, inset: u32) -> Vec<u8> {
+        let mut data = vec![0; (size * size * 4) as usize];
+        for y in inset..size - inset {
+            for x in inset..size - inset {
+                let i = ((y * size + x) * 4) as usize;
+                data[i..i + 4].copy_from_slice(&[255, 0, 0, 255]);
+            }
+        }
+        data
+    }
+
+    fn pixel(data: &[u8], size: u32, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * size + x) * 4) as usize;
+        [data[i], data[i + 1], data[i + 2], data[i + 3]]
+    }
+
+    #[test]
+    fn blur_spreads_alpha() {
+        let mut data = square(16, 6);
+        let filter = Filter::Blur(BlurFilter {
+            blur_x: 4.0,
+            blur_y: 4.0,
+            quality: 1,
+        });
+        apply_filters(16, 16, &mut data, &[filter]);
+        assert!(pixel(&data, 16, 5, 8)[3] > 0);
+        assert!(pixel(&data, 16, 8, 8)[3] < 255);
+        assert_eq!(pixel(&data, 16, 0, 0)[3], 0);
+    }
+
+    #[test]
+    fn drop_shadow_is_drawn_behind_object() {
+        let mut data = square(16, 4);
+        let filter = Filter::DropShadow(DropShadowFilter {
+            color: Color::from_rgb(0x000000, 255),
+            blur_x: 0.0,
+            blur_y: 0.0,
+            angle: 45.0,
+            distance: 2.0,
+            strength: 1.0,
+            quality: 1,
+            inner: false,
+            knockout: false,
+            hide_object: false,
+        });
+        apply_filters(16, 16, &mut data, &[filter]);
+        assert_eq!(pixel(&data, 16, 8, 8), [255, 0, 0, 255]);
+        assert_eq!(pixel(&data, 16, 12, 12), [0, 0, 0, 255]);
+        assert_eq!(pixel(&data, 16, 2, 2), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn color_matrix_swaps_channels() {
+        let mut data = square(4, 0);
+        #[rustfmt::skip]
+        let matrix = [
+            0.0, 0.0, 1.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0, 0.0,
+            1.0, 0.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 1.0, 0.0,
+        ];
+        apply_filters(
+            4,
+            4,
+            &mut data,
+            &[Filter::ColorMatrix(ColorMatrixFilter { matrix })],
+        );
+        assert_eq!(pixel(&data, 4, 1, 1), [0, 0, 255, 255]);
+    }
+}
@@ -1,7 +1,7 @@
 //! Contexts and helper types passed between functions.
 
 use crate::avm1::globals::system::SystemProperties;
-use crate::avm1::{Avm1, Object as Avm1Object, Timers, Value as Avm1Value};
+use crate::avm1::{Avm1, Object as Avm1Object, Value as Avm1Value};
 use crate::avm2::{Avm2, Object as Avm2Object, Value as Avm2Value};
 use crate::backend::{
     audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
@@ -20,6 +20,7 @@ use crate::loader::LoadManager;
 use crate::player::Player;
 use crate::prelude::*;
 use crate::tag_utils::{SwfMovie, SwfSlice};
+use crate::timer::Timers;
 use crate::transform::TransformStack;
 use core::fmt;
 use gc_arena::{Collect, CollectionContext, MutationContext};
@@ -94,6 +95,9 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The display object that the mouse is currently hovering over.
     pub mouse_hovered_object: Option<DisplayObject<'gc>>,
 
+    /// The display object that the mouse button was last pressed over.
+    pub mouse_pressed_object: Option<DisplayObject<'gc>>,
+
     /// The location of the mouse when it was last over the player.
     pub mouse_position: &'a (Twips, Twips),
 
@@ -249,6 +253,7 @@ unsafe impl<'a, 'gc, 'gc_context> Collect for UpdateContext<'a, 'gc, 'gc_context
         self.rng.trace(cc);
         self.levels.trace(cc);
         self.mouse_hovered_object.trace(cc);
+        self.mouse_pressed_object.trace(cc);
         self.mouse_position.trace(cc);
         self.drag_object.trace(cc);
         self.load_manager.trace(cc);
@@ -294,6 +299,7 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             rng: self.rng,
             levels: self.levels,
             mouse_hovered_object: self.mouse_hovered_object,
+            mouse_pressed_object: self.mouse_pressed_object,
             mouse_position: self.mouse_position,
             drag_object: self.drag_object,
             stage_size: self.stage_size,
@@ -423,6 +429,9 @@ pub struct RenderContext<'a, 'gc> {
     /// Whether to allow pushing a new mask. A masker-inside-a-masker does not work in Flash, instead
     /// causing the inner mask to be included as part of the outer mask. Maskee-inside-a-maskee works as one expects.
     pub allow_mask: bool,
+
+    /// The GC context, used to update the cached bitmaps of display objects.
+    pub gc_context: MutationContext<'gc, 'a>,
 }
 
 /// The type of action being run.
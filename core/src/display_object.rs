@@ -1,12 +1,15 @@
 use crate::avm1::{
     Error as Avm1Error, Object as Avm1Object, TObject as Avm1TObject, Value as Avm1Value,
 };
-use crate::avm2::{TObject as Avm2TObject, Value as Avm2Value};
+use crate::avm2::{Object as Avm2Object, TObject as Avm2TObject, Value as Avm2Value};
+use crate::backend::render::{BitmapFormat, BitmapHandle, RenderBackend};
+use crate::bitmap::filters::Filter;
 use crate::context::{RenderContext, UpdateContext};
+use crate::drawing::Drawing;
 use crate::player::NEWEST_PLAYER_VERSION;
 use crate::prelude::*;
 use crate::tag_utils::SwfMovie;
-use crate::transform::Transform;
+use crate::transform::{Transform, TransformStack};
 use crate::types::{Degrees, Percent};
 use crate::vminterface::Instantiator;
 use bitflags::bitflags;
@@ -16,6 +19,7 @@ use std::cell::{Ref, RefMut};
 use std::cmp::min;
 use std::fmt::Debug;
 use std::sync::Arc;
+use swf::BlendMode;
 
 mod bitmap;
 mod button;
@@ -70,6 +74,29 @@ pub struct DisplayObjectBase<'gc> {
     /// The sound transform of sounds playing via this display object.
     sound_transform: SoundTransform,
 
+    /// The bitmap filters applied to this display object when it is rendered.
+    filters: Vec<Filter>,
+
+    /// How this display object is composited onto the objects below it.
+    blend_mode: BlendMode,
+
+    /// The color this display object is filled with behind its contents, if any.
+    opaque_background: Option<Color>,
+
+    /// The rectangle in local space that this display object is clipped to and scrolled by, if any.
+    scroll_rect: Option<BoundingBox>,
+
+    /// The rectangle in local space that divides this display object into the nine regions
+    /// of 9-slice scaling, if any.
+    scaling_grid: Option<BoundingBox>,
+
+    /// The contents of this display object, rendered into a bitmap when `cacheAsBitmap` is set.
+    cached_bitmap: Option<CachedBitmap>,
+
+    /// The changes to how this display object looks since they were last taken by a cached
+    /// bitmap.
+    render_changes: RenderChanges,
+
     /// Bit flags for various display object properites.
     flags: DisplayObjectFlags,
 }
@@ -90,7 +117,16 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             prev_sibling: None,
             next_sibling: None,
             sound_transform: Default::default(),
-            flags: DisplayObjectFlags::VISIBLE,
+            filters: Vec::new(),
+            blend_mode: BlendMode::Normal,
+            opaque_background: None,
+            scroll_rect: None,
+            scaling_grid: None,
+            cached_bitmap: None,
+            render_changes: RenderChanges::all(),
+            flags: DisplayObjectFlags::VISIBLE
+                | DisplayObjectFlags::MOUSE_ENABLED
+                | DisplayObjectFlags::MOUSE_CHILDREN,
         }
     }
 }
@@ -109,7 +145,10 @@ impl<'gc> DisplayObjectBase<'gc> {
     /// Reset all properties that would be adjusted by a movie load.
     fn reset_for_movie_load(&mut self) {
         let flags_to_keep = self.flags & DisplayObjectFlags::LOCK_ROOT;
-        self.flags = flags_to_keep | DisplayObjectFlags::VISIBLE;
+        self.flags = flags_to_keep
+            | DisplayObjectFlags::VISIBLE
+            | DisplayObjectFlags::MOUSE_ENABLED
+            | DisplayObjectFlags::MOUSE_CHILDREN;
     }
 
     fn id(&self) -> CharacterId {
@@ -141,10 +180,12 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
 
     fn matrix_mut(&mut self, _context: MutationContext<'gc, '_>) -> &mut Matrix {
+        self.render_changes |= RenderChanges::PLACEMENT;
         &mut self.transform.matrix
     }
 
     fn set_matrix(&mut self, _context: MutationContext<'gc, '_>, matrix: &Matrix) {
+        self.render_changes |= RenderChanges::PLACEMENT;
         self.transform.matrix = *matrix;
         self.flags -= DisplayObjectFlags::SCALE_ROTATION_CACHED;
     }
@@ -154,6 +195,7 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
 
     fn color_transform_mut(&mut self) -> &mut ColorTransform {
+        self.render_changes |= RenderChanges::PLACEMENT;
         &mut self.transform.color_transform
     }
 
@@ -162,6 +204,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         _context: MutationContext<'gc, '_>,
         color_transform: &ColorTransform,
     ) {
+        self.render_changes |= RenderChanges::PLACEMENT;
         self.transform.color_transform = *color_transform;
     }
 
@@ -170,6 +213,7 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
 
     fn set_x(&mut self, value: f64) {
+        self.render_changes |= RenderChanges::PLACEMENT;
         self.set_transformed_by_script(true);
         self.transform.matrix.tx = Twips::from_pixels(value)
     }
@@ -179,6 +223,7 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
 
     fn set_y(&mut self, value: f64) {
+        self.render_changes |= RenderChanges::PLACEMENT;
         self.set_transformed_by_script(true);
         self.transform.matrix.ty = Twips::from_pixels(value)
     }
@@ -244,6 +289,7 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
 
     fn set_rotation(&mut self, degrees: Degrees) {
+        self.render_changes |= RenderChanges::PLACEMENT;
         self.set_transformed_by_script(true);
         self.cache_scale_rotation();
         self.rotation = degrees;
@@ -264,6 +310,7 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
 
     fn set_scale_x(&mut self, value: Percent) {
+        self.render_changes |= RenderChanges::PLACEMENT;
         self.set_transformed_by_script(true);
         self.cache_scale_rotation();
         self.scale_x = value;
@@ -280,6 +327,7 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
 
     fn set_scale_y(&mut self, value: Percent) {
+        self.render_changes |= RenderChanges::PLACEMENT;
         self.set_transformed_by_script(true);
         self.cache_scale_rotation();
         self.scale_y = value;
@@ -312,6 +360,7 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
 
     fn set_clip_depth(&mut self, _context: MutationContext<'gc, '_>, depth: Depth) {
+        self.render_changes |= RenderChanges::PLACEMENT;
         self.clip_depth = depth;
     }
 
@@ -371,11 +420,90 @@ impl<'gc> DisplayObjectBase<'gc> {
         self.sound_transform = sound_transform;
     }
 
+    fn filters(&self) -> Vec<Filter> {
+        self.filters.clone()
+    }
+
+    fn set_filters(&mut self, filters: Vec<Filter>) {
+        self.render_changes |= RenderChanges::PLACEMENT;
+        self.filters = filters;
+    }
+
+    fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.render_changes |= RenderChanges::PLACEMENT;
+        self.blend_mode = blend_mode;
+    }
+
+    fn opaque_background(&self) -> Option<Color> {
+        self.opaque_background.clone()
+    }
+
+    fn set_opaque_background(&mut self, color: Option<Color>) {
+        self.render_changes |= RenderChanges::CONTENTS;
+        self.opaque_background = color;
+    }
+
+    fn scroll_rect(&self) -> Option<BoundingBox> {
+        self.scroll_rect.clone()
+    }
+
+    fn set_scroll_rect(&mut self, rectangle: Option<BoundingBox>) {
+        self.render_changes |= RenderChanges::PLACEMENT;
+        self.scroll_rect = rectangle;
+    }
+
+    fn scaling_grid(&self) -> Option<BoundingBox> {
+        self.scaling_grid.clone()
+    }
+
+    fn set_scaling_grid(&mut self, rectangle: Option<BoundingBox>) {
+        self.render_changes |= RenderChanges::CONTENTS;
+        self.scaling_grid = rectangle;
+    }
+
+    fn cache_as_bitmap(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::CACHE_AS_BITMAP)
+    }
+
+    fn set_cache_as_bitmap(&mut self, value: bool) {
+        self.render_changes |= RenderChanges::PLACEMENT;
+        if value {
+            self.flags |= DisplayObjectFlags::CACHE_AS_BITMAP;
+        } else {
+            self.flags -= DisplayObjectFlags::CACHE_AS_BITMAP;
+        }
+    }
+
+    fn cached_bitmap(&self) -> Option<CachedBitmap> {
+        self.cached_bitmap.clone()
+    }
+
+    fn set_cached_bitmap(&mut self, cached_bitmap: Option<CachedBitmap>) {
+        self.cached_bitmap = cached_bitmap;
+    }
+
+    fn invalidate_cached_bitmap(&mut self) {
+        self.render_changes |= RenderChanges::CONTENTS;
+    }
+
+    fn render_changes(&self, flags: RenderChanges) -> bool {
+        self.render_changes.intersects(flags)
+    }
+
+    fn clear_render_changes(&mut self, flags: RenderChanges) {
+        self.render_changes -= flags;
+    }
+
     fn visible(&self) -> bool {
         self.flags.contains(DisplayObjectFlags::VISIBLE)
     }
 
     fn set_visible(&mut self, value: bool) {
+        self.render_changes |= RenderChanges::PLACEMENT;
         if value {
             self.flags |= DisplayObjectFlags::VISIBLE;
         } else {
@@ -395,6 +523,43 @@ impl<'gc> DisplayObjectBase<'gc> {
         }
     }
 
+    fn mouse_enabled(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::MOUSE_ENABLED)
+    }
+
+    fn set_mouse_enabled(&mut self, value: bool) {
+        if value {
+            self.flags |= DisplayObjectFlags::MOUSE_ENABLED;
+        } else {
+            self.flags -= DisplayObjectFlags::MOUSE_ENABLED;
+        }
+    }
+
+    fn mouse_children(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::MOUSE_CHILDREN)
+    }
+
+    fn set_mouse_children(&mut self, value: bool) {
+        if value {
+            self.flags |= DisplayObjectFlags::MOUSE_CHILDREN;
+        } else {
+            self.flags -= DisplayObjectFlags::MOUSE_CHILDREN;
+        }
+    }
+
+    fn double_click_enabled(&self) -> bool {
+        self.flags
+            .contains(DisplayObjectFlags::DOUBLE_CLICK_ENABLED)
+    }
+
+    fn set_double_click_enabled(&mut self, value: bool) {
+        if value {
+            self.flags |= DisplayObjectFlags::DOUBLE_CLICK_ENABLED;
+        } else {
+            self.flags -= DisplayObjectFlags::DOUBLE_CLICK_ENABLED;
+        }
+    }
+
     fn transformed_by_script(&self) -> bool {
         self.flags
             .contains(DisplayObjectFlags::TRANSFORMED_BY_SCRIPT)
@@ -444,6 +609,330 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
 }
 
+/// Renders a display object, clipped and scrolled by its `scrollRect`, and first drawing it
+/// into its own layer if it has filters or a blend mode that need to be applied to the result.
+pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_, 'gc>) {
+    let scroll_rect = match this.scroll_rect() {
+        Some(scroll_rect) if context.allow_mask => scroll_rect,
+        _ => return render_layer(this, context),
+    };
+
+    // The scroll rect is clipped out at the origin of the object, and the contents are
+    // shifted by its position. The shift happens in the local space of the object, so it
+    // is pushed beneath the object's own matrix.
+    let matrix = context.transform_stack.transform().matrix * *this.matrix();
+    let mut inverse = *this.matrix();
+    inverse.invert();
+    let scroll = Matrix::translate(
+        Twips::new(-scroll_rect.x_min.get()),
+        Twips::new(-scroll_rect.y_min.get()),
+    );
+    let clip = matrix
+        * Matrix::create_box(
+            scroll_rect.width().to_pixels() as f32,
+            scroll_rect.height().to_pixels() as f32,
+            0.0,
+            Twips::zero(),
+            Twips::zero(),
+        );
+
+    context.renderer.push_mask();
+    context.renderer.draw_rect(Color::from_rgb(0, 0xff), &clip);
+    context.renderer.activate_mask();
+    context.transform_stack.push(&Transform {
+        matrix: *this.matrix() * scroll * inverse,
+        ..Default::default()
+    });
+    render_layer(this, context);
+    context.transform_stack.pop();
+    context.renderer.deactivate_mask();
+    context.renderer.draw_rect(Color::from_rgb(0, 0xff), &clip);
+    context.renderer.pop_mask();
+}
+
+/// Renders a display object into its own layer if it has filters or a blend mode.
+fn render_layer<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_, 'gc>) {
+    let filters = this.filters();
+    let blend_mode = this.blend_mode();
+    if filters.is_empty() && blend_mode == BlendMode::Normal {
+        render_cached(this, context);
+    } else {
+        context.renderer.push_layer();
+        render_cached(this, context);
+        context.renderer.pop_layer(&filters, blend_mode);
+    }
+}
+
+/// Renders a display object, through its cached bitmap if it has `cacheAsBitmap` set.
+///
+/// The bitmap is rendered in stage space, so it is redrawn whenever the object changes
+/// or is scaled or rotated, but not when it only moves.
+fn render_cached<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_, 'gc>) {
+    if !this.cache_as_bitmap() || !context.allow_mask {
+        render_contents(this, context);
+        return;
+    }
+
+    let base_transform = context.transform_stack.transform().clone();
+    let world_matrix = base_transform.matrix * *this.matrix();
+    let bounds = this.bounds_with_transform(&world_matrix);
+    if !bounds.valid {
+        return;
+    }
+    let x = Twips::from_pixels(bounds.x_min.to_pixels().floor());
+    let y = Twips::from_pixels(bounds.y_min.to_pixels().floor());
+    let width = (bounds.x_max - x).to_pixels().ceil() as u32;
+    let height = (bounds.y_max - y).to_pixels().ceil() as u32;
+    if width == 0 || height == 0 {
+        return;
+    }
+    // Like Flash Player, objects that are too large for a bitmap are rendered without caching.
+    if width > MAX_CACHED_BITMAP_SIZE
+        || height > MAX_CACHED_BITMAP_SIZE
+        || width * height > MAX_CACHED_BITMAP_PIXELS
+    {
+        render_contents(this, context);
+        return;
+    }
+
+    let linear_matrix = Matrix {
+        tx: Twips::zero(),
+        ty: Twips::zero(),
+        ..world_matrix
+    };
+    let color_transform = *this.color_transform();
+    let mut cached_bitmap = this.cached_bitmap();
+    // The changes are taken even if the bitmap is rendered again anyway, so that they're reset.
+    let has_changed = take_contents_changes(this, context.gc_context);
+    let is_up_to_date = !has_changed
+        && cached_bitmap
+            .as_ref()
+            .map(|cached| {
+                cached.matrix == linear_matrix
+                    && cached.color_transform == color_transform
+                    && cached.width >= width
+                    && cached.height >= height
+            })
+            .unwrap_or(false);
+
+    if !is_up_to_date {
+        cached_bitmap =
+            render_to_pixels(this, context, base_transform.matrix, (x, y), width, height)
+                .and_then(|pixels| {
+                    CachedBitmap::update(context.renderer, cached_bitmap, &pixels, width, height)
+                })
+                .map(|cached| CachedBitmap {
+                    matrix: linear_matrix,
+                    color_transform,
+                    offset: (x - world_matrix.tx, y - world_matrix.ty),
+                    ..cached
+                });
+        this.set_cached_bitmap(context.gc_context, cached_bitmap.clone());
+    }
+
+    if let Some(cached) = cached_bitmap {
+        context.renderer.render_bitmap(
+            cached.handle,
+            &Transform {
+                matrix: Matrix::translate(
+                    world_matrix.tx + cached.offset.0,
+                    world_matrix.ty + cached.offset.1,
+                ),
+                color_transform: base_transform.color_transform,
+            },
+            false,
+        );
+    }
+}
+
+/// Renders the contents of a display object into `width` by `height` RGBA pixels,
+/// with the point `origin` of the stage at their top left corner.
+///
+/// Layers are only as large as the viewport, so larger contents are rendered in several tiles.
+fn render_to_pixels<'gc>(
+    this: DisplayObject<'gc>,
+    context: &mut RenderContext<'_, 'gc>,
+    base_matrix: Matrix,
+    origin: (Twips, Twips),
+    width: u32,
+    height: u32,
+) -> Option<Vec<u8>> {
+    let mut pixels = vec![0; width as usize * height as usize * 4];
+    let mut tile_y = 0;
+    while tile_y < height {
+        let mut tile_x = 0;
+        let mut tile_height = 0;
+        while tile_x < width {
+            // Render the contents with the corner of this tile at the origin of a new layer.
+            context.renderer.push_layer();
+            let mut transform_stack = TransformStack::new();
+            transform_stack.push(&Transform {
+                matrix: Matrix::translate(
+                    Twips::new(-origin.0.get()) - Twips::from_pixels(tile_x.into()),
+                    Twips::new(-origin.1.get()) - Twips::from_pixels(tile_y.into()),
+                ) * base_matrix,
+                ..Default::default()
+            });
+            let mut cache_context = RenderContext {
+                renderer: &mut *context.renderer,
+                library: context.library,
+                transform_stack: &mut transform_stack,
+                view_bounds: BoundingBox {
+                    x_min: Twips::new(i32::MIN),
+                    y_min: Twips::new(i32::MIN),
+                    x_max: Twips::new(i32::MAX),
+                    y_max: Twips::new(i32::MAX),
+                    valid: true,
+                },
+                clip_depth_stack: vec![],
+                allow_mask: true,
+                gc_context: context.gc_context,
+            };
+            render_contents(this, &mut cache_context);
+
+            let layer = context.renderer.pop_layer_to_bitmap()?;
+            let data = match &layer.data {
+                BitmapFormat::Rgba(data) => data,
+                BitmapFormat::Rgb(_) => return None,
+            };
+            if layer.width == 0 || layer.height == 0 {
+                return None;
+            }
+
+            let copy_width = (width - tile_x).min(layer.width) as usize * 4;
+            let copy_height = (height - tile_y).min(layer.height) as usize;
+            let rows = pixels
+                .chunks_exact_mut(width as usize * 4)
+                .skip(tile_y as usize)
+                .take(copy_height);
+            for (row, layer_row) in rows.zip(data.chunks_exact(layer.width as usize * 4)) {
+                let start = tile_x as usize * 4;
+                row[start..start + copy_width].copy_from_slice(&layer_row[..copy_width]);
+            }
+
+            tile_x += layer.width;
+            tile_height = layer.height;
+        }
+        tile_y += tile_height;
+    }
+    Some(pixels)
+}
+
+/// Returns whether the contents of a display object or anything about its descendants changed
+/// since this was last called, and resets those changes.
+fn take_contents_changes<'gc>(
+    this: DisplayObject<'gc>,
+    gc_context: MutationContext<'gc, '_>,
+) -> bool {
+    let mut has_changed = this.take_render_changes(gc_context, RenderChanges::CONTENTS);
+    if let Some(container) = this.as_container() {
+        for child in container.iter_render_list() {
+            has_changed |= child.take_render_changes(gc_context, RenderChanges::PLACEMENT);
+            has_changed |= take_contents_changes(child, gc_context);
+        }
+    }
+    has_changed
+}
+
+/// Renders the opaque background and the contents of a display object.
+fn render_contents<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_, 'gc>) {
+    if let Some(color) = this.opaque_background() {
+        let bounds = this.bounds();
+        if bounds.valid {
+            let matrix = context.transform_stack.transform().matrix
+                * *this.matrix()
+                * Matrix::create_box(
+                    bounds.width().to_pixels() as f32,
+                    bounds.height().to_pixels() as f32,
+                    0.0,
+                    bounds.x_min,
+                    bounds.y_min,
+                );
+            context.renderer.draw_rect(color, &matrix);
+        }
+    }
+    this.render(context);
+}
+
+/// The largest width or height of a cached bitmap, in pixels.
+const MAX_CACHED_BITMAP_SIZE: u32 = 8191;
+
+/// The largest amount of pixels in a cached bitmap.
+const MAX_CACHED_BITMAP_PIXELS: u32 = 16_777_215;
+
+/// The contents of a display object with `cacheAsBitmap` set, rendered into a bitmap.
+#[derive(Clone, Debug)]
+pub struct CachedBitmap {
+    handle: BitmapHandle,
+
+    /// The size of the bitmap, which may be larger than its contents.
+    width: u32,
+    height: u32,
+
+    /// The world matrix the contents were rendered with, without its translation.
+    matrix: Matrix,
+
+    /// The color transform of the object itself, which the contents were rendered with.
+    color_transform: ColorTransform,
+
+    /// The position of the bitmap relative to the translation of the world matrix.
+    offset: (Twips, Twips),
+}
+
+impl CachedBitmap {
+    /// Copies `width` by `height` RGBA pixels into the bitmap of a cache,
+    /// reusing the bitmap of `previous` if it is large enough.
+    fn update(
+        renderer: &mut dyn RenderBackend,
+        previous: Option<CachedBitmap>,
+        pixels: &[u8],
+        width: u32,
+        height: u32,
+    ) -> Option<Self> {
+        // Bitmaps can't be freed, so they grow in powers of two to limit how many are created
+        // for an object that keeps changing size.
+        let (handle, bitmap_width, bitmap_height) = match previous {
+            Some(previous) if previous.width >= width && previous.height >= height => {
+                (Some(previous.handle), previous.width, previous.height)
+            }
+            Some(previous) => (
+                None,
+                previous.width.max(width.next_power_of_two()),
+                previous.height.max(height.next_power_of_two()),
+            ),
+            None => (None, width.next_power_of_two(), height.next_power_of_two()),
+        };
+
+        let mut rgba = vec![0; bitmap_width as usize * bitmap_height as usize * 4];
+        let row_size = width as usize * 4;
+        for (row, pixels_row) in rgba
+            .chunks_exact_mut(bitmap_width as usize * 4)
+            .zip(pixels.chunks_exact(row_size))
+        {
+            row[..row_size].copy_from_slice(pixels_row);
+        }
+
+        let handle = match handle {
+            Some(handle) => renderer.update_texture(handle, bitmap_width, bitmap_height, rgba),
+            None => renderer.register_bitmap_raw(bitmap_width, bitmap_height, rgba),
+        };
+        match handle {
+            Ok(handle) => Some(Self {
+                handle,
+                width: bitmap_width,
+                height: bitmap_height,
+                matrix: Matrix::identity(),
+                color_transform: ColorTransform::default(),
+                offset: (Twips::zero(), Twips::zero()),
+            }),
+            Err(e) => {
+                log::error!("Unable to cache display object as bitmap: {}", e);
+                None
+            }
+        }
+    }
+}
+
 #[enum_trait_object(
     #[derive(Clone, Collect, Debug, Copy)]
     #[collect(no_drop)]
@@ -786,6 +1275,76 @@ pub trait TDisplayObject<'gc>:
         sound_transform: SoundTransform,
     );
 
+    /// The bitmap filters applied to this display object.
+    /// Returned by the `filters` ActionScript property.
+    fn filters(&self) -> Vec<Filter>;
+
+    /// Sets the bitmap filters applied to this display object.
+    /// Set by the `filters` ActionScript property.
+    fn set_filters(&self, context: MutationContext<'gc, '_>, filters: Vec<Filter>);
+
+    /// How this display object is composited onto the objects below it.
+    /// Returned by the `blendMode` ActionScript property.
+    fn blend_mode(&self) -> BlendMode;
+
+    /// Sets how this display object is composited onto the objects below it.
+    /// Set by the `blendMode` ActionScript property.
+    fn set_blend_mode(&self, context: MutationContext<'gc, '_>, blend_mode: BlendMode);
+
+    /// The color this display object is filled with behind its contents, if any.
+    /// Returned by the `opaqueBackground` ActionScript property.
+    fn opaque_background(&self) -> Option<Color>;
+
+    /// Sets the color this display object is filled with behind its contents.
+    /// Set by the `opaqueBackground` ActionScript property.
+    fn set_opaque_background(&self, context: MutationContext<'gc, '_>, color: Option<Color>);
+
+    /// The rectangle in local space that this display object is clipped to and scrolled by.
+    /// Returned by the `scrollRect` ActionScript property.
+    fn scroll_rect(&self) -> Option<BoundingBox>;
+
+    /// Sets the rectangle in local space that this display object is clipped to and scrolled by.
+    /// Set by the `scrollRect` ActionScript property.
+    fn set_scroll_rect(&self, context: MutationContext<'gc, '_>, rectangle: Option<BoundingBox>);
+
+    /// The rectangle in local space that divides this display object into the nine regions
+    /// of 9-slice scaling. The corners keep their size when the object is scaled, and the edges
+    /// are only scaled along their length.
+    /// Returned by the `scale9Grid` ActionScript property.
+    fn scaling_grid(&self) -> Option<BoundingBox>;
+
+    /// Sets the rectangle in local space that divides this display object for 9-slice scaling.
+    /// Set by the `scale9Grid` ActionScript property, or by the `DefineScalingGrid` tag.
+    fn set_scaling_grid(&self, context: MutationContext<'gc, '_>, rectangle: Option<BoundingBox>);
+
+    /// Whether this display object is rendered through a cached bitmap.
+    /// Returned by the `cacheAsBitmap` ActionScript property.
+    fn cache_as_bitmap(&self) -> bool;
+
+    /// Sets whether this display object is rendered through a cached bitmap.
+    /// Set by the `cacheAsBitmap` ActionScript property.
+    fn set_cache_as_bitmap(&self, context: MutationContext<'gc, '_>, value: bool);
+
+    /// The bitmap this display object was last rendered into, if `cacheAsBitmap` is set.
+    fn cached_bitmap(&self) -> Option<CachedBitmap>;
+
+    /// Sets the bitmap this display object was last rendered into.
+    fn set_cached_bitmap(
+        &self,
+        context: MutationContext<'gc, '_>,
+        cached_bitmap: Option<CachedBitmap>,
+    );
+
+    /// Records that the contents of this display object changed, so that the cached bitmaps
+    /// of it and its ancestors are rendered again.
+    /// This must be called whenever the contents of this object change outside of the
+    /// properties shared by all display objects, e.g. its drawing or its children.
+    fn invalidate_cached_bitmap(&self, context: MutationContext<'gc, '_>);
+
+    /// Returns whether any of the given changes happened since they were last taken,
+    /// and resets them.
+    fn take_render_changes(&self, context: MutationContext<'gc, '_>, flags: RenderChanges) -> bool;
+
     /// Whether this display object is used as the _root of itself and its children.
     /// Returned by the `_lockroot` ActionScript property.
     fn lock_root(&self) -> bool;
@@ -794,6 +1353,30 @@ pub trait TDisplayObject<'gc>:
     /// Returned by the `_lockroot` ActionScript property.
     fn set_lock_root(&self, context: MutationContext<'gc, '_>, value: bool);
 
+    /// Whether this display object can be the target of mouse events.
+    /// Returned by the AS3 `mouseEnabled` property.
+    fn mouse_enabled(&self) -> bool;
+
+    /// Sets whether this display object can be the target of mouse events.
+    /// Set by the AS3 `mouseEnabled` property.
+    fn set_mouse_enabled(&self, context: MutationContext<'gc, '_>, value: bool);
+
+    /// Whether the children of this display object can be the targets of
+    /// mouse events. Returned by the AS3 `mouseChildren` property.
+    fn mouse_children(&self) -> bool;
+
+    /// Sets whether the children of this display object can be the targets
+    /// of mouse events. Set by the AS3 `mouseChildren` property.
+    fn set_mouse_children(&self, context: MutationContext<'gc, '_>, value: bool);
+
+    /// Whether this display object receives `doubleClick` events.
+    /// Returned by the AS3 `doubleClickEnabled` property.
+    fn double_click_enabled(&self) -> bool;
+
+    /// Sets whether this display object receives `doubleClick` events.
+    /// Set by the AS3 `doubleClickEnabled` property.
+    fn set_double_click_enabled(&self, context: MutationContext<'gc, '_>, value: bool);
+
     /// Whether this display object has been transformed by ActionScript.
     /// When this flag is set, changes from SWF `PlaceObject` tags are ignored.
     fn transformed_by_script(&self) -> bool;
@@ -880,6 +1463,12 @@ pub trait TDisplayObject<'gc>:
         None
     }
 
+    /// Get this object's runtime drawing for mutation, if it can be drawn on
+    /// by script.
+    fn as_drawing(&self, _gc_context: MutationContext<'gc, '_>) -> Option<RefMut<'_, Drawing>> {
+        None
+    }
+
     fn apply_place_object(
         &self,
         gc_context: MutationContext<'gc, '_>,
@@ -907,6 +1496,18 @@ pub trait TDisplayObject<'gc>:
                     morph_shape.set_ratio(gc_context, ratio);
                 }
             }
+            if let Some(filters) = &place_object.filters {
+                self.set_filters(gc_context, filters.iter().map(Filter::from).collect());
+            }
+            if let Some(blend_mode) = place_object.blend_mode {
+                self.set_blend_mode(gc_context, blend_mode);
+            }
+            if let Some(is_bitmap_cached) = place_object.is_bitmap_cached {
+                self.set_cache_as_bitmap(gc_context, is_bitmap_cached);
+            }
+            if let Some(background_color) = &place_object.background_color {
+                self.set_opaque_background(gc_context, Some(background_color.clone()));
+            }
             // Clip events only apply to movie clips.
             if let (Some(clip_actions), Some(clip)) =
                 (&place_object.clip_actions, self.as_movie_clip())
@@ -942,6 +1543,12 @@ pub trait TDisplayObject<'gc>:
         self.set_matrix(gc_context, &*other.matrix());
         self.set_color_transform(gc_context, &*other.color_transform());
         self.set_clip_depth(gc_context, other.clip_depth());
+        self.set_filters(gc_context, other.filters());
+        self.set_blend_mode(gc_context, other.blend_mode());
+        self.set_cache_as_bitmap(gc_context, other.cache_as_bitmap());
+        self.set_opaque_background(gc_context, other.opaque_background());
+        self.set_scroll_rect(gc_context, other.scroll_rect());
+        self.set_scaling_grid(gc_context, other.scaling_grid());
         self.set_name(gc_context, &*other.name());
         if let (Some(mut me), Some(other)) = (self.as_morph_shape(), other.as_morph_shape()) {
             me.set_ratio(gc_context, other.ratio());
@@ -961,6 +1568,12 @@ pub trait TDisplayObject<'gc>:
         Avm2Value::Undefined // todo: see above
     }
 
+    /// Associate an AVM2 object with this display object.
+    ///
+    /// This is used when a display object is created by AVM2 code, as the
+    /// object exists before the display object does.
+    fn set_object2(&self, _mc: MutationContext<'gc, '_>, _to: Avm2Object<'gc>) {}
+
     /// Tests if a given stage position point intersects with the world bounds of this object.
     fn hit_test_bounds(&self, pos: (Twips, Twips)) -> bool {
         self.world_bounds().contains(pos)
@@ -991,6 +1604,20 @@ pub trait TDisplayObject<'gc>:
         None
     }
 
+    /// Find the display object that should receive AVM2 mouse events at a
+    /// given stage position, if any.
+    ///
+    /// Only interactive objects may be picked; hits on non-interactive
+    /// content are attributed to the nearest interactive ancestor.
+    fn mouse_pick_avm2(
+        &self,
+        _context: &mut UpdateContext<'_, 'gc, '_>,
+        _self_node: DisplayObject<'gc>,
+        _pos: (Twips, Twips),
+    ) -> Option<DisplayObject<'gc>> {
+        None
+    }
+
     fn post_instantiation(
         &self,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -1253,6 +1880,93 @@ macro_rules! impl_display_object_sansbounds {
                 .set_sound_transform(value);
             context.set_sound_transforms_dirty();
         }
+        fn filters(&self) -> Vec<crate::bitmap::filters::Filter> {
+            self.0.read().$field.filters()
+        }
+        fn set_filters(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            filters: Vec<crate::bitmap::filters::Filter>,
+        ) {
+            self.0.write(context).$field.set_filters(filters);
+        }
+        fn blend_mode(&self) -> swf::BlendMode {
+            self.0.read().$field.blend_mode()
+        }
+        fn set_blend_mode(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            blend_mode: swf::BlendMode,
+        ) {
+            self.0.write(context).$field.set_blend_mode(blend_mode);
+        }
+        fn opaque_background(&self) -> Option<swf::Color> {
+            self.0.read().$field.opaque_background()
+        }
+        fn set_opaque_background(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            color: Option<swf::Color>,
+        ) {
+            self.0.write(context).$field.set_opaque_background(color);
+        }
+        fn scroll_rect(&self) -> Option<crate::bounding_box::BoundingBox> {
+            self.0.read().$field.scroll_rect()
+        }
+        fn set_scroll_rect(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            rectangle: Option<crate::bounding_box::BoundingBox>,
+        ) {
+            self.0.write(context).$field.set_scroll_rect(rectangle);
+        }
+        fn scaling_grid(&self) -> Option<crate::bounding_box::BoundingBox> {
+            self.0.read().$field.scaling_grid()
+        }
+        fn set_scaling_grid(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            rectangle: Option<crate::bounding_box::BoundingBox>,
+        ) {
+            self.0.write(context).$field.set_scaling_grid(rectangle);
+        }
+        fn cache_as_bitmap(&self) -> bool {
+            self.0.read().$field.cache_as_bitmap()
+        }
+        fn set_cache_as_bitmap(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            let mut write = self.0.write(context);
+            write.$field.set_cache_as_bitmap(value);
+            if !value {
+                write.$field.set_cached_bitmap(None);
+            }
+        }
+        fn cached_bitmap(&self) -> Option<crate::display_object::CachedBitmap> {
+            self.0.read().$field.cached_bitmap()
+        }
+        fn set_cached_bitmap(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            cached_bitmap: Option<crate::display_object::CachedBitmap>,
+        ) {
+            self.0
+                .write(context)
+                .$field
+                .set_cached_bitmap(cached_bitmap);
+        }
+        fn invalidate_cached_bitmap(&self, context: gc_arena::MutationContext<'gc, '_>) {
+            self.0.write(context).$field.invalidate_cached_bitmap();
+        }
+        fn take_render_changes(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            flags: crate::display_object::RenderChanges,
+        ) -> bool {
+            let changed = self.0.read().$field.render_changes(flags);
+            if changed {
+                self.0.write(context).$field.clear_render_changes(flags);
+            }
+            changed
+        }
         fn visible(&self) -> bool {
             self.0.read().$field.visible()
         }
@@ -1265,6 +1979,28 @@ macro_rules! impl_display_object_sansbounds {
         fn set_lock_root(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
             self.0.write(context).$field.set_lock_root(value);
         }
+        fn mouse_enabled(&self) -> bool {
+            self.0.read().$field.mouse_enabled()
+        }
+        fn set_mouse_enabled(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_mouse_enabled(value);
+        }
+        fn mouse_children(&self) -> bool {
+            self.0.read().$field.mouse_children()
+        }
+        fn set_mouse_children(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_mouse_children(value);
+        }
+        fn double_click_enabled(&self) -> bool {
+            self.0.read().$field.double_click_enabled()
+        }
+        fn set_double_click_enabled(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            value: bool,
+        ) {
+            self.0.write(context).$field.set_double_click_enabled(value);
+        }
         fn transformed_by_script(&self) -> bool {
             self.0.read().$field.transformed_by_script()
         }
@@ -1347,7 +2083,7 @@ bitflags! {
     /// Bit flags used by `DisplayObject`.
     #[derive(Collect)]
     #[collect(no_drop)]
-    struct DisplayObjectFlags: u8 {
+    struct DisplayObjectFlags: u16 {
         /// Whether this object has been removed from the display list.
         /// Necessary in AVM1 to throw away queued actions from removed movie clips.
         const REMOVED                  = 1 << 0;
@@ -1373,6 +2109,30 @@ bitflags! {
         /// Whether this object has `_lockroot` set to true, in which case
         /// it becomes the _root of itself and of any children
         const LOCK_ROOT                = 1 << 6;
+
+        /// Whether this object is rendered through a cached bitmap (`cacheAsBitmap` property).
+        const CACHE_AS_BITMAP          = 1 << 7;
+
+        /// Whether this object can be the target of mouse events (AS3 `mouseEnabled` property).
+        const MOUSE_ENABLED            = 1 << 8;
+
+        /// Whether this object's children can be the targets of mouse events
+        /// (AS3 `mouseChildren` property).
+        const MOUSE_CHILDREN           = 1 << 9;
+
+        /// Whether this object receives `doubleClick` events (AS3 `doubleClickEnabled` property).
+        const DOUBLE_CLICK_ENABLED     = 1 << 10;
+    }
+}
+
+bitflags! {
+    /// Changes to a display object since the cached bitmap of an ancestor was last rendered.
+    pub struct RenderChanges: u8 {
+        /// How this object is placed in its parent, e.g. its transform or its filters.
+        const PLACEMENT = 1 << 0;
+
+        /// The contents of this object, e.g. its drawing or its children.
+        const CONTENTS  = 1 << 1;
     }
 }
 
@@ -109,6 +109,7 @@ impl<'gc> TDisplayObject<'gc> for Bitmap<'gc> {
                 );
                 drop(bd);
                 bitmap_data.write(context.gc_context).set_dirty(false);
+                self.invalidate_cached_bitmap(context.gc_context);
             }
         }
     }
@@ -1,9 +1,10 @@
 //! Container mix-in for display objects
 
+use crate::avm2::{Avm2, Event as Avm2Event, Value as Avm2Value};
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::button::Button;
 use crate::display_object::movie_clip::MovieClip;
-use crate::display_object::{Depth, DisplayObject, TDisplayObject};
+use crate::display_object::{render_base, Depth, DisplayObject, TDisplayObject};
 use crate::string_utils::swf_string_eq_ignore_case;
 use bitflags::bitflags;
 use gc_arena::{Collect, MutationContext};
@@ -222,7 +223,7 @@ pub trait TDisplayObjectContainer<'gc>:
                 context.renderer.activate_mask();
             } else if child.visible() {
                 // Normal child.
-                child.render(context);
+                render_base(child, context);
             }
         }
 
@@ -237,6 +238,97 @@ pub trait TDisplayObjectContainer<'gc>:
     }
 }
 
+/// Determine if a display object is on the stage.
+///
+/// A display object is on the stage if it, or one of its ancestors, is a
+/// level.
+pub fn is_on_stage<'gc>(context: &UpdateContext<'_, 'gc, '_>, object: DisplayObject<'gc>) -> bool {
+    let mut ancestor = object;
+    while let Some(parent) = ancestor.parent() {
+        ancestor = parent;
+    }
+
+    context
+        .levels
+        .values()
+        .any(|level| DisplayObject::ptr_eq(*level, ancestor))
+}
+
+/// Dispatch an AVM2 event to a display object's AVM2 side, if it has one.
+fn dispatch_avm2_event<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    object: DisplayObject<'gc>,
+    event: Avm2Event<'gc>,
+) {
+    if let Avm2Value::Object(target) = object.object2() {
+        if let Err(e) = Avm2::dispatch_event(context, event, target) {
+            log::error!("Encountered AVM2 error when dispatching event: {}", e);
+        }
+    }
+}
+
+/// Dispatch a non-bubbling event to a display object and all of its
+/// descendants.
+fn dispatch_to_descendants<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    object: DisplayObject<'gc>,
+    event_type: &'static str,
+) {
+    dispatch_avm2_event(context, object, Avm2Event::new(event_type));
+
+    if let Some(container) = object.as_container() {
+        let children: Vec<DisplayObject<'gc>> = container.iter_render_list().collect();
+
+        for child in children {
+            dispatch_to_descendants(context, child, event_type);
+        }
+    }
+}
+
+/// Dispatch the events for a child that was just added to a container.
+///
+/// The `added` event bubbles from the child. If the child is now on the
+/// stage, `addedToStage` is then dispatched to it and all of its
+/// descendants.
+pub fn dispatch_added_event<'gc>(
+    child: DisplayObject<'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) {
+    if !matches!(child.object2(), Avm2Value::Object(_)) {
+        return;
+    }
+
+    let mut added_event = Avm2Event::new("added");
+    added_event.set_bubbles(true);
+    dispatch_avm2_event(context, child, added_event);
+
+    if is_on_stage(context, child) {
+        dispatch_to_descendants(context, child, "addedToStage");
+    }
+}
+
+/// Dispatch the events for a child that is about to be removed from its
+/// container.
+///
+/// The `removed` event bubbles from the child. If the child is on the stage,
+/// `removedFromStage` is then dispatched to it and all of its descendants.
+pub fn dispatch_removed_event<'gc>(
+    child: DisplayObject<'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) {
+    if !matches!(child.object2(), Avm2Value::Object(_)) {
+        return;
+    }
+
+    let mut removed_event = Avm2Event::new("removed");
+    removed_event.set_bubbles(true);
+    dispatch_avm2_event(context, child, removed_event);
+
+    if is_on_stage(context, child) {
+        dispatch_to_descendants(context, child, "removedFromStage");
+    }
+}
+
 #[macro_export]
 macro_rules! impl_display_object_container {
     ($field:ident) => {
@@ -270,6 +362,16 @@ macro_rules! impl_display_object_container {
             child: DisplayObject<'gc>,
             depth: Depth,
         ) -> Option<DisplayObject<'gc>> {
+            let displaced_child = self.0.read().$field.get_depth(depth);
+            if let Some(displaced_child) = displaced_child {
+                if !displaced_child.placed_by_script() {
+                    $crate::display_object::container::dispatch_removed_event(
+                        displaced_child,
+                        context,
+                    );
+                }
+            }
+
             let mut write = self.0.write(context.gc_context);
 
             let prev_child = write.$field.insert_child_into_depth_list(depth, child);
@@ -332,6 +434,8 @@ macro_rules! impl_display_object_container {
                 removed_child.set_parent(context.gc_context, None);
             }
 
+            self.invalidate_cached_bitmap(context.gc_context);
+
             removed_child
         }
 
@@ -355,6 +459,7 @@ macro_rules! impl_display_object_container {
                 child,
                 depth,
             );
+            self.invalidate_cached_bitmap(context.gc_context);
         }
 
         fn insert_at_index(
@@ -363,11 +468,14 @@ macro_rules! impl_display_object_container {
             child: DisplayObject<'gc>,
             index: usize,
         ) {
+            let mut is_new_child = true;
             if let Some(old_parent) = child.parent() {
                 if !DisplayObject::ptr_eq(old_parent, (*self).into()) {
                     if let Some(mut old_parent) = old_parent.as_container() {
                         old_parent.remove_child(context, child, Lists::all());
                     }
+                } else {
+                    is_new_child = false;
                 }
             }
 
@@ -378,6 +486,11 @@ macro_rules! impl_display_object_container {
                 .write(context.gc_context)
                 .$field
                 .insert_at_id(context, child, index);
+            self.invalidate_cached_bitmap(context.gc_context);
+
+            if is_new_child {
+                $crate::display_object::container::dispatch_added_event(child, context);
+            }
         }
 
         fn swap_at_index(
@@ -390,6 +503,7 @@ macro_rules! impl_display_object_container {
                 .write(context.gc_context)
                 .$field
                 .swap_at_id(index1, index2);
+            self.invalidate_cached_bitmap(context.gc_context);
         }
 
         fn remove_child(
@@ -403,6 +517,16 @@ macro_rules! impl_display_object_container {
                 (*self).into()
             ));
 
+            let in_render_list = self
+                .0
+                .read()
+                .$field
+                .iter_render_list()
+                .any(|x| DisplayObject::ptr_eq(x, child));
+            if from_lists.contains(Lists::RENDER) && in_render_list {
+                $crate::display_object::container::dispatch_removed_event(child, context);
+            }
+
             let mut write = self.0.write(context.gc_context);
 
             let removed_from_depth_list = from_lists.contains(Lists::DEPTH)
@@ -425,6 +549,8 @@ macro_rules! impl_display_object_container {
                 }
             }
 
+            self.invalidate_cached_bitmap(context.gc_context);
+
             removed_from_render_list || removed_from_depth_list || removed_from_execution_list
         }
 
@@ -432,6 +558,19 @@ macro_rules! impl_display_object_container {
         where
             R: RangeBounds<usize>,
         {
+            let removing: Vec<DisplayObject<'gc>> = self
+                .0
+                .read()
+                .$field
+                .iter_render_list()
+                .enumerate()
+                .filter(|(i, _)| range.contains(i))
+                .map(|(_, child)| child)
+                .collect();
+            for child in removing {
+                $crate::display_object::container::dispatch_removed_event(child, context);
+            }
+
             let mut write = self.0.write(context.gc_context);
             let removed_list: Vec<DisplayObject<'gc>> =
                 write.$field.drain_render_range(range).collect();
@@ -450,10 +589,14 @@ macro_rules! impl_display_object_container {
 
                 write = self.0.write(context.gc_context);
             }
+
+            drop(write);
+            self.invalidate_cached_bitmap(context.gc_context);
         }
 
         fn clear(&mut self, gc_context: MutationContext<'gc, '_>) {
-            self.0.write(gc_context).$field.clear(gc_context)
+            self.0.write(gc_context).$field.clear(gc_context);
+            self.invalidate_cached_bitmap(gc_context);
         }
 
         fn is_empty(self) -> bool {
@@ -662,6 +662,7 @@ impl<'gc> EditText<'gc> {
     fn redraw_border(self, context: MutationContext<'gc, '_>) {
         let mut write = self.0.write(context);
 
+        write.base.invalidate_cached_bitmap();
         write.drawing.clear();
 
         if write.has_border {
@@ -714,6 +715,7 @@ impl<'gc> EditText<'gc> {
     /// text-span representation.
     fn relayout(self, context: &mut UpdateContext<'_, 'gc, '_>) {
         let mut edit_text = self.0.write(context.gc_context);
+        edit_text.base.invalidate_cached_bitmap();
         let autosize = edit_text.autosize;
         let is_word_wrap = edit_text.is_word_wrap;
         let movie = edit_text.static_data.swf.clone();
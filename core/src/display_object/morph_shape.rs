@@ -40,6 +40,7 @@ impl<'gc> MorphShape<'gc> {
 
     pub fn set_ratio(&mut self, gc_context: MutationContext<'gc, '_>, ratio: u16) {
         self.0.write(gc_context).ratio = ratio;
+        self.invalidate_cached_bitmap(gc_context);
     }
 }
 
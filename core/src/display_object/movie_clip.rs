@@ -11,9 +11,12 @@ use crate::backend::audio::{PreloadStreamHandle, SoundHandle, SoundInstanceHandl
 use bitflags::bitflags;
 
 use crate::avm1::activation::{Activation as Avm1Activation, ActivationIdentifier};
+use crate::backend::ui::MouseCursor;
 use crate::character::Character;
 use crate::context::{ActionType, RenderContext, UpdateContext};
-use crate::display_object::container::{ChildContainer, TDisplayObjectContainer};
+use crate::display_object::container::{
+    dispatch_added_event, ChildContainer, TDisplayObjectContainer,
+};
 use crate::display_object::{
     Bitmap, Button, DisplayObjectBase, EditText, Graphic, MorphShapeStatic, TDisplayObject, Text,
 };
@@ -23,11 +26,12 @@ use crate::font::Font;
 use crate::prelude::*;
 use crate::shape_utils::DrawCommand;
 use crate::tag_utils::{self, DecodeResult, SwfMovie, SwfSlice, SwfStream};
+use crate::transform::Transform;
 use crate::types::{Degrees, Percent};
 use crate::vminterface::{AvmObject, AvmType, Instantiator};
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
 use smallvec::SmallVec;
-use std::cell::{Ref, RefCell};
+use std::cell::{Ref, RefCell, RefMut};
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::sync::Arc;
@@ -59,6 +63,7 @@ pub struct MovieClipData<'gc> {
     has_button_clip_event: bool,
     flags: MovieClipFlags,
     avm2_constructor: Option<Avm2Object<'gc>>,
+    avm2_loader_info: Option<Avm2Object<'gc>>,
     drawing: Drawing,
     is_focusable: bool,
     has_focus: bool,
@@ -73,6 +78,7 @@ unsafe impl<'gc> Collect for MovieClipData<'gc> {
         self.static_data.trace(cc);
         self.object.trace(cc);
         self.avm2_constructor.trace(cc);
+        self.avm2_loader_info.trace(cc);
         self.frame_scripts.trace(cc);
     }
 }
@@ -95,6 +101,7 @@ impl<'gc> MovieClip<'gc> {
                 has_button_clip_event: false,
                 flags: MovieClipFlags::empty(),
                 avm2_constructor: None,
+                avm2_loader_info: None,
                 drawing: Drawing::new(),
                 is_focusable: false,
                 has_focus: false,
@@ -127,6 +134,7 @@ impl<'gc> MovieClip<'gc> {
                 has_button_clip_event: false,
                 flags: MovieClipFlags::PLAYING,
                 avm2_constructor: None,
+                avm2_loader_info: None,
                 drawing: Drawing::new(),
                 is_focusable: false,
                 has_focus: false,
@@ -256,6 +264,10 @@ impl<'gc> MovieClip<'gc> {
                 .0
                 .write(context.gc_context)
                 .define_font_4(context, reader),
+            TagCode::DefineScalingGrid => self
+                .0
+                .write(context.gc_context)
+                .define_scaling_grid(context, reader),
             TagCode::DefineMorphShape => self.0.write(context.gc_context).define_morph_shape(
                 context,
                 reader,
@@ -539,7 +551,7 @@ impl<'gc> MovieClip<'gc> {
                         if id == 0 {
                             //TODO: This assumes only the root movie has `SymbolClass` tags.
                             self.set_avm2_constructor(activation.context.gc_context, Some(proto));
-                            self.construct_as_avm2_object(&mut activation.context, self.into());
+                            self.allocate_as_avm2_object(&mut activation.context, self.into());
                         } else if let Some(Character::MovieClip(mc)) = library.character_by_id(id) {
                             mc.set_avm2_constructor(activation.context.gc_context, Some(proto))
                         } else {
@@ -615,6 +627,23 @@ impl<'gc> MovieClip<'gc> {
         self.0.write(mc).set_programmatically_played()
     }
 
+    /// Whether this clip acts like a button when hovered by the mouse.
+    /// Returned by the AS3 `buttonMode` property.
+    pub fn button_mode(self) -> bool {
+        self.0.read().flags.contains(MovieClipFlags::BUTTON_MODE)
+    }
+
+    /// Sets whether this clip acts like a button when hovered by the mouse.
+    /// Set by the AS3 `buttonMode` property.
+    pub fn set_button_mode(self, mc: MutationContext<'gc, '_>, value: bool) {
+        let mut write = self.0.write(mc);
+        if value {
+            write.flags |= MovieClipFlags::BUTTON_MODE;
+        } else {
+            write.flags -= MovieClipFlags::BUTTON_MODE;
+        }
+    }
+
     pub fn next_frame(self, context: &mut UpdateContext<'_, 'gc, '_>) {
         if self.current_frame() < self.total_frames() {
             self.goto_frame(context, self.current_frame() + 1, true);
@@ -849,6 +878,20 @@ impl<'gc> MovieClip<'gc> {
         write.avm2_constructor = prototype;
     }
 
+    /// The AVM2 `LoaderInfo` that describes the movie this clip is the root
+    /// of, if one has been created.
+    pub fn loader_info(self) -> Option<Avm2Object<'gc>> {
+        self.0.read().avm2_loader_info
+    }
+
+    pub fn set_loader_info(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        loader_info: Option<Avm2Object<'gc>>,
+    ) {
+        self.0.write(gc_context).avm2_loader_info = loader_info;
+    }
+
     pub fn frame_label_to_number(self, frame_label: &str) -> Option<FrameNumber> {
         // Frame labels are case insensitive.
         let label = frame_label.to_ascii_lowercase();
@@ -957,13 +1000,16 @@ impl<'gc> MovieClip<'gc> {
         context: &mut UpdateContext<'_, 'gc, '_>,
         style: Option<FillStyle>,
     ) {
-        let mut mc = self.0.write(context.gc_context);
-        mc.drawing.set_fill_style(style);
+        self.0
+            .write(context.gc_context)
+            .drawing
+            .set_fill_style(style);
+        self.invalidate_cached_bitmap(context.gc_context);
     }
 
     pub fn clear(self, context: &mut UpdateContext<'_, 'gc, '_>) {
-        let mut mc = self.0.write(context.gc_context);
-        mc.drawing.clear();
+        self.0.write(context.gc_context).drawing.clear();
+        self.invalidate_cached_bitmap(context.gc_context);
     }
 
     pub fn set_line_style(
@@ -971,13 +1017,19 @@ impl<'gc> MovieClip<'gc> {
         context: &mut UpdateContext<'_, 'gc, '_>,
         style: Option<LineStyle>,
     ) {
-        let mut mc = self.0.write(context.gc_context);
-        mc.drawing.set_line_style(style);
+        self.0
+            .write(context.gc_context)
+            .drawing
+            .set_line_style(style);
+        self.invalidate_cached_bitmap(context.gc_context);
     }
 
     pub fn draw_command(self, context: &mut UpdateContext<'_, 'gc, '_>, command: DrawCommand) {
-        let mut mc = self.0.write(context.gc_context);
-        mc.drawing.draw_command(command);
+        self.0
+            .write(context.gc_context)
+            .drawing
+            .draw_command(command);
+        self.invalidate_cached_bitmap(context.gc_context);
     }
 
     pub fn run_clip_event(
@@ -1059,6 +1111,8 @@ impl<'gc> MovieClip<'gc> {
             .map(|o| o.is_avm2_object())
             .unwrap_or(false)
         {
+            self.construct_avm2_object(context);
+
             let frame_id = self.0.read().current_frame;
             self.run_frame_scripts(frame_id, context);
         }
@@ -1117,6 +1171,8 @@ impl<'gc> MovieClip<'gc> {
                 }
             }
 
+            dispatch_added_event(child, context);
+
             Some(child)
         } else {
             log::error!("Unable to instantiate display node id {}", id);
@@ -1430,7 +1486,11 @@ impl<'gc> MovieClip<'gc> {
         );
     }
 
-    fn construct_as_avm2_object(
+    /// Allocate the AVM2 side of this display object.
+    ///
+    /// The object's constructor is not run until the clip has placed the
+    /// children on its first frame; see `construct_avm2_object`.
+    fn allocate_as_avm2_object(
         self,
         context: &mut UpdateContext<'_, 'gc, '_>,
         display_object: DisplayObject<'gc>,
@@ -1449,7 +1509,7 @@ impl<'gc> MovieClip<'gc> {
                 .unwrap()
         });
 
-        let mut constr_thing = || {
+        let mut allocate_thing = || {
             let mut activation = Avm2Activation::from_nothing(context.reborrow());
             let proto = constructor
                 .get_property(
@@ -1465,16 +1525,49 @@ impl<'gc> MovieClip<'gc> {
             )
             .into();
 
-            constructor.call(Some(object), &[], &mut activation, Some(proto))?;
-
-            Ok(object)
+            Ok((object, constructor))
         };
-        let result: Result<Avm2Object<'gc>, Avm2Error> = constr_thing();
+        let result: Result<(Avm2Object<'gc>, Avm2Object<'gc>), Avm2Error<'gc>> = allocate_thing();
+
+        match result {
+            Ok((object, constructor)) => {
+                let mut write = self.0.write(context.gc_context);
+                write.object = Some(object.into());
+                write.avm2_constructor = Some(constructor);
+                write.flags |= MovieClipFlags::AVM2_CONSTRUCTION_PENDING;
+            }
+            Err(e) => log::error!("Got {} when allocating AVM2 side of display object", e),
+        }
+    }
 
-        if let Ok(object) = result {
-            self.0.write(context.gc_context).object = Some(object.into());
-        } else if let Err(e) = result {
-            log::error!("Got {} when constructing AVM2 side of display object", e);
+    /// Run the AVM2 constructor of this display object, if it has not been
+    /// run yet.
+    ///
+    /// Flash Player runs a clip's constructor after the children on its first
+    /// frame have been placed, so that they are accessible from the
+    /// constructor.
+    fn construct_avm2_object(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let read = self.0.read();
+        if !read
+            .flags
+            .contains(MovieClipFlags::AVM2_CONSTRUCTION_PENDING)
+        {
+            return;
+        }
+
+        let object = read.object.and_then(|o| o.as_avm2_object().ok());
+        let constructor = read.avm2_constructor;
+        drop(read);
+
+        self.0.write(context.gc_context).flags -= MovieClipFlags::AVM2_CONSTRUCTION_PENDING;
+
+        if let (Some(object), Some(constructor)) = (object, constructor) {
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            let proto = object.proto();
+
+            if let Err(e) = constructor.call(Some(object), &[], &mut activation, proto) {
+                log::error!("Got {} when constructing AVM2 side of display object", e);
+            }
         }
     }
 
@@ -1570,6 +1663,156 @@ impl<'gc> MovieClip<'gc> {
     pub fn set_enabled(self, context: &mut UpdateContext<'_, 'gc, '_>, enabled: bool) {
         self.0.write(context.gc_context).enabled = enabled;
     }
+
+    /// Renders this clip with 9-slice scaling.
+    ///
+    /// Each of the nine regions of the scaling grid is rendered separately, masked to the
+    /// area it is stretched to, so that the corners keep their size and the edges are only
+    /// stretched along their length.
+    /// Returns `false` if the clip isn't scaled, in which case it should be rendered normally.
+    fn render_scaling_grid(self, context: &mut RenderContext<'_, 'gc>, grid: &BoundingBox) -> bool {
+        let matrix = *self.matrix();
+        let scale_x = f64::from(matrix.a).hypot(f64::from(matrix.b));
+        let scale_y = f64::from(matrix.c).hypot(f64::from(matrix.d));
+        let bounds = self.bounds();
+        if !bounds.valid
+            || (scale_x - 1.0).abs() < f64::EPSILON && (scale_y - 1.0).abs() < f64::EPSILON
+        {
+            return false;
+        }
+
+        let columns = match scaling_grid_slices(
+            bounds.x_min,
+            bounds.x_max,
+            grid.x_min,
+            grid.x_max,
+            scale_x,
+        ) {
+            Some(columns) => columns,
+            None => return false,
+        };
+        let rows = match scaling_grid_slices(
+            bounds.y_min,
+            bounds.y_max,
+            grid.y_min,
+            grid.y_max,
+            scale_y,
+        ) {
+            Some(rows) => rows,
+            None => return false,
+        };
+
+        let world_matrix = context.transform_stack.transform().matrix * matrix;
+        let color_transform = *self.color_transform();
+        for row in rows.iter() {
+            for column in columns.iter() {
+                let region = Matrix {
+                    a: column.scale() as f32,
+                    b: 0.0,
+                    c: 0.0,
+                    d: row.scale() as f32,
+                    tx: Twips::new(column.translation().round() as i32),
+                    ty: Twips::new(row.translation().round() as i32),
+                };
+                let mask = world_matrix
+                    * Matrix::create_box(
+                        ((column.dst_max - column.dst_min) / 20.0) as f32,
+                        ((row.dst_max - row.dst_min) / 20.0) as f32,
+                        0.0,
+                        Twips::new(column.dst_min.round() as i32),
+                        Twips::new(row.dst_min.round() as i32),
+                    );
+
+                context.renderer.push_mask();
+                context.renderer.draw_rect(Color::from_rgb(0, 0xff), &mask);
+                context.renderer.activate_mask();
+                context.transform_stack.push(&Transform {
+                    matrix: matrix * region,
+                    color_transform,
+                });
+                self.0.read().drawing.render(context);
+                self.render_children(context);
+                context.transform_stack.pop();
+                context.renderer.deactivate_mask();
+                context.renderer.draw_rect(Color::from_rgb(0, 0xff), &mask);
+                context.renderer.pop_mask();
+            }
+        }
+        true
+    }
+}
+
+/// A row or column of a 9-slice scaling grid, in local twips.
+/// Its contents are mapped from the `src` range onto the `dst` range.
+#[derive(Clone, Copy, Debug)]
+struct ScalingGridSlice {
+    src_min: f64,
+    src_max: f64,
+    dst_min: f64,
+    dst_max: f64,
+}
+
+impl ScalingGridSlice {
+    fn scale(&self) -> f64 {
+        (self.dst_max - self.dst_min) / (self.src_max - self.src_min)
+    }
+
+    fn translation(&self) -> f64 {
+        self.dst_min - self.src_min * self.scale()
+    }
+}
+
+/// Splits the bounds of a clip along one axis into the slices of its scaling grid,
+/// leaving out empty slices.
+///
+/// The outer slices keep their size once the clip is scaled by `scale`. If the clip is
+/// scaled smaller than the outer slices, they are shrunk proportionally instead.
+/// Returns `None` if the grid doesn't lie within the bounds.
+fn scaling_grid_slices(
+    min: Twips,
+    max: Twips,
+    grid_min: Twips,
+    grid_max: Twips,
+    scale: f64,
+) -> Option<Vec<ScalingGridSlice>> {
+    if grid_min < min || grid_max > max || grid_min >= grid_max || scale <= 0.0 {
+        return None;
+    }
+
+    let (min, max) = (f64::from(min.get()), f64::from(max.get()));
+    let (grid_min, grid_max) = (f64::from(grid_min.get()), f64::from(grid_max.get()));
+    let outer = (grid_min - min) + (max - grid_max);
+    let outer_scale = ((max - min) * scale / outer).min(1.0) / scale;
+    let dst_grid_min = min + (grid_min - min) * outer_scale;
+    let dst_grid_max = max - (max - grid_max) * outer_scale;
+
+    let slices = [
+        ScalingGridSlice {
+            src_min: min,
+            src_max: grid_min,
+            dst_min: min,
+            dst_max: dst_grid_min,
+        },
+        ScalingGridSlice {
+            src_min: grid_min,
+            src_max: grid_max,
+            dst_min: dst_grid_min,
+            dst_max: dst_grid_max,
+        },
+        ScalingGridSlice {
+            src_min: grid_max,
+            src_max: max,
+            dst_min: dst_grid_max,
+            dst_max: max,
+        },
+    ];
+    Some(
+        slices
+            .iter()
+            .filter(|slice| slice.src_max > slice.src_min && slice.dst_max > slice.dst_min)
+            .copied()
+            .collect(),
+    )
 }
 
 impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
@@ -1609,6 +1852,10 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
             self.run_frame_internal((*self).into(), context, true);
         }
 
+        // Clips that were stopped before their first frame still need their
+        // AVM2 constructor to run.
+        self.construct_avm2_object(context);
+
         if is_load_frame {
             self.0.write(context.gc_context).run_clip_postevent(
                 (*self).into(),
@@ -1619,6 +1866,12 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
     }
 
     fn render(&self, context: &mut RenderContext<'_, 'gc>) {
+        if let Some(scaling_grid) = self.scaling_grid() {
+            if context.allow_mask && self.render_scaling_grid(context, &scaling_grid) {
+                return;
+            }
+        }
+
         context.transform_stack.push(&*self.transform());
         self.0.read().drawing.render(context);
         self.render_children(context);
@@ -1661,6 +1914,10 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         self_node: DisplayObject<'gc>,
         point: (Twips, Twips),
     ) -> Option<DisplayObject<'gc>> {
+        if let Avm2Value::Object(_) = self.object2() {
+            return self.mouse_pick_avm2(context, self_node, point);
+        }
+
         if self.visible() {
             if self.world_bounds().contains(point) {
                 // This movieclip operates in "button mode" if it has a mouse handler,
@@ -1699,6 +1956,70 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         None
     }
 
+    fn mouse_cursor(&self) -> MouseCursor {
+        // AS3 clips only show the hand cursor in button mode.
+        if let Avm2Value::Object(_) = self.object2() {
+            if !self.button_mode() {
+                return MouseCursor::Arrow;
+            }
+        }
+
+        MouseCursor::Hand
+    }
+
+    fn mouse_pick_avm2(
+        &self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        self_node: DisplayObject<'gc>,
+        point: (Twips, Twips),
+    ) -> Option<DisplayObject<'gc>> {
+        if !self.visible() {
+            return None;
+        }
+
+        let this = if self.mouse_enabled() {
+            Some(self_node)
+        } else {
+            None
+        };
+
+        // Masks are not themselves hit-tested.
+        let children: Vec<DisplayObject<'gc>> = self
+            .iter_render_list()
+            .filter(|child| child.clip_depth() <= 0)
+            .collect();
+
+        for child in children.into_iter().rev() {
+            if child.as_movie_clip().is_some() {
+                if let Some(result) = child.mouse_pick_avm2(context, child, point) {
+                    return if self.mouse_children() {
+                        Some(result)
+                    } else {
+                        this
+                    };
+                }
+            }
+
+            // Children that ignore the mouse still count as part of our own
+            // hit area.
+            if child.visible() && child.hit_test_shape(context, point) {
+                return this;
+            }
+        }
+
+        let local_matrix = self.global_to_local_matrix();
+        if self
+            .0
+            .read()
+            .drawing
+            .hit_test(local_matrix * point, &local_matrix)
+        {
+            return this;
+        }
+
+        None
+    }
+
     fn handle_clip_event(
         &self,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -1731,6 +2052,10 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         Some(self.into())
     }
 
+    fn as_drawing(&self, gc_context: MutationContext<'gc, '_>) -> Option<RefMut<'_, Drawing>> {
+        Some(RefMut::map(self.0.write(gc_context), |s| &mut s.drawing))
+    }
+
     fn post_instantiation(
         &self,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -1744,13 +2069,17 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         let movie = self.movie().unwrap();
         let library = context.library.library_for_movie_mut(movie);
 
+        if let Some(scaling_grid) = library.get_scaling_grid(self.id()) {
+            self.set_scaling_grid(context.gc_context, Some(scaling_grid));
+        }
+
         // Attempt to divine the VM we should initialize this instance as.
         // If our movie doesn't already have that determined, then this is the
         // root movie clip and we need to scan the SWF for file attributes.
         let vm_type = library.avm_type();
 
         if vm_type == AvmType::Avm2 {
-            self.construct_as_avm2_object(context, display_object);
+            self.allocate_as_avm2_object(context, display_object);
         } else if vm_type == AvmType::Avm1 {
             self.construct_as_avm1_object(
                 context,
@@ -1780,6 +2109,10 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
             .unwrap_or(Avm2Value::Undefined)
     }
 
+    fn set_object2(&self, mc: MutationContext<'gc, '_>, to: Avm2Object<'gc>) {
+        self.0.write(mc).object = Some(to.into());
+    }
+
     fn unload(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
         for child in self.iter_execution_list() {
             child.unload(context);
@@ -2691,6 +3024,21 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn define_scaling_grid(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<'a>,
+    ) -> DecodeResult {
+        let id = reader.read_character_id()?;
+        let splitter_rect = reader.read_rectangle()?;
+        context
+            .library
+            .library_for_movie_mut(self.movie())
+            .register_scaling_grid(id, splitter_rect.into());
+        Ok(())
+    }
+
     #[inline]
     fn frame_label(
         &mut self,
@@ -3139,6 +3487,15 @@ bitflags! {
         /// The AS3 `isPlaying` property is broken and yields false until you first
         /// call `play` to unbreak it. This flag tracks that bug.
         const PROGRAMMATICALLY_PLAYED = 1 << 2;
+
+        /// Whether this `MovieClip`'s AVM2 object has been allocated, but its
+        /// constructor has yet to run.
+        const AVM2_CONSTRUCTION_PENDING = 1 << 3;
+
+        /// Whether this `MovieClip` acts like a button when hovered by the mouse.
+        ///
+        /// This is set by the AS3 `buttonMode` property.
+        const BUTTON_MODE             = 1 << 4;
     }
 }
 
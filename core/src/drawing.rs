@@ -82,6 +82,17 @@ impl Drawing {
         self.dirty.set(true);
     }
 
+    /// Set the fill used to paint the strokes of the current line style.
+    ///
+    /// This has no effect if there is no current line style.
+    pub fn set_line_fill_style(&mut self, style: FillStyle) {
+        if let Some((line_style, _)) = &mut self.current_line {
+            line_style.fill_style = Some(style);
+        }
+
+        self.dirty.set(true);
+    }
+
     pub fn draw_command(&mut self, command: DrawCommand) {
         let mut include_last = false;
         let stroke_width = if let Some((style, _)) = &self.current_line {
@@ -104,15 +115,8 @@ impl Drawing {
             }
         }
 
-        self.cursor = command.end_point();
-
-        if let Some((_, commands)) = &mut self.current_line {
-            commands.push(command.clone());
-        }
-        if let Some((_, commands)) = &mut self.current_fill {
-            commands.push(command);
-        }
-
+        // Include the point this segment starts from, which is the end of the
+        // previous command.
         if include_last {
             if let Some(command) = self
                 .current_fill
@@ -133,6 +137,15 @@ impl Drawing {
             }
         }
 
+        self.cursor = command.end_point();
+
+        if let Some((_, commands)) = &mut self.current_line {
+            commands.push(command.clone());
+        }
+        if let Some((_, commands)) = &mut self.current_fill {
+            commands.push(command);
+        }
+
         self.dirty.set(true);
     }
 
@@ -195,13 +208,18 @@ impl Drawing {
         }
     }
 
+    /// The point that the next drawing command will start from.
+    pub fn cursor(&self) -> (Twips, Twips) {
+        self.cursor
+    }
+
     pub fn self_bounds(&self) -> BoundingBox {
         self.shape_bounds.clone()
     }
 
     pub fn hit_test(&self, point: (Twips, Twips), local_matrix: &swf::Matrix) -> bool {
         use crate::shape_utils;
-        for path in &self.fills {
+        for path in self.fills.iter().chain(self.current_fill.iter()) {
             if shape_utils::draw_command_fill_hit_test(&path.1, point) {
                 return true;
             }
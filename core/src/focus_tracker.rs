@@ -1,6 +1,10 @@
 use crate::avm1::{Avm1, Value};
+use crate::avm2::{
+    Avm2, Event as Avm2Event, EventData as Avm2EventData, Object as Avm2Object, Value as Avm2Value,
+};
 use crate::context::UpdateContext;
 pub use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::events::KeyCode;
 use gc_arena::{Collect, GcCell, MutationContext};
 
 #[collect(no_drop)]
@@ -55,5 +59,48 @@ impl<'gc> FocusTracker<'gc> {
                 focused_element.map(|v| v.object()).unwrap_or(Value::Null),
             ],
         );
+
+        let old_object2 = match old.map(|v| v.object2()) {
+            Some(Avm2Value::Object(o)) => Some(o),
+            _ => None,
+        };
+        let new_object2 = match focused_element.map(|v| v.object2()) {
+            Some(Avm2Value::Object(o)) => Some(o),
+            _ => None,
+        };
+
+        if let Some(old_object2) = old_object2 {
+            Self::dispatch_avm2_focus_event(context, "focusOut", old_object2, new_object2);
+        }
+        if let Some(new_object2) = new_object2 {
+            Self::dispatch_avm2_focus_event(context, "focusIn", new_object2, old_object2);
+        }
+    }
+
+    /// Dispatch an AVM2 `FocusEvent` to an object gaining or losing focus.
+    fn dispatch_avm2_focus_event(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event_type: &str,
+        target: Avm2Object<'gc>,
+        related_object: Option<Avm2Object<'gc>>,
+    ) {
+        let shift_key = context.ui.is_key_down(KeyCode::Shift);
+        let mut event = Avm2Event::with_data(
+            event_type,
+            Avm2EventData::Focus {
+                related_object,
+                shift_key,
+                key_code: 0,
+            },
+        );
+        event.set_bubbles(true);
+
+        if let Err(e) = Avm2::dispatch_event(context, event, target) {
+            log::error!(
+                "Encountered AVM2 error when dispatching {}: {}",
+                event_type,
+                e
+            );
+        }
     }
 }
@@ -38,6 +38,7 @@ pub mod property_map;
 pub mod shape_utils;
 pub mod string_utils;
 pub mod tag_utils;
+mod timer;
 mod transform;
 mod types;
 mod vminterface;
@@ -63,6 +63,7 @@ pub struct MovieLibrary<'gc> {
     export_characters: PropertyMap<Character<'gc>>,
     jpeg_tables: Option<Vec<u8>>,
     fonts: HashMap<FontDescriptor, Font<'gc>>,
+    scaling_grids: HashMap<CharacterId, BoundingBox>,
     avm_type: AvmType,
     avm2_domain: Option<Avm2Domain<'gc>>,
     /// Shared reference to the constructor registry used for this movie.
@@ -77,6 +78,7 @@ impl<'gc> MovieLibrary<'gc> {
             export_characters: PropertyMap::new(),
             jpeg_tables: None,
             fonts: HashMap::new(),
+            scaling_grids: HashMap::new(),
             avm_type,
             avm2_domain: None,
             avm1_constructor_registry: None,
@@ -221,6 +223,16 @@ impl<'gc> MovieLibrary<'gc> {
         }
     }
 
+    /// Registers the 9-slice scaling grid of a character, from a `DefineScalingGrid` tag.
+    pub fn register_scaling_grid(&mut self, id: CharacterId, grid: BoundingBox) {
+        self.scaling_grids.insert(id, grid);
+    }
+
+    /// Returns the 9-slice scaling grid that instances of a character are created with.
+    pub fn get_scaling_grid(&self, id: CharacterId) -> Option<BoundingBox> {
+        self.scaling_grids.get(&id).cloned()
+    }
+
     pub fn set_jpeg_tables(&mut self, data: Vec<u8>) {
         if self.jpeg_tables.is_some() {
             // SWF spec says there should only be one JPEGTables tag.
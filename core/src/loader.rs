@@ -2,10 +2,16 @@
 
 use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::{Avm1, AvmString, Object, TObject, Value};
-use crate::avm2::Domain as Avm2Domain;
+use crate::avm2::{
+    Avm2, Domain as Avm2Domain, Event as Avm2Event, EventData as Avm2EventData, LoaderInfoObject,
+    TObject as Avm2TObject,
+};
 use crate::backend::navigator::OwnedFuture;
-use crate::context::{ActionQueue, ActionType};
-use crate::display_object::{DisplayObject, MorphShape, TDisplayObject};
+use crate::backend::render::{determine_jpeg_tag_format, JpegTagFormat};
+use crate::context::{ActionQueue, ActionType, UpdateContext};
+use crate::display_object::{
+    Bitmap, DisplayObject, MorphShape, MovieClip, TDisplayObject, TDisplayObjectContainer,
+};
 use crate::player::{Player, NEWEST_PLAYER_VERSION};
 use crate::property_map::PropertyMap;
 use crate::tag_utils::SwfMovie;
@@ -41,12 +47,21 @@ pub enum Error {
     #[error("Non-XML loader spawned as XML loader")]
     NotXmlLoader,
 
+    #[error("Non-AVM2 loader spawned as AVM2 loader")]
+    NotAvm2Loader,
+
     #[error("Could not fetch movie {0}")]
     FetchError(String),
 
     #[error("Invalid SWF")]
     InvalidSwf(#[from] crate::tag_utils::Error),
 
+    #[error("Loaded file is an unknown type")]
+    UnknownContentType,
+
+    #[error("Invalid image: {0}")]
+    InvalidImage(String),
+
     #[error("Invalid XML encoding")]
     InvalidXmlEncoding(#[from] FromUtf8Error),
 
@@ -186,6 +201,41 @@ impl<'gc> LoadManager<'gc> {
         }
     }
 
+    /// Kick off a load of a movie or image into an AVM2 `Loader`.
+    ///
+    /// The `Loader` is the one described by `loader_info`. Loaded movies run
+    /// their code in `domain`, and are given `parameters` as their
+    /// flashvars.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_into_avm2_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        loader_info: LoaderInfoObject<'gc>,
+        domain: Avm2Domain<'gc>,
+        parameters: Vec<(String, String)>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+        url: Option<String>,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::Avm2Loader {
+            self_handle: None,
+            loader_info,
+            domain,
+            parameters,
+        };
+        let handle = self.add_loader(loader);
+
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.introduce_loader_handle(handle);
+
+        loader.avm2_loader(player, fetch, url)
+    }
+
+    /// Remove a loader whose load has finished, invalidating its handle.
+    pub fn remove_loader(&mut self, handle: Handle) {
+        self.0.remove(handle);
+    }
+
     /// Kick off a form data load into an AVM1 object.
     ///
     /// Returns the loader's async process, which you will need to spawn.
@@ -334,6 +384,22 @@ pub enum Loader<'gc> {
         /// The target node whose contents will be replaced with the parsed XML.
         target_node: XMLNode<'gc>,
     },
+
+    /// Loader that is loading a movie or image into an AVM2 `Loader`.
+    Avm2Loader {
+        /// The handle to refer to this loader instance.
+        self_handle: Option<Handle>,
+
+        /// The `LoaderInfo` of the `Loader`, which describes the load and
+        /// receives its events.
+        loader_info: LoaderInfoObject<'gc>,
+
+        /// The application domain that a loaded movie's code runs in.
+        domain: Avm2Domain<'gc>,
+
+        /// The flashvars given to a loaded movie.
+        parameters: Vec<(String, String)>,
+    },
 }
 
 unsafe impl<'gc> Collect for Loader<'gc> {
@@ -351,6 +417,14 @@ unsafe impl<'gc> Collect for Loader<'gc> {
             Loader::Form { target_object, .. } => target_object.trace(cc),
             Loader::LoadVars { target_object, .. } => target_object.trace(cc),
             Loader::XML { target_node, .. } => target_node.trace(cc),
+            Loader::Avm2Loader {
+                loader_info,
+                domain,
+                ..
+            } => {
+                loader_info.trace(cc);
+                domain.trace(cc);
+            }
         }
     }
 }
@@ -367,6 +441,7 @@ impl<'gc> Loader<'gc> {
             Loader::Form { self_handle, .. } => *self_handle = Some(handle),
             Loader::LoadVars { self_handle, .. } => *self_handle = Some(handle),
             Loader::XML { self_handle, .. } => *self_handle = Some(handle),
+            Loader::Avm2Loader { self_handle, .. } => *self_handle = Some(handle),
         }
     }
 
@@ -848,4 +923,241 @@ impl<'gc> Loader<'gc> {
             Ok(())
         })
     }
+
+    /// Construct a future for a load into an AVM2 `Loader`.
+    ///
+    /// The `open`, `progress`, `init`, `complete` and `ioError` events of the
+    /// load are dispatched to the `Loader`'s `LoaderInfo`.
+    ///
+    /// If the loader is not an AVM2 loader then the returned future will
+    /// yield an error immediately once spawned.
+    pub fn avm2_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+        mut url: Option<String>,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::Avm2Loader { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotAvm2Loader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            player
+                .lock()
+                .expect("Could not lock player!!")
+                .update(|uc| -> Result<(), Error> {
+                    if let Some(url) = &mut url {
+                        *url = uc.navigator.resolve_relative_url(url).into_owned();
+                    }
+
+                    let loader_info = match uc.load_manager.get_loader(handle) {
+                        Some(Loader::Avm2Loader { loader_info, .. }) => *loader_info,
+                        None => return Err(Error::Cancelled),
+                        _ => unreachable!(),
+                    };
+
+                    dispatch_avm2_event(uc, loader_info, Avm2Event::new("open"));
+
+                    Ok(())
+                })?;
+
+            let data = fetch.await;
+
+            player
+                .lock()
+                .expect("Could not lock player!!")
+                .update(|uc| -> Result<(), Error> {
+                    let (loader_info, domain, parameters) = match uc.load_manager.get_loader(handle)
+                    {
+                        Some(Loader::Avm2Loader {
+                            loader_info,
+                            domain,
+                            parameters,
+                            ..
+                        }) => (*loader_info, *domain, parameters.clone()),
+                        None => return Err(Error::Cancelled),
+                        _ => unreachable!(),
+                    };
+
+                    uc.load_manager.remove_loader(handle);
+
+                    let data = match data {
+                        Ok(data) => data,
+                        Err(_) => {
+                            //TODO: Inspect the fetch error.
+                            //This requires cooperation from the backend to send
+                            //abstract error types we can actually inspect.
+                            let text = format!(
+                                "Error #2035: URL Not Found. URL: {}",
+                                url.as_deref().unwrap_or_default()
+                            );
+                            dispatch_avm2_io_error(uc, loader_info, 2035, text);
+
+                            return Ok(());
+                        }
+                    };
+
+                    let length = data.len();
+                    loader_info.set_progress(uc.gc_context, length, length);
+                    dispatch_avm2_event(
+                        uc,
+                        loader_info,
+                        Avm2Event::with_data(
+                            "progress",
+                            Avm2EventData::Progress {
+                                bytes_loaded: length as f64,
+                                bytes_total: length as f64,
+                            },
+                        ),
+                    );
+
+                    if let Err(e) =
+                        load_avm2_content(uc, loader_info, domain, &parameters, &data, url)
+                    {
+                        log::warn!("Could not load content into AVM2 Loader: {}", e);
+
+                        let text = "Error #2124: Loaded file is an unknown type.".to_string();
+                        dispatch_avm2_io_error(uc, loader_info, 2124, text);
+
+                        return Ok(());
+                    }
+
+                    complete_avm2_load(uc, loader_info);
+
+                    Ok(())
+                })
+        })
+    }
+}
+
+/// Create the display object for content loaded into an AVM2 `Loader`,
+/// make it the only child of the `Loader`, and run its first frame.
+///
+/// SWF movies become movie clips that run in `domain`. PNG, JPEG and GIF
+/// images become bitmaps.
+fn load_avm2_content<'gc>(
+    uc: &mut UpdateContext<'_, 'gc, '_>,
+    loader_info: LoaderInfoObject<'gc>,
+    domain: Avm2Domain<'gc>,
+    parameters: &[(String, String)],
+    data: &[u8],
+    url: Option<String>,
+) -> Result<(), Error> {
+    let loader_clip = loader_info
+        .loader()
+        .and_then(|loader| loader.as_display_object())
+        .ok_or(Error::Cancelled)?;
+
+    let (content, content_type, movie) = match data {
+        [b'F', b'W', b'S', ..] | [b'C', b'W', b'S', ..] | [b'Z', b'W', b'S', ..] => {
+            let mut movie = SwfMovie::from_data(data, url)?;
+            for (key, value) in parameters {
+                movie.parameters_mut().insert(key, value.to_owned(), false);
+            }
+            let movie = Arc::new(movie);
+
+            uc.library
+                .library_for_movie_mut(movie.clone())
+                .set_avm2_domain(domain);
+
+            let clip = MovieClip::from_movie(uc.gc_context, movie.clone());
+            clip.set_loader_info(uc.gc_context, Some(loader_info.into()));
+
+            let content: DisplayObject<'gc> = clip.into();
+            content.set_lock_root(uc.gc_context, true);
+            content.post_instantiation(uc, content, None, Instantiator::Movie, false);
+
+            let mut morph_shapes = fnv::FnvHashMap::default();
+            clip.preload(uc, &mut morph_shapes);
+
+            // Finalize morph shapes.
+            for (id, static_data) in morph_shapes {
+                let morph_shape = MorphShape::new(uc.gc_context, static_data);
+                uc.library
+                    .library_for_movie_mut(movie.clone())
+                    .register_character(id, crate::character::Character::MorphShape(morph_shape));
+            }
+
+            (content, "application/x-shockwave-flash", Some(movie))
+        }
+        _ => {
+            let content_type = match determine_jpeg_tag_format(data) {
+                JpegTagFormat::Jpeg => "image/jpeg",
+                JpegTagFormat::Png => "image/png",
+                JpegTagFormat::Gif => "image/gif",
+                JpegTagFormat::Unknown => return Err(Error::UnknownContentType),
+            };
+            let bitmap = uc
+                .renderer
+                .register_bitmap_jpeg_2(data)
+                .map_err(|e| Error::InvalidImage(e.to_string()))?;
+            let content: DisplayObject<'gc> =
+                Bitmap::new(uc, 0, bitmap.handle, bitmap.width, bitmap.height).into();
+
+            (content, content_type, None)
+        }
+    };
+
+    if let Some(mut container) = loader_clip.as_container() {
+        container.clear(uc.gc_context);
+        container.insert_at_index(uc, content, 0);
+        content.set_placed_by_script(uc.gc_context, true);
+    }
+
+    loader_info.set_content(uc.gc_context, content, content_type, movie, Some(domain));
+
+    // Flash Player runs the first frame of loaded content, including its
+    // document class constructor, before `init` is dispatched.
+    content.run_frame(uc);
+
+    Ok(())
+}
+
+/// Dispatch an event to an AVM2 `LoaderInfo`.
+fn dispatch_avm2_event<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    loader_info: LoaderInfoObject<'gc>,
+    event: Avm2Event<'gc>,
+) {
+    if let Err(e) = Avm2::dispatch_event(context, event, loader_info.into()) {
+        log::error!("Encountered AVM2 error when dispatching event: {}", e);
+    }
+}
+
+/// Dispatch an `ioError` event to an AVM2 `LoaderInfo`.
+fn dispatch_avm2_io_error<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    loader_info: LoaderInfoObject<'gc>,
+    error_id: i32,
+    text: String,
+) {
+    let text = AvmString::new(context.gc_context, text);
+    let event = Avm2Event::with_data("ioError", Avm2EventData::IOError { text, error_id });
+
+    dispatch_avm2_event(context, loader_info, event);
+}
+
+/// Dispatch the `init` and `complete` events that finish a load to an AVM2
+/// `LoaderInfo`.
+///
+/// The events are only dispatched once for each load.
+pub fn complete_avm2_load<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    loader_info: LoaderInfoObject<'gc>,
+) {
+    if loader_info.is_complete() {
+        return;
+    }
+
+    loader_info.set_complete(context.gc_context);
+
+    dispatch_avm2_event(context, loader_info, Avm2Event::new("init"));
+    dispatch_avm2_event(context, loader_info, Avm2Event::new("complete"));
 }
@@ -3,8 +3,11 @@ use crate::avm1::debug::VariableDumper;
 use crate::avm1::globals::system::SystemProperties;
 use crate::avm1::object::Object;
 use crate::avm1::property::Attribute;
-use crate::avm1::{Avm1, AvmString, ScriptObject, TObject, Timers, Value};
-use crate::avm2::{Avm2, Domain as Avm2Domain};
+use crate::avm1::{Avm1, AvmString, ScriptObject, TObject, Value};
+use crate::avm2::{
+    Avm2, Domain as Avm2Domain, Event as Avm2Event, EventData as Avm2EventData,
+    Object as Avm2Object, TObject as Avm2TObject,
+};
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
     locale::LocaleBackend,
@@ -16,7 +19,7 @@ use crate::backend::{
 };
 use crate::config::Letterbox;
 use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
-use crate::display_object::{EditText, MorphShape, MovieClip};
+use crate::display_object::{render_base, EditText, MorphShape, MovieClip};
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, PlayerEvent};
 use crate::external::Value as ExternalValue;
 use crate::external::{ExternalInterface, ExternalInterfaceProvider};
@@ -26,6 +29,7 @@ use crate::loader::LoadManager;
 use crate::prelude::*;
 use crate::property_map::PropertyMap;
 use crate::tag_utils::SwfMovie;
+use crate::timer::Timers;
 use crate::transform::TransformStack;
 use crate::vminterface::{AvmType, Instantiator};
 use gc_arena::{make_arena, ArenaParameters, Collect, GcCell};
@@ -44,6 +48,9 @@ pub static DEVICE_FONT_TAG: &[u8] = include_bytes!("../assets/noto-sans-definefo
 /// `player_version`.
 pub const NEWEST_PLAYER_VERSION: u8 = 32;
 
+/// The longest time between two clicks that still counts as a double click.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
 #[derive(Collect)]
 #[collect(no_drop)]
 struct GcRoot<'gc>(GcCell<'gc, GcRootData<'gc>>);
@@ -62,6 +69,9 @@ struct GcRootData<'gc> {
 
     mouse_hovered_object: Option<DisplayObject<'gc>>, // TODO: Remove GcCell wrapped inside GcCell.
 
+    /// The display object that the mouse button was last pressed over.
+    mouse_pressed_object: Option<DisplayObject<'gc>>,
+
     /// The object being dragged via a `startDrag` action.
     drag_object: Option<DragObject<'gc>>,
 
@@ -201,6 +211,9 @@ pub struct Player {
     mouse_pos: (Twips, Twips),
     is_mouse_down: bool,
 
+    /// When the last AVM2 `click` event was fired, used to detect double clicks.
+    last_click_time: Option<Duration>,
+
     /// The current mouse cursor icon.
     mouse_cursor: MouseCursor,
 
@@ -269,6 +282,7 @@ impl Player {
                         library: Library::empty(gc_context),
                         levels: BTreeMap::new(),
                         mouse_hovered_object: None,
+                        mouse_pressed_object: None,
                         drag_object: None,
                         avm1: Avm1::new(gc_context, NEWEST_PLAYER_VERSION),
                         avm2: Avm2::new(gc_context),
@@ -296,6 +310,7 @@ impl Player {
 
             mouse_pos: (Twips::new(0), Twips::new(0)),
             is_mouse_down: false,
+            last_click_time: None,
             mouse_cursor: MouseCursor::Arrow,
 
             renderer,
@@ -325,7 +340,8 @@ impl Player {
             );
             context.levels.insert(0u32, fake_root.into());
 
-            Avm2::load_player_globals(context)
+            // AVM2 errors can't outlive the arena, so they're converted to a message.
+            Avm2::load_player_globals(context).map_err(|e| e.to_string())
         })?;
 
         player.build_matrices();
@@ -716,6 +732,17 @@ impl Player {
                 _ => (None, None),
             };
 
+            // Fire keyboard events on the focused AVM2 object.
+            match event {
+                PlayerEvent::KeyDown { key_code } => {
+                    Self::dispatch_avm2_keyboard_event(context, "keyDown", key_code)
+                }
+                PlayerEvent::KeyUp { key_code } => {
+                    Self::dispatch_avm2_keyboard_event(context, "keyUp", key_code)
+                }
+                _ => (),
+            }
+
             // Fire clip event on all clips.
             if let Some(clip_event) = clip_event {
                 let levels: Vec<DisplayObject<'_>> = context.levels.values().copied().collect();
@@ -739,6 +766,7 @@ impl Player {
         });
 
         let mut is_mouse_down = self.is_mouse_down;
+        let mut last_click_time = self.last_click_time;
         self.mutate_with_update_context(|context| {
             if let Some(node) = context.mouse_hovered_object {
                 if node.removed() {
@@ -752,7 +780,19 @@ impl Player {
                     needs_render = true;
                     if let Some(node) = context.mouse_hovered_object {
                         node.handle_clip_event(context, ClipEvent::Press);
+
+                        // A second press on the same object may complete a double click.
+                        let is_same_object =
+                            context.mouse_pressed_object.map(|o| o.as_ptr()) == Some(node.as_ptr());
+                        if !is_same_object {
+                            last_click_time = None;
+                        }
+
+                        Self::dispatch_avm2_mouse_event(context, "mouseDown", node, None, true, 0);
+                    } else {
+                        last_click_time = None;
                     }
+                    context.mouse_pressed_object = context.mouse_hovered_object;
                 }
 
                 PlayerEvent::MouseUp { .. } => {
@@ -760,6 +800,61 @@ impl Player {
                     needs_render = true;
                     if let Some(node) = context.mouse_hovered_object {
                         node.handle_clip_event(context, ClipEvent::Release);
+
+                        Self::dispatch_avm2_mouse_event(context, "mouseUp", node, None, false, 0);
+
+                        let is_same_object =
+                            context.mouse_pressed_object.map(|o| o.as_ptr()) == Some(node.as_ptr());
+                        if is_same_object {
+                            let now = context.navigator.time_since_launch();
+                            let is_double_click = node.double_click_enabled()
+                                && last_click_time
+                                    .map(|time| now - time <= DOUBLE_CLICK_INTERVAL)
+                                    .unwrap_or(false);
+
+                            if is_double_click {
+                                last_click_time = None;
+                                Self::dispatch_avm2_mouse_event(
+                                    context,
+                                    "doubleClick",
+                                    node,
+                                    None,
+                                    false,
+                                    0,
+                                );
+                            } else {
+                                last_click_time = Some(now);
+                                Self::dispatch_avm2_mouse_event(
+                                    context, "click", node, None, false, 0,
+                                );
+                            }
+                        }
+                    }
+                }
+
+                PlayerEvent::MouseMove { .. } => {
+                    if let Some(node) = context.mouse_hovered_object {
+                        Self::dispatch_avm2_mouse_event(
+                            context,
+                            "mouseMove",
+                            node,
+                            None,
+                            is_mouse_down,
+                            0,
+                        );
+                    }
+                }
+
+                PlayerEvent::MouseWheel { delta } => {
+                    if let Some(node) = context.mouse_hovered_object {
+                        Self::dispatch_avm2_mouse_event(
+                            context,
+                            "mouseWheel",
+                            node,
+                            None,
+                            is_mouse_down,
+                            delta.lines() as i32,
+                        );
                     }
                 }
 
@@ -769,6 +864,7 @@ impl Player {
             Self::run_actions(context);
         });
         self.is_mouse_down = is_mouse_down;
+        self.last_click_time = last_click_time;
         if needs_render {
             self.needs_render = true;
         }
@@ -830,14 +926,50 @@ impl Player {
                 if let Some(node) = cur_hovered {
                     if !node.removed() {
                         node.handle_clip_event(context, ClipEvent::RollOut);
+                        Self::dispatch_avm2_mouse_event(
+                            context,
+                            "mouseOut",
+                            node,
+                            new_hovered,
+                            false,
+                            0,
+                        );
+                    }
+                }
+
+                // `rollOut` and `rollOver` do not bubble; instead, they are
+                // sent to every ancestor the mouse has left or entered.
+                let new_chain = Self::ancestor_chain(new_hovered);
+                let old_chain = Self::ancestor_chain(cur_hovered.filter(|node| !node.removed()));
+                for node in old_chain.iter() {
+                    if new_chain.iter().any(|n| n.as_ptr() == node.as_ptr()) {
+                        break;
                     }
+                    Self::dispatch_avm2_roll_event(context, "rollOut", *node, new_hovered);
                 }
 
-                // RollOver on new node.I still
+                // RollOver on new node.
                 new_cursor = MouseCursor::Arrow;
                 if let Some(node) = new_hovered {
                     new_cursor = node.mouse_cursor();
                     node.handle_clip_event(context, ClipEvent::RollOver);
+                    Self::dispatch_avm2_mouse_event(
+                        context,
+                        "mouseOver",
+                        node,
+                        cur_hovered,
+                        false,
+                        0,
+                    );
+                }
+
+                let entered = new_chain
+                    .iter()
+                    .take_while(|node| !old_chain.iter().any(|n| n.as_ptr() == node.as_ptr()))
+                    .copied()
+                    .collect::<Vec<_>>();
+                for node in entered.into_iter().rev() {
+                    Self::dispatch_avm2_roll_event(context, "rollOver", node, cur_hovered);
                 }
 
                 context.mouse_hovered_object = new_hovered;
@@ -858,6 +990,121 @@ impl Player {
         hover_changed
     }
 
+    /// Collect a display object and all of its ancestors, innermost first.
+    fn ancestor_chain<'gc>(node: Option<DisplayObject<'gc>>) -> Vec<DisplayObject<'gc>> {
+        let mut chain = vec![];
+        let mut node = node;
+        while let Some(n) = node {
+            chain.push(n);
+            node = n.parent();
+        }
+        chain
+    }
+
+    /// Build the data for an AVM2 `MouseEvent` targeted at a display object.
+    fn avm2_mouse_event_data<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: DisplayObject<'gc>,
+        related_object: Option<DisplayObject<'gc>>,
+        button_down: bool,
+        delta: i32,
+    ) -> Avm2EventData<'gc> {
+        let (local_x, local_y) = target.global_to_local(*context.mouse_position);
+        let related_object = match related_object.map(|o| o.object2()) {
+            Some(Avm2Value::Object(o)) => Some(o),
+            _ => None,
+        };
+
+        Avm2EventData::Mouse {
+            local_x: local_x.to_pixels(),
+            local_y: local_y.to_pixels(),
+            related_object,
+            ctrl_key: context.ui.is_key_down(KeyCode::Control),
+            alt_key: context.ui.is_key_down(KeyCode::Alt),
+            shift_key: context.ui.is_key_down(KeyCode::Shift),
+            button_down,
+            delta,
+        }
+    }
+
+    /// Dispatch a bubbling AVM2 `MouseEvent` to a display object, if it has an
+    /// AVM2 side.
+    fn dispatch_avm2_mouse_event<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event_type: &'static str,
+        target: DisplayObject<'gc>,
+        related_object: Option<DisplayObject<'gc>>,
+        button_down: bool,
+        delta: i32,
+    ) {
+        if let Avm2Value::Object(object) = target.object2() {
+            let data =
+                Self::avm2_mouse_event_data(context, target, related_object, button_down, delta);
+            let mut event = Avm2Event::with_data(event_type, data);
+            event.set_bubbles(true);
+
+            Self::dispatch_avm2_event(context, event, object);
+        }
+    }
+
+    /// Dispatch a non-bubbling `rollOut` or `rollOver` event to a display
+    /// object, if it has an AVM2 side.
+    fn dispatch_avm2_roll_event<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event_type: &'static str,
+        target: DisplayObject<'gc>,
+        related_object: Option<DisplayObject<'gc>>,
+    ) {
+        if let Avm2Value::Object(object) = target.object2() {
+            let data = Self::avm2_mouse_event_data(context, target, related_object, false, 0);
+            let event = Avm2Event::with_data(event_type, data);
+
+            Self::dispatch_avm2_event(context, event, object);
+        }
+    }
+
+    /// Dispatch an AVM2 `KeyboardEvent` to the focused object, or to the root
+    /// movie if nothing has focus.
+    fn dispatch_avm2_keyboard_event<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event_type: &'static str,
+        key_code: KeyCode,
+    ) {
+        let target = context
+            .focus_tracker
+            .get()
+            .or_else(|| context.levels.get(&0).copied());
+
+        if let Some(Avm2Value::Object(object)) = target.map(|t| t.object2()) {
+            let char_code = context.ui.last_key_char().unwrap_or_default() as u32;
+            let mut event = Avm2Event::with_data(
+                event_type,
+                Avm2EventData::Keyboard {
+                    char_code,
+                    key_code: key_code as u32,
+                    key_location: 0,
+                    ctrl_key: context.ui.is_key_down(KeyCode::Control),
+                    alt_key: context.ui.is_key_down(KeyCode::Alt),
+                    shift_key: context.ui.is_key_down(KeyCode::Shift),
+                },
+            );
+            event.set_bubbles(true);
+
+            Self::dispatch_avm2_event(context, event, object);
+        }
+    }
+
+    /// Dispatch an AVM2 event, logging any error thrown by its handlers.
+    fn dispatch_avm2_event<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event: Avm2Event<'gc>,
+        target: Avm2Object<'gc>,
+    ) {
+        if let Err(e) = Avm2::dispatch_event(context, event, target) {
+            log::error!("Encountered AVM2 error when dispatching event: {}", e);
+        }
+    }
+
     /// Preload the first movie in the player.
     ///
     /// This should only be called once. Further movie loads should preload the
@@ -895,11 +1142,34 @@ impl Player {
             // want to run frames on
             let levels: Vec<_> = update_context.levels.values().copied().collect();
 
+            // AVM2 display objects are notified of each phase of the frame
+            // with broadcast events, in the order Flash Player runs them.
+            Avm2::broadcast_event(update_context, Avm2Event::new("enterFrame"));
+
             for level in levels {
                 level.run_frame(update_context);
             }
 
+            Avm2::broadcast_event(update_context, Avm2Event::new("frameConstructed"));
+
             update_context.update_sounds();
+
+            // Frame scripts must run before `exitFrame` is broadcast.
+            Self::run_actions(update_context);
+
+            // The root movie is loaded in full before it starts playing, so
+            // its `LoaderInfo` completes once its first frame has run.
+            let root_loader_info = update_context
+                .levels
+                .get(&0)
+                .and_then(|root| root.as_movie_clip())
+                .and_then(|root| root.loader_info())
+                .and_then(|loader_info| loader_info.as_loader_info_object());
+            if let Some(loader_info) = root_loader_info {
+                crate::loader::complete_avm2_load(update_context, loader_info);
+            }
+
+            Avm2::broadcast_event(update_context, Avm2Event::new("exitFrame"));
         });
         self.needs_render = true;
     }
@@ -919,7 +1189,7 @@ impl Player {
         });
 
         let view_bounds = self.view_bounds.clone();
-        self.gc_arena.mutate(|_gc_context, gc_root| {
+        self.gc_arena.mutate(|gc_context, gc_root| {
             let root_data = gc_root.0.read();
             let mut render_context = RenderContext {
                 renderer: renderer.deref_mut(),
@@ -928,10 +1198,11 @@ impl Player {
                 view_bounds,
                 clip_depth_stack: vec![],
                 allow_mask: true,
+                gc_context,
             };
 
             for (_depth, level) in root_data.levels.iter() {
-                level.render(&mut render_context);
+                render_base(*level, &mut render_context);
             }
         });
         transform_stack.pop();
@@ -1205,6 +1476,7 @@ impl Player {
         self.gc_arena.mutate(|gc_context, gc_root| {
             let mut root_data = gc_root.0.write(gc_context);
             let mouse_hovered_object = root_data.mouse_hovered_object;
+            let mouse_pressed_object = root_data.mouse_pressed_object;
             let focus_tracker = root_data.focus_tracker;
             let (
                 levels,
@@ -1235,6 +1507,7 @@ impl Player {
                 gc_context,
                 levels,
                 mouse_hovered_object,
+                mouse_pressed_object,
                 mouse_position,
                 drag_object,
                 stage_size: (stage_width, stage_height),
@@ -1268,8 +1541,9 @@ impl Player {
                 .and_then(|root| root.as_movie_clip())
                 .map(|clip| clip.current_frame());
 
-            // Hovered object may have been updated; copy it back to the GC root.
+            // Hovered and pressed objects may have been updated; copy them back to the GC root.
             root_data.mouse_hovered_object = update_context.mouse_hovered_object;
+            root_data.mouse_pressed_object = update_context.mouse_pressed_object;
 
             ret
         })
@@ -1,13 +1,15 @@
-//! Timer handling for `setInterval` AVM timers.
+//! Timer handling for `setInterval`/`setTimeout` and `flash.utils.Timer`.
 //!
 //! We tick the timers during our normal frame loop for deterministic operation.
 //! The timers are stored in a priority queue, where we check if the nearest timer
 //! is ready to tick each frame.
 //!
-//! TODO: Could we use this for AVM2 timers as well?
+//! Both AVM1 and AVM2 timers share this queue.
 
+use crate::avm1::activation::{Activation as Avm1Activation, ActivationIdentifier};
 use crate::avm1::object::search_prototype;
-use crate::avm1::{Activation, ActivationIdentifier, Object, TObject, Value};
+use crate::avm1::{Object as Avm1Object, TObject, Value as Avm1Value};
+use crate::avm2::{Avm2, Object as Avm2Object, Value as Avm2Value};
 use crate::context::UpdateContext;
 use gc_arena::Collect;
 use std::collections::{binary_heap::PeekMut, BinaryHeap};
@@ -37,99 +39,127 @@ impl<'gc> Timers<'gc> {
             return None;
         }
 
-        let version = context.swf.header().version;
-        let globals = context.avm1.global_object_cell();
-        let level0 = context.levels.get(&0).copied().unwrap();
-
-        let mut activation = Activation::from_nothing(
-            context.reborrow(),
-            ActivationIdentifier::root("[Timer Callback]"),
-            version,
-            globals,
-            level0,
-        );
-
-        // TODO: `this` is undefined for non-method timer callbacks, but our VM
-        // currently doesn't allow `this` to be a Value.
-        let undefined = Value::Undefined.coerce_to_object(&mut activation);
-
         let mut tick_count = 0;
-        let cur_time = activation.context.timers.cur_time;
+        let cur_time = context.timers.cur_time;
 
         // We have to be careful because the timer list can be mutated while updating;
         // a timer callback could add more timers, clear timers, etc.
-        while activation
-            .context
+        while context
             .timers
             .peek()
             .map(|timer| timer.tick_time)
             .unwrap_or(cur_time)
             < cur_time
         {
-            let timer = activation.context.timers.peek().unwrap();
+            let timer = context.timers.peek().unwrap();
 
             // TODO: This is only really necessary because BinaryHeap lacks `remove` or `retain` on stable.
             // We can remove the timers straight away in `clearInterval` once this is stable.
             if !timer.is_alive.get() {
-                activation.context.timers.pop();
+                context.timers.pop();
                 continue;
             }
 
+            // If an earlier callback used up this update's execution time,
+            // every further callback would be terminated before doing
+            // anything. Leave the remaining timers due so that they fire on
+            // the next update instead of being silently skipped.
+            if context.update_start.elapsed() >= context.max_execution_duration {
+                break;
+            }
+
             tick_count += 1;
             // SANITY: Only allow so many ticks per timer per update.
             if tick_count > Self::MAX_TICKS {
                 // Reset our time to a little bit before the nearest timer.
-                let next_time = activation.context.timers.peek_mut().unwrap().tick_time;
-                activation.context.timers.cur_time = next_time.wrapping_sub(100);
+                let next_time = context.timers.peek_mut().unwrap().tick_time;
+                context.timers.cur_time = next_time.wrapping_sub(100);
                 break;
             }
 
-            // TODO: Can we avoid these clones?
-            let params = timer.params.clone();
+            // TODO: Can we avoid this clone?
             let callback = timer.callback.clone();
 
-            let callback = match callback {
-                TimerCallback::Function(f) => Some((undefined, None, f)),
-                TimerCallback::Method { this, method_name } => {
-                    // Fetch the callback method from the object.
-                    if let Ok((f, base_proto)) =
-                        search_prototype(Some(this), &method_name, &mut activation, this)
+            match callback {
+                TimerCallback::Avm2Callable { callable, params } => {
+                    if let Err(e) =
+                        Avm2::run_stack_frame_for_callable(callable, None, &params, context)
                     {
-                        let f = f.coerce_to_object(&mut activation);
-                        Some((this, base_proto, f))
-                    } else {
-                        None
+                        log::error!("Unhandled AVM2 exception in timer callback: {}", e);
                     }
                 }
-            };
-
-            if let Some((this, base_proto, function)) = callback {
-                let _ = function.call(
-                    "[Timer Callback]",
-                    &mut activation,
-                    this,
-                    base_proto,
-                    &params,
-                );
+                callback => Self::run_avm1_callback(context, callback),
             }
 
-            let mut timer = activation.context.timers.peek_mut().unwrap();
+            let mut timer = context.timers.peek_mut().unwrap();
             if timer.is_timeout {
                 // Timeouts only fire once.
                 drop(timer);
-                activation.context.timers.pop();
+                context.timers.pop();
             } else {
                 // Reset setInterval timers. `peek_mut` re-sorts the timer in the priority queue.
                 timer.tick_time = timer.tick_time.wrapping_add(timer.interval);
             }
         }
 
-        // Return estimated time until next timer tick.
-        activation
-            .context
+        // Return estimated time until next timer tick. Timers that are
+        // already overdue are due immediately.
+        context
             .timers
             .peek()
-            .map(|timer| (timer.tick_time.wrapping_sub(cur_time)) as f64 / Self::TIMER_SCALE)
+            .map(|timer| timer.tick_time.saturating_sub(cur_time) as f64 / Self::TIMER_SCALE)
+    }
+
+    /// Run the callback of an AVM1 `setInterval`/`setTimeout` timer.
+    fn run_avm1_callback(context: &mut UpdateContext<'_, 'gc, '_>, callback: TimerCallback<'gc>) {
+        let version = context.swf.header().version;
+        let globals = context.avm1.global_object_cell();
+        let level0 = match context.levels.get(&0).copied() {
+            Some(level0) => level0,
+            None => return,
+        };
+
+        let mut activation = Avm1Activation::from_nothing(
+            context.reborrow(),
+            ActivationIdentifier::root("[Timer Callback]"),
+            version,
+            globals,
+            level0,
+        );
+
+        // TODO: `this` is undefined for non-method timer callbacks, but our VM
+        // currently doesn't allow `this` to be a Value.
+        let undefined = Avm1Value::Undefined.coerce_to_object(&mut activation);
+
+        let (callback, params) = match callback {
+            TimerCallback::Avm1Function { func, params } => (Some((undefined, None, func)), params),
+            TimerCallback::Avm1Method {
+                this,
+                method_name,
+                params,
+            } => {
+                // Fetch the callback method from the object.
+                if let Ok((f, base_proto)) =
+                    search_prototype(Some(this), &method_name, &mut activation, this)
+                {
+                    let f = f.coerce_to_object(&mut activation);
+                    (Some((this, base_proto, f)), params)
+                } else {
+                    (None, params)
+                }
+            }
+            TimerCallback::Avm2Callable { .. } => (None, Vec::new()),
+        };
+
+        if let Some((this, base_proto, function)) = callback {
+            let _ = function.call(
+                "[Timer Callback]",
+                &mut activation,
+                this,
+                base_proto,
+                &params,
+            );
+        }
     }
 
     /// The minimum interval we allow for timers.
@@ -160,7 +190,6 @@ impl<'gc> Timers<'gc> {
         &mut self,
         callback: TimerCallback<'gc>,
         interval: i32,
-        params: Vec<Value<'gc>>,
         is_timeout: bool,
     ) -> i32 {
         // SANITY: Set a minimum interval so we don't spam too much.
@@ -171,7 +200,6 @@ impl<'gc> Timers<'gc> {
         let timer = Timer {
             id,
             callback,
-            params,
             tick_time: self.cur_time + interval,
             interval,
             is_timeout,
@@ -218,7 +246,7 @@ unsafe impl<'gc> Collect for Timers<'gc> {
         }
     }
 }
-/// A timer created via `setInterval`/`setTimeout`.
+/// A timer created via `setInterval`/`setTimeout` or `flash.utils.Timer`.
 /// Runs a callback when it ticks.
 #[derive(Debug, Collect)]
 #[collect(no_drop)]
@@ -227,12 +255,8 @@ struct Timer<'gc> {
     id: i32,
 
     /// The callback that this timer runs when it fires.
-    /// A callback is either a function object, or a parent object with a method name.
     callback: TimerCallback<'gc>,
 
-    /// The parameters to pass to the callback function.
-    params: Vec<Value<'gc>>,
-
     /// The time when this timer should fire.
     tick_time: u64,
 
@@ -269,13 +293,27 @@ impl Ord for Timer<'_> {
     }
 }
 
-/// A callback fired by a `setInterval`/`setTimeout` timer.
+/// A callback fired by a timer, along with the parameters to pass to it.
 #[derive(Debug, Collect, Clone)]
 #[collect(no_drop)]
 pub enum TimerCallback<'gc> {
-    Function(Object<'gc>),
-    Method {
-        this: Object<'gc>,
+    /// An AVM1 function object.
+    Avm1Function {
+        func: Avm1Object<'gc>,
+        params: Vec<Avm1Value<'gc>>,
+    },
+
+    /// An AVM1 method, looked up by name on its parent object when the timer
+    /// fires.
+    Avm1Method {
+        this: Avm1Object<'gc>,
         method_name: String,
+        params: Vec<Avm1Value<'gc>>,
+    },
+
+    /// An AVM2 function object, or a method closure bound to its receiver.
+    Avm2Callable {
+        callable: Avm2Object<'gc>,
+        params: Vec<Avm2Value<'gc>>,
     },
 }
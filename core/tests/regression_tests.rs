@@ -13,6 +13,7 @@ use ruffle_core::backend::{
     ui::NullUiBackend,
 };
 use ruffle_core::context::UpdateContext;
+use ruffle_core::events::{KeyCode, MouseWheelDelta, PlayerEvent};
 use ruffle_core::external::Value as ExternalValue;
 use ruffle_core::external::{ExternalInterfaceMethod, ExternalInterfaceProvider};
 use ruffle_core::tag_utils::SwfMovie;
@@ -503,6 +504,23 @@ swf_tests! {
     (as3_movieclip_dispatchevent_cancel, "avm2/movieclip_dispatchevent_cancel", 1),
     (as3_movieclip_dispatchevent_target, "avm2/movieclip_dispatchevent_target", 1),
     (as3_movieclip_dispatchevent_selfadd, "avm2/movieclip_dispatchevent_selfadd", 1),
+    (as3_try_catch, "avm2/try_catch", 1),
+    (as3_type_coercion, "avm2/type_coercion", 1),
+    (as3_vector_int, "avm2/vector_int", 1),
+    (as3_dictionary, "avm2/dictionary", 1),
+    (as3_e4x, "avm2/e4x", 1),
+    (as3_json, "avm2/json", 1),
+    (as3_date, "avm2/date", 1),
+    (as3_frame_events, "avm2/frame_events", 3),
+    (as3_graphics, "avm2/graphics", 1),
+    (as3_geom, "avm2/geom", 1),
+    (as3_timers, "avm2/timers", 10),
+    (as3_domain_memory, "avm2/domain_memory", 1),
+    (as3_reflection, "avm2/reflection", 1),
+    (as3_loader, "avm2/loader", 5),
+    (as3_bytearray, "avm2/bytearray", 1),
+    (as3_string_regexp, "avm2/string_regexp", 1),
+    (as3_stack_trace, "avm2/stack_trace", 1),
 }
 
 // TODO: These tests have some inaccuracies currently, so we use approx_eq to test that numeric values are close enough.
@@ -598,6 +616,83 @@ fn timeout_avm1() -> Result<(), Error> {
     )
 }
 
+#[test]
+fn input_events_avm2() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm2/input_events/test.swf",
+        1,
+        "tests/swfs/avm2/input_events/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            let steps = vec![
+                (
+                    "Move over the button",
+                    vec![PlayerEvent::MouseMove { x: 55.0, y: 55.0 }],
+                ),
+                (
+                    "Click the button",
+                    vec![
+                        PlayerEvent::MouseDown { x: 55.0, y: 55.0 },
+                        PlayerEvent::MouseUp { x: 55.0, y: 55.0 },
+                    ],
+                ),
+                (
+                    "Click the button again",
+                    vec![
+                        PlayerEvent::MouseDown { x: 55.0, y: 55.0 },
+                        PlayerEvent::MouseUp { x: 55.0, y: 55.0 },
+                    ],
+                ),
+                (
+                    "Scroll over the button",
+                    vec![PlayerEvent::MouseWheel {
+                        delta: MouseWheelDelta::Lines(3.0),
+                    }],
+                ),
+                (
+                    "Move over the icon",
+                    vec![PlayerEvent::MouseMove { x: 75.0, y: 75.0 }],
+                ),
+                (
+                    "Move over the mouse-disabled label",
+                    vec![PlayerEvent::MouseMove { x: 120.0, y: 120.0 }],
+                ),
+                (
+                    "Press space",
+                    vec![
+                        PlayerEvent::KeyDown {
+                            key_code: KeyCode::Space,
+                        },
+                        PlayerEvent::KeyUp {
+                            key_code: KeyCode::Space,
+                        },
+                    ],
+                ),
+                (
+                    "Move over the icon again",
+                    vec![PlayerEvent::MouseMove { x: 75.0, y: 75.0 }],
+                ),
+                (
+                    "Move off the button",
+                    vec![PlayerEvent::MouseMove { x: 500.0, y: 350.0 }],
+                ),
+            ];
+
+            for (description, events) in steps {
+                player
+                    .log_backend()
+                    .avm_trace(&format!("// {}", description));
+                for event in events {
+                    player.handle_event(event);
+                }
+            }
+
+            Ok(())
+        },
+    )
+}
+
 /// Wrapper around string slice that makes debug output `{:?}` to print string same way as `{}`.
 /// Used in different `assert*!` macros in combination with `pretty_assertions` crate to make
 /// test failures to show nice diffs.
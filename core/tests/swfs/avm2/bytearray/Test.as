@@ -0,0 +1,105 @@
+﻿package {
+	import flash.display.MovieClip;
+	import flash.errors.EOFError;
+	import flash.errors.IOError;
+	import flash.errors.MemoryError;
+	import flash.utils.ByteArray;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var numbers:ByteArray = new ByteArray();
+			trace("//endian");
+			trace(numbers.endian);
+			numbers.writeInt(0x01020304);
+			numbers.endian = "littleEndian";
+			numbers.writeShort(-2);
+			trace("//length, position");
+			trace(numbers.length + ", " + numbers.position);
+			trace("//bytes");
+			trace(numbers[0] + ", " + numbers[3] + ", " + numbers[4] + ", " + numbers[5]);
+			numbers.position = 0;
+			trace("//readUnsignedInt(), readShort() (little endian)");
+			trace(numbers.readUnsignedInt());
+			trace(numbers.readShort());
+			numbers.endian = "bigEndian";
+			numbers.position = 0;
+			trace("//readUnsignedInt(), readUnsignedShort() (big endian)");
+			trace(numbers.readUnsignedInt());
+			trace(numbers.readUnsignedShort());
+			numbers.endian = "littleEndian";
+			numbers.writeDouble(1.5);
+			trace("//writeDouble(1.5) (little endian)");
+			trace(numbers[6] + ", " + numbers[12] + ", " + numbers[13]);
+			numbers.position = 6;
+			trace(numbers.readDouble());
+			trace("//reading past the end");
+			try {
+				numbers.readInt();
+			} catch (e:EOFError) {
+				trace(e.errorID);
+			}
+			trace("//length = 0xFFFFFFFF");
+			try {
+				numbers.length = 0xFFFFFFFF;
+			} catch (e:MemoryError) {
+				trace(e.errorID);
+			}
+			trace(numbers.length);
+
+			var text:ByteArray = new ByteArray();
+			text.writeUTF("héllo");
+			trace("//writeUTF()");
+			trace(text.length);
+			text.position = 0;
+			trace(text.readUnsignedShort());
+			text.position = 0;
+			trace("//readUTF()");
+			trace(text.readUTF());
+			trace(text.bytesAvailable);
+			text.writeUTFBytes("abc");
+			text.position = 8;
+			trace("//readUTFBytes()");
+			trace(text.readUTFBytes(3));
+
+			var compressed:ByteArray = new ByteArray();
+			compressed.writeUTFBytes("hello hello hello hello hello");
+			compressed.compress();
+			trace("//compress()");
+			trace(compressed.length < 29);
+			trace(compressed.position == compressed.length);
+			compressed.uncompress();
+			trace("//uncompress()");
+			trace(compressed.length + ", " + compressed.position);
+			trace(compressed.readUTFBytes(compressed.length));
+			compressed.compress("deflate");
+			compressed.uncompress("deflate");
+			trace("//compress(\"deflate\"), uncompress(\"deflate\")");
+			trace(compressed.length + ", " + compressed.position);
+			trace(compressed.readUTFBytes(compressed.length));
+			var invalid:ByteArray = new ByteArray();
+			invalid.writeUTFBytes("not compressed");
+			trace("//uncompress() of invalid data");
+			try {
+				invalid.uncompress();
+			} catch (e:IOError) {
+				trace(e.errorID);
+			}
+			trace(invalid.length);
+
+			var objects:ByteArray = new ByteArray();
+			objects.writeObject({a: 1, b: "two", c: [1, 2, 3], d: true});
+			objects.writeObject(3.5);
+			objects.writeObject("str");
+			objects.position = 0;
+			var object:Object = objects.readObject();
+			trace("//readObject()");
+			trace(object.a);
+			trace(object.b);
+			trace(object.c.length + ", " + object.c[2]);
+			trace(object.d);
+			trace(objects.readObject());
+			trace(objects.readObject());
+			trace(objects.bytesAvailable);
+		}
+	}
+}
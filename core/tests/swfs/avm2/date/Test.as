@@ -0,0 +1,103 @@
+﻿package {
+	public class Test {
+	}
+}
+
+import flash.utils.ByteArray;
+
+var local:Date = new Date(2001, 1, 3, 4, 5, 6, 7);
+trace("//local.getTime()");
+trace(local.getTime());
+trace("//local.getFullYear()");
+trace(local.getFullYear());
+trace("//local.getMonth()");
+trace(local.getMonth());
+trace("//local.getDate()");
+trace(local.getDate());
+trace("//local.getDay()");
+trace(local.getDay());
+trace("//local.getHours()");
+trace(local.getHours());
+trace("//local.getMilliseconds()");
+trace(local.getMilliseconds());
+trace("//local.getUTCDate()");
+trace(local.getUTCDate());
+trace("//local.getUTCDay()");
+trace(local.getUTCDay());
+trace("//local.getUTCHours()");
+trace(local.getUTCHours());
+trace("//local.getUTCMinutes()");
+trace(local.getUTCMinutes());
+trace("//local.getTimezoneOffset()");
+trace(local.getTimezoneOffset());
+trace("//local.fullYear");
+trace(local.fullYear);
+
+trace("//local.toString()");
+trace(local.toString());
+trace("//local.toUTCString()");
+trace(local.toUTCString());
+trace("//local.toDateString()");
+trace(local.toDateString());
+trace("//local.toTimeString()");
+trace(local.toTimeString());
+
+trace("//Date.UTC(2001, 1, 3)");
+trace(Date.UTC(2001, 1, 3));
+trace("//Date.parse(local.toString()) == local.time - 7");
+trace(Date.parse(local.toString()) == local.time - 7);
+trace("//Date.parse(\"02/03/2001\") == new Date(2001, 1, 3).time");
+trace(Date.parse("02/03/2001") == new Date(2001, 1, 3).time);
+trace("//Date.parse(\"2001/02/03\") == new Date(2001, 1, 3).time");
+trace(Date.parse("2001/02/03") == new Date(2001, 1, 3).time);
+trace("//new Date(99, 0, 1).getFullYear()");
+trace(new Date(99, 0, 1).getFullYear());
+trace("//new Date(0).getUTCFullYear()");
+trace(new Date(0).getUTCFullYear());
+
+var utc:Date = new Date(Date.UTC(2000, 0, 31));
+utc.setUTCMonth(1);
+trace("//utc.setUTCMonth(1)");
+trace(utc.getUTCMonth());
+trace(utc.getUTCDate());
+utc.setUTCHours(25);
+trace("//utc.setUTCHours(25)");
+trace(utc.getUTCDate());
+trace(utc.getUTCHours());
+utc.month = 11;
+trace("//utc.month = 11");
+trace(utc.month);
+utc.time = 0;
+trace("//utc.time = 0");
+trace(utc.getUTCFullYear());
+
+var bad:Date = new Date(NaN);
+trace("//bad.getTime()");
+trace(bad.getTime());
+trace("//bad.getFullYear()");
+trace(bad.getFullYear());
+trace("//bad");
+trace(bad);
+bad.setFullYear(2010);
+trace("//bad.setFullYear(2010)");
+trace(bad.getFullYear());
+trace(bad.getMonth());
+trace(bad.getDate());
+
+var shared:Object = {name: "shared"};
+var bytes:ByteArray = new ByteArray();
+bytes.writeObject([local, shared, shared, local]);
+bytes.position = 0;
+var copy:Array = bytes.readObject();
+trace("//copy.length");
+trace(copy.length);
+trace("//copy[0] is Date");
+trace(copy[0] is Date);
+trace("//copy[0].time == local.time");
+trace(copy[0].time == local.time);
+trace("//copy[1] === copy[2]");
+trace(copy[1] === copy[2]);
+trace("//copy[2].name");
+trace(copy[2].name);
+trace("//copy[3].time == local.time");
+trace(copy[3].time == local.time);
@@ -0,0 +1,110 @@
+﻿package {
+	public class Test {
+	}
+}
+
+import flash.utils.Dictionary;
+
+class Key {
+	public var name:String;
+
+	public function Key(name:String) {
+		this.name = name;
+	}
+
+	public function toString():String {
+		return "Key " + this.name;
+	}
+}
+
+function sortedKeys(dictionary:Dictionary):Array {
+	var keys:Array = [];
+	for (var key in dictionary) {
+		keys.push("" + key);
+	}
+	keys.sort();
+	return keys;
+}
+
+trace("//var dict:Dictionary = new Dictionary()");
+var dict:Dictionary = new Dictionary();
+var a:Key = new Key("a");
+var b:Key = new Key("b");
+var c:Key = new Key("c");
+trace("//dict[a] = 1, dict[b] = 2, dict[c] = 3");
+dict[a] = 1;
+dict[b] = 2;
+dict[c] = 3;
+trace("//dict[a]");
+trace(dict[a]);
+trace("//dict[new Key(\"a\")]");
+trace(dict[new Key("a")]);
+trace("//dict[\"Key a\"]");
+trace(dict["Key a"]);
+trace("//a in dict");
+trace(a in dict);
+trace("//new Key(\"a\") in dict");
+trace(new Key("a") in dict);
+trace("//sortedKeys(dict)");
+trace(sortedKeys(dict));
+
+trace("//dict[a] = 10");
+dict[a] = 10;
+trace(dict[a]);
+trace("//sortedKeys(dict)");
+trace(sortedKeys(dict));
+trace("//for each (var value in dict)");
+var total = 0;
+for each (var value in dict) {
+	total += value;
+}
+trace(total);
+
+trace("//dict[sortedKeys] = \"a function\"");
+dict[sortedKeys] = "a function";
+trace(dict[sortedKeys]);
+trace("//delete dict[sortedKeys]");
+trace(delete dict[sortedKeys]);
+trace("//sortedKeys in dict");
+trace(sortedKeys in dict);
+
+trace("//delete dict[b]");
+trace(delete dict[b]);
+trace("//b in dict");
+trace(b in dict);
+trace("//dict[b]");
+trace(dict[b]);
+trace("//sortedKeys(dict)");
+trace(sortedKeys(dict));
+trace("//dict[b] = 20");
+dict[b] = 20;
+trace("//sortedKeys(dict)");
+trace(sortedKeys(dict));
+
+trace("//delete every key while enumerating");
+var visited = 0;
+for (var key in dict) {
+	visited++;
+	delete dict[key];
+}
+trace(visited);
+trace("//sortedKeys(dict).length");
+trace(sortedKeys(dict).length);
+trace("//a in dict");
+trace(a in dict);
+
+trace("//var names:Dictionary = new Dictionary(true)");
+var names:Dictionary = new Dictionary(true);
+names["one"] = 1;
+names[2] = "two";
+names[a] = "an object";
+trace("//names[\"one\"]");
+trace(names["one"]);
+trace("//names[2]");
+trace(names[2]);
+trace("//names[a]");
+trace(names[a]);
+trace("//\"one\" in names");
+trace("one" in names);
+trace("//sortedKeys(names)");
+trace(sortedKeys(names));
@@ -0,0 +1,106 @@
+﻿package {
+	import avm2.intrinsics.memory.lf32;
+	import avm2.intrinsics.memory.lf64;
+	import avm2.intrinsics.memory.li16;
+	import avm2.intrinsics.memory.li32;
+	import avm2.intrinsics.memory.li8;
+	import avm2.intrinsics.memory.sf32;
+	import avm2.intrinsics.memory.sf64;
+	import avm2.intrinsics.memory.si16;
+	import avm2.intrinsics.memory.si32;
+	import avm2.intrinsics.memory.si8;
+	import avm2.intrinsics.memory.sxi1;
+	import avm2.intrinsics.memory.sxi16;
+	import avm2.intrinsics.memory.sxi8;
+	import flash.display.MovieClip;
+	import flash.system.ApplicationDomain;
+	import flash.utils.ByteArray;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			trace("//ApplicationDomain.currentDomain.domainMemory");
+			trace(ApplicationDomain.currentDomain.domainMemory);
+
+			try {
+				li8(0);
+			} catch (e:RangeError) {
+				trace("//li8(0) without domain memory");
+				trace(e.errorID);
+			}
+
+			try {
+				ApplicationDomain.currentDomain.domainMemory = new Object();
+			} catch (e:TypeError) {
+				trace("//domainMemory = new Object()");
+				trace(e.errorID);
+			}
+
+			var memory = new ByteArray();
+			memory.length = 1024;
+			ApplicationDomain.currentDomain.domainMemory = memory;
+			trace("//ApplicationDomain.currentDomain.domainMemory === memory");
+			trace(ApplicationDomain.currentDomain.domainMemory === memory);
+
+			trace("//si8(0x1FF, 0)");
+			si8(0x1FF, 0);
+			trace(li8(0) + ", " + memory[0]);
+
+			trace("//si16(0x12345, 2)");
+			si16(0x12345, 2);
+			trace(li16(2) + ", " + li8(2) + ", " + li8(3));
+
+			trace("//si32(-2, 4)");
+			si32(-2, 4);
+			trace(li32(4) + ", " + li16(4) + ", " + li8(7));
+
+			trace("//sf32(1.5, 8)");
+			sf32(1.5, 8);
+			trace(lf32(8));
+
+			trace("//sf32(0.1, 8)");
+			sf32(0.1, 8);
+			trace(lf32(8));
+
+			trace("//sf64(Math.PI, 16)");
+			sf64(Math.PI, 16);
+			trace(lf64(16));
+
+			trace("//memory.writeByte(42) at position 32");
+			memory.position = 32;
+			memory.writeByte(42);
+			trace(li8(32));
+
+			trace("//li8(1023)");
+			trace(li8(1023));
+
+			try {
+				li32(1021);
+			} catch (e:RangeError) {
+				trace("//li32(1021)");
+				trace(e.errorID);
+			}
+
+			try {
+				si8(1, -1);
+			} catch (e:RangeError) {
+				trace("//si8(1, -1)");
+				trace(e.errorID);
+			}
+
+			trace("//sxi1(1), sxi1(2)");
+			trace(sxi1(1) + ", " + sxi1(2));
+			trace("//sxi8(0xFF), sxi8(0x7F)");
+			trace(sxi8(0xFF) + ", " + sxi8(0x7F));
+			trace("//sxi16(0x8000), sxi16(0x17FFF)");
+			trace(sxi16(0x8000) + ", " + sxi16(0x17FFF));
+
+			ApplicationDomain.currentDomain.domainMemory = null;
+			try {
+				li8(0);
+			} catch (e:RangeError) {
+				trace("//li8(0) after domainMemory = null");
+				trace(e.errorID);
+			}
+		}
+	}
+}
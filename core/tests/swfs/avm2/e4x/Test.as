@@ -0,0 +1,90 @@
+﻿package {
+	public class Test {
+	}
+}
+
+function namespaced():void {
+	var feed:XML = <feed xmlns="http://example.com/feed"><entry>a</entry><entry>b</entry></feed>;
+	var ns:Namespace = new Namespace("http://example.com/feed");
+
+	trace("//feed.entry.length()");
+	trace(feed.entry.length());
+	trace("//feed.ns::entry.length()");
+	trace(feed.ns::entry.length());
+
+	default xml namespace = "http://example.com/feed";
+
+	trace("//feed.entry.length() (after default xml namespace)");
+	trace(feed.entry.length());
+	trace("//feed.entry[1]");
+	trace(feed.entry[1]);
+}
+
+function namespacedLate():void {
+	var feed:XML = <feed xmlns="http://example.com/feed"><entry>c</entry></feed>;
+
+	default xml namespace = new Namespace("http://example.com/feed");
+
+	trace("//feed.entry (default xml namespace from a Namespace)");
+	trace(feed.entry);
+}
+
+var xml:XML = <root>
+	<item id="1"><label>one</label><price>10</price></item>
+	<item id="2"><label>two</label><price>25</price></item>
+	<item id="3"><label>three</label><price>40</price></item>
+	<group><item id="4"><label>four</label><price>5</price></item></group>
+</root>;
+
+trace("//xml.item.length()");
+trace(xml.item.length());
+trace("//xml.item.(@id == 3).label");
+trace(xml.item.(@id == 3).label);
+trace("//xml.item.(price > 20).label");
+trace(xml.item.(price > 20).label);
+trace("//xml.item.(@id == 5).length()");
+trace(xml.item.(@id == 5).length());
+
+trace("//xml..item.length()");
+trace(xml..item.length());
+trace("//xml..label[3]");
+trace(xml..label[3]);
+trace("//xml..@id");
+trace(xml..@id);
+trace("//xml..@id.length()");
+trace(xml..@id.length());
+
+trace("//xml.item[1].@id");
+trace(xml.item[1].@id);
+trace("//xml.item.@id");
+trace(xml.item.@id);
+trace("//xml.group.item.@id");
+trace(xml.group.item.@id);
+trace("//xml.item.@missing.length()");
+trace(xml.item.@missing.length());
+
+var attr:String = "id";
+trace("//xml.item[0].@[attr]");
+trace(xml.item[0].@[attr]);
+trace("//typeof xml.item.@id");
+trace(typeof xml.item.@id);
+
+var total:Number = 0;
+for each (var item:XML in xml.item) {
+	total += Number(item.price);
+}
+trace("//total of xml.item.price");
+trace(total);
+
+xml.item[0].@id = 10;
+trace("//xml.item[0].@id = 10");
+trace(xml.item[0].@id);
+trace("//xml.item.(@id == 10).label");
+trace(xml.item.(@id == 10).label);
+
+delete xml.group;
+trace("//xml..item.length() (after delete xml.group)");
+trace(xml..item.length());
+
+namespaced();
+namespacedLate();
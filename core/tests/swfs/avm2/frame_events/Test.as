@@ -0,0 +1,78 @@
+﻿package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.events.Event;
+
+	public class Test extends MovieClip {
+		var frame:int = 1;
+
+		public function Test() {
+			trace("constructor");
+
+			this.addEventListener(Event.ENTER_FRAME, this.onEnterFrame);
+			this.addEventListener(Event.FRAME_CONSTRUCTED, this.onFrameConstructed);
+			this.addEventListener(Event.EXIT_FRAME, this.onExitFrame);
+			this.addEventListener(Event.ADDED, this.onRootAdded);
+
+			var child = new Sprite();
+			child.name = "child";
+			child.addEventListener(Event.ADDED, this.onDisplayListEvent);
+			child.addEventListener(Event.ADDED_TO_STAGE, this.onDisplayListEvent);
+
+			trace("//this.addChild(child)");
+			this.addChild(child);
+
+			this.removeEventListener(Event.ADDED, this.onRootAdded);
+
+			var outer = new Sprite();
+			outer.name = "outer";
+			var inner = new Sprite();
+			inner.name = "inner";
+			for each (var obj in [outer, inner]) {
+				obj.addEventListener(Event.ADDED, this.onDisplayListEvent);
+				obj.addEventListener(Event.ADDED_TO_STAGE, this.onDisplayListEvent);
+				obj.addEventListener(Event.REMOVED, this.onDisplayListEvent);
+				obj.addEventListener(Event.REMOVED_FROM_STAGE, this.onDisplayListEvent);
+			}
+
+			trace("//outer.addChild(inner)");
+			outer.addChild(inner);
+
+			trace("//this.addChild(outer)");
+			this.addChild(outer);
+
+			trace("//this.removeChild(outer)");
+			this.removeChild(outer);
+
+			trace("//inner.parent.name");
+			trace(inner.parent.name);
+		}
+
+		function onDisplayListEvent(e:Event) {
+			trace(e.currentTarget.name + ": " + e.type + " (target: " + e.target.name + ")");
+		}
+
+		function onRootAdded(e:Event) {
+			trace("root: " + e.type + " (target: " + e.target.name + ", currentTarget is root: " + (e.currentTarget === this) + ")");
+		}
+
+		function onEnterFrame(e:Event) {
+			this.frame++;
+			trace("enterFrame " + this.frame);
+		}
+
+		function onFrameConstructed(e:Event) {
+			trace("frameConstructed " + this.frame);
+		}
+
+		function onExitFrame(e:Event) {
+			trace("exitFrame " + this.frame);
+
+			if (this.frame == 2) {
+				this.removeEventListener(Event.ENTER_FRAME, this.onEnterFrame);
+				this.removeEventListener(Event.FRAME_CONSTRUCTED, this.onFrameConstructed);
+				this.removeEventListener(Event.EXIT_FRAME, this.onExitFrame);
+			}
+		}
+	}
+}
@@ -0,0 +1,159 @@
+﻿package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.geom.ColorTransform;
+	import flash.geom.Matrix;
+	import flash.geom.Point;
+	import flash.geom.Rectangle;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			trace("//new Point()");
+			trace(new Point());
+			var p = new Point(3, 4);
+			trace("//new Point(3, 4)");
+			trace(p);
+			trace("//p.length");
+			trace(p.length);
+			trace("//p.add(new Point(1, 2))");
+			trace(p.add(new Point(1, 2)));
+			trace("//p.subtract(new Point(1, 2))");
+			trace(p.subtract(new Point(1, 2)));
+			trace("//p.clone().equals(p)");
+			trace(p.clone().equals(p));
+			trace("//p.equals(new Point(4, 3))");
+			trace(p.equals(new Point(4, 3)));
+			trace("//p.normalize(10)");
+			p.normalize(10);
+			trace(p);
+			trace("//p.offset(1, 1)");
+			p.offset(1, 1);
+			trace(p);
+			trace("//Point.distance(new Point(0, 0), new Point(6, 8))");
+			trace(Point.distance(new Point(0, 0), new Point(6, 8)));
+			trace("//Point.interpolate(new Point(10, 10), new Point(0, 0), 0.25)");
+			trace(Point.interpolate(new Point(10, 10), new Point(0, 0), 0.25));
+			trace("//Point.polar(2, 0)");
+			trace(Point.polar(2, 0));
+
+			var r = new Rectangle(10, 20, 30, 40);
+			trace("//new Rectangle(10, 20, 30, 40)");
+			trace(r);
+			trace("//r.right, r.bottom");
+			trace(r.right + ", " + r.bottom);
+			trace("//r.topLeft, r.bottomRight, r.size");
+			trace(r.topLeft + ", " + r.bottomRight + ", " + r.size);
+			trace("//r.contains(40, 60)");
+			trace(r.contains(40, 60));
+			trace("//r.containsPoint(new Point(39, 59))");
+			trace(r.containsPoint(new Point(39, 59)));
+			trace("//r.containsRect(new Rectangle(15, 25, 5, 5))");
+			trace(r.containsRect(new Rectangle(15, 25, 5, 5)));
+			trace("//r.intersects(new Rectangle(30, 50, 20, 20))");
+			trace(r.intersects(new Rectangle(30, 50, 20, 20)));
+			trace("//r.intersection(new Rectangle(30, 50, 20, 20))");
+			trace(r.intersection(new Rectangle(30, 50, 20, 20)));
+			trace("//r.intersection(new Rectangle(100, 100, 5, 5))");
+			trace(r.intersection(new Rectangle(100, 100, 5, 5)));
+			trace("//r.union(new Rectangle(0, 0, 5, 5))");
+			trace(r.union(new Rectangle(0, 0, 5, 5)));
+			trace("//r.union(new Rectangle())");
+			trace(r.union(new Rectangle()));
+			trace("//r.inflate(5, 5)");
+			r.inflate(5, 5);
+			trace(r);
+			trace("//r.left = 0");
+			r.left = 0;
+			trace(r);
+			trace("//r.offset(10, 10)");
+			r.offset(10, 10);
+			trace(r);
+			trace("//new Rectangle(0, 0, -1, 5).isEmpty()");
+			trace(new Rectangle(0, 0, -1, 5).isEmpty());
+			trace("//r.setEmpty()");
+			r.setEmpty();
+			trace(r + " " + r.isEmpty());
+
+			var m = new Matrix();
+			trace("//new Matrix()");
+			trace(m);
+			trace("//m.translate(10, 20)");
+			m.translate(10, 20);
+			trace(m);
+			trace("//m.scale(2, 3)");
+			m.scale(2, 3);
+			trace(m);
+			trace("//m.transformPoint(new Point(1, 1))");
+			trace(m.transformPoint(new Point(1, 1)));
+			trace("//m.deltaTransformPoint(new Point(1, 1))");
+			trace(m.deltaTransformPoint(new Point(1, 1)));
+			trace("//m.concat(new Matrix(1, 0, 0, 1, 5, 5))");
+			m.concat(new Matrix(1, 0, 0, 1, 5, 5));
+			trace(m);
+			trace("//m.invert() on (a=2, b=0, c=0, d=4, tx=20, ty=60)");
+			m.setTo(2, 0, 0, 4, 20, 60);
+			m.invert();
+			trace(m);
+			trace("//m.createBox(2, 3, 0, 10, 20)");
+			m.createBox(2, 3, 0, 10, 20);
+			trace(m);
+			trace("//m.identity()");
+			m.identity();
+			trace(m);
+
+			var ct = new ColorTransform();
+			trace("//new ColorTransform()");
+			trace(ct);
+			trace("//ct.color = 0xFF8000");
+			ct.color = 0xFF8000;
+			trace(ct);
+			trace("//ct.color");
+			trace(ct.color);
+			trace("//concat of (0.5, 0.5, 0.5, 1, 20, 20, 20, 0) and (2, 2, 2, 1, 10, 10, 10, 0)");
+			ct = new ColorTransform(0.5, 0.5, 0.5, 1, 20, 20, 20, 0);
+			ct.concat(new ColorTransform(2, 2, 2, 1, 10, 10, 10, 0));
+			trace(ct);
+
+			var parent = new Sprite();
+			parent.x = 100;
+			parent.y = 50;
+			parent.scaleX = 2;
+			parent.scaleY = 2;
+			this.addChild(parent);
+			var child = new Sprite();
+			child.x = 10;
+			child.y = 10;
+			child.graphics.beginFill(0xFF0000);
+			child.graphics.drawRect(0, 0, 20, 10);
+			child.graphics.endFill();
+			parent.addChild(child);
+
+			trace("//parent.transform.matrix");
+			trace(parent.transform.matrix);
+			trace("//child.transform.concatenatedMatrix");
+			trace(child.transform.concatenatedMatrix);
+			trace("//child.localToGlobal(new Point(5, 5))");
+			trace(child.localToGlobal(new Point(5, 5)));
+			trace("//child.globalToLocal(new Point(130, 80))");
+			trace(child.globalToLocal(new Point(130, 80)));
+			trace("//child.getBounds(child)");
+			trace(child.getBounds(child));
+			trace("//child.getBounds(parent)");
+			trace(child.getBounds(parent));
+			trace("//child.getBounds(this)");
+			trace(child.getBounds(this));
+			trace("//parent.getRect(this)");
+			trace(parent.getRect(this));
+
+			trace("//child.transform.matrix = new Matrix(1, 0, 0, 1, 5, 6)");
+			child.transform.matrix = new Matrix(1, 0, 0, 1, 5, 6);
+			trace(child.x + ", " + child.y);
+			trace("//child.transform.colorTransform = new ColorTransform(1, 1, 1, 0.5)");
+			child.transform.colorTransform = new ColorTransform(1, 1, 1, 0.5);
+			trace(child.alpha);
+			trace("//parent.transform.colorTransform = new ColorTransform(0.5)");
+			parent.transform.colorTransform = new ColorTransform(0.5);
+			trace(child.transform.concatenatedColorTransform);
+		}
+	}
+}
@@ -0,0 +1,150 @@
+﻿package {
+	import flash.display.Graphics;
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.display.Sprite;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var shape = new Shape();
+			this.addChild(shape);
+
+			trace("//shape.graphics is Graphics");
+			trace(shape.graphics is Graphics);
+			trace("//shape.graphics === shape.graphics");
+			trace(shape.graphics === shape.graphics);
+			trace("//empty shape size");
+			trace(shape.width + " x " + shape.height);
+
+			try {
+				new Graphics();
+			} catch (e:ArgumentError) {
+				trace("//new Graphics()");
+				trace(e.errorID);
+			}
+
+			trace("//drawRect(10, 20, 100, 50)");
+			shape.graphics.beginFill(0xFF0000);
+			shape.graphics.drawRect(10, 20, 100, 50);
+			shape.graphics.endFill();
+			trace(shape.width + " x " + shape.height);
+			trace("//hitTestPoint(50, 40, true)");
+			trace(shape.hitTestPoint(50, 40, true));
+			trace("//hitTestPoint(5, 5, true)");
+			trace(shape.hitTestPoint(5, 5, true));
+
+			trace("//clear()");
+			shape.graphics.clear();
+			trace(shape.width + " x " + shape.height);
+			trace("//hitTestPoint(50, 40, true)");
+			trace(shape.hitTestPoint(50, 40, true));
+
+			trace("//lineStyle(4) with lineTo(100, 0)");
+			shape.graphics.lineStyle(4);
+			shape.graphics.moveTo(0, 0);
+			shape.graphics.lineTo(100, 0);
+			trace(shape.width + " x " + shape.height);
+			trace("//hitTestPoint(50, 1, true)");
+			trace(shape.hitTestPoint(50, 1, true));
+			trace("//hitTestPoint(50, 10, true)");
+			trace(shape.hitTestPoint(50, 10, true));
+
+			trace("//lineStyle(4) with drawRect(0, 0, 100, 50)");
+			shape.graphics.clear();
+			shape.graphics.lineStyle(4);
+			shape.graphics.drawRect(0, 0, 100, 50);
+			trace(shape.width + " x " + shape.height);
+			trace("//hitTestPoint(50, 25, true) on an unfilled rectangle");
+			trace(shape.hitTestPoint(50, 25, true));
+
+			trace("//lineStyle() with drawRect(0, 0, 100, 50)");
+			shape.graphics.clear();
+			shape.graphics.lineStyle(4);
+			shape.graphics.lineStyle();
+			shape.graphics.beginFill(0x00FF00);
+			shape.graphics.drawRect(0, 0, 100, 50);
+			trace(shape.width + " x " + shape.height);
+
+			trace("//drawCircle(50, 50, 25)");
+			shape.graphics.clear();
+			shape.graphics.beginFill(0x0000FF);
+			shape.graphics.drawCircle(50, 50, 25);
+			shape.graphics.endFill();
+			trace(shape.width + " x " + shape.height);
+			trace("//hitTestPoint(50, 50, true)");
+			trace(shape.hitTestPoint(50, 50, true));
+			trace("//hitTestPoint(27, 27, true)");
+			trace(shape.hitTestPoint(27, 27, true));
+			trace("//hitTestPoint(27, 27, false)");
+			trace(shape.hitTestPoint(27, 27, false));
+
+			trace("//drawEllipse(0, 0, 60, 30)");
+			shape.graphics.clear();
+			shape.graphics.beginFill(0x0000FF);
+			shape.graphics.drawEllipse(0, 0, 60, 30);
+			trace(shape.width + " x " + shape.height);
+
+			trace("//drawRoundRect(0, 0, 80, 40, 20)");
+			shape.graphics.clear();
+			shape.graphics.beginFill(0x0000FF);
+			shape.graphics.drawRoundRect(0, 0, 80, 40, 20);
+			trace(shape.width + " x " + shape.height);
+			trace("//hitTestPoint(1, 1, true)");
+			trace(shape.hitTestPoint(1, 1, true));
+			trace("//hitTestPoint(40, 1, true)");
+			trace(shape.hitTestPoint(40, 1, true));
+
+			trace("//curveTo(50, 0, 50, 50)");
+			shape.graphics.clear();
+			shape.graphics.beginFill(0x0000FF);
+			shape.graphics.moveTo(0, 0);
+			shape.graphics.curveTo(50, 0, 50, 50);
+			shape.graphics.lineTo(0, 50);
+			shape.graphics.lineTo(0, 0);
+			trace(shape.width + " x " + shape.height);
+
+			trace("//drawPath");
+			shape.graphics.clear();
+			shape.graphics.beginFill(0x0000FF);
+			shape.graphics.drawPath(new <int>[1, 2, 2, 2, 2], new <Number>[0, 0, 40, 0, 40, 30, 0, 30, 0, 0]);
+			trace(shape.width + " x " + shape.height);
+			trace("//hitTestPoint(20, 15, true)");
+			trace(shape.hitTestPoint(20, 15, true));
+
+			trace("//drawTriangles");
+			shape.graphics.clear();
+			shape.graphics.beginFill(0x0000FF);
+			shape.graphics.drawTriangles(new <Number>[0, 0, 100, 0, 0, 50]);
+			trace(shape.width + " x " + shape.height);
+			trace("//hitTestPoint(10, 10, true)");
+			trace(shape.hitTestPoint(10, 10, true));
+			trace("//hitTestPoint(90, 40, true)");
+			trace(shape.hitTestPoint(90, 40, true));
+
+			trace("//beginGradientFill");
+			shape.graphics.clear();
+			shape.graphics.beginGradientFill("linear", [0xFF0000, 0x0000FF], [1, 1], [0, 255]);
+			shape.graphics.drawRect(0, 0, 10, 10);
+			trace(shape.width + " x " + shape.height);
+			trace("//hitTestPoint(5, 5, true)");
+			trace(shape.hitTestPoint(5, 5, true));
+
+			trace("//Sprite with a drawing and a child");
+			var sprite = new Sprite();
+			sprite.graphics.beginFill(0xFF0000);
+			sprite.graphics.drawRect(0, 0, 10, 10);
+			var child = new Shape();
+			child.x = 20;
+			child.graphics.beginFill(0xFF0000);
+			child.graphics.drawRect(0, 0, 10, 10);
+			sprite.addChild(child);
+			trace(sprite.width + " x " + sprite.height);
+
+			trace("//MovieClip graphics");
+			var clip = new MovieClip();
+			clip.graphics.beginFill(0xFF0000);
+			clip.graphics.drawRect(0, 0, 15, 25);
+			trace(clip.width + " x " + clip.height);
+		}
+	}
+}
@@ -0,0 +1,91 @@
+﻿package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.events.KeyboardEvent;
+	import flash.events.MouseEvent;
+
+	public class Test extends MovieClip {
+		var button:Sprite;
+
+		public function Test() {
+			this.button = new Sprite();
+			this.button.name = "button";
+			this.button.x = 50;
+			this.button.y = 50;
+			this.button.buttonMode = true;
+			this.button.doubleClickEnabled = true;
+			this.button.graphics.beginFill(0xFF0000);
+			this.button.graphics.drawRect(0, 0, 100, 100);
+			this.button.graphics.endFill();
+			this.addChild(this.button);
+
+			var icon = new Sprite();
+			icon.name = "icon";
+			icon.x = 10;
+			icon.y = 10;
+			icon.graphics.beginFill(0x00FF00);
+			icon.graphics.drawRect(0, 0, 20, 20);
+			icon.graphics.endFill();
+			this.button.addChild(icon);
+
+			var label = new Sprite();
+			label.name = "label";
+			label.x = 60;
+			label.y = 60;
+			label.mouseEnabled = false;
+			label.graphics.beginFill(0x0000FF);
+			label.graphics.drawRect(0, 0, 30, 30);
+			label.graphics.endFill();
+			this.button.addChild(label);
+
+			for each (var type in [MouseEvent.MOUSE_OVER, MouseEvent.MOUSE_OUT, MouseEvent.ROLL_OVER,
+					MouseEvent.ROLL_OUT, MouseEvent.MOUSE_MOVE, MouseEvent.MOUSE_DOWN, MouseEvent.MOUSE_UP,
+					MouseEvent.CLICK, MouseEvent.DOUBLE_CLICK, MouseEvent.MOUSE_WHEEL]) {
+				this.button.addEventListener(type, this.onMouseEvent);
+			}
+
+			icon.addEventListener(MouseEvent.ROLL_OVER, this.onMouseEvent);
+			icon.addEventListener(MouseEvent.ROLL_OUT, this.onMouseEvent);
+
+			this.addEventListener(MouseEvent.CLICK, this.onMouseEvent, true);
+			this.addEventListener(MouseEvent.CLICK, this.onMouseEvent);
+			this.addEventListener(KeyboardEvent.KEY_DOWN, this.onKeyboardEvent);
+			this.addEventListener(KeyboardEvent.KEY_UP, this.onKeyboardEvent);
+		}
+
+		function nameOf(obj) {
+			if (obj === null) {
+				return "null";
+			}
+
+			return obj === this ? "root" : obj.name;
+		}
+
+		function onMouseEvent(e:MouseEvent) {
+			var line = this.nameOf(e.currentTarget) + ": " + e.type + " (target: " + this.nameOf(e.target) + ", phase: " + e.eventPhase + ", local: " + e.localX + "," + e.localY;
+
+			if (e.type == MouseEvent.MOUSE_OVER || e.type == MouseEvent.MOUSE_OUT || e.type == MouseEvent.ROLL_OVER || e.type == MouseEvent.ROLL_OUT) {
+				line += ", related: " + this.nameOf(e.relatedObject);
+			}
+
+			if (e.type == MouseEvent.MOUSE_DOWN || e.type == MouseEvent.MOUSE_MOVE) {
+				line += ", buttonDown: " + e.buttonDown;
+			}
+
+			if (e.type == MouseEvent.MOUSE_WHEEL) {
+				line += ", delta: " + e.delta;
+			}
+
+			trace(line + ")");
+		}
+
+		function onKeyboardEvent(e:KeyboardEvent) {
+			trace(this.nameOf(e.currentTarget) + ": " + e.type + " (target: " + this.nameOf(e.target) + ", keyCode: " + e.keyCode + ")");
+
+			if (e.type == KeyboardEvent.KEY_DOWN) {
+				this.button.mouseChildren = false;
+				trace("button.mouseChildren = " + this.button.mouseChildren);
+			}
+		}
+	}
+}
@@ -0,0 +1,89 @@
+﻿package {
+	public class Test {
+	}
+}
+
+class Thing {
+	public function toJSON(key:String):* {
+		return "custom:" + key;
+	}
+}
+
+function doubleNumbers(key:String, value:*):* {
+	if (typeof value == "number") {
+		return value * 2;
+	}
+
+	return value;
+}
+
+function timesTen(key:String, value:*):* {
+	if (value is Number) {
+		return value * 10;
+	}
+
+	return value;
+}
+
+function dropKey(key:String, value:*):* {
+	if (key == "drop") {
+		return undefined;
+	}
+
+	return value;
+}
+
+trace("//JSON.stringify({a: [1, \"two\", true, null]})");
+trace(JSON.stringify({a: [1, "two", true, null]}));
+trace("//JSON.stringify(\"a\\\"b\\n\")");
+trace(JSON.stringify("a\"b\n"));
+trace("//JSON.stringify([NaN, 1.5])");
+trace(JSON.stringify([NaN, 1.5]));
+trace("//JSON.stringify(undefined)");
+trace(JSON.stringify(undefined));
+trace("//JSON.stringify([1, [2, 3]], null, 2)");
+trace(JSON.stringify([1, [2, 3]], null, 2));
+trace("//JSON.stringify({a: 1, b: 2, c: 3}, [\"c\", \"a\"])");
+trace(JSON.stringify({a: 1, b: 2, c: 3}, ["c", "a"]));
+trace("//JSON.stringify([1, 2], doubleNumbers)");
+trace(JSON.stringify([1, 2], doubleNumbers));
+trace("//JSON.stringify({item: new Thing()})");
+trace(JSON.stringify({item: new Thing()}));
+
+var cycle:Object = {};
+cycle.self = cycle;
+try {
+	JSON.stringify(cycle);
+} catch (e:TypeError) {
+	trace("//JSON.stringify(cycle)");
+	trace(e.errorID);
+}
+
+var parsed:Object = JSON.parse("{\"a\":[1,2,{\"b\":\"c\"}],\"d\":true}");
+trace("//parsed.a.length");
+trace(parsed.a.length);
+trace("//parsed.a[2].b");
+trace(parsed.a[2].b);
+trace("//parsed.d");
+trace(parsed.d);
+trace("//JSON.parse(\"1.5\") + 1");
+trace(JSON.parse("1.5") + 1);
+trace("//JSON.parse(\"null\")");
+trace(JSON.parse("null"));
+
+trace("//JSON.parse(\"[1,2,3]\", timesTen)");
+trace(JSON.parse("[1,2,3]", timesTen));
+
+var revived:Object = JSON.parse("{\"keep\":1,\"drop\":2}", dropKey);
+trace("//revived.keep");
+trace(revived.keep);
+trace("//\"drop\" in revived");
+trace("drop" in revived);
+
+try {
+	JSON.parse("{bad");
+} catch (e:SyntaxError) {
+	trace("//JSON.parse(\"{bad\")");
+	trace(e.errorID);
+	trace(e.name);
+}
@@ -0,0 +1,16 @@
+﻿package {
+	import flash.display.MovieClip;
+	import flash.system.ApplicationDomain;
+
+	public class Child extends MovieClip {
+		public var message;
+
+		public function Child() {
+			trace("//Child()");
+			trace(this.loaderInfo != null);
+			trace("//ApplicationDomain.currentDomain.hasDefinition(\"Test\")");
+			trace(ApplicationDomain.currentDomain.hasDefinition("Test"));
+			this.message = "Hello from Child";
+		}
+	}
+}
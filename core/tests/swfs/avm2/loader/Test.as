@@ -0,0 +1,156 @@
+﻿package {
+	import flash.display.Loader;
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.IOErrorEvent;
+	import flash.events.ProgressEvent;
+	import flash.net.URLRequest;
+	import flash.system.ApplicationDomain;
+	import flash.system.LoaderContext;
+	import flash.utils.ByteArray;
+	import flash.utils.getQualifiedClassName;
+
+	public class Test extends MovieClip {
+		var frame = 1;
+		var childLoader;
+		var missingLoader;
+		var domainLoader;
+		var bytesLoader;
+
+		public function Test() {
+			trace("//loaderInfo != null");
+			trace(this.loaderInfo != null);
+			trace("//root.loaderInfo === loaderInfo");
+			trace(this.root.loaderInfo === this.loaderInfo);
+			trace("//loaderInfo.content === this");
+			trace(this.loaderInfo.content === this);
+			trace("//loaderInfo.contentType");
+			trace(this.loaderInfo.contentType);
+			trace("//loaderInfo.swfVersion, actionScriptVersion, frameRate");
+			trace(this.loaderInfo.swfVersion + ", " + this.loaderInfo.actionScriptVersion + ", " + this.loaderInfo.frameRate);
+			trace("//loaderInfo.width, height");
+			trace(this.loaderInfo.width + ", " + this.loaderInfo.height);
+			trace("//loaderInfo.bytesLoaded == loaderInfo.bytesTotal");
+			trace(this.loaderInfo.bytesLoaded == this.loaderInfo.bytesTotal);
+			this.loaderInfo.addEventListener(Event.COMPLETE, this.onRootComplete);
+
+			this.childLoader = new Loader();
+			this.addChild(this.childLoader);
+			trace("//contentLoaderInfo.loader === childLoader");
+			trace(this.childLoader.contentLoaderInfo.loader === this.childLoader);
+			trace("//childLoader.content");
+			trace(this.childLoader.content);
+
+			this.childLoader.contentLoaderInfo.addEventListener(Event.OPEN, this.onChildEvent);
+			this.childLoader.contentLoaderInfo.addEventListener(ProgressEvent.PROGRESS, this.onChildEvent);
+			this.childLoader.contentLoaderInfo.addEventListener(Event.INIT, this.onChildEvent);
+			this.childLoader.contentLoaderInfo.addEventListener(Event.COMPLETE, this.onChildEvent);
+			this.childLoader.contentLoaderInfo.addEventListener(IOErrorEvent.IO_ERROR, this.onChildEvent);
+			this.childLoader.load(new URLRequest("child.swf"));
+			trace("//childLoader.load(new URLRequest(\"child.swf\"))");
+			trace(this.childLoader.content);
+
+			this.addEventListener(Event.ENTER_FRAME, this.onEnterFrame);
+		}
+
+		function onRootComplete(e) {
+			trace("//loaderInfo complete");
+			trace(e.target === this.loaderInfo);
+		}
+
+		function onChildEvent(e) {
+			trace("//contentLoaderInfo " + e.type);
+
+			if (e.type == ProgressEvent.PROGRESS) {
+				trace(e.bytesLoaded == e.bytesTotal && e.bytesTotal > 0);
+			} else if (e.type == Event.INIT) {
+				trace(getQualifiedClassName(this.childLoader.content));
+				trace(this.childLoader.content.message);
+			} else if (e.type == Event.COMPLETE) {
+				var info = this.childLoader.contentLoaderInfo;
+				var content = this.childLoader.content;
+				trace("//e.target === contentLoaderInfo");
+				trace(e.target === info);
+				trace("//contentLoaderInfo.content === childLoader.content");
+				trace(info.content === content);
+				trace("//content.loaderInfo === contentLoaderInfo");
+				trace(content.loaderInfo === info);
+				trace("//content.parent === childLoader");
+				trace(content.parent === this.childLoader);
+				trace("//childLoader.numChildren");
+				trace(this.childLoader.numChildren);
+				trace("//contentLoaderInfo.contentType");
+				trace(info.contentType);
+				trace("//contentLoaderInfo.bytesLoaded == contentLoaderInfo.bytesTotal");
+				trace(info.bytesLoaded == info.bytesTotal);
+				trace("//contentLoaderInfo.url ends with child.swf");
+				trace(info.url.lastIndexOf("child.swf") == info.url.length - 9);
+				trace("//contentLoaderInfo.swfVersion, actionScriptVersion");
+				trace(info.swfVersion + ", " + info.actionScriptVersion);
+				trace("//contentLoaderInfo.applicationDomain.hasDefinition(\"Child\")");
+				trace(info.applicationDomain.hasDefinition("Child"));
+				trace("//ApplicationDomain.currentDomain.hasDefinition(\"Child\")");
+				trace(ApplicationDomain.currentDomain.hasDefinition("Child"));
+				trace("//content is Child");
+				trace(content is info.applicationDomain.getDefinition("Child"));
+			}
+		}
+
+		function onMissingError(e) {
+			trace("//missingLoader ioError");
+			trace(e.errorID);
+			trace(e.text.indexOf("Error #2035") == 0);
+			trace(this.missingLoader.content);
+		}
+
+		function onMissingComplete(e) {
+			trace("//missingLoader complete");
+		}
+
+		function onDomainComplete(e) {
+			trace("//domainLoader complete");
+			trace(getQualifiedClassName(this.domainLoader.content));
+			trace("//ApplicationDomain.currentDomain.hasDefinition(\"Child\")");
+			trace(ApplicationDomain.currentDomain.hasDefinition("Child"));
+		}
+
+		function onBytesComplete(e) {
+			trace("//bytesLoader complete");
+			trace(this.bytesLoader.contentLoaderInfo.contentType);
+			trace(this.bytesLoader.numChildren);
+		}
+
+		function onEnterFrame(e) {
+			this.frame++;
+
+			if (this.frame == 2) {
+				this.missingLoader = new Loader();
+				this.missingLoader.contentLoaderInfo.addEventListener(IOErrorEvent.IO_ERROR, this.onMissingError);
+				this.missingLoader.contentLoaderInfo.addEventListener(Event.COMPLETE, this.onMissingComplete);
+				this.missingLoader.load(new URLRequest("missing.swf"));
+			} else if (this.frame == 3) {
+				trace("//childLoader.unload()");
+				this.childLoader.unload();
+				trace(this.childLoader.numChildren);
+				trace(this.childLoader.content);
+
+				this.domainLoader = new Loader();
+				this.domainLoader.contentLoaderInfo.addEventListener(Event.COMPLETE, this.onDomainComplete);
+				this.domainLoader.load(new URLRequest("child.swf"), new LoaderContext(false, ApplicationDomain.currentDomain));
+			} else if (this.frame == 4) {
+				var png = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44, 0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x00, 0x00, 0x00, 0x90, 0x77, 0x53, 0xDE, 0x00, 0x00, 0x00, 0x0C, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9C, 0x63, 0xF8, 0xCF, 0xC0, 0x00, 0x00, 0x03, 0x01, 0x01, 0x00, 0xC9, 0xFE, 0x92, 0xEF, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82];
+				var bytes = new ByteArray();
+
+				for (var i = 0; i < png.length; i++) {
+					bytes.writeByte(png[i]);
+				}
+
+				this.bytesLoader = new Loader();
+				this.bytesLoader.contentLoaderInfo.addEventListener(Event.COMPLETE, this.onBytesComplete);
+				this.bytesLoader.loadBytes(bytes);
+			} else if (this.frame == 5) {
+				this.removeEventListener(Event.ENTER_FRAME, this.onEnterFrame);
+			}
+		}
+	}
+}
@@ -0,0 +1,126 @@
+﻿package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.system.ApplicationDomain;
+	import flash.utils.describeType;
+	import flash.utils.getDefinitionByName;
+	import flash.utils.getQualifiedClassName;
+	import flash.utils.getQualifiedSuperclassName;
+
+	public class Test extends MovieClip {
+		public static var instances:int = 0;
+		public var count:int = 0;
+		public var anything;
+		public const LABEL:String = "label";
+		private var hidden:String = "hidden";
+
+		public function Test() {
+			trace("//getQualifiedClassName");
+			trace(getQualifiedClassName(this));
+			trace(getQualifiedClassName(Test));
+			trace(getQualifiedClassName(new Sprite()));
+			trace(getQualifiedClassName(Sprite));
+			trace(getQualifiedClassName(5));
+			trace(getQualifiedClassName(1.5));
+			trace(getQualifiedClassName("s"));
+			trace(getQualifiedClassName(true));
+			trace(getQualifiedClassName([]));
+			trace(getQualifiedClassName({}));
+			trace(getQualifiedClassName(null));
+			trace(getQualifiedClassName(undefined));
+
+			trace("//getQualifiedSuperclassName");
+			trace(getQualifiedSuperclassName(this));
+			trace(getQualifiedSuperclassName(Sprite));
+			trace(getQualifiedSuperclassName(new Object()));
+			trace(getQualifiedSuperclassName(Object));
+
+			trace("//getDefinitionByName");
+			trace(getDefinitionByName("flash.display::Sprite") === Sprite);
+			trace(getDefinitionByName("flash.display.Sprite") === Sprite);
+			trace(getDefinitionByName("Test") === Test);
+			try {
+				getDefinitionByName("flash.display.Nope");
+			} catch (e:ReferenceError) {
+				trace("//getDefinitionByName(\"flash.display.Nope\")");
+				trace(e.errorID);
+			}
+
+			trace("//ApplicationDomain.currentDomain.hasDefinition");
+			trace(ApplicationDomain.currentDomain.hasDefinition("flash.display.Sprite"));
+			trace(ApplicationDomain.currentDomain.hasDefinition("Test"));
+			trace(ApplicationDomain.currentDomain.hasDefinition("Nope"));
+			trace("//ApplicationDomain.currentDomain.getDefinition(\"Test\") === Test");
+			trace(ApplicationDomain.currentDomain.getDefinition("Test") === Test);
+
+			var d = describeType(this);
+			trace("//describeType(this) type attributes");
+			trace(d.@name + ", " + d.@base + ", " + d.@isDynamic + ", " + d.@isFinal + ", " + d.@isStatic);
+			trace("//extendsClass");
+			trace(d.extendsClass.length());
+			trace(d.extendsClass[0].@type);
+			trace(d.extendsClass[1].@type);
+			trace(d.extendsClass[d.extendsClass.length() - 1].@type);
+			trace("//variable count");
+			trace(d.variable.(@name == "count").@type);
+			trace("//variable anything");
+			trace(d.variable.(@name == "anything").@type);
+			trace("//constant LABEL");
+			trace(d.constant.(@name == "LABEL").@type);
+			trace("//private and static variables");
+			trace(d.variable.(@name == "hidden").length());
+			trace(d.variable.(@name == "instances").length());
+			trace("//accessor size");
+			var size = d.accessor.(@name == "size");
+			trace(size.@access + ", " + size.@type + ", " + size.@declaredBy);
+			trace("//accessor readOnly");
+			var readOnly = d.accessor.(@name == "readOnly");
+			trace(readOnly.@access + ", " + readOnly.@type + ", " + readOnly.@declaredBy);
+			trace("//accessor x");
+			trace(d.accessor.(@name == "x").@declaredBy);
+			trace("//method greet");
+			var greet = d.method.(@name == "greet");
+			trace(greet.@returnType + ", " + greet.@declaredBy);
+			trace(greet.parameter.length());
+			trace(greet.parameter[0].@index + ", " + greet.parameter[0].@type + ", " + greet.parameter[0].@optional);
+			trace(greet.parameter[1].@index + ", " + greet.parameter[1].@type + ", " + greet.parameter[1].@optional);
+			trace("//method clear");
+			trace(d.method.(@name == "clear").@returnType);
+			trace("//method addChild");
+			trace(d.method.(@name == "addChild").@declaredBy);
+
+			var c = describeType(Test);
+			trace("//describeType(Test) type attributes");
+			trace(c.@name + ", " + c.@base + ", " + c.@isDynamic + ", " + c.@isFinal + ", " + c.@isStatic);
+			trace("//static variable instances");
+			trace(c.variable.(@name == "instances").@type);
+			trace("//static variable count");
+			trace(c.variable.(@name == "count").length());
+			trace("//factory");
+			trace(c.factory.@type);
+			trace(c.factory.variable.(@name == "count").length());
+
+			var n = describeType(null);
+			trace("//describeType(null)");
+			trace(n.@name + ", " + n.@isDynamic + ", " + n.@isFinal + ", " + n.@isStatic);
+		}
+
+		public function get size():Number {
+			return 1;
+		}
+
+		public function set size(value:Number):void {
+		}
+
+		public function get readOnly():String {
+			return "";
+		}
+
+		public function greet(name:String, times:int = 1):String {
+			return name;
+		}
+
+		public function clear():void {
+		}
+	}
+}
@@ -0,0 +1,37 @@
+﻿package {
+	import flash.display.MovieClip;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			trace("//getStackTrace() of an error thrown two calls deep");
+			try {
+				this.outer();
+			} catch (e:Error) {
+				trace(e.getStackTrace());
+			}
+
+			trace("//getStackTrace() of an error that was caught and thrown again");
+			try {
+				this.rethrow();
+			} catch (e:Error) {
+				trace(e.getStackTrace());
+			}
+		}
+
+		public function outer():void {
+			this.inner();
+		}
+
+		public function inner():void {
+			throw new Error("boom");
+		}
+
+		public function rethrow():void {
+			try {
+				this.outer();
+			} catch (e:Error) {
+				throw e;
+			}
+		}
+	}
+}
@@ -0,0 +1,62 @@
+﻿package {
+	import flash.display.MovieClip;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			trace("//replace() with $1, $2");
+			trace("john smith".replace(/(\w+)\s(\w+)/, "$2, $1"));
+			trace("//replace() with $&");
+			trace("abc".replace(/b/, "[$&]"));
+			trace("//replace() with $` and $'");
+			trace("abc".replace(/b/, "[$`|$']"));
+			trace("//replace() with $$");
+			trace("a-b-c".replace(/-/g, "$$"));
+			trace("//replace() without and with g");
+			trace("aaa".replace(/a/, "b"));
+			trace("aaa".replace(/a/g, "b"));
+			trace("//replace() with a function");
+			trace("1 2 3".replace(/\d/g, function(match, index, input) {
+				return String(int(match) * 2);
+			}));
+			trace("//replace() with a function and groups");
+			trace("a1b2".replace(/([a-z])(\d)/g, function(match, letter, digit, index, input) {
+				return digit + letter + index;
+			}));
+
+			trace("//split() with a RegExp");
+			trace("a1b22c333d".split(/\d+/));
+			trace("a, b ,c".split(/\s*,\s*/));
+			trace("//split() with a RegExp with groups");
+			trace("a1b2c".split(/(\d)/));
+			trace("//split() with a RegExp and a limit");
+			trace("a1b2c3".split(/\d/, 2));
+
+			trace("//match() with g");
+			trace("a1b22c333".match(/\d+/g));
+			trace("A1a2".match(/a\d/gi));
+			trace("//match() without g");
+			var groups:Array = "x12y".match(/(\d)(\d)/);
+			trace(groups[0] + ", " + groups[1] + ", " + groups[2] + ", " + groups.index);
+
+			trace("//lastIndex with exec()");
+			var global:RegExp = /o/g;
+			var text:String = "foo boo";
+			trace(global.lastIndex);
+			trace(global.exec(text).index + ", " + global.lastIndex);
+			trace(global.exec(text).index + ", " + global.lastIndex);
+			trace(global.exec(text).index + ", " + global.lastIndex);
+			trace(global.exec(text).index + ", " + global.lastIndex);
+			trace(global.exec(text));
+			trace(global.lastIndex);
+			trace("//setting lastIndex");
+			global.lastIndex = 4;
+			trace(global.exec(text).index + ", " + global.lastIndex);
+			trace("//lastIndex with test()");
+			trace(global.test(text) + ", " + global.lastIndex);
+			trace(global.test(text) + ", " + global.lastIndex);
+			trace("//lastIndex without g");
+			var single:RegExp = /o/;
+			trace(single.exec(text).index + ", " + single.lastIndex);
+		}
+	}
+}
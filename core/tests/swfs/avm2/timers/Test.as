@@ -0,0 +1,84 @@
+﻿package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.TimerEvent;
+	import flash.utils.Timer;
+	import flash.utils.clearInterval;
+	import flash.utils.clearTimeout;
+	import flash.utils.getTimer;
+	import flash.utils.setInterval;
+	import flash.utils.setTimeout;
+
+	public class Test extends MovieClip {
+		var frame = 1;
+		var timer;
+		var intervalId;
+		var intervalTicks = 0;
+		var restarted = false;
+
+		public function Test() {
+			trace("//typeof getTimer()");
+			trace(typeof getTimer());
+
+			try {
+				new Timer(-1);
+			} catch (e:RangeError) {
+				trace("//new Timer(-1)");
+				trace(e.errorID);
+			}
+
+			this.timer = new Timer(100, 3);
+			trace("//new Timer(100, 3)");
+			trace(this.timer.delay + ", " + this.timer.repeatCount + ", " + this.timer.currentCount + ", " + this.timer.running);
+			this.timer.addEventListener(TimerEvent.TIMER, this.onTimer);
+			this.timer.addEventListener(TimerEvent.TIMER_COMPLETE, this.onTimerComplete);
+			this.timer.start();
+			trace("//timer.start()");
+			trace(this.timer.running);
+
+			setTimeout(this.onTimeout, 50, "a", "b");
+			this.intervalId = setInterval(this.onInterval, 130);
+			var cancelled = setTimeout(this.onCancelled, 10);
+			clearTimeout(cancelled);
+
+			this.addEventListener(Event.ENTER_FRAME, this.onEnterFrame);
+		}
+
+		function onEnterFrame(e) {
+			this.frame++;
+		}
+
+		function onTimeout(first, second) {
+			trace("setTimeout(" + first + ", " + second + ") on frame " + this.frame);
+		}
+
+		function onCancelled() {
+			trace("cleared timeout fired");
+		}
+
+		function onInterval() {
+			this.intervalTicks++;
+			trace("setInterval tick " + this.intervalTicks + " on frame " + this.frame);
+			if (this.intervalTicks == 2) {
+				clearInterval(this.intervalId);
+			}
+		}
+
+		function onTimer(e) {
+			trace("timer (currentCount: " + this.timer.currentCount + ", target is timer: " + (e.target === this.timer) + ") on frame " + this.frame);
+		}
+
+		function onTimerComplete(e) {
+			trace("timerComplete (currentCount: " + this.timer.currentCount + ", running: " + this.timer.running + ") on frame " + this.frame);
+			if (!this.restarted) {
+				this.restarted = true;
+				this.timer.reset();
+				trace("//timer.reset()");
+				trace(this.timer.currentCount + ", " + this.timer.running);
+				this.timer.delay = 50;
+				this.timer.repeatCount = 1;
+				this.timer.start();
+			}
+		}
+	}
+}
@@ -0,0 +1,121 @@
+﻿package {
+	public class Test {
+	}
+}
+
+function thrower(value) {
+	throw value;
+}
+
+function withFinally(shouldThrow) {
+	try {
+		trace("try");
+		if (shouldThrow) {
+			throw new Error("thrown from try");
+		}
+		trace("end of try");
+	} catch (e:Error) {
+		trace("catch: " + e.message);
+	} finally {
+		trace("finally");
+	}
+	trace("after try");
+}
+
+function returnFromTry() {
+	try {
+		return "returned from try";
+	} finally {
+		trace("finally before returning");
+	}
+}
+
+trace("//thrower(\"a string\")");
+try {
+	thrower("a string");
+	trace("not reached");
+} catch (e) {
+	trace("caught: " + e);
+}
+
+trace("//throw new RangeError(\"out of range\")");
+try {
+	throw new RangeError("out of range");
+} catch (e:TypeError) {
+	trace("TypeError handler");
+} catch (e:RangeError) {
+	trace("RangeError handler: " + e.message);
+} catch (e:Error) {
+	trace("Error handler");
+}
+
+trace("//withFinally(false)");
+withFinally(false);
+
+trace("//withFinally(true)");
+withFinally(true);
+
+trace("//returnFromTry()");
+trace(returnFromTry());
+
+trace("//throw new ArgumentError(\"inner\") from a try with only a finally");
+try {
+	try {
+		throw new ArgumentError("inner");
+	} finally {
+		trace("inner finally");
+	}
+} catch (e:ArgumentError) {
+	trace("outer handler: " + e.name + ": " + e.message);
+}
+
+trace("//throw e from a catch");
+try {
+	try {
+		throw new Error("first");
+	} catch (e:Error) {
+		trace("rethrowing " + e.message);
+		throw e;
+	}
+} catch (e:Error) {
+	trace("caught again: " + e.message);
+}
+
+trace("//throw from a forEach callback");
+try {
+	[1, 2, 3].forEach(function(item, index, array) {
+		if (item == 2) {
+			throw "stopped at " + item;
+		}
+		trace("visited " + item);
+	});
+} catch (e) {
+	trace("caught: " + e);
+}
+
+trace("//nothing.someMethod()");
+var nothing:Object = null;
+try {
+	nothing.someMethod();
+} catch (e:TypeError) {
+	trace(e.name);
+	trace(e.errorID);
+	trace(e.message);
+}
+
+trace("//var error = new Error(\"a message\", 5)");
+var error = new Error("a message", 5);
+trace("//error.message");
+trace(error.message);
+trace("//error.errorID");
+trace(error.errorID);
+trace("//error.name");
+trace(error.name);
+trace("//error");
+trace(error);
+trace("//new Error()");
+trace(new Error());
+trace("//new TypeError(\"a type error\") is Error");
+trace(new TypeError("a type error") is Error);
+trace("//new TypeError(\"a type error\")");
+trace(new TypeError("a type error"));
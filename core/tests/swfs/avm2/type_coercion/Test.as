@@ -0,0 +1,182 @@
+﻿package {
+	public class Test {
+	}
+}
+
+class Typed {
+	public var i:int;
+	public var u:uint;
+	public var n:Number;
+	public var b:Boolean;
+	public var s:String;
+	public var o:Object;
+	public var a:Array;
+	public var any:*;
+}
+
+var top:int;
+
+function takesInt(value:int):int {
+	return value;
+}
+
+function takesString(value:String):String {
+	return value;
+}
+
+function takesTyped(value:Typed):Typed {
+	return value;
+}
+
+function localInt() {
+	var local:int = 3.7;
+	return local;
+}
+
+trace("//top");
+trace(top);
+
+trace("//var t = new Typed()");
+var t = new Typed();
+trace("//t.i");
+trace(t.i);
+trace("//t.u");
+trace(t.u);
+trace("//t.n");
+trace(t.n);
+trace("//t.b");
+trace(t.b);
+trace("//t.s");
+trace(t.s);
+trace("//t.o");
+trace(t.o);
+trace("//t.a");
+trace(t.a);
+trace("//t.any");
+trace(t.any);
+
+trace("//t.i = 3.7");
+t.i = 3.7;
+trace(t.i);
+trace("//t.i = -1.5");
+t.i = -1.5;
+trace(t.i);
+trace("//t.i = 4294967297");
+t.i = 4294967297;
+trace(t.i);
+trace("//t.i = \"7\"");
+t.i = "7";
+trace(t.i + 1);
+trace("//t.i = undefined");
+t.i = undefined;
+trace(t.i);
+trace("//t.u = -1");
+t.u = -1;
+trace(t.u);
+trace("//t.n = \"12.5\"");
+t.n = "12.5";
+trace(t.n + 1);
+trace("//t.n = true");
+t.n = true;
+trace(t.n);
+trace("//t.n = null");
+t.n = null;
+trace(t.n);
+trace("//t.n = undefined");
+t.n = undefined;
+trace(t.n);
+trace("//t.b = \"false\"");
+t.b = "false";
+trace(t.b);
+trace("//t.b = 0");
+t.b = 0;
+trace(t.b);
+trace("//t.s = 42");
+t.s = 42;
+trace(t.s + 1);
+trace("//typeof t.s");
+trace(typeof t.s);
+trace("//t.s = undefined");
+t.s = undefined;
+trace(t.s);
+trace("//t.o = undefined");
+t.o = undefined;
+trace(t.o);
+trace("//t.any = undefined");
+t.any = undefined;
+trace(t.any);
+trace("//t.a = [1, 2]");
+t.a = [1, 2];
+trace(t.a);
+
+trace("//t.a = \"not an array\"");
+try {
+	t.a = "not an array";
+} catch (e:TypeError) {
+	trace(e.errorID);
+}
+trace("//t.a");
+trace(t.a);
+
+trace("//takesInt(\"7\") + 1");
+trace(takesInt("7") + 1);
+trace("//takesString(5) + 5");
+trace(takesString(5) + 5);
+trace("//takesString(undefined)");
+trace(takesString(undefined));
+trace("//takesTyped(t) == t");
+trace(takesTyped(t) == t);
+trace("//takesTyped(null)");
+trace(takesTyped(null));
+trace("//takesTyped(\"a string\")");
+try {
+	takesTyped("a string");
+} catch (e:TypeError) {
+	trace(e.errorID);
+}
+trace("//localInt()");
+trace(localInt());
+
+trace("//5 as String");
+trace(5 as String);
+trace("//\"5\" as String");
+trace("5" as String);
+trace("//2 as int");
+trace(2 as int);
+trace("//1.5 as int");
+trace(1.5 as int);
+trace("//-1 as uint");
+trace(-1 as uint);
+trace("//[1] as Array");
+trace([1] as Array);
+trace("//(t as Typed) == t");
+trace((t as Typed) == t);
+trace("//\"a string\" as Typed");
+trace("a string" as Typed);
+trace("//var cls = Typed");
+var cls = Typed;
+trace("//(t as cls) == t");
+trace((t as cls) == t);
+trace("//\"a string\" as cls");
+trace("a string" as cls);
+
+trace("//typeof undefined");
+trace(typeof undefined);
+trace("//typeof null");
+trace(typeof null);
+trace("//typeof true");
+trace(typeof true);
+trace("//typeof 1");
+trace(typeof 1);
+trace("//typeof 1.5");
+trace(typeof 1.5);
+trace("//typeof \"a string\"");
+trace(typeof "a string");
+trace("//typeof t");
+trace(typeof t);
+trace("//typeof Typed");
+trace(typeof Typed);
+trace("//typeof takesInt");
+trace(typeof takesInt);
+trace("//typeof <a/>");
+trace(typeof <a/>);
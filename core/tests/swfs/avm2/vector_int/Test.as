@@ -0,0 +1,142 @@
+﻿package {
+	public class Test {
+	}
+}
+
+class Item {
+}
+
+trace("//var v:Vector.<int> = new Vector.<int>()");
+var v:Vector.<int> = new Vector.<int>();
+trace("//v.push(1, 2.5, \"3\")");
+trace(v.push(1, 2.5, "3"));
+trace("//v");
+trace(v);
+trace("//v.length");
+trace(v.length);
+trace("//v[3] = 4.9");
+v[3] = 4.9;
+trace(v);
+trace("//v[10] = 1");
+try {
+	v[10] = 1;
+} catch (e:RangeError) {
+	trace(e.errorID);
+}
+trace("//v[10]");
+try {
+	trace(v[10]);
+} catch (e:RangeError) {
+	trace(e.errorID);
+}
+trace("//v is Vector.<int>");
+trace(v is Vector.<int>);
+trace("//v is Vector.<Number>");
+trace(v is Vector.<Number>);
+
+trace("//v.fixed = true");
+v.fixed = true;
+trace("//v.push(5)");
+try {
+	v.push(5);
+} catch (e:RangeError) {
+	trace(e.errorID);
+}
+trace("//v.length = 1");
+try {
+	v.length = 1;
+} catch (e:RangeError) {
+	trace(e.errorID);
+}
+trace("//v[0] = 10");
+v[0] = 10;
+trace(v);
+trace("//v.fixed = false");
+v.fixed = false;
+trace("//v.length = 2");
+v.length = 2;
+trace(v);
+
+trace("//new Vector.<int>(2, true).fixed");
+trace(new Vector.<int>(2, true).fixed);
+trace("//new Vector.<int>(3)");
+trace(new Vector.<int>(3));
+trace("//new Vector.<uint>(2)");
+trace(new Vector.<uint>(2));
+trace("//new Vector.<Number>(2)");
+trace(new Vector.<Number>(2));
+trace("//new Vector.<Boolean>(2)");
+trace(new Vector.<Boolean>(2));
+trace("//new Vector.<String>(1)[0]");
+trace(new Vector.<String>(1)[0]);
+trace("//new Vector.<int>(-1)");
+try {
+	new Vector.<int>(-1);
+} catch (e:RangeError) {
+	trace(e.errorID);
+}
+
+trace("//var w:Vector.<int> = new <int>[5, 1, 4, 2, 3]");
+var w:Vector.<int> = new <int>[5, 1, 4, 2, 3];
+trace("//w.sort(function(a, b) { return a - b; })");
+trace(w.sort(function(a, b) {
+	return a - b;
+}));
+trace("//w.indexOf(3)");
+trace(w.indexOf(3));
+trace("//w.indexOf(9)");
+trace(w.indexOf(9));
+trace("//w.lastIndexOf(1)");
+trace(w.lastIndexOf(1));
+trace("//w.slice(1, 3)");
+trace(w.slice(1, 3));
+trace("//w.splice(1, 2, 20, 30, 40)");
+trace(w.splice(1, 2, 20, 30, 40));
+trace("//w");
+trace(w);
+trace("//w.concat(new <int>[6, 7])");
+trace(w.concat(new <int>[6, 7]));
+trace("//w.map(function(item, index, vector) { return item * 1.5; })");
+trace(w.map(function(item, index, vector) {
+	return item * 1.5;
+}));
+trace("//w.filter(function(item, index, vector) { return item > 10; })");
+trace(w.filter(function(item, index, vector) {
+	return item > 10;
+}));
+trace("//w.forEach(function(item, index, vector) { ... })");
+w.forEach(function(item, index, vector) {
+	trace(index + ": " + item);
+});
+trace("//w.join(\"-\")");
+trace(w.join("-"));
+trace("//w.reverse()");
+trace(w.reverse());
+trace("//w.pop()");
+trace(w.pop());
+trace("//w.shift()");
+trace(w.shift());
+trace("//w.unshift(0)");
+trace(w.unshift(0));
+trace("//w");
+trace(w);
+trace("//w.push(\"abc\")");
+w.push("abc");
+trace(w);
+
+trace("//var items:Vector.<Item> = new Vector.<Item>()");
+var items:Vector.<Item> = new Vector.<Item>();
+trace("//items.push(new Item())");
+trace(items.push(new Item()));
+trace("//items.push(null)");
+trace(items.push(null));
+trace("//items[1]");
+trace(items[1]);
+trace("//items.push(\"a string\")");
+try {
+	items.push("a string");
+} catch (e:TypeError) {
+	trace(e.errorID);
+}
+trace("//items.length");
+trace(items.length);
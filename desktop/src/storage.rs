@@ -24,14 +24,31 @@ impl DiskStorageBackend {
     }
 }
 
+impl DiskStorageBackend {
+    /// The path of a shared object's `.sol` file.
+    fn sol_path(&self, name: &str) -> PathBuf {
+        self.base_path.join(Path::new(&format!("{}.sol", name)))
+    }
+
+    /// The path that older versions of Ruffle saved a shared object to, as JSON.
+    fn legacy_path(&self, name: &str) -> PathBuf {
+        self.base_path.join(Path::new(name))
+    }
+}
+
 impl StorageBackend for DiskStorageBackend {
-    fn get_string(&self, name: &str) -> Option<String> {
-        let full_path = self.base_path.join(Path::new(name));
+    fn get(&self, name: &str) -> Option<Vec<u8>> {
+        let full_path = self.sol_path(name);
+        let full_path = if full_path.exists() {
+            full_path
+        } else {
+            self.legacy_path(name)
+        };
 
         match File::open(full_path) {
             Ok(mut file) => {
-                let mut buffer = String::new();
-                if let Err(r) = file.read_to_string(&mut buffer) {
+                let mut buffer = Vec::new();
+                if let Err(r) = file.read_to_end(&mut buffer) {
                     log::warn!("Unable to read file content {:?}", r);
                     None
                 } else {
@@ -45,8 +62,8 @@ impl StorageBackend for DiskStorageBackend {
         }
     }
 
-    fn put_string(&mut self, name: &str, value: String) -> bool {
-        let full_path = self.base_path.join(Path::new(name));
+    fn put(&mut self, name: &str, value: &[u8]) -> bool {
+        let full_path = self.sol_path(name);
         if let Some(parent_dir) = full_path.parent() {
             if !parent_dir.exists() {
                 if let Err(r) = fs::create_dir_all(&parent_dir) {
@@ -58,10 +75,12 @@ impl StorageBackend for DiskStorageBackend {
 
         match File::create(full_path) {
             Ok(mut file) => {
-                if let Err(r) = file.write_all(value.as_bytes()) {
+                if let Err(r) = file.write_all(value) {
                     log::warn!("Unable to write file content {:?}", r);
                     false
                 } else {
+                    // The shared object has been migrated, so its legacy save is no longer needed.
+                    let _ = fs::remove_file(self.legacy_path(name));
                     true
                 }
             }
@@ -73,7 +92,7 @@ impl StorageBackend for DiskStorageBackend {
     }
 
     fn remove_key(&mut self, name: &str) {
-        let full_path = self.base_path.join(Path::new(name));
-        let _ = fs::remove_file(full_path);
+        let _ = fs::remove_file(self.sol_path(name));
+        let _ = fs::remove_file(self.legacy_path(name));
     }
 }
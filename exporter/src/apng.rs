@@ -0,0 +1,220 @@
+//! A minimal animated PNG (APNG) encoder.
+//!
+//! The `png` and `image` crates we use can only write still images, so the frame
+//! chunks are assembled by hand here. Every frame covers the whole canvas and is
+//! stored without any filtering.
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use image::RgbaImage;
+use std::io::{self, Write};
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+/// Writes `frames` as a looping APNG, played back at `frame_rate` frames per second.
+pub fn write_apng<W: Write>(
+    mut writer: W,
+    frames: &[RgbaImage],
+    frame_rate: f64,
+) -> io::Result<()> {
+    let first = frames
+        .first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "No frames to encode"))?;
+    let (width, height) = first.dimensions();
+
+    writer.write_all(&PNG_SIGNATURE)?;
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    // 8-bit RGBA, deflate, adaptive filtering, no interlacing.
+    ihdr.extend_from_slice(&[8, 6, 0, 0, 0]);
+    write_chunk(&mut writer, b"IHDR", &ihdr)?;
+
+    let mut actl = Vec::with_capacity(8);
+    actl.extend_from_slice(&(frames.len() as u32).to_be_bytes());
+    // Loop forever.
+    actl.extend_from_slice(&0u32.to_be_bytes());
+    write_chunk(&mut writer, b"acTL", &actl)?;
+
+    // Flash frame rates are 8.8 fixed point, so they can be stored exactly as a fraction.
+    let delay_den = (frame_rate * 256.0)
+        .round()
+        .max(1.0)
+        .min(f64::from(u16::MAX)) as u16;
+    let delay_num: u16 = 256;
+
+    let mut sequence_number = 0u32;
+    for (i, frame) in frames.iter().enumerate() {
+        if frame.dimensions() != (width, height) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "All frames of an APNG must have the same size",
+            ));
+        }
+
+        let mut fctl = Vec::with_capacity(26);
+        fctl.extend_from_slice(&sequence_number.to_be_bytes());
+        fctl.extend_from_slice(&width.to_be_bytes());
+        fctl.extend_from_slice(&height.to_be_bytes());
+        fctl.extend_from_slice(&0u32.to_be_bytes());
+        fctl.extend_from_slice(&0u32.to_be_bytes());
+        fctl.extend_from_slice(&delay_num.to_be_bytes());
+        fctl.extend_from_slice(&delay_den.to_be_bytes());
+        // APNG_DISPOSE_OP_NONE, APNG_BLEND_OP_SOURCE
+        fctl.extend_from_slice(&[0, 0]);
+        write_chunk(&mut writer, b"fcTL", &fctl)?;
+        sequence_number += 1;
+
+        let data = compress_image(frame)?;
+        if i == 0 {
+            // The first frame doubles as the default image.
+            write_chunk(&mut writer, b"IDAT", &data)?;
+        } else {
+            let mut fdat = Vec::with_capacity(data.len() + 4);
+            fdat.extend_from_slice(&sequence_number.to_be_bytes());
+            fdat.extend_from_slice(&data);
+            write_chunk(&mut writer, b"fdAT", &fdat)?;
+            sequence_number += 1;
+        }
+    }
+
+    write_chunk(&mut writer, b"IEND", &[])?;
+    writer.flush()
+}
+
+/// Compresses the scanlines of an image, each prefixed with filter type 0 (none).
+fn compress_image(image: &RgbaImage) -> io::Result<Vec<u8>> {
+    let row_len = image.width() as usize * 4;
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    for row in image.as_raw().chunks(row_len.max(1)) {
+        encoder.write_all(&[0])?;
+        encoder.write_all(row)?;
+    }
+    encoder.finish()
+}
+
+fn write_chunk<W: Write>(writer: &mut W, chunk_type: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(chunk_type);
+    hasher.update(data);
+
+    writer.write_all(&(data.len() as u32).to_be_bytes())?;
+    writer.write_all(chunk_type)?;
+    writer.write_all(data)?;
+    writer.write_all(&hasher.finalize().to_be_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::ZlibDecoder;
+    use image::Rgba;
+    use std::io::Read;
+
+    /// Splits a PNG file into its chunks, checking the signature and every CRC.
+    fn read_chunks(data: &[u8]) -> Vec<([u8; 4], Vec<u8>)> {
+        assert_eq!(data[..8], PNG_SIGNATURE);
+        let mut chunks = Vec::new();
+        let mut rest = &data[8..];
+        while !rest.is_empty() {
+            let len = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+            let chunk_type = [rest[4], rest[5], rest[6], rest[7]];
+            let body = rest[8..8 + len].to_vec();
+            let crc =
+                u32::from_be_bytes([rest[8 + len], rest[9 + len], rest[10 + len], rest[11 + len]]);
+            assert_eq!(crc, crc32fast::hash(&rest[4..8 + len]));
+            chunks.push((chunk_type, body));
+            rest = &rest[12 + len..];
+        }
+        chunks
+    }
+
+    fn be_u32(data: &[u8]) -> u32 {
+        u32::from_be_bytes([data[0], data[1], data[2], data[3]])
+    }
+
+    fn be_u16(data: &[u8]) -> u16 {
+        u16::from_be_bytes([data[0], data[1]])
+    }
+
+    /// Decompresses frame data back into RGBA pixels, dropping the filter bytes.
+    fn decompress_frame(data: &[u8], width: usize) -> Vec<u8> {
+        let mut raw = Vec::new();
+        ZlibDecoder::new(data).read_to_end(&mut raw).unwrap();
+        raw.chunks(width * 4 + 1)
+            .flat_map(|row| {
+                assert_eq!(row[0], 0);
+                row[1..].to_vec()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn frames_are_numbered_in_sequence() {
+        let frames = vec![
+            RgbaImage::from_pixel(3, 2, Rgba([255, 0, 0, 255])),
+            RgbaImage::from_pixel(3, 2, Rgba([0, 255, 0, 128])),
+            RgbaImage::from_pixel(3, 2, Rgba([0, 0, 255, 0])),
+        ];
+        let mut apng = Vec::new();
+        write_apng(&mut apng, &frames, 24.0).unwrap();
+
+        let chunks = read_chunks(&apng);
+        let types: Vec<[u8; 4]> = chunks.iter().map(|(chunk_type, _)| *chunk_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                *b"IHDR", *b"acTL", *b"fcTL", *b"IDAT", *b"fcTL", *b"fdAT", *b"fcTL", *b"fdAT",
+                *b"IEND",
+            ]
+        );
+
+        let actl = &chunks[1].1;
+        assert_eq!(be_u32(&actl[0..]), 3);
+        assert_eq!(be_u32(&actl[4..]), 0);
+
+        let mut sequence_numbers = Vec::new();
+        let mut frame_data = Vec::new();
+        for (chunk_type, body) in &chunks {
+            match chunk_type {
+                b"fcTL" => {
+                    sequence_numbers.push(be_u32(&body[0..]));
+                    assert_eq!((be_u32(&body[4..]), be_u32(&body[8..])), (3, 2));
+                    // 24 fps, stored exactly as 256/6144 seconds.
+                    assert_eq!((be_u16(&body[20..]), be_u16(&body[22..])), (256, 6144));
+                }
+                b"IDAT" => frame_data.push(decompress_frame(body, 3)),
+                b"fdAT" => {
+                    sequence_numbers.push(be_u32(&body[0..]));
+                    frame_data.push(decompress_frame(&body[4..], 3));
+                }
+                _ => (),
+            }
+        }
+        assert_eq!(sequence_numbers, vec![0, 1, 2, 3, 4]);
+        for (data, frame) in frame_data.iter().zip(&frames) {
+            assert_eq!(data, frame.as_raw());
+        }
+    }
+
+    #[test]
+    fn first_frame_is_default_image() {
+        let frames = vec![
+            RgbaImage::from_pixel(2, 2, Rgba([10, 20, 30, 255])),
+            RgbaImage::from_pixel(2, 2, Rgba([40, 50, 60, 255])),
+        ];
+        let mut apng = Vec::new();
+        write_apng(&mut apng, &frames, 30.0).unwrap();
+
+        let image = image::load_from_memory(&apng).unwrap().to_rgba8();
+        assert_eq!(image, frames[0]);
+    }
+
+    #[test]
+    fn frames_must_share_size() {
+        let frames = vec![RgbaImage::new(2, 2), RgbaImage::new(3, 3)];
+        assert!(write_apng(Vec::new(), &frames, 30.0).is_err());
+        assert!(write_apng(Vec::new(), &[], 30.0).is_err());
+    }
+}
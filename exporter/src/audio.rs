@@ -0,0 +1,187 @@
+//! An audio backend that mixes all playing sounds offline, one movie frame at a time,
+//! so that the exporter can write the soundtrack of a capture to a file.
+
+use ruffle_core::backend::audio::mixer::AudioMixer;
+use ruffle_core::backend::audio::{
+    swf, AudioBackend, SoundHandle, SoundInstanceHandle, SoundTransform,
+};
+use ruffle_core::tag_utils::SwfSlice;
+
+type Error = Box<dyn std::error::Error>;
+
+/// The sample rate of the mixed output.
+pub const SAMPLE_RATE: u32 = 44100;
+
+/// Audio backend that records everything that plays into a buffer.
+///
+/// Sounds are only advanced when `mix_frame` is called, so the output stays in
+/// sync with the rendered frames regardless of how fast they are produced.
+pub struct CaptureAudioBackend {
+    mixer: AudioMixer,
+    is_playing: bool,
+
+    /// Fractional output sample frames left over from the previous movie frame.
+    remainder: f64,
+}
+
+impl CaptureAudioBackend {
+    pub fn new() -> Self {
+        Self {
+            mixer: AudioMixer::new(2, SAMPLE_RATE),
+            is_playing: true,
+            remainder: 0.0,
+        }
+    }
+
+    /// Mixes the audio for a single movie frame.
+    pub fn mix_frame(&mut self, frame_rate: f64) -> Vec<[i16; 2]> {
+        let num_frames = f64::from(SAMPLE_RATE) / frame_rate + self.remainder;
+        self.remainder = num_frames.fract();
+        self.mix(num_frames as usize)
+    }
+
+    /// Mixes the given amount of output sample frames from all playing sounds.
+    ///
+    /// Nothing is advanced while the backend is paused, and silence is returned.
+    pub fn mix(&mut self, num_frames: usize) -> Vec<[i16; 2]> {
+        let mut output = vec![0i16; num_frames * 2];
+        if self.is_playing {
+            self.mixer.mix(&mut output);
+        }
+
+        output
+            .chunks_exact(2)
+            .map(|frame| [frame[0], frame[1]])
+            .collect()
+    }
+}
+
+impl Default for CaptureAudioBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioBackend for CaptureAudioBackend {
+    fn play(&mut self) {
+        self.is_playing = true;
+    }
+
+    fn pause(&mut self) {
+        self.is_playing = false;
+    }
+
+    fn register_sound(&mut self, swf_sound: &swf::Sound) -> Result<SoundHandle, Error> {
+        self.mixer.register_sound(swf_sound)
+    }
+
+    fn start_sound(
+        &mut self,
+        sound_handle: SoundHandle,
+        settings: &swf::SoundInfo,
+    ) -> Result<SoundInstanceHandle, Error> {
+        self.mixer.start_sound(sound_handle, settings)
+    }
+
+    fn start_stream(
+        &mut self,
+        _stream_handle: Option<SoundHandle>,
+        _clip_frame: u16,
+        clip_data: SwfSlice,
+        stream_info: &swf::SoundStreamHead,
+    ) -> Result<SoundInstanceHandle, Error> {
+        self.mixer.start_stream(clip_data, stream_info)
+    }
+
+    fn stop_sound(&mut self, sound: SoundInstanceHandle) {
+        self.mixer.stop_sound(sound)
+    }
+
+    fn stop_all_sounds(&mut self) {
+        self.mixer.stop_all_sounds()
+    }
+
+    fn get_sound_position(&self, instance: SoundInstanceHandle) -> Option<u32> {
+        self.mixer.get_sound_position(instance)
+    }
+
+    fn get_sound_duration(&self, sound: SoundHandle) -> Option<u32> {
+        self.mixer.get_sound_duration(sound)
+    }
+
+    fn set_sound_transform(&mut self, instance: SoundInstanceHandle, transform: SoundTransform) {
+        self.mixer.set_sound_transform(instance, transform)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Registers a mono, 16-bit uncompressed 44.1kHz sound holding a constant value.
+    fn register_constant_sound(
+        audio: &mut CaptureAudioBackend,
+        value: i16,
+        num_samples: usize,
+    ) -> SoundHandle {
+        let data: Vec<u8> = std::iter::repeat(value.to_le_bytes().to_vec())
+            .take(num_samples)
+            .flatten()
+            .collect();
+        audio
+            .register_sound(&swf::Sound {
+                id: 1,
+                format: swf::SoundFormat {
+                    compression: swf::AudioCompression::Uncompressed,
+                    sample_rate: 44100,
+                    is_stereo: false,
+                    is_16_bit: true,
+                },
+                num_samples: num_samples as u32,
+                data: &data,
+            })
+            .unwrap()
+    }
+
+    fn sound_info() -> swf::SoundInfo {
+        swf::SoundInfo {
+            event: swf::SoundEvent::Event,
+            in_sample: None,
+            out_sample: None,
+            num_loops: 1,
+            envelope: None,
+        }
+    }
+
+    #[test]
+    fn mixes_sounds_together() {
+        let mut audio = CaptureAudioBackend::new();
+        let quiet = register_constant_sound(&mut audio, 1000, 100);
+        let loud = register_constant_sound(&mut audio, 2000, 100);
+        audio.start_sound(quiet, &sound_info()).unwrap();
+        audio.start_sound(loud, &sound_info()).unwrap();
+
+        let samples = audio.mix(10);
+        assert_eq!(samples, vec![[3000, 3000]; 10]);
+    }
+
+    #[test]
+    fn frames_carry_over_fractional_samples() {
+        let mut audio = CaptureAudioBackend::new();
+        let frame_rate = f64::from(SAMPLE_RATE) / 1.5;
+
+        let lengths: Vec<usize> = (0..4).map(|_| audio.mix_frame(frame_rate).len()).collect();
+        assert_eq!(lengths, vec![1, 2, 1, 2]);
+    }
+
+    #[test]
+    fn paused_backend_is_silent() {
+        let mut audio = CaptureAudioBackend::new();
+        let sound = register_constant_sound(&mut audio, 1000, 100);
+        let instance = audio.start_sound(sound, &sound_info()).unwrap();
+        audio.pause();
+
+        assert_eq!(audio.mix(10), vec![[0, 0]; 10]);
+        assert_eq!(audio.get_sound_position(instance), Some(0));
+    }
+}
@@ -1,23 +1,57 @@
+mod apng;
+mod audio;
+
+use crate::audio::CaptureAudioBackend;
 use clap::Clap;
-use image::RgbaImage;
+use image::codecs::gif::GifEncoder;
+use image::{Delay, Frame, RgbaImage};
 use indicatif::{ProgressBar, ProgressStyle};
-use ruffle_core::backend::audio::NullAudioBackend;
+use ruffle_core::backend::audio::{AudioBackend, NullAudioBackend};
 use ruffle_core::backend::locale::NullLocaleBackend;
 use ruffle_core::backend::log::NullLogBackend;
 use ruffle_core::backend::navigator::NullNavigatorBackend;
+use ruffle_core::backend::render::RenderBackend;
 use ruffle_core::backend::storage::MemoryStorageBackend;
 use ruffle_core::backend::ui::NullUiBackend;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::Player;
+use ruffle_render_software::SoftwareRenderBackend;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use ruffle_render_wgpu::target::TextureTarget;
 use ruffle_render_wgpu::{wgpu, Descriptors, WgpuRenderBackend};
 use std::error::Error;
-use std::fs::create_dir_all;
+use std::fs::{create_dir_all, File};
+use std::io::{BufWriter, Seek, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use walkdir::{DirEntry, WalkDir};
 
+#[derive(Copy, Clone, Clap, PartialEq, Debug)]
+enum RendererType {
+    Wgpu,
+    Software,
+}
+
+#[derive(Copy, Clone, Clap, PartialEq, Debug)]
+enum OutputFormat {
+    Png,
+    Apng,
+    Gif,
+}
+
+impl OutputFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Png | OutputFormat::Apng => "png",
+            OutputFormat::Gif => "gif",
+        }
+    }
+
+    fn is_animated(self) -> bool {
+        self != OutputFormat::Png
+    }
+}
+
 #[derive(Clap, Debug, Copy, Clone)]
 struct SizeOpt {
     /// The amount to scale the page size with
@@ -60,6 +94,19 @@ struct Opt {
     #[clap(short, long)]
     silent: bool,
 
+    /// How to store the captured frames. Animated formats put all frames into a single
+    /// file, played back at the frame rate of the movie.
+    #[clap(long, case_insensitive = true, default_value = "png", arg_enum)]
+    format: OutputFormat,
+
+    /// Also mix the audio of the movie during the captured frames into a WAV file
+    #[clap(long)]
+    audio: bool,
+
+    /// The renderer to capture frames with. The software renderer works without a GPU.
+    #[clap(long, case_insensitive = true, default_value = "wgpu", arg_enum)]
+    renderer: RendererType,
+
     #[clap(flatten)]
     size: SizeOpt,
 
@@ -85,14 +132,31 @@ struct Opt {
     trace_path: Option<PathBuf>,
 }
 
+/// The renderer used for captures. The wgpu device is reused between movies.
+enum CaptureRenderer {
+    Wgpu(Descriptors),
+    Software,
+}
+
+/// The result of capturing a movie.
+struct Capture {
+    frames: Vec<RgbaImage>,
+    frame_rate: f64,
+
+    /// The mixed stereo audio of the captured frames, if requested.
+    audio: Option<Vec<[i16; 2]>>,
+}
+
+#[allow(clippy::too_many_arguments)]
 fn take_screenshot(
-    descriptors: Descriptors,
+    renderer: CaptureRenderer,
     swf_path: &Path,
     frames: u32,
     skipframes: u32,
     progress: &Option<ProgressBar>,
     size: SizeOpt,
-) -> Result<(Descriptors, Vec<RgbaImage>), Box<dyn std::error::Error>> {
+    capture_audio: bool,
+) -> Result<(CaptureRenderer, Capture), Box<dyn std::error::Error>> {
     let movie = SwfMovie::from_path(&swf_path)?;
 
     let width = size.width.unwrap_or_else(|| movie.width());
@@ -101,10 +165,21 @@ fn take_screenshot(
     let height = size.height.unwrap_or_else(|| movie.height());
     let height = (height as f32 * size.scale).round() as u32;
 
-    let target = TextureTarget::new(&descriptors.device, (width, height));
+    let renderer: Box<dyn RenderBackend> = match renderer {
+        CaptureRenderer::Wgpu(descriptors) => {
+            let target = TextureTarget::new(&descriptors.device, (width, height));
+            Box::new(WgpuRenderBackend::new(descriptors, target)?)
+        }
+        CaptureRenderer::Software => Box::new(SoftwareRenderBackend::new(width, height)),
+    };
+    let audio: Box<dyn AudioBackend> = if capture_audio {
+        Box::new(CaptureAudioBackend::new())
+    } else {
+        Box::new(NullAudioBackend::new())
+    };
     let player = Player::new(
-        Box::new(WgpuRenderBackend::new(descriptors, target)?),
-        Box::new(NullAudioBackend::new()),
+        renderer,
+        audio,
         Box::new(NullNavigatorBackend::new()),
         Box::new(MemoryStorageBackend::default()),
         Box::new(NullLocaleBackend::new()),
@@ -119,6 +194,12 @@ fn take_screenshot(
     player.lock().unwrap().set_root_movie(Arc::new(movie));
 
     let mut result = Vec::new();
+    let mut audio = if capture_audio {
+        Some(Vec::new())
+    } else {
+        None
+    };
+    let frame_rate = player.lock().unwrap().frame_rate();
     let totalframes = frames + skipframes;
 
     for i in 0..totalframes {
@@ -130,15 +211,21 @@ fn take_screenshot(
             ));
         }
         player.lock().unwrap().run_frame();
+        if let Some(audio) = &mut audio {
+            let mut player = player.lock().unwrap();
+            let samples = player
+                .audio_mut()
+                .downcast_mut::<CaptureAudioBackend>()
+                .unwrap()
+                .mix_frame(frame_rate);
+            if i >= skipframes {
+                audio.extend(samples);
+            }
+        }
         if i >= skipframes {
             player.lock().unwrap().render();
             let mut player = player.lock().unwrap();
-            let renderer = player
-                .renderer_mut()
-                .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                .unwrap();
-            let target = renderer.target();
-            if let Some(image) = target.capture(renderer.device()) {
+            if let Some(image) = capture_frame(player.renderer_mut()) {
                 result.push(image);
             } else {
                 return Err(format!("Unable to capture frame {} of {:?}", i, swf_path).into());
@@ -150,16 +237,90 @@ fn take_screenshot(
         }
     }
 
-    let descriptors = Arc::try_unwrap(player)
+    let renderer = Arc::try_unwrap(player)
         .ok()
         .unwrap()
         .into_inner()?
-        .destroy()
-        .downcast::<WgpuRenderBackend<TextureTarget>>()
-        .ok()
-        .unwrap()
-        .descriptors();
-    Ok((descriptors, result))
+        .destroy();
+    let renderer = match renderer.downcast::<WgpuRenderBackend<TextureTarget>>() {
+        Ok(renderer) => CaptureRenderer::Wgpu(renderer.descriptors()),
+        Err(_) => CaptureRenderer::Software,
+    };
+    Ok((
+        renderer,
+        Capture {
+            frames: result,
+            frame_rate,
+            audio,
+        },
+    ))
+}
+
+fn capture_frame(renderer: &mut Box<dyn RenderBackend>) -> Option<RgbaImage> {
+    if let Some(renderer) = renderer.downcast_mut::<WgpuRenderBackend<TextureTarget>>() {
+        renderer.target().capture(renderer.device())
+    } else if let Some(renderer) = renderer.downcast_mut::<SoftwareRenderBackend>() {
+        RgbaImage::from_raw(
+            renderer.width(),
+            renderer.height(),
+            renderer.capture_frame(),
+        )
+    } else {
+        None
+    }
+}
+
+/// Saves all frames of a capture into a single animated file.
+fn save_animation(
+    capture: &Capture,
+    format: OutputFormat,
+    path: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let file = BufWriter::new(File::create(path)?);
+    match format {
+        OutputFormat::Apng => apng::write_apng(file, &capture.frames, capture.frame_rate)?,
+        OutputFormat::Gif => write_gif(file, &capture.frames, capture.frame_rate)?,
+        OutputFormat::Png => unreachable!("PNG captures are saved frame by frame"),
+    }
+    Ok(())
+}
+
+/// Writes `frames` as an animated GIF, played back at `frame_rate` frames per second.
+fn write_gif<W: Write>(
+    writer: W,
+    frames: &[RgbaImage],
+    frame_rate: f64,
+) -> Result<(), Box<dyn Error>> {
+    let delay = Delay::from_numer_denom_ms(256_000, (frame_rate * 256.0).round().max(1.0) as u32);
+    let mut encoder = GifEncoder::new(writer);
+    encoder.encode_frames(
+        frames
+            .iter()
+            .map(|image| Frame::from_parts(image.clone(), 0, 0, delay)),
+    )?;
+    Ok(())
+}
+
+/// Saves the mixed audio of a capture as a 16-bit stereo WAV file.
+fn save_audio(samples: &[[i16; 2]], path: &Path) -> Result<(), Box<dyn Error>> {
+    write_wav(BufWriter::new(File::create(path)?), samples)
+}
+
+/// Writes mixed audio as a 16-bit stereo WAV file.
+fn write_wav<W: Write + Seek>(writer: W, samples: &[[i16; 2]]) -> Result<(), Box<dyn Error>> {
+    let spec = hound::WavSpec {
+        channels: 2,
+        sample_rate: audio::SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::new(writer, spec)?;
+    for [left, right] in samples {
+        writer.write_sample(*left)?;
+        writer.write_sample(*right)?;
+    }
+    writer.finalize()?;
+    Ok(())
 }
 
 fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
@@ -192,19 +353,19 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     results
 }
 
-fn capture_single_swf(descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn Error>> {
+fn capture_single_swf(renderer: CaptureRenderer, opt: &Opt) -> Result<(), Box<dyn Error>> {
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
-        if opt.frames == 1 {
+        if opt.frames == 1 || opt.format.is_animated() {
             result.set_file_name(opt.swf.file_stem().unwrap());
-            result.set_extension("png");
+            result.set_extension(opt.format.extension());
         } else {
             result.set_file_name(opt.swf.file_stem().unwrap());
         }
         result
     });
 
-    if opt.frames > 1 {
+    if opt.frames > 1 && !opt.format.is_animated() {
         let _ = create_dir_all(&output);
     }
 
@@ -222,27 +383,38 @@ fn capture_single_swf(descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn
         None
     };
 
-    let (_, frames) = take_screenshot(
-        descriptors,
+    let (_, capture) = take_screenshot(
+        renderer,
         &opt.swf,
         opt.frames,
         opt.skipframes,
         &progress,
         opt.size,
+        opt.audio,
     )?;
+    let frames = &capture.frames;
 
     if let Some(progress) = &progress {
         progress.set_message(&opt.swf.file_stem().unwrap().to_string_lossy());
     }
 
-    if frames.len() == 1 {
+    let audio_path = if opt.format.is_animated() {
+        save_animation(&capture, opt.format, &output)?;
+        output.with_extension("wav")
+    } else if frames.len() == 1 {
         frames.get(0).unwrap().save(&output)?;
+        output.with_extension("wav")
     } else {
         for (frame, image) in frames.iter().enumerate() {
             let mut path = PathBuf::from(&output);
             path.push(format!("{}.png", frame));
             image.save(&path)?;
         }
+        output.join("audio.wav")
+    };
+
+    if let Some(audio) = &capture.audio {
+        save_audio(audio, &audio_path)?;
     }
 
     let message = if frames.len() == 1 {
@@ -269,7 +441,7 @@ fn capture_single_swf(descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn
     Ok(())
 }
 
-fn capture_multiple_swfs(mut descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn Error>> {
+fn capture_multiple_swfs(mut renderer: CaptureRenderer, opt: &Opt) -> Result<(), Box<dyn Error>> {
     let output = opt.output_path.clone().unwrap();
     let files = find_files(&opt.swf, !opt.silent);
 
@@ -288,15 +460,17 @@ fn capture_multiple_swfs(mut descriptors: Descriptors, opt: &Opt) -> Result<(),
     };
 
     for file in &files {
-        let (new_descriptors, frames) = take_screenshot(
-            descriptors,
+        let (new_renderer, capture) = take_screenshot(
+            renderer,
             &file.path(),
             opt.frames,
             opt.skipframes,
             &progress,
             opt.size,
+            opt.audio,
         )?;
-        descriptors = new_descriptors;
+        renderer = new_renderer;
+        let frames = &capture.frames;
 
         if let Some(progress) = &progress {
             progress.set_message(&file.path().file_stem().unwrap().to_string_lossy());
@@ -308,14 +482,19 @@ fn capture_multiple_swfs(mut descriptors: Descriptors, opt: &Opt) -> Result<(),
             .unwrap_or_else(|_| &file.path())
             .to_path_buf();
 
-        if frames.len() == 1 {
+        let audio_path = if frames.len() == 1 || opt.format.is_animated() {
             let mut destination = PathBuf::from(&output);
-            relative_path.set_extension("png");
+            relative_path.set_extension(opt.format.extension());
             destination.push(relative_path);
             if let Some(parent) = destination.parent() {
                 let _ = create_dir_all(parent);
             }
-            frames.get(0).unwrap().save(&destination)?;
+            if opt.format.is_animated() {
+                save_animation(&capture, opt.format, &destination)?;
+            } else {
+                frames.get(0).unwrap().save(&destination)?;
+            }
+            destination.with_extension("wav")
         } else {
             let mut parent = PathBuf::from(&output);
             relative_path.set_extension("");
@@ -326,6 +505,11 @@ fn capture_multiple_swfs(mut descriptors: Descriptors, opt: &Opt) -> Result<(),
                 destination.push(format!("{}.png", frame));
                 image.save(&destination)?;
             }
+            parent.join("audio.wav")
+        };
+
+        if let Some(audio) = &capture.audio {
+            save_audio(audio, &audio_path)?;
         }
     }
 
@@ -370,22 +554,79 @@ fn trace_path(_opt: &Opt) -> Option<&Path> {
 
 fn main() -> Result<(), Box<dyn Error>> {
     let opt: Opt = Opt::parse();
-    let instance = wgpu::Instance::new(opt.graphics.into());
-    let descriptors = WgpuRenderBackend::<TextureTarget>::build_descriptors(
-        opt.graphics.into(),
-        instance,
-        None,
-        opt.power.into(),
-        trace_path(&opt),
-    )?;
+    let renderer = match opt.renderer {
+        RendererType::Wgpu => {
+            let instance = wgpu::Instance::new(opt.graphics.into());
+            let descriptors = WgpuRenderBackend::<TextureTarget>::build_descriptors(
+                opt.graphics.into(),
+                instance,
+                None,
+                opt.power.into(),
+                trace_path(&opt),
+            )?;
+            CaptureRenderer::Wgpu(descriptors)
+        }
+        RendererType::Software => CaptureRenderer::Software,
+    };
 
     if opt.swf.is_file() {
-        capture_single_swf(descriptors, &opt)?;
+        capture_single_swf(renderer, &opt)?;
     } else if opt.output_path.is_some() {
-        capture_multiple_swfs(descriptors, &opt)?;
+        capture_multiple_swfs(renderer, &opt)?;
     } else {
         return Err("Output directory is required when exporting multiple files.".into());
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::codecs::gif::GifDecoder;
+    use image::{AnimationDecoder, Rgba};
+    use std::io::Cursor;
+
+    #[test]
+    fn gif_frames_have_frame_rate_delay() {
+        let frames = vec![
+            RgbaImage::from_pixel(4, 4, Rgba([255, 0, 0, 255])),
+            RgbaImage::from_pixel(4, 4, Rgba([0, 0, 255, 255])),
+        ];
+        let mut gif = Vec::new();
+        write_gif(&mut gif, &frames, 25.0).unwrap();
+
+        let decoded = GifDecoder::new(Cursor::new(gif))
+            .unwrap()
+            .into_frames()
+            .collect_frames()
+            .unwrap();
+        assert_eq!(decoded.len(), 2);
+        for (frame, expected) in decoded.iter().zip(&frames) {
+            let (numer, denom) = frame.delay().numer_denom_ms();
+            assert_eq!(f64::from(numer) / f64::from(denom), 40.0);
+            assert_eq!(frame.buffer().dimensions(), (4, 4));
+            // GIF palettes are quantized, so colors may be off by a little.
+            let actual = frame.buffer().get_pixel(0, 0);
+            let expected = expected.get_pixel(0, 0);
+            for (a, e) in actual.0.iter().zip(&expected.0) {
+                assert!((i16::from(*a) - i16::from(*e)).abs() <= 8);
+            }
+        }
+    }
+
+    #[test]
+    fn wav_holds_interleaved_stereo_samples() {
+        let samples = [[1, -1], [i16::MAX, i16::MIN], [0, 300]];
+        let mut wav = Cursor::new(Vec::new());
+        write_wav(&mut wav, &samples).unwrap();
+
+        let mut reader = hound::WavReader::new(Cursor::new(wav.into_inner())).unwrap();
+        let spec = reader.spec();
+        assert_eq!(spec.channels, 2);
+        assert_eq!(spec.sample_rate, audio::SAMPLE_RATE);
+        assert_eq!(spec.bits_per_sample, 16);
+        let decoded: Vec<i16> = reader.samples::<i16>().map(Result::unwrap).collect();
+        assert_eq!(decoded, vec![1, -1, i16::MAX, i16::MIN, 0, 300]);
+    }
+}
@@ -1,17 +1,19 @@
 use ruffle_core::backend::render::{
     swf::{self, CharacterId, GradientInterpolation, GradientSpread},
-    Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, JpegTagFormat, MovieLibrary,
-    RenderBackend, ShapeHandle, Transform,
+    Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, BlendMode, Color, Filter, JpegTagFormat,
+    MovieLibrary, RenderBackend, ShapeHandle, Transform,
 };
+use ruffle_core::bitmap::blend::blend_rgba;
+use ruffle_core::bitmap::filters::apply_filters;
 use ruffle_core::color_transform::ColorTransform;
 use ruffle_core::shape_utils::{DistilledShape, DrawCommand};
 use ruffle_core::swf::Matrix;
 use ruffle_web_common::JsResult;
 use std::convert::TryInto;
-use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen::{Clamped, JsCast, JsValue};
 use web_sys::{
     CanvasGradient, CanvasPattern, CanvasRenderingContext2d, Element, HtmlCanvasElement,
-    HtmlImageElement, Path2d, SvgsvgElement,
+    HtmlImageElement, ImageData, Path2d, SvgsvgElement,
 };
 
 type Error = Box<dyn std::error::Error>;
@@ -30,6 +32,9 @@ pub struct WebCanvasRenderBackend {
     use_color_transform_hack: bool,
     pixelated_property_value: &'static str,
     deactivating_mask: bool,
+
+    /// The amount of layers started by `push_layer` that are currently being drawn into.
+    num_layers: usize,
 }
 
 /// Canvas-drawable shape data extracted from an SWF file.
@@ -212,6 +217,7 @@ impl WebCanvasRenderBackend {
             viewport_height: 0,
             use_color_transform_hack: is_firefox,
             deactivating_mask: false,
+            num_layers: 0,
 
             // For rendering non-smoothed bitmaps.
             // crisp-edges works in Firefox, pixelated works in Chrome (and others)?
@@ -532,6 +538,7 @@ impl RenderBackend for WebCanvasRenderBackend {
             .fill_rect(0.0, 0.0, width.into(), height.into());
 
         self.deactivating_mask = false;
+        self.num_layers = 0;
     }
 
     fn end_frame(&mut self) {
@@ -695,6 +702,77 @@ impl RenderBackend for WebCanvasRenderBackend {
             .unwrap();
     }
 
+    fn push_layer(&mut self) {
+        self.num_layers += 1;
+        self.push_render_target();
+    }
+
+    fn pop_layer(&mut self, filters: &[Filter], blend_mode: BlendMode) {
+        self.num_layers = self.num_layers.saturating_sub(1);
+        let (layer_canvas, layer_context) = self.pop_render_target();
+        let width = layer_canvas.width();
+        let height = layer_canvas.height();
+
+        if !filters.is_empty() {
+            if let Some(mut data) = get_premultiplied_pixels(&layer_context, width, height) {
+                apply_filters(width, height, &mut data, filters);
+                put_premultiplied_pixels(&layer_context, &mut data, width, height);
+            }
+        }
+
+        // `Alpha` and `Erase` only affect an enclosing layer, not the stage itself.
+        let blend_mode = match blend_mode {
+            BlendMode::Alpha | BlendMode::Erase if self.num_layers == 0 => BlendMode::Normal,
+            blend_mode => blend_mode,
+        };
+        let composite_operation = match blend_mode {
+            BlendMode::Normal | BlendMode::Layer => "source-over",
+            BlendMode::Multiply => "multiply",
+            BlendMode::Screen => "screen",
+            BlendMode::Lighten => "lighten",
+            BlendMode::Darken => "darken",
+            BlendMode::Difference => "difference",
+            BlendMode::Add => "lighter",
+            BlendMode::Overlay => "overlay",
+            BlendMode::HardLight => "hard-light",
+            BlendMode::Alpha => "destination-in",
+            BlendMode::Erase => "destination-out",
+            BlendMode::Subtract | BlendMode::Invert => {
+                // Canvas has no composite operation for these, so blend the pixels by hand.
+                let layer = get_premultiplied_pixels(&layer_context, width, height);
+                let parent = get_premultiplied_pixels(&self.context, width, height);
+                if let (Some(layer), Some(mut parent)) = (layer, parent) {
+                    blend_rgba(blend_mode, &layer, &mut parent);
+                    put_premultiplied_pixels(&self.context, &mut parent, width, height);
+                }
+                return;
+            }
+        };
+
+        self.context.reset_transform().warn_on_error();
+        self.context
+            .set_global_composite_operation(composite_operation)
+            .warn_on_error();
+        self.context
+            .draw_image_with_html_canvas_element(&layer_canvas, 0.0, 0.0)
+            .warn_on_error();
+        self.context
+            .set_global_composite_operation("source-over")
+            .warn_on_error();
+    }
+
+    fn pop_layer_to_bitmap(&mut self) -> Option<Bitmap> {
+        self.num_layers = self.num_layers.saturating_sub(1);
+        let (layer_canvas, layer_context) = self.pop_render_target();
+        let width = layer_canvas.width();
+        let height = layer_canvas.height();
+        get_premultiplied_pixels(&layer_context, width, height).map(|data| Bitmap {
+            width,
+            height,
+            data: BitmapFormat::Rgba(data),
+        })
+    }
+
     fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
         let window = web_sys::window().unwrap();
         let document = window.document().unwrap();
@@ -779,6 +857,38 @@ impl RenderBackend for WebCanvasRenderBackend {
     }
 }
 
+/// Reads back the pixels of a canvas as premultiplied RGBA,
+/// which is what the filters and blend modes in core work with.
+fn get_premultiplied_pixels(
+    context: &CanvasRenderingContext2d,
+    width: u32,
+    height: u32,
+) -> Option<Vec<u8>> {
+    let image_data = context
+        .get_image_data(0.0, 0.0, width.into(), height.into())
+        .ok()?;
+    let mut data = image_data.data().to_vec();
+    ruffle_core::backend::render::premultiply_alpha_rgba(&mut data);
+    Some(data)
+}
+
+/// Replaces the pixels of a canvas with premultiplied RGBA pixels.
+fn put_premultiplied_pixels(
+    context: &CanvasRenderingContext2d,
+    data: &mut [u8],
+    width: u32,
+    height: u32,
+) {
+    ruffle_core::backend::render::unmultiply_alpha_rgba(data);
+    if let Ok(image_data) =
+        ImageData::new_with_u8_clamped_array_and_sh(Clamped(data), width, height)
+    {
+        context
+            .put_image_data(&image_data, 0.0, 0.0)
+            .warn_on_error();
+    }
+}
+
 #[allow(clippy::cognitive_complexity)]
 fn swf_shape_to_svg(
     shape: DistilledShape,
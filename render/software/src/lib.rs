@@ -0,0 +1,618 @@
+//! A `RenderBackend` that rasterizes entirely on the CPU.
+//!
+//! This is much slower than the GPU backends, but it doesn't need a graphics
+//! device or a browser, so it can be used to render frames on headless machines
+//! (e.g. for the exporter, or for pixel comparison tests).
+
+use ruffle_core::backend::render::swf::{DefineBitsLossless, Glyph};
+use ruffle_core::backend::render::{
+    Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, BlendMode, Color, Filter, MovieLibrary,
+    RenderBackend, ShapeHandle, Transform,
+};
+use ruffle_core::bitmap::filters::apply_filters;
+use ruffle_core::color_transform::ColorTransform;
+use ruffle_core::shape_utils::DistilledShape;
+use ruffle_core::swf::Matrix;
+use ruffle_render_common_tess::{Draw, DrawType, ShapeTessellator, Vertex};
+
+mod raster;
+
+pub use crate::raster::MaskState;
+use crate::raster::{Paint, RasterTarget, Texture};
+
+type Error = Box<dyn std::error::Error>;
+
+/// The default amount of samples per pixel along each axis.
+pub const DEFAULT_SUPERSAMPLING: u32 = 2;
+
+pub struct SoftwareRenderBackend {
+    shape_tessellator: ShapeTessellator,
+    target: RasterTarget,
+    meshes: Vec<Vec<Draw>>,
+    textures: Vec<Texture>,
+    mask_state: MaskState,
+    num_masks: u32,
+
+    /// The targets and mask states underneath the layers started by `push_layer`.
+    layers: Vec<(RasterTarget, MaskState, u32)>,
+}
+
+impl SoftwareRenderBackend {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self::with_supersampling(width, height, DEFAULT_SUPERSAMPLING)
+    }
+
+    /// Creates a renderer that takes `supersampling * supersampling` samples per pixel.
+    /// A value of 1 disables antialiasing.
+    pub fn with_supersampling(width: u32, height: u32, supersampling: u32) -> Self {
+        Self {
+            shape_tessellator: ShapeTessellator::new(),
+            target: RasterTarget::new(width, height, supersampling),
+            meshes: Vec::new(),
+            textures: Vec::new(),
+            mask_state: MaskState::NoMask,
+            num_masks: 0,
+            layers: Vec::new(),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.target.width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.target.height()
+    }
+
+    /// Returns the last rendered frame as non-premultiplied RGBA pixels,
+    /// `width * height * 4` bytes in row-major order.
+    pub fn capture_frame(&self) -> Vec<u8> {
+        self.target.capture()
+    }
+
+    fn register_shape_internal(
+        &mut self,
+        shape: DistilledShape,
+        library: Option<&MovieLibrary<'_>>,
+    ) -> Vec<Draw> {
+        let textures = &self.textures;
+        self.shape_tessellator.tessellate_shape(shape, |id| {
+            library
+                .and_then(|lib| lib.get_bitmap(id))
+                .and_then(|bitmap| {
+                    let handle = bitmap.bitmap_handle();
+                    textures.get(handle.0).map(|texture| (texture, handle))
+                })
+                .map(|(texture, handle)| (texture.width, texture.height, handle))
+        })
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> BitmapInfo {
+        let data = match &bitmap.data {
+            BitmapFormat::Rgba(data) => data.clone(),
+            BitmapFormat::Rgb(data) => {
+                let mut rgba = Vec::with_capacity(data.len() / 3 * 4);
+                for rgb in data.chunks_exact(3) {
+                    rgba.extend_from_slice(rgb);
+                    rgba.push(255);
+                }
+                rgba
+            }
+        };
+
+        let handle = BitmapHandle(self.textures.len());
+        let width = bitmap.width;
+        let height = bitmap.height;
+        self.textures.push(Texture {
+            width,
+            height,
+            data,
+        });
+
+        BitmapInfo {
+            handle,
+            width: width as u16,
+            height: height as u16,
+        }
+    }
+}
+
+impl RenderBackend for SoftwareRenderBackend {
+    fn set_viewport_dimensions(&mut self, width: u32, height: u32) {
+        self.layers.clear();
+        self.target.resize(width, height);
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        library: Option<&MovieLibrary<'_>>,
+    ) -> ShapeHandle {
+        let handle = ShapeHandle(self.meshes.len());
+        let mesh = self.register_shape_internal(shape, library);
+        self.meshes.push(mesh);
+        handle
+    }
+
+    fn replace_shape(
+        &mut self,
+        shape: DistilledShape,
+        library: Option<&MovieLibrary<'_>>,
+        handle: ShapeHandle,
+    ) {
+        let mesh = self.register_shape_internal(shape, library);
+        self.meshes[handle.0] = mesh;
+    }
+
+    fn register_glyph_shape(&mut self, glyph: &Glyph) -> ShapeHandle {
+        let shape = ruffle_core::shape_utils::swf_glyph_to_shape(glyph);
+        let handle = ShapeHandle(self.meshes.len());
+        let mesh = self.register_shape_internal((&shape).into(), None);
+        self.meshes.push(mesh);
+        handle
+    }
+
+    fn register_bitmap_jpeg(
+        &mut self,
+        data: &[u8],
+        jpeg_tables: Option<&[u8]>,
+    ) -> Result<BitmapInfo, Error> {
+        let data = ruffle_core::backend::render::glue_tables_to_jpeg(data, jpeg_tables);
+        self.register_bitmap_jpeg_2(&data[..])
+    }
+
+    fn register_bitmap_jpeg_2(&mut self, data: &[u8]) -> Result<BitmapInfo, Error> {
+        let bitmap = ruffle_core::backend::render::decode_define_bits_jpeg(data, None)?;
+        Ok(self.register_bitmap(bitmap))
+    }
+
+    fn register_bitmap_jpeg_3(
+        &mut self,
+        jpeg_data: &[u8],
+        alpha_data: &[u8],
+    ) -> Result<BitmapInfo, Error> {
+        let bitmap =
+            ruffle_core::backend::render::decode_define_bits_jpeg(jpeg_data, Some(alpha_data))?;
+        Ok(self.register_bitmap(bitmap))
+    }
+
+    fn register_bitmap_png(&mut self, swf_tag: &DefineBitsLossless) -> Result<BitmapInfo, Error> {
+        let bitmap = ruffle_core::backend::render::decode_define_bits_lossless(swf_tag)?;
+        Ok(self.register_bitmap(bitmap))
+    }
+
+    fn begin_frame(&mut self, clear: Color) {
+        if let Some((target, _, _)) = self.layers.drain(..).next() {
+            self.target = target;
+        }
+        self.mask_state = MaskState::NoMask;
+        self.num_masks = 0;
+        self.target.clear([clear.r, clear.g, clear.b, clear.a]);
+    }
+
+    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, smoothing: bool) {
+        if let Some(texture) = self.textures.get(bitmap.0) {
+            let (width, height) = (texture.width as f32, texture.height as f32);
+            let vertices = quad_vertices(width, height, 0xffff_ffff);
+            let paint = Paint::Bitmap {
+                texture,
+                matrix: [
+                    [1.0 / width, 0.0, 0.0],
+                    [0.0, 1.0 / height, 0.0],
+                    [0.0, 0.0, 1.0],
+                ],
+                is_smoothed: smoothing,
+                is_repeating: false,
+            };
+            self.target.draw_triangles(
+                &vertices,
+                &QUAD_INDICES,
+                &transform.matrix,
+                &transform.color_transform,
+                &paint,
+                self.mask_state,
+                self.num_masks,
+            );
+        }
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform) {
+        let mesh = if let Some(mesh) = self.meshes.get(shape.0) {
+            mesh
+        } else {
+            return;
+        };
+
+        for draw in mesh {
+            let paint = match &draw.draw_type {
+                DrawType::Color => Paint::Solid,
+                DrawType::Gradient(gradient) => Paint::Gradient(gradient),
+                DrawType::Bitmap(bitmap) => {
+                    if let Some(texture) = self.textures.get(bitmap.bitmap.0) {
+                        Paint::Bitmap {
+                            texture,
+                            matrix: bitmap.matrix,
+                            is_smoothed: bitmap.is_smoothed,
+                            is_repeating: bitmap.is_repeating,
+                        }
+                    } else {
+                        continue;
+                    }
+                }
+            };
+            self.target.draw_triangles(
+                &draw.vertices,
+                &draw.indices,
+                &transform.matrix,
+                &transform.color_transform,
+                &paint,
+                self.mask_state,
+                self.num_masks,
+            );
+        }
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: &Matrix) {
+        let color = ((color.a as u32) << 24)
+            | ((color.b as u32) << 16)
+            | ((color.g as u32) << 8)
+            | (color.r as u32);
+        let vertices = quad_vertices(1.0, 1.0, color);
+        self.target.draw_triangles(
+            &vertices,
+            &QUAD_INDICES,
+            matrix,
+            &ColorTransform::default(),
+            &Paint::Solid,
+            self.mask_state,
+            self.num_masks,
+        );
+    }
+
+    fn end_frame(&mut self) {}
+
+    fn push_mask(&mut self) {
+        debug_assert!(
+            self.mask_state == MaskState::NoMask || self.mask_state == MaskState::DrawMaskedContent
+        );
+        self.num_masks += 1;
+        self.mask_state = MaskState::DrawMaskStencil;
+    }
+
+    fn activate_mask(&mut self) {
+        debug_assert!(self.num_masks > 0 && self.mask_state == MaskState::DrawMaskStencil);
+        self.mask_state = MaskState::DrawMaskedContent;
+    }
+
+    fn deactivate_mask(&mut self) {
+        debug_assert!(self.num_masks > 0 && self.mask_state == MaskState::DrawMaskedContent);
+        self.mask_state = MaskState::ClearMaskStencil;
+    }
+
+    fn pop_mask(&mut self) {
+        debug_assert!(self.num_masks > 0 && self.mask_state == MaskState::ClearMaskStencil);
+        self.num_masks -= 1;
+        self.mask_state = if self.num_masks == 0 {
+            MaskState::NoMask
+        } else {
+            MaskState::DrawMaskedContent
+        };
+    }
+
+    fn push_layer(&mut self) {
+        let layer = self.target.new_layer();
+        let parent = std::mem::replace(&mut self.target, layer);
+        self.layers.push((parent, self.mask_state, self.num_masks));
+        self.mask_state = MaskState::NoMask;
+        self.num_masks = 0;
+    }
+
+    fn pop_layer(&mut self, filters: &[Filter], blend_mode: BlendMode) {
+        let (parent, mask_state, num_masks) = if let Some(parent) = self.layers.pop() {
+            parent
+        } else {
+            log::warn!("pop_layer: No layer to pop");
+            return;
+        };
+        let layer = std::mem::replace(&mut self.target, parent);
+        self.mask_state = mask_state;
+        self.num_masks = num_masks;
+
+        // Filters are applied at the output resolution, where their parameters are given.
+        let (width, height) = (layer.width(), layer.height());
+        let mut data = layer.resolve();
+        apply_filters(width, height, &mut data, filters);
+
+        // `Alpha` and `Erase` only affect an enclosing layer, not the stage itself.
+        let blend_mode = match blend_mode {
+            BlendMode::Alpha | BlendMode::Erase if self.layers.is_empty() => BlendMode::Normal,
+            blend_mode => blend_mode,
+        };
+        let is_drawing_content = matches!(
+            self.mask_state,
+            MaskState::NoMask | MaskState::DrawMaskedContent
+        );
+        if is_drawing_content && !matches!(blend_mode, BlendMode::Normal | BlendMode::Layer) {
+            self.target
+                .blend_layer(&data, blend_mode, self.mask_state, self.num_masks);
+            return;
+        }
+
+        let texture = Texture {
+            width,
+            height,
+            data,
+        };
+        let vertices = quad_vertices(width as f32, height as f32, 0xffff_ffff);
+        let paint = Paint::Bitmap {
+            texture: &texture,
+            matrix: [
+                [1.0 / width as f32, 0.0, 0.0],
+                [0.0, 1.0 / height as f32, 0.0],
+                [0.0, 0.0, 1.0],
+            ],
+            is_smoothed: false,
+            is_repeating: false,
+        };
+        self.target.draw_triangles(
+            &vertices,
+            &QUAD_INDICES,
+            &Matrix::identity(),
+            &ColorTransform::default(),
+            &paint,
+            self.mask_state,
+            self.num_masks,
+        );
+    }
+
+    fn pop_layer_to_bitmap(&mut self) -> Option<Bitmap> {
+        let (parent, mask_state, num_masks) = if let Some(parent) = self.layers.pop() {
+            parent
+        } else {
+            log::warn!("pop_layer_to_bitmap: No layer to pop");
+            return None;
+        };
+        let layer = std::mem::replace(&mut self.target, parent);
+        self.mask_state = mask_state;
+        self.num_masks = num_masks;
+
+        Some(Bitmap {
+            width: layer.width(),
+            height: layer.height(),
+            data: BitmapFormat::Rgba(layer.resolve()),
+        })
+    }
+
+    fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
+        // Textures are the only copy of a bitmap's pixels, so that updates made
+        // through `update_texture` are visible here.
+        self.textures.get(bitmap.0).map(|texture| Bitmap {
+            width: texture.width,
+            height: texture.height,
+            data: BitmapFormat::Rgba(texture.data.clone()),
+        })
+    }
+
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        Ok(self
+            .register_bitmap(Bitmap {
+                width,
+                height,
+                data: BitmapFormat::Rgba(rgba),
+            })
+            .handle)
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let texture = if let Some(texture) = self.textures.get_mut(handle.0) {
+            texture
+        } else {
+            return Err("update_texture: Bitmap not registered".into());
+        };
+
+        texture.width = width;
+        texture.height = height;
+        texture.data = rgba;
+
+        Ok(handle)
+    }
+}
+
+const QUAD_INDICES: [u32; 6] = [0, 1, 2, 0, 2, 3];
+
+fn quad_vertices(width: f32, height: f32, color: u32) -> [Vertex; 4] {
+    [
+        Vertex {
+            position: [0.0, 0.0],
+            color,
+        },
+        Vertex {
+            position: [width, 0.0],
+            color,
+        },
+        Vertex {
+            position: [width, height],
+            color,
+        },
+        Vertex {
+            position: [0.0, height],
+            color,
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruffle_core::swf::Twips;
+
+    fn pixel(renderer: &SoftwareRenderBackend, x: u32, y: u32) -> [u8; 4] {
+        let frame = renderer.capture_frame();
+        let i = ((y * renderer.width() + x) * 4) as usize;
+        [frame[i], frame[i + 1], frame[i + 2], frame[i + 3]]
+    }
+
+    fn rect(x: f64, y: f64, width: f32, height: f32) -> Matrix {
+        Matrix {
+            a: width,
+            d: height,
+            tx: Twips::from_pixels(x),
+            ty: Twips::from_pixels(y),
+            ..Default::default()
+        }
+    }
+
+    const WHITE: Color = Color {
+        r: 255,
+        g: 255,
+        b: 255,
+        a: 255,
+    };
+
+    const RED: Color = Color {
+        r: 255,
+        g: 0,
+        b: 0,
+        a: 255,
+    };
+
+    #[test]
+    fn draw_rect_fills_pixels() {
+        let mut renderer = SoftwareRenderBackend::new(8, 8);
+        renderer.begin_frame(WHITE);
+        renderer.draw_rect(RED, &rect(2.0, 2.0, 4.0, 4.0));
+        renderer.end_frame();
+
+        assert_eq!(pixel(&renderer, 0, 0), [255, 255, 255, 255]);
+        assert_eq!(pixel(&renderer, 2, 2), [255, 0, 0, 255]);
+        assert_eq!(pixel(&renderer, 5, 5), [255, 0, 0, 255]);
+        assert_eq!(pixel(&renderer, 6, 6), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn mask_clips_content() {
+        let mut renderer = SoftwareRenderBackend::new(8, 8);
+        renderer.begin_frame(WHITE);
+        renderer.push_mask();
+        renderer.draw_rect(WHITE, &rect(0.0, 0.0, 4.0, 8.0));
+        renderer.activate_mask();
+        renderer.draw_rect(RED, &rect(0.0, 0.0, 8.0, 8.0));
+        renderer.deactivate_mask();
+        renderer.draw_rect(WHITE, &rect(0.0, 0.0, 4.0, 8.0));
+        renderer.pop_mask();
+        renderer.end_frame();
+
+        assert_eq!(pixel(&renderer, 1, 4), [255, 0, 0, 255]);
+        assert_eq!(pixel(&renderer, 6, 4), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn bitmap_uses_color_transform() {
+        let mut renderer = SoftwareRenderBackend::with_supersampling(2, 2, 1);
+        let bitmap = renderer
+            .register_bitmap_raw(1, 1, vec![0, 0, 255, 255])
+            .unwrap();
+        renderer.begin_frame(WHITE);
+        renderer.render_bitmap(
+            bitmap,
+            &Transform {
+                matrix: Matrix::scale(2.0, 2.0),
+                color_transform: ColorTransform {
+                    r_add: 1.0,
+                    ..Default::default()
+                },
+            },
+            false,
+        );
+        renderer.end_frame();
+
+        assert_eq!(pixel(&renderer, 1, 1), [255, 0, 255, 255]);
+    }
+
+    #[test]
+    fn updated_texture_pixels_are_read_back() {
+        let mut renderer = SoftwareRenderBackend::new(2, 2);
+        let bitmap = renderer
+            .register_bitmap_raw(1, 1, vec![0, 0, 255, 255])
+            .unwrap();
+        renderer
+            .update_texture(bitmap, 2, 1, vec![255, 0, 0, 255, 0, 255, 0, 128])
+            .unwrap();
+
+        let pixels = renderer.get_bitmap_pixels(bitmap).unwrap();
+        assert_eq!((pixels.width, pixels.height), (2, 1));
+        if let BitmapFormat::Rgba(data) = pixels.data {
+            assert_eq!(data, vec![255, 0, 0, 255, 0, 255, 0, 128]);
+        } else {
+            panic!("Expected RGBA bitmap");
+        }
+    }
+
+    #[test]
+    fn layer_is_filtered_and_composited() {
+        use ruffle_core::bitmap::filters::ColorMatrixFilter;
+
+        // Swaps the red and blue channels.
+        let mut matrix = [0.0; 20];
+        matrix[2] = 1.0;
+        matrix[6] = 1.0;
+        matrix[10] = 1.0;
+        matrix[18] = 1.0;
+
+        let mut renderer = SoftwareRenderBackend::new(8, 8);
+        renderer.begin_frame(WHITE);
+        renderer.push_layer();
+        renderer.draw_rect(RED, &rect(2.0, 2.0, 4.0, 4.0));
+        renderer.pop_layer(
+            &[Filter::ColorMatrix(ColorMatrixFilter { matrix })],
+            BlendMode::Normal,
+        );
+        renderer.end_frame();
+
+        assert_eq!(pixel(&renderer, 0, 0), [255, 255, 255, 255]);
+        assert_eq!(pixel(&renderer, 3, 3), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn layer_uses_blend_mode() {
+        let mut renderer = SoftwareRenderBackend::new(8, 8);
+        renderer.begin_frame(RED);
+        renderer.push_layer();
+        renderer.draw_rect(WHITE, &rect(0.0, 0.0, 4.0, 8.0));
+        renderer.pop_layer(&[], BlendMode::Difference);
+        renderer.end_frame();
+
+        assert_eq!(pixel(&renderer, 1, 1), [0, 255, 255, 255]);
+        assert_eq!(pixel(&renderer, 6, 1), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn layer_to_bitmap_is_not_composited() {
+        let mut renderer = SoftwareRenderBackend::new(8, 8);
+        renderer.begin_frame(WHITE);
+        renderer.push_layer();
+        renderer.draw_rect(RED, &rect(2.0, 2.0, 4.0, 4.0));
+        let bitmap = renderer.pop_layer_to_bitmap().unwrap();
+        renderer.end_frame();
+
+        assert_eq!((bitmap.width, bitmap.height), (8, 8));
+        if let BitmapFormat::Rgba(data) = bitmap.data {
+            assert_eq!(data[..4], [0, 0, 0, 0]);
+            assert_eq!(data[(3 * 8 + 3) * 4..][..4], [255, 0, 0, 255]);
+        } else {
+            panic!("Expected RGBA bitmap");
+        }
+        assert_eq!(pixel(&renderer, 3, 3), [255, 255, 255, 255]);
+    }
+}
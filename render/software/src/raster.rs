@@ -0,0 +1,576 @@
+//! Triangle rasterization into a CPU-side color and stencil buffer.
+
+use ruffle_core::bitmap::blend::blend_rgba;
+use ruffle_core::color_transform::ColorTransform;
+use ruffle_core::swf::{BlendMode, GradientInterpolation, Matrix};
+use ruffle_render_common_tess::{Gradient, GradientSpread, GradientType, Vertex};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaskState {
+    NoMask,
+    DrawMaskStencil,
+    DrawMaskedContent,
+    ClearMaskStencil,
+}
+
+/// A bitmap registered with the software renderer.
+/// The pixels are stored as RGBA with premultiplied alpha.
+#[derive(Debug, Clone)]
+pub struct Texture {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+impl Texture {
+    /// Returns the premultiplied color of the texel at the given position.
+    fn texel(&self, x: i32, y: i32) -> [f32; 4] {
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        [
+            f32::from(self.data[i]) / 255.0,
+            f32::from(self.data[i + 1]) / 255.0,
+            f32::from(self.data[i + 2]) / 255.0,
+            f32::from(self.data[i + 3]) / 255.0,
+        ]
+    }
+
+    /// Samples this texture at the given normalized texture coordinates.
+    fn sample(&self, u: f32, v: f32, is_smoothed: bool, is_repeating: bool) -> [f32; 4] {
+        if self.width == 0 || self.height == 0 {
+            return [0.0; 4];
+        }
+
+        let (width, height) = (self.width as i32, self.height as i32);
+        let wrap = |n: i32, size: i32| {
+            if is_repeating {
+                n.rem_euclid(size)
+            } else {
+                n.max(0).min(size - 1)
+            }
+        };
+
+        let x = u * self.width as f32;
+        let y = v * self.height as f32;
+        if !is_smoothed {
+            return self.texel(
+                wrap(x.floor() as i32, width),
+                wrap(y.floor() as i32, height),
+            );
+        }
+
+        let x = x - 0.5;
+        let y = y - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i32, y0 as i32);
+        let (x1, y1) = (wrap(x0 + 1, width), wrap(y0 + 1, height));
+        let (x0, y0) = (wrap(x0, width), wrap(y0, height));
+
+        let c00 = self.texel(x0, y0);
+        let c10 = self.texel(x1, y0);
+        let c01 = self.texel(x0, y1);
+        let c11 = self.texel(x1, y1);
+        let mut out = [0.0; 4];
+        for i in 0..4 {
+            let top = c00[i] + (c10[i] - c00[i]) * fx;
+            let bottom = c01[i] + (c11[i] - c01[i]) * fx;
+            out[i] = top + (bottom - top) * fy;
+        }
+        out
+    }
+}
+
+/// How the pixels covered by a triangle are colored.
+pub enum Paint<'a> {
+    /// Use the color of the vertices.
+    Solid,
+
+    /// A gradient fill, with the matrix mapping shape space to gradient space.
+    Gradient(&'a Gradient),
+
+    /// A bitmap fill, with the matrix mapping shape space to texture coordinates.
+    Bitmap {
+        texture: &'a Texture,
+        matrix: [[f32; 3]; 3],
+        is_smoothed: bool,
+        is_repeating: bool,
+    },
+}
+
+/// The color and stencil buffers that the renderer draws into.
+///
+/// Everything is rendered at `supersampling` times the output resolution and
+/// averaged down when captured, which gives us antialiasing without having to
+/// worry about seams between adjacent triangles.
+pub struct RasterTarget {
+    width: u32,
+    height: u32,
+    supersampling: u32,
+
+    /// Premultiplied RGBA samples.
+    color: Vec<u8>,
+
+    /// Mask depth of each sample, mirroring the stencil buffer of the GPU backends.
+    stencil: Vec<u8>,
+}
+
+impl RasterTarget {
+    pub fn new(width: u32, height: u32, supersampling: u32) -> Self {
+        let supersampling = supersampling.max(1);
+        let mut target = Self {
+            width: 0,
+            height: 0,
+            supersampling,
+            color: Vec::new(),
+            stencil: Vec::new(),
+        };
+        target.resize(width, height);
+        target
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+        let num_samples = self.sample_width() as usize * self.sample_height() as usize;
+        self.color = vec![0; num_samples * 4];
+        self.stencil = vec![0; num_samples];
+    }
+
+    fn sample_width(&self) -> u32 {
+        self.width * self.supersampling
+    }
+
+    fn sample_height(&self) -> u32 {
+        self.height * self.supersampling
+    }
+
+    /// Fills the whole target with the given color and resets the stencil.
+    pub fn clear(&mut self, color: [u8; 4]) {
+        let a = f32::from(color[3]) / 255.0;
+        let premultiplied = [
+            (f32::from(color[0]) * a) as u8,
+            (f32::from(color[1]) * a) as u8,
+            (f32::from(color[2]) * a) as u8,
+            color[3],
+        ];
+        for pixel in self.color.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&premultiplied);
+        }
+        for stencil in &mut self.stencil {
+            *stencil = 0;
+        }
+    }
+
+    /// Resolves the supersampled buffer into straight (non-premultiplied) RGBA
+    /// at the output resolution.
+    pub fn capture(&self) -> Vec<u8> {
+        let mut out = self.resolve();
+        for pixel in out.chunks_exact_mut(4) {
+            let a = u32::from(pixel[3]);
+            for c in pixel.iter_mut().take(3) {
+                *c = if a > 0 {
+                    (u32::from(*c) * 255 / a).min(255) as u8
+                } else {
+                    0
+                };
+            }
+        }
+        out
+    }
+
+    /// Averages the supersampled buffer down to premultiplied RGBA at the output resolution.
+    pub fn resolve(&self) -> Vec<u8> {
+        let ss = self.supersampling as usize;
+        let sample_width = self.sample_width() as usize;
+        let num_samples = (ss * ss) as u32;
+        let mut out = Vec::with_capacity(self.width as usize * self.height as usize * 4);
+        for y in 0..self.height as usize {
+            for x in 0..self.width as usize {
+                let mut sum = [0u32; 4];
+                for sy in 0..ss {
+                    let row = (y * ss + sy) * sample_width;
+                    for sx in 0..ss {
+                        let i = (row + x * ss + sx) * 4;
+                        for c in 0..4 {
+                            sum[c] += u32::from(self.color[i + c]);
+                        }
+                    }
+                }
+                for c in &sum {
+                    out.push((c / num_samples) as u8);
+                }
+            }
+        }
+        out
+    }
+
+    /// Creates an empty, transparent target with the same size as this one.
+    pub fn new_layer(&self) -> Self {
+        Self::new(self.width, self.height, self.supersampling)
+    }
+
+    /// Rasterizes a triangle list.
+    ///
+    /// `matrix` maps the vertex positions (in pixels) into the stage, and
+    /// `mask_state`/`num_masks` decide how the stencil is tested and updated.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_triangles(
+        &mut self,
+        vertices: &[Vertex],
+        indices: &[u32],
+        matrix: &Matrix,
+        color_transform: &ColorTransform,
+        paint: &Paint,
+        mask_state: MaskState,
+        num_masks: u32,
+    ) {
+        let ss = self.supersampling as f32;
+        let tx = matrix.tx.to_pixels() as f32;
+        let ty = matrix.ty.to_pixels() as f32;
+        let to_screen = |v: &Vertex| {
+            let [x, y] = v.position;
+            [
+                (matrix.a * x + matrix.c * y + tx) * ss,
+                (matrix.b * x + matrix.d * y + ty) * ss,
+            ]
+        };
+
+        for triangle in indices.chunks_exact(3) {
+            let v0 = &vertices[triangle[0] as usize];
+            let mut v1 = &vertices[triangle[1] as usize];
+            let mut v2 = &vertices[triangle[2] as usize];
+            let p0 = to_screen(v0);
+            let mut p1 = to_screen(v1);
+            let mut p2 = to_screen(v2);
+
+            let mut area = edge(p0, p1, p2);
+            if area == 0.0 || !area.is_finite() {
+                continue;
+            }
+            if area < 0.0 {
+                std::mem::swap(&mut v1, &mut v2);
+                std::mem::swap(&mut p1, &mut p2);
+                area = -area;
+            }
+
+            self.fill_triangle(
+                [p0, p1, p2],
+                [v0, v1, v2],
+                area,
+                color_transform,
+                paint,
+                mask_state,
+                num_masks,
+            );
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn fill_triangle(
+        &mut self,
+        points: [[f32; 2]; 3],
+        vertices: [&Vertex; 3],
+        area: f32,
+        color_transform: &ColorTransform,
+        paint: &Paint,
+        mask_state: MaskState,
+        num_masks: u32,
+    ) {
+        let [p0, p1, p2] = points;
+        let sample_width = self.sample_width();
+        let sample_height = self.sample_height();
+        let min_x = p0[0].min(p1[0]).min(p2[0]).floor().max(0.0) as u32;
+        let min_y = p0[1].min(p1[1]).min(p2[1]).floor().max(0.0) as u32;
+        let max_x = (p0[0].max(p1[0]).max(p2[0]).ceil().max(0.0) as u32).min(sample_width);
+        let max_y = (p0[1].max(p1[1]).max(p2[1]).ceil().max(0.0) as u32).min(sample_height);
+
+        let top_left = [
+            is_top_left(p1, p2),
+            is_top_left(p2, p0),
+            is_top_left(p0, p1),
+        ];
+        let solid_color = color_from_vertex(vertices[0].color);
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let p = [x as f32 + 0.5, y as f32 + 0.5];
+                let w = [edge(p1, p2, p), edge(p2, p0, p), edge(p0, p1, p)];
+                let inside = w
+                    .iter()
+                    .zip(top_left.iter())
+                    .all(|(&w, &top_left)| w > 0.0 || (w == 0.0 && top_left));
+                if !inside {
+                    continue;
+                }
+
+                let index = (y * sample_width + x) as usize;
+                let stencil = &mut self.stencil[index];
+                match mask_state {
+                    MaskState::NoMask => (),
+                    MaskState::DrawMaskStencil => {
+                        if u32::from(*stencil) + 1 == num_masks {
+                            *stencil = stencil.wrapping_add(1);
+                        }
+                        continue;
+                    }
+                    MaskState::DrawMaskedContent => {
+                        if u32::from(*stencil) != num_masks {
+                            continue;
+                        }
+                    }
+                    MaskState::ClearMaskStencil => {
+                        if u32::from(*stencil) == num_masks {
+                            *stencil = stencil.wrapping_sub(1);
+                        }
+                        continue;
+                    }
+                }
+
+                let src = match paint {
+                    Paint::Solid => {
+                        premultiply(apply_color_transform(solid_color, color_transform))
+                    }
+                    Paint::Gradient(gradient) => {
+                        let local = interpolate_position(vertices, w, area);
+                        let [u, v] = apply_gl_matrix(&gradient.matrix, local);
+                        premultiply(apply_color_transform(
+                            sample_gradient(gradient, u, v),
+                            color_transform,
+                        ))
+                    }
+                    Paint::Bitmap {
+                        texture,
+                        matrix,
+                        is_smoothed,
+                        is_repeating,
+                    } => {
+                        let local = interpolate_position(vertices, w, area);
+                        let [u, v] = apply_gl_matrix(matrix, local);
+                        let color = texture.sample(u, v, *is_smoothed, *is_repeating);
+                        if color_transform.is_identity() {
+                            color
+                        } else {
+                            premultiply(apply_color_transform(unmultiply(color), color_transform))
+                        }
+                    }
+                };
+
+                self.blend_sample(index, src);
+            }
+        }
+    }
+
+    /// Composites premultiplied RGBA pixels at the output resolution onto this target
+    /// using `blend_mode`. Samples hidden by the current mask are left untouched.
+    pub fn blend_layer(
+        &mut self,
+        layer: &[u8],
+        blend_mode: BlendMode,
+        mask_state: MaskState,
+        num_masks: u32,
+    ) {
+        let ss = self.supersampling as usize;
+        let sample_width = self.sample_width() as usize;
+        let width = self.width as usize;
+        for (index, stencil) in self.stencil.iter().enumerate() {
+            if mask_state == MaskState::DrawMaskedContent && u32::from(*stencil) != num_masks {
+                continue;
+            }
+            let (x, y) = (index % sample_width / ss, index / sample_width / ss);
+            let src = (y * width + x) * 4;
+            blend_rgba(
+                blend_mode,
+                &layer[src..src + 4],
+                &mut self.color[index * 4..index * 4 + 4],
+            );
+        }
+    }
+
+    /// Composites a premultiplied color over the sample at `index`.
+    fn blend_sample(&mut self, index: usize, src: [f32; 4]) {
+        let dst = &mut self.color[index * 4..index * 4 + 4];
+        let inv_alpha = 1.0 - src[3];
+        for c in 0..4 {
+            let out = src[c] * 255.0 + f32::from(dst[c]) * inv_alpha;
+            dst[c] = out.round().max(0.0).min(255.0) as u8;
+        }
+    }
+}
+
+fn edge(a: [f32; 2], b: [f32; 2], p: [f32; 2]) -> f32 {
+    (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])
+}
+
+/// Fill rule for samples lying exactly on an edge, so that triangles sharing an
+/// edge never both draw the same sample.
+fn is_top_left(a: [f32; 2], b: [f32; 2]) -> bool {
+    let dx = b[0] - a[0];
+    let dy = b[1] - a[1];
+    dy < 0.0 || (dy == 0.0 && dx > 0.0)
+}
+
+/// Returns the position in shape space for the given barycentric weights.
+fn interpolate_position(vertices: [&Vertex; 3], w: [f32; 3], area: f32) -> [f32; 2] {
+    let mut out = [0.0; 2];
+    for (vertex, weight) in vertices.iter().zip(w.iter()) {
+        out[0] += vertex.position[0] * weight / area;
+        out[1] += vertex.position[1] * weight / area;
+    }
+    out
+}
+
+/// Applies a column-major 3x3 matrix as generated by `ruffle_render_common_tess`.
+fn apply_gl_matrix(m: &[[f32; 3]; 3], p: [f32; 2]) -> [f32; 2] {
+    [
+        m[0][0] * p[0] + m[1][0] * p[1] + m[2][0],
+        m[0][1] * p[0] + m[1][1] * p[1] + m[2][1],
+    ]
+}
+
+/// Unpacks a vertex color from the tessellator into straight RGBA.
+fn color_from_vertex(color: u32) -> [f32; 4] {
+    [
+        (color & 0xff) as f32 / 255.0,
+        ((color >> 8) & 0xff) as f32 / 255.0,
+        ((color >> 16) & 0xff) as f32 / 255.0,
+        ((color >> 24) & 0xff) as f32 / 255.0,
+    ]
+}
+
+pub fn apply_color_transform(color: [f32; 4], transform: &ColorTransform) -> [f32; 4] {
+    [
+        (color[0] * transform.r_mult + transform.r_add)
+            .max(0.0)
+            .min(1.0),
+        (color[1] * transform.g_mult + transform.g_add)
+            .max(0.0)
+            .min(1.0),
+        (color[2] * transform.b_mult + transform.b_add)
+            .max(0.0)
+            .min(1.0),
+        (color[3] * transform.a_mult + transform.a_add)
+            .max(0.0)
+            .min(1.0),
+    ]
+}
+
+pub fn premultiply(color: [f32; 4]) -> [f32; 4] {
+    [
+        color[0] * color[3],
+        color[1] * color[3],
+        color[2] * color[3],
+        color[3],
+    ]
+}
+
+pub fn unmultiply(color: [f32; 4]) -> [f32; 4] {
+    if color[3] > 0.0 {
+        [
+            color[0] / color[3],
+            color[1] / color[3],
+            color[2] / color[3],
+            color[3],
+        ]
+    } else {
+        color
+    }
+}
+
+/// Computes the color of a gradient at the given point in gradient space.
+/// This mirrors the gradient shaders of the GPU backends.
+fn sample_gradient(gradient: &Gradient, u: f32, v: f32) -> [f32; 4] {
+    let mut t = match gradient.gradient_type {
+        GradientType::Linear => u,
+        GradientType::Radial => {
+            let (x, y) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+            (x * x + y * y).sqrt()
+        }
+        GradientType::Focal => {
+            let focal_point = gradient.focal_point;
+            let (x, y) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+            let (dx, dy) = (focal_point - x, -y);
+            let l = (dx * dx + dy * dy).sqrt();
+            if l > 0.0 {
+                let (dx, dy) = (dx / l, dy / l);
+                l / ((1.0 - focal_point * focal_point * dy * dy).sqrt() + focal_point * dx)
+            } else {
+                0.0
+            }
+        }
+    };
+
+    t = match gradient.repeat_mode {
+        GradientSpread::Pad => t.max(0.0).min(1.0),
+        GradientSpread::Repeat => t - t.floor(),
+        GradientSpread::Reflect => {
+            let t = t.abs();
+            if (t % 2.0) < 1.0 {
+                t.fract()
+            } else {
+                1.0 - t.fract()
+            }
+        }
+    };
+
+    let num_colors = (gradient.num_colors as usize)
+        .min(gradient.colors.len())
+        .min(gradient.ratios.len());
+    if num_colors == 0 {
+        return [0.0; 4];
+    }
+
+    let is_linear = gradient.interpolation == GradientInterpolation::LinearRGB;
+    let color_at = |i: usize| {
+        if is_linear {
+            ruffle_core::backend::render::srgb_to_linear(gradient.colors[i])
+        } else {
+            gradient.colors[i]
+        }
+    };
+
+    let ratios = &gradient.ratios;
+    let mut color = color_at(num_colors - 1);
+    if t <= ratios[0] {
+        color = color_at(0);
+    } else {
+        for i in 1..num_colors {
+            if t <= ratios[i] {
+                let span = ratios[i] - ratios[i - 1];
+                let a = if span > 0.0 {
+                    (t - ratios[i - 1]) / span
+                } else {
+                    1.0
+                };
+                let (from, to) = (color_at(i - 1), color_at(i));
+                for c in 0..4 {
+                    color[c] = from[c] + (to[c] - from[c]) * a;
+                }
+                break;
+            }
+        }
+    }
+
+    if is_linear {
+        for c in color.iter_mut().take(3) {
+            *c = linear_to_srgb(*c);
+        }
+    }
+
+    color
+}
+
+fn linear_to_srgb(n: f32) -> f32 {
+    if n <= 0.003_130_8 {
+        n * 12.92
+    } else {
+        1.055 * n.powf(1.0 / 2.4) - 0.055
+    }
+}
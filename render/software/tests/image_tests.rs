@@ -0,0 +1,175 @@
+//! Renders SWFs with the software renderer, and checks the colors of the output.
+//!
+//! The test movies only draw axis-aligned rectangles on whole pixels, so the checked
+//! pixels aren't affected by antialiasing.
+
+use ruffle_core::backend::audio::NullAudioBackend;
+use ruffle_core::backend::locale::NullLocaleBackend;
+use ruffle_core::backend::log::NullLogBackend;
+use ruffle_core::backend::navigator::NullNavigatorBackend;
+use ruffle_core::backend::storage::MemoryStorageBackend;
+use ruffle_core::backend::ui::NullUiBackend;
+use ruffle_core::tag_utils::SwfMovie;
+use ruffle_core::Player;
+use ruffle_render_software::SoftwareRenderBackend;
+use std::sync::Arc;
+
+type Error = Box<dyn std::error::Error>;
+
+const WHITE: [u8; 4] = [255, 255, 255, 255];
+const RED: [u8; 4] = [255, 0, 0, 255];
+const GREEN: [u8; 4] = [0, 255, 0, 255];
+const BLUE: [u8; 4] = [0, 0, 255, 255];
+const YELLOW: [u8; 4] = [255, 255, 0, 255];
+
+/// A single rendered frame.
+struct Capture {
+    width: u32,
+    pixels: Vec<u8>,
+}
+
+impl Capture {
+    fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * self.width + x) * 4) as usize;
+        [
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ]
+    }
+}
+
+/// Runs an SWF for a number of frames, and captures every frame after it's rendered.
+fn render_swf(swf_path: &str, num_frames: u32) -> Result<Vec<Capture>, Error> {
+    let movie = SwfMovie::from_path(swf_path)?;
+    let (width, height) = (movie.width(), movie.height());
+
+    let player = Player::new(
+        Box::new(SoftwareRenderBackend::new(width, height)),
+        Box::new(NullAudioBackend::new()),
+        Box::new(NullNavigatorBackend::new()),
+        Box::new(MemoryStorageBackend::default()),
+        Box::new(NullLocaleBackend::new()),
+        Box::new(NullLogBackend::new()),
+        Box::new(NullUiBackend::new()),
+    )?;
+    let mut player = player.lock().unwrap();
+    player.set_viewport_dimensions(width, height);
+    player.set_root_movie(Arc::new(movie));
+
+    let mut captures = Vec::new();
+    for _ in 0..num_frames {
+        player.run_frame();
+        player.render();
+        let renderer = player
+            .renderer_mut()
+            .downcast_mut::<SoftwareRenderBackend>()
+            .unwrap();
+        captures.push(Capture {
+            width: renderer.width(),
+            pixels: renderer.capture_frame(),
+        });
+    }
+    Ok(captures)
+}
+
+#[test]
+fn scroll_rect() -> Result<(), Error> {
+    let frames = render_swf("tests/swfs/scroll_rect/test.swf", 2)?;
+
+    // The blue square is scrolled to the origin of the clip, and the rest is clipped.
+    assert_eq!(frames[0].pixel(15, 15), BLUE);
+    assert_eq!(frames[0].pixel(22, 22), WHITE);
+    assert_eq!(frames[0].pixel(5, 5), WHITE);
+
+    // Scrolling back to the origin of the contents shows the red corner.
+    assert_eq!(frames[1].pixel(15, 15), RED);
+    assert_eq!(frames[1].pixel(22, 22), WHITE);
+    Ok(())
+}
+
+#[test]
+fn opaque_background() -> Result<(), Error> {
+    let frames = render_swf("tests/swfs/opaque_background/test.swf", 1)?;
+
+    assert_eq!(frames[0].pixel(12, 12), RED);
+    assert_eq!(frames[0].pixel(27, 27), RED);
+    assert_eq!(frames[0].pixel(20, 20), GREEN);
+    assert_eq!(frames[0].pixel(12, 27), GREEN);
+    assert_eq!(frames[0].pixel(5, 5), WHITE);
+    assert_eq!(frames[0].pixel(35, 35), WHITE);
+    Ok(())
+}
+
+#[test]
+fn cache_as_bitmap() -> Result<(), Error> {
+    let frames = render_swf("tests/swfs/cache_as_bitmap/test.swf", 4)?;
+
+    assert_eq!(frames[0].pixel(12, 12), RED);
+    assert_eq!(frames[0].pixel(7, 7), YELLOW);
+    assert_eq!(frames[0].pixel(20, 20), WHITE);
+
+    // Redrawing the clip updates its bitmap.
+    assert_eq!(frames[1].pixel(12, 12), BLUE);
+    assert_eq!(frames[1].pixel(7, 7), YELLOW);
+
+    // Moving the clip draws its bitmap somewhere else.
+    assert_eq!(frames[2].pixel(12, 12), WHITE);
+    assert_eq!(frames[2].pixel(32, 12), BLUE);
+    assert_eq!(frames[2].pixel(27, 7), YELLOW);
+
+    // Moving a child updates the bitmap of its parent.
+    assert_eq!(frames[3].pixel(27, 7), BLUE);
+    assert_eq!(frames[3].pixel(31, 7), YELLOW);
+    Ok(())
+}
+
+#[test]
+fn cache_as_bitmap_larger_than_stage() -> Result<(), Error> {
+    let frames = render_swf("tests/swfs/cache_as_bitmap_large/test.swf", 2)?;
+
+    assert_eq!(frames[0].pixel(5, 5), RED);
+    assert_eq!(frames[0].pixel(5, 15), WHITE);
+
+    // The part of the bitmap that was off the stage is drawn once the clip moves.
+    assert_eq!(frames[1].pixel(5, 5), BLUE);
+    assert_eq!(frames[1].pixel(15, 5), RED);
+    assert_eq!(frames[1].pixel(25, 5), WHITE);
+    Ok(())
+}
+
+#[test]
+fn scale9_grid() -> Result<(), Error> {
+    let frames = render_swf("tests/swfs/scale9_grid/test.swf", 1)?;
+
+    // The clip is stretched to 90x45, but its corners stay 10x10.
+    for &(x, y) in &[
+        (7, 7),
+        (13, 13),
+        (87, 7),
+        (93, 13),
+        (7, 42),
+        (13, 48),
+        (93, 48),
+    ] {
+        assert_eq!(frames[0].pixel(x, y), RED, "corner pixel at ({}, {})", x, y);
+    }
+
+    // The edges are only stretched along their length, and the center in both directions.
+    for &(x, y) in &[
+        (20, 8),
+        (80, 8),
+        (8, 18),
+        (8, 37),
+        (90, 20),
+        (50, 45),
+        (50, 27),
+    ] {
+        assert_eq!(frames[0].pixel(x, y), BLUE, "edge pixel at ({}, {})", x, y);
+    }
+
+    assert_eq!(frames[0].pixel(97, 27), WHITE);
+    assert_eq!(frames[0].pixel(50, 52), WHITE);
+    Ok(())
+}
@@ -0,0 +1,52 @@
+﻿package {
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.display.Sprite;
+	import flash.events.Event;
+
+	public class Test extends MovieClip {
+		var frame:int = 1;
+		var clip:Sprite;
+		var dot:Shape;
+
+		public function Test() {
+			this.clip = new Sprite();
+			this.clip.graphics.beginFill(0xFF0000);
+			this.clip.graphics.drawRect(0, 0, 10, 10);
+			this.clip.graphics.endFill();
+			this.clip.x = 5;
+			this.clip.y = 5;
+
+			this.dot = new Shape();
+			this.dot.graphics.beginFill(0xFFFF00);
+			this.dot.graphics.drawRect(0, 0, 2, 2);
+			this.dot.graphics.endFill();
+			this.dot.x = 2;
+			this.dot.y = 2;
+			this.clip.addChild(this.dot);
+
+			this.clip.cacheAsBitmap = true;
+			this.addChild(this.clip);
+
+			this.addEventListener(Event.ENTER_FRAME, this.onEnterFrame);
+		}
+
+		function onEnterFrame(event:Event) {
+			this.frame++;
+			if (this.frame == 2) {
+				// Redrawing the clip invalidates its bitmap.
+				this.clip.graphics.clear();
+				this.clip.graphics.beginFill(0x0000FF);
+				this.clip.graphics.drawRect(0, 0, 10, 10);
+				this.clip.graphics.endFill();
+			} else if (this.frame == 3) {
+				// Moving the clip reuses its bitmap.
+				this.clip.x = 25;
+			} else if (this.frame == 4) {
+				// Moving a child invalidates the bitmap of its parent.
+				this.dot.x = 6;
+				this.removeEventListener(Event.ENTER_FRAME, this.onEnterFrame);
+			}
+		}
+	}
+}
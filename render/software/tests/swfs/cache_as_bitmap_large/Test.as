@@ -0,0 +1,30 @@
+﻿package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.events.Event;
+
+	public class Test extends MovieClip {
+		var clip:Sprite;
+
+		public function Test() {
+			// The clip is wider than the stage, so its bitmap is rendered in several parts.
+			this.clip = new Sprite();
+			this.clip.graphics.beginFill(0xFF0000);
+			this.clip.graphics.drawRect(0, 0, 100, 10);
+			this.clip.graphics.endFill();
+			this.clip.graphics.beginFill(0x0000FF);
+			this.clip.graphics.drawRect(80, 0, 10, 10);
+			this.clip.graphics.endFill();
+			this.clip.cacheAsBitmap = true;
+			this.addChild(this.clip);
+
+			this.addEventListener(Event.ENTER_FRAME, this.onEnterFrame);
+		}
+
+		function onEnterFrame(event:Event) {
+			// Moving the clip reuses its bitmap, which has to include what was off the stage.
+			this.clip.x = -80;
+			this.removeEventListener(Event.ENTER_FRAME, this.onEnterFrame);
+		}
+	}
+}
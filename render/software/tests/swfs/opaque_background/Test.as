@@ -0,0 +1,20 @@
+﻿package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var clip:Sprite = new Sprite();
+			clip.graphics.beginFill(0xFF0000);
+			clip.graphics.drawRect(0, 0, 5, 5);
+			clip.graphics.drawRect(15, 15, 5, 5);
+			clip.graphics.endFill();
+			clip.x = 10;
+			clip.y = 10;
+
+			// The background fills the bounds of the clip, behind its contents.
+			clip.opaqueBackground = 0x00FF00;
+			this.addChild(clip);
+		}
+	}
+}
@@ -0,0 +1,29 @@
+﻿package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.geom.Rectangle;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			// A blue square with red 10x10 corners.
+			var clip:Sprite = new Sprite();
+			clip.graphics.beginFill(0x0000FF);
+			clip.graphics.drawRect(0, 0, 30, 30);
+			clip.graphics.endFill();
+			clip.graphics.beginFill(0xFF0000);
+			clip.graphics.drawRect(0, 0, 10, 10);
+			clip.graphics.drawRect(20, 0, 10, 10);
+			clip.graphics.drawRect(0, 20, 10, 10);
+			clip.graphics.drawRect(20, 20, 10, 10);
+			clip.graphics.endFill();
+			clip.x = 5;
+			clip.y = 5;
+
+			// The corners keep their size, and only the edges and center are stretched.
+			clip.scale9Grid = new Rectangle(10, 10, 10, 10);
+			clip.scaleX = 3;
+			clip.scaleY = 1.5;
+			this.addChild(clip);
+		}
+	}
+}
@@ -0,0 +1,34 @@
+﻿package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.geom.Rectangle;
+
+	public class Test extends MovieClip {
+		var clip:Sprite;
+
+		public function Test() {
+			this.clip = new Sprite();
+			this.clip.graphics.beginFill(0xFF0000);
+			this.clip.graphics.drawRect(0, 0, 40, 40);
+			this.clip.graphics.endFill();
+			this.clip.graphics.beginFill(0x0000FF);
+			this.clip.graphics.drawRect(10, 10, 10, 10);
+			this.clip.graphics.endFill();
+			this.clip.x = 10;
+			this.clip.y = 10;
+
+			// Only the blue square is visible, moved to the origin of the clip.
+			this.clip.scrollRect = new Rectangle(10, 10, 10, 10);
+			this.addChild(this.clip);
+
+			this.addEventListener(Event.ENTER_FRAME, this.onEnterFrame);
+		}
+
+		function onEnterFrame(event:Event) {
+			// Scrolls back to the red corner.
+			this.clip.scrollRect = new Rectangle(0, 0, 10, 10);
+			this.removeEventListener(Event.ENTER_FRAME, this.onEnterFrame);
+		}
+	}
+}
@@ -1,7 +1,7 @@
 use ruffle_core::backend::render::swf;
 use ruffle_core::backend::render::{
-    srgb_to_linear, Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, MovieLibrary,
-    RenderBackend, ShapeHandle, Transform,
+    srgb_to_linear, Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, BlendMode, Color, Filter,
+    MovieLibrary, RenderBackend, ShapeHandle, Transform,
 };
 use ruffle_core::shape_utils::DistilledShape;
 use ruffle_core::swf::Matrix;
@@ -1329,6 +1329,16 @@ impl RenderBackend for WebGlRenderBackend {
         self.mask_state_dirty = true;
     }
 
+    // TODO: Filters and blend modes aren't supported yet, so the contents of layers
+    // are drawn as if they were part of the layer below.
+    fn push_layer(&mut self) {}
+
+    fn pop_layer(&mut self, _filters: &[Filter], _blend_mode: BlendMode) {}
+
+    fn pop_layer_to_bitmap(&mut self) -> Option<Bitmap> {
+        None
+    }
+
     fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
         self.bitmap_registry.get(&bitmap).cloned()
     }
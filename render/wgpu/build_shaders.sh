@@ -4,3 +4,8 @@ glslangValidator -V ./shaders/color.vert -o ./shaders/color.vert.spv
 glslangValidator -V ./shaders/bitmap.frag -o ./shaders/bitmap.frag.spv
 glslangValidator -V ./shaders/gradient.frag -o ./shaders/gradient.frag.spv
 glslangValidator -V ./shaders/texture.vert -o ./shaders/texture.vert.spv
+glslangValidator -V ./shaders/blend.frag -o ./shaders/blend.frag.spv
+glslangValidator -V ./shaders/filter.vert -o ./shaders/filter.vert.spv
+glslangValidator -V ./shaders/blur.frag -o ./shaders/blur.frag.spv
+glslangValidator -V ./shaders/shadow.frag -o ./shaders/shadow.frag.spv
+glslangValidator -V ./shaders/color_matrix.frag -o ./shaders/color_matrix.frag.spv
@@ -0,0 +1,53 @@
+#version 450
+
+// Push constants: matrix + blend mode
+layout(push_constant) uniform FragmentPushConstants {
+    layout(offset = 64) int blend_mode;
+};
+
+// Set 1: the layer, and a copy of the layer below it
+layout(set = 1, binding = 1) uniform texture2D t_layer;
+layout(set = 1, binding = 2) uniform texture2D t_backdrop;
+
+// Set 2: sampler
+layout(set = 2, binding = 0) uniform sampler s_color;
+
+layout(location=0) in vec2 frag_uv;
+
+layout(location=0) out vec4 out_color;
+
+vec3 hard_light(vec3 s, vec3 d) {
+    vec3 screen = 2.0 * s - 1.0;
+    return mix(d * 2.0 * s, d + screen - d * screen, step(0.5, s));
+}
+
+void main() {
+    vec4 src = texture(sampler2D(t_layer, s_color), frag_uv);
+    vec4 dst = texture(sampler2D(t_backdrop, s_color), frag_uv);
+
+    // The blend functions work on unmultiplied colors.
+    vec3 s = src.rgb / max(src.a, 0.0001);
+    vec3 d = dst.rgb / max(dst.a, 0.0001);
+
+    vec3 blended;
+    switch( blend_mode ) {
+        case 0: // Darken
+            blended = min(s, d);
+            break;
+        case 1: // Difference
+            blended = abs(s - d);
+            break;
+        case 2: // Overlay
+            blended = hard_light(d, s);
+            break;
+        default: // Hard light
+            blended = hard_light(s, d);
+            break;
+    }
+
+    // Composite the unmultiplied result, as described by the W3C compositing spec.
+    out_color = vec4(
+        (1.0 - dst.a) * src.rgb + (1.0 - src.a) * dst.rgb + src.a * dst.a * clamp(blended, 0.0, 1.0),
+        src.a + dst.a - src.a * dst.a
+    );
+}
\ No newline at end of file
@@ -0,0 +1,28 @@
+#version 450
+
+// Push constants: the distance between samples, and the radius of the blur
+layout(push_constant) uniform FragmentPushConstants {
+    vec2 direction;
+    int radius;
+};
+
+// Set 0: the texture to blur
+layout(set = 0, binding = 0) uniform texture2D t_source;
+
+// Set 1: sampler
+layout(set = 1, binding = 0) uniform sampler s_color;
+
+layout(location=0) in vec2 frag_uv;
+
+layout(location=0) out vec4 out_color;
+
+void main() {
+    // One pass of a box blur, where everything outside of the texture is transparent.
+    vec4 sum = vec4(0.0);
+    for( int i = -radius; i <= radius; i++ ) {
+        vec2 uv = frag_uv + direction * float(i);
+        float inside = step(0.0, uv.x) * step(uv.x, 1.0) * step(0.0, uv.y) * step(uv.y, 1.0);
+        sum += textureLod(sampler2D(t_source, s_color), uv, 0.0) * inside;
+    }
+    out_color = sum / float(radius * 2 + 1);
+}
\ No newline at end of file
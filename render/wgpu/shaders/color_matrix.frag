@@ -0,0 +1,26 @@
+#version 450
+
+// Push constants: the color matrix, split into a 4x4 matrix and the offsets
+layout(push_constant) uniform FragmentPushConstants {
+    mat4 matrix;
+    vec4 offset;
+};
+
+// Set 0: the texture to transform
+layout(set = 0, binding = 0) uniform texture2D t_source;
+
+// Set 1: sampler
+layout(set = 1, binding = 0) uniform sampler s_color;
+
+layout(location=0) in vec2 frag_uv;
+
+layout(location=0) out vec4 out_color;
+
+void main() {
+    vec4 color = textureLod(sampler2D(t_source, s_color), frag_uv, 0.0);
+
+    // The matrix works on unmultiplied colors.
+    color = vec4(color.rgb / max(color.a, 0.0001), color.a);
+    color = clamp(matrix * color + offset, 0.0, 1.0);
+    out_color = vec4(color.rgb * color.a, color.a);
+}
\ No newline at end of file
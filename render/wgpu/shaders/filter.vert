@@ -0,0 +1,11 @@
+#version 450
+
+layout(location = 0) in vec2 position;
+
+layout(location = 0) out vec2 frag_uv;
+
+void main() {
+    // Filters are drawn as a single quad covering the whole layer.
+    frag_uv = position;
+    gl_Position = vec4(position.x * 2.0 - 1.0, 1.0 - position.y * 2.0, 0.0, 1.0);
+}
\ No newline at end of file
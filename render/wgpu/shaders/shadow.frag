@@ -0,0 +1,41 @@
+#version 450
+
+// Push constants: the shadow or glow, and how it's combined with the source
+layout(push_constant) uniform FragmentPushConstants {
+    vec4 color;
+    vec2 offset;
+    float strength;
+    float inner;
+    float knockout;
+    float hide_object;
+};
+
+// Set 0: the source, and a blurred copy of it
+layout(set = 0, binding = 0) uniform texture2D t_source;
+layout(set = 0, binding = 1) uniform texture2D t_blurred;
+
+// Set 1: sampler
+layout(set = 1, binding = 0) uniform sampler s_color;
+
+layout(location=0) in vec2 frag_uv;
+
+layout(location=0) out vec4 out_color;
+
+void main() {
+    vec4 src = textureLod(sampler2D(t_source, s_color), frag_uv, 0.0);
+
+    // The shadow is cast by the blurred alpha of the source, moved by the offset.
+    // Inner shadows are cast by the inverted alpha.
+    vec2 uv = frag_uv - offset;
+    float inside = step(0.0, uv.x) * step(uv.x, 1.0) * step(0.0, uv.y) * step(uv.y, 1.0);
+    float alpha = textureLod(sampler2D(t_blurred, s_color), uv, 0.0).a * inside;
+    float coverage = clamp(mix(alpha, 1.0 - alpha, inner) * strength, 0.0, 1.0);
+    vec4 layer = color * coverage;
+
+    // Inner shadows are only drawn on top of the source, and outer shadows behind it.
+    vec4 masked = layer * mix(1.0 - src.a, src.a, inner);
+    vec4 outer_result = src + masked * (1.0 - src.a);
+    vec4 inner_result = masked + src * (1.0 - masked.a);
+    vec4 composited = mix(mix(outer_result, inner_result, inner), masked, knockout);
+    out_color = mix(composited, layer, hide_object);
+}
\ No newline at end of file
@@ -0,0 +1,304 @@
+//! Filters applied to layers on the GPU.
+//!
+//! These mirror the CPU implementations in `ruffle_core::bitmap::filters`, drawing the
+//! contents of a layer from one of its textures into another for every pass.
+
+use crate::layers::LayerTarget;
+use crate::Descriptors;
+use bytemuck::{Pod, Zeroable};
+use ruffle_core::backend::render::Filter;
+use ruffle_core::bitmap::filters::{blur_radius, offset, ColorMatrixFilter};
+use ruffle_core::swf::Color;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+struct BlurParameters {
+    direction: [f32; 2],
+    radius: i32,
+}
+
+unsafe impl Pod for BlurParameters {}
+unsafe impl Zeroable for BlurParameters {}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+struct ShadowParameters {
+    color: [f32; 4],
+    offset: [f32; 2],
+    strength: f32,
+    inner: f32,
+    knockout: f32,
+    hide_object: f32,
+}
+
+unsafe impl Pod for ShadowParameters {}
+unsafe impl Zeroable for ShadowParameters {}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+struct ColorMatrixParameters {
+    matrix: [[f32; 4]; 4],
+    offset: [f32; 4],
+}
+
+impl From<&ColorMatrixFilter> for ColorMatrixParameters {
+    fn from(filter: &ColorMatrixFilter) -> Self {
+        // Each row of the color matrix produces one channel, and the last column is added
+        // to it. The shader works on colors in the range 0-1, so the offsets are scaled.
+        let m = &filter.matrix;
+        let column = |i: usize| [m[i], m[5 + i], m[10 + i], m[15 + i]];
+        Self {
+            matrix: [column(0), column(1), column(2), column(3)],
+            offset: [m[4] / 255.0, m[9] / 255.0, m[14] / 255.0, m[19] / 255.0],
+        }
+    }
+}
+
+unsafe impl Pod for ColorMatrixParameters {}
+unsafe impl Zeroable for ColorMatrixParameters {}
+
+/// Records the passes that apply `filters` to the contents of a layer, and returns the
+/// texture of the layer that holds the result.
+///
+/// Bevel, gradient, convolution and displacement map filters aren't supported yet,
+/// and are skipped.
+pub fn apply_filters(
+    layer: &LayerTarget,
+    descriptors: &Descriptors,
+    encoder: &mut wgpu::CommandEncoder,
+    (quad_vbo, quad_ibo): (&wgpu::Buffer, &wgpu::Buffer),
+    filters: &[Filter],
+) -> usize {
+    let passes = FilterPasses {
+        layer,
+        descriptors,
+        quad_vbo,
+        quad_ibo,
+    };
+
+    let mut current = 0;
+    for filter in filters {
+        current = match filter {
+            Filter::Blur(filter) => {
+                // Every pass draws into another texture, so the blur can end up back in
+                // the texture it started from.
+                let spare = (current + 1) % 3;
+                passes.blur(
+                    encoder,
+                    current,
+                    [spare, current],
+                    (filter.blur_x, filter.blur_y, filter.quality),
+                )
+            }
+            Filter::DropShadow(filter) => {
+                // A hidden object leaves only the shadow. Inner shadows are only visible
+                // on top of the object, so it's knocked out instead.
+                let hide_object = filter.hide_object && !filter.inner;
+                passes.shadow(
+                    encoder,
+                    current,
+                    (filter.blur_x, filter.blur_y, filter.quality),
+                    passes.shadow_parameters(
+                        &filter.color,
+                        offset(filter.angle, filter.distance),
+                        filter.strength,
+                        filter.inner,
+                        filter.knockout || filter.hide_object,
+                        hide_object,
+                    ),
+                )
+            }
+            Filter::Glow(filter) => passes.shadow(
+                encoder,
+                current,
+                (filter.blur_x, filter.blur_y, filter.quality),
+                passes.shadow_parameters(
+                    &filter.color,
+                    (0, 0),
+                    filter.strength,
+                    filter.inner,
+                    filter.knockout,
+                    false,
+                ),
+            ),
+            Filter::ColorMatrix(filter) => {
+                let output = (current + 1) % 3;
+                passes.draw(
+                    encoder,
+                    &descriptors.pipelines.filter_pipelines.color_matrix,
+                    [current, current],
+                    output,
+                    bytemuck::cast_slice(&[ColorMatrixParameters::from(filter)]),
+                );
+                output
+            }
+            Filter::Bevel(_)
+            | Filter::GradientGlow(_)
+            | Filter::GradientBevel(_)
+            | Filter::Convolution(_)
+            | Filter::DisplacementMap(_) => current,
+        };
+    }
+    current
+}
+
+/// Everything needed to record the passes of a filter on a layer.
+struct FilterPasses<'a> {
+    layer: &'a LayerTarget,
+    descriptors: &'a Descriptors,
+    quad_vbo: &'a wgpu::Buffer,
+    quad_ibo: &'a wgpu::Buffer,
+}
+
+impl FilterPasses<'_> {
+    fn shadow_parameters(
+        &self,
+        color: &Color,
+        (dx, dy): (i32, i32),
+        strength: f32,
+        inner: bool,
+        knockout: bool,
+        hide_object: bool,
+    ) -> ShadowParameters {
+        let size = self.layer.size();
+        let a = f32::from(color.a) / 255.0;
+        ShadowParameters {
+            color: [
+                f32::from(color.r) / 255.0 * a,
+                f32::from(color.g) / 255.0 * a,
+                f32::from(color.b) / 255.0 * a,
+                a,
+            ],
+            offset: [
+                dx as f32 / size.width as f32,
+                dy as f32 / size.height as f32,
+            ],
+            strength,
+            inner: if inner { 1.0 } else { 0.0 },
+            knockout: if knockout { 1.0 } else { 0.0 },
+            hide_object: if hide_object { 1.0 } else { 0.0 },
+        }
+    }
+
+    /// Records the passes of a box blur. The first pass reads from `source`, and the passes
+    /// then alternate between the two `targets`. Returns the texture holding the result.
+    fn blur(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        source: usize,
+        targets: [usize; 2],
+        (blur_x, blur_y, quality): (f32, f32, u8),
+    ) -> usize {
+        let size = self.layer.size();
+        let directions = [
+            (blur_radius(blur_x), [1.0 / size.width as f32, 0.0]),
+            (blur_radius(blur_y), [0.0, 1.0 / size.height as f32]),
+        ];
+
+        let mut current = source;
+        let mut num_passes = 0;
+        for _ in 0..quality {
+            for &(radius, direction) in &directions {
+                if radius == 0 {
+                    continue;
+                }
+                let output = targets[num_passes % 2];
+                self.draw(
+                    encoder,
+                    &self.descriptors.pipelines.filter_pipelines.blur,
+                    [current, current],
+                    output,
+                    bytemuck::cast_slice(&[BlurParameters {
+                        direction,
+                        radius: radius as i32,
+                    }]),
+                );
+                current = output;
+                num_passes += 1;
+            }
+        }
+        current
+    }
+
+    /// Records a drop shadow or glow of `source`, and returns the texture holding the result.
+    fn shadow(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        source: usize,
+        blur: (f32, f32, u8),
+        parameters: ShadowParameters,
+    ) -> usize {
+        // The shadow is combined with the source, so the source is kept intact.
+        let others = [(source + 1) % 3, (source + 2) % 3];
+        let blurred = self.blur(encoder, source, others, blur);
+        let output = if blurred == others[0] {
+            others[1]
+        } else {
+            others[0]
+        };
+        self.draw(
+            encoder,
+            &self.descriptors.pipelines.filter_pipelines.shadow,
+            [source, blurred],
+            output,
+            bytemuck::cast_slice(&[parameters]),
+        );
+        output
+    }
+
+    /// Records a single pass that draws a filter over the whole of the `output` texture,
+    /// reading from the `sources` textures.
+    fn draw(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        pipeline: &wgpu::RenderPipeline,
+        sources: [usize; 2],
+        output: usize,
+        push_constants: &[u8],
+    ) {
+        let label = create_debug_label!("Filter bind group");
+        let bind_group = self
+            .descriptors
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                layout: &self.descriptors.pipelines.filter_pipelines.layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(self.layer.view(sources[0])),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(self.layer.view(sources[1])),
+                    },
+                ],
+                label: label.as_deref(),
+            });
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: self.layer.view(output),
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: true,
+                },
+                resolve_target: None,
+            }],
+            depth_stencil_attachment: None,
+            label: None,
+        });
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_push_constants(wgpu::ShaderStage::FRAGMENT, 0, push_constants);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.set_bind_group(
+            1,
+            self.descriptors
+                .bitmap_samplers
+                .get_bind_group(false, false),
+            &[],
+        );
+        render_pass.set_vertex_buffer(0, self.quad_vbo.slice(..));
+        render_pass.set_index_buffer(self.quad_ibo.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.draw_indexed(0..6, 0, 0..1);
+    }
+}
@@ -0,0 +1,302 @@
+use crate::utils::BufferDimensions;
+use crate::TextureTransforms;
+use futures::executor::block_on;
+
+/// An offscreen target that a display object with filters or a blend mode is rendered into,
+/// before the result is drawn onto the layer below it. The stage itself is rendered into
+/// the root layer, so that every layer has a parent to blend onto.
+///
+/// Filters are applied by drawing the contents of the layer from one of its textures into
+/// another, so the result may end up in any of them.
+#[derive(Debug)]
+pub struct LayerTarget {
+    size: wgpu::Extent3d,
+    format: wgpu::TextureFormat,
+    frame_buffer_view: Option<wgpu::TextureView>,
+    depth_texture_view: wgpu::TextureView,
+
+    /// The textures holding the contents of this layer. The layer is rendered into the first
+    /// one, and the others are used as targets for filters.
+    textures: [LayerTexture; 3],
+
+    /// A buffer that the contents of this layer are copied into, to read them back to the CPU.
+    buffer: wgpu::Buffer,
+    buffer_dimensions: BufferDimensions,
+
+    /// A copy of this layer, read by the shader of a `ShaderBlend` that draws onto it.
+    /// A texture can't be sampled while it's being rendered to.
+    backdrop: wgpu::Texture,
+    pub(crate) backdrop_view: wgpu::TextureView,
+
+    /// The bind group used to blend this layer onto the layer below it with a `ShaderBlend`.
+    /// This is kept around until the layer is reused, as the draw has to stay valid until
+    /// the frame is submitted.
+    pub(crate) blend_bind_group: Option<wgpu::BindGroup>,
+}
+
+/// One of the textures holding the contents of a layer.
+#[derive(Debug)]
+struct LayerTexture {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+
+    /// The bind group used to draw this texture onto the layer below, as a bitmap.
+    bind_group: wgpu::BindGroup,
+}
+
+impl LayerTexture {
+    fn new(
+        device: &wgpu::Device,
+        size: wgpu::Extent3d,
+        format: wgpu::TextureFormat,
+        bitmap_layout: &wgpu::BindGroupLayout,
+        quad_tex_transforms: &wgpu::Buffer,
+    ) -> Self {
+        let label = create_debug_label!("Layer texture");
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: label.as_deref(),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsage::RENDER_ATTACHMENT
+                | wgpu::TextureUsage::COPY_SRC
+                | wgpu::TextureUsage::SAMPLED,
+        });
+        let view = texture.create_view(&Default::default());
+
+        let label = create_debug_label!("Layer bind group");
+        let bind_group =
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                layout: bitmap_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::Buffer {
+                            buffer: quad_tex_transforms,
+                            offset: 0,
+                            size: wgpu::BufferSize::new(
+                                std::mem::size_of::<TextureTransforms>() as u64
+                            ),
+                        },
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&view),
+                    },
+                ],
+                label: label.as_deref(),
+            });
+
+        Self {
+            texture,
+            view,
+            bind_group,
+        }
+    }
+}
+
+impl LayerTarget {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        device: &wgpu::Device,
+        msaa_sample_count: u32,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+        bitmap_layout: &wgpu::BindGroupLayout,
+        quad_tex_transforms: &wgpu::Buffer,
+    ) -> Self {
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth: 1,
+        };
+
+        let frame_buffer_view = if msaa_sample_count >= 2 {
+            let label = create_debug_label!("Layer framebuffer texture");
+            let frame_buffer = device.create_texture(&wgpu::TextureDescriptor {
+                label: label.as_deref(),
+                size,
+                mip_level_count: 1,
+                sample_count: msaa_sample_count,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsage::RENDER_ATTACHMENT,
+            });
+            Some(frame_buffer.create_view(&Default::default()))
+        } else {
+            None
+        };
+
+        let create_texture =
+            || LayerTexture::new(device, size, format, bitmap_layout, quad_tex_transforms);
+        let textures = [create_texture(), create_texture(), create_texture()];
+
+        let label = create_debug_label!("Layer backdrop texture");
+        let backdrop = device.create_texture(&wgpu::TextureDescriptor {
+            label: label.as_deref(),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsage::COPY_DST | wgpu::TextureUsage::SAMPLED,
+        });
+        let backdrop_view = backdrop.create_view(&Default::default());
+
+        let label = create_debug_label!("Layer depth texture");
+        let depth_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: label.as_deref(),
+            size,
+            mip_level_count: 1,
+            sample_count: msaa_sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Depth24PlusStencil8,
+            usage: wgpu::TextureUsage::RENDER_ATTACHMENT,
+        });
+        let depth_texture_view = depth_texture.create_view(&Default::default());
+
+        let buffer_dimensions = BufferDimensions::new(width as usize, height as usize);
+        let label = create_debug_label!("Layer buffer");
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: label.as_deref(),
+            size: (buffer_dimensions.padded_bytes_per_row * buffer_dimensions.height) as u64,
+            usage: wgpu::BufferUsage::COPY_DST | wgpu::BufferUsage::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            size,
+            format,
+            frame_buffer_view,
+            depth_texture_view,
+            textures,
+            buffer,
+            buffer_dimensions,
+            backdrop,
+            backdrop_view,
+            blend_bind_group: None,
+        }
+    }
+
+    pub fn size(&self) -> wgpu::Extent3d {
+        self.size
+    }
+
+    /// Returns the view of one of the textures holding the contents of this layer.
+    pub fn view(&self, texture: usize) -> &wgpu::TextureView {
+        &self.textures[texture].view
+    }
+
+    /// Returns the bind group that draws one of the textures of this layer as a bitmap.
+    pub fn bind_group(&self, texture: usize) -> &wgpu::BindGroup {
+        &self.textures[texture].bind_group
+    }
+
+    /// Returns the color attachment, resolve target and depth/stencil attachment to render into.
+    pub fn attachments(
+        &self,
+    ) -> (
+        &wgpu::TextureView,
+        Option<&wgpu::TextureView>,
+        &wgpu::TextureView,
+    ) {
+        match &self.frame_buffer_view {
+            Some(frame_buffer_view) => (
+                frame_buffer_view,
+                Some(&self.textures[0].view),
+                &self.depth_texture_view,
+            ),
+            None => (&self.textures[0].view, None, &self.depth_texture_view),
+        }
+    }
+
+    /// Records a copy of the contents of this layer into its backdrop texture.
+    pub fn copy_to_backdrop(&self, encoder: &mut wgpu::CommandEncoder) {
+        encoder.copy_texture_to_texture(
+            wgpu::TextureCopyView {
+                texture: &self.textures[0].texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            wgpu::TextureCopyView {
+                texture: &self.backdrop,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            self.size,
+        );
+    }
+
+    /// Records a copy of the contents of this layer into its buffer.
+    /// Filters aren't applied to the copy.
+    pub fn copy_to_buffer(&self, encoder: &mut wgpu::CommandEncoder) {
+        encoder.copy_texture_to_buffer(
+            wgpu::TextureCopyView {
+                texture: &self.textures[0].texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            wgpu::BufferCopyView {
+                buffer: &self.buffer,
+                layout: wgpu::TextureDataLayout {
+                    offset: 0,
+                    bytes_per_row: self.buffer_dimensions.padded_bytes_per_row as u32,
+                    rows_per_image: 0,
+                },
+            },
+            self.size,
+        );
+    }
+
+    /// Reads back the pixels copied by `copy_to_buffer` as premultiplied RGBA.
+    /// The copy must have been submitted before calling this.
+    ///
+    /// This blocks until the GPU has finished all work submitted so far.
+    /// Layers in any format other than 8-bit RGBA or BGRA can't be read back.
+    pub fn read_pixels(&self, device: &wgpu::Device) -> Option<Vec<u8>> {
+        let is_bgra = match self.format {
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => true,
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => false,
+            format => {
+                log::error!(
+                    "Unsupported layer texture format for read back: {:?}",
+                    format
+                );
+                return None;
+            }
+        };
+
+        let buffer_future = self.buffer.slice(..).map_async(wgpu::MapMode::Read);
+        device.poll(wgpu::Maintain::Wait);
+        match block_on(buffer_future) {
+            Ok(()) => {
+                let map = self.buffer.slice(..).get_mapped_range();
+                let mut pixels = Vec::with_capacity(
+                    self.buffer_dimensions.height * self.buffer_dimensions.unpadded_bytes_per_row,
+                );
+
+                for chunk in map.chunks(self.buffer_dimensions.padded_bytes_per_row) {
+                    let row = &chunk[..self.buffer_dimensions.unpadded_bytes_per_row];
+                    if is_bgra {
+                        for bgra in row.chunks(4) {
+                            pixels.extend_from_slice(&[bgra[2], bgra[1], bgra[0], bgra[3]]);
+                        }
+                    } else {
+                        pixels.extend_from_slice(row);
+                    }
+                }
+
+                drop(map);
+                self.buffer.unmap();
+                Some(pixels)
+            }
+            Err(e) => {
+                log::error!("Unknown error reading layer buffer: {:?}", e);
+                None
+            }
+        }
+    }
+}
@@ -5,8 +5,8 @@ use lyon::tessellation::{
 };
 use ruffle_core::backend::render::swf::{self, FillStyle};
 use ruffle_core::backend::render::{
-    srgb_to_linear, Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, MovieLibrary,
-    RenderBackend, ShapeHandle, Transform,
+    srgb_to_linear, Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, BlendMode, Color, Filter,
+    MovieLibrary, RenderBackend, ShapeHandle, Transform,
 };
 use ruffle_core::shape_utils::{DistilledShape, DrawPath};
 use std::borrow::Cow;
@@ -17,7 +17,8 @@ use bytemuck::{Pod, Zeroable};
 use futures::executor::block_on;
 use raw_window_handle::HasRawWindowHandle;
 
-use crate::pipelines::Pipelines;
+use crate::layers::LayerTarget;
+use crate::pipelines::{LayerBlend, Pipelines, ShaderBlend};
 use crate::shapes::{Draw, DrawType, GradientUniforms, IncompleteDrawType, Mesh};
 use crate::target::{RenderTarget, RenderTargetFrame, SwapChainTarget};
 use crate::utils::{
@@ -33,7 +34,9 @@ type Error = Box<dyn std::error::Error>;
 mod utils;
 
 mod bitmaps;
+mod filters;
 mod globals;
+mod layers;
 mod pipelines;
 mod shapes;
 pub mod target;
@@ -96,6 +99,13 @@ pub struct WgpuRenderBackend<T: RenderTarget> {
     quad_ibo: wgpu::Buffer,
     quad_tex_transforms: wgpu::Buffer,
     bitmap_registry: HashMap<BitmapHandle, Bitmap>,
+
+    /// The mask states underneath the layers started by `push_layer`.
+    layers: Vec<(MaskState, u32)>,
+
+    /// Offscreen targets for each level of nested layers, reused between frames.
+    /// The stage is rendered into the root layer at level 0.
+    layer_targets: Vec<LayerTarget>,
 }
 
 #[allow(dead_code)]
@@ -118,6 +128,13 @@ impl<'a, T: RenderTarget> Frame<'static, T> {
     }
 }
 
+/// How a layer is drawn onto the layer below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LayerComposite {
+    Blend(LayerBlend),
+    Shader(ShaderBlend),
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
 pub enum MaskState {
     NoMask,
@@ -287,6 +304,8 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             quad_ibo,
             quad_tex_transforms,
             bitmap_registry: HashMap::new(),
+            layers: Vec::new(),
+            layer_targets: Vec::new(),
         })
     }
 
@@ -700,7 +719,42 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             }
         };
 
-        let texture_label = create_debug_label!("{} Texture", debug_str);
+        let handle = BitmapHandle(self.textures.len());
+        let width = bitmap.width;
+        let height = bitmap.height;
+        let texture = self.create_texture(
+            width,
+            height,
+            &data,
+            create_debug_label!("{} Texture", debug_str),
+            create_debug_label!("Bitmap {} bind group", handle.0),
+        );
+
+        self.bitmap_registry.insert(handle, bitmap);
+        self.textures.push(texture);
+
+        BitmapInfo {
+            handle,
+            width: width as u16,
+            height: height as u16,
+        }
+    }
+
+    /// Creates a texture from RGBA pixels, along with a bind group to draw it as a bitmap.
+    fn create_texture(
+        &self,
+        width: u32,
+        height: u32,
+        data: &[u8],
+        texture_label: Option<String>,
+        bind_group_label: Option<String>,
+    ) -> Texture {
+        let extent = wgpu::Extent3d {
+            width,
+            height,
+            depth: 1,
+        };
+
         let texture = self
             .descriptors
             .device
@@ -720,7 +774,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                 mip_level: 0,
                 origin: Default::default(),
             },
-            &data,
+            data,
             wgpu::TextureDataLayout {
                 offset: 0,
                 bytes_per_row: 4 * extent.width,
@@ -729,10 +783,6 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             extent,
         );
 
-        let handle = BitmapHandle(self.textures.len());
-        let width = bitmap.width;
-        let height = bitmap.height;
-
         // Make bind group for bitmap quad.
         let texture_view = texture.create_view(&Default::default());
         let bind_group = self
@@ -756,24 +806,111 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                         resource: wgpu::BindingResource::TextureView(&texture_view),
                     },
                 ],
-                label: create_debug_label!("Bitmap {} bind group", handle.0).as_deref(),
+                label: bind_group_label.as_deref(),
             });
 
-        self.bitmap_registry.insert(handle, bitmap);
-        self.textures.push(Texture {
+        Texture {
             texture,
             width,
             height,
             bind_group,
-        });
+        }
+    }
 
-        BitmapInfo {
-            handle,
-            width: width as u16,
-            height: height as u16,
+    /// Submits everything recorded into `encoder` so far, and replaces it with a new encoder.
+    fn submit_encoder(&self, encoder: &mut wgpu::CommandEncoder) {
+        let label = create_debug_label!("Draw encoder");
+        let draw_encoder =
+            self.descriptors
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: label.as_deref(),
+                });
+        let encoder = std::mem::replace(encoder, draw_encoder);
+        self.descriptors.queue.submit(Some(encoder.finish()));
+    }
+
+    /// Returns the attachments to render into at the given level of nested layers,
+    /// where level 0 is the root layer that the stage is rendered into.
+    fn layer_attachments(
+        &self,
+        level: usize,
+    ) -> (
+        &wgpu::TextureView,
+        Option<&wgpu::TextureView>,
+        &wgpu::TextureView,
+    ) {
+        self.layer_targets[level].attachments()
+    }
+
+    /// Creates the offscreen target for the given level of nested layers, if it doesn't exist yet.
+    fn ensure_layer_target(&mut self, level: usize) {
+        while self.layer_targets.len() <= level {
+            self.layer_targets.push(LayerTarget::new(
+                &self.descriptors.device,
+                self.descriptors.msaa_sample_count,
+                self.target.format(),
+                self.target.width(),
+                self.target.height(),
+                &self.descriptors.pipelines.bitmap_layout,
+                &self.quad_tex_transforms,
+            ));
         }
     }
 
+    /// Draws a layer as a quad covering the whole viewport, using the current mask state.
+    fn draw_layer<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        pipeline: &'a wgpu::RenderPipeline,
+        bind_group: &'a wgpu::BindGroup,
+        fragment_push_constants: &[u8],
+    ) {
+        let world_matrix = [
+            [self.target.width() as f32, 0.0, 0.0, 0.0],
+            [0.0, self.target.height() as f32, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_push_constants(
+            wgpu::ShaderStage::VERTEX,
+            0,
+            bytemuck::cast_slice(&[Transforms { world_matrix }]),
+        );
+        render_pass.set_push_constants(
+            wgpu::ShaderStage::FRAGMENT,
+            std::mem::size_of::<Transforms>() as u32,
+            fragment_push_constants,
+        );
+        render_pass.set_bind_group(0, self.descriptors.globals.bind_group(), &[]);
+        render_pass.set_bind_group(1, bind_group, &[]);
+        render_pass.set_bind_group(
+            2,
+            self.descriptors
+                .bitmap_samplers
+                .get_bind_group(false, false),
+            &[],
+        );
+        render_pass.set_vertex_buffer(0, self.quad_vbo.slice(..));
+        render_pass.set_index_buffer(self.quad_ibo.slice(..), wgpu::IndexFormat::Uint32);
+
+        match self.mask_state {
+            MaskState::NoMask => (),
+            MaskState::DrawMaskStencil => {
+                debug_assert!(self.num_masks > 0);
+                render_pass.set_stencil_reference(self.num_masks - 1);
+            }
+            MaskState::DrawMaskedContent | MaskState::ClearMaskStencil => {
+                debug_assert!(self.num_masks > 0);
+                render_pass.set_stencil_reference(self.num_masks);
+            }
+        };
+
+        render_pass.draw_indexed(0..6, 0, 0..1);
+    }
+
     pub fn target(&self) -> &T {
         &self.target
     }
@@ -828,6 +965,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 usage: wgpu::TextureUsage::RENDER_ATTACHMENT,
             });
         self.depth_texture_view = depth_texture.create_view(&Default::default());
+        self.layer_targets.clear();
         self.descriptors.globals.set_resolution(width, height);
     }
 
@@ -892,6 +1030,8 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
     fn begin_frame(&mut self, clear: Color) {
         self.mask_state = MaskState::NoMask;
         self.num_masks = 0;
+        self.layers.clear();
+        self.ensure_layer_target(0);
 
         let frame_output = match self.target.get_next_texture() {
             Ok(frame) => frame,
@@ -920,47 +1060,19 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             .globals
             .update_uniform(&self.descriptors.device, &mut frame_data.0);
 
-        let (color_attachment, resolve_target) = if self.descriptors.msaa_sample_count >= 2 {
-            (&self.frame_buffer_view, Some(frame_data.1.view()))
-        } else {
-            (frame_data.1.view(), None)
-        };
-
-        let render_pass = frame_data.0.begin_render_pass(&wgpu::RenderPassDescriptor {
-            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
-                attachment: color_attachment,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color {
-                        r: f64::from(clear.r) / 255.0,
-                        g: f64::from(clear.g) / 255.0,
-                        b: f64::from(clear.b) / 255.0,
-                        a: f64::from(clear.a) / 255.0,
-                    }),
-                    store: true,
-                },
-                resolve_target,
-            }],
-            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
-                attachment: &self.depth_texture_view,
-                depth_ops: Some(wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(0.0),
-                    store: true,
-                }),
-                stencil_ops: Some(wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(0),
-                    store: true,
-                }),
+        let attachments = self.layer_attachments(0);
+        let render_pass = begin_frame_render_pass(
+            &mut frame_data.0,
+            attachments,
+            Some(wgpu::Color {
+                r: f64::from(clear.r) / 255.0,
+                g: f64::from(clear.g) / 255.0,
+                b: f64::from(clear.b) / 255.0,
+                a: f64::from(clear.a) / 255.0,
             }),
-            label: None,
-        });
-
-        // Since RenderPass holds a reference to the CommandEncoder, we cast the lifetime
-        // away to allow for the self-referencing struct. draw_encoder is boxed so its
-        // address should remain stable.
+        );
         self.current_frame = Some(Frame {
-            render_pass: unsafe {
-                std::mem::transmute::<_, wgpu::RenderPass<'static>>(render_pass)
-            },
+            render_pass,
             frame_data,
         });
     }
@@ -1229,7 +1341,33 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             // Finalize render pass.
             drop(frame.render_pass);
 
-            let draw_encoder = frame.frame_data.0;
+            let (mut draw_encoder, frame_output) = *frame.frame_data;
+
+            // The stage was rendered into the root layer, so that blend modes can read back
+            // what's below them. Copy it onto the render target.
+            let attachments = if self.descriptors.msaa_sample_count >= 2 {
+                (
+                    &self.frame_buffer_view,
+                    Some(frame_output.view()),
+                    &self.depth_texture_view,
+                )
+            } else {
+                (frame_output.view(), None, &self.depth_texture_view)
+            };
+            let mut render_pass = begin_render_pass(
+                &mut draw_encoder,
+                attachments,
+                Some(wgpu::Color::TRANSPARENT),
+            );
+            self.draw_layer(
+                &mut render_pass,
+                self.descriptors.pipelines.layer_pipelines[LayerBlend::Replace]
+                    .pipeline_for(MaskState::NoMask),
+                self.layer_targets[0].bind_group(0),
+                bytemuck::cast_slice(&[ColorAdjustments::from(ColorTransform::default())]),
+            );
+            drop(render_pass);
+
             self.target.submit(
                 &self.descriptors.device,
                 &self.descriptors.queue,
@@ -1266,6 +1404,216 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         };
     }
 
+    fn push_layer(&mut self) {
+        self.layers.push((self.mask_state, self.num_masks));
+        self.mask_state = MaskState::NoMask;
+        self.num_masks = 0;
+
+        let level = self.layers.len();
+        self.ensure_layer_target(level);
+
+        let Frame {
+            mut frame_data,
+            render_pass,
+        } = if let Some(frame) = self.current_frame.take() {
+            frame
+        } else {
+            return;
+        };
+        drop(render_pass);
+
+        let attachments = self.layer_attachments(level);
+        let render_pass = begin_frame_render_pass(
+            &mut frame_data.0,
+            attachments,
+            Some(wgpu::Color::TRANSPARENT),
+        );
+        self.current_frame = Some(Frame {
+            render_pass,
+            frame_data,
+        });
+    }
+
+    fn pop_layer(&mut self, filters: &[Filter], blend_mode: BlendMode) {
+        let (mask_state, num_masks) = if let Some(state) = self.layers.pop() {
+            state
+        } else {
+            log::warn!("pop_layer: No layer to pop");
+            return;
+        };
+        self.mask_state = mask_state;
+        self.num_masks = num_masks;
+
+        // The popped layer is at `level + 1`, and is drawn onto the layer at `level`.
+        let level = self.layers.len();
+        let composite = match blend_mode {
+            BlendMode::Normal | BlendMode::Layer => LayerComposite::Blend(LayerBlend::Normal),
+            BlendMode::Add => LayerComposite::Blend(LayerBlend::Add),
+            BlendMode::Subtract => LayerComposite::Blend(LayerBlend::Subtract),
+            BlendMode::Multiply => LayerComposite::Blend(LayerBlend::Multiply),
+            BlendMode::Screen => LayerComposite::Blend(LayerBlend::Screen),
+            BlendMode::Lighten => LayerComposite::Blend(LayerBlend::Lighten),
+            BlendMode::Invert => LayerComposite::Blend(LayerBlend::Invert),
+            // Alpha and erase only affect a parent that is itself a layer, not the stage.
+            BlendMode::Alpha | BlendMode::Erase if level == 0 => {
+                LayerComposite::Blend(LayerBlend::Normal)
+            }
+            BlendMode::Alpha => LayerComposite::Blend(LayerBlend::Alpha),
+            BlendMode::Erase => LayerComposite::Blend(LayerBlend::Erase),
+            BlendMode::Darken => LayerComposite::Shader(ShaderBlend::Darken),
+            BlendMode::Difference => LayerComposite::Shader(ShaderBlend::Difference),
+            BlendMode::Overlay => LayerComposite::Shader(ShaderBlend::Overlay),
+            BlendMode::HardLight => LayerComposite::Shader(ShaderBlend::HardLight),
+        };
+
+        let Frame {
+            mut frame_data,
+            render_pass,
+        } = if let Some(frame) = self.current_frame.take() {
+            frame
+        } else {
+            return;
+        };
+        drop(render_pass);
+
+        // Filters are applied by drawing the layer between its textures.
+        let texture = filters::apply_filters(
+            &self.layer_targets[level + 1],
+            &self.descriptors,
+            &mut frame_data.0,
+            (&self.quad_vbo, &self.quad_ibo),
+            filters,
+        );
+
+        if let LayerComposite::Shader(_) = composite {
+            // The layer below can't be read while it's drawn to, so the shader reads a copy of it.
+            self.layer_targets[level].copy_to_backdrop(&mut frame_data.0);
+
+            let layer = &self.layer_targets[level + 1];
+            let label = create_debug_label!("Layer {} blend bind group", level + 1);
+            let bind_group =
+                self.descriptors
+                    .device
+                    .create_bind_group(&wgpu::BindGroupDescriptor {
+                        layout: &self.descriptors.pipelines.shader_blend_layout,
+                        entries: &[
+                            wgpu::BindGroupEntry {
+                                binding: 0,
+                                resource: wgpu::BindingResource::Buffer {
+                                    buffer: &self.quad_tex_transforms,
+                                    offset: 0,
+                                    size: wgpu::BufferSize::new(
+                                        std::mem::size_of::<TextureTransforms>() as u64,
+                                    ),
+                                },
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 1,
+                                resource: wgpu::BindingResource::TextureView(layer.view(texture)),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 2,
+                                resource: wgpu::BindingResource::TextureView(
+                                    &self.layer_targets[level].backdrop_view,
+                                ),
+                            },
+                        ],
+                        label: label.as_deref(),
+                    });
+            self.layer_targets[level + 1].blend_bind_group = Some(bind_group);
+        }
+
+        let attachments = self.layer_attachments(level);
+        let render_pass = begin_frame_render_pass(&mut frame_data.0, attachments, None);
+        let mut frame = Frame {
+            render_pass,
+            frame_data,
+        };
+
+        let layer = &self.layer_targets[level + 1];
+        let bind_group = if let LayerComposite::Shader(_) = composite {
+            layer.blend_bind_group.as_ref()
+        } else {
+            Some(layer.bind_group(texture))
+        };
+        if let Some(bind_group) = bind_group {
+            match composite {
+                LayerComposite::Blend(layer_blend) => {
+                    // Invert only uses the alpha of the layer,
+                    // so every color is replaced by its alpha.
+                    let color_transform = if layer_blend == LayerBlend::Invert {
+                        ColorTransform {
+                            r_mult: 0.0,
+                            g_mult: 0.0,
+                            b_mult: 0.0,
+                            r_add: 1.0,
+                            g_add: 1.0,
+                            b_add: 1.0,
+                            ..Default::default()
+                        }
+                    } else {
+                        ColorTransform::default()
+                    };
+                    self.draw_layer(
+                        &mut frame.get().render_pass,
+                        self.descriptors.pipelines.layer_pipelines[layer_blend]
+                            .pipeline_for(self.mask_state),
+                        bind_group,
+                        bytemuck::cast_slice(&[ColorAdjustments::from(color_transform)]),
+                    );
+                }
+                LayerComposite::Shader(shader_blend) => {
+                    self.draw_layer(
+                        &mut frame.get().render_pass,
+                        self.descriptors
+                            .pipelines
+                            .shader_blend_pipelines
+                            .pipeline_for(self.mask_state),
+                        bind_group,
+                        bytemuck::cast_slice(&[shader_blend as i32]),
+                    );
+                }
+            }
+        }
+
+        self.current_frame = Some(frame);
+    }
+
+    fn pop_layer_to_bitmap(&mut self) -> Option<Bitmap> {
+        let (mask_state, num_masks) = if let Some(state) = self.layers.pop() {
+            state
+        } else {
+            log::warn!("pop_layer_to_bitmap: No layer to pop");
+            return None;
+        };
+        self.mask_state = mask_state;
+        self.num_masks = num_masks;
+
+        let level = self.layers.len();
+        let Frame {
+            mut frame_data,
+            render_pass,
+        } = self.current_frame.take()?;
+        drop(render_pass);
+
+        self.layer_targets[level + 1].copy_to_buffer(&mut frame_data.0);
+        self.submit_encoder(&mut frame_data.0);
+        let pixels = self.layer_targets[level + 1].read_pixels(&self.descriptors.device);
+
+        let attachments = self.layer_attachments(level);
+        let render_pass = begin_frame_render_pass(&mut frame_data.0, attachments, None);
+        self.current_frame = Some(Frame {
+            render_pass,
+            frame_data,
+        });
+
+        pixels.map(|data| Bitmap {
+            width: self.target.width(),
+            height: self.target.height(),
+            data: BitmapFormat::Rgba(data),
+        })
+    }
+
     fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
         self.bitmap_registry.get(&bitmap).cloned()
     }
@@ -1326,6 +1674,59 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
     }
 }
 
+/// Begins a render pass into the given color attachment, resolve target and depth/stencil
+/// attachment. The attachments are cleared if `clear` is set, otherwise their contents are kept.
+fn begin_render_pass<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    (attachment, resolve_target, depth_stencil_attachment): (
+        &'a wgpu::TextureView,
+        Option<&'a wgpu::TextureView>,
+        &'a wgpu::TextureView,
+    ),
+    clear: Option<wgpu::Color>,
+) -> wgpu::RenderPass<'a> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+            attachment,
+            ops: wgpu::Operations {
+                load: clear.map_or(wgpu::LoadOp::Load, wgpu::LoadOp::Clear),
+                store: true,
+            },
+            resolve_target,
+        }],
+        depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
+            attachment: depth_stencil_attachment,
+            depth_ops: Some(wgpu::Operations {
+                load: clear.map_or(wgpu::LoadOp::Load, |_| wgpu::LoadOp::Clear(0.0)),
+                store: true,
+            }),
+            stencil_ops: Some(wgpu::Operations {
+                load: clear.map_or(wgpu::LoadOp::Load, |_| wgpu::LoadOp::Clear(0)),
+                store: true,
+            }),
+        }),
+        label: None,
+    })
+}
+
+/// Begins a render pass that is kept in the current `Frame`, as with `begin_render_pass`.
+fn begin_frame_render_pass(
+    encoder: &mut wgpu::CommandEncoder,
+    attachments: (
+        &wgpu::TextureView,
+        Option<&wgpu::TextureView>,
+        &wgpu::TextureView,
+    ),
+    clear: Option<wgpu::Color>,
+) -> wgpu::RenderPass<'static> {
+    let render_pass = begin_render_pass(encoder, attachments, clear);
+
+    // Since RenderPass holds a reference to the CommandEncoder, we cast the lifetime
+    // away to allow for the self-referencing struct. draw_encoder is boxed so its
+    // address should remain stable.
+    unsafe { std::mem::transmute::<_, wgpu::RenderPass<'static>>(render_pass) }
+}
+
 fn create_quad_buffers(device: &wgpu::Device) -> (wgpu::Buffer, wgpu::Buffer, wgpu::Buffer) {
     let vertices = [
         GPUVertex {
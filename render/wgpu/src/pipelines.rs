@@ -1,5 +1,5 @@
 use crate::{Error, GPUVertex, MaskState};
-use enum_map::{enum_map, EnumMap};
+use enum_map::{enum_map, Enum, EnumMap};
 use wgpu::vertex_attr_array;
 
 #[derive(Debug)]
@@ -16,6 +16,137 @@ pub struct Pipelines {
 
     pub gradient_pipelines: ShapePipeline,
     pub gradient_layout: wgpu::BindGroupLayout,
+
+    pub layer_pipelines: EnumMap<LayerBlend, ShapePipeline>,
+
+    pub shader_blend_pipelines: ShapePipeline,
+    pub shader_blend_layout: wgpu::BindGroupLayout,
+
+    pub filter_pipelines: FilterPipelines,
+}
+
+/// The fixed-function blending used to draw a layer onto the layer below it.
+///
+/// Blend modes that can't be expressed with blend factors use a `ShaderBlend` instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum LayerBlend {
+    Normal,
+    Add,
+    Subtract,
+    Multiply,
+    Screen,
+    Lighten,
+    Invert,
+    Alpha,
+    Erase,
+    Replace,
+}
+
+/// A blend mode that is composited by a shader, which reads a copy of the layer below.
+/// The result replaces the contents of the layer below.
+///
+/// The values match the `blend_mode` push constant of `blend.frag`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShaderBlend {
+    Darken = 0,
+    Difference = 1,
+    Overlay = 2,
+    HardLight = 3,
+}
+
+/// The pipelines that apply filters to a layer, by drawing it into another texture.
+///
+/// These draw a single quad over the whole target, without multisampling or masks.
+#[derive(Debug)]
+pub struct FilterPipelines {
+    pub blur: wgpu::RenderPipeline,
+    pub shadow: wgpu::RenderPipeline,
+    pub color_matrix: wgpu::RenderPipeline,
+
+    /// The layout of the textures read by a filter: the source, and a second input
+    /// used by shadows and glows.
+    pub layout: wgpu::BindGroupLayout,
+}
+
+impl LayerBlend {
+    /// Returns the color and alpha blending, for premultiplied colors.
+    fn blend_descriptors(self) -> (wgpu::BlendDescriptor, wgpu::BlendDescriptor) {
+        use wgpu::{BlendDescriptor, BlendFactor, BlendOperation};
+        let descriptor = |src_factor, dst_factor, operation| BlendDescriptor {
+            src_factor,
+            dst_factor,
+            operation,
+        };
+        let over = descriptor(
+            BlendFactor::One,
+            BlendFactor::OneMinusSrcAlpha,
+            BlendOperation::Add,
+        );
+        match self {
+            LayerBlend::Normal => (over.clone(), over),
+            LayerBlend::Add => (
+                descriptor(BlendFactor::One, BlendFactor::One, BlendOperation::Add),
+                over,
+            ),
+            LayerBlend::Subtract => (
+                descriptor(
+                    BlendFactor::One,
+                    BlendFactor::One,
+                    BlendOperation::ReverseSubtract,
+                ),
+                over,
+            ),
+            LayerBlend::Multiply => (
+                descriptor(
+                    BlendFactor::DstColor,
+                    BlendFactor::OneMinusSrcAlpha,
+                    BlendOperation::Add,
+                ),
+                over,
+            ),
+            LayerBlend::Screen => (
+                descriptor(
+                    BlendFactor::One,
+                    BlendFactor::OneMinusSrcColor,
+                    BlendOperation::Add,
+                ),
+                over,
+            ),
+            LayerBlend::Lighten => (
+                descriptor(BlendFactor::One, BlendFactor::One, BlendOperation::Max),
+                over,
+            ),
+            // The layer is drawn with a color transform that turns every color into its alpha.
+            LayerBlend::Invert => (
+                descriptor(
+                    BlendFactor::OneMinusDstColor,
+                    BlendFactor::OneMinusSrcAlpha,
+                    BlendOperation::Add,
+                ),
+                descriptor(BlendFactor::Zero, BlendFactor::One, BlendOperation::Add),
+            ),
+            LayerBlend::Alpha => {
+                let alpha = descriptor(
+                    BlendFactor::Zero,
+                    BlendFactor::SrcAlpha,
+                    BlendOperation::Add,
+                );
+                (alpha.clone(), alpha)
+            }
+            LayerBlend::Erase => {
+                let erase = descriptor(
+                    BlendFactor::Zero,
+                    BlendFactor::OneMinusSrcAlpha,
+                    BlendOperation::Add,
+                );
+                (erase.clone(), erase)
+            }
+            LayerBlend::Replace => {
+                let replace = descriptor(BlendFactor::One, BlendFactor::Zero, BlendOperation::Add);
+                (replace.clone(), replace)
+            }
+        }
+    }
 }
 
 impl ShapePipeline {
@@ -41,6 +172,16 @@ impl Pipelines {
             device.create_shader_module(&wgpu::include_spirv!("../shaders/gradient.frag.spv"));
         let bitmap_fs =
             device.create_shader_module(&wgpu::include_spirv!("../shaders/bitmap.frag.spv"));
+        let blend_fs =
+            device.create_shader_module(&wgpu::include_spirv!("../shaders/blend.frag.spv"));
+        let filter_vs =
+            device.create_shader_module(&wgpu::include_spirv!("../shaders/filter.vert.spv"));
+        let blur_fs =
+            device.create_shader_module(&wgpu::include_spirv!("../shaders/blur.frag.spv"));
+        let shadow_fs =
+            device.create_shader_module(&wgpu::include_spirv!("../shaders/shadow.frag.spv"));
+        let color_matrix_fs =
+            device.create_shader_module(&wgpu::include_spirv!("../shaders/color_matrix.frag.spv"));
 
         let vertex_buffers_description = [wgpu::VertexBufferDescriptor {
             stride: std::mem::size_of::<GPUVertex>() as u64,
@@ -137,12 +278,115 @@ impl Pipelines {
             &gradient_bind_layout,
         );
 
+        let layer_pipelines = create_layer_pipelines(
+            &device,
+            &texture_vs,
+            &bitmap_fs,
+            msaa_sample_count,
+            &vertex_buffers_description,
+            sampler_layout,
+            globals_layout,
+            &bitmap_bind_layout,
+        );
+
+        let shader_blend_bind_layout_label = create_debug_label!("Shader blend bind group layout");
+        let shader_blend_bind_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStage::VERTEX,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStage::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            multisampled: false,
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStage::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            multisampled: false,
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                        },
+                        count: None,
+                    },
+                ],
+                label: shader_blend_bind_layout_label.as_deref(),
+            });
+
+        let shader_blend_pipelines = create_shader_blend_pipelines(
+            &device,
+            &texture_vs,
+            &blend_fs,
+            msaa_sample_count,
+            &vertex_buffers_description,
+            sampler_layout,
+            globals_layout,
+            &shader_blend_bind_layout,
+        );
+
+        let filter_bind_layout_label = create_debug_label!("Filter bind group layout");
+        let filter_bind_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStage::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            multisampled: false,
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStage::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            multisampled: false,
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                        },
+                        count: None,
+                    },
+                ],
+                label: filter_bind_layout_label.as_deref(),
+            });
+
+        let filter_pipelines = create_filter_pipelines(
+            &device,
+            &filter_vs,
+            &blur_fs,
+            &shadow_fs,
+            &color_matrix_fs,
+            &vertex_buffers_description,
+            sampler_layout,
+            filter_bind_layout,
+        );
+
         Ok(Self {
             color_pipelines,
             bitmap_pipelines,
             bitmap_layout: bitmap_bind_layout,
             gradient_pipelines,
             gradient_layout: gradient_bind_layout,
+            layer_pipelines,
+            shader_blend_pipelines,
+            shader_blend_layout: shader_blend_bind_layout,
+            filter_pipelines,
         })
     }
 }
@@ -240,7 +484,7 @@ fn create_color_pipelines(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -272,7 +516,7 @@ fn create_color_pipelines(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -304,7 +548,7 @@ fn create_color_pipelines(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -336,7 +580,7 @@ fn create_color_pipelines(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -400,7 +644,7 @@ fn create_bitmap_pipeline(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -432,7 +676,7 @@ fn create_bitmap_pipeline(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -464,7 +708,7 @@ fn create_bitmap_pipeline(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -496,7 +740,7 @@ fn create_bitmap_pipeline(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -511,6 +755,179 @@ fn create_bitmap_pipeline(
     ShapePipeline { mask_pipelines }
 }
 
+#[allow(clippy::too_many_arguments)]
+fn create_layer_pipelines(
+    device: &wgpu::Device,
+    vertex_shader: &wgpu::ShaderModule,
+    fragment_shader: &wgpu::ShaderModule,
+    msaa_sample_count: u32,
+    vertex_buffers_description: &[wgpu::VertexBufferDescriptor<'_>],
+    sampler_layout: &wgpu::BindGroupLayout,
+    globals_layout: &wgpu::BindGroupLayout,
+    bitmap_bind_layout: &wgpu::BindGroupLayout,
+) -> EnumMap<LayerBlend, ShapePipeline> {
+    let pipeline_layout_label = create_debug_label!("Layer pipeline layout");
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: pipeline_layout_label.as_deref(),
+        bind_group_layouts: &[globals_layout, bitmap_bind_layout, sampler_layout],
+        push_constant_ranges: &[
+            wgpu::PushConstantRange {
+                stages: wgpu::ShaderStage::VERTEX,
+                range: 0..64,
+            },
+            wgpu::PushConstantRange {
+                stages: wgpu::ShaderStage::FRAGMENT,
+                range: 64..96,
+            },
+        ],
+    });
+
+    EnumMap::from(|blend: LayerBlend| {
+        create_layer_pipeline(
+            device,
+            &format!("Layer pipeline {:?}", blend),
+            vertex_shader,
+            fragment_shader,
+            &pipeline_layout,
+            blend.blend_descriptors(),
+            vertex_buffers_description,
+            msaa_sample_count,
+        )
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn create_shader_blend_pipelines(
+    device: &wgpu::Device,
+    vertex_shader: &wgpu::ShaderModule,
+    fragment_shader: &wgpu::ShaderModule,
+    msaa_sample_count: u32,
+    vertex_buffers_description: &[wgpu::VertexBufferDescriptor<'_>],
+    sampler_layout: &wgpu::BindGroupLayout,
+    globals_layout: &wgpu::BindGroupLayout,
+    shader_blend_bind_layout: &wgpu::BindGroupLayout,
+) -> ShapePipeline {
+    let pipeline_layout_label = create_debug_label!("Shader blend pipeline layout");
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: pipeline_layout_label.as_deref(),
+        bind_group_layouts: &[globals_layout, shader_blend_bind_layout, sampler_layout],
+        push_constant_ranges: &[
+            wgpu::PushConstantRange {
+                stages: wgpu::ShaderStage::VERTEX,
+                range: 0..64,
+            },
+            wgpu::PushConstantRange {
+                stages: wgpu::ShaderStage::FRAGMENT,
+                range: 64..96,
+            },
+        ],
+    });
+
+    // The shader outputs the blended result, which replaces the layer below.
+    create_layer_pipeline(
+        device,
+        "Shader blend pipeline",
+        vertex_shader,
+        fragment_shader,
+        &pipeline_layout,
+        LayerBlend::Replace.blend_descriptors(),
+        vertex_buffers_description,
+        msaa_sample_count,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn create_filter_pipelines(
+    device: &wgpu::Device,
+    vertex_shader: &wgpu::ShaderModule,
+    blur_shader: &wgpu::ShaderModule,
+    shadow_shader: &wgpu::ShaderModule,
+    color_matrix_shader: &wgpu::ShaderModule,
+    vertex_buffers_description: &[wgpu::VertexBufferDescriptor<'_>],
+    sampler_layout: &wgpu::BindGroupLayout,
+    filter_bind_layout: wgpu::BindGroupLayout,
+) -> FilterPipelines {
+    let pipeline_layout_label = create_debug_label!("Filter pipeline layout");
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: pipeline_layout_label.as_deref(),
+        bind_group_layouts: &[&filter_bind_layout, sampler_layout],
+        push_constant_ranges: &[wgpu::PushConstantRange {
+            stages: wgpu::ShaderStage::FRAGMENT,
+            range: 0..80,
+        }],
+    });
+
+    // Every filter replaces the contents of its target.
+    let (color_blend, alpha_blend) = LayerBlend::Replace.blend_descriptors();
+    let create_pipeline = |name: &str, fragment_shader| {
+        device.create_render_pipeline(&create_pipeline_descriptor(
+            create_debug_label!("Filter pipeline {}", name).as_deref(),
+            vertex_shader,
+            fragment_shader,
+            &pipeline_layout,
+            None,
+            &[wgpu::ColorStateDescriptor {
+                format: wgpu::TextureFormat::Bgra8Unorm,
+                color_blend: color_blend.clone(),
+                alpha_blend: alpha_blend.clone(),
+                write_mask: wgpu::ColorWrite::ALL,
+            }],
+            vertex_buffers_description,
+            1,
+        ))
+    };
+
+    FilterPipelines {
+        blur: create_pipeline("blur", blur_shader),
+        shadow: create_pipeline("shadow", shadow_shader),
+        color_matrix: create_pipeline("color matrix", color_matrix_shader),
+        layout: filter_bind_layout,
+    }
+}
+
+/// Creates the pipelines that draw a layer onto the layer below it, for every mask state.
+#[allow(clippy::too_many_arguments)]
+fn create_layer_pipeline(
+    device: &wgpu::Device,
+    name: &str,
+    vertex_shader: &wgpu::ShaderModule,
+    fragment_shader: &wgpu::ShaderModule,
+    pipeline_layout: &wgpu::PipelineLayout,
+    (color_blend, alpha_blend): (wgpu::BlendDescriptor, wgpu::BlendDescriptor),
+    vertex_buffers_description: &[wgpu::VertexBufferDescriptor<'_>],
+    msaa_sample_count: u32,
+) -> ShapePipeline {
+    let mask_pipelines = EnumMap::from(|mask_state| {
+        let (stencil, write_mask) = mask_render_state(mask_state);
+        let depth_compare = if mask_state == MaskState::DrawMaskedContent {
+            wgpu::CompareFunction::Equal
+        } else {
+            wgpu::CompareFunction::Always
+        };
+        device.create_render_pipeline(&create_pipeline_descriptor(
+            create_debug_label!("{} {:?}", name, mask_state).as_deref(),
+            vertex_shader,
+            fragment_shader,
+            pipeline_layout,
+            Some(wgpu::DepthStencilStateDescriptor {
+                format: wgpu::TextureFormat::Depth24PlusStencil8,
+                depth_write_enabled: true,
+                depth_compare,
+                stencil,
+            }),
+            &[wgpu::ColorStateDescriptor {
+                format: wgpu::TextureFormat::Bgra8Unorm,
+                color_blend: color_blend.clone(),
+                alpha_blend: alpha_blend.clone(),
+                write_mask,
+            }],
+            vertex_buffers_description,
+            msaa_sample_count,
+        ))
+    });
+    ShapePipeline { mask_pipelines }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn create_gradient_pipeline(
     device: &wgpu::Device,
@@ -559,7 +976,7 @@ fn create_gradient_pipeline(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -591,7 +1008,7 @@ fn create_gradient_pipeline(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -624,7 +1041,7 @@ fn create_gradient_pipeline(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -656,7 +1073,7 @@ fn create_gradient_pipeline(
                         operation: wgpu::BlendOperation::Add,
                     },
                     alpha_blend: wgpu::BlendDescriptor {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
+                        src_factor: wgpu::BlendFactor::One,
                         dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                         operation: wgpu::BlendOperation::Add,
                     },
@@ -16,6 +16,16 @@ where
     pub fn seek(&mut self, relative_offset: i64) -> std::io::Result<u64> {
         self.input.seek(SeekFrom::Current(relative_offset as i64))
     }
+
+    #[inline]
+    pub fn seek_absolute(&mut self, offset: u64) -> std::io::Result<u64> {
+        self.input.seek(SeekFrom::Start(offset))
+    }
+
+    #[inline]
+    pub fn pos(&mut self) -> std::io::Result<u64> {
+        self.input.seek(SeekFrom::Current(0))
+    }
 }
 
 impl<R: Read> Reader<R> {
@@ -194,6 +204,18 @@ impl<R: Read> Reader<R> {
             0x1c => Multiname::MultinameLA {
                 namespace_set: self.read_index()?,
             },
+            0x1d => {
+                let base_type = self.read_index()?;
+                let count = self.read_u30()?;
+                let mut parameters = Vec::with_capacity(count as usize);
+                for _ in 0..count {
+                    parameters.push(self.read_index()?);
+                }
+                Multiname::TypeName {
+                    base_type,
+                    parameters,
+                }
+            }
             _ => return Err(Error::invalid_data("Invalid multiname kind")),
         })
     }
@@ -543,6 +565,9 @@ impl<R: Read> Reader<R> {
         let op = match opcode {
             OpCode::Add => Op::Add,
             OpCode::AddI => Op::AddI,
+            OpCode::ApplyType => Op::ApplyType {
+                num_types: self.read_u30()?,
+            },
             OpCode::AsType => Op::AsType {
                 type_name: self.read_index()?,
             },
@@ -749,6 +774,11 @@ impl<R: Read> Reader<R> {
             OpCode::Label => Op::Label,
             OpCode::LessEquals => Op::LessEquals,
             OpCode::LessThan => Op::LessThan,
+            OpCode::Lf32 => Op::Lf32,
+            OpCode::Lf64 => Op::Lf64,
+            OpCode::Li16 => Op::Li16,
+            OpCode::Li32 => Op::Li32,
+            OpCode::Li8 => Op::Li8,
             OpCode::LookupSwitch => Op::LookupSwitch {
                 default_offset: self.read_i24()?,
                 case_offsets: {
@@ -838,10 +868,18 @@ impl<R: Read> Reader<R> {
             OpCode::SetSuper => Op::SetSuper {
                 index: self.read_index()?,
             },
+            OpCode::Sf32 => Op::Sf32,
+            OpCode::Sf64 => Op::Sf64,
+            OpCode::Si16 => Op::Si16,
+            OpCode::Si32 => Op::Si32,
+            OpCode::Si8 => Op::Si8,
             OpCode::StrictEquals => Op::StrictEquals,
             OpCode::Subtract => Op::Subtract,
             OpCode::SubtractI => Op::SubtractI,
             OpCode::Swap => Op::Swap,
+            OpCode::Sxi1 => Op::Sxi1,
+            OpCode::Sxi16 => Op::Sxi16,
+            OpCode::Sxi8 => Op::Sxi8,
             OpCode::Throw => Op::Throw,
             OpCode::TypeOf => Op::TypeOf,
             OpCode::URShift => Op::URShift,
@@ -940,4 +978,64 @@ pub mod tests {
             }
         }
     }
+
+    #[test]
+    fn read_alchemy_and_type_ops() {
+        let bytes = [
+            0x35, 0x36, 0x37, 0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x50, 0x51, 0x52, 0x53,
+            0x02,
+        ];
+        let expected = [
+            Op::Li8,
+            Op::Li16,
+            Op::Li32,
+            Op::Lf32,
+            Op::Lf64,
+            Op::Si8,
+            Op::Si16,
+            Op::Si32,
+            Op::Sf32,
+            Op::Sf64,
+            Op::Sxi1,
+            Op::Sxi8,
+            Op::Sxi16,
+            Op::ApplyType { num_types: 2 },
+        ];
+
+        let mut reader = Reader::new(&bytes[..]);
+        for op in expected.iter() {
+            assert_eq!(reader.read_op().unwrap().as_ref(), Some(op));
+        }
+        assert!(reader.read_op().is_err());
+    }
+
+    #[test]
+    fn read_type_name() {
+        // `Vector.<int>`, with the base type and the parameter as multiname indices.
+        let bytes = [0x1d, 0x05, 0x01, 0x06];
+        let mut reader = Reader::new(&bytes[..]);
+        assert_eq!(
+            reader.read_multiname().unwrap(),
+            Multiname::TypeName {
+                base_type: Index::new(5),
+                parameters: vec![Index::new(6)],
+            }
+        );
+    }
+
+    #[test]
+    fn read_exception_variable_name() {
+        let bytes = [0x01, 0x05, 0x07, 0x02, 0x03];
+        let mut reader = Reader::new(&bytes[..]);
+        assert_eq!(
+            reader.read_exception().unwrap(),
+            Exception {
+                from_offset: 1,
+                to_offset: 5,
+                target_offset: 7,
+                type_name: Index::new(2),
+                variable_name: Index::new(3),
+            }
+        );
+    }
 }
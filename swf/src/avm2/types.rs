@@ -82,6 +82,10 @@ pub enum Multiname {
     MultinameLA {
         namespace_set: Index<NamespaceSet>,
     },
+    TypeName {
+        base_type: Index<Multiname>,
+        parameters: Vec<Index<Multiname>>,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -119,7 +123,7 @@ pub struct Exception {
     pub from_offset: u32,
     pub to_offset: u32,
     pub target_offset: u32,
-    pub variable_name: Index<String>,
+    pub variable_name: Index<Multiname>,
     pub type_name: Index<Multiname>,
 }
 
@@ -229,6 +233,9 @@ pub struct Script {
 pub enum Op {
     Add,
     AddI,
+    ApplyType {
+        num_types: u32,
+    },
     AsType {
         type_name: Index<Multiname>,
     },
@@ -427,6 +434,11 @@ pub enum Op {
     Label,
     LessEquals,
     LessThan,
+    Lf32,
+    Lf64,
+    Li16,
+    Li32,
+    Li8,
     LookupSwitch {
         default_offset: i32,
         case_offsets: Vec<i32>,
@@ -505,10 +517,18 @@ pub enum Op {
     SetSuper {
         index: Index<Multiname>,
     },
+    Sf32,
+    Sf64,
+    Si16,
+    Si32,
+    Si8,
     StrictEquals,
     Subtract,
     SubtractI,
     Swap,
+    Sxi1,
+    Sxi16,
+    Sxi8,
     Throw,
     TypeOf,
     URShift,
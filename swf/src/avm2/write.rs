@@ -323,6 +323,17 @@ impl<W: Write> Writer<W> {
                 self.write_u8(0x1c)?;
                 self.write_index(namespace_set)?;
             }
+            Multiname::TypeName {
+                ref base_type,
+                ref parameters,
+            } => {
+                self.write_u8(0x1d)?;
+                self.write_index(base_type)?;
+                self.write_u30(parameters.len() as u32)?;
+                for parameter in parameters {
+                    self.write_index(parameter)?;
+                }
+            }
         }
         Ok(())
     }
@@ -603,6 +614,10 @@ impl<W: Write> Writer<W> {
         match *op {
             Op::Add => self.write_opcode(OpCode::Add)?,
             Op::AddI => self.write_opcode(OpCode::AddI)?,
+            Op::ApplyType { num_types } => {
+                self.write_opcode(OpCode::ApplyType)?;
+                self.write_u30(num_types)?;
+            }
             Op::AsType { ref type_name } => {
                 self.write_opcode(OpCode::AsType)?;
                 self.write_index(type_name)?;
@@ -890,6 +905,11 @@ impl<W: Write> Writer<W> {
             Op::Label => self.write_opcode(OpCode::Label)?,
             Op::LessEquals => self.write_opcode(OpCode::LessEquals)?,
             Op::LessThan => self.write_opcode(OpCode::LessThan)?,
+            Op::Lf32 => self.write_opcode(OpCode::Lf32)?,
+            Op::Lf64 => self.write_opcode(OpCode::Lf64)?,
+            Op::Li16 => self.write_opcode(OpCode::Li16)?,
+            Op::Li32 => self.write_opcode(OpCode::Li32)?,
+            Op::Li8 => self.write_opcode(OpCode::Li8)?,
             Op::LookupSwitch {
                 default_offset,
                 ref case_offsets,
@@ -998,10 +1018,18 @@ impl<W: Write> Writer<W> {
                 self.write_opcode(OpCode::SetSuper)?;
                 self.write_index(index)?;
             }
+            Op::Sf32 => self.write_opcode(OpCode::Sf32)?,
+            Op::Sf64 => self.write_opcode(OpCode::Sf64)?,
+            Op::Si16 => self.write_opcode(OpCode::Si16)?,
+            Op::Si32 => self.write_opcode(OpCode::Si32)?,
+            Op::Si8 => self.write_opcode(OpCode::Si8)?,
             Op::StrictEquals => self.write_opcode(OpCode::StrictEquals)?,
             Op::Subtract => self.write_opcode(OpCode::Subtract)?,
             Op::SubtractI => self.write_opcode(OpCode::SubtractI)?,
             Op::Swap => self.write_opcode(OpCode::Swap)?,
+            Op::Sxi1 => self.write_opcode(OpCode::Sxi1)?,
+            Op::Sxi16 => self.write_opcode(OpCode::Sxi16)?,
+            Op::Sxi8 => self.write_opcode(OpCode::Sxi8)?,
             Op::Throw => self.write_opcode(OpCode::Throw)?,
             Op::TypeOf => self.write_opcode(OpCode::TypeOf)?,
             Op::URShift => self.write_opcode(OpCode::URShift)?,
@@ -1018,6 +1046,7 @@ impl<W: Write> Writer<W> {
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::avm2::read::Reader;
     use crate::test_data;
 
     #[test]
@@ -1037,4 +1066,71 @@ pub mod tests {
             }
         }
     }
+
+    #[test]
+    fn write_alchemy_and_type_ops() {
+        let ops = [
+            Op::Li8,
+            Op::Li16,
+            Op::Li32,
+            Op::Lf32,
+            Op::Lf64,
+            Op::Si8,
+            Op::Si16,
+            Op::Si32,
+            Op::Sf32,
+            Op::Sf64,
+            Op::Sxi1,
+            Op::Sxi8,
+            Op::Sxi16,
+            Op::ApplyType { num_types: 2 },
+        ];
+
+        let mut out = vec![];
+        {
+            let mut writer = Writer::new(&mut out);
+            for op in ops.iter() {
+                writer.write_op(op).unwrap();
+            }
+        }
+        assert_eq!(
+            out,
+            [
+                0x35, 0x36, 0x37, 0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x50, 0x51, 0x52, 0x53,
+                0x02
+            ]
+        );
+
+        let mut reader = Reader::new(&out[..]);
+        for op in ops.iter() {
+            assert_eq!(reader.read_op().unwrap().as_ref(), Some(op));
+        }
+    }
+
+    #[test]
+    fn write_type_name() {
+        let multiname = Multiname::TypeName {
+            base_type: Index::new(5),
+            parameters: vec![Index::new(6)],
+        };
+
+        let mut out = vec![];
+        Writer::new(&mut out).write_multiname(&multiname).unwrap();
+        assert_eq!(out, [0x1d, 0x05, 0x01, 0x06]);
+    }
+
+    #[test]
+    fn write_exception_variable_name() {
+        let exception = Exception {
+            from_offset: 1,
+            to_offset: 5,
+            target_offset: 7,
+            type_name: Index::new(2),
+            variable_name: Index::new(3),
+        };
+
+        let mut out = vec![];
+        Writer::new(&mut out).write_exception(&exception).unwrap();
+        assert_eq!(out, [0x01, 0x05, 0x07, 0x02, 0x03]);
+    }
 }
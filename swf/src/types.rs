@@ -414,6 +414,77 @@ pub enum BlendMode {
     HardLight,
 }
 
+impl BlendMode {
+    /// Converts the numeric value of a blend mode, as stored in `PlaceObject3` tags
+    /// and accepted by AVM1 `MovieClip.blendMode`.
+    pub fn from_u8(n: u8) -> Option<Self> {
+        Some(match n {
+            0 | 1 => BlendMode::Normal,
+            2 => BlendMode::Layer,
+            3 => BlendMode::Multiply,
+            4 => BlendMode::Screen,
+            5 => BlendMode::Lighten,
+            6 => BlendMode::Darken,
+            7 => BlendMode::Difference,
+            8 => BlendMode::Add,
+            9 => BlendMode::Subtract,
+            10 => BlendMode::Invert,
+            11 => BlendMode::Alpha,
+            12 => BlendMode::Erase,
+            13 => BlendMode::Overlay,
+            14 => BlendMode::HardLight,
+            _ => return None,
+        })
+    }
+}
+
+impl std::fmt::Display for BlendMode {
+    /// Formats the blend mode as its ActionScript name, such as `hardlight`.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            BlendMode::Normal => "normal",
+            BlendMode::Layer => "layer",
+            BlendMode::Multiply => "multiply",
+            BlendMode::Screen => "screen",
+            BlendMode::Lighten => "lighten",
+            BlendMode::Darken => "darken",
+            BlendMode::Difference => "difference",
+            BlendMode::Add => "add",
+            BlendMode::Subtract => "subtract",
+            BlendMode::Invert => "invert",
+            BlendMode::Alpha => "alpha",
+            BlendMode::Erase => "erase",
+            BlendMode::Overlay => "overlay",
+            BlendMode::HardLight => "hardlight",
+        })
+    }
+}
+
+impl std::str::FromStr for BlendMode {
+    type Err = ();
+
+    /// Parses the ActionScript name of a blend mode, such as `hardlight`.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match s {
+            "normal" => BlendMode::Normal,
+            "layer" => BlendMode::Layer,
+            "multiply" => BlendMode::Multiply,
+            "screen" => BlendMode::Screen,
+            "lighten" => BlendMode::Lighten,
+            "darken" => BlendMode::Darken,
+            "difference" => BlendMode::Difference,
+            "add" => BlendMode::Add,
+            "subtract" => BlendMode::Subtract,
+            "invert" => BlendMode::Invert,
+            "alpha" => BlendMode::Alpha,
+            "erase" => BlendMode::Erase,
+            "overlay" => BlendMode::Overlay,
+            "hardlight" => BlendMode::HardLight,
+            _ => return Err(()),
+        })
+    }
+}
+
 /// An clip action (a.k.a. clip event) placed on a movieclip instance.
 /// Created in the Flash IDE using `onClipEvent` or `on` blocks.
 ///
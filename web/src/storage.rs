@@ -12,12 +12,14 @@ impl LocalStorageBackend {
 }
 
 impl StorageBackend for LocalStorageBackend {
-    fn get_string(&self, name: &str) -> Option<String> {
-        self.storage.get(name).unwrap_or_default()
+    fn get(&self, name: &str) -> Option<Vec<u8>> {
+        let value = self.storage.get(name).unwrap_or_default()?;
+        // Older versions of Ruffle stored shared objects as JSON, which isn't valid base64.
+        Some(base64::decode(&value).unwrap_or_else(|_| value.into_bytes()))
     }
 
-    fn put_string(&mut self, name: &str, value: String) -> bool {
-        self.storage.set(name, &value).is_ok()
+    fn put(&mut self, name: &str, value: &[u8]) -> bool {
+        self.storage.set(name, &base64::encode(value)).is_ok()
     }
 
     fn remove_key(&mut self, name: &str) {